@@ -0,0 +1,98 @@
+//! Cloning long task histories and large artifacts.
+//!
+//! Compares `Part` against a copy of its previous, unboxed layout, where
+//! every part was as large as a file part, and `Task` against `SharedTask`
+//! for a task holding a 5 MB artifact. Run with `cargo bench --bench clone`.
+
+use a2a_rs::shared::SharedTask;
+use a2a_rs::{
+    Artifact, DataPart, FileContent, FilePart, FileWithBytes, FileWithUri, Message, MessageRole, Part, Task, TextPart,
+};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+/// `Part` as it was laid out before `Part::File` was boxed.
+#[allow(dead_code)]
+#[derive(Clone)]
+enum UnboxedPart {
+    Text(TextPart),
+    File(FilePart),
+    Data(DataPart),
+}
+
+fn history() -> Vec<Message> {
+    (0..1000)
+        .map(|i| {
+            let parts = (0..10)
+                .map(|j| match j {
+                    9 => Part::from(FilePart::from(FileContent::from(FileWithUri {
+                        uri: format!("https://example.com/files/{}", i),
+                        name: None,
+                        mime_type: Some("image/png".to_string()),
+                    }))),
+                    _ => Part::from(format!("Line {} of message {}", j, i)),
+                })
+                .collect();
+            Message::minimal(format!("msg-{}", i), MessageRole::Agent, parts)
+        })
+        .collect()
+}
+
+fn unboxed(history: &[Message]) -> Vec<Vec<UnboxedPart>> {
+    history
+        .iter()
+        .map(|message| {
+            message
+                .parts
+                .iter()
+                .map(|part| match part {
+                    Part::Text(part) => UnboxedPart::Text(part.clone()),
+                    Part::File(part) => UnboxedPart::File((**part).clone()),
+                    Part::Data(part) => UnboxedPart::Data(part.clone()),
+                    Part::Unknown(_) => unreachable!("the benchmark history has no unknown parts"),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn bench_clone(c: &mut Criterion) {
+    let history = history();
+    let parts: Vec<Vec<Part>> = history.iter().map(|message| message.parts.clone()).collect();
+    let unboxed = unboxed(&history);
+    println!(
+        "size_of::<Part>() = {}, unboxed = {}",
+        std::mem::size_of::<Part>(),
+        std::mem::size_of::<UnboxedPart>()
+    );
+
+    let mut group = c.benchmark_group("history_1000x10_parts");
+    group.bench_function("parts", |b| b.iter(|| black_box(&parts).clone()));
+    group.bench_function("parts_unboxed", |b| b.iter(|| black_box(&unboxed).clone()));
+    group.bench_function("messages", |b| b.iter(|| black_box(&history).clone()));
+    group.finish();
+}
+
+fn large_artifact_task() -> Task {
+    let file = FilePart::from(FileContent::from(FileWithBytes {
+        bytes: "A".repeat(5 * 1024 * 1024),
+        name: Some("render.png".to_string()),
+        mime_type: Some("image/png".to_string()),
+    }));
+    let mut task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+    task.artifacts = Some(vec![Artifact::minimal("render".to_string(), vec![Part::from(file)])]);
+    task
+}
+
+fn bench_shared(c: &mut Criterion) {
+    let task = large_artifact_task();
+    let shared = SharedTask::from(task.clone());
+
+    let mut group = c.benchmark_group("task_5mb_artifact");
+    group.bench_function("task", |b| b.iter(|| black_box(&task).clone()));
+    group.bench_function("shared_task", |b| b.iter(|| black_box(&shared).clone()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_clone, bench_shared);
+criterion_main!(benches);
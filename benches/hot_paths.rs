@@ -0,0 +1,154 @@
+//! Baselines for the hot paths of clients and servers.
+//!
+//! Parsing and re-serializing a 1 MB task with a long history, parsing a
+//! `message/send` request carrying a 5 MB file, folding 10,000 artifact
+//! updates into a `ClientConversation`, and dispatching 10,000 small
+//! requests to a handler that does nothing: parsing each request, building
+//! its `RequestContext` and serializing the response. Run with
+//! `cargo bench --bench hot_paths`.
+
+use a2a_rs::borrowed::SendMessageRequestRef;
+use a2a_rs::client::ClientConversation;
+use a2a_rs::context::RequestContext;
+use a2a_rs::helpers::{parse_a2a_request, parse_request_borrowed};
+use a2a_rs::{A2ARequest, ExtraFields, Message, MessageRole, Part, SendMessageParams, SendMessageRequest, Task, TaskState};
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use serde_json::json;
+use std::hint::black_box;
+
+/// A task whose history serializes to about 1 MB.
+fn one_megabyte_task() -> String {
+    let mut task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+    task.status.state = TaskState::Working;
+    task.history = Some(
+        (0..2000)
+            .map(|i| {
+                let role = if i % 2 == 0 { MessageRole::User } else { MessageRole::Agent };
+                let text = format!("Message {} of a long conversation about the quarterly report. ", i).repeat(6);
+                Message::minimal(format!("msg-{}", i), role, vec![Part::from(text)])
+            })
+            .collect(),
+    );
+    serde_json::to_string(&task).unwrap()
+}
+
+/// A `message/send` request with a 5 MB base64 file part.
+fn five_megabyte_file_request() -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "message/send",
+        "params": {
+            "message": {
+                "kind": "message",
+                "messageId": "msg-1",
+                "role": "user",
+                "parts": [
+                    {"kind": "text", "text": "Summarize the attached document."},
+                    {"kind": "file", "file": {"bytes": "QUJD".repeat(5 * 1024 * 1024 / 4), "mimeType": "application/pdf"}}
+                ]
+            }
+        }
+    })
+    .to_string()
+}
+
+fn frame(result: serde_json::Value) -> Vec<u8> {
+    format!("data: {}\n\n", json!({"jsonrpc": "2.0", "id": "1", "result": result})).into_bytes()
+}
+
+/// A conversation with an open stream and a created task, and the stream
+/// body of 10,000 chunks of ten artifacts.
+fn artifact_stream() -> (ClientConversation, Vec<u8>) {
+    let mut conversation = ClientConversation::new();
+    conversation
+        .start_send(SendMessageParams {
+            message: Message::user("msg-1".to_string(), ["Write the report"]),
+            configuration: None,
+            metadata: None,
+            extra: ExtraFields::default(),
+        })
+        .unwrap();
+    conversation
+        .handle_response(&frame(json!({"kind": "task", "id": "task-1", "contextId": "ctx-1", "status": {"state": "working"}})))
+        .unwrap();
+
+    let body = (0..10_000)
+        .flat_map(|i| {
+            frame(json!({
+                "kind": "artifact-update",
+                "taskId": "task-1",
+                "contextId": "ctx-1",
+                "artifact": {"artifactId": format!("artifact-{}", i % 10), "parts": [{"kind": "text", "text": format!("Chunk {}", i)}]},
+                "append": i >= 10
+            }))
+        })
+        .collect();
+    (conversation, body)
+}
+
+/// The result of every request: the handler does nothing.
+fn noop_handler(_request: &A2ARequest, _context: &RequestContext) -> serde_json::Value {
+    serde_json::Value::Null
+}
+
+fn small_requests() -> Vec<String> {
+    (0..10_000)
+        .map(|i| {
+            json!({"jsonrpc": "2.0", "id": format!("req-{}", i), "method": "tasks/get", "params": {"taskId": format!("task-{}", i)}})
+                .to_string()
+        })
+        .collect()
+}
+
+fn bench_task(c: &mut Criterion) {
+    let json = one_megabyte_task();
+    c.bench_function("task_1mb_parse_and_serialize", |b| {
+        b.iter(|| {
+            let task: Task = serde_json::from_str(black_box(&json)).unwrap();
+            serde_json::to_string(&task).unwrap()
+        })
+    });
+}
+
+fn bench_large_file(c: &mut Criterion) {
+    let json = five_megabyte_file_request();
+    let mut group = c.benchmark_group("message_send_5mb_file");
+    group.sample_size(20);
+    group.bench_function("owned", |b| {
+        b.iter(|| serde_json::from_str::<SendMessageRequest>(black_box(&json)).unwrap())
+    });
+    group.bench_function("borrowed", |b| {
+        b.iter(|| parse_request_borrowed::<SendMessageRequestRef>(black_box(&json)).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_artifact_fold(c: &mut Criterion) {
+    let (conversation, body) = artifact_stream();
+    c.bench_function("fold_10k_artifact_updates", |b| {
+        b.iter_batched(
+            || conversation.clone(),
+            |mut conversation| conversation.handle_response(black_box(&body)).unwrap(),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn bench_dispatch(c: &mut Criterion) {
+    let requests = small_requests();
+    c.bench_function("dispatch_10k_small_requests", |b| {
+        b.iter(|| {
+            for json in &requests {
+                let request = parse_a2a_request(black_box(json)).unwrap();
+                let context = RequestContext::for_request(&request);
+                let result = noop_handler(&request, &context);
+                let response = json!({"jsonrpc": "2.0", "id": request.id(), "result": result});
+                black_box(serde_json::to_vec(&response).unwrap());
+            }
+        })
+    });
+}
+
+criterion_group!(benches, bench_task, bench_large_file, bench_artifact_fold, bench_dispatch);
+criterion_main!(benches);
@@ -0,0 +1,70 @@
+//! Owned vs borrowed parsing of a `message/send` request with 100 parts.
+//!
+//! Run with `cargo bench --bench parse` (add `--features simd` to include
+//! the simd-json path).
+
+use a2a_rs::SendMessageRequest;
+use a2a_rs::borrowed::SendMessageRequestRef;
+use a2a_rs::helpers::parse_request_borrowed;
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+fn hundred_part_request() -> String {
+    let parts: Vec<serde_json::Value> = (0..100)
+        .map(|i| match i % 4 {
+            0 | 1 => serde_json::json!({
+                "kind": "text",
+                "text": format!("Paragraph {} of a longer document that the agent should summarize.", i)
+            }),
+            2 => serde_json::json!({
+                "kind": "file",
+                "file": {"bytes": "aGVsbG8gd29ybGQ=".repeat(64), "mimeType": "text/plain", "name": format!("part-{}.txt", i)}
+            }),
+            _ => serde_json::json!({
+                "kind": "file",
+                "file": {"uri": format!("https://example.com/files/{}", i), "mimeType": "image/png"}
+            }),
+        })
+        .collect();
+
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "message/send",
+        "params": {
+            "message": {
+                "kind": "message",
+                "messageId": "9229e770-767c-417b-a0b0-f0741243c589",
+                "contextId": "c295ea44-7543-4f78-b524-7a38915ad6e4",
+                "role": "user",
+                "parts": parts
+            }
+        }
+    })
+    .to_string()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let json = hundred_part_request();
+    let mut group = c.benchmark_group("message_send_100_parts");
+
+    group.bench_function("owned", |b| {
+        b.iter(|| serde_json::from_str::<SendMessageRequest>(black_box(&json)).unwrap())
+    });
+    group.bench_function("borrowed", |b| {
+        b.iter(|| parse_request_borrowed::<SendMessageRequestRef>(black_box(&json)).unwrap())
+    });
+    #[cfg(feature = "simd")]
+    group.bench_function("simd_owned", |b| {
+        b.iter_batched_ref(
+            || json.clone().into_bytes(),
+            |bytes| a2a_rs::helpers::parse_request_simd::<SendMessageRequest>(black_box(bytes)).unwrap(),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
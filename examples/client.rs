@@ -0,0 +1,177 @@
+//! A command-line client for streaming agents.
+//!
+//! The client fetches the agent card, sends one message with
+//! `message/stream`, prints the events as they arrive and finally the
+//! artifacts of the task, or the agent's reply if it answered without one:
+//!
+//! ```text
+//! cargo run --example client -- http://127.0.0.1:8080 "Hello, agent"
+//! ```
+//!
+//! The protocol logic is [`ClientConversation`]; this file only moves its
+//! bytes over a blocking TCP connection with just enough HTTP/1.1 for the
+//! example agent in `echo_agent.rs`. Only `http://` URLs are supported.
+
+use a2a_rs::agent::Capability;
+use a2a_rs::client::{ClientConversation, ClientError, ConversationState, Effect, TransportErrorKind};
+use a2a_rs::discovery::discover_agent;
+use a2a_rs::{AgentCard, ExtraFields, Message, RequestMethod, SendMessageParams, SendMessageResult};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long the agent may stay silent before the call times out.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Fetch and validate the card of the agent at `base_url`.
+pub fn fetch_card(base_url: &str) -> Result<AgentCard, ClientError> {
+    let (card, _) = discover_agent(base_url, |url| {
+        let mut body = String::new();
+        request(url, "GET", None)?.read_to_string(&mut body).map_err(io_error)?;
+        Ok::<_, ClientError>(body)
+    })?;
+    Ok(card)
+}
+
+/// Send a text message to an agent and follow the response stream to its
+/// end.
+///
+/// # Arguments
+///
+/// * `card` - The card of the agent, which must declare streaming.
+/// * `text` - The text of the message.
+/// * `on_effect` - Called with each effect as its event arrives.
+///
+/// # Returns
+///
+/// The task as of the end of the stream, or the message the agent answered
+/// with instead of a task; or the error that ended the call.
+pub fn send(
+    card: &AgentCard,
+    text: &str,
+    mut on_effect: impl FnMut(&Effect),
+) -> Result<SendMessageResult, ClientError> {
+    if !Capability::Streaming.is_supported_by(&card.capabilities) {
+        return Err(Capability::Streaming.unsupported_error(RequestMethod::MessageStream).into());
+    }
+
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+    let mut conversation = ClientConversation::new();
+    let body = conversation.start_send(SendMessageParams {
+        message: Message::user(format!("cli-{}", nanos), [text]),
+        configuration: None,
+        metadata: None,
+        extra: ExtraFields::default(),
+    })?;
+
+    let mut response = request(&card.url, "POST", Some(&body))?;
+    let mut chunk = [0; 8192];
+    let mut reply = None;
+    while conversation.state() == ConversationState::Streaming {
+        let effects = match response.read(&mut chunk).map_err(io_error)? {
+            0 => conversation.handle_stream_end()?,
+            read => conversation.handle_response(&chunk[..read])?,
+        };
+        for effect in &effects {
+            if let Effect::Message(message) = effect {
+                reply = Some(message.clone());
+            }
+            on_effect(effect);
+        }
+    }
+
+    match (conversation.task(), reply) {
+        (Some(task), _) => Ok(task.clone().into()),
+        (None, Some(message)) => Ok(message.into()),
+        (None, None) => Err(ClientError::Decode("The stream ended without an answer".to_string())),
+    }
+}
+
+/// Send an HTTP request and read the head of the response.
+///
+/// # Returns
+///
+/// The connection positioned at the response body, or an error unless
+/// the status is `200 OK`.
+fn request(url: &str, method: &str, body: Option<&[u8]>) -> Result<BufReader<TcpStream>, ClientError> {
+    let invalid = || ClientError::transport(TransportErrorKind::InvalidEndpoint, format!("Not an http URL: {}", url));
+    let rest = url.strip_prefix("http://").ok_or_else(invalid)?;
+    let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    if authority.is_empty() {
+        return Err(invalid());
+    }
+    let path = if path.is_empty() { "/" } else { path };
+
+    let mut socket = TcpStream::connect(authority)
+        .map_err(|e| ClientError::transport(TransportErrorKind::of(&e, TransportErrorKind::Connect), e))?;
+    socket.set_read_timeout(Some(READ_TIMEOUT)).map_err(io_error)?;
+
+    let mut head = format!("{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n", method, path, authority);
+    if let Some(body) = body {
+        head.push_str(&format!("Content-Type: application/json\r\nContent-Length: {}\r\n", body.len()));
+    }
+    head.push_str("\r\n");
+    socket.write_all(head.as_bytes()).map_err(io_error)?;
+    socket.write_all(body.unwrap_or_default()).map_err(io_error)?;
+
+    let mut response = BufReader::new(socket);
+    let mut line = String::new();
+    response.read_line(&mut line).map_err(io_error)?;
+    let status = line.split_whitespace().nth(1).and_then(|status| status.parse::<u16>().ok());
+    if status != Some(200) {
+        let kind = match status {
+            Some(500..) | None => TransportErrorKind::Unavailable,
+            Some(_) => TransportErrorKind::InvalidEndpoint,
+        };
+        return Err(ClientError::transport(kind, format!("{} {} answered {}", method, url, line.trim_end())));
+    }
+    loop {
+        line.clear();
+        if response.read_line(&mut line).map_err(io_error)? == 0 || line.trim_end().is_empty() {
+            return Ok(response);
+        }
+    }
+}
+
+fn io_error(error: io::Error) -> ClientError {
+    match error.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => ClientError::Timeout,
+        _ => ClientError::transport(TransportErrorKind::of(&error, TransportErrorKind::Unavailable), error),
+    }
+}
+
+fn print_effect(effect: &Effect) {
+    match effect {
+        Effect::TaskCreated(task) => println!("task {} created in context {}", task.id, task.context_id),
+        Effect::StatusUpdate(event) => println!("status: {:?}", event.status.state),
+        Effect::ArtifactUpdate(event) => println!("artifact {} updated", event.artifact.artifact_id),
+        Effect::Message(message) => println!("message {} from the agent", message.message_id),
+        Effect::InputRequired(_) => println!("the agent needs input"),
+        Effect::Completed(task) => println!("task {} finished", task.id),
+        Effect::NeedResubscribe(task_id) => println!("the stream of task {} was cut off", task_id),
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let base_url = args.next().unwrap_or_else(|| "http://127.0.0.1:8080".to_string());
+    let text = args.next().unwrap_or_else(|| "Hello, agent".to_string());
+
+    let result = fetch_card(&base_url).and_then(|card| {
+        println!("Talking to {} {}", card.name, card.version);
+        send(&card, &text, print_effect)
+    });
+    match result {
+        Ok(SendMessageResult::Task(task)) => {
+            for artifact in task.artifacts.iter().flatten() {
+                let name = artifact.name.as_deref().unwrap_or(&artifact.artifact_id);
+                println!("{}: {}", name, artifact.text().unwrap_or_default());
+            }
+        }
+        Ok(reply) => println!("reply: {}", reply.text().unwrap_or_default()),
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
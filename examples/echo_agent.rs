@@ -0,0 +1,383 @@
+//! An agent that echoes every message back.
+//!
+//! The agent publishes its card at the well-known paths and answers
+//! `message/stream` with the whole life of a task: the `submitted` task,
+//! a `working` update, an artifact holding the text of the message, and
+//! the final `completed` update. `message/send` runs the same steps and
+//! answers with the finished task, and `tasks/get` reads it back.
+//!
+//! The crate does no I/O, so this example speaks just enough HTTP/1.1 over
+//! a tokio socket to stay self-contained: one request per connection, no
+//! chunked bodies. A real agent would call the same handlers from its web
+//! framework.
+//!
+//! ```text
+//! cargo run --example echo_agent
+//! cargo run --example client -- http://127.0.0.1:8080 "Hello, agent"
+//! ```
+
+use a2a_rs::discovery::{AgentCardDocument, WELL_KNOWN_PATHS};
+use a2a_rs::helpers::parse_incoming_request;
+use a2a_rs::ids::{ConfigId, ContextId, IdSource, MessageId, TaskId};
+use a2a_rs::manager::TaskManager;
+use a2a_rs::store::{InMemoryPushNotificationConfigStore, InMemoryTaskStore};
+use a2a_rs::{
+    A2AError, A2ARequest, AgentCapabilities, AgentCard, AgentSkill, ArtifactUpdateSequenceBuilder, InternalError,
+    JSONRPCErrorResponse, JsonRpcId, Message, Part, SendMessageParams, SendMessageResult, StreamingMessageResult,
+    TaskStatusUpdateEvent, UnsupportedOperationError,
+};
+use serde::Serialize;
+use std::io;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
+
+/// Largest request body the agent reads.
+const MAX_BODY: usize = 1 << 20;
+
+/// The state shared by all connections.
+pub struct EchoAgent {
+    card: AgentCardDocument,
+    tasks: TaskManager<InMemoryTaskStore, InMemoryPushNotificationConfigStore>,
+}
+
+impl EchoAgent {
+    /// Create an agent served at `url`.
+    pub fn new(url: &str) -> Self {
+        Self {
+            card: AgentCardDocument::new(card(url)),
+            tasks: TaskManager::new(InMemoryTaskStore::new(), InMemoryPushNotificationConfigStore::new())
+                .with_id_source(EchoIds::default()),
+        }
+    }
+
+    /// The task manager holding the agent's tasks.
+    pub fn tasks(&self) -> &TaskManager<InMemoryTaskStore, InMemoryPushNotificationConfigStore> {
+        &self.tasks
+    }
+
+    /// Answer one HTTP request on a fresh connection.
+    async fn handle(&self, socket: TcpStream) -> io::Result<()> {
+        let mut socket = BufReader::new(socket);
+        let request = read_request(&mut socket).await?;
+        let mut socket = socket.into_inner();
+
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", path) if WELL_KNOWN_PATHS.contains(&path) => {
+                let tags = request.header("if-none-match");
+                if tags.is_some_and(|tags| self.card.matches_if_none_match(tags)) {
+                    respond(&mut socket, "304 Not Modified", &self.card.headers(), b"").await
+                } else {
+                    respond(&mut socket, "200 OK", &self.card.headers(), self.card.body().as_bytes()).await
+                }
+            }
+            ("POST", "/") => self.handle_rpc(&request.body, socket).await,
+            _ => respond(&mut socket, "404 Not Found", &[], b"").await,
+        }
+    }
+
+    /// Answer a JSON-RPC request.
+    async fn handle_rpc(&self, body: &[u8], mut socket: TcpStream) -> io::Result<()> {
+        const JSON: [(&str, &str); 1] = [("Content-Type", "application/json")];
+
+        let incoming = match parse_incoming_request(&String::from_utf8_lossy(body)) {
+            Ok(incoming) => incoming,
+            Err(error) => {
+                return match error.into_response() {
+                    Some(response) => respond(&mut socket, "200 OK", &JSON, &response).await,
+                    None => respond(&mut socket, "204 No Content", &[], b"").await,
+                };
+            }
+        };
+
+        let result = match incoming.request() {
+            A2ARequest::SendStreamingMessage(request) => {
+                let id = incoming.id().cloned().unwrap_or(JsonRpcId::Null);
+                return self.stream(&id, request.params.clone(), socket).await;
+            }
+            A2ARequest::SendMessage(request) => {
+                // Nobody listens to the events of a blocking send
+                let (events, _) = unbounded_channel();
+                let result = self.echo(request.params.clone(), &events).await;
+                result.map(|result| to_value(&result))
+            }
+            A2ARequest::GetTask(request) => self.tasks.get_task(&request.params).await.map(|task| to_value(&task)),
+            request => Err(A2AError::UnsupportedOperation(Box::new(UnsupportedOperationError {
+                code: -32004,
+                message: "This operation is not supported".to_string(),
+                data: Some(serde_json::json!({"method": request.method().as_str()})),
+            }))),
+        };
+
+        let mut log = |request: &A2ARequest, error: A2AError| {
+            eprintln!("Notification {} failed: {}", request.method().as_str(), error)
+        };
+        match incoming.dispatch(|_| result, &mut log) {
+            Some(response) => respond(&mut socket, "200 OK", &JSON, &response).await,
+            None => respond(&mut socket, "204 No Content", &[], b"").await,
+        }
+    }
+
+    /// Answer `message/stream` with a server-sent event per step.
+    async fn stream(&self, id: &JsonRpcId, params: SendMessageParams, mut socket: TcpStream) -> io::Result<()> {
+        write_head(&mut socket, "200 OK", &[("Content-Type", "text/event-stream"), ("Cache-Control", "no-cache")], None)
+            .await?;
+
+        let (events, mut received) = unbounded_channel();
+        let work = async move { self.echo(params, &events).await };
+        let forward = async {
+            while let Some(event) = received.recv().await {
+                let event = serde_json::json!({"jsonrpc": "2.0", "id": id, "result": event});
+                write_event(&mut socket, &event).await?;
+            }
+            Ok::<_, io::Error>(())
+        };
+        let (result, forwarded) = tokio::join!(work, forward);
+        forwarded?;
+
+        if let Err(error) = result {
+            let response = JSONRPCErrorResponse {
+                id: id.clone(),
+                jsonrpc: "2.0".to_string(),
+                error,
+            };
+            write_event(&mut socket, &response).await?;
+        }
+        socket.shutdown().await
+    }
+
+    /// Echo the text of a message back as the artifact of a new task.
+    ///
+    /// Each step is saved before it is reported to `events`, so a client
+    /// asking for the task never sees it behind the stream. Clients that
+    /// accept no text get the task rejected. A `ping` needs no task: it is
+    /// answered at once with a `pong` message.
+    async fn echo(
+        &self,
+        params: SendMessageParams,
+        events: &UnboundedSender<StreamingMessageResult>,
+    ) -> Result<SendMessageResult, A2AError> {
+        let message = params.message;
+        let text: Vec<_> = message
+            .parts
+            .iter()
+            .filter_map(|part| match part {
+                Part::Text(part) => Some(part.text.as_str()),
+                _ => None,
+            })
+            .collect();
+        let text = text.join("\n");
+
+        if text == "ping" {
+            let pong = Message::agent(format!("{}-pong", message.message_id), ["pong"]);
+            let reply = self.tasks.reply(message, pong).await?;
+            if let Some(pong) = reply.message() {
+                let _ = events.send(StreamingMessageResult::Message(pong.clone()));
+            }
+            return Ok(reply);
+        }
+
+        let task = self.tasks.create_task(message).await?;
+        let _ = events.send(StreamingMessageResult::Task(task.clone()));
+        let task_id = TaskId::from(task.id.as_str());
+        let configuration = params.configuration.as_ref();
+        if let Some(rejected) = self.tasks.negotiate_output(&task_id, self.card.card(), configuration).await? {
+            let (id, context_id) = (rejected.id.clone(), rejected.context_id.clone());
+            let status = TaskStatusUpdateEvent::new(id, context_id, rejected.status.clone(), true);
+            let _ = events.send(StreamingMessageResult::TaskStatusUpdate(status));
+            return Ok(rejected.into());
+        }
+        let mut task = task;
+
+        let working = TaskStatusUpdateEvent::working(task.id.clone(), task.context_id.clone());
+        task.set_status_checked(working.status.clone()).map_err(internal)?;
+        let mut task = self.tasks.save_task(task).await?;
+        let _ = events.send(StreamingMessageResult::TaskStatusUpdate(working));
+
+        let artifact = ArtifactUpdateSequenceBuilder::new(task.id.clone(), task.context_id.clone(), "echo".to_string())
+            .with_name("echo")
+            .last([text])
+            .map_err(internal)?;
+        task.artifacts.get_or_insert_with(Vec::new).push(artifact.artifact.clone());
+        let mut task = self.tasks.save_task(task).await?;
+        let _ = events.send(StreamingMessageResult::TaskArtifactUpdate(artifact));
+
+        let completed = TaskStatusUpdateEvent::completed(task.id.clone(), task.context_id.clone());
+        task.set_status_checked(completed.status.clone()).map_err(internal)?;
+        let task = self.tasks.save_task(task).await?;
+        let _ = events.send(StreamingMessageResult::TaskStatusUpdate(completed));
+        Ok(task.into())
+    }
+}
+
+/// Build the card of an echo agent served at `url`.
+pub fn card(url: &str) -> AgentCard {
+    AgentCard::new(
+        "Echo Agent".to_string(),
+        "Echoes the text of every message back as an artifact".to_string(),
+        "1.0.0".to_string(),
+        url.to_string(),
+        AgentCapabilities {
+            streaming: Some(true),
+            ..AgentCapabilities::default()
+        },
+        vec!["text/plain".to_string()],
+        vec!["text/plain".to_string()],
+        vec![AgentSkill::minimal("echo".to_string(), "Repeats the text it receives".to_string())],
+    )
+}
+
+/// Accept connections until the listener fails, answering each on its own
+/// tokio task.
+pub async fn serve(listener: TcpListener, agent: Arc<EchoAgent>) -> io::Result<()> {
+    loop {
+        let (socket, _) = listener.accept().await?;
+        let agent = agent.clone();
+        tokio::spawn(async move {
+            if let Err(error) = agent.handle(socket).await {
+                eprintln!("Connection failed: {}", error);
+            }
+        });
+    }
+}
+
+/// Mints the IDs of the agent's tasks, contexts and messages from one
+/// counter.
+#[derive(Default)]
+struct EchoIds(u64);
+
+impl EchoIds {
+    fn next(&mut self) -> u64 {
+        self.0 += 1;
+        self.0
+    }
+}
+
+impl IdSource for EchoIds {
+    fn new_task_id(&mut self) -> TaskId {
+        TaskId::from(format!("echo-task-{}", self.next()))
+    }
+
+    fn new_context_id(&mut self) -> ContextId {
+        ContextId::from(format!("echo-ctx-{}", self.next()))
+    }
+
+    fn new_message_id(&mut self) -> MessageId {
+        MessageId::from(format!("echo-msg-{}", self.next()))
+    }
+
+    fn new_config_id(&mut self) -> ConfigId {
+        ConfigId::from(format!("echo-config-{}", self.next()))
+    }
+}
+
+/// The parts of an HTTP request the agent looks at.
+struct HttpRequest {
+    method: String,
+    path: String,
+    /// Headers with lowercase names.
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl HttpRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+}
+
+async fn read_request(socket: &mut BufReader<TcpStream>) -> io::Result<HttpRequest> {
+    let malformed = |what: &str| io::Error::new(io::ErrorKind::InvalidData, format!("Malformed request: {}", what));
+
+    let mut line = String::new();
+    socket.read_line(&mut line).await?;
+    let mut words = line.split_whitespace();
+    let (Some(method), Some(path)) = (words.next(), words.next()) else {
+        return Err(malformed("no request line"));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        if socket.read_line(&mut line).await? == 0 {
+            return Err(malformed("headers cut off"));
+        }
+        let Some((name, value)) = line.trim_end().split_once(':') else {
+            break;
+        };
+        headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+    }
+
+    let mut request = HttpRequest {
+        method,
+        path,
+        headers,
+        body: Vec::new(),
+    };
+    let length = match request.header("content-length") {
+        Some(length) => length.parse().map_err(|_| malformed("bad Content-Length"))?,
+        None => 0,
+    };
+    if length > MAX_BODY {
+        return Err(malformed("body too large"));
+    }
+    request.body.resize(length, 0);
+    socket.read_exact(&mut request.body).await?;
+    Ok(request)
+}
+
+async fn write_head(
+    socket: &mut TcpStream,
+    status: &str,
+    headers: &[(&str, &str)],
+    length: Option<usize>,
+) -> io::Result<()> {
+    let mut head = format!("HTTP/1.1 {}\r\nConnection: close\r\n", status);
+    if let Some(length) = length {
+        head.push_str(&format!("Content-Length: {}\r\n", length));
+    }
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+    socket.write_all(head.as_bytes()).await
+}
+
+async fn respond(socket: &mut TcpStream, status: &str, headers: &[(&str, &str)], body: &[u8]) -> io::Result<()> {
+    write_head(socket, status, headers, Some(body.len())).await?;
+    socket.write_all(body).await?;
+    socket.shutdown().await
+}
+
+async fn write_event<T: Serialize>(socket: &mut TcpStream, response: &T) -> io::Result<()> {
+    let mut event = b"data: ".to_vec();
+    event.extend(to_json(response));
+    event.extend(b"\n\n");
+    socket.write_all(&event).await
+}
+
+fn to_json<T: Serialize>(value: &T) -> Vec<u8> {
+    serde_json::to_vec(value).expect("protocol types always serialize")
+}
+
+fn to_value<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).expect("protocol types always serialize")
+}
+
+fn internal(message: String) -> A2AError {
+    A2AError::Internal(Box::new(InternalError {
+        code: -32603,
+        message,
+        data: None,
+    }))
+}
+
+#[tokio::main]
+async fn main() -> io::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:8080").await?;
+    let url = format!("http://{}/", listener.local_addr()?);
+    println!("Echo agent listening on {}", url);
+    serve(listener, Arc::new(EchoAgent::new(&url))).await
+}
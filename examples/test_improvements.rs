@@ -1,5 +1,4 @@
 use a2a_rs::*;
-use std::collections::HashMap;
 
 fn main() {
     println!("Testing A2A Rust Implementation Improvements");
@@ -39,7 +38,7 @@ fn main() {
 
     // Test 3: OAuth2 Flow Validation
     println!("\n3. Testing OAuth2 Flow Validation:");
-    let mut scopes = HashMap::new();
+    let mut scopes = Map::new();
     scopes.insert("read".to_string(), "Read access".to_string());
     scopes.insert("write".to_string(), "Write access".to_string());
 
@@ -79,6 +78,7 @@ fn main() {
         extensions: None,
         metadata: None,
         name: Some("Generated Artifact".to_string()),
+        extra: ExtraFields::default(),
     };
 
     let artifact_event = TaskArtifactUpdateEvent::new(
@@ -0,0 +1,32 @@
+//! Feeds arbitrary bytes to the request, response and error parsers.
+//!
+//! Seed with `fuzz/corpus/parse_request`:
+//! `cargo fuzz run parse_request fuzz/corpus/parse_request`.
+
+#![no_main]
+
+use a2a_rs::rest::RestRoute;
+use a2a_rs::{A2AError, A2ARequest, RequestMethod, SendMessageResponse, helpers};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = serde_json::from_slice::<A2ARequest>(data);
+    let _ = serde_json::from_slice::<A2AError>(data);
+    let _ = serde_json::from_slice::<RequestMethod>(data);
+    let _ = serde_json::from_slice::<SendMessageResponse>(data);
+
+    if let Ok(text) = std::str::from_utf8(data) {
+        let _ = helpers::parse_request(text);
+        if let Ok(request) = helpers::parse_envelope(text) {
+            let _ = request.method();
+            let _ = request.params_as::<serde_json::Value>();
+            let _ = request.forward();
+        }
+
+        // Treat the first line as a path and the rest as a body
+        let (path, body) = text.split_once('\n').unwrap_or((text, ""));
+        for method in ["GET", "POST", "DELETE"] {
+            let _ = RestRoute::parse(method, path, Some(body), Some(body));
+        }
+    }
+});
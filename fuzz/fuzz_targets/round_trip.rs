@@ -0,0 +1,34 @@
+//! Checks that structured values survive a `T -> JSON -> T` round trip.
+
+#![no_main]
+
+use a2a_rs::{AgentCard, Message, Task, TaskArtifactUpdateEvent, TaskStatusUpdateEvent};
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+#[derive(Debug, Arbitrary)]
+enum Input {
+    Message(Message),
+    Task(Task),
+    StatusUpdate(TaskStatusUpdateEvent),
+    ArtifactUpdate(TaskArtifactUpdateEvent),
+    AgentCard(AgentCard),
+}
+
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) {
+    let json = serde_json::to_string(value).unwrap();
+    let decoded: T = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+}
+
+fuzz_target!(|input: Input| {
+    match input {
+        Input::Message(value) => round_trip(&value),
+        Input::Task(value) => round_trip(&value),
+        Input::StatusUpdate(value) => round_trip(&value),
+        Input::ArtifactUpdate(value) => round_trip(&value),
+        Input::AgentCard(value) => round_trip(&value),
+    }
+});
@@ -0,0 +1,29 @@
+//! Runs the validators on arbitrary strings and structured values.
+
+#![no_main]
+
+use a2a_rs::validation;
+use a2a_rs::{AgentCard, AgentExtension, SecurityScheme};
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+struct Input<'a> {
+    text: &'a str,
+    card: AgentCard,
+    extension: AgentExtension,
+    scheme: SecurityScheme,
+}
+
+fuzz_target!(|input: Input| {
+    let _ = validation::validate_url(input.text);
+    let _ = validation::validate_media_type(input.text);
+    let _ = validation::validate_message_id(input.text);
+    let _ = validation::validate_task_id(input.text);
+    let _ = validation::validate_agent_name(input.text);
+    let _ = validation::validate_version(input.text);
+    let _ = validation::validate_skill_id(input.text);
+    let _ = input.card.url_for_transport(input.text);
+    let _ = input.extension.validate();
+    let _ = input.scheme.validate();
+});
@@ -0,0 +1,72 @@
+//! Builds a2a-rs with `default-features = false` from a `no_std` crate.
+//!
+//! Run with `cargo test -p a2a-rs-no-std-check`. Testing the whole workspace
+//! unifies features and builds a2a-rs with `std`, so select this package on
+//! its own.
+
+#![no_std]
+
+extern crate alloc;
+#[cfg(test)]
+extern crate std;
+
+use a2a_rs::{A2AError, Message, Part};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Serialize a user message and parse it back.
+pub fn round_trip(message_id: &str, text: &str) -> Result<Message, A2AError> {
+    let message = Message::user(message_id.to_string(), [text]);
+    let json: String = a2a_rs::helpers::serialize_response(&message)?;
+    a2a_rs::helpers::parse_request_borrowed(&json)
+}
+
+/// The text of every text part of a message.
+pub fn texts(message: &Message) -> Vec<&str> {
+    message
+        .parts
+        .iter()
+        .filter_map(|part| match part {
+            Part::Text(part) => Some(part.text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use a2a_rs::{AgentCapabilities, AgentCard, Task, TaskState};
+    use alloc::vec;
+
+    #[test]
+    fn test_message_round_trip() {
+        let message = round_trip("msg-1", "Hello from an edge device").unwrap();
+        assert_eq!(message.message_id, "msg-1");
+        assert_eq!(texts(&message), ["Hello from an edge device"]);
+    }
+
+    #[test]
+    fn test_core_types_parse() {
+        let task: Task = serde_json::from_str(
+            r#"{"kind": "task", "id": "task-1", "contextId": "ctx-1", "status": {"state": "completed"}}"#,
+        )
+        .unwrap();
+        assert!(task.status.state.is_terminal());
+        assert_eq!(task.status.state, TaskState::Completed);
+
+        let card = AgentCard::new(
+            "Edge".to_string(),
+            "Runs without std".to_string(),
+            "1.0.0".to_string(),
+            "https://edge.local/a2a".to_string(),
+            AgentCapabilities::default(),
+            vec!["text/plain".to_string()],
+            vec!["text/plain".to_string()],
+            Vec::new(),
+        );
+        assert!(card.validate().is_ok());
+        let json = serde_json::to_string(&card).unwrap();
+        assert_eq!(serde_json::from_str::<AgentCard>(&json).unwrap(), card);
+    }
+}
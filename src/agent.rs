@@ -0,0 +1,807 @@
+//! Agent cards and the capabilities, skills and extensions they advertise.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::{
+    A2AError, ExtraFields, PROTOCOL_VERSION, PushNotificationNotSupportedError, RequestMethod, SecurityScheme,
+    UnsupportedOperationError,
+    extensions::{self, ExtensionRegistry, TypedExtension},
+    validation::{self, ValidationError},
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use crate::Map;
+
+/// Optional agent capabilities that gate protocol methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// Server-sent event streaming, declared by `capabilities.streaming`.
+    Streaming,
+    /// Push notifications, declared by `capabilities.pushNotifications`.
+    PushNotifications,
+}
+
+impl Capability {
+    /// Check if the capability is declared in an agent's capabilities.
+    ///
+    /// # Arguments
+    ///
+    /// * `capabilities` - The capabilities from the agent card.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the capability is declared as supported.
+    pub fn is_supported_by(&self, capabilities: &AgentCapabilities) -> bool {
+        match self {
+            Capability::Streaming => capabilities.streaming.unwrap_or(false),
+            Capability::PushNotifications => capabilities.push_notifications.unwrap_or(false),
+        }
+    }
+
+    /// Build the error returned when a method needs an undeclared capability.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The method that was requested.
+    ///
+    /// # Returns
+    ///
+    /// `PushNotificationNotSupported` for push notifications, otherwise `UnsupportedOperation`.
+    pub fn unsupported_error(&self, method: RequestMethod) -> A2AError {
+        match self {
+            Capability::PushNotifications => A2AError::PushNotificationNotSupported(Box::new(PushNotificationNotSupportedError {
+                code: -32003,
+                message: "Push Notification is not supported".to_string(),
+                data: Some(serde_json::json!({"method": method.as_str()})),
+            })),
+            Capability::Streaming => A2AError::UnsupportedOperation(Box::new(UnsupportedOperationError {
+                code: -32004,
+                message: "This operation is not supported".to_string(),
+                data: Some(serde_json::json!({"method": method.as_str()})),
+            })),
+        }
+    }
+}
+
+/// Agent extension.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub struct AgentExtension {
+    /// The URI of the extension.
+    pub uri: String,
+    /// Whether the client must follow specific requirements of the extension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    /// A description of how this agent uses this extension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Optional configuration for the extension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::object))]
+    pub params: Option<serde_json::Value>,
+}
+
+impl AgentExtension {
+    /// Create a new agent extension with the specified URI.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - The URI of the extension.
+    ///
+    /// # Returns
+    ///
+    /// A new `AgentExtension` with the specified URI.
+    pub fn new(uri: String) -> Self {
+        Self {
+            uri,
+            required: None,
+            description: None,
+            params: None,
+        }
+    }
+
+    /// Create a new required agent extension with description and parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - The URI of the extension.
+    /// * `description` - A description of how this agent uses this extension.
+    /// * `required` - Whether the client must follow specific requirements.
+    /// * `params` - Optional configuration for the extension.
+    ///
+    /// # Returns
+    ///
+    /// A new `AgentExtension` with the specified parameters.
+    pub fn with_config(
+        uri: String,
+        description: Option<String>,
+        required: Option<bool>,
+        params: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            uri,
+            required,
+            description,
+            params,
+        }
+    }
+
+    /// Deserialize the extension parameters into a typed value.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(None)` if the extension has no parameters, or an `InvalidParams`
+    /// error if they do not match `T`.
+    pub fn params_as<T: DeserializeOwned>(&self) -> Result<Option<T>, A2AError> {
+        self.params
+            .as_ref()
+            .map(|params| extensions::from_extension_value(&self.uri, params))
+            .transpose()
+    }
+
+    /// Deserialize the parameters of a [`TypedExtension`].
+    ///
+    /// # Returns
+    ///
+    /// `Ok(None)` if this is a different extension or it has no parameters,
+    /// or an `InvalidParams` error if they do not match `E::Params`.
+    pub fn params_for<E: TypedExtension>(&self) -> Result<Option<E::Params>, A2AError> {
+        if self.uri != E::URI {
+            return Ok(None);
+        }
+        self.params_as()
+    }
+
+    /// Replace the extension parameters with a serialized value.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameters to store.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or an `Internal` error if `params` cannot be serialized.
+    pub fn set_params_from<T: Serialize>(&mut self, params: &T) -> Result<(), A2AError> {
+        self.params = Some(extensions::to_extension_value(&self.uri, params)?);
+        Ok(())
+    }
+
+    /// Validate the extension URI format.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the URI is valid, `Err(String)` with error message if invalid.
+    pub fn validate_uri(&self) -> Result<(), String> {
+        validation::validate_extension_uri(&self.uri)
+    }
+
+    /// Validate the structure of the extension parameters.
+    ///
+    /// Only structural rules are checked here. Use [`AgentExtension::validate_with`]
+    /// to also check the parameters against a registered validator.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if parameters are valid, `Err(String)` with error message if invalid.
+    pub fn validate_params(&self) -> Result<(), String> {
+        if let Some(params) = &self.params {
+            // Validate that params is an object
+            if !params.is_object() {
+                return Err("Extension params must be a JSON object".to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Perform comprehensive validation of the extension.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the extension is valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        self.validate_uri()?;
+        self.validate_params()?;
+
+        // Validate description length if present
+        if let Some(desc) = &self.description
+            && desc.len() > 1000
+        {
+            return Err("Extension description is too long (max 1000 characters)".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Validate the extension, checking its parameters with the validator
+    /// registered for its URI.
+    ///
+    /// Extensions without a registered validator only get the structural
+    /// checks of [`AgentExtension::validate`].
+    ///
+    /// # Arguments
+    ///
+    /// * `registry` - The registry to look the validator up in.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the extension is valid, `Err(String)` with error message if invalid.
+    pub fn validate_with(&self, registry: &ExtensionRegistry) -> Result<(), String> {
+        self.validate()?;
+
+        if let (Some(validator), Some(params)) = (registry.get(&self.uri), &self.params) {
+            validator.validate_params(params)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Agent capabilities.
+///
+/// The default declares no capabilities and no extensions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AgentCapabilities {
+    /// Extensions supported by this agent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<Vec<AgentExtension>>,
+    /// True if the agent can notify updates to client.
+    #[serde(rename = "pushNotifications", skip_serializing_if = "Option::is_none")]
+    pub push_notifications: Option<bool>,
+    /// True if the agent exposes status change history for tasks.
+    #[serde(rename = "stateTransitionHistory", skip_serializing_if = "Option::is_none")]
+    pub state_transition_history: Option<bool>,
+    /// True if the agent supports SSE.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub streaming: Option<bool>,
+}
+
+/// Agent interface.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub struct AgentInterface {
+    /// The URL for this interface.
+    pub url: String,
+    /// The transport supported by this URL.
+    pub transport: String,
+}
+
+/// Agent provider.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AgentProvider {
+    /// Agent provider's organization name.
+    pub organization: String,
+    /// Agent provider's URL.
+    pub url: String,
+}
+
+impl AgentProvider {
+    /// The maximum length of an organization name, in characters.
+    pub const MAX_ORGANIZATION_LENGTH: usize = 200;
+
+    /// Validate the provider.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, otherwise a `ValidationError` naming the
+    /// offending field.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.organization.trim().is_empty() {
+            return Err(ValidationError::for_field("organization", "Provider organization cannot be empty"));
+        }
+        if self.organization.chars().count() > Self::MAX_ORGANIZATION_LENGTH {
+            return Err(ValidationError::for_field(
+                "organization",
+                format!("Provider organization is too long (max {} characters)", Self::MAX_ORGANIZATION_LENGTH),
+            ));
+        }
+        validation::validate_url(&self.url)
+            .map_err(|e| ValidationError::for_field("url", format!("Provider URL is invalid: {}", e)))
+    }
+}
+
+/// Agent skill.
+///
+/// With the `strict-types` feature it is non-exhaustive, so fields can be
+/// added without breaking callers; build one with [`AgentSkill::minimal`] and set the
+/// optional fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "strict-types", non_exhaustive)]
+pub struct AgentSkill {
+    /// The name of the skill.
+    pub name: String,
+    /// A description of the skill.
+    pub description: String,
+    /// Input modes supported by this skill.
+    #[serde(rename = "inputModes", skip_serializing_if = "Option::is_none")]
+    pub input_modes: Option<Vec<String>>,
+    /// Output modes supported by this skill.
+    #[serde(rename = "outputModes", skip_serializing_if = "Option::is_none")]
+    pub output_modes: Option<Vec<String>>,
+    /// Example scenarios that the skill can perform.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<String>>,
+    /// The name of the skill by BCP-47 language tag, under the
+    /// `x-nameLocalizations` extension key.
+    #[serde(rename = "x-nameLocalizations", skip_serializing_if = "crate::localization::is_empty")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::localizations))]
+    pub name_localizations: Option<Map<String, String>>,
+    /// The description of the skill by BCP-47 language tag, under the
+    /// `x-descriptionLocalizations` extension key.
+    #[serde(rename = "x-descriptionLocalizations", skip_serializing_if = "crate::localization::is_empty")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::localizations))]
+    pub description_localizations: Option<Map<String, String>>,
+}
+
+impl AgentSkill {
+    /// Create a skill with only the required fields set.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the skill.
+    /// * `description` - A description of the skill.
+    ///
+    /// # Returns
+    ///
+    /// A new `AgentSkill` with no input modes, output modes or examples.
+    pub fn minimal(name: String, description: String) -> Self {
+        Self {
+            name,
+            description,
+            input_modes: None,
+            output_modes: None,
+            examples: None,
+            name_localizations: None,
+            description_localizations: None,
+        }
+    }
+
+    /// Set the input modes of the skill.
+    pub fn with_input_modes(mut self, modes: Vec<String>) -> Self {
+        self.input_modes = Some(modes);
+        self
+    }
+
+    /// Set the output modes of the skill.
+    pub fn with_output_modes(mut self, modes: Vec<String>) -> Self {
+        self.output_modes = Some(modes);
+        self
+    }
+
+    /// Set the example scenarios of the skill.
+    pub fn with_examples(mut self, examples: Vec<String>) -> Self {
+        self.examples = Some(examples);
+        self
+    }
+}
+
+/// Agent card.
+///
+/// With the `strict-types` feature it is non-exhaustive, so fields can be
+/// added without breaking callers; build one with [`AgentCard::new`] and set the
+/// optional fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "strict-types", non_exhaustive)]
+pub struct AgentCard {
+    /// Human readable name of the agent.
+    pub name: String,
+    /// A human-readable description of the agent.
+    pub description: String,
+    /// The version of the agent.
+    pub version: String,
+    /// The version of the A2A protocol this agent supports.
+    #[serde(rename = "protocolVersion")]
+    pub protocol_version: String,
+    /// A URL to the address the agent is hosted at.
+    pub url: String,
+    /// The transport of the preferred endpoint. If empty, defaults to JSONRPC.
+    #[serde(rename = "preferredTransport", skip_serializing_if = "Option::is_none")]
+    pub preferred_transport: Option<String>,
+    /// Optional capabilities supported by the agent.
+    pub capabilities: AgentCapabilities,
+    /// The set of interaction modes that the agent supports across all skills.
+    #[serde(rename = "defaultInputModes")]
+    pub default_input_modes: Vec<String>,
+    /// Supported media types for output.
+    #[serde(rename = "defaultOutputModes")]
+    pub default_output_modes: Vec<String>,
+    /// Skills are a unit of capability that an agent can perform.
+    pub skills: Vec<AgentSkill>,
+    /// The service provider of the agent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<AgentProvider>,
+    /// A URL to documentation for the agent.
+    #[serde(rename = "documentationUrl", skip_serializing_if = "Option::is_none")]
+    pub documentation_url: Option<String>,
+    /// A URL to an icon for the agent.
+    #[serde(rename = "iconUrl", skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+    /// True if the agent supports providing an extended agent card when the user is authenticated.
+    #[serde(rename = "supportsAuthenticatedExtendedCard", skip_serializing_if = "Option::is_none")]
+    pub supports_authenticated_extended_card: Option<bool>,
+    /// Announcement of additional supported transports.
+    #[serde(rename = "additionalInterfaces", skip_serializing_if = "Option::is_none")]
+    pub additional_interfaces: Option<Vec<AgentInterface>>,
+    /// Security requirements for contacting the agent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<Map<String, Vec<String>>>>,
+    /// Security scheme details used for authenticating with this agent.
+    #[serde(rename = "securitySchemes", skip_serializing_if = "Option::is_none")]
+    pub security_schemes: Option<Map<String, SecurityScheme>>,
+    /// JSON Web Signatures of the card (A2A 0.3).
+    #[cfg(feature = "spec-0-3")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signatures: Option<Vec<AgentCardSignature>>,
+    /// The name of the agent by BCP-47 language tag, under the
+    /// `x-nameLocalizations` extension key; see [`AgentCard::localized`].
+    #[serde(rename = "x-nameLocalizations", skip_serializing_if = "crate::localization::is_empty")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::localizations))]
+    pub name_localizations: Option<Map<String, String>>,
+    /// The description of the agent by BCP-47 language tag, under the
+    /// `x-descriptionLocalizations` extension key.
+    #[serde(rename = "x-descriptionLocalizations", skip_serializing_if = "crate::localization::is_empty")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::localizations))]
+    pub description_localizations: Option<Map<String, String>>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// A JSON Web Signature of an agent card, in flattened JWS JSON form (A2A 0.3).
+#[cfg(feature = "spec-0-3")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AgentCardSignature {
+    /// The base64url-encoded protected JWS header.
+    pub protected: String,
+    /// The base64url-encoded signature.
+    pub signature: String,
+    /// The unprotected JWS header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::optional_value))]
+    pub header: Option<serde_json::Value>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// Options for [`AgentCard::validate_with_options`] and
+/// [`AgentCard::validate_links`].
+///
+/// The default applies only the checks the spec requires.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CardValidationOptions {
+    /// Require `iconUrl` to point to an image file or be an image `data:`
+    /// URI, rather than any HTTP(S) URL. Defaults to `false`: the spec
+    /// allows icon URLs without a file extension.
+    pub require_image_icon: bool,
+}
+
+impl AgentCard {
+    /// Create a new agent card with the specified parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the agent.
+    /// * `description` - A description of the agent.
+    /// * `version` - The version of the agent.
+    /// * `url` - The URL where the agent is hosted.
+    /// * `capabilities` - The capabilities of the agent.
+    /// * `default_input_modes` - The default input modes supported by the agent.
+    /// * `default_output_modes` - The default output modes supported by the agent.
+    /// * `skills` - The skills provided by the agent.
+    ///
+    /// # Returns
+    ///
+    /// A new `AgentCard` with the specified parameters.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: String,
+        description: String,
+        version: String,
+        url: String,
+        capabilities: AgentCapabilities,
+        default_input_modes: Vec<String>,
+        default_output_modes: Vec<String>,
+        skills: Vec<AgentSkill>,
+    ) -> Self {
+        Self {
+            name,
+            description,
+            version,
+            protocol_version: PROTOCOL_VERSION.to_string(),
+            url,
+            preferred_transport: None,
+            capabilities,
+            default_input_modes,
+            default_output_modes,
+            skills,
+            provider: None,
+            documentation_url: None,
+            icon_url: None,
+            supports_authenticated_extended_card: None,
+            additional_interfaces: None,
+            security: None,
+            security_schemes: None,
+            #[cfg(feature = "spec-0-3")]
+            signatures: None,
+            name_localizations: None,
+            description_localizations: None,
+            extra: ExtraFields::default(),
+        }
+    }
+
+    /// Find the URL serving a given transport.
+    ///
+    /// The main `url` serves the preferred transport, which defaults to
+    /// `JSONRPC`; additional interfaces are checked after it. Transport names
+    /// are compared case-insensitively.
+    ///
+    /// # Arguments
+    ///
+    /// * `transport` - The transport name, e.g. `GRPC`.
+    ///
+    /// # Returns
+    ///
+    /// The URL, or `None` if the agent does not announce the transport.
+    pub fn url_for_transport(&self, transport: &str) -> Option<&str> {
+        let preferred = self.preferred_transport.as_deref().unwrap_or("JSONRPC");
+        if preferred.eq_ignore_ascii_case(transport) {
+            return Some(&self.url);
+        }
+
+        self.additional_interfaces
+            .iter()
+            .flatten()
+            .find(|interface| interface.transport.eq_ignore_ascii_case(transport))
+            .map(|interface| interface.url.as_str())
+    }
+
+    /// Validate the agent card.
+    ///
+    /// Checks the name, version and URLs, the provider and documentation
+    /// and icon links, and validates any declared extensions and security
+    /// schemes, including the scopes security requirements ask for, and the
+    /// language tags of localizations.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        self.validate_with_options(&CardValidationOptions::default())
+    }
+
+    /// Validate the agent card, with optional stricter checks.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Which optional checks to apply on top of
+    ///   [`AgentCard::validate`].
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate_with_options(&self, options: &CardValidationOptions) -> Result<(), String> {
+        crate::validation::validate_agent_name(&self.name)?;
+        crate::validation::validate_version(&self.version)?;
+        crate::validation::validate_url(&self.url)?;
+        self.validate_links(options)?;
+
+        for interface in self.additional_interfaces.iter().flatten() {
+            crate::validation::validate_url(&interface.url)?;
+        }
+
+        for extension in self.capabilities.extensions.iter().flatten() {
+            extension.validate()?;
+        }
+
+        for (name, scheme) in self.security_schemes.iter().flatten() {
+            scheme
+                .validate()
+                .map_err(|e| format!("Security scheme '{}' is invalid: {}", name, e))?;
+        }
+
+        self.validate_requirement_scopes()?;
+        crate::localization::validate(self)?;
+
+        Ok(())
+    }
+
+    /// Check that the scopes named in `security` requirements are declared
+    /// by the OAuth2 schemes they refer to.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, otherwise a `ValidationError` for the first
+    /// unknown scope, with a field such as `security[0].oauth`.
+    pub fn validate_requirement_scopes(&self) -> Result<(), ValidationError> {
+        for (i, requirement) in self.security.iter().flatten().enumerate() {
+            for (name, scopes) in requirement {
+                let Some(SecurityScheme::OAuth2(scheme)) = self.security_schemes.as_ref().and_then(|s| s.get(name))
+                else {
+                    continue;
+                };
+                if let Some(scope) = scopes.iter().find(|scope| !scheme.declares_scope(scope)) {
+                    return Err(ValidationError::for_field(
+                        format!("security[{}].{}", i, name),
+                        format!("Security requirement uses scope '{}' not declared by scheme '{}'", scope, name),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate the provider, documentation URL and icon URL.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Which optional checks to apply.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, otherwise a `ValidationError` whose field is the
+    /// JSON path of the offending value, e.g. `provider.url`.
+    pub fn validate_links(&self, options: &CardValidationOptions) -> Result<(), ValidationError> {
+        if let Some(provider) = &self.provider {
+            provider.validate().map_err(|e| ValidationError {
+                field: e.field.map(|field| format!("provider.{}", field)),
+                message: e.message,
+            })?;
+        }
+
+        if let Some(url) = &self.documentation_url {
+            validation::validate_url(url).map_err(|e| {
+                ValidationError::for_field("documentationUrl", format!("Documentation URL is invalid: {}", e))
+            })?;
+        }
+
+        if let Some(url) = &self.icon_url {
+            let result = if options.require_image_icon {
+                validation::validate_icon_url(url)
+            } else {
+                validation::validate_url(url)
+            };
+            result.map_err(|e| ValidationError::for_field("iconUrl", format!("Icon URL is invalid: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Find a skill by name.
+    ///
+    /// Skills carry no separate id in this protocol version, so the name is
+    /// the identifier. An exact match is preferred over a case-insensitive one.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The skill name.
+    ///
+    /// # Returns
+    ///
+    /// The skill, or `None` if the agent has no skill with that name.
+    pub fn find_skill(&self, name: &str) -> Option<&AgentSkill> {
+        self.skills
+            .iter()
+            .find(|skill| skill.name == name)
+            .or_else(|| self.skills.iter().find(|skill| skill.name.eq_ignore_ascii_case(name)))
+    }
+
+    /// Get the input modes of a skill, falling back to the card defaults.
+    pub fn input_modes_for<'a>(&'a self, skill: &'a AgentSkill) -> &'a [String] {
+        skill.input_modes.as_deref().unwrap_or(&self.default_input_modes)
+    }
+
+    /// Get the output modes of a skill, falling back to the card defaults.
+    pub fn output_modes_for<'a>(&'a self, skill: &'a AgentSkill) -> &'a [String] {
+        skill.output_modes.as_deref().unwrap_or(&self.default_output_modes)
+    }
+
+    /// Find the skills that accept a media type as input.
+    ///
+    /// Wildcards such as `text/*` and `*/*` match on either side, and media
+    /// type parameters are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The media type, e.g. `text/plain`.
+    ///
+    /// # Returns
+    ///
+    /// The matching skills, in card order.
+    pub fn skills_accepting(&self, mode: &str) -> Vec<&AgentSkill> {
+        self.skills
+            .iter()
+            .filter(|skill| self.input_modes_for(skill).iter().any(|m| media_types_match(m, mode)))
+            .collect()
+    }
+
+    /// Find the skills that produce a media type as output.
+    ///
+    /// Wildcards such as `text/*` and `*/*` match on either side, and media
+    /// type parameters are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The media type, e.g. `application/json`.
+    ///
+    /// # Returns
+    ///
+    /// The matching skills, in card order.
+    pub fn skills_producing(&self, mode: &str) -> Vec<&AgentSkill> {
+        self.skills
+            .iter()
+            .filter(|skill| self.output_modes_for(skill).iter().any(|m| media_types_match(m, mode)))
+            .collect()
+    }
+
+    /// Describe the agent in one line, for CLI listings.
+    ///
+    /// # Returns
+    ///
+    /// The name, version, description, skill names and declared capabilities.
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{} v{}", self.name, self.version);
+        if !self.description.is_empty() {
+            summary.push_str(" - ");
+            summary.push_str(&self.description);
+        }
+
+        let mut details = Vec::new();
+        if !self.skills.is_empty() {
+            let names: Vec<&str> = self.skills.iter().map(|skill| skill.name.as_str()).collect();
+            details.push(format!("skills: {}", names.join(", ")));
+        }
+
+        let capabilities: Vec<&str> = [
+            (self.capabilities.streaming, "streaming"),
+            (self.capabilities.push_notifications, "push notifications"),
+            (self.capabilities.state_transition_history, "state history"),
+        ]
+        .into_iter()
+        .filter(|(flag, _)| flag.unwrap_or(false))
+        .map(|(_, name)| name)
+        .collect();
+        if !capabilities.is_empty() {
+            details.push(format!("capabilities: {}", capabilities.join(", ")));
+        }
+
+        if !details.is_empty() {
+            summary.push_str(&format!(" ({})", details.join("; ")));
+        }
+
+        summary
+    }
+}
+
+/// Check if two media types match, allowing wildcards on either side.
+///
+/// Comparison is case-insensitive and ignores parameters such as `charset`.
+pub(crate) fn media_types_match(a: &str, b: &str) -> bool {
+    fn split(media_type: &str) -> (String, String) {
+        let essence = media_type.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+        match essence.split_once('/') {
+            Some((kind, subtype)) => (kind.to_string(), subtype.to_string()),
+            None => (essence, "*".to_string()),
+        }
+    }
+
+    let (a_kind, a_subtype) = split(a);
+    let (b_kind, b_subtype) = split(b);
+
+    (a_kind == "*" || b_kind == "*" || a_kind == b_kind)
+        && (a_subtype == "*" || b_subtype == "*" || a_subtype == b_subtype)
+}
@@ -0,0 +1,190 @@
+//! Support for deriving `arbitrary::Arbitrary` on the protocol types.
+//!
+//! `serde_json::Value` does not implement `Arbitrary`, so fields holding JSON
+//! use the generators below. They only produce values that survive a JSON
+//! round trip unchanged: no floats, and no `Some(Value::Null)`, which
+//! deserializes back to `None`.
+
+use crate::ExtraFields;
+use arbitrary::{Arbitrary, Result, Unstructured};
+use serde_json::{Map, Value};
+
+/// Maximum nesting depth of generated JSON.
+const MAX_DEPTH: usize = 3;
+
+/// Maximum number of elements in generated arrays and objects.
+const MAX_LEN: usize = 4;
+
+/// Generate any JSON value.
+pub(crate) fn value(u: &mut Unstructured) -> Result<Value> {
+    value_at_depth(u, 0)
+}
+
+/// Generate an optional JSON value that is never `null`.
+pub(crate) fn optional_value(u: &mut Unstructured) -> Result<Option<Value>> {
+    if u.arbitrary()? {
+        let value = value(u)?;
+        Ok(Some(if value.is_null() { Value::Object(Map::new()) } else { value }))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Generate an optional JSON object, as used for metadata and parameters.
+pub(crate) fn object(u: &mut Unstructured) -> Result<Option<Value>> {
+    if u.arbitrary()? {
+        Ok(Some(Value::Object(object_at_depth(u, 0)?)))
+    } else {
+        Ok(None)
+    }
+}
+
+fn value_at_depth(u: &mut Unstructured, depth: usize) -> Result<Value> {
+    let kinds = if depth >= MAX_DEPTH { 4 } else { 6 };
+    Ok(match u.choose_index(kinds)? {
+        0 => Value::Null,
+        1 => Value::Bool(u.arbitrary()?),
+        2 => Value::from(i64::arbitrary(u)?),
+        3 => Value::String(u.arbitrary()?),
+        4 => {
+            let len = u.int_in_range(0..=MAX_LEN)?;
+            Value::Array((0..len).map(|_| value_at_depth(u, depth + 1)).collect::<Result<_>>()?)
+        }
+        _ => Value::Object(object_at_depth(u, depth + 1)?),
+    })
+}
+
+fn object_at_depth(u: &mut Unstructured, depth: usize) -> Result<Map<String, Value>> {
+    let len = u.int_in_range(0..=MAX_LEN)?;
+    let mut object = Map::new();
+    for _ in 0..len {
+        object.insert(u.arbitrary()?, value_at_depth(u, depth)?);
+    }
+    Ok(object)
+}
+
+/// Generate optional localizations, which are never `Some` and empty since
+/// empty ones are not serialized.
+pub(crate) fn localizations(u: &mut Unstructured) -> Result<Option<crate::Map<String, String>>> {
+    let localizations: Option<crate::Map<String, String>> = u.arbitrary()?;
+    Ok(localizations.filter(|localizations| !localizations.is_empty()))
+}
+
+/// Generate an unknown message role, which never spells a known one.
+pub(crate) fn other_role(u: &mut Unstructured) -> Result<String> {
+    let role: String = u.arbitrary()?;
+    Ok(match role.as_str() {
+        "agent" | "user" => format!("{}-other", role),
+        _ => role,
+    })
+}
+
+/// Generate an unknown part kind, which never spells a known one.
+pub(crate) fn other_part_kind(u: &mut Unstructured) -> Result<String> {
+    let kind: String = u.arbitrary()?;
+    Ok(match kind.as_str() {
+        "text" | "file" | "data" => format!("{}-other", kind),
+        _ => kind,
+    })
+}
+
+/// Generate the fields of an unknown part, without the `kind` and
+/// `metadata` fields it has on its own.
+pub(crate) fn part_body(u: &mut Unstructured) -> Result<Map<String, Value>> {
+    let mut body = object_at_depth(u, 0)?;
+    body.remove("kind");
+    body.remove("metadata");
+    Ok(body)
+}
+
+/// Extra fields are always generated empty, since arbitrary keys could
+/// collide with the fields of the type they are flattened into.
+impl<'a> Arbitrary<'a> for ExtraFields {
+    fn arbitrary(_u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(ExtraFields::default())
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use serde::{Serialize, de::DeserializeOwned};
+
+    /// Deterministic pseudo-random input buffers.
+    fn inputs() -> impl Iterator<Item = Vec<u8>> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..200).map(move |i| {
+            (0..64 + i * 16)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state as u8
+                })
+                .collect()
+        })
+    }
+
+    fn assert_round_trips<T>()
+    where
+        T: for<'a> Arbitrary<'a> + Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        for bytes in inputs() {
+            let Ok(value) = T::arbitrary(&mut Unstructured::new(&bytes)) else {
+                continue;
+            };
+            let json = serde_json::to_value(&value).unwrap();
+            let decoded: T = serde_json::from_value(json.clone())
+                .unwrap_or_else(|e| panic!("{} did not round trip: {}\n{}", std::any::type_name::<T>(), e, json));
+            assert_eq!(decoded, value);
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_values_round_trip() {
+        assert_round_trips::<Message>();
+        assert_round_trips::<Part>();
+        assert_round_trips::<Task>();
+        assert_round_trips::<Artifact>();
+        assert_round_trips::<TaskStatusUpdateEvent>();
+        assert_round_trips::<TaskArtifactUpdateEvent>();
+        assert_round_trips::<AgentCard>();
+        assert_round_trips::<RequestMethod>();
+        assert_round_trips::<JsonRpcId>();
+    }
+
+    #[test]
+    fn test_parsers_survive_mutated_payloads() {
+        let seeds = [
+            r#"{"jsonrpc":"2.0","id":1,"method":"message/send","params":{"message":{"kind":"message","messageId":"m","role":"user","parts":[{"kind":"file","file":{"uri":"https://e.com"}}]}}}"#,
+            r#"{"jsonrpc":"2.0","id":"2","method":"tasks/pushNotificationConfig/set","params":{"taskId":"t","config":{"url":"http://x"}}}"#,
+            r#"{"code":-32001,"message":"Task not found","data":{"taskId":"t"}}"#,
+        ];
+        let tokens = [b'{', b'}', b'[', b'"', b',', b':', b'0', b'-', b'n', b'\\'];
+
+        for seed in seeds {
+            let seed = seed.as_bytes();
+            for i in 0..seed.len() {
+                let mut mutations = vec![seed[..i].to_vec()];
+                for token in tokens {
+                    let mut mutated = seed.to_vec();
+                    mutated[i] = token;
+                    mutations.push(mutated);
+                }
+                for bytes in mutations {
+                    let text = String::from_utf8_lossy(&bytes);
+                    let _ = helpers::parse_request(&text);
+                    let _ = helpers::parse_envelope(&text);
+                    let _ = serde_json::from_slice::<A2ARequest>(&bytes);
+                    let _ = serde_json::from_slice::<A2AError>(&bytes);
+                    let _ = serde_json::from_slice::<SendMessageResponse>(&bytes);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Audio file parts.
+//!
+//! An audio clip is a file part with an `audio/*` media type, conventionally
+//! carrying its length in the `durationMs` metadata key so players can show
+//! it before fetching the file. [`FilePart::audio_from_bytes`] and
+//! [`FilePart::audio_from_uri`] build such parts, and [`Part::as_audio`]
+//! recognizes them.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::metadata::{get_meta, set_meta};
+use crate::validation::check_media_kind;
+use crate::{FileContent, FilePart, FileWithBytes, FileWithUri, Part};
+use core::time::Duration;
+use serde_json::Value;
+
+/// Metadata key of the length of an audio clip in milliseconds.
+pub const DURATION_KEY: &str = "durationMs";
+
+/// A file part recognized as an audio clip.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioPart<'a> {
+    file: &'a FilePart,
+    mime_type: &'a str,
+}
+
+impl<'a> AudioPart<'a> {
+    /// The file part of the clip.
+    pub fn file(&self) -> &'a FilePart {
+        self.file
+    }
+
+    /// The media type of the clip, e.g. `audio/ogg`.
+    pub fn mime_type(&self) -> &'a str {
+        self.mime_type
+    }
+
+    /// The length of the clip, if its metadata holds a valid one.
+    pub fn duration(&self) -> Option<Duration> {
+        get_meta(&self.file.metadata, DURATION_KEY)
+            .and_then(Value::as_u64)
+            .map(Duration::from_millis)
+    }
+}
+
+impl FilePart {
+    /// Create a file part holding an audio clip.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The raw clip, base64 encoded into the part.
+    /// * `mime` - The media type, which must be `audio/*`.
+    /// * `duration` - The length of the clip, if known.
+    ///
+    /// # Returns
+    ///
+    /// The file part, or an error if the media type is not an audio type.
+    pub fn audio_from_bytes(bytes: &[u8], mime: &str, duration: Option<Duration>) -> Result<Self, String> {
+        let file = FileContent::WithBytes(FileWithBytes {
+            bytes: crate::security::base64_encode(bytes),
+            name: None,
+            mime_type: Some(mime.to_string()),
+        });
+        audio(file, mime, duration)
+    }
+
+    /// Create a file part referring to an audio clip.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - Where the clip can be fetched.
+    /// * `mime` - The media type, which must be `audio/*`.
+    /// * `duration` - The length of the clip, if known.
+    ///
+    /// # Returns
+    ///
+    /// The file part, or an error if the media type is not an audio type.
+    pub fn audio_from_uri(uri: String, mime: &str, duration: Option<Duration>) -> Result<Self, String> {
+        let file = FileContent::WithUri(FileWithUri {
+            uri,
+            name: None,
+            mime_type: Some(mime.to_string()),
+        });
+        audio(file, mime, duration)
+    }
+
+    /// View the part as an audio clip.
+    ///
+    /// # Returns
+    ///
+    /// The clip, or `None` if the file has no `audio/*` media type.
+    pub fn as_audio(&self) -> Option<AudioPart<'_>> {
+        let mime_type = match &self.file {
+            FileContent::WithBytes(file) => file.mime_type.as_deref(),
+            FileContent::WithUri(file) => file.mime_type.as_deref(),
+        }?;
+        check_media_kind(Some(mime_type), "audio").ok()?;
+        Some(AudioPart { file: self, mime_type })
+    }
+}
+
+impl Part {
+    /// View the part as an audio clip.
+    ///
+    /// # Returns
+    ///
+    /// The clip, or `None` if the part is not a file part with an
+    /// `audio/*` media type.
+    pub fn as_audio(&self) -> Option<AudioPart<'_>> {
+        match self {
+            Part::File(part) => part.as_audio(),
+            _ => None,
+        }
+    }
+}
+
+fn audio(file: FileContent, mime: &str, duration: Option<Duration>) -> Result<FilePart, String> {
+    check_media_kind(Some(mime), "audio")?;
+    let mut metadata = None;
+    if let Some(duration) = duration {
+        let millis = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        set_meta(&mut metadata, DURATION_KEY, Value::from(millis));
+    }
+    Ok(FilePart { file, metadata })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_round_trip() {
+        let part = FilePart::audio_from_bytes(b"OggS", "audio/ogg", Some(Duration::from_millis(1500))).unwrap();
+        assert_eq!(part.metadata, Some(serde_json::json!({"durationMs": 1500})));
+
+        let json = serde_json::to_string(&Part::from(part)).unwrap();
+        let part: Part = serde_json::from_str(&json).unwrap();
+        let audio = part.as_audio().unwrap();
+        assert_eq!(audio.mime_type(), "audio/ogg");
+        assert_eq!(audio.duration(), Some(Duration::from_millis(1500)));
+        assert!(matches!(&audio.file().file, FileContent::WithBytes(file) if file.bytes == "T2dnUw=="));
+
+        let part = FilePart::audio_from_uri("https://example.com/clip.mp3".to_string(), "audio/mpeg", None).unwrap();
+        assert_eq!(part.metadata, None);
+        assert_eq!(part.as_audio().unwrap().duration(), None);
+    }
+
+    #[test]
+    fn test_non_audio_parts() {
+        assert!(FilePart::audio_from_bytes(b"%PDF", "application/pdf", None).is_err());
+        assert!(Part::from("a clip").as_audio().is_none());
+
+        let with_mime = |mime_type: Option<&str>| {
+            FilePart::from(FileContent::WithUri(FileWithUri {
+                uri: "https://example.com/a.wav".to_string(),
+                name: None,
+                mime_type: mime_type.map(str::to_string),
+            }))
+        };
+        assert!(with_mime(Some("AUDIO/WAV")).as_audio().is_some());
+        assert!(with_mime(Some("video/mp4")).as_audio().is_none());
+        assert!(Part::from(with_mime(None)).as_audio().is_none());
+
+        // A malformed duration leaves the clip recognized but unmeasured
+        let mut part = FilePart::audio_from_uri("https://example.com/a.wav".to_string(), "audio/wav", None).unwrap();
+        part.metadata = Some(serde_json::json!({"durationMs": "long"}));
+        assert_eq!(part.as_audio().unwrap().duration(), None);
+    }
+}
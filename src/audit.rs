@@ -0,0 +1,291 @@
+//! Chronological event logs for auditing tasks.
+//!
+//! Every change to a task can be recorded as a [`TaskEvent`]: status
+//! transitions, new messages, new artifacts and push notification
+//! deliveries. Producers append events to an [`EventSink`];
+//! [`TaskManager::save_task`](crate::manager::TaskManager::save_task) does so
+//! for every change it stores, and push notification senders record each
+//! delivery themselves. [`TaskEventLog`] is an in-memory sink keeping the
+//! latest events of each task, which it exports as JSON and from which a
+//! task's `statusHistory` can be rebuilt.
+
+use crate::ids::TaskId;
+use crate::clock::format_time;
+use crate::{ExtraFields, MessageRole, Task, TaskState, TaskStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// How a push notification delivery ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "kebab-case")]
+pub enum DeliveryOutcome {
+    /// The receiver accepted the notification.
+    Delivered,
+    /// The receiver answered with an error status.
+    Rejected {
+        /// The HTTP status code.
+        status: u16,
+    },
+    /// The notification could not be sent.
+    Failed {
+        /// What went wrong.
+        reason: String,
+    },
+}
+
+/// One thing that happened to a task.
+///
+/// Times are RFC 3339 timestamps in UTC.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum TaskEvent {
+    /// The task changed state, or was created if `from` is `None`.
+    StatusChanged {
+        /// The previous state.
+        from: Option<TaskState>,
+        /// The new state.
+        to: TaskState,
+        /// When the change was recorded.
+        at: String,
+    },
+    /// A message was added to the task history.
+    MessageAdded {
+        /// Who sent the message.
+        role: MessageRole,
+        /// The ID of the message.
+        #[serde(rename = "messageId")]
+        message_id: String,
+        /// When the message was recorded.
+        at: String,
+    },
+    /// An artifact was added to the task.
+    ArtifactAdded {
+        /// The ID of the artifact.
+        #[serde(rename = "artifactId")]
+        artifact_id: String,
+        /// When the artifact was recorded.
+        at: String,
+    },
+    /// A push notification about the task was sent.
+    PushDelivered {
+        /// The ID of the push notification config, if it has one.
+        #[serde(rename = "configId")]
+        config_id: Option<String>,
+        /// When the delivery ended.
+        at: String,
+        /// How it ended.
+        outcome: DeliveryOutcome,
+    },
+}
+
+impl TaskEvent {
+    /// The events that turn one version of a task into the next.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - The task before the change, or `None` if it is new.
+    /// * `current` - The task after the change.
+    /// * `at` - When the change happened.
+    ///
+    /// # Returns
+    ///
+    /// A status change if the state changed, then one event per message and
+    /// artifact whose ID the previous version did not have, in task order.
+    pub fn between(previous: Option<&Task>, current: &Task, at: SystemTime) -> Vec<TaskEvent> {
+        let at = format_time(at);
+        let mut events = Vec::new();
+
+        let from = previous.map(|task| task.status.state.clone());
+        if from.as_ref() != Some(&current.status.state) {
+            events.push(TaskEvent::StatusChanged {
+                from,
+                to: current.status.state.clone(),
+                at: at.clone(),
+            });
+        }
+
+        let known_messages: HashSet<&str> = previous
+            .and_then(|task| task.history.as_ref())
+            .into_iter()
+            .flatten()
+            .map(|message| message.message_id.as_str())
+            .collect();
+        for message in current.history.iter().flatten() {
+            if !known_messages.contains(message.message_id.as_str()) {
+                events.push(TaskEvent::MessageAdded {
+                    role: message.role.clone(),
+                    message_id: message.message_id.clone(),
+                    at: at.clone(),
+                });
+            }
+        }
+
+        let known_artifacts: HashSet<&str> = previous
+            .and_then(|task| task.artifacts.as_ref())
+            .into_iter()
+            .flatten()
+            .map(|artifact| artifact.artifact_id.as_str())
+            .collect();
+        for artifact in current.artifacts.iter().flatten() {
+            if !known_artifacts.contains(artifact.artifact_id.as_str()) {
+                events.push(TaskEvent::ArtifactAdded {
+                    artifact_id: artifact.artifact_id.clone(),
+                    at: at.clone(),
+                });
+            }
+        }
+
+        events
+    }
+
+    /// When the event happened.
+    pub fn at(&self) -> &str {
+        match self {
+            TaskEvent::StatusChanged { at, .. }
+            | TaskEvent::MessageAdded { at, .. }
+            | TaskEvent::ArtifactAdded { at, .. }
+            | TaskEvent::PushDelivered { at, .. } => at,
+        }
+    }
+}
+
+/// Receives task events.
+pub trait EventSink: fmt::Debug + Send + Sync {
+    /// Record an event of a task.
+    fn record(&self, task_id: &TaskId, event: TaskEvent);
+}
+
+impl<E: EventSink + ?Sized> EventSink for Arc<E> {
+    fn record(&self, task_id: &TaskId, event: TaskEvent) {
+        (**self).record(task_id, event)
+    }
+}
+
+/// An in-memory event log keeping the latest events of each task.
+///
+/// Each task's events are a ring buffer: once it holds `capacity` events,
+/// recording another drops the oldest.
+#[derive(Debug)]
+pub struct TaskEventLog {
+    capacity: usize,
+    events: Mutex<HashMap<TaskId, VecDeque<TaskEvent>>>,
+}
+
+impl Default for TaskEventLog {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl TaskEventLog {
+    /// How many events are kept per task by default.
+    pub const DEFAULT_CAPACITY: usize = 1000;
+
+    /// Create a log keeping up to `capacity` events per task.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            events: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<TaskId, VecDeque<TaskEvent>>> {
+        self.events.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The events of a task, oldest first.
+    pub fn events(&self, task_id: &TaskId) -> Vec<TaskEvent> {
+        self.lock()
+            .get(task_id)
+            .map(|events| events.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// The events of a task as a JSON array, oldest first.
+    pub fn export_json(&self, task_id: &TaskId) -> String {
+        serde_json::to_string(&self.events(task_id)).expect("task events always serialize")
+    }
+
+    /// Rebuild a task's `statusHistory` from its status changes.
+    ///
+    /// # Returns
+    ///
+    /// One status per recorded state, oldest first, with the time of the
+    /// change as its timestamp.
+    pub fn status_history(&self, task_id: &TaskId) -> Vec<TaskStatus> {
+        self.lock()
+            .get(task_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|event| match event {
+                TaskEvent::StatusChanged { to, at, .. } => Some(TaskStatus {
+                    state: to.clone(),
+                    message: None,
+                    timestamp: Some(at.clone()),
+                    extra: ExtraFields::default(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Forget the events of a task.
+    ///
+    /// # Returns
+    ///
+    /// The events that were removed, oldest first.
+    pub fn remove(&self, task_id: &TaskId) -> Vec<TaskEvent> {
+        self.lock().remove(task_id).map(Vec::from).unwrap_or_default()
+    }
+}
+
+impl EventSink for TaskEventLog {
+    fn record(&self, task_id: &TaskId, event: TaskEvent) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut events = self.lock();
+        let events = events.entry(task_id.clone()).or_default();
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_ring_buffer_and_status_history() {
+        let log = TaskEventLog::new(2);
+        let id = TaskId::from("task-1");
+        for (from, to) in [(None, TaskState::Submitted), (Some(TaskState::Submitted), TaskState::Working)] {
+            log.record(&id, TaskEvent::StatusChanged { from, to, at: format_time(UNIX_EPOCH) });
+        }
+        log.record(&id, TaskEvent::PushDelivered {
+            config_id: None,
+            at: format_time(UNIX_EPOCH + Duration::from_secs(1)),
+            outcome: DeliveryOutcome::Rejected { status: 503 },
+        });
+
+        let events = log.events(&id);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], TaskEvent::StatusChanged { to: TaskState::Working, .. }));
+        assert_eq!(events[1].at(), "1970-01-01T00:00:01.000000Z");
+
+        let history = log.status_history(&id);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].state, TaskState::Working);
+        assert_eq!(history[0].timestamp.as_deref(), Some("1970-01-01T00:00:00.000000Z"));
+
+        assert_eq!(log.remove(&id).len(), 2);
+        assert_eq!(log.export_json(&id), "[]");
+        TaskEventLog::new(0).record(&id, events[0].clone());
+    }
+}
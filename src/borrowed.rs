@@ -0,0 +1,424 @@
+//! Borrowed variants of the hot-path message types.
+//!
+//! Gateways that only inspect or forward messages do not need owned copies
+//! of every string. The types in this module borrow their strings from the
+//! input buffer wherever the JSON contains no escape sequences, and can be
+//! converted to the owned types with `into_owned()` when mutation is needed.
+//!
+//! Unlike the owned types, the borrowed types do not preserve unrecognized
+//! fields, and metadata and data parts are still parsed into owned
+//! `serde_json::Value`s.
+//!
+//! ```
+//! use a2a_rs::borrowed::SendMessageRequestRef;
+//! use a2a_rs::helpers::parse_request_borrowed;
+//!
+//! let json = r#"{"jsonrpc":"2.0","id":1,"method":"message/send","params":{"message":{
+//!     "kind":"message","messageId":"msg-1","role":"user",
+//!     "parts":[{"kind":"text","text":"Hello"}]}}}"#;
+//! let request: SendMessageRequestRef = parse_request_borrowed(json).unwrap();
+//! assert_eq!(request.params.message.message_id, "msg-1");
+//! ```
+
+use crate::{
+    DataPart, ExtraFields, FileContent, FilePart, FileWithBytes, FileWithUri, JsonRpcId, Message,
+    MessageRole, MessageSendConfiguration, Part, RequestMethod, SendMessageParams, SendMessageRequest,
+    TextPart,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::value::RawValue;
+use std::borrow::Cow;
+
+/// Borrowed variant of [`SendMessageRequest`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SendMessageRequestRef<'a> {
+    /// The method name.
+    pub method: RequestMethod,
+    /// The parameters for the request.
+    #[serde(borrow)]
+    pub params: SendMessageParamsRef<'a>,
+    /// The JSON-RPC ID.
+    pub id: JsonRpcId,
+    /// The JSON-RPC version.
+    #[serde(borrow)]
+    pub jsonrpc: Cow<'a, str>,
+}
+
+impl SendMessageRequestRef<'_> {
+    /// Convert to the owned request.
+    pub fn into_owned(self) -> SendMessageRequest {
+        SendMessageRequest {
+            method: self.method,
+            params: self.params.into_owned(),
+            id: self.id,
+            jsonrpc: self.jsonrpc.into_owned(),
+            extra: ExtraFields::default(),
+        }
+    }
+}
+
+/// Borrowed variant of [`SendMessageParams`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SendMessageParamsRef<'a> {
+    /// The message being sent to the server
+    #[serde(borrow)]
+    pub message: MessageRef<'a>,
+    /// Send message configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub configuration: Option<MessageSendConfiguration>,
+    /// Extension metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+}
+
+impl SendMessageParamsRef<'_> {
+    /// Convert to the owned parameters.
+    pub fn into_owned(self) -> SendMessageParams {
+        SendMessageParams {
+            message: self.message.into_owned(),
+            configuration: self.configuration,
+            metadata: self.metadata,
+            extra: ExtraFields::default(),
+        }
+    }
+}
+
+/// Borrowed variant of [`Message`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageRef<'a> {
+    /// Event type
+    #[serde(borrow)]
+    pub kind: Cow<'a, str>,
+    /// Identifier created by the message creator
+    #[serde(borrow)]
+    pub message_id: Cow<'a, str>,
+    /// Message content
+    #[serde(borrow)]
+    pub parts: Vec<PartRef<'a>>,
+    /// Message sender's role
+    pub role: MessageRole,
+    /// The context the message is associated with
+    #[serde(borrow, default, deserialize_with = "optional_cow", skip_serializing_if = "Option::is_none")]
+    pub context_id: Option<Cow<'a, str>>,
+    /// The URIs of extensions that are present or contributed to this Message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<Vec<String>>,
+    /// Extension metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+    /// List of tasks referenced as context by this message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_task_ids: Option<Vec<String>>,
+    /// Identifier of task the message is related to
+    #[serde(borrow, default, deserialize_with = "optional_cow", skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<Cow<'a, str>>,
+}
+
+impl MessageRef<'_> {
+    /// Convert to the owned message.
+    pub fn into_owned(self) -> Message {
+        Message {
+            kind: self.kind.into_owned(),
+            message_id: self.message_id.into_owned(),
+            parts: self.parts.into_iter().map(PartRef::into_owned).collect(),
+            role: self.role,
+            context_id: self.context_id.map(Cow::into_owned),
+            extensions: self.extensions,
+            metadata: self.metadata,
+            reference_task_ids: self.reference_task_ids,
+            task_id: self.task_id.map(Cow::into_owned),
+            extra: ExtraFields::default(),
+        }
+    }
+}
+
+/// Borrowed variant of [`Part`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind")]
+pub enum PartRef<'a> {
+    #[serde(rename = "text")]
+    Text(TextPartRef<'a>),
+    #[serde(rename = "file")]
+    File(FilePartRef<'a>),
+    #[serde(rename = "data")]
+    Data(DataPart),
+    /// A part of a kind this version does not know, kept as the raw JSON
+    /// it was sent as.
+    #[serde(untagged)]
+    Unknown(&'a RawValue),
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for PartRef<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(Deserialize)]
+        #[serde(tag = "kind")]
+        enum Known<'a> {
+            #[serde(rename = "text", borrow)]
+            Text(TextPartRef<'a>),
+            #[serde(rename = "file", borrow)]
+            File(FilePartRef<'a>),
+            #[serde(rename = "data")]
+            Data(DataPart),
+        }
+
+        #[derive(Deserialize)]
+        struct Kind<'a> {
+            #[serde(borrow, default, deserialize_with = "optional_cow")]
+            kind: Option<Cow<'a, str>>,
+        }
+
+        // Borrowing the raw part costs a scan, not a copy
+        let raw = <&'a RawValue>::deserialize(deserializer)?;
+        if !raw.get().starts_with('{') {
+            return Err(D::Error::invalid_type(serde::de::Unexpected::Other(raw.get()), &"a part object"));
+        }
+        let kind = serde_json::from_str::<Kind<'a>>(raw.get()).map_err(D::Error::custom)?.kind;
+        match kind.as_deref() {
+            // Malformed parts of a known kind are errors, not unknown parts
+            Some("text" | "file" | "data") | None => match serde_json::from_str(raw.get()).map_err(D::Error::custom)? {
+                Known::Text(part) => Ok(PartRef::Text(part)),
+                Known::File(part) => Ok(PartRef::File(part)),
+                Known::Data(part) => Ok(PartRef::Data(part)),
+            },
+            Some(_) => Ok(PartRef::Unknown(raw)),
+        }
+    }
+}
+
+impl PartRef<'_> {
+    /// Convert to the owned part.
+    pub fn into_owned(self) -> Part {
+        match self {
+            PartRef::Text(part) => Part::Text(TextPart {
+                text: part.text.into_owned(),
+                metadata: part.metadata,
+            }),
+            PartRef::File(part) => Part::File(Box::new(FilePart {
+                file: part.file.into_owned(),
+                metadata: part.metadata,
+            })),
+            PartRef::Data(part) => Part::Data(part),
+            // Checked to be an object with a string kind when parsed, which
+            // always makes a Part::Unknown
+            PartRef::Unknown(raw) => serde_json::from_str(raw.get()).expect("unknown parts are objects"),
+        }
+    }
+}
+
+impl PartialEq for PartRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (PartRef::Text(a), PartRef::Text(b)) => a == b,
+            (PartRef::File(a), PartRef::File(b)) => a == b,
+            (PartRef::Data(a), PartRef::Data(b)) => a == b,
+            (PartRef::Unknown(a), PartRef::Unknown(b)) => a.get() == b.get(),
+            _ => false,
+        }
+    }
+}
+
+/// Borrowed variant of [`TextPart`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextPartRef<'a> {
+    /// Text content
+    #[serde(borrow)]
+    pub text: Cow<'a, str>,
+    /// Optional metadata associated with the part
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Borrowed variant of [`FilePart`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilePartRef<'a> {
+    /// File content either as url or bytes
+    #[serde(borrow)]
+    pub file: FileContentRef<'a>,
+    /// Optional metadata associated with the part
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Borrowed variant of [`FileContent`].
+///
+/// The base64 `bytes` are kept as text; decoding is left to the consumer.
+/// Like [`FileContent`], deserialization picks the variant by which of
+/// `bytes` and `uri` is present, and rejects files with both or neither.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum FileContentRef<'a> {
+    WithBytes {
+        /// base64 encoded content of the file
+        bytes: Cow<'a, str>,
+        /// Optional name for the file
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<Cow<'a, str>>,
+        /// Optional mimeType for the file
+        #[serde(rename = "mimeType", skip_serializing_if = "Option::is_none")]
+        mime_type: Option<Cow<'a, str>>,
+    },
+    WithUri {
+        /// URL for the File content
+        uri: Cow<'a, str>,
+        /// Optional name for the file
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<Cow<'a, str>>,
+        /// Optional mimeType for the file
+        #[serde(rename = "mimeType", skip_serializing_if = "Option::is_none")]
+        mime_type: Option<Cow<'a, str>>,
+    },
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for FileContentRef<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(Deserialize)]
+        struct Fields<'a> {
+            #[serde(borrow, default, deserialize_with = "optional_cow")]
+            bytes: Option<Cow<'a, str>>,
+            #[serde(borrow, default, deserialize_with = "optional_cow")]
+            uri: Option<Cow<'a, str>>,
+            #[serde(borrow, default, deserialize_with = "optional_cow")]
+            name: Option<Cow<'a, str>>,
+            #[serde(rename = "mimeType", borrow, default, deserialize_with = "optional_cow")]
+            mime_type: Option<Cow<'a, str>>,
+        }
+
+        let Fields { bytes, uri, name, mime_type } = Fields::deserialize(deserializer)?;
+        match (bytes, uri) {
+            (Some(bytes), None) => Ok(FileContentRef::WithBytes { bytes, name, mime_type }),
+            (None, Some(uri)) => Ok(FileContentRef::WithUri { uri, name, mime_type }),
+            (Some(_), Some(_)) => Err(D::Error::custom("file has both `bytes` and `uri`, expected exactly one")),
+            (None, None) => Err(D::Error::custom("file has neither `bytes` nor `uri`, expected exactly one")),
+        }
+    }
+}
+
+impl FileContentRef<'_> {
+    /// Convert to the owned file content.
+    pub fn into_owned(self) -> FileContent {
+        match self {
+            FileContentRef::WithBytes { bytes, name, mime_type } => FileContent::WithBytes(FileWithBytes {
+                bytes: bytes.into_owned(),
+                name: name.map(Cow::into_owned),
+                mime_type: mime_type.map(Cow::into_owned),
+            }),
+            FileContentRef::WithUri { uri, name, mime_type } => FileContent::WithUri(FileWithUri {
+                uri: uri.into_owned(),
+                name: name.map(Cow::into_owned),
+                mime_type: mime_type.map(Cow::into_owned),
+            }),
+        }
+    }
+}
+
+/// Deserialize an optional string, borrowing it from the input when possible.
+///
+/// `Option<Cow<str>>` does not borrow on its own, since serde only special
+/// cases `#[serde(borrow)]` on a bare `Cow<str>`.
+fn optional_cow<'de: 'a, 'a, D>(deserializer: D) -> Result<Option<Cow<'a, str>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Borrowed<'a>(#[serde(borrow)] Cow<'a, str>);
+
+    Ok(Option::<Borrowed<'a>>::deserialize(deserializer)?.map(|b| b.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::parse_request_borrowed;
+
+    const REQUEST: &str = r#"{
+        "jsonrpc": "2.0",
+        "id": "req-1",
+        "method": "message/send",
+        "params": {
+            "message": {
+                "kind": "message",
+                "messageId": "msg-1",
+                "contextId": "ctx-1",
+                "role": "user",
+                "parts": [
+                    {"kind": "text", "text": "plain text"},
+                    {"kind": "text", "text": "escaped \"text\""},
+                    {"kind": "file", "file": {"bytes": "aGVsbG8=", "mimeType": "text/plain"}},
+                    {"kind": "file", "file": {"uri": "https://example.com/a.png", "name": "a.png"}},
+                    {"kind": "data", "data": {"n": 1}},
+                    {"kind": "video", "url": "https://example.com/clip.mp4", "durationMs": 1500}
+                ]
+            },
+            "configuration": {"acceptedOutputModes": ["text/plain"], "blocking": true}
+        }
+    }"#;
+
+    #[test]
+    fn test_strings_are_borrowed_from_input() {
+        let request: SendMessageRequestRef = parse_request_borrowed(REQUEST).unwrap();
+        let message = &request.params.message;
+
+        assert!(matches!(message.message_id, Cow::Borrowed("msg-1")));
+        assert!(matches!(message.context_id, Some(Cow::Borrowed("ctx-1"))));
+        assert!(matches!(&message.parts[0], PartRef::Text(t) if matches!(t.text, Cow::Borrowed("plain text"))));
+        // Escape sequences force an owned copy
+        assert!(matches!(&message.parts[1], PartRef::Text(t) if matches!(t.text, Cow::Owned(_))));
+        assert!(matches!(
+            &message.parts[2],
+            PartRef::File(FilePartRef { file: FileContentRef::WithBytes { bytes: Cow::Borrowed("aGVsbG8="), .. }, .. })
+        ));
+        assert!(matches!(
+            &message.parts[3],
+            PartRef::File(FilePartRef { file: FileContentRef::WithUri { name: Some(Cow::Borrowed("a.png")), .. }, .. })
+        ));
+        // Unknown kinds are kept as raw JSON instead of failing the message
+        let PartRef::Unknown(raw) = &message.parts[5] else { panic!("not an unknown part") };
+        assert_eq!(raw.get(), r#"{"kind": "video", "url": "https://example.com/clip.mp4", "durationMs": 1500}"#);
+        assert_eq!(serde_json::to_value(&message.parts[5]).unwrap()["durationMs"], 1500);
+    }
+
+    #[test]
+    fn test_malformed_parts_fail() {
+        let part = |json: &'static str| serde_json::from_str::<PartRef<'static>>(json);
+        assert!(part(r#"{"kind": "text", "body": "x"}"#).unwrap_err().to_string().contains("missing field `text`"));
+        assert!(part(r#"["video"]"#).is_err());
+        assert!(part(r#"{"text": "no kind"}"#).is_err());
+    }
+
+    #[test]
+    fn test_file_content_matches_owned_discrimination() {
+        for json in [
+            r#"{"bytes": "aGVsbG8=", "uri": "https://example.com/a.png"}"#,
+            r#"{"name": "a.png"}"#,
+            r#"{"bytes": null, "uri": null}"#,
+        ] {
+            let borrowed = serde_json::from_str::<FileContentRef>(json).unwrap_err().to_string();
+            let owned = serde_json::from_str::<FileContent>(json).unwrap_err().to_string();
+            assert_eq!(borrowed, owned);
+        }
+
+        let json = r#"{"uri": "https://example.com/a.png", "bytes": null, "name": "a.png"}"#;
+        let borrowed: FileContentRef = serde_json::from_str(json).unwrap();
+        assert!(matches!(borrowed, FileContentRef::WithUri { uri: Cow::Borrowed(_), .. }));
+        assert_eq!(borrowed.into_owned(), serde_json::from_str::<FileContent>(json).unwrap());
+    }
+
+    #[test]
+    fn test_into_owned_matches_owned_parsing() {
+        let borrowed: SendMessageRequestRef = parse_request_borrowed(REQUEST).unwrap();
+        let owned: SendMessageRequest = serde_json::from_str(REQUEST).unwrap();
+
+        assert_eq!(borrowed.into_owned(), owned);
+    }
+}
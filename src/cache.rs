@@ -0,0 +1,635 @@
+//! Client-side caches for agent cards and task polling.
+//!
+//! [`CardCache`] keeps fetched agent cards for a TTL and revalidates expired
+//! ones with their ETag, so an unchanged card costs a `304 Not Modified`
+//! instead of a download. [`TaskCache`] keeps `tasks/get` results for a short
+//! TTL, so callers polling the same task share one request. Like
+//! [`discover_agent`](crate::discovery::discover_agent), neither does I/O
+//! itself: the request is a callback. Entries live in a [`CacheBackend`],
+//! in memory unless another backend is plugged in, and hits and misses are
+//! counted in [`CacheStats`].
+//!
+//! ```
+//! use a2a_rs::cache::{CardCache, CardResponse};
+//!
+//! let body = r#"{"name": "Agent", "description": "An agent", "version": "1.0.0", "url": "https://agent.example.com/a2a",
+//!     "protocolVersion": "0.2.5", "capabilities": {}, "defaultInputModes": ["text/plain"], "defaultOutputModes": ["text/plain"], "skills": []}"#;
+//! let cache = CardCache::default();
+//! let url = "https://agent.example.com/.well-known/agent-card.json";
+//! for _ in 0..3 {
+//!     let card = cache
+//!         .fetch(url, |_url: &str, _etag: Option<&str>| -> Result<_, String> {
+//!             Ok(CardResponse::Modified { body: body.to_string(), etag: Some("\"v1\"".to_string()) })
+//!         })
+//!         .unwrap();
+//!     assert_eq!(card.name, "Agent");
+//! }
+//! assert_eq!((cache.stats().hits, cache.stats().misses), (2, 1));
+//! ```
+
+use crate::clock::{Clock, SystemClock};
+use crate::discovery::parse_agent_card;
+use crate::ids::TaskId;
+use crate::{A2AError, AgentCard, InvalidAgentResponseError, Task};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+use std::time::{Duration, SystemTime};
+
+/// A cached value with its validator and expiry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheEntry<V> {
+    /// The cached value.
+    pub value: V,
+    /// The ETag it was served with, if any.
+    pub etag: Option<String>,
+    /// When the value must be revalidated or fetched again.
+    pub expires_at: SystemTime,
+}
+
+/// Stores cache entries by key.
+///
+/// Expired entries are still returned, so their ETag can be used to
+/// revalidate them.
+pub trait CacheBackend<V>: fmt::Debug + Send + Sync {
+    /// Get the entry for a key.
+    fn get(&self, key: &str) -> Option<CacheEntry<V>>;
+
+    /// Store an entry, replacing any entry for the same key.
+    fn put(&self, key: &str, entry: CacheEntry<V>);
+
+    /// Forget the entry for a key.
+    fn remove(&self, key: &str);
+}
+
+/// A backend keeping entries in a map in memory.
+#[derive(Debug)]
+pub struct InMemoryBackend<V> {
+    entries: Mutex<HashMap<String, CacheEntry<V>>>,
+}
+
+impl<V> Default for InMemoryBackend<V> {
+    fn default() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<V> InMemoryBackend<V> {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, CacheEntry<V>>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<V: Clone + fmt::Debug + Send> CacheBackend<V> for InMemoryBackend<V> {
+    fn get(&self, key: &str) -> Option<CacheEntry<V>> {
+        self.lock().get(key).cloned()
+    }
+
+    fn put(&self, key: &str, entry: CacheEntry<V>) {
+        self.lock().insert(key.to_string(), entry);
+    }
+
+    fn remove(&self, key: &str) {
+        self.lock().remove(key);
+    }
+}
+
+/// How often a cache answered without a full fetch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups answered from a fresh entry, without a request.
+    pub hits: u64,
+    /// Lookups that needed a request.
+    pub misses: u64,
+    /// Misses the server answered with `304 Not Modified`.
+    pub revalidated: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    revalidated: AtomicU64,
+}
+
+impl Counters {
+    fn count(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            revalidated: self.revalidated.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The answer to a card request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardResponse {
+    /// The server sent the card.
+    Modified {
+        /// The response body.
+        body: String,
+        /// The `ETag` response header, if any.
+        etag: Option<String>,
+    },
+    /// The server answered `304 Not Modified` to the `If-None-Match` header.
+    NotModified,
+}
+
+/// A TTL cache of agent cards, revalidated by ETag.
+#[derive(Debug)]
+pub struct CardCache {
+    backend: Arc<dyn CacheBackend<AgentCard>>,
+    ttl: Duration,
+    clock: Arc<dyn Clock>,
+    counters: Counters,
+}
+
+impl Default for CardCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TTL)
+    }
+}
+
+impl CardCache {
+    /// How long a card is used without asking the server by default.
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+    /// Create an in-memory cache keeping cards fresh for `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            backend: Arc::new(InMemoryBackend::default()),
+            ttl,
+            clock: Arc::new(SystemClock),
+            counters: Counters::default(),
+        }
+    }
+
+    /// Keep the entries in another backend.
+    pub fn with_backend(mut self, backend: impl CacheBackend<AgentCard> + 'static) -> Self {
+        self.backend = Arc::new(backend);
+        self
+    }
+
+    /// Set the clock that expiry is measured with.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Get the card at a URL, from the cache while it is fresh.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The card URL, e.g. one of
+    ///   [`candidate_urls`](crate::discovery::candidate_urls).
+    /// * `fetch` - Requests the URL, sending the given ETag, if any, as the
+    ///   `If-None-Match` header. Returns an error for any failure, including
+    ///   status codes other than success and `304`.
+    ///
+    /// # Returns
+    ///
+    /// The card, or an `InvalidAgentResponse` error if the request failed,
+    /// the card is not valid, or the server answered `304` to a request
+    /// without an ETag. Failed requests leave the cache unchanged.
+    pub fn fetch<F, E>(&self, url: &str, fetch: F) -> Result<AgentCard, A2AError>
+    where
+        F: FnOnce(&str, Option<&str>) -> Result<CardResponse, E>,
+        E: fmt::Display,
+    {
+        let now = self.clock.now();
+        let cached = self.backend.get(url);
+        if let Some(entry) = &cached
+            && now < entry.expires_at
+        {
+            Counters::count(&self.counters.hits);
+            return Ok(entry.value.clone());
+        }
+
+        Counters::count(&self.counters.misses);
+        let etag = cached.as_ref().and_then(|entry| entry.etag.as_deref());
+        let response = fetch(url, etag).map_err(|e| invalid_card(url, e.to_string()))?;
+        let (card, etag) = match (response, cached) {
+            (CardResponse::Modified { body, etag }, _) => (parse_agent_card(&body).map_err(|e| invalid_card(url, e))?, etag),
+            (CardResponse::NotModified, Some(entry)) if entry.etag.is_some() => {
+                Counters::count(&self.counters.revalidated);
+                (entry.value, entry.etag)
+            }
+            (CardResponse::NotModified, _) => {
+                return Err(invalid_card(url, "304 Not Modified without a cached card".to_string()));
+            }
+        };
+
+        self.backend.put(url, CacheEntry {
+            value: card.clone(),
+            etag,
+            expires_at: expiry(now, self.ttl),
+        });
+        Ok(card)
+    }
+
+    /// Forget the card of a URL, so the next fetch downloads it again.
+    pub fn invalidate(&self, url: &str) {
+        self.backend.remove(url);
+    }
+
+    /// The hits and misses so far.
+    pub fn stats(&self) -> CacheStats {
+        self.counters.snapshot()
+    }
+}
+
+/// The longest TTL used; longer ones are capped so the expiry stays a
+/// valid `SystemTime`.
+const MAX_TTL: Duration = Duration::from_secs(100 * 365 * 24 * 60 * 60);
+
+fn expiry(now: SystemTime, ttl: Duration) -> SystemTime {
+    now.checked_add(ttl).or_else(|| now.checked_add(MAX_TTL)).unwrap_or(now)
+}
+
+fn invalid_card(url: &str, reason: String) -> A2AError {
+    A2AError::InvalidAgentResponse(Box::new(InvalidAgentResponseError {
+        code: -32006,
+        message: format!("No agent card from {}: {}", url, reason),
+        data: None,
+    }))
+}
+
+/// A short-lived cache of `tasks/get` results.
+///
+/// Polling loops that share a cache send at most one request per task and
+/// TTL, however many callers wait for the task: callers arriving while a
+/// request is in flight wait for its answer instead of sending their own.
+#[derive(Debug)]
+pub struct TaskCache {
+    backend: Arc<dyn CacheBackend<Task>>,
+    ttl: Duration,
+    clock: Arc<dyn Clock>,
+    counters: Counters,
+    flights: Mutex<HashMap<String, Arc<Flight>>>,
+}
+
+/// A `tasks/get` request in flight, shared by the callers waiting for it.
+#[derive(Debug, Default)]
+struct Flight {
+    state: Mutex<FlightState>,
+}
+
+#[derive(Debug, Default)]
+struct FlightState {
+    landing: Landing,
+    wakers: Vec<Waker>,
+}
+
+#[derive(Debug, Default)]
+enum Landing {
+    #[default]
+    Pending,
+    Done(Box<Result<Task, A2AError>>),
+    /// The caller sending the request dropped it before it was answered.
+    Abandoned,
+}
+
+impl Flight {
+    fn lock(&self) -> std::sync::MutexGuard<'_, FlightState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn land(&self, landing: Landing) {
+        let wakers = {
+            let mut state = self.lock();
+            state.landing = landing;
+            std::mem::take(&mut state.wakers)
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+
+    /// Wait for the answer, or `None` if the request was abandoned.
+    async fn wait(&self) -> Option<Result<Task, A2AError>> {
+        std::future::poll_fn(|cx| {
+            let mut state = self.lock();
+            match &state.landing {
+                Landing::Pending => {
+                    if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                        state.wakers.push(cx.waker().clone());
+                    }
+                    Poll::Pending
+                }
+                Landing::Done(result) => Poll::Ready(Some(Result::clone(result))),
+                Landing::Abandoned => Poll::Ready(None),
+            }
+        })
+        .await
+    }
+}
+
+/// Ends a flight when its request is answered or dropped, so waiters never
+/// wait for a request nobody is sending.
+struct FlightGuard<'a> {
+    flights: &'a Mutex<HashMap<String, Arc<Flight>>>,
+    key: &'a str,
+    flight: Arc<Flight>,
+    landing: Landing,
+}
+
+impl Drop for FlightGuard<'_> {
+    fn drop(&mut self) {
+        self.flights.lock().unwrap_or_else(|e| e.into_inner()).remove(self.key);
+        self.flight.land(std::mem::take(&mut self.landing));
+    }
+}
+
+impl Default for TaskCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TTL)
+    }
+}
+
+impl TaskCache {
+    /// How long a fetched task is reused by default.
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(1);
+
+    /// Create an in-memory cache keeping tasks fresh for `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            backend: Arc::new(InMemoryBackend::default()),
+            ttl,
+            clock: Arc::new(SystemClock),
+            counters: Counters::default(),
+            flights: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Keep the entries in another backend.
+    pub fn with_backend(mut self, backend: impl CacheBackend<Task> + 'static) -> Self {
+        self.backend = Arc::new(backend);
+        self
+    }
+
+    /// Set the clock that expiry is measured with.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Get a task, from the cache while it is fresh.
+    ///
+    /// If another caller is already requesting the task, waits for its
+    /// answer instead of calling `fetch`.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task to get.
+    /// * `fetch` - Sends the `tasks/get` request.
+    ///
+    /// # Returns
+    ///
+    /// The task, or the error of the request. Errors are passed to the
+    /// callers waiting for the request but not cached.
+    pub async fn get_task<F, Fut>(&self, task_id: &TaskId, fetch: F) -> Result<Task, A2AError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Task, A2AError>>,
+    {
+        let key = task_id.as_str();
+        let flight = loop {
+            let waiting = {
+                let mut flights = self.flights.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(entry) = self.backend.get(key)
+                    && self.clock.now() < entry.expires_at
+                {
+                    Counters::count(&self.counters.hits);
+                    return Ok(entry.value);
+                }
+                match flights.get(key) {
+                    Some(flight) => flight.clone(),
+                    None => {
+                        let flight = Arc::new(Flight::default());
+                        flights.insert(key.to_string(), flight.clone());
+                        break flight;
+                    }
+                }
+            };
+            // A dropped request is retried by whichever waiter gets there first
+            if let Some(result) = waiting.wait().await {
+                Counters::count(&self.counters.hits);
+                return result;
+            }
+        };
+
+        Counters::count(&self.counters.misses);
+        let mut guard = FlightGuard {
+            flights: &self.flights,
+            key,
+            flight,
+            landing: Landing::Abandoned,
+        };
+        let result = fetch().await;
+        if let Ok(task) = &result {
+            self.backend.put(key, CacheEntry {
+                value: task.clone(),
+                etag: None,
+                expires_at: expiry(self.clock.now(), self.ttl),
+            });
+        }
+        guard.landing = Landing::Done(Box::new(result.clone()));
+        result
+    }
+
+    /// Forget a task, e.g. after cancelling it or sending it a message.
+    pub fn invalidate(&self, task_id: &TaskId) {
+        self.backend.remove(task_id.as_str());
+    }
+
+    /// The hits and misses so far.
+    pub fn stats(&self) -> CacheStats {
+        self.counters.snapshot()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TaskState;
+    use crate::clock::MockClock;
+    use std::time::UNIX_EPOCH;
+
+    fn card_body(version: &str) -> String {
+        serde_json::json!({
+            "name": "Agent",
+            "description": "Test agent",
+            "version": version,
+            "protocolVersion": "0.2.5",
+            "url": "https://example.com/a2a",
+            "capabilities": {},
+            "defaultInputModes": ["text/plain"],
+            "defaultOutputModes": ["text/plain"],
+            "skills": []
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_card_cache_revalidates_by_etag() {
+        let clock = MockClock::new(UNIX_EPOCH);
+        let cache = CardCache::new(Duration::from_secs(60)).with_clock(clock.clone());
+        let url = "https://example.com/.well-known/agent-card.json";
+        let server_etag = std::cell::RefCell::new("\"v1\"".to_string());
+        let requests = std::cell::RefCell::new(Vec::new());
+        let fetch = |_: &str, if_none_match: Option<&str>| -> Result<CardResponse, String> {
+            requests.borrow_mut().push(if_none_match.map(str::to_string));
+            let etag = server_etag.borrow().clone();
+            if if_none_match == Some(etag.as_str()) {
+                return Ok(CardResponse::NotModified);
+            }
+            Ok(CardResponse::Modified {
+                body: card_body(etag.trim_matches('"')),
+                etag: Some(etag),
+            })
+        };
+
+        assert_eq!(cache.fetch(url, fetch).unwrap().version, "v1");
+        assert_eq!(cache.fetch(url, fetch).unwrap().version, "v1");
+        assert_eq!(requests.borrow().len(), 1);
+
+        // Expired and unchanged: revalidated without a download
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(cache.fetch(url, fetch).unwrap().version, "v1");
+        assert_eq!(cache.fetch(url, fetch).unwrap().version, "v1");
+
+        // Expired and changed: downloaded again
+        clock.advance(Duration::from_secs(60));
+        *server_etag.borrow_mut() = "\"v2\"".to_string();
+        assert_eq!(cache.fetch(url, fetch).unwrap().version, "v2");
+
+        // Invalidated: downloaded without a validator
+        cache.invalidate(url);
+        assert_eq!(cache.fetch(url, fetch).unwrap().version, "v2");
+
+        assert_eq!(*requests.borrow(), [
+            None,
+            Some("\"v1\"".to_string()),
+            Some("\"v1\"".to_string()),
+            None
+        ]);
+        assert_eq!(cache.stats(), CacheStats {
+            hits: 2,
+            misses: 4,
+            revalidated: 1
+        });
+
+        // Failures are not cached
+        let other = "https://example.com/card.json";
+        let error = cache.fetch(other, |_: &str, _: Option<&str>| Err("503 Service Unavailable")).unwrap_err();
+        assert!(matches!(error, A2AError::InvalidAgentResponse(_)));
+        assert!(cache.fetch(other, |_: &str, _: Option<&str>| Ok::<_, String>(CardResponse::NotModified)).is_err());
+        assert_eq!(cache.stats().misses, 6);
+    }
+
+    #[tokio::test]
+    async fn test_task_cache_shares_polls() {
+        let clock = MockClock::new(UNIX_EPOCH);
+        let cache = TaskCache::new(Duration::from_millis(500)).with_clock(clock.clone());
+        let task_id = TaskId::from("task-1");
+        let requests = AtomicU64::new(0);
+        let fetch = || async {
+            requests.fetch_add(1, Ordering::Relaxed);
+            let mut task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+            task.status.state = TaskState::Working;
+            Ok(task)
+        };
+
+        // Three callers polling the same task within the TTL send one request
+        for _ in 0..3 {
+            assert_eq!(cache.get_task(&task_id, fetch).await.unwrap().status.state, TaskState::Working);
+        }
+        assert_eq!(requests.load(Ordering::Relaxed), 1);
+
+        clock.advance(Duration::from_millis(500));
+        cache.get_task(&task_id, fetch).await.unwrap();
+        cache.invalidate(&task_id);
+        cache.get_task(&task_id, fetch).await.unwrap();
+        assert_eq!(requests.load(Ordering::Relaxed), 3);
+        assert_eq!(cache.stats(), CacheStats {
+            hits: 2,
+            misses: 3,
+            revalidated: 0
+        });
+
+        let error = cache.get_task(&TaskId::from("task-2"), || async {
+            Err(crate::store::task_not_found(&TaskId::from("task-2")))
+        }).await;
+        assert!(error.is_err());
+        assert!(cache.backend.get("task-2").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_task_cache_waits_for_the_request_in_flight() {
+        let cache = TaskCache::default();
+        let task_id = TaskId::from("task-1");
+        let (release, answered) = tokio::sync::oneshot::channel::<()>();
+        let fetch = || async {
+            answered.await.unwrap();
+            Ok(Task::minimal("task-1".to_string(), "ctx-1".to_string()))
+        };
+        let unused = || async { unreachable!("a second request was sent") };
+
+        let (first, second, third, ()) = tokio::join!(
+            cache.get_task(&task_id, fetch),
+            cache.get_task(&task_id, unused),
+            cache.get_task(&task_id, unused),
+            async {
+                tokio::task::yield_now().await;
+                release.send(()).unwrap();
+            }
+        );
+        assert_eq!(first.unwrap().id, "task-1");
+        assert_eq!(second.unwrap(), third.unwrap());
+        assert_eq!((cache.stats().hits, cache.stats().misses), (2, 1));
+        assert!(cache.flights.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_task_cache_retries_dropped_requests() {
+        use std::task::{Context, Waker};
+
+        let cache = TaskCache::default();
+        let task_id = TaskId::from("task-1");
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut dropped = Box::pin(cache.get_task(&task_id, std::future::pending));
+        assert!(dropped.as_mut().poll(&mut cx).is_pending());
+        let mut waiter = Box::pin(cache.get_task(&task_id, || async {
+            Ok(Task::minimal("task-1".to_string(), "ctx-1".to_string()))
+        }));
+        assert!(waiter.as_mut().poll(&mut cx).is_pending());
+
+        // The waiter sends the request itself once the first caller gives up
+        drop(dropped);
+        assert_eq!(waiter.await.unwrap().id, "task-1");
+        assert_eq!(cache.stats().misses, 2);
+    }
+
+    #[tokio::test]
+    async fn test_huge_ttls_do_not_overflow() {
+        let now = SystemTime::now();
+        assert!(expiry(now, Duration::MAX) > now + Duration::from_secs(50 * 365 * 24 * 60 * 60));
+        assert_eq!(expiry(now, Duration::from_secs(1)), now + Duration::from_secs(1));
+
+        let cache = TaskCache::new(Duration::MAX);
+        let task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+        let fetch = || async { Ok(task.clone()) };
+        let task_id = TaskId::from("task-1");
+        cache.get_task(&task_id, fetch).await.unwrap();
+        cache.get_task(&task_id, fetch).await.unwrap();
+        assert_eq!(cache.stats().hits, 1);
+    }
+}
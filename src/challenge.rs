@@ -0,0 +1,376 @@
+//! Answering tasks that need authorization.
+//!
+//! A task enters the `auth-required` state when the agent needs credentials
+//! it does not have, for example to act on the user's behalf in another
+//! service. The status message says what is needed, as text and usually as
+//! a data part or metadata object in one of a few conventional shapes. The
+//! client then:
+//!
+//! 1. extracts the challenge with [`AuthRequiredInfo::from_task`],
+//! 2. obtains the credentials out of band, e.g. by running the OAuth2 flow
+//!    at [`AuthRequiredInfo::authorization_url`] with the requested scopes,
+//! 3. answers with [`SendMessageParams::continuation`], which links the
+//!    message to the same task and context and names the message it
+//!    answers under [`IN_REPLY_TO_KEY`].
+//!
+//! The task then resumes, or asks again.
+//!
+//! Tasks in the `input-required` state work the same way without the
+//! credentials: [`Task::input_request`] is the agent's question and
+//! [`SendMessageParams::reply_to_task`] the answer.
+//!
+//! ```
+//! use a2a_rs::challenge::AuthRequiredInfo;
+//! use a2a_rs::{DataPart, Message, SendMessageParams, Task, TaskState};
+//!
+//! let mut task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+//! task.status.state = TaskState::AuthRequired;
+//! task.status.message = Some(Message::agent("msg-1".to_string(), [
+//!     a2a_rs::Part::from("Please sign in to your calendar"),
+//!     a2a_rs::Part::from(DataPart::from(serde_json::json!({
+//!         "type": "oauth2",
+//!         "authorizationUrl": "https://auth.example.com/authorize",
+//!         "scopes": ["calendar.read"]
+//!     }))),
+//! ]));
+//!
+//! let info = AuthRequiredInfo::from_task(&task).unwrap();
+//! assert_eq!(info.authorization_url.as_deref(), Some("https://auth.example.com/authorize"));
+//! assert_eq!(info.scopes, ["calendar.read"]);
+//!
+//! // ... obtain an authorization code, then answer
+//! let params = SendMessageParams::continuation(&task, "msg-2".to_string(), ["signed in"]);
+//! assert_eq!(params.message.task_id.as_deref(), Some("task-1"));
+//! assert_eq!(params.message.context_id.as_deref(), Some("ctx-1"));
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::metadata::{get_meta, set_meta};
+use crate::{ExtraFields, Message, MessageRole, Part, SendMessageParams, Task, TaskState};
+use serde_json::{Map, Value};
+
+/// The message metadata key under which a continuation names the agent
+/// message it answers.
+pub const IN_REPLY_TO_KEY: &str = "inReplyTo";
+
+/// Message metadata keys conventionally holding an authorization challenge.
+const METADATA_KEYS: &[&str] = &["auth", "authRequired", "authorization"];
+
+/// Object keys that mark a data part as an authorization challenge.
+const CHALLENGE_KEYS: &[&str] = &[
+    "authorizationUrl",
+    "authorization_url",
+    "authUrl",
+    "auth_url",
+    "tokenUrl",
+    "token_url",
+    "scopes",
+    "scope",
+];
+
+/// What a task in the `auth-required` state asks for.
+///
+/// Every field but the IDs is optional, since agents describe their needs
+/// in different ways; [`AuthRequiredInfo::details`] keeps the raw challenge
+/// for anything not covered here.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuthRequiredInfo {
+    /// The task waiting for authorization.
+    pub task_id: String,
+    /// The context of the task.
+    pub context_id: String,
+    /// The ID of the agent message asking for authorization.
+    pub message_id: Option<String>,
+    /// The text of that message.
+    pub description: Option<String>,
+    /// The kind of credentials, e.g. `oauth2` or `bearer`.
+    pub scheme: Option<String>,
+    /// Where the user authorizes the agent, for OAuth2 style flows.
+    pub authorization_url: Option<String>,
+    /// Where an authorization code is exchanged for a token.
+    pub token_url: Option<String>,
+    /// The scopes requested.
+    pub scopes: Vec<String>,
+    /// The challenge object as the agent sent it.
+    pub details: Option<Value>,
+}
+
+impl AuthRequiredInfo {
+    /// Extract the challenge of a task in the `auth-required` state.
+    ///
+    /// The challenge is read from the status message, or if there is none,
+    /// from the last agent message in the history. It is taken from the
+    /// first data part with challenge fields such as `authorizationUrl` or
+    /// `scopes`, or else from the message metadata under `auth`,
+    /// `authRequired` or `authorization`. Both camelCase and snake_case
+    /// field names are understood, and scopes may be a list or a
+    /// space-separated string.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task.
+    ///
+    /// # Returns
+    ///
+    /// The challenge, or `None` if the task is in another state. A task
+    /// whose message carries no structured challenge still yields the IDs
+    /// and description.
+    pub fn from_task(task: &Task) -> Option<Self> {
+        if task.status.state != TaskState::AuthRequired {
+            return None;
+        }
+
+        let mut info = AuthRequiredInfo {
+            task_id: task.id.clone(),
+            context_id: task.context_id.clone(),
+            ..AuthRequiredInfo::default()
+        };
+        let Some(message) = prompting_message(task) else {
+            return Some(info);
+        };
+        info.message_id = Some(message.message_id.clone());
+
+        let text: Vec<&str> = message
+            .parts
+            .iter()
+            .filter_map(|part| match part {
+                Part::Text(text) => Some(text.text.trim()),
+                _ => None,
+            })
+            .filter(|text| !text.is_empty())
+            .collect();
+        info.description = (!text.is_empty()).then(|| text.join("\n"));
+
+        let challenge = message
+            .parts
+            .iter()
+            .find_map(|part| match part {
+                Part::Data(data) => data
+                    .data
+                    .as_object()
+                    .filter(|object| CHALLENGE_KEYS.iter().any(|key| object.contains_key(*key))),
+                _ => None,
+            })
+            .or_else(|| {
+                METADATA_KEYS
+                    .iter()
+                    .find_map(|key| get_meta(&message.metadata, key).and_then(Value::as_object))
+            });
+        if let Some(challenge) = challenge {
+            info.scheme = string_field(challenge, &["scheme", "type", "securityScheme"]);
+            info.authorization_url =
+                string_field(challenge, &["authorizationUrl", "authorization_url", "authUrl", "auth_url"]);
+            info.token_url = string_field(challenge, &["tokenUrl", "token_url"]);
+            info.scopes = scopes(challenge);
+            info.details = Some(Value::Object(challenge.clone()));
+        }
+        Some(info)
+    }
+}
+
+/// The agent message that put the task into its current state.
+fn prompting_message(task: &Task) -> Option<&Message> {
+    task.status.message.as_ref().or_else(|| {
+        task.history
+            .as_deref()?
+            .iter()
+            .rev()
+            .find(|message| message.role == MessageRole::Agent)
+    })
+}
+
+fn string_field(object: &Map<String, Value>, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|key| object.get(*key).and_then(Value::as_str))
+        .map(str::to_string)
+}
+
+fn scopes(object: &Map<String, Value>) -> Vec<String> {
+    match object.get("scopes").or_else(|| object.get("scope")) {
+        Some(Value::Array(scopes)) => scopes.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+        Some(Value::String(scopes)) => scopes.split_whitespace().map(str::to_string).collect(),
+        _ => Vec::new(),
+    }
+}
+
+impl Task {
+    /// The agent's question to the user, if the task is waiting for input.
+    ///
+    /// # Returns
+    ///
+    /// The status message, or `None` if the task is in another state or
+    /// has no status message.
+    pub fn input_request(&self) -> Option<&Message> {
+        match self.status.state {
+            TaskState::InputRequired => self.status.message.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl SendMessageParams {
+    /// Build the answer to a task in the `input-required` state.
+    ///
+    /// This is [`SendMessageParams::continuation`] under the name of the
+    /// input-required flow.
+    pub fn reply_to_task<P: Into<Part>>(task: &Task, message_id: String, parts: impl IntoIterator<Item = P>) -> Self {
+        Self::continuation(task, message_id, parts)
+    }
+
+    /// Build a user message continuing a task, such as the answer to an
+    /// `auth-required` or `input-required` status.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task to continue.
+    /// * `message_id` - The ID of the new message.
+    /// * `parts` - The message content, e.g. a list of strings.
+    ///
+    /// # Returns
+    ///
+    /// Parameters for a message with the task's ID and context ID, whose
+    /// metadata names the agent message it answers under
+    /// [`IN_REPLY_TO_KEY`] if the task has one.
+    pub fn continuation<P: Into<Part>>(task: &Task, message_id: String, parts: impl IntoIterator<Item = P>) -> Self {
+        let mut message = Message::user(message_id, parts);
+        message.task_id = Some(task.id.clone());
+        message.context_id = Some(task.context_id.clone());
+        if let Some(prompt) = prompting_message(task) {
+            set_meta(&mut message.metadata, IN_REPLY_TO_KEY, Value::String(prompt.message_id.clone()));
+        }
+        SendMessageParams {
+            message,
+            configuration: None,
+            metadata: None,
+            extra: ExtraFields::default(),
+        }
+    }
+
+    /// Continue a conversation by echoing its context ID.
+    ///
+    /// The server assigns a context ID to the first message of a
+    /// conversation; clients send it back with every later message. A
+    /// context ID already set on the message is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `existing` - The context ID of the conversation, or `None` for a new
+    ///   conversation, which leaves the message without one.
+    pub fn ensure_context(&mut self, existing: Option<&str>) {
+        if self.message.context_id.is_none() {
+            self.message.context_id = existing.map(str::to_string);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn auth_required_task() -> Task {
+        serde_json::from_value(json!({
+            "kind": "task",
+            "id": "task-7",
+            "contextId": "ctx-3",
+            "status": {
+                "state": "auth-required",
+                "message": {
+                    "kind": "message",
+                    "messageId": "msg-agent-2",
+                    "role": "agent",
+                    "parts": [
+                        {"kind": "text", "text": "I need access to your Google Calendar."},
+                        {"kind": "data", "data": {
+                            "type": "oauth2",
+                            "authorization_url": "https://accounts.example.com/o/oauth2/auth?client_id=agent",
+                            "token_url": "https://accounts.example.com/o/oauth2/token",
+                            "scope": "calendar.readonly calendar.events"
+                        }}
+                    ]
+                }
+            },
+            "history": [
+                {"kind": "message", "messageId": "msg-user-1", "role": "user",
+                 "parts": [{"kind": "text", "text": "What's on my calendar?"}]}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_oauth_data_part() {
+        let task = auth_required_task();
+        let info = AuthRequiredInfo::from_task(&task).unwrap();
+        assert_eq!(info.task_id, "task-7");
+        assert_eq!(info.context_id, "ctx-3");
+        assert_eq!(info.message_id.as_deref(), Some("msg-agent-2"));
+        assert_eq!(info.description.as_deref(), Some("I need access to your Google Calendar."));
+        assert_eq!(info.scheme.as_deref(), Some("oauth2"));
+        assert_eq!(
+            info.authorization_url.as_deref(),
+            Some("https://accounts.example.com/o/oauth2/auth?client_id=agent")
+        );
+        assert_eq!(info.token_url.as_deref(), Some("https://accounts.example.com/o/oauth2/token"));
+        assert_eq!(info.scopes, ["calendar.readonly", "calendar.events"]);
+        assert_eq!(info.details.unwrap()["type"], "oauth2");
+
+        let mut working = task.clone();
+        working.status.state = TaskState::Working;
+        assert_eq!(AuthRequiredInfo::from_task(&working), None);
+    }
+
+    #[test]
+    fn test_metadata_and_history_shapes() {
+        let mut task = auth_required_task();
+        let mut message = task.status.message.take().unwrap();
+        message.parts.truncate(1);
+        message.metadata = Some(json!({"authRequired": {"scheme": "bearer", "scopes": ["a", "b"]}}));
+        task.history.as_mut().unwrap().push(message);
+
+        let info = AuthRequiredInfo::from_task(&task).unwrap();
+        assert_eq!(info.message_id.as_deref(), Some("msg-agent-2"));
+        assert_eq!(info.scheme.as_deref(), Some("bearer"));
+        assert_eq!(info.scopes, ["a", "b"]);
+        assert_eq!(info.authorization_url, None);
+
+        // Nothing structured: still an auth-required task
+        task.history = None;
+        let info = AuthRequiredInfo::from_task(&task).unwrap();
+        assert_eq!(info.message_id, None);
+        assert_eq!(info.details, None);
+        assert_eq!(info.task_id, "task-7");
+    }
+
+    #[test]
+    fn test_continuation() {
+        let task = auth_required_task();
+        let params = SendMessageParams::continuation(&task, "msg-user-3".to_string(), ["Done, I signed in."]);
+        let json = serde_json::to_value(&params).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "message": {
+                    "kind": "message",
+                    "messageId": "msg-user-3",
+                    "role": "user",
+                    "parts": [{"kind": "text", "text": "Done, I signed in."}],
+                    "taskId": "task-7",
+                    "contextId": "ctx-3",
+                    "metadata": {"inReplyTo": "msg-agent-2"}
+                }
+            })
+        );
+
+        assert_eq!(task.input_request(), None);
+        let mut asking = task.clone();
+        asking.status.state = TaskState::InputRequired;
+        assert_eq!(asking.input_request().unwrap().message_id, "msg-agent-2");
+        assert_eq!(SendMessageParams::reply_to_task(&asking, "msg-user-3".to_string(), ["Done, I signed in."]), params);
+
+        let fresh = Task::minimal("task-8".to_string(), "ctx-3".to_string());
+        let params = SendMessageParams::continuation(&fresh, "msg-1".to_string(), ["hi"]);
+        assert_eq!(params.message.metadata, None);
+    }
+}
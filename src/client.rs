@@ -0,0 +1,812 @@
+//! Transport-independent client conversations.
+//!
+//! [`ClientConversation`] is the protocol logic of a streaming client,
+//! without any I/O: it produces the bytes of each request and consumes the
+//! bytes of the `text/event-stream` response, reporting what happened as
+//! [`Effect`]s. Moving the bytes is left to the caller, so the same state
+//! machine drives HTTP clients, custom message buses and scripted tests.
+//!
+//! A conversation follows one task at a time. Messages sent while the agent
+//! waits for input continue that task; messages sent after it finished
+//! start a new task in the same context.
+//!
+//! Clients that do their own I/O report failures as [`ClientError`], which
+//! tells errors answered by the agent apart from transport failures,
+//! timeouts and undecodable responses. Answers to `message/send` read
+//! through the accessors of [`SendMessageResult`](crate::SendMessageResult),
+//! whether the agent created a task or only replied with a message.
+
+mod error;
+
+pub use error::{ClientError, TransportErrorKind};
+
+use crate::extensions::{EXTENSIONS_HEADER, parse_extensions_headers, render_extensions_header};
+use crate::ids::TaskId;
+use crate::response::{IdMatching, ResponseValidator};
+use crate::sse::{SseDecoder, SseLimits};
+use crate::{
+    A2AError, ExtraFields, InvalidAgentResponseError, InvalidRequestError, JSONRPCErrorResponse, JsonRpcId, Message,
+    MessageRole, RequestMethod, SendMessageParams, SendStreamingMessageRequest, StreamingMessageResult, Task,
+    TaskArtifactUpdateEvent, TaskResubscriptionParams, TaskResubscriptionRequest, TaskState, TaskStatusUpdateEvent,
+};
+use serde::Serialize;
+
+/// Something the caller should know about or act on.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum Effect {
+    /// The agent created a task for the request.
+    TaskCreated(Task),
+    /// The task status changed.
+    StatusUpdate(TaskStatusUpdateEvent),
+    /// An artifact was added or extended.
+    ArtifactUpdate(TaskArtifactUpdateEvent),
+    /// The agent answered with a message instead of a task.
+    Message(Message),
+    /// The agent needs more input; answer with [`ClientConversation::start_send`].
+    InputRequired(Message),
+    /// The task reached a terminal state, which need not be `completed`.
+    Completed(Task),
+    /// The stream ended before the task finished; call
+    /// [`ClientConversation::resubscribe`] to continue following it.
+    NeedResubscribe(TaskId),
+}
+
+/// Where a conversation is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversationState {
+    /// Nothing has been sent, or the last request ended without a task.
+    Idle,
+    /// A response stream is open.
+    Streaming,
+    /// The agent is waiting for the user to answer.
+    InputRequired,
+    /// The stream was lost while the task was still running.
+    Disconnected,
+    /// The task reached a terminal state.
+    Finished,
+}
+
+/// A sans-IO streaming client conversation with one agent.
+#[derive(Debug, Clone)]
+pub struct ClientConversation {
+    state: ConversationState,
+    next_request_id: u64,
+    pending_request: Option<String>,
+    validator: ResponseValidator,
+    id_matching: IdMatching,
+    decoder: SseDecoder,
+    task: Option<Task>,
+    context_id: Option<String>,
+    requested_extensions: Vec<String>,
+    activated_extensions: Vec<String>,
+}
+
+impl Default for ClientConversation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClientConversation {
+    /// Create a conversation with no task.
+    pub fn new() -> Self {
+        Self {
+            state: ConversationState::Idle,
+            next_request_id: 1,
+            pending_request: None,
+            validator: ResponseValidator::new(RequestMethod::MessageStream),
+            id_matching: IdMatching::Strict,
+            decoder: SseDecoder::new(),
+            task: None,
+            context_id: None,
+            requested_extensions: Vec::new(),
+            activated_extensions: Vec::new(),
+        }
+    }
+
+    /// Ask the agent to activate extensions on every request.
+    ///
+    /// Send [`ClientConversation::extensions_header`] with each request and
+    /// pass the response headers to
+    /// [`ClientConversation::handle_response_headers`].
+    ///
+    /// # Arguments
+    ///
+    /// * `uris` - The extension URIs; duplicates are dropped.
+    pub fn request_extensions<S: Into<String>>(mut self, uris: impl IntoIterator<Item = S>) -> Self {
+        for uri in uris {
+            let uri = uri.into();
+            if !self.requested_extensions.contains(&uri) {
+                self.requested_extensions.push(uri);
+            }
+        }
+        self
+    }
+
+    /// Bound the size of the stream events the conversation accepts.
+    pub fn with_sse_limits(mut self, limits: SseLimits) -> Self {
+        self.decoder = SseDecoder::with_limits(limits);
+        self
+    }
+
+    /// Accept stream events whose `id` is a number where the request sent
+    /// the same digits as a string.
+    pub fn lenient_ids(mut self) -> Self {
+        self.id_matching = IdMatching::Lenient;
+        self
+    }
+
+    /// Accept stream events whatever their `id`, for agents known not to
+    /// echo the request id.
+    pub fn allow_id_mismatch(mut self) -> Self {
+        self.id_matching = IdMatching::Ignore;
+        self
+    }
+
+    /// The value of the [`EXTENSIONS_HEADER`] to send with each request, or
+    /// `None` if no extensions are requested.
+    pub fn extensions_header(&self) -> Option<String> {
+        (!self.requested_extensions.is_empty()).then(|| render_extensions_header(&self.requested_extensions))
+    }
+
+    /// Record the extensions the agent activated for the last request.
+    ///
+    /// # Arguments
+    ///
+    /// * `headers` - The response header names and values; the
+    ///   [`EXTENSIONS_HEADER`] is matched case-insensitively and may be
+    ///   repeated.
+    pub fn handle_response_headers<'a>(&mut self, headers: impl IntoIterator<Item = (&'a str, &'a str)>) {
+        self.activated_extensions = parse_extensions_headers(
+            headers
+                .into_iter()
+                .filter(|(name, _)| name.eq_ignore_ascii_case(EXTENSIONS_HEADER))
+                .map(|(_, value)| value),
+        );
+    }
+
+    /// The extensions the agent activated for the last request.
+    pub fn activated_extensions(&self) -> &[String] {
+        &self.activated_extensions
+    }
+
+    /// Where the conversation is.
+    pub fn state(&self) -> ConversationState {
+        self.state
+    }
+
+    /// The task as last reported by the agent, with all updates applied.
+    pub fn task(&self) -> Option<&Task> {
+        self.task.as_ref()
+    }
+
+    /// The context shared by every task in the conversation.
+    pub fn context_id(&self) -> Option<&str> {
+        self.context_id.as_deref()
+    }
+
+    /// The last SSE event ID received, for a `Last-Event-ID` header.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.decoder.last_event_id()
+    }
+
+    /// Start a `message/stream` request.
+    ///
+    /// The message is bound to the conversation: it continues the current
+    /// task unless that task has finished, and always carries the
+    /// conversation's context ID once one is known.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The message and its configuration.
+    ///
+    /// # Returns
+    ///
+    /// The JSON-RPC request to transmit, or an `InvalidRequest` error if a
+    /// response stream is still open.
+    pub fn start_send(&mut self, mut params: SendMessageParams) -> Result<Vec<u8>, A2AError> {
+        if self.state == ConversationState::Streaming {
+            return Err(invalid_request("A response stream is still open".to_string()));
+        }
+
+        if self.state == ConversationState::Finished {
+            self.task = None;
+        }
+        let message = &mut params.message;
+        if let Some(task) = &self.task {
+            message.task_id.get_or_insert_with(|| task.id.clone());
+        }
+        if let Some(context_id) = &self.context_id {
+            message.context_id.get_or_insert_with(|| context_id.clone());
+        }
+
+        let id = self.begin_request(RequestMethod::MessageStream);
+        encode(&SendStreamingMessageRequest {
+            method: RequestMethod::MessageStream,
+            params,
+            id,
+            jsonrpc: "2.0".to_string(),
+            extra: ExtraFields::default(),
+        })
+    }
+
+    /// Start a `tasks/resubscribe` request for the current task.
+    ///
+    /// # Returns
+    ///
+    /// The JSON-RPC request to transmit, or an `InvalidRequest` error unless
+    /// the conversation is [`ConversationState::Disconnected`].
+    pub fn resubscribe(&mut self) -> Result<Vec<u8>, A2AError> {
+        let task_id = match (&self.state, &self.task) {
+            (ConversationState::Disconnected, Some(task)) => task.id.clone(),
+            _ => return Err(invalid_request("There is no lost stream to resubscribe to".to_string())),
+        };
+
+        let id = self.begin_request(RequestMethod::TasksResubscribe);
+        encode(&TaskResubscriptionRequest {
+            method: RequestMethod::TasksResubscribe,
+            params: TaskResubscriptionParams {
+                task_id,
+                extra: ExtraFields::default(),
+            },
+            id,
+            jsonrpc: "2.0".to_string(),
+            extra: ExtraFields::default(),
+        })
+    }
+
+    /// Consume bytes of the open response stream.
+    ///
+    /// Events after the one that ends the stream are ignored. On error the
+    /// stream is abandoned, but updates from earlier events in the same
+    /// chunk remain applied to [`ClientConversation::task`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The next chunk of the `text/event-stream` body, split
+    ///   anywhere.
+    ///
+    /// # Returns
+    ///
+    /// The effects of the events completed by this chunk, or the error the
+    /// agent responded with, or `InvalidAgentResponse` if an event is
+    /// malformed, exceeds the [`SseLimits`] or is inconsistent with the
+    /// task.
+    pub fn handle_response(&mut self, bytes: &[u8]) -> Result<Vec<Effect>, A2AError> {
+        if self.state != ConversationState::Streaming {
+            return Err(invalid_request("No response stream is open".to_string()));
+        }
+
+        let mut effects = Vec::new();
+        let mut events = Vec::new();
+        self.decoder.feed_into(bytes, &mut events);
+        for event in events {
+            let handled = match event {
+                Ok(event) => self.handle_event(&event.data, &mut effects),
+                Err(overflow) => Err(invalid_response(format!("Stream event discarded: {}", overflow))),
+            };
+            if let Err(e) = handled {
+                self.end_stream(if self.task.is_some() {
+                    ConversationState::Disconnected
+                } else {
+                    ConversationState::Idle
+                });
+                return Err(e);
+            }
+            if self.state != ConversationState::Streaming {
+                break;
+            }
+        }
+        Ok(effects)
+    }
+
+    /// Report that the transport closed the response stream.
+    ///
+    /// # Returns
+    ///
+    /// `NeedResubscribe` if the task was still running, no effects if the
+    /// stream had already ended normally, or `InvalidAgentResponse` if the
+    /// stream closed before the agent answered at all.
+    pub fn handle_stream_end(&mut self) -> Result<Vec<Effect>, A2AError> {
+        if self.state != ConversationState::Streaming {
+            return Ok(Vec::new());
+        }
+
+        match &self.task {
+            Some(task) => {
+                let task_id = task.typed_id();
+                self.end_stream(ConversationState::Disconnected);
+                Ok(vec![Effect::NeedResubscribe(task_id)])
+            }
+            None => {
+                self.end_stream(ConversationState::Idle);
+                Err(invalid_response("Stream ended before the agent responded".to_string()))
+            }
+        }
+    }
+
+    fn begin_request(&mut self, method: RequestMethod) -> String {
+        let id = self.next_request_id.to_string();
+        self.next_request_id += 1;
+        self.pending_request = Some(id.clone());
+        self.validator = match &self.task {
+            Some(task) => ResponseValidator::for_task(method, task.id.clone()),
+            None => ResponseValidator::new(method),
+        };
+        self.decoder.reset();
+        self.state = ConversationState::Streaming;
+        id
+    }
+
+    fn end_stream(&mut self, state: ConversationState) {
+        self.pending_request = None;
+        self.state = state;
+    }
+
+    fn handle_event(&mut self, data: &str, effects: &mut Vec<Effect>) -> Result<(), A2AError> {
+        let envelope: serde_json::Value = serde_json::from_str(data)
+            .map_err(|e| invalid_response(format!("Stream event is not valid JSON: {}", e)))?;
+
+        let expected_id = JsonRpcId::String(self.pending_request.clone().unwrap_or_default());
+        let id = envelope.get("id").unwrap_or(&serde_json::Value::Null);
+        ResponseValidator::check_id_with(&expected_id, id, self.id_matching)?;
+
+        if envelope.get("error").is_some() {
+            let response: JSONRPCErrorResponse = serde_json::from_value(envelope)
+                .map_err(|e| invalid_response(format!("Malformed error response: {}", e)))?;
+            return Err(response.error);
+        }
+
+        let result = envelope
+            .get("result")
+            .cloned()
+            .ok_or_else(|| invalid_response("Stream event has neither result nor error".to_string()))?;
+        self.validator.validate(&result)?;
+        let result: StreamingMessageResult = serde_json::from_value(result)
+            .map_err(|e| invalid_response(format!("Unrecognized stream event: {}", e)))?;
+
+        match result {
+            StreamingMessageResult::Message(message) => {
+                self.end_stream(ConversationState::Idle);
+                effects.push(Effect::Message(message));
+            }
+            StreamingMessageResult::Task(task) => {
+                if self.task.is_none() {
+                    effects.push(Effect::TaskCreated(task.clone()));
+                }
+                self.context_id = Some(task.context_id.clone());
+                self.task = Some(task);
+                self.settle(effects)?;
+            }
+            StreamingMessageResult::TaskStatusUpdate(event) => {
+                event.validate().map_err(invalid_response)?;
+                let task = match &mut self.task {
+                    Some(task) => task,
+                    None => {
+                        // Agents may announce a task with its first status update
+                        let task = Task::minimal(event.task_id.clone(), event.context_id.clone());
+                        effects.push(Effect::TaskCreated(task.clone()));
+                        self.context_id = Some(task.context_id.clone());
+                        self.task.insert(task)
+                    }
+                };
+                task.set_status_checked(event.status.clone()).map_err(invalid_response)?;
+                effects.push(Effect::StatusUpdate(event));
+                self.settle(effects)?;
+            }
+            StreamingMessageResult::TaskArtifactUpdate(event) => {
+                event.validate().map_err(invalid_response)?;
+                let task = self
+                    .task
+                    .as_mut()
+                    .ok_or_else(|| invalid_response("Artifact update before any task".to_string()))?;
+                let artifacts = task.artifacts.get_or_insert_with(Vec::new);
+                match artifacts.iter_mut().find(|a| a.artifact_id == event.artifact.artifact_id) {
+                    Some(existing) if event.append == Some(true) => {
+                        existing.parts.extend(event.artifact.parts.iter().cloned())
+                    }
+                    Some(existing) => *existing = event.artifact.clone(),
+                    None => artifacts.push(event.artifact.clone()),
+                }
+                effects.push(Effect::ArtifactUpdate(event));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// End the stream if the task finished or needs input.
+    fn settle(&mut self, effects: &mut Vec<Effect>) -> Result<(), A2AError> {
+        let Some(task) = &self.task else {
+            return Ok(());
+        };
+
+        if task.status.state.is_terminal() {
+            effects.push(Effect::Completed(task.clone()));
+            self.end_stream(ConversationState::Finished);
+        } else if task.status.state == TaskState::InputRequired {
+            let question = task
+                .status
+                .message
+                .clone()
+                .or_else(|| {
+                    task.history
+                        .iter()
+                        .flatten()
+                        .rev()
+                        .find(|message| message.role == MessageRole::Agent)
+                        .cloned()
+                })
+                .ok_or_else(|| invalid_response("Input required without a message from the agent".to_string()))?;
+            effects.push(Effect::InputRequired(question));
+            self.end_stream(ConversationState::InputRequired);
+        }
+
+        Ok(())
+    }
+}
+
+fn encode<T: Serialize>(request: &T) -> Result<Vec<u8>, A2AError> {
+    serde_json::to_vec(request).map_err(|e| invalid_request(format!("Failed to serialize request: {}", e)))
+}
+
+fn invalid_request(message: String) -> A2AError {
+    A2AError::InvalidRequest(Box::new(InvalidRequestError {
+        code: -32600,
+        message,
+        data: None,
+    }))
+}
+
+fn invalid_response(message: String) -> A2AError {
+    A2AError::InvalidAgentResponse(Box::new(InvalidAgentResponseError {
+        code: -32006,
+        message,
+        data: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extensions::RequestContext;
+    use crate::{ArtifactUpdateSequenceBuilder, Part, TaskStatus, TextPart};
+    use serde_json::{Value, json};
+
+    fn params(message_id: &str, text: &str) -> SendMessageParams {
+        SendMessageParams {
+            message: Message::user(message_id.to_string(), [text]),
+            configuration: None,
+            metadata: None,
+            extra: ExtraFields::default(),
+        }
+    }
+
+    fn frame(id: &str, result: Value) -> Vec<u8> {
+        format!("data: {}\n\n", json!({"jsonrpc": "2.0", "id": id, "result": result})).into_bytes()
+    }
+
+    fn status(state: &str, message: Option<Value>, is_final: bool) -> Value {
+        let mut status = json!({"state": state});
+        if let Some(message) = message {
+            status["message"] = message;
+        }
+        json!({
+            "kind": "status-update",
+            "taskId": "task-1",
+            "contextId": "ctx-1",
+            "status": status,
+            "final": is_final
+        })
+    }
+
+    fn agent_text(message_id: &str, text: &str) -> Value {
+        json!({"kind": "message", "messageId": message_id, "role": "agent", "parts": [{"kind": "text", "text": text}]})
+    }
+
+    fn sent(bytes: &[u8]) -> Value {
+        serde_json::from_slice(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_send_stream_input_required_continuation() {
+        let mut conversation = ClientConversation::new();
+
+        // First turn: the agent creates a task, works on it and asks a question
+        let request = sent(&conversation.start_send(params("msg-1", "Book a flight")).unwrap());
+        assert_eq!(request["method"], "message/stream");
+        assert_eq!(request["id"], "1");
+        assert!(request["params"]["message"].get("taskId").is_none());
+
+        let mut body = b": connected\n\n".to_vec();
+        body.extend(frame(
+            "1",
+            json!({"kind": "task", "id": "task-1", "contextId": "ctx-1", "status": {"state": "submitted"}}),
+        ));
+        body.extend(frame("1", status("working", None, false)));
+        body.extend(frame(
+            "1",
+            status("input-required", Some(agent_text("msg-2", "Which date?")), true),
+        ));
+
+        // Deliver the body in awkward pieces
+        let mut effects = Vec::new();
+        for chunk in body.chunks(7) {
+            effects.extend(conversation.handle_response(chunk).unwrap());
+        }
+        assert!(matches!(&effects[0], Effect::TaskCreated(task) if task.id == "task-1"));
+        assert!(matches!(&effects[1], Effect::StatusUpdate(e) if e.status.state == TaskState::Working));
+        assert!(matches!(&effects[2], Effect::StatusUpdate(e) if e.status.state == TaskState::InputRequired));
+        let Effect::InputRequired(question) = &effects[3] else {
+            panic!("expected InputRequired, got {:?}", effects[3]);
+        };
+        assert_eq!(question.parts, [crate::Part::Text(TextPart {
+                text: "Which date?".to_string(),
+                metadata: None
+            })]);
+        assert_eq!(effects.len(), 4);
+        assert_eq!(conversation.state(), ConversationState::InputRequired);
+        assert!(conversation.handle_stream_end().unwrap().is_empty());
+
+        // Second turn: the answer continues the same task, which then completes
+        let request = sent(&conversation.start_send(params("msg-3", "Tomorrow")).unwrap());
+        assert_eq!(request["id"], "2");
+        assert_eq!(request["params"]["message"]["taskId"], "task-1");
+        assert_eq!(request["params"]["message"]["contextId"], "ctx-1");
+
+        let mut body = frame("2", status("working", None, false));
+        body.extend(frame(
+            "2",
+            json!({
+                "kind": "artifact-update",
+                "taskId": "task-1",
+                "contextId": "ctx-1",
+                "artifact": {"artifactId": "ticket", "parts": [{"kind": "text", "text": "LH 400"}]}
+            }),
+        ));
+        body.extend(frame("2", status("completed", None, true)));
+        let effects = conversation.handle_response(&body).unwrap();
+        assert!(matches!(&effects[1], Effect::ArtifactUpdate(_)));
+        let Some(Effect::Completed(task)) = effects.last() else {
+            panic!("expected Completed, got {:?}", effects.last());
+        };
+        assert_eq!(task.status.state, TaskState::Completed);
+        assert_eq!(task.artifacts.as_ref().unwrap()[0].artifact_id, "ticket");
+        assert_eq!(conversation.state(), ConversationState::Finished);
+
+        // A new message after completion starts a new task in the same context
+        let request = sent(&conversation.start_send(params("msg-4", "Thanks")).unwrap());
+        assert!(request["params"]["message"].get("taskId").is_none());
+        assert_eq!(request["params"]["message"]["contextId"], "ctx-1");
+    }
+
+    #[test]
+    fn test_lost_stream_resubscribes() {
+        let mut conversation = ClientConversation::new();
+        conversation.start_send(params("msg-1", "Summarize")).unwrap();
+        assert!(conversation.resubscribe().is_err());
+
+        let effects = conversation.handle_response(&frame("1", status("working", None, false))).unwrap();
+        assert!(matches!(&effects[0], Effect::TaskCreated(task) if task.status == TaskStatus::default()));
+        // The connection drops mid-event
+        conversation.handle_response(b"data: {\"jsonrpc\"").unwrap();
+        assert_eq!(conversation.handle_stream_end().unwrap(), [Effect::NeedResubscribe(TaskId::from("task-1"))]);
+        assert!(conversation.start_send(params("msg-2", "Hello?")).is_ok());
+
+        let mut conversation = ClientConversation::new();
+        conversation.start_send(params("msg-1", "Summarize")).unwrap();
+        conversation.handle_response(&frame("1", status("working", None, false))).unwrap();
+        conversation.handle_stream_end().unwrap();
+        let request = sent(&conversation.resubscribe().unwrap());
+        assert_eq!(request["method"], "tasks/resubscribe");
+        assert_eq!(request["params"]["taskId"], "task-1");
+
+        let effects = conversation.handle_response(&frame("2", status("failed", None, true))).unwrap();
+        assert!(matches!(&effects[1], Effect::Completed(task) if task.status.state == TaskState::Failed));
+    }
+
+    #[test]
+    fn test_rejects_inconsistent_responses() {
+        let mut conversation = ClientConversation::new();
+        assert!(matches!(conversation.handle_response(b"data: {}\n\n"), Err(A2AError::InvalidRequest(_))));
+
+        // Responses must answer the pending request
+        conversation.start_send(params("msg-1", "Hi")).unwrap();
+        let error = conversation.handle_response(&frame("9", status("working", None, false))).unwrap_err();
+        assert!(matches!(error, A2AError::InvalidAgentResponse(_)));
+        assert_eq!(conversation.state(), ConversationState::Idle);
+
+        // Terminal states cannot be left
+        conversation.start_send(params("msg-2", "Hi")).unwrap();
+        let mut body = frame("2", status("canceled", None, false));
+        body.extend(frame("2", status("working", None, false)));
+        let effects = conversation.handle_response(&body).unwrap();
+        assert!(matches!(effects.last(), Some(Effect::Completed(_))));
+
+        let mut conversation = ClientConversation::new();
+        conversation.start_send(params("msg-1", "Hi")).unwrap();
+        conversation.handle_response(&frame("1", status("completed", None, false))).unwrap();
+        conversation.start_send(params("msg-2", "Again")).unwrap();
+        conversation.handle_response(&frame("2", status("submitted", None, false))).unwrap();
+        let mut skip = frame("2", status("working", None, false));
+        skip.extend(frame("2", status("submitted", None, false)));
+        assert!(matches!(conversation.handle_response(&skip), Err(A2AError::InvalidAgentResponse(_))));
+        assert_eq!(conversation.state(), ConversationState::Disconnected);
+
+        // Streams cannot switch tasks or end while the task is running
+        let mut conversation = ClientConversation::new();
+        conversation.start_send(params("msg-1", "Hi")).unwrap();
+        let mut other = status("working", None, false);
+        other["taskId"] = json!("task-2");
+        let mut body = frame("1", status("working", None, false));
+        body.extend(frame("1", other));
+        let error = conversation.handle_response(&body).unwrap_err();
+        assert!(matches!(&error, A2AError::InvalidAgentResponse(e) if e.data.as_ref().unwrap()["check"] == "taskId"));
+        let mut conversation = ClientConversation::new();
+        conversation.start_send(params("msg-1", "Hi")).unwrap();
+        let error = conversation.handle_response(&frame("1", status("working", None, true))).unwrap_err();
+        assert!(matches!(&error, A2AError::InvalidAgentResponse(e) if e.data.as_ref().unwrap()["check"] == "finalState"));
+
+        // Errors from the agent are passed through
+        let mut conversation = ClientConversation::new();
+        conversation.start_send(params("msg-1", "Hi")).unwrap();
+        let error = json!({"jsonrpc": "2.0", "id": "1", "error": {"code": -32001, "message": "Task not found"}});
+        let error = conversation.handle_response(format!("data: {}\n\n", error).as_bytes()).unwrap_err();
+        assert!(matches!(error, A2AError::TaskNotFound(_)));
+    }
+
+    #[test]
+    fn test_rejected_task_carries_the_reason() {
+        let mut conversation = ClientConversation::new();
+        conversation.start_send(params("msg-1", "Diagnose me")).unwrap();
+        let mut message = agent_text("msg-2", "Medical advice is out of scope");
+        message["parts"].as_array_mut().unwrap().push(json!({"kind": "data", "data": {"rejectionReason": {
+            "code": "policy-violation",
+            "message": "Medical advice is out of scope"
+        }}}));
+        let mut body = frame("1", status("submitted", None, false));
+        body.extend(frame("1", status("rejected", Some(message), true)));
+
+        let effects = conversation.handle_response(&body).unwrap();
+        let Some(Effect::Completed(task)) = effects.last() else { panic!("{:?}", effects) };
+        let reason = task.rejection_reason().unwrap();
+        assert_eq!(reason.code, crate::rejection::RejectionReason::POLICY_VIOLATION);
+        assert_eq!(reason.message, "Medical advice is out of scope");
+        assert_eq!(conversation.state(), ConversationState::Finished);
+    }
+
+    #[test]
+    fn test_oversized_stream_event_is_rejected() {
+        let limits = SseLimits {
+            max_event_bytes: 256,
+            ..SseLimits::default()
+        };
+        let mut conversation = ClientConversation::new().with_sse_limits(limits);
+        conversation.start_send(params("msg-1", "Hi")).unwrap();
+        let huge = agent_text("msg-2", &"x".repeat(1000));
+        let error = conversation.handle_response(&frame("1", huge)).unwrap_err();
+        assert!(matches!(error, A2AError::InvalidAgentResponse(_)));
+    }
+
+    #[test]
+    fn test_response_id_matching() {
+        let numeric = |result: Value| format!("data: {}\n\n", json!({"jsonrpc": "2.0", "id": 1, "result": result}));
+
+        // The request id "1" is echoed as the number 1
+        let mut conversation = ClientConversation::new();
+        conversation.start_send(params("msg-1", "Hi")).unwrap();
+        let error = conversation.handle_response(numeric(status("working", None, false)).as_bytes()).unwrap_err();
+        let A2AError::InvalidAgentResponse(error) = error else { panic!("expected an invalid agent response") };
+        assert_eq!(error.data.unwrap(), json!({"check": "id", "expected": "1", "actual": 1}));
+
+        let mut conversation = ClientConversation::new().lenient_ids();
+        conversation.start_send(params("msg-1", "Hi")).unwrap();
+        assert!(conversation.handle_response(numeric(status("working", None, false)).as_bytes()).is_ok());
+        let error = conversation.handle_response(&frame("2", status("working", None, false))).unwrap_err();
+        assert!(matches!(&error, A2AError::InvalidAgentResponse(e) if e.data.as_ref().unwrap()["actual"] == "2"));
+
+        let mut conversation = ClientConversation::new().allow_id_mismatch();
+        conversation.start_send(params("msg-1", "Hi")).unwrap();
+        assert!(conversation.handle_response(&frame("2", status("working", None, false))).is_ok());
+    }
+
+    #[test]
+    fn test_artifact_chunk_sequences() {
+        let mut conversation = ClientConversation::new();
+        conversation.start_send(params("msg-1", "Write a report")).unwrap();
+        conversation.handle_response(&frame("1", status("working", None, false))).unwrap();
+
+        let mut report = ArtifactUpdateSequenceBuilder::new(
+            "task-1".to_string(),
+            "ctx-1".to_string(),
+            "report".to_string(),
+        )
+        .with_name("report.md");
+        let mut summary =
+            ArtifactUpdateSequenceBuilder::new("task-1".to_string(), "ctx-1".to_string(), "summary".to_string());
+        let events = [
+            report.first(["# Report\n"]).unwrap(),
+            report.next(["Revenue grew "]).unwrap(),
+            summary.last(["Up 4%."]).unwrap(),
+            report.next(["4% "]).unwrap(),
+            report.last(["year over year."]).unwrap(),
+        ];
+        assert!(report.is_finished());
+        assert_eq!((events[4].append, events[4].last_chunk), (Some(true), Some(true)));
+        assert_eq!((events[2].append, events[2].last_chunk), (Some(false), Some(true)));
+
+        let mut body = Vec::new();
+        for event in &events {
+            body.extend(frame("1", serde_json::to_value(event).unwrap()));
+        }
+        let effects = conversation.handle_response(&body).unwrap();
+        assert_eq!(effects.len(), 5);
+        let artifacts = conversation.task().unwrap().artifacts.as_ref().unwrap();
+        assert_eq!(artifacts.len(), 2);
+        assert_eq!(artifacts[0].name.as_deref(), Some("report.md"));
+        assert_eq!(artifacts[0].text().unwrap(), "# Report\nRevenue grew 4% year over year.");
+        assert_eq!(artifacts[1].text().unwrap(), "Up 4%.");
+
+        // Streaming the artifact again replaces it
+        let mut again =
+            ArtifactUpdateSequenceBuilder::new("task-1".to_string(), "ctx-1".to_string(), "report".to_string());
+        let mut body = frame("1", serde_json::to_value(again.first(["Draft "]).unwrap()).unwrap());
+        body.extend(frame("1", serde_json::to_value(again.last(["two"]).unwrap()).unwrap()));
+        conversation.handle_response(&body).unwrap();
+        let artifacts = conversation.task().unwrap().artifacts.as_ref().unwrap();
+        assert_eq!(artifacts[0].text().unwrap(), "Draft two");
+        assert_eq!(artifacts[0].name, None);
+    }
+
+    #[test]
+    fn test_artifact_sequence_order() {
+        let mut chunks =
+            ArtifactUpdateSequenceBuilder::new("task-1".to_string(), "ctx-1".to_string(), "a".to_string());
+        assert!(chunks.next(["too early"]).unwrap_err().contains("has not started"));
+        chunks.first(["one"]).unwrap();
+        assert!(chunks.first(["again"]).unwrap_err().contains("already started"));
+        assert!(chunks.next(Vec::<Part>::new()).unwrap_err().contains("at least one part"));
+        chunks.last(["two"]).unwrap();
+        assert!(chunks.last(["three"]).unwrap_err().contains("already finished"));
+        assert!(chunks.next(["four"]).is_err());
+
+        let mut chunks =
+            ArtifactUpdateSequenceBuilder::new("".to_string(), "ctx-1".to_string(), "a".to_string());
+        // An invalid chunk does not advance the sequence
+        assert!(chunks.first(["one"]).unwrap_err().contains("Task ID"));
+        assert!(chunks.first(["one"]).unwrap_err().contains("Task ID"));
+    }
+
+    #[test]
+    fn test_extension_activation_round_trip() {
+        let mut conversation = ClientConversation::new().request_extensions([
+            "https://example.com/ext/a",
+            "https://example.com/ext/b",
+            "https://example.com/ext/a",
+            "https://example.com/ext/c",
+        ]);
+        let header = conversation.extensions_header().unwrap();
+        assert_eq!(header, "https://example.com/ext/a, https://example.com/ext/b, https://example.com/ext/c");
+        assert_eq!(ClientConversation::new().extensions_header(), None);
+
+        // The agent honors only one of the requested extensions
+        let mut context = RequestContext::from_headers([header.as_str()]);
+        assert!(context.activate("https://example.com/ext/b"));
+        assert!(!context.activate("https://example.com/ext/z"));
+        let response = context.header_value().unwrap();
+
+        conversation.handle_response_headers([("content-type", "text/event-stream"), ("x-a2a-extensions", response.as_str())]);
+        assert_eq!(conversation.activated_extensions(), ["https://example.com/ext/b"]);
+
+        // Repeated lines with stray whitespace and duplicates
+        conversation.handle_response_headers([
+            (EXTENSIONS_HEADER, " https://example.com/ext/a ,"),
+            ("X-A2A-EXTENSIONS", "https://example.com/ext/c,https://example.com/ext/a"),
+        ]);
+        assert_eq!(conversation.activated_extensions(), ["https://example.com/ext/a", "https://example.com/ext/c"]);
+
+        conversation.handle_response_headers([]);
+        assert!(conversation.activated_extensions().is_empty());
+    }
+}
@@ -0,0 +1,223 @@
+//! Failures of client calls.
+
+use crate::A2AError;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Why a client call failed.
+///
+/// [`A2AError`] only describes what an agent can answer. A client also
+/// fails to reach the agent, to hear back in time or to understand the
+/// answer, and callers deciding whether to retry need to tell these apart.
+#[derive(Debug)]
+pub enum ClientError {
+    /// The agent answered with an error, or the request was invalid before
+    /// it was sent.
+    Protocol(A2AError),
+    /// The request or its response did not get through.
+    Transport {
+        /// What went wrong.
+        kind: TransportErrorKind,
+        /// The error reported by the transport.
+        source: Box<dyn Error + Send + Sync>,
+    },
+    /// No response arrived in time.
+    Timeout,
+    /// The response could not be decoded into the protocol types.
+    Decode(String),
+    /// The agent refused the credentials, or none could be obtained.
+    Auth(String),
+}
+
+/// The kinds of [`ClientError::Transport`] failures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportErrorKind {
+    /// The endpoint URL is not valid.
+    InvalidEndpoint,
+    /// Nothing accepts connections at the endpoint.
+    ConnectionRefused,
+    /// The connection could not be established for another reason, e.g. a
+    /// failed DNS lookup.
+    Connect,
+    /// The connection was lost, or the agent is temporarily unavailable.
+    Unavailable,
+}
+
+impl TransportErrorKind {
+    /// Classify a transport error by the I/O error in its source chain.
+    ///
+    /// Refused connections are `ConnectionRefused`, and reset, aborted or
+    /// cut off ones `Unavailable`.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The transport error.
+    /// * `default` - The kind if the chain holds no telling I/O error.
+    pub fn of(error: &(dyn Error + 'static), default: Self) -> Self {
+        let mut source = Some(error);
+        while let Some(error) = source {
+            if let Some(error) = error.downcast_ref::<io::Error>() {
+                return match error.kind() {
+                    io::ErrorKind::ConnectionRefused => Self::ConnectionRefused,
+                    io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::UnexpectedEof => Self::Unavailable,
+                    _ => default,
+                };
+            }
+            source = error.source();
+        }
+        default
+    }
+}
+
+impl ClientError {
+    /// Create a transport error.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - What went wrong.
+    /// * `source` - The error reported by the transport.
+    pub fn transport(kind: TransportErrorKind, source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self::Transport {
+            kind,
+            source: source.into(),
+        }
+    }
+
+    /// Whether sending the same request again may succeed.
+    ///
+    /// Lost connections, unavailable agents and timeouts are retryable, and
+    /// so are errors the agent marked retryable (see
+    /// [`A2AError::is_retryable`]). Other errors the agent answered with,
+    /// invalid endpoints, undecodable responses and refused credentials are
+    /// not: repeating the request would fail the same way.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::Transport { kind, .. } => *kind != TransportErrorKind::InvalidEndpoint,
+            ClientError::Timeout => true,
+            ClientError::Protocol(error) => error.is_retryable(),
+            ClientError::Decode(_) | ClientError::Auth(_) => false,
+        }
+    }
+
+    /// The protocol error, if the agent answered with one.
+    pub fn as_protocol(&self) -> Option<&A2AError> {
+        match self {
+            ClientError::Protocol(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// The kind of transport failure, if the call failed in transport.
+    pub fn transport_kind(&self) -> Option<TransportErrorKind> {
+        match self {
+            ClientError::Transport { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
+}
+
+impl From<A2AError> for ClientError {
+    fn from(error: A2AError) -> Self {
+        ClientError::Protocol(error)
+    }
+}
+
+#[cfg(feature = "async")]
+impl From<crate::auth::AuthError> for ClientError {
+    fn from(error: crate::auth::AuthError) -> Self {
+        ClientError::Auth(error.to_string())
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Protocol(error) => write!(f, "{}", error),
+            ClientError::Transport { kind, source } => write!(f, "Transport error ({:?}): {}", kind, source),
+            ClientError::Timeout => f.write_str("The agent did not respond in time"),
+            ClientError::Decode(message) => write!(f, "Cannot decode the response: {}", message),
+            ClientError::Auth(message) => write!(f, "Authentication failed: {}", message),
+        }
+    }
+}
+
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ClientError::Protocol(error) => Some(error),
+            ClientError::Transport { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TaskNotFoundError;
+
+    #[test]
+    fn test_classification() {
+        let not_found = A2AError::TaskNotFound(Box::new(TaskNotFoundError {
+            code: -32001,
+            message: "Task not found".to_string(),
+            data: None,
+        }));
+        let error = ClientError::from(not_found.clone());
+        assert_eq!(error.as_protocol(), Some(&not_found));
+        assert!(!error.is_retryable());
+        assert_eq!(error.to_string(), not_found.to_string());
+
+        let refused = io::Error::from(io::ErrorKind::ConnectionRefused);
+        let error = ClientError::transport(TransportErrorKind::of(&refused, TransportErrorKind::Connect), refused);
+        assert_eq!(error.transport_kind(), Some(TransportErrorKind::ConnectionRefused));
+        assert!(error.is_retryable());
+        assert!(error.as_protocol().is_none());
+        assert!(error.source().unwrap().downcast_ref::<io::Error>().is_some());
+
+        let error = ClientError::transport(TransportErrorKind::InvalidEndpoint, "not a URL");
+        assert!(!error.is_retryable());
+        assert!(ClientError::Timeout.is_retryable());
+        assert!(!ClientError::Decode("Task has no status".to_string()).is_retryable());
+        assert!(!ClientError::Auth("Token expired".to_string()).is_retryable());
+
+        // Agents mark errors worth retrying in their data
+        let draining = A2AError::Internal(Box::new(crate::InternalError {
+            code: -32603,
+            message: "Server is shutting down".to_string(),
+            data: Some(serde_json::json!({"retryable": true})),
+        }));
+        assert!(ClientError::from(draining).is_retryable());
+    }
+
+    #[test]
+    fn test_transport_kind_follows_the_source_chain() {
+        #[derive(Debug)]
+        struct Wrapper(io::Error);
+
+        impl fmt::Display for Wrapper {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("connection error")
+            }
+        }
+
+        impl Error for Wrapper {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let kind = |kind| TransportErrorKind::of(&Wrapper(io::Error::from(kind)), TransportErrorKind::Connect);
+        assert_eq!(kind(io::ErrorKind::ConnectionReset), TransportErrorKind::Unavailable);
+        assert_eq!(kind(io::ErrorKind::ConnectionRefused), TransportErrorKind::ConnectionRefused);
+        assert_eq!(kind(io::ErrorKind::NotFound), TransportErrorKind::Connect);
+        assert_eq!(
+            TransportErrorKind::of(&ClientError::Timeout, TransportErrorKind::Unavailable),
+            TransportErrorKind::Unavailable
+        );
+    }
+}
@@ -0,0 +1,360 @@
+//! The source of the current time.
+//!
+//! Code that compares against "now", such as token expiry and task
+//! retention, reads it from a [`Clock`] so tests can control it, and with
+//! the `async` feature waits on it with [`Clock::sleep`]. Everything
+//! defaults to the [`SystemClock`]; with the `test-util` feature,
+//! [`MockClock`] is a clock that only moves when told to.
+//!
+//! Times on the wire are RFC 3339 timestamps, read with [`parse_timestamp`]
+//! and written with [`format_time`].
+//!
+//! ```
+//! # #[cfg(feature = "test-util")] {
+//! use a2a_rs::clock::{Clock, MockClock};
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! let clock = MockClock::new(UNIX_EPOCH);
+//! let handle = clock.clone();
+//! handle.advance(Duration::from_secs(90));
+//! assert_eq!(clock.now_millis(), 90_000);
+//! # }
+//! ```
+
+use std::fmt;
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The future of [`Clock::sleep`].
+#[cfg(feature = "async")]
+pub type Sleep<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+/// Tells the current time.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// The current time.
+    fn now(&self) -> SystemTime;
+
+    /// The current time in milliseconds since the Unix epoch, negative
+    /// before it.
+    fn now_millis(&self) -> i64 {
+        match self.now().duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_millis() as i64,
+            Err(e) => -(e.duration().as_millis() as i64),
+        }
+    }
+
+    /// Wait until the clock has moved on by `duration`.
+    ///
+    /// The default waits on the tokio timer, so it follows the real time.
+    #[cfg(feature = "async")]
+    fn sleep(&self, duration: Duration) -> Sleep<'_> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> SystemTime {
+        (**self).now()
+    }
+
+    #[cfg(feature = "async")]
+    fn sleep(&self, duration: Duration) -> Sleep<'_> {
+        (**self).sleep(duration)
+    }
+}
+
+/// The system's wall clock.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that stands still until it is set or advanced.
+///
+/// Clones share the time, so a test can hand a clone to the code under test
+/// and move the time from outside. Sleeps on the clock end when it is moved
+/// past their end, not when real time passes.
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    state: Arc<std::sync::Mutex<MockState>>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Debug)]
+struct MockState {
+    now: SystemTime,
+    /// The sleeps to check again when the time moves.
+    sleepers: Vec<std::task::Waker>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl MockClock {
+    /// Create a clock showing a given time.
+    pub fn new(now: SystemTime) -> Self {
+        Self {
+            state: Arc::new(std::sync::Mutex::new(MockState { now, sleepers: Vec::new() })),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Set the time.
+    pub fn set(&self, now: SystemTime) {
+        let sleepers = {
+            let mut state = self.state();
+            state.now = now;
+            std::mem::take(&mut state.sleepers)
+        };
+        sleepers.into_iter().for_each(std::task::Waker::wake);
+    }
+
+    /// Move the time forward.
+    pub fn advance(&self, by: std::time::Duration) {
+        let now = self.now() + by;
+        self.set(now);
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Default for MockClock {
+    /// A clock showing the Unix epoch.
+    fn default() -> Self {
+        Self::new(UNIX_EPOCH)
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Clock for MockClock {
+    fn now(&self) -> SystemTime {
+        self.state().now
+    }
+
+    #[cfg(feature = "async")]
+    fn sleep(&self, duration: Duration) -> Sleep<'_> {
+        let end = self.now().checked_add(duration);
+        Box::pin(std::future::poll_fn(move |cx| {
+            let mut state = self.state();
+            match end {
+                Some(end) if state.now >= end => std::task::Poll::Ready(()),
+                // A sleep past the end of time never ends
+                _ => {
+                    state.sleepers.push(cx.waker().clone());
+                    std::task::Poll::Pending
+                }
+            }
+        }))
+    }
+}
+
+/// Parse an RFC 3339 timestamp such as `2025-01-31T12:00:00.5+01:00`.
+///
+/// # Returns
+///
+/// The time, or `None` if the string is not a valid timestamp.
+pub fn parse_timestamp(value: &str) -> Option<SystemTime> {
+    let bytes = value.as_bytes();
+    if bytes.len() < 20 || bytes[4] != b'-' || bytes[7] != b'-' || !matches!(bytes[10], b'T' | b't' | b' ') {
+        return None;
+    }
+    if bytes[13] != b':' || bytes[16] != b':' {
+        return None;
+    }
+
+    let number = |range: std::ops::Range<usize>| -> Option<i64> {
+        let digits = value.get(range)?;
+        digits.bytes().all(|b| b.is_ascii_digit()).then(|| digits.parse().ok())?
+    };
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    // Fractional seconds, truncated to microseconds
+    let mut rest = &value[19..];
+    let mut micros = 0;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        micros = fraction[..digits.min(6)].parse::<i64>().ok()? * 10_i64.pow(6 - digits.min(6) as u32);
+        rest = &fraction[digits..];
+    }
+
+    let offset = match rest {
+        "Z" | "z" => 0,
+        _ => {
+            let sign = match rest.as_bytes().first()? {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            if rest.len() != 6 || rest.as_bytes()[3] != b':' {
+                return None;
+            }
+            // Two digits each, without a sign of their own
+            let (hours, minutes) = (rest.get(1..3)?, rest.get(4..6)?);
+            if !hours.bytes().chain(minutes.bytes()).all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let (hours, minutes): (i64, i64) = (hours.parse().ok()?, minutes.parse().ok()?);
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            sign * (hours * 3600 + minutes * 60)
+        }
+    };
+
+    // Days since 1970-01-01 from the civil date
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    let seconds = days * 86_400 + hour * 3600 + minute * 60 + second - offset;
+    let total = seconds.checked_mul(1_000_000)?.checked_add(micros)?;
+    if total >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_micros(total as u64))
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_micros(total.unsigned_abs()))
+    }
+}
+
+/// The number of days in a month of the proleptic Gregorian calendar.
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Format a time as an RFC 3339 UTC timestamp, the form [`parse_timestamp`]
+/// reads and the stores write.
+pub fn format_time(time: SystemTime) -> String {
+    let micros = match time.duration_since(UNIX_EPOCH) {
+        Ok(elapsed) => elapsed.as_micros() as i64,
+        Err(e) => -(e.duration().as_micros() as i64),
+    };
+    format_timestamp(micros)
+}
+
+/// Format microseconds since the Unix epoch as an RFC 3339 UTC timestamp.
+pub(crate) fn format_timestamp(micros: i64) -> String {
+    let days = micros.div_euclid(86_400_000_000);
+    let time = micros.rem_euclid(86_400_000_000);
+
+    // Civil date from days since 1970-01-01
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    let seconds = time / 1_000_000;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        time % 1_000_000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock() {
+        let clock = MockClock::default();
+        let shared: Arc<dyn Clock> = Arc::new(clock.clone());
+        assert_eq!(shared.now_millis(), 0);
+
+        clock.advance(Duration::from_millis(1500));
+        assert_eq!(shared.now(), UNIX_EPOCH + Duration::from_millis(1500));
+        clock.set(UNIX_EPOCH - Duration::from_secs(2));
+        assert_eq!(shared.now_millis(), -2000);
+
+        let before = SystemTime::now();
+        assert!(SystemClock.now() >= before);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_mock_clock_sleep_ends_when_advanced() {
+        let clock = MockClock::default();
+        let sleeper = tokio::spawn({
+            let clock = clock.clone();
+            async move { clock.sleep(Duration::from_secs(10)).await }
+        });
+        tokio::task::yield_now().await;
+        clock.advance(Duration::from_secs(9));
+        tokio::task::yield_now().await;
+        assert!(!sleeper.is_finished());
+
+        clock.advance(Duration::from_secs(1));
+        sleeper.await.unwrap();
+        // A sleep that has already ended returns at once
+        clock.sleep(Duration::ZERO).await;
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00.000000Z");
+        assert_eq!(format_timestamp(951_782_400_000_001), "2000-02-29T00:00:00.000001Z");
+        assert_eq!(format_timestamp(1_767_225_599_999_999), "2025-12-31T23:59:59.999999Z");
+
+        for micros in [0, 951_782_400_000_001, 1_767_225_599_999_999] {
+            let parsed = parse_timestamp(&format_timestamp(micros)).unwrap();
+            assert_eq!(parsed, UNIX_EPOCH + Duration::from_micros(micros as u64));
+        }
+        assert_eq!(
+            parse_timestamp("2000-01-01T01:00:00+01:00"),
+            parse_timestamp("2000-01-01T00:00:00Z")
+        );
+        assert_eq!(parse_timestamp("1970-01-01T00:00:01.5Z"), Some(UNIX_EPOCH + Duration::from_millis(1500)));
+        assert_eq!(parse_timestamp("2000-13-01T00:00:00Z"), None);
+
+        // Days past the end of their month, with leap years
+        for invalid in ["2025-04-31", "2025-02-29", "1900-02-29", "2025-06-31", "2025-01-32", "2025-01-00"] {
+            assert_eq!(parse_timestamp(&format!("{}T00:00:00Z", invalid)), None, "{}", invalid);
+        }
+        for valid in ["2024-02-29", "2000-02-29", "2025-01-31", "2025-04-30", "2025-12-31"] {
+            assert!(parse_timestamp(&format!("{}T00:00:00Z", valid)).is_some(), "{}", valid);
+        }
+        assert_eq!(parse_timestamp("yesterday"), None);
+
+        // Offsets need a sign, then two-digit hours and minutes in range
+        for invalid in ["+-1:00", "+1:000", "+24:00", "+00:60", "01:00", "+ 1:00", "+01:0x", "+01:\u{e9}"] {
+            assert_eq!(parse_timestamp(&format!("2000-01-01T00:00:00{}", invalid)), None, "{}", invalid);
+        }
+        assert_eq!(parse_timestamp("2000-01-01T00:00:00-23:59"), parse_timestamp("2000-01-01T23:59:00Z"));
+    }
+}
@@ -0,0 +1,269 @@
+//! Per-request context for server code.
+//!
+//! A [`RequestContext`] travels with one request through the layers that
+//! handle it: the transport fills in what it knows, such as the JSON-RPC ID,
+//! the authenticated principal and the requested extensions, and anything
+//! else is attached by type with [`RequestContext::insert`], so a middleware
+//! can hand data to the handler without either knowing the other's types.
+//!
+//! ```
+//! use a2a_rs::A2ARequest;
+//! use a2a_rs::context::RequestContext;
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Tenant(&'static str);
+//!
+//! let request: A2ARequest = serde_json::from_str(
+//!     r#"{"jsonrpc": "2.0", "id": "7", "method": "tasks/get", "params": {"taskId": "task-1"}}"#,
+//! ).unwrap();
+//!
+//! // In a middleware
+//! let mut context = RequestContext::for_request(&request).with_principal("alice");
+//! context.insert(Tenant("acme"));
+//!
+//! // In the handler
+//! assert_eq!(context.principal(), Some("alice"));
+//! assert_eq!(context.get::<Tenant>(), Some(&Tenant("acme")));
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::extensions::{NegotiationResult, negotiate, parse_extensions_headers, render_extensions_header};
+use crate::{A2ARequest, AgentCard, JsonRpcId, RequestMethod};
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::any::{Any, TypeId};
+use core::fmt;
+
+/// Everything known about one request besides its params.
+///
+/// Cloning is cheap for the typed values, which are shared between clones.
+#[derive(Clone, Default)]
+pub struct RequestContext {
+    request_id: Option<JsonRpcId>,
+    method: Option<RequestMethod>,
+    principal: Option<String>,
+    correlation_id: Option<String>,
+    client_metadata: BTreeMap<String, String>,
+    requested: Vec<String>,
+    activated: Vec<String>,
+    values: BTreeMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl fmt::Debug for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestContext")
+            .field("request_id", &self.request_id)
+            .field("method", &self.method)
+            .field("principal", &self.principal)
+            .field("correlation_id", &self.correlation_id)
+            .field("client_metadata", &self.client_metadata)
+            .field("requested", &self.requested)
+            .field("activated", &self.activated)
+            .field("values", &self.values.len())
+            .finish()
+    }
+}
+
+impl RequestContext {
+    /// Create a context for a request asking for the given extensions.
+    pub fn new(requested: Vec<String>) -> Self {
+        Self {
+            requested: parse_extensions_headers(requested.iter().map(String::as_str)),
+            ..Self::default()
+        }
+    }
+
+    /// Create a context from the values of the request's
+    /// [`EXTENSIONS_HEADER`](crate::extensions::EXTENSIONS_HEADER) lines.
+    pub fn from_headers<'a>(values: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            requested: parse_extensions_headers(values),
+            ..Self::default()
+        }
+    }
+
+    /// Create a context for a parsed request, with its JSON-RPC ID and
+    /// method.
+    pub fn for_request(request: &A2ARequest) -> Self {
+        Self {
+            request_id: Some(request.id()),
+            method: Some(request.method()),
+            ..Self::default()
+        }
+    }
+
+    /// Set the requested extensions from the values of the request's
+    /// [`EXTENSIONS_HEADER`](crate::extensions::EXTENSIONS_HEADER) lines.
+    pub fn with_extensions_headers<'a>(mut self, values: impl IntoIterator<Item = &'a str>) -> Self {
+        self.requested = parse_extensions_headers(values);
+        self.activated.retain(|uri| self.requested.contains(uri));
+        self
+    }
+
+    /// Set the authenticated principal, e.g. the subject of a verified token.
+    pub fn with_principal(mut self, principal: impl Into<String>) -> Self {
+        self.principal = Some(principal.into());
+        self
+    }
+
+    /// Set the ID correlating the request across services, e.g. from a
+    /// `traceparent` or `X-Request-ID` header.
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// Add an item of client metadata, such as the user agent.
+    pub fn with_client_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.client_metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// The JSON-RPC ID of the request, if it was parsed.
+    pub fn request_id(&self) -> Option<&JsonRpcId> {
+        self.request_id.as_ref()
+    }
+
+    /// The method of the request, if it was parsed.
+    pub fn method(&self) -> Option<RequestMethod> {
+        self.method
+    }
+
+    /// The authenticated principal, or `None` for anonymous requests.
+    pub fn principal(&self) -> Option<&str> {
+        self.principal.as_deref()
+    }
+
+    /// The correlation ID, if the transport found one.
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.correlation_id.as_deref()
+    }
+
+    /// Get an item of client metadata.
+    pub fn client_metadata(&self, key: &str) -> Option<&str> {
+        self.client_metadata.get(key).map(String::as_str)
+    }
+
+    /// Attach a value, replacing any earlier value of the same type.
+    ///
+    /// # Returns
+    ///
+    /// The value it replaced, shared with any clones of the context taken
+    /// before.
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) -> Option<Arc<T>> {
+        self.values
+            .insert(TypeId::of::<T>(), Arc::new(value))
+            .and_then(|old| old.downcast().ok())
+    }
+
+    /// Get the attached value of a type.
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.values.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    /// Detach the value of a type.
+    pub fn remove<T: Any + Send + Sync>(&mut self) -> Option<Arc<T>> {
+        self.values.remove(&TypeId::of::<T>())?.downcast().ok()
+    }
+
+    /// The requested extension URIs, in request order.
+    pub fn requested(&self) -> &[String] {
+        &self.requested
+    }
+
+    /// Check if the client asked for an extension.
+    pub fn is_requested(&self, uri: &str) -> bool {
+        self.requested.iter().any(|requested| requested == uri)
+    }
+
+    /// Activate a requested extension.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the extension was requested and is now active, `false` if
+    /// the client did not ask for it, in which case nothing changes.
+    pub fn activate(&mut self, uri: &str) -> bool {
+        if !self.is_requested(uri) {
+            return false;
+        }
+        if !self.activated.iter().any(|activated| activated == uri) {
+            self.activated.push(uri.to_string());
+        }
+        true
+    }
+
+    /// Activate every requested extension the agent declares.
+    ///
+    /// # Returns
+    ///
+    /// The outcome of [`negotiate`], for checking required extensions.
+    pub fn activate_supported(&mut self, card: &AgentCard) -> NegotiationResult {
+        let result = negotiate(&self.requested, card);
+        for uri in &result.activated {
+            self.activate(uri);
+        }
+        result
+    }
+
+    /// The activated extension URIs, in activation order.
+    pub fn activated(&self) -> &[String] {
+        &self.activated
+    }
+
+    /// The response's [`EXTENSIONS_HEADER`](crate::extensions::EXTENSIONS_HEADER)
+    /// value, or `None` if no extension was activated and the header should
+    /// be left out.
+    pub fn header_value(&self) -> Option<String> {
+        (!self.activated.is_empty()).then(|| render_extensions_header(&self.activated))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Tenant(String);
+
+    /// A middleware that attaches the tenant named by the principal.
+    fn tenant_interceptor(context: &mut RequestContext) {
+        let tenant = context.principal().and_then(|p| p.split('@').nth(1)).unwrap_or("public").to_string();
+        context.insert(Tenant(tenant));
+    }
+
+    /// A handler that only reads the context.
+    fn handle(context: &RequestContext) -> String {
+        format!("{:?} for {}", context.method().unwrap(), context.get::<Tenant>().unwrap().0)
+    }
+
+    #[test]
+    fn test_interceptor_value_reaches_handler() {
+        let request: A2ARequest = serde_json::from_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "req-9",
+            "method": "tasks/get",
+            "params": {"taskId": "task-1"}
+        }))
+        .unwrap();
+        let mut context = RequestContext::for_request(&request)
+            .with_principal("bob@acme")
+            .with_correlation_id("trace-1")
+            .with_client_metadata("user-agent", "test/1.0")
+            .with_extensions_headers(["https://example.com/a"]);
+        tenant_interceptor(&mut context);
+
+        assert_eq!(handle(&context.clone()), "TasksGet for acme");
+        assert_eq!(context.request_id(), Some(&JsonRpcId::String("req-9".to_string())));
+        assert_eq!(context.correlation_id(), Some("trace-1"));
+        assert_eq!(context.client_metadata("user-agent"), Some("test/1.0"));
+        assert_eq!(context.client_metadata("accept"), None);
+        assert!(context.is_requested("https://example.com/a"));
+
+        let old = context.insert(Tenant("other".to_string())).unwrap();
+        assert_eq!(old.0, "acme");
+        assert_eq!(context.remove::<Tenant>().unwrap().0, "other");
+        assert_eq!(context.get::<Tenant>(), None);
+        assert_eq!(context.get::<u32>(), None);
+    }
+}
@@ -0,0 +1,342 @@
+//! Ordered, role-aware reconstruction of task conversations.
+//!
+//! A [`Conversation`] turns the history of one or more tasks into a flat list
+//! of [`Turn`]s, suitable for rendering transcripts in logs or prompts.
+
+use crate::{Artifact, Message, MessageRole, Part, Task};
+use std::collections::HashSet;
+
+/// Options controlling how a conversation is reconstructed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConversationOptions {
+    /// Include status messages from `statusHistory` and the current status
+    /// that do not already appear in the task history.
+    pub include_status_messages: bool,
+}
+
+/// A single turn in a conversation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Turn {
+    /// The role of the sender.
+    pub role: MessageRole,
+    /// The ID of the message this turn was built from, if any.
+    pub message_id: Option<String>,
+    /// The text parts of the message joined by newlines.
+    pub text: String,
+    /// All parts of the message.
+    pub parts: Vec<Part>,
+    /// Artifacts produced by the agent in this turn.
+    pub artifacts: Vec<Artifact>,
+    /// ISO 8601 timestamp of the turn, when known.
+    pub at: Option<String>,
+}
+
+impl Turn {
+    fn from_message(message: &Message, at: Option<String>) -> Self {
+        Self {
+            role: message.role.clone(),
+            message_id: Some(message.message_id.clone()),
+            text: text_of(&message.parts),
+            parts: message.parts.clone(),
+            artifacts: Vec::new(),
+            at,
+        }
+    }
+}
+
+/// An ordered transcript of the messages exchanged in one or more tasks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Conversation {
+    /// The turns, in conversation order.
+    pub turns: Vec<Turn>,
+}
+
+impl Conversation {
+    /// Build a conversation from the history of a single task.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task to reconstruct.
+    ///
+    /// # Returns
+    ///
+    /// A `Conversation` with the task history in order and the task
+    /// artifacts attached to the last agent turn.
+    pub fn from_task(task: &Task) -> Self {
+        Self::from_task_with(task, &ConversationOptions::default())
+    }
+
+    /// Build a conversation from a single task with the given options.
+    ///
+    /// History messages keep their position. Status messages, when included,
+    /// follow the history ordered by timestamp, skipping any message whose ID
+    /// is already present. Artifacts are attached to the last agent turn, or
+    /// to a new agent turn when the agent has not spoken.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task to reconstruct.
+    /// * `options` - Reconstruction options.
+    ///
+    /// # Returns
+    ///
+    /// A new `Conversation`.
+    pub fn from_task_with(task: &Task, options: &ConversationOptions) -> Self {
+        let mut turns: Vec<Turn> = task
+            .history
+            .iter()
+            .flatten()
+            .map(|message| Turn::from_message(message, None))
+            .collect();
+
+        if options.include_status_messages {
+            let mut seen: HashSet<String> =
+                turns.iter().filter_map(|turn| turn.message_id.clone()).collect();
+
+            let mut statuses: Vec<_> = task.status_history.iter().flatten().collect();
+            statuses.push(&task.status);
+            // Stable sort: entries without a timestamp keep their relative order at the end.
+            statuses.sort_by(|a, b| match (&a.timestamp, &b.timestamp) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            });
+
+            for status in statuses {
+                if let Some(message) = &status.message
+                    && seen.insert(message.message_id.clone())
+                {
+                    turns.push(Turn::from_message(message, status.timestamp.clone()));
+                }
+            }
+        }
+
+        if let Some(artifacts) = &task.artifacts
+            && !artifacts.is_empty()
+        {
+            match turns.iter_mut().rev().find(|turn| turn.role == MessageRole::Agent) {
+                Some(turn) => turn.artifacts.extend(artifacts.iter().cloned()),
+                None => turns.push(Turn {
+                    role: MessageRole::Agent,
+                    message_id: None,
+                    text: String::new(),
+                    parts: Vec::new(),
+                    artifacts: artifacts.clone(),
+                    at: task.status.timestamp.clone(),
+                }),
+            }
+        }
+
+        Self { turns }
+    }
+
+    /// Build a conversation from all tasks belonging to a context.
+    ///
+    /// Tasks are ordered by `createdAt` when present; tasks without a creation
+    /// time keep their input order after the timestamped ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - The tasks to consider.
+    /// * `context_id` - Only tasks in this context are included.
+    /// * `options` - Reconstruction options.
+    ///
+    /// # Returns
+    ///
+    /// A new `Conversation` spanning the matching tasks.
+    pub fn from_tasks<'a>(
+        tasks: impl IntoIterator<Item = &'a Task>,
+        context_id: &str,
+        options: &ConversationOptions,
+    ) -> Self {
+        let mut tasks: Vec<&Task> = tasks
+            .into_iter()
+            .filter(|task| task.context_id == context_id)
+            .collect();
+        tasks.sort_by(|a, b| match (&a.created_at, &b.created_at) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+
+        let turns = tasks
+            .into_iter()
+            .flat_map(|task| Self::from_task_with(task, options).turns)
+            .collect();
+
+        Self { turns }
+    }
+
+    /// Check if the conversation has no turns.
+    pub fn is_empty(&self) -> bool {
+        self.turns.is_empty()
+    }
+
+    /// Render the conversation as plain text.
+    ///
+    /// Each turn is rendered as `role: text`, followed by one indented line
+    /// per artifact.
+    ///
+    /// # Returns
+    ///
+    /// The rendered transcript.
+    pub fn to_plain_text(&self) -> String {
+        let mut out = String::new();
+
+        for turn in &self.turns {
+            out.push_str(turn.role.as_str());
+            out.push_str(": ");
+            out.push_str(&turn.text);
+            out.push('\n');
+
+            for artifact in &turn.artifacts {
+                let label = artifact.name.as_deref().unwrap_or(&artifact.artifact_id);
+                out.push_str(&format!("  [{}] {}\n", label, text_of(&artifact.parts)));
+            }
+        }
+
+        out
+    }
+}
+
+/// Join the text parts of a part list with newlines.
+fn text_of(parts: &[Part]) -> String {
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            Part::Text(text) => Some(text.text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ExtraFields, TaskState, TaskStatus, TextPart};
+
+    fn message(id: &str, role: MessageRole, text: &str) -> Message {
+        Message {
+            kind: "message".to_string(),
+            message_id: id.to_string(),
+            parts: vec![Part::Text(TextPart {
+                text: text.to_string(),
+                metadata: None,
+            })],
+            role,
+            context_id: Some("ctx-1".to_string()),
+            extensions: None,
+            metadata: None,
+            reference_task_ids: None,
+            task_id: Some("task-1".to_string()),
+            extra: ExtraFields::default(),
+        }
+    }
+
+    fn task(id: &str, history: Option<Vec<Message>>) -> Task {
+        Task {
+            id: id.to_string(),
+            kind: "task".to_string(),
+            status: TaskStatus {
+                state: TaskState::Completed,
+                message: Some(message("status-1", MessageRole::Agent, "All done")),
+                timestamp: Some("2024-01-01T00:00:10Z".to_string()),
+                extra: ExtraFields::default(),
+            },
+            context_id: "ctx-1".to_string(),
+            artifacts: Some(vec![Artifact {
+                artifact_id: "artifact-1".to_string(),
+                parts: vec![Part::Text(TextPart {
+                    text: "Bonjour".to_string(),
+                    metadata: None,
+                })],
+                description: None,
+                extensions: None,
+                metadata: None,
+                name: Some("translation".to_string()),
+                extra: ExtraFields::default(),
+            }]),
+            history,
+            metadata: None,
+            result: None,
+            error: None,
+            created_at: None,
+            updated_at: None,
+            status_history: None,
+            extra: ExtraFields::default(),
+        }
+    }
+
+    #[test]
+    fn test_conversation_from_task() {
+        let task = task(
+            "task-1",
+            Some(vec![
+                message("m1", MessageRole::User, "Translate hello"),
+                message("m2", MessageRole::Agent, "Which language?"),
+                message("m3", MessageRole::User, "French"),
+                message("m4", MessageRole::Agent, "Translating"),
+            ]),
+        );
+
+        let conversation = Conversation::from_task(&task);
+        assert_eq!(conversation.turns.len(), 4);
+        assert_eq!(conversation.turns[0].role, MessageRole::User);
+        assert_eq!(conversation.turns[3].text, "Translating");
+        assert!(conversation.turns[1].artifacts.is_empty());
+        assert_eq!(conversation.turns[3].artifacts.len(), 1);
+
+        assert_eq!(
+            conversation.to_plain_text(),
+            "user: Translate hello\n\
+             agent: Which language?\n\
+             user: French\n\
+             agent: Translating\n  \
+             [translation] Bonjour\n"
+        );
+
+        let with_status = Conversation::from_task_with(
+            &task,
+            &ConversationOptions {
+                include_status_messages: true,
+            },
+        );
+        assert_eq!(with_status.turns.len(), 5);
+        assert_eq!(with_status.turns[4].text, "All done");
+        assert_eq!(with_status.turns[4].at.as_deref(), Some("2024-01-01T00:00:10Z"));
+        assert_eq!(with_status.turns[4].artifacts.len(), 1);
+    }
+
+    #[test]
+    fn test_conversation_without_history() {
+        let mut task = task("task-1", None);
+        let conversation = Conversation::from_task(&task);
+        assert_eq!(conversation.turns.len(), 1);
+        assert_eq!(conversation.turns[0].role, MessageRole::Agent);
+        assert_eq!(conversation.turns[0].artifacts.len(), 1);
+
+        task.artifacts = None;
+        assert!(Conversation::from_task(&task).is_empty());
+    }
+
+    #[test]
+    fn test_conversation_from_tasks_in_context() {
+        let mut first = task("task-1", Some(vec![message("m1", MessageRole::User, "first")]));
+        first.created_at = Some("2024-01-01T00:00:00Z".to_string());
+        first.artifacts = None;
+        let mut second = task("task-2", Some(vec![message("m2", MessageRole::User, "second")]));
+        second.created_at = Some("2024-01-02T00:00:00Z".to_string());
+        second.artifacts = None;
+        let mut other = task("task-3", Some(vec![message("m3", MessageRole::User, "other")]));
+        other.context_id = "ctx-2".to_string();
+
+        let tasks = [second, other, first];
+        let conversation =
+            Conversation::from_tasks(&tasks, "ctx-1", &ConversationOptions::default());
+        let texts: Vec<_> = conversation.turns.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["first", "second"]);
+    }
+}
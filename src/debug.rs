@@ -0,0 +1,542 @@
+//! Human-readable explanations of protocol traffic.
+//!
+//! Enabled by the `diagnostics` feature. [`explain`] reads any A2A payload
+//! (a request, a response, an error, a task, a message, an event or an
+//! agent card) with the protocol types and renders an indented summary:
+//! the method and id, task and context ids, states, the kind and size of
+//! every part, and the name of error codes from the spec's error table.
+//! Whatever the types do not recognize is shown as raw JSON.
+//! [`explain_stream`] does the same for every event of a captured
+//! `text/event-stream` body and follows each task's state across events.
+//!
+//! The output is meant for people debugging interoperability problems; its
+//! format may change between releases.
+//!
+//! ```
+//! let request = r#"{"jsonrpc": "2.0", "id": "7", "method": "tasks/get", "params": {"taskId": "task-1"}}"#;
+//! assert_eq!(a2a_rs::debug::explain(request), "Request tasks/get (id \"7\")\n  taskId: \"task-1\"\n");
+//! ```
+
+use crate::sse::SseDecoder;
+use crate::{
+    A2AError, A2AErrorResponse, A2ARequest, AgentCard, Artifact, ExtraFields, FileContent, IncomingRequest, Message,
+    Part, SendMessageParams, Task, TaskArtifactUpdateEvent, TaskState, TaskStatus, TaskStatusUpdateEvent,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fmt::{self, Write};
+
+/// Number of characters of text parts shown.
+const PREVIEW_CHARS: usize = 60;
+
+/// Explain one protocol payload.
+///
+/// # Arguments
+///
+/// * `json` - The payload, e.g. a JSON-RPC request or response body.
+///
+/// # Returns
+///
+/// The summary, one item per line and indented by two spaces per level.
+pub fn explain(json: &str) -> String {
+    let mut explainer = Explainer::default();
+    explainer.json(json, 0);
+    explainer.out
+}
+
+/// Explain a captured `text/event-stream` response.
+///
+/// Each event is explained as by [`explain`], with status updates showing
+/// the transition from the task's previous state. A summary at the end
+/// lists the states each task went through, where the stream ended, and
+/// events that came after the end or were cut off.
+///
+/// # Arguments
+///
+/// * `capture` - The response body as received.
+///
+/// # Returns
+///
+/// The summary of every event, followed by the summary of the stream.
+pub fn explain_stream(capture: &str) -> String {
+    let mut decoder = SseDecoder::new();
+    let mut events = Vec::new();
+    decoder.feed_into(capture.as_bytes(), &mut events);
+
+    let mut explainer = Explainer::default();
+    let mut ended_at = None;
+    for (index, event) in events.iter().enumerate() {
+        let number = index + 1;
+        match event {
+            Ok(event) => {
+                let mut header = format!("Event {}", number);
+                if let Some(id) = &event.id {
+                    let _ = write!(header, " (id {})", id);
+                }
+                if let Some(kind) = &event.event {
+                    let _ = write!(header, ", type {}", kind);
+                }
+                explainer.line(0, header);
+                explainer.json(&event.data, 1);
+            }
+            Err(overflow) => explainer.line(0, format_args!("Event {} discarded: {}", number, overflow)),
+        }
+        if explainer.ended && ended_at.is_none() {
+            ended_at = Some(number);
+        }
+    }
+
+    explainer.line(0, format_args!("Summary: {}", count(events.len(), "event")));
+    for (task_id, states) in std::mem::take(&mut explainer.timeline) {
+        let states: Vec<_> = states.iter().map(state_name).collect();
+        explainer.line(1, format_args!("task {}: {}", task_id, states.join(" → ")));
+    }
+    match ended_at {
+        Some(number) => {
+            explainer.line(1, format_args!("ended by event {}", number));
+            if number < events.len() {
+                explainer.line(1, format_args!("{} after the end", count(events.len() - number, "event")));
+            }
+        }
+        None => explainer.line(1, "not ended: no final event, message, error or finished task"),
+    }
+    if decoder.buffered_bytes() > 0 {
+        explainer.line(1, format_args!("{} bytes of an unterminated event at the end", decoder.buffered_bytes()));
+    }
+    explainer.out
+}
+
+/// Renders payloads and remembers task states across them.
+#[derive(Default)]
+struct Explainer {
+    out: String,
+    /// The states of each task in the order seen, tasks in order of
+    /// appearance.
+    timeline: Vec<(String, Vec<TaskState>)>,
+    /// Whether a payload that ends a response stream was seen.
+    ended: bool,
+}
+
+impl Explainer {
+    fn line(&mut self, depth: usize, text: impl fmt::Display) {
+        let _ = writeln!(self.out, "{:indent$}{}", "", text, indent = depth * 2);
+    }
+
+    fn raw(&mut self, depth: usize, value: &Value) {
+        let pretty = serde_json::to_string_pretty(value).unwrap_or_default();
+        for line in pretty.lines() {
+            self.line(depth, line);
+        }
+    }
+
+    fn json(&mut self, json: &str, depth: usize) {
+        match serde_json::from_str(json) {
+            Ok(value) => self.value(&value, depth),
+            Err(e) => {
+                self.line(depth, format_args!("Not JSON: {}", e));
+                for line in json.lines() {
+                    self.line(depth + 1, line);
+                }
+            }
+        }
+    }
+
+    fn value(&mut self, value: &Value, depth: usize) {
+        let has = |key: &str| value.get(key).is_some();
+        if has("method") {
+            self.request(value, depth);
+        } else if has("error") && (has("jsonrpc") || has("id")) {
+            self.line(depth, format_args!("Error response ({})", id_label(value)));
+            self.ended = true;
+            self.error(&value["error"], depth + 1);
+        } else if has("result") && (has("jsonrpc") || has("id")) {
+            self.line(depth, format_args!("Response ({})", id_label(value)));
+            self.result(&value["result"], depth + 1);
+        } else if has("kind") {
+            self.result(value, depth);
+        } else if has("capabilities") && has("skills") {
+            match AgentCard::deserialize(value) {
+                Ok(card) => self.card(&card, depth),
+                Err(e) => self.invalid("agent card", e, value, depth),
+            }
+        } else if has("code") && has("message") {
+            self.error(value, depth);
+        } else {
+            self.line(depth, "Unrecognized payload");
+            self.raw(depth + 1, value);
+        }
+    }
+
+    fn invalid(&mut self, what: &str, error: impl fmt::Display, value: &Value, depth: usize) {
+        self.line(depth, format_args!("Invalid {}: {}", what, error));
+        self.raw(depth + 1, value);
+    }
+
+    fn request(&mut self, value: &Value, depth: usize) {
+        let sent_as = value["method"].as_str().unwrap_or_default();
+        let incoming = match IncomingRequest::try_from(value.clone()) {
+            Ok(incoming) => incoming,
+            Err(e) => {
+                let what = format!("request {} ({})", sent_as, id_label(value));
+                return self.invalid(&what, A2AErrorResponse::from(e.error).message, value, depth);
+            }
+        };
+
+        let kind = if incoming.is_notification() { "Notification" } else { "Request" };
+        let method = incoming.request().method();
+        let mut header = format!("{} {} ({})", kind, method, id_label(value));
+        if method.as_str() != sent_as {
+            let _ = write!(header, ", sent as {}", sent_as);
+        }
+        self.line(depth, header);
+
+        match incoming.request() {
+            A2ARequest::SendMessage(request) => self.send_params(&request.params, depth + 1),
+            A2ARequest::SendStreamingMessage(request) => self.send_params(&request.params, depth + 1),
+            other => match other.params() {
+                Ok(Value::Object(params)) => {
+                    for (key, value) in &params {
+                        self.line(depth + 1, format_args!("{}: {}", key, value));
+                    }
+                }
+                Ok(params) => self.raw(depth + 1, &params),
+                Err(e) => self.line(depth + 1, format_args!("params unavailable: {}", e)),
+            },
+        }
+    }
+
+    fn send_params(&mut self, params: &SendMessageParams, depth: usize) {
+        self.message("message", &params.message, depth);
+        if let Some(configuration) = &params.configuration {
+            let mut settings = Vec::new();
+            if let Some(blocking) = configuration.blocking {
+                settings.push(if blocking { "blocking".to_string() } else { "non-blocking".to_string() });
+            }
+            if let Some(modes) = &configuration.accepted_output_modes {
+                settings.push(format!("accepts {}", modes.join(", ")));
+            }
+            if let Some(length) = configuration.history_length {
+                settings.push(format!("history length {}", length));
+            }
+            if let Some(push) = &configuration.push_notification_config {
+                settings.push(format!("push notifications to {}", push.url));
+            }
+            self.line(depth, format_args!("configuration: {}", settings.join("; ")));
+            self.extra(&configuration.extra, depth + 1);
+        }
+        self.metadata(params.metadata.as_ref(), depth);
+        self.extra(&params.extra, depth);
+    }
+
+    fn result(&mut self, value: &Value, depth: usize) {
+        match value.get("kind").and_then(Value::as_str) {
+            Some("task") => match Task::deserialize(value) {
+                Ok(task) => self.task(&task, depth),
+                Err(e) => self.invalid("task", e, value, depth),
+            },
+            Some("message") => match Message::deserialize(value) {
+                Ok(message) => {
+                    self.ended = true;
+                    self.message("message", &message, depth);
+                }
+                Err(e) => self.invalid("message", e, value, depth),
+            },
+            Some("status-update") => match TaskStatusUpdateEvent::deserialize(value) {
+                Ok(event) => self.status_update(&event, depth),
+                Err(e) => self.invalid("status update", e, value, depth),
+            },
+            Some("artifact-update") => match TaskArtifactUpdateEvent::deserialize(value) {
+                Ok(event) => self.artifact_update(&event, depth),
+                Err(e) => self.invalid("artifact update", e, value, depth),
+            },
+            _ => match value {
+                Value::Array(items) => {
+                    self.line(depth, count(items.len(), "item"));
+                    for item in items {
+                        self.result(item, depth + 1);
+                    }
+                }
+                Value::Object(fields) => {
+                    for (key, value) in fields {
+                        self.line(depth, format_args!("{}: {}", key, value));
+                    }
+                }
+                scalar => self.line(depth, scalar),
+            },
+        }
+    }
+
+    fn error(&mut self, value: &Value, depth: usize) {
+        let error = match A2AErrorResponse::deserialize(value) {
+            Ok(error) => error,
+            Err(e) => return self.invalid("error", e, value, depth),
+        };
+        self.line(depth, format_args!("code {}: {}", error.code, code_meaning(error.code)));
+        self.line(depth, format_args!("message: {:?}", error.message));
+        if let Some(data) = &error.data {
+            self.line(depth, format_args!("data: {}", data));
+        }
+    }
+
+    /// Record a task's state and describe how it got there.
+    fn transition(&mut self, task_id: &str, state: &TaskState) -> String {
+        let index = match self.timeline.iter().position(|(id, _)| id == task_id) {
+            Some(index) => index,
+            None => {
+                self.timeline.push((task_id.to_string(), Vec::new()));
+                self.timeline.len() - 1
+            }
+        };
+        let states = &mut self.timeline[index].1;
+        let previous = states.last().cloned();
+        if previous.as_ref() != Some(state) {
+            states.push(state.clone());
+        }
+        match previous {
+            Some(previous) if previous != *state => {
+                let valid = previous.successors().contains(state);
+                let note = if valid { "" } else { " (not a valid transition)" };
+                format!("{} → {}{}", state_name(&previous), state_name(state), note)
+            }
+            _ => state_name(state),
+        }
+    }
+
+    fn status(&mut self, task_id: &str, status: &TaskStatus) -> String {
+        let mut text = self.transition(task_id, &status.state);
+        if let Some(timestamp) = &status.timestamp {
+            let _ = write!(text, " at {}", timestamp);
+        }
+        text
+    }
+
+    fn task(&mut self, task: &Task, depth: usize) {
+        let status = self.status(&task.id, &task.status);
+        self.line(depth, format_args!("task {} in context {}: {}", task.id, task.context_id, status));
+        // Streams end once the task finishes or waits for the user
+        let state = &task.status.state;
+        if state.is_terminal() || matches!(state, TaskState::InputRequired | TaskState::AuthRequired) {
+            self.ended = true;
+        }
+        if let Some(message) = &task.status.message {
+            self.message("status message", message, depth + 1);
+        }
+        self.extra(&task.status.extra, depth + 1);
+        if let Some(error) = &task.error {
+            let meaning = code_meaning(error.code);
+            self.line(depth + 1, format_args!("error {}: {}: {:?}", error.code, meaning, error.message));
+        }
+        if let Some(history) = &task.history {
+            self.line(depth + 1, format_args!("history: {}", count(history.len(), "message")));
+            for message in history {
+                self.message("message", message, depth + 2);
+            }
+        }
+        for artifact in task.artifacts.iter().flatten() {
+            self.artifact(artifact, depth + 1);
+        }
+        self.metadata(task.metadata.as_ref(), depth + 1);
+        self.extra(&task.extra, depth + 1);
+    }
+
+    fn message(&mut self, label: &str, message: &Message, depth: usize) {
+        let mut header = format!("{} {} from {}", label, message.message_id, message.role);
+        if let Some(task_id) = &message.task_id {
+            let _ = write!(header, ", task {}", task_id);
+        }
+        if let Some(context_id) = &message.context_id {
+            let _ = write!(header, ", context {}", context_id);
+        }
+        if let Some(references) = message.reference_task_ids.as_ref().filter(|ids| !ids.is_empty()) {
+            let _ = write!(header, ", references {}", references.join(", "));
+        }
+        self.line(depth, header);
+        self.parts(&message.parts, depth + 1);
+        if let Some(extensions) = message.extensions.as_ref().filter(|uris| !uris.is_empty()) {
+            self.line(depth + 1, format_args!("extensions: {}", extensions.join(", ")));
+        }
+        self.metadata(message.metadata.as_ref(), depth + 1);
+        self.extra(&message.extra, depth + 1);
+    }
+
+    fn parts(&mut self, parts: &[Part], depth: usize) {
+        for (index, part) in parts.iter().enumerate() {
+            let number = index + 1;
+            let metadata = match part {
+                Part::Text(text) => {
+                    let (size, preview) = (text.text.len(), preview(&text.text));
+                    self.line(depth, format_args!("part {}: text, {} bytes: {}", number, size, preview));
+                    text.metadata.as_ref()
+                }
+                Part::File(file) => {
+                    let (name, mime_type, location) = match &file.file {
+                        FileContent::WithBytes(bytes) => {
+                            (&bytes.name, &bytes.mime_type, format!("{} bytes inline", decoded_len(&bytes.bytes)))
+                        }
+                        FileContent::WithUri(uri) => (&uri.name, &uri.mime_type, format!("at {}", uri.uri)),
+                    };
+                    let name = name.as_deref().unwrap_or("(unnamed)");
+                    let mime_type = mime_type.as_deref().unwrap_or("unknown type");
+                    self.line(depth, format_args!("part {}: file {} ({}), {}", number, name, mime_type, location));
+                    file.metadata.as_ref()
+                }
+                Part::Data(data) => {
+                    let size = serde_json::to_string(&data.data).map_or(0, |json| json.len());
+                    let shape = shape(&data.data);
+                    self.line(depth, format_args!("part {}: data, {}, {} bytes of JSON", number, shape, size));
+                    data.metadata.as_ref()
+                }
+                Part::Unknown(unknown) => {
+                    self.line(depth, format_args!("part {}: unknown kind {}", number, unknown.kind));
+                    self.raw(depth + 1, &serde_json::to_value(unknown).unwrap_or_default());
+                    None
+                }
+            };
+            self.metadata(metadata, depth + 1);
+        }
+    }
+
+    fn artifact(&mut self, artifact: &Artifact, depth: usize) {
+        let mut header = format!("artifact {}", artifact.artifact_id);
+        if let Some(name) = &artifact.name {
+            let _ = write!(header, " {:?}", name);
+        }
+        let _ = write!(header, ", {}", count(artifact.parts.len(), "part"));
+        self.line(depth, header);
+        if let Some(description) = &artifact.description {
+            self.line(depth + 1, format_args!("description: {:?}", description));
+        }
+        self.parts(&artifact.parts, depth + 1);
+        self.metadata(artifact.metadata.as_ref(), depth + 1);
+        self.extra(&artifact.extra, depth + 1);
+    }
+
+    fn status_update(&mut self, event: &TaskStatusUpdateEvent, depth: usize) {
+        let mut status = self.status(&event.task_id, &event.status);
+        if event.final_event {
+            self.ended = true;
+            status.push_str(", final");
+        }
+        let (task_id, context_id) = (&event.task_id, &event.context_id);
+        self.line(depth, format_args!("status-update for task {} in context {}: {}", task_id, context_id, status));
+        if let Some(message) = &event.status.message {
+            self.message("status message", message, depth + 1);
+        }
+        self.metadata(event.metadata.as_ref(), depth + 1);
+        self.extra(&event.extra, depth + 1);
+    }
+
+    fn artifact_update(&mut self, event: &TaskArtifactUpdateEvent, depth: usize) {
+        let mut header = format!("artifact-update for task {} in context {}", event.task_id, event.context_id);
+        if event.append == Some(true) {
+            header.push_str(", append");
+        }
+        if event.last_chunk == Some(true) {
+            header.push_str(", last chunk");
+        }
+        self.line(depth, header);
+        self.artifact(&event.artifact, depth + 1);
+        self.metadata(event.metadata.as_ref(), depth + 1);
+        self.extra(&event.extra, depth + 1);
+    }
+
+    fn card(&mut self, card: &AgentCard, depth: usize) {
+        let (name, version) = (&card.name, &card.version);
+        self.line(depth, format_args!("agent card {:?} version {}, protocol {}", name, version, card.protocol_version));
+        let transport = card.preferred_transport.as_deref().unwrap_or("JSONRPC");
+        self.line(depth + 1, format_args!("url: {} ({})", card.url, transport));
+        for interface in card.additional_interfaces.iter().flatten() {
+            self.line(depth + 1, format_args!("interface: {} ({})", interface.url, interface.transport));
+        }
+        let capabilities = &card.capabilities;
+        let flags = [
+            (capabilities.streaming, "streaming"),
+            (capabilities.push_notifications, "push notifications"),
+            (capabilities.state_transition_history, "state transition history"),
+        ];
+        let enabled: Vec<_> = flags.iter().filter(|(flag, _)| *flag == Some(true)).map(|(_, name)| *name).collect();
+        let enabled = if enabled.is_empty() { "none".to_string() } else { enabled.join(", ") };
+        self.line(depth + 1, format_args!("capabilities: {}", enabled));
+        for extension in capabilities.extensions.iter().flatten() {
+            self.line(depth + 2, format_args!("extension {}", extension.uri));
+        }
+        self.line(depth + 1, format_args!("input modes: {}", card.default_input_modes.join(", ")));
+        self.line(depth + 1, format_args!("output modes: {}", card.default_output_modes.join(", ")));
+        self.line(depth + 1, format_args!("skills: {}", card.skills.len()));
+        for skill in &card.skills {
+            self.line(depth + 2, format_args!("skill {:?}: {:?}", skill.name, skill.description));
+        }
+        if let Some(schemes) = card.security_schemes.as_ref().filter(|schemes| !schemes.is_empty()) {
+            let names: Vec<_> = schemes.keys().map(String::as_str).collect();
+            self.line(depth + 1, format_args!("security schemes: {}", names.join(", ")));
+        }
+        self.extra(&card.extra, depth + 1);
+    }
+
+    fn metadata(&mut self, metadata: Option<&Value>, depth: usize) {
+        if let Some(metadata) = metadata {
+            self.line(depth, format_args!("metadata: {}", metadata));
+        }
+    }
+
+    fn extra(&mut self, extra: &ExtraFields, depth: usize) {
+        if !extra.is_empty() {
+            self.line(depth, format_args!("unknown fields: {}", Value::Object(extra.0.clone())));
+        }
+    }
+}
+
+fn id_label(envelope: &Value) -> String {
+    match envelope.get("id") {
+        Some(id) => format!("id {}", id),
+        None => "no id".to_string(),
+    }
+}
+
+fn state_name(state: &TaskState) -> String {
+    match serde_json::to_value(state) {
+        Ok(Value::String(name)) => name,
+        _ => format!("{:?}", state),
+    }
+}
+
+/// The name of an error code and whether JSON-RPC or A2A defines it.
+fn code_meaning(code: i32) -> String {
+    match A2AError::code_name(code) {
+        Some(name) if code <= -32600 => format!("{} (JSON-RPC)", name),
+        Some(name) => format!("{} (A2A)", name),
+        None if (-32099..=-32000).contains(&code) => "implementation-defined server error".to_string(),
+        None => "not a standard code".to_string(),
+    }
+}
+
+/// The beginning of a text, quoted.
+fn preview(text: &str) -> String {
+    match text.char_indices().nth(PREVIEW_CHARS) {
+        Some((end, _)) => format!("{:?}…", &text[..end]),
+        None => format!("{:?}", text),
+    }
+}
+
+/// The decoded size of base64 content.
+fn decoded_len(base64: &str) -> usize {
+    let digits = base64.bytes().filter(|byte| !byte.is_ascii_whitespace() && *byte != b'=').count();
+    digits * 3 / 4
+}
+
+/// A number of things, e.g. `1 part` or `2 parts`.
+fn count(n: usize, noun: &str) -> String {
+    if n == 1 { format!("1 {}", noun) } else { format!("{} {}s", n, noun) }
+}
+
+/// The type of a JSON value, with the size of objects and arrays.
+fn shape(value: &Value) -> String {
+    match value {
+        Value::Object(fields) => format!("object with {}", count(fields.len(), "key")),
+        Value::Array(items) => format!("array of {}", count(items.len(), "item")),
+        Value::String(_) => "string".to_string(),
+        Value::Number(_) => "number".to_string(),
+        Value::Bool(_) => "boolean".to_string(),
+        Value::Null => "null".to_string(),
+    }
+}
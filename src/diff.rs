@@ -0,0 +1,366 @@
+//! Change detection between two versions of an agent card.
+//!
+//! Clients that cache agent cards can use [`AgentCard::diff`] to decide
+//! whether a refreshed card needs attention: [`CardDiff::is_breaking`] flags
+//! changes that can break existing callers, while edits such as a new
+//! description are reported but harmless.
+
+use crate::Map;
+use crate::{AgentCard, AgentSkill, SecurityScheme};
+use std::collections::BTreeSet;
+
+/// A change to one of the agent's skills, keyed by skill name.
+///
+/// The skills are boxed so a diff of a few changes stays small.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SkillChange {
+    /// The skill is new.
+    Added(Box<AgentSkill>),
+    /// The skill is no longer offered.
+    Removed(Box<AgentSkill>),
+    /// The skill is still offered but its description or modes changed.
+    Modified { old: Box<AgentSkill>, new: Box<AgentSkill> },
+}
+
+/// A change to one of the boolean capability flags.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapabilityChange {
+    /// The wire name of the capability, e.g. `streaming`.
+    pub capability: &'static str,
+    /// The previous value.
+    pub old: Option<bool>,
+    /// The new value.
+    pub new: Option<bool>,
+}
+
+impl CapabilityChange {
+    /// Check if a previously declared capability was withdrawn.
+    pub fn is_removal(&self) -> bool {
+        self.old.unwrap_or(false) && !self.new.unwrap_or(false)
+    }
+}
+
+/// A change to the security schemes or requirements.
+///
+/// The schemes are boxed like the skills of [`SkillChange`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecurityChange {
+    /// A security scheme was declared.
+    SchemeAdded { name: String, scheme: Box<SecurityScheme> },
+    /// A security scheme was withdrawn.
+    SchemeRemoved { name: String, scheme: Box<SecurityScheme> },
+    /// A security scheme's definition changed.
+    SchemeModified {
+        name: String,
+        old: Box<SecurityScheme>,
+        new: Box<SecurityScheme>,
+    },
+    /// The security requirements changed.
+    RequirementsChanged {
+        old: Option<Vec<Map<String, Vec<String>>>>,
+        new: Option<Vec<Map<String, Vec<String>>>>,
+    },
+}
+
+/// A change to any other top-level card field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    /// The wire name of the field, e.g. `url`.
+    pub field: &'static str,
+    /// The previous value, `null` if absent.
+    pub old: serde_json::Value,
+    /// The new value, `null` if absent.
+    pub new: serde_json::Value,
+}
+
+/// Categorized changes between two agent cards.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CardDiff {
+    /// Added, removed and modified skills.
+    pub skills: Vec<SkillChange>,
+    /// Capability flags that changed.
+    pub capabilities: Vec<CapabilityChange>,
+    /// Security scheme and requirement changes.
+    pub security: Vec<SecurityChange>,
+    /// Changes to the remaining fields, such as `url` and `version`.
+    pub fields: Vec<FieldChange>,
+}
+
+/// Fields whose changes never affect callers.
+const COSMETIC_FIELDS: &[&str] = &[
+    "name",
+    "description",
+    "x-nameLocalizations",
+    "x-descriptionLocalizations",
+    "documentationUrl",
+    "iconUrl",
+    "provider",
+];
+
+impl CardDiff {
+    /// Check if the cards are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.skills.is_empty() && self.capabilities.is_empty() && self.security.is_empty() && self.fields.is_empty()
+    }
+
+    /// Check if only descriptive fields changed.
+    pub fn is_cosmetic(&self) -> bool {
+        self.skills.is_empty()
+            && self.capabilities.is_empty()
+            && self.security.is_empty()
+            && self.fields.iter().all(|change| COSMETIC_FIELDS.contains(&change.field))
+    }
+
+    /// Check if the changes can break existing callers.
+    ///
+    /// Removed skills, withdrawn capabilities, removed or redefined security
+    /// schemes, and tightened security requirements are breaking. Security
+    /// requirements are tightened when any previously accepted alternative
+    /// is no longer accepted.
+    pub fn is_breaking(&self) -> bool {
+        self.skills.iter().any(|change| matches!(change, SkillChange::Removed(_)))
+            || self.capabilities.iter().any(CapabilityChange::is_removal)
+            || self.security.iter().any(|change| match change {
+                SecurityChange::SchemeAdded { .. } => false,
+                SecurityChange::SchemeRemoved { .. } | SecurityChange::SchemeModified { .. } => true,
+                SecurityChange::RequirementsChanged { old, new } => requirements_tightened(old, new),
+            })
+    }
+}
+
+/// Check if a client satisfying the old requirements may fail the new ones.
+fn requirements_tightened(
+    old: &Option<Vec<Map<String, Vec<String>>>>,
+    new: &Option<Vec<Map<String, Vec<String>>>>,
+) -> bool {
+    let new = match new.as_deref() {
+        None | Some([]) => return false,
+        Some(new) => new,
+    };
+
+    match old.as_deref() {
+        None | Some([]) => true,
+        Some(old) => old.iter().any(|alternative| !new.contains(alternative)),
+    }
+}
+
+impl AgentCard {
+    /// Compare this card with a newer version of it.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The newer card.
+    ///
+    /// # Returns
+    ///
+    /// The categorized changes from `self` to `other`.
+    pub fn diff(&self, other: &AgentCard) -> CardDiff {
+        let mut diff = CardDiff::default();
+
+        // Skills, keyed by name
+        for skill in &self.skills {
+            match other.skills.iter().find(|s| s.name == skill.name) {
+                None => diff.skills.push(SkillChange::Removed(Box::new(skill.clone()))),
+                Some(new) if new != skill => diff.skills.push(SkillChange::Modified {
+                    old: Box::new(skill.clone()),
+                    new: Box::new(new.clone()),
+                }),
+                Some(_) => {}
+            }
+        }
+        for skill in &other.skills {
+            if !self.skills.iter().any(|s| s.name == skill.name) {
+                diff.skills.push(SkillChange::Added(Box::new(skill.clone())));
+            }
+        }
+
+        // Capability flags
+        let flags = [
+            ("streaming", self.capabilities.streaming, other.capabilities.streaming),
+            ("pushNotifications", self.capabilities.push_notifications, other.capabilities.push_notifications),
+            (
+                "stateTransitionHistory",
+                self.capabilities.state_transition_history,
+                other.capabilities.state_transition_history,
+            ),
+        ];
+        for (capability, old, new) in flags {
+            if old.unwrap_or(false) != new.unwrap_or(false) {
+                diff.capabilities.push(CapabilityChange { capability, old, new });
+            }
+        }
+
+        // Security schemes and requirements
+        let no_schemes = Map::new();
+        let old_schemes = self.security_schemes.as_ref().unwrap_or(&no_schemes);
+        let new_schemes = other.security_schemes.as_ref().unwrap_or(&no_schemes);
+        let names: BTreeSet<&String> = old_schemes.keys().chain(new_schemes.keys()).collect();
+        for name in names {
+            match (old_schemes.get(name), new_schemes.get(name)) {
+                (Some(old), None) => diff.security.push(SecurityChange::SchemeRemoved {
+                    name: name.clone(),
+                    scheme: Box::new(old.clone()),
+                }),
+                (None, Some(new)) => diff.security.push(SecurityChange::SchemeAdded {
+                    name: name.clone(),
+                    scheme: Box::new(new.clone()),
+                }),
+                (Some(old), Some(new)) if old != new => diff.security.push(SecurityChange::SchemeModified {
+                    name: name.clone(),
+                    old: Box::new(old.clone()),
+                    new: Box::new(new.clone()),
+                }),
+                _ => {}
+            }
+        }
+        if self.security != other.security {
+            diff.security.push(SecurityChange::RequirementsChanged {
+                old: self.security.clone(),
+                new: other.security.clone(),
+            });
+        }
+
+        // Remaining fields
+        let extension_uris = |card: &AgentCard| -> Vec<String> {
+            card.capabilities.extensions.iter().flatten().map(|e| e.uri.clone()).collect()
+        };
+        let fields = [
+            ("name", to_value(&self.name), to_value(&other.name)),
+            ("description", to_value(&self.description), to_value(&other.description)),
+            ("x-nameLocalizations", to_value(&self.name_localizations), to_value(&other.name_localizations)),
+            (
+                "x-descriptionLocalizations",
+                to_value(&self.description_localizations),
+                to_value(&other.description_localizations),
+            ),
+            ("version", to_value(&self.version), to_value(&other.version)),
+            ("protocolVersion", to_value(&self.protocol_version), to_value(&other.protocol_version)),
+            ("url", to_value(&self.url), to_value(&other.url)),
+            ("preferredTransport", to_value(&self.preferred_transport), to_value(&other.preferred_transport)),
+            ("additionalInterfaces", to_value(&self.additional_interfaces), to_value(&other.additional_interfaces)),
+            ("defaultInputModes", to_value(&self.default_input_modes), to_value(&other.default_input_modes)),
+            ("defaultOutputModes", to_value(&self.default_output_modes), to_value(&other.default_output_modes)),
+            ("extensions", to_value(&extension_uris(self)), to_value(&extension_uris(other))),
+            ("provider", to_value(&self.provider), to_value(&other.provider)),
+            ("documentationUrl", to_value(&self.documentation_url), to_value(&other.documentation_url)),
+            ("iconUrl", to_value(&self.icon_url), to_value(&other.icon_url)),
+            (
+                "supportsAuthenticatedExtendedCard",
+                to_value(&self.supports_authenticated_extended_card),
+                to_value(&other.supports_authenticated_extended_card),
+            ),
+        ];
+        for (field, old, new) in fields {
+            if old != new {
+                diff.fields.push(FieldChange { field, old, new });
+            }
+        }
+
+        diff
+    }
+}
+
+fn to_value<T: serde::Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or(serde_json::Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> AgentCard {
+        serde_json::from_value(serde_json::json!({
+            "name": "Translator",
+            "description": "Translates text",
+            "version": "1.0.0",
+            "protocolVersion": "0.2.5",
+            "url": "https://example.com/a2a",
+            "capabilities": {"streaming": true, "pushNotifications": false},
+            "defaultInputModes": ["text/plain"],
+            "defaultOutputModes": ["text/plain"],
+            "skills": [
+                {"name": "translate", "description": "Translate text"},
+                {"name": "detect", "description": "Detect the language"}
+            ],
+            "security": [{"apiKey": []}],
+            "securitySchemes": {
+                "apiKey": {"type": "apiKey", "in": "header", "name": "X-API-Key"}
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_identical_cards_have_no_diff() {
+        assert!(fixture().diff(&fixture()).is_empty());
+    }
+
+    #[test]
+    fn test_cosmetic_changes_are_not_breaking() {
+        let mut new = fixture();
+        new.description = "Translates text between 40 languages".to_string();
+
+        let diff = fixture().diff(&new);
+        assert_eq!(diff.fields.len(), 1);
+        assert_eq!(diff.fields[0].field, "description");
+        assert!(diff.is_cosmetic());
+        assert!(!diff.is_breaking());
+    }
+
+    #[test]
+    fn test_mixed_changes_are_categorized() {
+        let mut new = fixture();
+        new.version = "2.0.0".to_string();
+        new.skills.retain(|s| s.name != "detect");
+        new.skills[0].description = "Translate text and documents".to_string();
+        new.skills.push(AgentSkill::minimal("summarize".to_string(), "Summarize text".to_string()));
+        new.capabilities.streaming = None;
+        new.capabilities.push_notifications = Some(true);
+
+        let diff = fixture().diff(&new);
+        assert_eq!(diff.skills.len(), 3);
+        assert!(matches!(&diff.skills[0], SkillChange::Modified { new, .. } if new.name == "translate"));
+        assert!(matches!(&diff.skills[1], SkillChange::Removed(s) if s.name == "detect"));
+        assert!(matches!(&diff.skills[2], SkillChange::Added(s) if s.name == "summarize"));
+
+        assert_eq!(diff.capabilities.len(), 2);
+        assert!(diff.capabilities[0].is_removal());
+        assert_eq!(diff.capabilities[1].capability, "pushNotifications");
+        assert!(!diff.capabilities[1].is_removal());
+
+        assert_eq!(diff.fields[0].field, "version");
+        assert_eq!(diff.fields[0].old, "1.0.0");
+        assert_eq!(diff.fields[0].new, "2.0.0");
+        assert!(diff.security.is_empty());
+        assert!(diff.is_breaking());
+        assert!(!diff.is_cosmetic());
+    }
+
+    #[test]
+    fn test_security_tightening_is_breaking() {
+        let old = fixture();
+
+        // Adding an alternative only loosens the requirements
+        let mut loosened = fixture();
+        loosened.security = Some(vec![
+            Map::from([("apiKey".to_string(), vec![])]),
+            Map::from([("oauth".to_string(), vec!["read".to_string()])]),
+        ]);
+        let diff = old.diff(&loosened);
+        assert_eq!(diff.security.len(), 1);
+        assert!(!diff.is_breaking());
+
+        // Replacing the only alternative tightens them
+        let mut tightened = fixture();
+        tightened.security = Some(vec![Map::from([("oauth".to_string(), vec!["read".to_string()])])]);
+        assert!(old.diff(&tightened).is_breaking());
+
+        // Removing a scheme is breaking, adding one is not
+        let mut removed = fixture();
+        removed.security_schemes = None;
+        let diff = old.diff(&removed);
+        assert!(matches!(&diff.security[0], SecurityChange::SchemeRemoved { name, .. } if name == "apiKey"));
+        assert!(diff.is_breaking());
+        assert!(!removed.diff(&old).is_breaking());
+    }
+}
@@ -0,0 +1,466 @@
+//! Agent card discovery.
+//!
+//! Agents publish their card at a well-known path below their base URL, or
+//! are listed in a registry document. This module does no I/O itself:
+//! [`discover_agent`] takes a `fetch` callback, so it works with any HTTP
+//! client, and [`parse_agent_registry`] works on an already fetched body.
+//! On the serving side, [`AgentCardDocument`] renders the card with the
+//! headers any web framework needs to serve it.
+
+use crate::hash::fnv1a;
+use crate::{A2AError, AgentCard, InvalidAgentResponseError, InvalidParamsError, JSONParseError};
+use serde_json::Value;
+use std::sync::OnceLock;
+
+/// Well-known card locations, in the order they are tried.
+pub const WELL_KNOWN_PATHS: &[&str] = &["/.well-known/agent.json", "/.well-known/agent-card.json"];
+
+/// A location or registry entry that did not yield a card.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveryFailure {
+    /// The URL that was tried, or the index of the registry entry.
+    pub location: String,
+    /// Why no card was obtained from it.
+    pub error: String,
+}
+
+/// What happened during discovery.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiscoveryReport {
+    /// The URL the card was loaded from, when discovering a single agent.
+    pub matched_url: Option<String>,
+    /// Locations tried before the match, or registry entries that were skipped.
+    pub skipped: Vec<DiscoveryFailure>,
+}
+
+/// Build the candidate card URLs for an agent.
+///
+/// # Arguments
+///
+/// * `base_url` - The agent's base URL, or the full URL of a card document.
+///
+/// # Returns
+///
+/// The URLs to try, in order. A URL that already ends in `.json` is returned as is.
+pub fn candidate_urls(base_url: &str) -> Vec<String> {
+    if base_url.ends_with(".json") {
+        return vec![base_url.to_string()];
+    }
+
+    let base = base_url.trim_end_matches('/');
+    WELL_KNOWN_PATHS.iter().map(|path| format!("{}{}", base, path)).collect()
+}
+
+/// Parse and validate a fetched agent card.
+///
+/// # Arguments
+///
+/// * `body` - The fetched JSON document.
+///
+/// # Returns
+///
+/// The card, or `Err(String)` with the reason it was rejected.
+pub fn parse_agent_card(body: &str) -> Result<AgentCard, String> {
+    let card: AgentCard = serde_json::from_str(body).map_err(|e| format!("Invalid agent card: {}", e))?;
+    card.validate()?;
+    Ok(card)
+}
+
+/// Discover an agent's card by trying each well-known location in turn.
+///
+/// # Arguments
+///
+/// * `base_url` - The agent's base URL.
+/// * `fetch` - Fetches a URL, returning the response body or an error for
+///   any failure, including non-success status codes.
+///
+/// # Returns
+///
+/// The first valid card with a report of the locations that were skipped,
+/// or an `InvalidAgentResponse` error listing every failed location.
+pub fn discover_agent<F, E>(base_url: &str, mut fetch: F) -> Result<(AgentCard, DiscoveryReport), A2AError>
+where
+    F: FnMut(&str) -> Result<String, E>,
+    E: std::fmt::Display,
+{
+    let mut report = DiscoveryReport::default();
+
+    for url in candidate_urls(base_url) {
+        let result = fetch(&url)
+            .map_err(|e| e.to_string())
+            .and_then(|body| parse_agent_card(&body));
+
+        match result {
+            Ok(card) => {
+                report.matched_url = Some(url);
+                return Ok((card, report));
+            }
+            Err(error) => report.skipped.push(DiscoveryFailure { location: url, error }),
+        }
+    }
+
+    let attempts: Vec<_> = report
+        .skipped
+        .iter()
+        .map(|failure| serde_json::json!({"url": failure.location, "error": failure.error}))
+        .collect();
+
+    Err(A2AError::InvalidAgentResponse(Box::new(InvalidAgentResponseError {
+        code: -32006,
+        message: format!("No agent card found at {}", base_url),
+        data: Some(serde_json::json!({"attempts": attempts})),
+    })))
+}
+
+/// Parse a registry document listing several agents.
+///
+/// The document is either an array of cards or an object with an `agents`
+/// array. Entries that fail to parse or validate are skipped and reported
+/// rather than failing the whole list.
+///
+/// # Arguments
+///
+/// * `json` - The registry document.
+///
+/// # Returns
+///
+/// The valid cards with a report of the skipped entries, or an error if the
+/// document itself is not valid JSON or not in a registry format.
+pub fn parse_agent_registry(json: &str) -> Result<(Vec<AgentCard>, DiscoveryReport), A2AError> {
+    let document: serde_json::Value = serde_json::from_str(json).map_err(|e| A2AError::JSONParse(Box::new(JSONParseError {
+        code: -32700,
+        message: format!("Invalid JSON payload: {}", e),
+        data: None,
+    })))?;
+
+    let entries = match document {
+        serde_json::Value::Array(entries) => entries,
+        serde_json::Value::Object(mut object) => match object.remove("agents") {
+            Some(serde_json::Value::Array(entries)) => entries,
+            _ => return Err(registry_format_error()),
+        },
+        _ => return Err(registry_format_error()),
+    };
+
+    let mut cards = Vec::with_capacity(entries.len());
+    let mut report = DiscoveryReport::default();
+
+    for (index, entry) in entries.into_iter().enumerate() {
+        let result = serde_json::from_value::<AgentCard>(entry)
+            .map_err(|e| format!("Invalid agent card: {}", e))
+            .and_then(|card| card.validate().map(|_| card));
+
+        match result {
+            Ok(card) => cards.push(card),
+            Err(error) => report.skipped.push(DiscoveryFailure {
+                location: index.to_string(),
+                error,
+            }),
+        }
+    }
+
+    Ok((cards, report))
+}
+
+fn registry_format_error() -> A2AError {
+    A2AError::InvalidParams(Box::new(InvalidParamsError {
+        code: -32602,
+        message: "Agent registry must be an array of cards or an object with an \"agents\" array".to_string(),
+        data: None,
+    }))
+}
+
+/// The `Content-Type` of agent cards.
+pub const CARD_CONTENT_TYPE: &str = "application/json";
+
+/// The `Cache-Control` of agent cards unless set otherwise.
+pub const DEFAULT_CARD_CACHE_CONTROL: &str = "public, max-age=300";
+
+/// An agent card rendered for serving at a well-known path.
+///
+/// The body is the canonical JSON of the card, with object keys sorted,
+/// so the same card always renders to the same bytes regardless of the
+/// order of its maps. The ETag is a hash of the body. Both are computed on
+/// first use and again after [`AgentCardDocument::set_card`].
+///
+/// ```
+/// use a2a_rs::discovery::AgentCardDocument;
+/// use a2a_rs::{AgentCapabilities, AgentCard};
+///
+/// let card = AgentCard::new(
+///     "Echo".to_string(),
+///     "Echoes messages".to_string(),
+///     "1.0.0".to_string(),
+///     "https://example.com/a2a".to_string(),
+///     AgentCapabilities::default(),
+///     vec!["text/plain".to_string()],
+///     vec!["text/plain".to_string()],
+///     vec![],
+/// );
+/// let document = AgentCardDocument::new(card);
+/// // In a handler of `GET /.well-known/agent-card.json`
+/// let if_none_match = Some(document.etag().to_string());
+/// let (status, body) = match if_none_match {
+///     Some(header) if document.matches_if_none_match(&header) => (304, ""),
+///     _ => (200, document.body()),
+/// };
+/// assert_eq!(status, 304);
+/// for (name, value) in document.headers() {
+///     // response.header(name, value)
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AgentCardDocument {
+    card: AgentCard,
+    cache_control: String,
+    rendered: OnceLock<Rendered>,
+}
+
+#[derive(Debug, Clone)]
+struct Rendered {
+    body: String,
+    etag: String,
+}
+
+impl AgentCardDocument {
+    /// Create a document serving the card with the default `Cache-Control`.
+    pub fn new(card: AgentCard) -> Self {
+        Self {
+            card,
+            cache_control: DEFAULT_CARD_CACHE_CONTROL.to_string(),
+            rendered: OnceLock::new(),
+        }
+    }
+
+    /// Set the `Cache-Control` header value, e.g. `no-cache` for cards that
+    /// change often.
+    pub fn with_cache_control(mut self, cache_control: impl Into<String>) -> Self {
+        self.cache_control = cache_control.into();
+        self
+    }
+
+    /// Get the card.
+    pub fn card(&self) -> &AgentCard {
+        &self.card
+    }
+
+    /// Replace the card, e.g. after its skills changed.
+    ///
+    /// The body and ETag are rendered again on next use.
+    pub fn set_card(&mut self, card: AgentCard) {
+        self.card = card;
+        self.rendered = OnceLock::new();
+    }
+
+    /// Get the JSON body.
+    pub fn body(&self) -> &str {
+        &self.rendered().body
+    }
+
+    /// Get the strong ETag of the body, quoted as the `ETag` header needs.
+    pub fn etag(&self) -> &str {
+        &self.rendered().etag
+    }
+
+    /// Get the `Content-Type` header value.
+    pub fn content_type(&self) -> &'static str {
+        CARD_CONTENT_TYPE
+    }
+
+    /// Get the `Cache-Control` header value.
+    pub fn cache_control(&self) -> &str {
+        &self.cache_control
+    }
+
+    /// Get the headers to send with the body, or with a `304 Not Modified`.
+    pub fn headers(&self) -> [(&'static str, &str); 3] {
+        [
+            ("Content-Type", self.content_type()),
+            ("ETag", self.etag()),
+            ("Cache-Control", self.cache_control()),
+        ]
+    }
+
+    /// Check if a request's `If-None-Match` header matches the card, so
+    /// the response should be `304 Not Modified` without a body.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The header value: `*` or a comma-separated list of
+    ///   ETags, compared weakly as RFC 9110 requires for this header.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the client's copy is current.
+    pub fn matches_if_none_match(&self, header: &str) -> bool {
+        let etag = self.etag();
+        header.split(',').map(str::trim).any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag)
+    }
+
+    fn rendered(&self) -> &Rendered {
+        self.rendered.get_or_init(|| {
+            let card = serde_json::to_value(&self.card).expect("agent cards always serialize");
+            let body = sort_keys(card).to_string();
+            let etag = format!("\"{:016x}\"", fnv1a(body.as_bytes()));
+            Rendered { body, etag }
+        })
+    }
+}
+
+/// Rebuild objects with their keys in order, whichever map `serde_json`
+/// was built with.
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<(String, Value)> = object.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(entries.into_iter().map(|(key, value)| (key, sort_keys(value))).collect())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ApiKeyLocation, ApiKeySecurityScheme, SecurityScheme};
+
+    fn card(name: &str) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "description": "Test agent",
+            "version": "1.0.0",
+            "protocolVersion": "0.2.5",
+            "url": "https://example.com/a2a",
+            "capabilities": {},
+            "defaultInputModes": ["text/plain"],
+            "defaultOutputModes": ["text/plain"],
+            "skills": []
+        })
+    }
+
+    #[test]
+    fn test_candidate_urls() {
+        assert_eq!(
+            candidate_urls("https://example.com/"),
+            [
+                "https://example.com/.well-known/agent.json",
+                "https://example.com/.well-known/agent-card.json"
+            ]
+        );
+        assert_eq!(candidate_urls("https://example.com/card.json"), ["https://example.com/card.json"]);
+    }
+
+    #[test]
+    fn test_discovery_falls_back_in_order() {
+        let mut requested = Vec::new();
+        let (card, report) = discover_agent("https://example.com", |url: &str| {
+            requested.push(url.to_string());
+            if url.ends_with("agent-card.json") {
+                Ok(card("Fallback Agent").to_string())
+            } else {
+                Err("404 Not Found")
+            }
+        })
+        .unwrap();
+
+        assert_eq!(card.name, "Fallback Agent");
+        assert_eq!(requested, candidate_urls("https://example.com"));
+        assert_eq!(report.matched_url.as_deref(), Some("https://example.com/.well-known/agent-card.json"));
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].error, "404 Not Found");
+    }
+
+    #[test]
+    fn test_discovery_skips_invalid_cards_and_reports_all_failures() {
+        let result = discover_agent("https://example.com", |url: &str| -> Result<String, String> {
+            if url.ends_with("agent.json") {
+                Ok(card("").to_string())
+            } else {
+                Ok("<html>".to_string())
+            }
+        });
+
+        match result.unwrap_err() {
+            A2AError::InvalidAgentResponse(e) => {
+                let attempts = e.data.unwrap()["attempts"].as_array().unwrap().clone();
+                assert_eq!(attempts.len(), 2);
+                assert_eq!(attempts[0]["error"], "Agent name cannot be empty");
+            }
+            other => panic!("Expected InvalidAgentResponse, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_registry_skips_broken_entries() {
+        let registry = serde_json::json!({"agents": [card("First"), {"name": "Broken"}, card("Third")]});
+
+        let (cards, report) = parse_agent_registry(&registry.to_string()).unwrap();
+        assert_eq!(cards.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), ["First", "Third"]);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].location, "1");
+        assert!(report.matched_url.is_none());
+
+        let list = serde_json::json!([card("Only")]);
+        assert_eq!(parse_agent_registry(&list.to_string()).unwrap().0.len(), 1);
+
+        assert!(matches!(parse_agent_registry("{\"cards\": []}"), Err(A2AError::InvalidParams(_))));
+        assert!(matches!(parse_agent_registry("["), Err(A2AError::JSONParse(_))));
+    }
+
+    fn card_with_schemes(names: &[&str]) -> AgentCard {
+        let mut card: AgentCard = serde_json::from_value(card("Echo")).unwrap();
+        let schemes = names.iter().map(|name| {
+            let scheme = SecurityScheme::ApiKey(ApiKeySecurityScheme::new(ApiKeyLocation::Header, format!("X-{}", name)));
+            (name.to_string(), scheme)
+        });
+        card.security_schemes = Some(schemes.collect());
+        card
+    }
+
+    #[test]
+    fn test_card_document_is_stable() {
+        let names = ["alpha", "bravo", "charlie", "delta", "echo", "foxtrot"];
+        let mut reversed = names;
+        reversed.reverse();
+        let document = AgentCardDocument::new(card_with_schemes(&names));
+        // Maps filled in another order render the same
+        for _ in 0..8 {
+            let other = AgentCardDocument::new(card_with_schemes(&reversed));
+            assert_eq!(other.body(), document.body());
+            assert_eq!(other.etag(), document.etag());
+        }
+
+        let etag = document.etag();
+        assert!(etag.len() == 18 && etag.starts_with('"') && etag.ends_with('"'));
+        assert!(document.body().find("\"alpha\"").unwrap() < document.body().find("\"bravo\"").unwrap());
+        assert_eq!(serde_json::from_str::<AgentCard>(document.body()).unwrap(), *document.card());
+        assert_eq!(document.headers(), [
+            ("Content-Type", "application/json"),
+            ("ETag", etag),
+            ("Cache-Control", "public, max-age=300"),
+        ]);
+    }
+
+    #[test]
+    fn test_card_document_revalidation() {
+        let mut document = AgentCardDocument::new(card_with_schemes(&["key"])).with_cache_control("no-cache");
+        assert_eq!(document.cache_control(), "no-cache");
+        let etag = document.etag().to_string();
+
+        assert!(document.matches_if_none_match(&etag));
+        assert!(document.matches_if_none_match(&format!("\"stale\", W/{}", etag)));
+        assert!(document.matches_if_none_match("*"));
+        assert!(!document.matches_if_none_match("\"stale\""));
+        assert!(!document.matches_if_none_match(etag.trim_matches('"')));
+        assert!(!document.matches_if_none_match(""));
+
+        let mut card = document.card().clone();
+        card.description = "Echoes louder".to_string();
+        document.set_card(card);
+        assert_ne!(document.etag(), etag);
+        assert!(!document.matches_if_none_match(&etag));
+        assert!(document.body().contains("Echoes louder"));
+    }
+}
@@ -0,0 +1,363 @@
+//! Protocol errors: the JSON-RPC error objects and the A2A-specific ones,
+//! together as [`A2AError`], and their wire form [`A2AErrorResponse`].
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// JSON-RPC error indicating invalid JSON was received by the server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct JSONParseError {
+    /// A Number that indicates the error type that occurred.
+    pub code: i32, // Always -32700
+    /// A String providing a short description of the error.
+    pub message: String,
+    /// A Primitive or Structured value that contains additional information about the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+/// JSON-RPC error indicating the JSON sent is not a valid Request object.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct InvalidRequestError {
+    /// A Number that indicates the error type that occurred.
+    pub code: i32, // Always -32600
+    /// A String providing a short description of the error.
+    pub message: String,
+    /// A Primitive or Structured value that contains additional information about the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+/// JSON-RPC error indicating the method does not exist / is not available.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MethodNotFoundError {
+    /// A Number that indicates the error type that occurred.
+    pub code: i32, // Always -32601
+    /// A String providing a short description of the error.
+    pub message: String,
+    /// A Primitive or Structured value that contains additional information about the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+/// JSON-RPC error indicating invalid method parameter(s).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct InvalidParamsError {
+    /// A Number that indicates the error type that occurred.
+    pub code: i32, // Always -32602
+    /// A String providing a short description of the error.
+    pub message: String,
+    /// A Primitive or Structured value that contains additional information about the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+/// JSON-RPC error indicating an internal JSON-RPC error on the server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct InternalError {
+    /// A Number that indicates the error type that occurred.
+    pub code: i32, // Always -32603
+    /// A String providing a short description of the error.
+    pub message: String,
+    /// A Primitive or Structured value that contains additional information about the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+/// A2A specific error indicating the requested task ID was not found.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TaskNotFoundError {
+    /// A Number that indicates the error type that occurred.
+    pub code: i32, // Always -32001
+    /// A String providing a short description of the error.
+    pub message: String,
+    /// A Primitive or Structured value that contains additional information about the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+/// A2A specific error indicating the task is in a state where it cannot be canceled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TaskNotCancelableError {
+    /// A Number that indicates the error type that occurred.
+    pub code: i32, // Always -32002
+    /// A String providing a short description of the error.
+    pub message: String,
+    /// A Primitive or Structured value that contains additional information about the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+/// A2A specific error indicating the agent does not support push notifications.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PushNotificationNotSupportedError {
+    /// A Number that indicates the error type that occurred.
+    pub code: i32, // Always -32003
+    /// A String providing a short description of the error.
+    pub message: String,
+    /// A Primitive or Structured value that contains additional information about the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+/// A2A specific error indicating the requested operation is not supported by the agent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UnsupportedOperationError {
+    /// A Number that indicates the error type that occurred.
+    pub code: i32, // Always -32004
+    /// A String providing a short description of the error.
+    pub message: String,
+    /// A Primitive or Structured value that contains additional information about the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+/// A2A specific error indicating incompatible content types between request and agent capabilities.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ContentTypeNotSupportedError {
+    /// A Number that indicates the error type that occurred.
+    pub code: i32, // Always -32005
+    /// A String providing a short description of the error.
+    pub message: String,
+    /// A Primitive or Structured value that contains additional information about the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+/// A2A specific error indicating agent returned invalid response for the current method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct InvalidAgentResponseError {
+    /// A Number that indicates the error type that occurred.
+    pub code: i32, // Always -32006
+    /// A String providing a short description of the error.
+    pub message: String,
+    /// A Primitive or Structured value that contains additional information about the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+/// A2A Error union type.
+///
+/// Each variant is boxed so `Result<T, A2AError>` stays small on the
+/// success path; the serialized form is unchanged.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum A2AError {
+    /// JSON parse error.
+    JSONParse(Box<JSONParseError>),
+    /// Invalid request error.
+    InvalidRequest(Box<InvalidRequestError>),
+    /// Method not found error.
+    MethodNotFound(Box<MethodNotFoundError>),
+    /// Invalid parameters error.
+    InvalidParams(Box<InvalidParamsError>),
+    /// Internal error.
+    Internal(Box<InternalError>),
+    /// Task not found error.
+    TaskNotFound(Box<TaskNotFoundError>),
+    /// Task not cancelable error.
+    TaskNotCancelable(Box<TaskNotCancelableError>),
+    /// Push notification not supported error.
+    PushNotificationNotSupported(Box<PushNotificationNotSupportedError>),
+    /// Unsupported operation error.
+    UnsupportedOperation(Box<UnsupportedOperationError>),
+    /// Content type not supported error.
+    ContentTypeNotSupported(Box<ContentTypeNotSupportedError>),
+    /// Invalid agent response error.
+    InvalidAgentResponse(Box<InvalidAgentResponseError>),
+}
+
+// A boxed payload and a discriminant.
+const _: () = assert!(core::mem::size_of::<A2AError>() <= 2 * core::mem::size_of::<usize>());
+
+/// The error table: the standard code, variant, payload type and display
+/// prefix of every error.
+///
+/// Parsing, display and the conversions to and from [`A2AErrorResponse`]
+/// are all generated from this one table, and the generated matches are
+/// exhaustive, so a new variant fails to compile until it has a row.
+macro_rules! error_table {
+    ($($code:literal => $variant:ident($error:ident), $prefix:literal;)*) => {
+        impl A2AError {
+            /// The standard code of every variant, in declaration order.
+            pub const CODES: &'static [i32] = &[$($code),*];
+
+            /// Get the standard code of the variant.
+            ///
+            /// This is the code the variant is parsed from. The payload
+            /// usually carries the same code, but an [`A2AError::Internal`]
+            /// converted from an unknown code keeps the unknown one.
+            pub const fn standard_code(&self) -> i32 {
+                match self {
+                    $(A2AError::$variant(_) => $code,)*
+                }
+            }
+
+            /// Get the name of a standard error code, as in the spec's
+            /// error table, e.g. `Task not found` for -32001.
+            ///
+            /// # Returns
+            ///
+            /// The name, or `None` if the code is not a standard one.
+            pub const fn code_name(code: i32) -> Option<&'static str> {
+                match code {
+                    $($code => Some($prefix),)*
+                    _ => None,
+                }
+            }
+
+            /// Get the additional data of the error.
+            pub fn data(&self) -> Option<&serde_json::Value> {
+                match self {
+                    $(A2AError::$variant(e) => e.data.as_ref(),)*
+                }
+            }
+
+            /// Build the variant of a code; unknown codes become
+            /// [`A2AError::Internal`].
+            fn from_parts(code: i32, message: String, data: Option<serde_json::Value>) -> Self {
+                match code {
+                    $($code => A2AError::$variant(Box::new($error { code, message, data })),)*
+                    _ => A2AError::Internal(Box::new(InternalError { code, message, data })),
+                }
+            }
+
+            /// Deserialize the payload of the variant of a code, or `None`
+            /// for unknown codes.
+            fn deserialize_code(code: i64, value: serde_json::Value) -> Option<Result<Self, serde_json::Error>> {
+                match code {
+                    $($code => Some($error::deserialize(value).map(|e| A2AError::$variant(Box::new(e)))),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl core::fmt::Display for A2AError {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match self {
+                    $(A2AError::$variant(e) => write!(f, concat!($prefix, ": {}"), e.message),)*
+                }
+            }
+        }
+
+        impl From<A2AError> for A2AErrorResponse {
+            fn from(error: A2AError) -> Self {
+                match error {
+                    $(A2AError::$variant(e) => A2AErrorResponse {
+                        code: e.code,
+                        message: e.message,
+                        data: e.data,
+                    },)*
+                }
+            }
+        }
+    };
+}
+
+error_table! {
+    -32700 => JSONParse(JSONParseError), "JSON parse error";
+    -32600 => InvalidRequest(InvalidRequestError), "Invalid request";
+    -32601 => MethodNotFound(MethodNotFoundError), "Method not found";
+    -32602 => InvalidParams(InvalidParamsError), "Invalid parameters";
+    -32603 => Internal(InternalError), "Internal error";
+    -32001 => TaskNotFound(TaskNotFoundError), "Task not found";
+    -32002 => TaskNotCancelable(TaskNotCancelableError), "Task not cancelable";
+    -32003 => PushNotificationNotSupported(PushNotificationNotSupportedError), "Push notification not supported";
+    -32004 => UnsupportedOperation(UnsupportedOperationError), "Unsupported operation";
+    -32005 => ContentTypeNotSupported(ContentTypeNotSupportedError), "Content type not supported";
+    -32006 => InvalidAgentResponse(InvalidAgentResponseError), "Invalid agent response";
+}
+
+// No two variants share a code.
+const _: () = {
+    let codes = A2AError::CODES;
+    let mut i = 0;
+    while i < codes.len() {
+        let mut j = i + 1;
+        while j < codes.len() {
+            assert!(codes[i] != codes[j], "two A2AError variants share a code");
+            j += 1;
+        }
+        i += 1;
+    }
+};
+
+impl A2AError {
+    /// The key of the error data that marks an error as worth retrying.
+    pub const RETRYABLE_KEY: &'static str = "retryable";
+
+    /// Check if the agent marked the error as worth retrying, with
+    /// `"retryable": true` in its data, e.g. while it shuts down.
+    pub fn is_retryable(&self) -> bool {
+        self.data().and_then(|data| data.get(Self::RETRYABLE_KEY)).and_then(serde_json::Value::as_bool) == Some(true)
+    }
+}
+
+impl<'de> Deserialize<'de> for A2AError {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+        let code = value.get("code").ok_or_else(|| D::Error::missing_field("code"))?;
+        // Some SDKs write the code as a float; codes out of range are unknown
+        let code = code
+            .as_i64()
+            .or_else(|| code.as_f64().filter(|c| crate::integers::is_integral(*c)).map(|c| c as i64))
+            .ok_or_else(|| D::Error::custom(format!("Invalid error code: {}", code)))?;
+        value["code"] = code.into();
+
+        A2AError::deserialize_code(code, value)
+            .ok_or_else(|| D::Error::custom(format!("Unknown error code: {}", code)))?
+            .map_err(D::Error::custom)
+    }
+}
+
+impl core::error::Error for A2AError {}
+
+/// A2A error response (used within JSONRPCErrorResponse).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub struct A2AErrorResponse {
+    /// The error code.
+    pub code: i32,
+    /// The error message.
+    pub message: String,
+    /// Additional data about the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::optional_value))]
+    pub data: Option<serde_json::Value>,
+}
+
+impl A2AErrorResponse {
+    /// Convert to the [`A2AError`] variant of the code.
+    ///
+    /// Codes without a variant become [`A2AError::Internal`] keeping the
+    /// original code, so the code, message and data survive either way.
+    pub fn to_a2a_error(&self) -> A2AError {
+        let Self { code, message, data } = self.clone();
+        A2AError::from_parts(code, message, data)
+    }
+}
+
+impl From<A2AErrorResponse> for A2AError {
+    fn from(response: A2AErrorResponse) -> Self {
+        response.to_a2a_error()
+    }
+}
@@ -0,0 +1,555 @@
+//! Events sent by the server on `message/stream` and `tasks/resubscribe`.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::ids::IdSource;
+#[cfg(feature = "std")]
+use crate::clock::format_time;
+use crate::task::now_timestamp;
+use crate::{AgentCard, Artifact, ExtraFields, Message, Part, TaskState, TaskStatus};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+/// Sent by server during sendStream or subscribe requests for artifact updates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "strict-types", non_exhaustive)]
+pub struct TaskArtifactUpdateEvent {
+    /// Event type
+    pub kind: String, // Always "artifact-update"
+    /// Task id
+    #[serde(rename = "taskId")]
+    pub task_id: String,
+    /// The context the task is associated with
+    #[serde(rename = "contextId")]
+    pub context_id: String,
+    /// Generated artifact
+    pub artifact: Artifact,
+    /// Indicates if this artifact appends to a previous one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub append: Option<bool>,
+    /// Indicates if this is the last chunk of the artifact
+    #[serde(rename = "lastChunk", skip_serializing_if = "Option::is_none")]
+    pub last_chunk: Option<bool>,
+    /// Extension metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::object))]
+    pub metadata: Option<serde_json::Value>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+impl TaskArtifactUpdateEvent {
+    /// Create a new task artifact update event.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task ID.
+    /// * `context_id` - The context ID.
+    /// * `artifact` - The generated artifact.
+    ///
+    /// # Returns
+    ///
+    /// A new `TaskArtifactUpdateEvent`.
+    pub fn new(task_id: String, context_id: String, artifact: Artifact) -> Self {
+        Self {
+            kind: "artifact-update".to_string(),
+            task_id,
+            context_id,
+            artifact,
+            append: None,
+            last_chunk: None,
+            metadata: None,
+            extra: ExtraFields::default(),
+        }
+    }
+
+    /// Create an artifact update event, or an error if it would not pass
+    /// [`validate`](Self::validate).
+    pub fn try_new(task_id: String, context_id: String, artifact: Artifact) -> Result<Self, String> {
+        let event = Self::new(task_id, context_id, artifact);
+        event.validate()?;
+        Ok(event)
+    }
+
+    /// Validate the artifact update event.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.kind != "artifact-update" {
+            return Err("TaskArtifactUpdateEvent kind must be 'artifact-update'".to_string());
+        }
+
+        crate::validation::validate_task_id(&self.task_id)?;
+
+        if self.context_id.is_empty() {
+            return Err("Context ID cannot be empty".to_string());
+        }
+
+        self.artifact.validate()?;
+
+        // Any combination of `append` and `lastChunk` is valid: the final
+        // chunk of a streamed artifact appends to the earlier ones and is the
+        // last, and a single-event artifact is the last without appending.
+        // Whether the flags fit the events before is up to the sequence, see
+        // `ArtifactUpdateSequenceBuilder`.
+
+        Ok(())
+    }
+
+    /// Validate the event, and check that the artifact only lists
+    /// extensions the agent or the message declared.
+    ///
+    /// # Arguments
+    ///
+    /// * `card` - The card of the agent producing the artifact.
+    /// * `message` - The message the task is answering, whose extensions
+    ///   the artifact may also list.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, otherwise the error naming the first undeclared
+    /// extension URI.
+    pub fn validate_with_card(&self, card: &AgentCard, message: Option<&Message>) -> Result<(), String> {
+        self.validate()?;
+
+        let declared = |uri: &String| {
+            card.capabilities.extensions.iter().flatten().any(|extension| &extension.uri == uri)
+                || message.and_then(|message| message.extensions.as_ref()).is_some_and(|uris| uris.contains(uri))
+        };
+        match self.artifact.extensions.iter().flatten().find(|uri| !declared(uri)) {
+            Some(uri) => Err(format!("Artifact extension '{}' is not declared by the agent or the message", uri)),
+            None => Ok(()),
+        }
+    }
+
+    /// Check if this is a streaming chunk.
+    ///
+    /// # Returns
+    ///
+    /// `true` if this is part of a streaming sequence.
+    pub fn is_streaming_chunk(&self) -> bool {
+        self.append.unwrap_or(false) || self.last_chunk.unwrap_or(false)
+    }
+
+    /// Check if this is the final chunk in a streaming sequence.
+    ///
+    /// # Returns
+    ///
+    /// `true` if this is the last chunk.
+    pub fn is_final_chunk(&self) -> bool {
+        self.last_chunk.unwrap_or(false)
+    }
+}
+
+/// Produces the artifact update events of one artifact streamed in chunks.
+///
+/// The first event replaces any earlier artifact with the same ID, every
+/// later event has `append` set, and the last has `lastChunk` set. Calls out
+/// of that order fail instead of producing a sequence clients would merge
+/// wrongly.
+///
+/// ```
+/// use a2a_rs::ArtifactUpdateSequenceBuilder;
+///
+/// let mut chunks = ArtifactUpdateSequenceBuilder::new(
+///     "task-1".to_string(),
+///     "ctx-1".to_string(),
+///     "report".to_string(),
+/// );
+/// let first = chunks.first(["Quarterly "]).unwrap();
+/// assert_eq!(first.append, Some(false));
+/// chunks.next(["revenue grew "]).unwrap();
+/// let last = chunks.last(["4%."]).unwrap();
+/// assert_eq!((last.append, last.last_chunk), (Some(true), Some(true)));
+/// assert!(chunks.next(["more"]).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArtifactUpdateSequenceBuilder {
+    task_id: String,
+    context_id: String,
+    artifact_id: String,
+    name: Option<String>,
+    description: Option<String>,
+    started: bool,
+    finished: bool,
+}
+
+impl ArtifactUpdateSequenceBuilder {
+    /// Start the sequence of an artifact.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task ID.
+    /// * `context_id` - The context ID.
+    /// * `artifact_id` - The ID shared by all chunks of the artifact.
+    ///
+    /// # Returns
+    ///
+    /// A builder expecting [`first`](Self::first) or, for an artifact sent
+    /// in one piece, [`last`](Self::last).
+    pub fn new(task_id: String, context_id: String, artifact_id: String) -> Self {
+        Self {
+            task_id,
+            context_id,
+            artifact_id,
+            name: None,
+            description: None,
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Set the artifact name, sent with the first chunk.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the artifact description, sent with the first chunk.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Build the first chunk, which does not append.
+    ///
+    /// # Returns
+    ///
+    /// The event, or an error if the sequence has already started.
+    pub fn first<P: Into<Part>>(&mut self, parts: impl IntoIterator<Item = P>) -> Result<TaskArtifactUpdateEvent, String> {
+        if self.started {
+            return Err(format!("Artifact '{}' has already started", self.artifact_id));
+        }
+        self.chunk(parts, false)
+    }
+
+    /// Build a middle chunk, which appends.
+    ///
+    /// # Returns
+    ///
+    /// The event, or an error if the sequence has not started or is
+    /// finished.
+    pub fn next<P: Into<Part>>(&mut self, parts: impl IntoIterator<Item = P>) -> Result<TaskArtifactUpdateEvent, String> {
+        if !self.started {
+            return Err(format!("Artifact '{}' has not started", self.artifact_id));
+        }
+        self.chunk(parts, false)
+    }
+
+    /// Build the last chunk, which appends unless it is the only one.
+    ///
+    /// # Returns
+    ///
+    /// The event, or an error if the sequence is already finished.
+    pub fn last<P: Into<Part>>(&mut self, parts: impl IntoIterator<Item = P>) -> Result<TaskArtifactUpdateEvent, String> {
+        self.chunk(parts, true)
+    }
+
+    /// Whether the last chunk has been built.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn chunk<P: Into<Part>>(
+        &mut self,
+        parts: impl IntoIterator<Item = P>,
+        last_chunk: bool,
+    ) -> Result<TaskArtifactUpdateEvent, String> {
+        if self.finished {
+            return Err(format!("Artifact '{}' is already finished", self.artifact_id));
+        }
+        let mut artifact = Artifact::minimal(self.artifact_id.clone(), parts.into_iter().map(Into::into).collect());
+        if !self.started {
+            artifact.name = self.name.clone();
+            artifact.description = self.description.clone();
+        }
+        let mut event = TaskArtifactUpdateEvent::new(self.task_id.clone(), self.context_id.clone(), artifact);
+        event.append = Some(self.started);
+        event.last_chunk = Some(last_chunk);
+        event.validate()?;
+
+        self.started = true;
+        self.finished = last_chunk;
+        Ok(event)
+    }
+}
+
+/// Sent by server during sendStream or subscribe requests for status updates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "strict-types", non_exhaustive)]
+pub struct TaskStatusUpdateEvent {
+    /// Event type
+    pub kind: String, // Always "status-update"
+    /// Task id
+    #[serde(rename = "taskId")]
+    pub task_id: String,
+    /// The context the task is associated with
+    #[serde(rename = "contextId")]
+    pub context_id: String,
+    /// Current status of the task
+    pub status: TaskStatus,
+    /// Indicates the end of the event stream
+    #[serde(rename = "final")]
+    pub final_event: bool,
+    /// Extension metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::object))]
+    pub metadata: Option<serde_json::Value>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+const FINAL_STATE_ERROR: &str = "Final status update events must have terminal or interrupted task states";
+
+/// Check if a final status update may have a state: terminal states, and
+/// interrupted states that end the stream until the client answers.
+fn ends_stream(state: &TaskState) -> bool {
+    state.is_terminal() || matches!(state, TaskState::InputRequired | TaskState::AuthRequired)
+}
+
+impl TaskStatusUpdateEvent {
+    /// Create a new task status update event.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task ID.
+    /// * `context_id` - The context ID.
+    /// * `status` - The current task status.
+    /// * `final_event` - Whether this is the final event.
+    ///
+    /// # Returns
+    ///
+    /// A new `TaskStatusUpdateEvent`.
+    pub fn new(task_id: String, context_id: String, status: TaskStatus, final_event: bool) -> Self {
+        Self {
+            kind: "status-update".to_string(),
+            task_id,
+            context_id,
+            status,
+            final_event,
+            metadata: None,
+            extra: ExtraFields::default(),
+        }
+    }
+
+    /// Create a status update event, or an error if it would not pass
+    /// [`validate`](Self::validate), e.g. a final event in the `working`
+    /// state.
+    pub fn try_new(task_id: String, context_id: String, status: TaskStatus, final_event: bool) -> Result<Self, String> {
+        let event = Self::new(task_id, context_id, status, final_event);
+        event.validate()?;
+        Ok(event)
+    }
+
+    /// Replace the status, unless the event is final and the new state
+    /// cannot end the stream.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())`, or an error leaving the event unchanged.
+    pub fn set_status_checked(&mut self, status: TaskStatus) -> Result<(), String> {
+        if self.final_event && !ends_stream(&status.state) {
+            return Err(FINAL_STATE_ERROR.to_string());
+        }
+        self.status = status;
+        Ok(())
+    }
+
+    /// Mark the event as final or not, unless it would be final in a state
+    /// that cannot end the stream.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())`, or an error leaving the event unchanged.
+    pub fn set_final_checked(&mut self, final_event: bool) -> Result<(), String> {
+        if final_event && !ends_stream(&self.status.state) {
+            return Err(FINAL_STATE_ERROR.to_string());
+        }
+        self.final_event = final_event;
+        Ok(())
+    }
+
+    /// Create a `working` update, sent when the agent starts or resumes the
+    /// task.
+    ///
+    /// With the `std` feature the status is stamped with the current time.
+    pub fn working(task_id: String, context_id: String) -> Self {
+        Self::transition(task_id, context_id, TaskState::Working, None, false, now_timestamp())
+    }
+
+    /// Like [`working`](Self::working), with the status stamped with a given
+    /// time, e.g. from a [`Clock`](crate::clock::Clock).
+    #[cfg(feature = "std")]
+    pub fn working_at(task_id: String, context_id: String, time: SystemTime) -> Self {
+        Self::transition(task_id, context_id, TaskState::Working, None, false, Some(format_time(time)))
+    }
+
+    /// Create the final `completed` update.
+    ///
+    /// With the `std` feature the status is stamped with the current time.
+    pub fn completed(task_id: String, context_id: String) -> Self {
+        Self::transition(task_id, context_id, TaskState::Completed, None, true, now_timestamp())
+    }
+
+    /// Like [`completed`](Self::completed), with the status stamped with a given
+    /// time, e.g. from a [`Clock`](crate::clock::Clock).
+    #[cfg(feature = "std")]
+    pub fn completed_at(task_id: String, context_id: String, time: SystemTime) -> Self {
+        Self::transition(task_id, context_id, TaskState::Completed, None, true, Some(format_time(time)))
+    }
+
+    /// Create the final `failed` update, with a message saying what went
+    /// wrong.
+    ///
+    /// With the `std` feature the status is stamped with the current time.
+    pub fn failed(task_id: String, context_id: String, message: Message) -> Self {
+        Self::transition(task_id, context_id, TaskState::Failed, Some(message), true, now_timestamp())
+    }
+
+    /// Like [`failed`](Self::failed), with the status stamped with a given
+    /// time, e.g. from a [`Clock`](crate::clock::Clock).
+    #[cfg(feature = "std")]
+    pub fn failed_at(task_id: String, context_id: String, message: Message, time: SystemTime) -> Self {
+        Self::transition(task_id, context_id, TaskState::Failed, Some(message), true, Some(format_time(time)))
+    }
+
+    /// Create an `input-required` update asking the user a question. The
+    /// event is not final, so the stream stays open for the answer's updates.
+    ///
+    /// With the `std` feature the status is stamped with the current time.
+    pub fn input_required(task_id: String, context_id: String, prompt: Message) -> Self {
+        Self::transition(task_id, context_id, TaskState::InputRequired, Some(prompt), false, now_timestamp())
+    }
+
+    /// Like [`input_required`](Self::input_required), with the status stamped with a given
+    /// time, e.g. from a [`Clock`](crate::clock::Clock).
+    #[cfg(feature = "std")]
+    pub fn input_required_at(task_id: String, context_id: String, prompt: Message, time: SystemTime) -> Self {
+        Self::transition(task_id, context_id, TaskState::InputRequired, Some(prompt), false, Some(format_time(time)))
+    }
+
+    /// Create an update whose status carries a short agent message, built
+    /// by [`TaskStatus::with_agent_text`].
+    ///
+    /// With the `std` feature the status is stamped with the current time.
+    #[cfg(feature = "uuid")]
+    pub fn with_text(
+        task_id: String,
+        context_id: String,
+        state: TaskState,
+        text: impl Into<String>,
+        final_event: bool,
+    ) -> Self {
+        let status = TaskStatus::with_agent_text(state, &task_id, &context_id, text);
+        Self::new(task_id, context_id, status, final_event)
+    }
+
+    /// Create an update whose status carries a short agent message, built
+    /// by [`TaskStatus::with_agent_text_from`].
+    ///
+    /// With the `std` feature the status is stamped with the current time.
+    pub fn with_text_from<I: IdSource + ?Sized>(
+        ids: &mut I,
+        task_id: String,
+        context_id: String,
+        state: TaskState,
+        text: impl Into<String>,
+        final_event: bool,
+    ) -> Self {
+        let status = TaskStatus::with_agent_text_from(ids, state, &task_id, &context_id, text);
+        Self::new(task_id, context_id, status, final_event)
+    }
+
+    /// Like [`with_text_from`](Self::with_text_from), with the status
+    /// stamped with a given time, e.g. from a [`Clock`](crate::clock::Clock).
+    #[cfg(feature = "std")]
+    pub fn with_text_from_at<I: IdSource + ?Sized>(
+        ids: &mut I,
+        task_id: String,
+        context_id: String,
+        state: TaskState,
+        text: impl Into<String>,
+        final_event: bool,
+        time: SystemTime,
+    ) -> Self {
+        let status = TaskStatus::with_agent_text_from_at(ids, state, &task_id, &context_id, text, time);
+        Self::new(task_id, context_id, status, final_event)
+    }
+
+    /// Stamp the status with a time other than the current one, e.g. from a
+    /// [`Clock`](crate::clock::Clock).
+    #[cfg(feature = "std")]
+    pub fn at(mut self, time: SystemTime) -> Self {
+        self.status.timestamp = Some(format_time(time));
+        self
+    }
+
+    fn transition(
+        task_id: String,
+        context_id: String,
+        state: TaskState,
+        message: Option<Message>,
+        final_event: bool,
+        timestamp: Option<String>,
+    ) -> Self {
+        let status = TaskStatus {
+            state,
+            message,
+            timestamp,
+            extra: ExtraFields::default(),
+        };
+        Self::new(task_id, context_id, status, final_event)
+    }
+
+    /// Validate the status update event.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.kind != "status-update" {
+            return Err("TaskStatusUpdateEvent kind must be 'status-update'".to_string());
+        }
+
+        crate::validation::validate_task_id(&self.task_id)?;
+
+        if self.context_id.is_empty() {
+            return Err("Context ID cannot be empty".to_string());
+        }
+
+        if self.final_event && !ends_stream(&self.status.state) {
+            return Err(FINAL_STATE_ERROR.to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Check if this event indicates a terminal state.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the task has reached a terminal state.
+    pub fn is_terminal_state(&self) -> bool {
+        matches!(self.status.state, 
+            TaskState::Completed | TaskState::Failed | TaskState::Canceled | TaskState::Rejected
+        )
+    }
+
+    /// Check if this is the final event in the stream.
+    ///
+    /// # Returns
+    ///
+    /// `true` if this is the final event.
+    pub fn is_final_event(&self) -> bool {
+        self.final_event
+    }
+}
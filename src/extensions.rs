@@ -0,0 +1,795 @@
+//! Parameter validation for agent extensions.
+//!
+//! Extensions are identified by URI, and their `params` are only meaningful
+//! to code that knows that URI. An [`ExtensionRegistry`] maps URIs to
+//! [`ExtensionValidator`]s so applications can validate the extensions they
+//! understand with [`AgentExtension::validate_with`](crate::AgentExtension::validate_with).
+//!
+//! Extensions with a fixed params schema can implement [`TypedExtension`] to
+//! get typed access through [`AgentExtension::params_for`](crate::AgentExtension::params_for)
+//! and validation through [`ExtensionRegistry::register_typed`].
+//!
+//! Clients activate extensions per request by listing their URIs in the
+//! [`EXTENSIONS_HEADER`]. Servers decide which of them to honor with
+//! [`negotiate`], or per request with a [`RequestContext`], and record the
+//! outcome on messages with [`NegotiationResult::apply_to`].
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::validation::ValidationError;
+pub use crate::context::RequestContext;
+use crate::{A2AError, AgentCard, InternalError, InvalidParamsError, InvalidRequestError, Message};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use crate::Map;
+use core::marker::PhantomData;
+
+/// Well-known URI of the OAuth extension validated by [`OAuthExtensionValidator`].
+pub const OAUTH_EXTENSION_URI: &str = "https://github.com/tdrozdowski/a2a-rs/extensions/oauth/v1";
+
+/// Well-known URI of the webhook extension validated by [`WebhookExtensionValidator`].
+pub const WEBHOOK_EXTENSION_URI: &str = "https://github.com/tdrozdowski/a2a-rs/extensions/webhook/v1";
+
+/// URI of the extension under which agents on protocol versions before 0.3
+/// serve the `tasks/list` method.
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+pub const TASKS_LIST_EXTENSION_URI: &str = "https://github.com/tdrozdowski/a2a-rs/extensions/tasks-list/v1";
+
+/// Header listing the extension URIs activated for a request or response.
+pub const EXTENSIONS_HEADER: &str = "X-A2A-Extensions";
+
+/// Validates the parameters of one extension.
+pub trait ExtensionValidator: Send + Sync {
+    /// The extension URI this validator handles.
+    fn uri(&self) -> &str;
+
+    /// Validate the extension parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The extension's `params` object.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the parameters are valid, `Err(ValidationError)` otherwise.
+    fn validate_params(&self, params: &serde_json::Value) -> Result<(), ValidationError>;
+}
+
+/// Extension validators keyed by exact extension URI.
+#[derive(Default)]
+pub struct ExtensionRegistry {
+    validators: Map<String, Box<dyn ExtensionValidator>>,
+}
+
+impl ExtensionRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with the built-in OAuth and webhook validators.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register(OAuthExtensionValidator);
+        registry.register(WebhookExtensionValidator);
+        registry
+    }
+
+    /// Register a validator under its URI.
+    ///
+    /// # Returns
+    ///
+    /// The validator previously registered for the URI, if any.
+    pub fn register<V: ExtensionValidator + 'static>(&mut self, validator: V) -> Option<Box<dyn ExtensionValidator>> {
+        self.validators.insert(validator.uri().to_string(), Box::new(validator))
+    }
+
+    /// Register a [`TypedExtension`], validating params by deserializing
+    /// them into its `Params` type and then calling [`TypedExtension::validate`].
+    ///
+    /// # Returns
+    ///
+    /// The validator previously registered for the URI, if any.
+    pub fn register_typed<E: TypedExtension + 'static>(&mut self) -> Option<Box<dyn ExtensionValidator>> {
+        self.register(TypedValidator::<E>(PhantomData))
+    }
+
+    /// Look up the validator for an extension URI.
+    pub fn get(&self, uri: &str) -> Option<&dyn ExtensionValidator> {
+        self.validators.get(uri).map(|validator| validator.as_ref())
+    }
+
+    /// Check if a validator is registered for an extension URI.
+    pub fn contains(&self, uri: &str) -> bool {
+        self.validators.contains_key(uri)
+    }
+}
+
+impl core::fmt::Debug for ExtensionRegistry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.validators.keys()).finish()
+    }
+}
+
+/// An extension whose params have a known Rust type.
+pub trait TypedExtension {
+    /// The extension URI.
+    const URI: &'static str;
+
+    /// The params type. Unknown fields are allowed unless the type denies them.
+    type Params: Serialize + DeserializeOwned;
+
+    /// Validate rules that the params type cannot express.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The deserialized params.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the parameters are valid, `Err(ValidationError)` otherwise.
+    fn validate(params: &Self::Params) -> Result<(), ValidationError> {
+        let _ = params;
+        Ok(())
+    }
+}
+
+/// Adapts a [`TypedExtension`] to [`ExtensionValidator`].
+struct TypedValidator<E>(PhantomData<fn() -> E>);
+
+impl<E: TypedExtension> ExtensionValidator for TypedValidator<E> {
+    fn uri(&self) -> &str {
+        E::URI
+    }
+
+    fn validate_params(&self, params: &serde_json::Value) -> Result<(), ValidationError> {
+        let params: E::Params = serde_path_to_error::deserialize(params).map_err(|e| {
+            ValidationError::for_field(e.path().to_string(), format!("Invalid params for extension '{}': {}", E::URI, e.inner()))
+        })?;
+        E::validate(&params)
+    }
+}
+
+/// Deserialize extension-scoped data.
+pub(crate) fn from_extension_value<T: DeserializeOwned>(uri: &str, value: &serde_json::Value) -> Result<T, A2AError> {
+    serde_path_to_error::deserialize(value).map_err(|e| {
+        A2AError::InvalidParams(Box::new(InvalidParamsError {
+            code: -32602,
+            message: format!("Invalid params for extension '{}': {}", uri, e.inner()),
+            data: Some(serde_json::json!({"extension": uri, "path": e.path().to_string()})),
+        }))
+    })
+}
+
+/// Serialize extension-scoped data.
+pub(crate) fn to_extension_value<T: Serialize>(uri: &str, value: &T) -> Result<serde_json::Value, A2AError> {
+    serde_json::to_value(value).map_err(|e| {
+        A2AError::Internal(Box::new(InternalError {
+            code: -32603,
+            message: format!("Failed to serialize params for extension '{}': {}", uri, e),
+            data: None,
+        }))
+    })
+}
+
+fn params_object(params: &serde_json::Value) -> Result<&serde_json::Map<String, serde_json::Value>, ValidationError> {
+    params
+        .as_object()
+        .ok_or_else(|| ValidationError::new("Extension params must be a JSON object"))
+}
+
+fn is_http_url(value: &str) -> bool {
+    value.starts_with("http://") || value.starts_with("https://")
+}
+
+/// Validate the params of the OAuth extension at [`OAUTH_EXTENSION_URI`].
+///
+/// The fields are optional, but when present `clientId` must be a non-empty
+/// string, `scopes` an array of strings and `redirectUri` an HTTP or HTTPS
+/// URL.
+///
+/// # Arguments
+///
+/// * `params` - The extension's params object.
+///
+/// # Returns
+///
+/// The first problem found, with the offending field, e.g. `scopes[1]`.
+pub fn validate_oauth_params(params: &serde_json::Map<String, serde_json::Value>) -> Result<(), ValidationError> {
+    if let Some(client_id) = params.get("clientId")
+        && client_id.as_str().is_none_or(|s| s.is_empty())
+    {
+        return Err(ValidationError::for_field("clientId", "Auth extension clientId must be a non-empty string"));
+    }
+
+    if let Some(scopes) = params.get("scopes") {
+        let Some(scopes) = scopes.as_array() else {
+            return Err(ValidationError::for_field("scopes", "Auth extension scopes must be an array"));
+        };
+        if let Some(i) = scopes.iter().position(|scope| !scope.is_string()) {
+            return Err(ValidationError::for_field(
+                format!("scopes[{}]", i),
+                "Auth extension scopes must be strings",
+            ));
+        }
+    }
+
+    if let Some(redirect_uri) = params.get("redirectUri") {
+        let Some(uri_str) = redirect_uri.as_str() else {
+            return Err(ValidationError::for_field("redirectUri", "Auth extension redirectUri must be a string"));
+        };
+        if !is_http_url(uri_str) {
+            return Err(ValidationError::for_field("redirectUri", "Auth extension redirectUri must be a valid URL"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate the params of the webhook extension at [`WEBHOOK_EXTENSION_URI`].
+///
+/// The fields are optional, but when present `url` must be an HTTP or HTTPS
+/// URL, `secret` a string of at least
+/// [`WebhookExtensionValidator::MIN_SECRET_LENGTH`] characters and `events`
+/// a non-empty array of non-empty strings.
+///
+/// # Arguments
+///
+/// * `params` - The extension's params object.
+///
+/// # Returns
+///
+/// The first problem found, with the offending field, e.g. `events[0]`.
+pub fn validate_webhook_params(params: &serde_json::Map<String, serde_json::Value>) -> Result<(), ValidationError> {
+    if let Some(url) = params.get("url") {
+        let Some(url_str) = url.as_str().filter(|s| !s.is_empty()) else {
+            return Err(ValidationError::for_field("url", "Webhook extension url must be a non-empty string"));
+        };
+        if !is_http_url(url_str) {
+            return Err(ValidationError::for_field("url", "Webhook extension url must be a valid HTTP or HTTPS URL"));
+        }
+    }
+
+    if let Some(secret) = params.get("secret") {
+        let Some(secret) = secret.as_str() else {
+            return Err(ValidationError::for_field("secret", "Webhook extension secret must be a string"));
+        };
+        if secret.chars().count() < WebhookExtensionValidator::MIN_SECRET_LENGTH {
+            return Err(ValidationError::for_field(
+                "secret",
+                format!(
+                    "Webhook extension secret must be at least {} characters",
+                    WebhookExtensionValidator::MIN_SECRET_LENGTH
+                ),
+            ));
+        }
+    }
+
+    if let Some(events) = params.get("events") {
+        let Some(events) = events.as_array() else {
+            return Err(ValidationError::for_field("events", "Webhook extension events must be an array"));
+        };
+        if events.is_empty() {
+            return Err(ValidationError::for_field("events", "Webhook extension events cannot be empty"));
+        }
+        if let Some(i) = events.iter().position(|event| event.as_str().is_none_or(|s| s.is_empty())) {
+            return Err(ValidationError::for_field(
+                format!("events[{}]", i),
+                "Webhook extension events must be non-empty strings",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validator for the OAuth extension at [`OAUTH_EXTENSION_URI`].
+///
+/// Checks that the params are an object, then applies
+/// [`validate_oauth_params`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OAuthExtensionValidator;
+
+impl ExtensionValidator for OAuthExtensionValidator {
+    fn uri(&self) -> &str {
+        OAUTH_EXTENSION_URI
+    }
+
+    fn validate_params(&self, params: &serde_json::Value) -> Result<(), ValidationError> {
+        validate_oauth_params(params_object(params)?)
+    }
+}
+
+/// Validator for the webhook extension at [`WEBHOOK_EXTENSION_URI`].
+///
+/// Checks that the params are an object, then applies
+/// [`validate_webhook_params`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WebhookExtensionValidator;
+
+impl WebhookExtensionValidator {
+    /// The minimum length of a webhook secret, in characters.
+    pub const MIN_SECRET_LENGTH: usize = 16;
+}
+
+impl ExtensionValidator for WebhookExtensionValidator {
+    fn uri(&self) -> &str {
+        WEBHOOK_EXTENSION_URI
+    }
+
+    fn validate_params(&self, params: &serde_json::Value) -> Result<(), ValidationError> {
+        validate_webhook_params(params_object(params)?)
+    }
+}
+
+// ============================================================================
+// NEGOTIATION
+// ============================================================================
+
+/// The outcome of matching requested extensions against an agent card.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NegotiationResult {
+    /// Requested extensions the agent supports, in request order.
+    pub activated: Vec<String>,
+    /// Requested extensions the agent does not declare.
+    pub rejected: Vec<String>,
+    /// Extensions the agent requires that were not requested.
+    pub missing_required: Vec<String>,
+}
+
+impl NegotiationResult {
+    /// Check if every required extension was requested.
+    pub fn is_satisfied(&self) -> bool {
+        self.missing_required.is_empty()
+    }
+
+    /// Fail if a required extension was not requested.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())`, or an `InvalidRequest` error listing the missing extensions
+    /// under `data.missingExtensions`.
+    pub fn require_satisfied(&self) -> Result<(), A2AError> {
+        if self.is_satisfied() {
+            return Ok(());
+        }
+
+        Err(A2AError::InvalidRequest(Box::new(InvalidRequestError {
+            code: -32600,
+            message: format!("Required extensions were not requested: {}", self.missing_required.join(", ")),
+            data: Some(serde_json::json!({"missingExtensions": self.missing_required})),
+        })))
+    }
+
+    /// Render the activated extensions as an [`EXTENSIONS_HEADER`] value.
+    pub fn header_value(&self) -> String {
+        render_extensions_header(&self.activated)
+    }
+
+    /// Add the activated extensions to a message's `extensions`.
+    ///
+    /// URIs already listed on the message are kept and not duplicated.
+    pub fn apply_to(&self, message: &mut Message) {
+        activate_on_message(message, &self.activated);
+    }
+}
+
+/// Match the extensions a client requested against those an agent declares.
+///
+/// # Arguments
+///
+/// * `requested` - The requested extension URIs, e.g. parsed from the
+///   [`EXTENSIONS_HEADER`]. Duplicates are ignored.
+/// * `card` - The agent card declaring the supported extensions.
+///
+/// # Returns
+///
+/// The activated, rejected and missing required extensions.
+pub fn negotiate(requested: &[String], card: &AgentCard) -> NegotiationResult {
+    let declared = card.capabilities.extensions.as_deref().unwrap_or_default();
+    let mut result = NegotiationResult::default();
+
+    for uri in requested {
+        if result.activated.contains(uri) || result.rejected.contains(uri) {
+            continue;
+        }
+        if declared.iter().any(|extension| &extension.uri == uri) {
+            result.activated.push(uri.clone());
+        } else {
+            result.rejected.push(uri.clone());
+        }
+    }
+
+    result.missing_required = declared
+        .iter()
+        .filter(|extension| extension.required == Some(true) && !requested.contains(&extension.uri))
+        .map(|extension| extension.uri.clone())
+        .collect();
+
+    result
+}
+
+/// Parse an [`EXTENSIONS_HEADER`] value.
+///
+/// # Arguments
+///
+/// * `value` - The comma-separated header value.
+///
+/// # Returns
+///
+/// The URIs in order, trimmed, without empty entries or duplicates.
+pub fn parse_extensions_header(value: &str) -> Vec<String> {
+    parse_extensions_headers([value])
+}
+
+/// Parse every [`EXTENSIONS_HEADER`] line of a request or response.
+///
+/// HTTP allows a header to be sent several times; the lines are read as if
+/// they were one comma-separated value.
+///
+/// # Arguments
+///
+/// * `values` - The values of the header lines, in order.
+///
+/// # Returns
+///
+/// The URIs in order, trimmed, without empty entries or duplicates.
+pub fn parse_extensions_headers<'a>(values: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut uris: Vec<String> = Vec::new();
+    for uri in values.into_iter().flat_map(|value| value.split(',')).map(str::trim).filter(|uri| !uri.is_empty()) {
+        if !uris.iter().any(|existing| existing == uri) {
+            uris.push(uri.to_string());
+        }
+    }
+    uris
+}
+
+/// Render extension URIs as an [`EXTENSIONS_HEADER`] value.
+///
+/// # Arguments
+///
+/// * `uris` - The extension URIs.
+///
+/// # Returns
+///
+/// The comma-separated header value.
+pub fn render_extensions_header(uris: &[String]) -> String {
+    uris.join(", ")
+}
+
+/// Add extension URIs to a message's `extensions`, skipping duplicates.
+pub(crate) fn activate_on_message(message: &mut Message, uris: &[String]) {
+    if uris.is_empty() {
+        return;
+    }
+
+    let extensions = message.extensions.get_or_insert_with(Vec::new);
+    for uri in uris {
+        if !extensions.contains(uri) {
+            extensions.push(uri.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AgentExtension;
+    use alloc::sync::Arc;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingValidator {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl ExtensionValidator for CountingValidator {
+        fn uri(&self) -> &str {
+            "https://example.com/extensions/pricing"
+        }
+
+        fn validate_params(&self, params: &serde_json::Value) -> Result<(), ValidationError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match params.get("currency") {
+                Some(serde_json::Value::String(_)) => Ok(()),
+                _ => Err(ValidationError::for_field("currency", "currency is required")),
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_validator_is_invoked() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut registry = ExtensionRegistry::new();
+        assert!(registry.register(CountingValidator { calls: calls.clone() }).is_none());
+
+        let uri = "https://example.com/extensions/pricing".to_string();
+        let valid = AgentExtension::with_config(uri.clone(), None, None, Some(serde_json::json!({"currency": "EUR"})));
+        let invalid = AgentExtension::with_config(uri, None, None, Some(serde_json::json!({})));
+
+        assert!(valid.validate_with(&registry).is_ok());
+        assert_eq!(invalid.validate_with(&registry).unwrap_err(), "currency is required");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Without the registry only the structure is checked
+        assert!(invalid.validate().is_ok());
+    }
+
+    #[test]
+    fn test_uri_substrings_do_not_select_validators() {
+        let registry = ExtensionRegistry::with_builtins();
+        let params = serde_json::json!({"clientId": 42, "url": "ftp://example.com"});
+
+        // "author" contains "auth" and "notifications" contains "notification"
+        for uri in ["https://example.com/author-tools", "https://example.com/notifications"] {
+            let extension = AgentExtension::with_config(uri.to_string(), None, None, Some(params.clone()));
+            assert!(extension.validate().is_ok());
+            assert!(extension.validate_with(&registry).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_builtin_validators_use_well_known_uris() {
+        let registry = ExtensionRegistry::with_builtins();
+        assert!(registry.contains(OAUTH_EXTENSION_URI));
+        assert!(registry.contains(WEBHOOK_EXTENSION_URI));
+
+        let oauth = AgentExtension::with_config(
+            OAUTH_EXTENSION_URI.to_string(),
+            None,
+            None,
+            Some(serde_json::json!({"clientId": 42})),
+        );
+        assert_eq!(oauth.validate_with(&registry).unwrap_err(), "Auth extension clientId must be a non-empty string");
+
+        let error = WebhookExtensionValidator
+            .validate_params(&serde_json::json!({"url": "ftp://example.com"}))
+            .unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("url"));
+    }
+
+    #[test]
+    fn test_builtin_validators_reject_non_object_params() {
+        for params in [serde_json::json!(["clientId"]), serde_json::json!("clientId"), serde_json::Value::Null] {
+            let error = OAuthExtensionValidator.validate_params(&params).unwrap_err();
+            assert_eq!(error.field, None);
+            assert_eq!(error.message, "Extension params must be a JSON object");
+            assert!(WebhookExtensionValidator.validate_params(&params).is_err());
+        }
+
+        let empty = serde_json::Map::new();
+        assert!(validate_oauth_params(&empty).is_ok());
+        assert!(validate_webhook_params(&empty).is_ok());
+    }
+
+    #[test]
+    fn test_builtin_validators_check_nested_values() {
+        let object = |value: serde_json::Value| value.as_object().cloned().unwrap();
+
+        let error = validate_oauth_params(&object(serde_json::json!({"scopes": ["read", 7]}))).unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("scopes[1]"));
+        assert!(validate_oauth_params(&object(serde_json::json!({"clientId": "abc", "scopes": ["read"]}))).is_ok());
+
+        let error = validate_webhook_params(&object(serde_json::json!({"events": []}))).unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("events"));
+        let error = validate_webhook_params(&object(serde_json::json!({"events": ["task.completed", ""]}))).unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("events[1]"));
+
+        let error = validate_webhook_params(&object(serde_json::json!({"secret": "short"}))).unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("secret"));
+        assert_eq!(error.message, "Webhook extension secret must be at least 16 characters");
+
+        let valid = serde_json::json!({
+            "url": "https://example.com/hook",
+            "secret": "0123456789abcdef",
+            "events": ["task.completed"],
+        });
+        assert!(validate_webhook_params(&object(valid.clone())).is_ok());
+        assert!(WebhookExtensionValidator.validate_params(&valid).is_ok());
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct OAuthParams {
+        client_id: String,
+        scopes: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        redirect_uri: Option<String>,
+    }
+
+    struct OAuthExtension;
+
+    impl TypedExtension for OAuthExtension {
+        const URI: &'static str = "https://example.com/extensions/oauth/v2";
+        type Params = OAuthParams;
+
+        fn validate(params: &OAuthParams) -> Result<(), ValidationError> {
+            if params.scopes.is_empty() {
+                return Err(ValidationError::for_field("scopes", "At least one scope is required"));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_typed_params_allow_unknown_fields() {
+        let extension = AgentExtension::with_config(
+            OAuthExtension::URI.to_string(),
+            None,
+            None,
+            Some(serde_json::json!({"clientId": "abc", "scopes": ["read"], "audience": "api"})),
+        );
+
+        let expected = OAuthParams {
+            client_id: "abc".to_string(),
+            scopes: vec!["read".to_string()],
+            redirect_uri: None,
+        };
+        assert_eq!(extension.params_as::<OAuthParams>().unwrap(), Some(expected));
+        assert!(extension.params_for::<OAuthExtension>().unwrap().is_some());
+
+        let other = AgentExtension::with_config("https://example.com/other".to_string(), None, None, extension.params.clone());
+        assert_eq!(other.params_for::<OAuthExtension>().unwrap(), None);
+
+        let mut empty = AgentExtension::new(OAuthExtension::URI.to_string());
+        assert_eq!(empty.params_as::<OAuthParams>().unwrap(), None);
+        empty
+            .set_params_from(&OAuthParams {
+                client_id: "xyz".to_string(),
+                scopes: vec![],
+                redirect_uri: Some("https://example.com/cb".to_string()),
+            })
+            .unwrap();
+        assert_eq!(empty.params.as_ref().unwrap()["redirectUri"], "https://example.com/cb");
+
+        // Registered typed extensions validate both shape and custom rules
+        let mut registry = ExtensionRegistry::new();
+        registry.register_typed::<OAuthExtension>();
+        assert!(extension.validate_with(&registry).is_ok());
+        assert_eq!(empty.validate_with(&registry).unwrap_err(), "At least one scope is required");
+
+        let wrong = AgentExtension::with_config(
+            OAuthExtension::URI.to_string(),
+            None,
+            None,
+            Some(serde_json::json!({"clientId": 1, "scopes": []})),
+        );
+        assert!(wrong.validate_with(&registry).unwrap_err().contains("Invalid params for extension"));
+        match wrong.params_as::<OAuthParams>().unwrap_err() {
+            A2AError::InvalidParams(e) => assert_eq!(e.data.unwrap()["path"], "clientId"),
+            other => panic!("Expected InvalidParams, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_extension_data_in_message_metadata() {
+        use crate::metadata::{MetadataExt, extension_key};
+        use crate::{Message, MessageRole};
+
+        let mut message = Message::minimal("msg-1".to_string(), MessageRole::User, vec![]);
+        assert_eq!(message.extension_data_as::<OAuthParams>(OAuthExtension::URI).unwrap(), None);
+
+        let params = OAuthParams {
+            client_id: "abc".to_string(),
+            scopes: vec!["read".to_string()],
+            redirect_uri: None,
+        };
+        message.set_extension_data(OAuthExtension::URI, &params).unwrap();
+
+        let key = extension_key(OAuthExtension::URI);
+        assert_eq!(key, "extensions.https://example.com/extensions/oauth/v2");
+        assert_eq!(message.get_metadata(&key).unwrap()["clientId"], "abc");
+        assert_eq!(message.extension_data_as::<OAuthParams>(OAuthExtension::URI).unwrap(), Some(params));
+    }
+
+    #[test]
+    fn test_extensions_header_round_trip() {
+        let uris = parse_extensions_header(" https://example.com/a ,https://example.com/b,, https://example.com/a ");
+        assert_eq!(uris, ["https://example.com/a", "https://example.com/b"]);
+
+        let header = render_extensions_header(&uris);
+        assert_eq!(header, "https://example.com/a, https://example.com/b");
+        assert_eq!(parse_extensions_header(&header), uris);
+        assert!(parse_extensions_header("  ").is_empty());
+
+        // Repeated header lines
+        let uris = parse_extensions_headers(["https://example.com/a", " https://example.com/b , https://example.com/a", ""]);
+        assert_eq!(uris, ["https://example.com/a", "https://example.com/b"]);
+    }
+
+    #[test]
+    fn test_request_context_activation() {
+        let mut context = RequestContext::from_headers(["https://example.com/a, https://example.com/b", "https://example.com/a"]);
+        assert_eq!(context.requested(), ["https://example.com/a", "https://example.com/b"]);
+        assert_eq!(context.header_value(), None);
+
+        assert!(!context.activate("https://example.com/unrequested"));
+        assert!(context.activate("https://example.com/b"));
+        assert!(context.activate("https://example.com/b"));
+        assert_eq!(context.activated(), ["https://example.com/b"]);
+        assert_eq!(context.header_value().as_deref(), Some("https://example.com/b"));
+        assert_eq!(RequestContext::new(vec!["x".to_string(), "x".to_string()]).requested(), ["x"]);
+    }
+
+    #[test]
+    fn test_negotiate_reports_missing_required_extension() {
+        let mut card = crate::AgentCard::new(
+            "Agent".to_string(),
+            "Test agent".to_string(),
+            "1.0.0".to_string(),
+            "https://example.com/a2a".to_string(),
+            crate::AgentCapabilities::default(),
+            vec!["text/plain".to_string()],
+            vec!["text/plain".to_string()],
+            vec![],
+        );
+        card.capabilities.extensions = Some(vec![
+            AgentExtension::new("https://example.com/tracing".to_string()),
+            AgentExtension::with_config("https://example.com/billing".to_string(), None, Some(true), None),
+        ]);
+
+        let requested = parse_extensions_header("https://example.com/tracing, https://example.com/unknown");
+        let result = negotiate(&requested, &card);
+        assert_eq!(result.activated, ["https://example.com/tracing"]);
+        assert_eq!(result.rejected, ["https://example.com/unknown"]);
+        assert_eq!(result.missing_required, ["https://example.com/billing"]);
+        assert_eq!(result.header_value(), "https://example.com/tracing");
+
+        match result.require_satisfied().unwrap_err() {
+            A2AError::InvalidRequest(e) => {
+                assert_eq!(e.data.unwrap()["missingExtensions"][0], "https://example.com/billing")
+            }
+            other => panic!("Expected InvalidRequest, got {:?}", other),
+        }
+
+        let mut message = Message::user("msg-1".to_string(), ["hi"]);
+        message.extensions = Some(vec!["https://example.com/tracing".to_string()]);
+        result.apply_to(&mut message);
+        assert_eq!(message.extensions.as_deref().unwrap(), ["https://example.com/tracing"]);
+
+        let requested = parse_extensions_header("https://example.com/billing");
+        assert!(negotiate(&requested, &card).require_satisfied().is_ok());
+    }
+
+    #[test]
+    fn test_extension_uri_validation() {
+        use crate::validation::validate_extension_uri;
+        use crate::{Artifact, TaskArtifactUpdateEvent};
+
+        assert_eq!(AgentExtension::new("https://a.io/x".to_string()).validate_uri(), Ok(()));
+        assert_eq!(validate_extension_uri("http://a"), Ok(()));
+        for uri in ["", "urn:example:ext", "https://", "https:///path", "https://a.io/x y"] {
+            assert!(validate_extension_uri(uri).is_err(), "{}", uri);
+        }
+
+        let mut message = Message::user("msg-1".to_string(), ["hi"]);
+        message.extensions = Some(vec!["https://a.io/x".to_string(), "ftp://a.io/y".to_string()]);
+        assert!(message.validate_extensions().unwrap_err().contains("ftp://a.io/y"));
+        message.extensions = Some(vec!["https://a.io/x".to_string()]);
+        assert_eq!(message.validate_extensions(), Ok(()));
+
+        let mut card = crate::AgentCard::new(
+            "Agent".to_string(),
+            "Test agent".to_string(),
+            "1.0.0".to_string(),
+            "https://example.com/a2a".to_string(),
+            crate::AgentCapabilities::default(),
+            vec!["text/plain".to_string()],
+            vec!["text/plain".to_string()],
+            vec![],
+        );
+        card.capabilities.extensions = Some(vec![AgentExtension::new("https://a.io/card".to_string())]);
+
+        let mut artifact = Artifact::minimal("artifact-1".to_string(), vec!["Report".into()]);
+        artifact.extensions = Some(vec!["https://a.io/card".to_string(), "https://a.io/x".to_string()]);
+        let mut event = TaskArtifactUpdateEvent::new("task-1".to_string(), "ctx-1".to_string(), artifact);
+        assert_eq!(event.validate(), Ok(()));
+        assert_eq!(event.validate_with_card(&card, Some(&message)), Ok(()));
+        assert_eq!(
+            event.validate_with_card(&card, None),
+            Err("Artifact extension 'https://a.io/x' is not declared by the agent or the message".to_string())
+        );
+
+        event.artifact.extensions = Some(vec!["not a uri".to_string()]);
+        assert!(event.validate().is_err());
+    }
+}
@@ -0,0 +1,324 @@
+//! Form requests and responses carried in data parts.
+//!
+//! An agent that needs several inputs at once can ask for them as a form:
+//! a data part holding a [`FormRequest`], marked by the `a2a.form` metadata
+//! key set to `request`. The client answers with a data part holding a
+//! [`FormResponse`], marked with `response`. This is a convention on top of
+//! the protocol, not part of it; peers that do not know it see an ordinary
+//! data part.
+//!
+//! ```
+//! use a2a_rs::form::{FormField, FormRequest, FormResponse};
+//! use a2a_rs::Part;
+//!
+//! let request = FormRequest::new()
+//!     .with_title("Book a flight")
+//!     .with_field(FormField::text("from").with_label("From").required())
+//!     .with_field(FormField::choice("class", ["economy", "business"]));
+//! let part = Part::from(request.clone());
+//! assert_eq!(part.as_form_request(), Some(request));
+//!
+//! let response = FormResponse::new().with_value("from", "WAW").with_value("class", "economy");
+//! assert_eq!(Part::from(response).as_form_response().unwrap().get("from"), Some(&"WAW".into()));
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::metadata::get_meta;
+use crate::{DataPart, Part};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Metadata key marking a data part as a form request or response.
+pub const FORM_KEY: &str = "a2a.form";
+/// Value of [`FORM_KEY`] on form requests.
+pub const FORM_REQUEST: &str = "request";
+/// Value of [`FORM_KEY`] on form responses.
+pub const FORM_RESPONSE: &str = "response";
+
+/// Inputs an agent asks the user for.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FormRequest {
+    /// Heading of the form
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The inputs, in display order
+    pub fields: Vec<FormField>,
+}
+
+/// One input of a [`FormRequest`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormField {
+    /// Key of the value in the [`FormResponse`]
+    pub name: String,
+    /// Human-readable label, defaulting to the name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Kind of value expected
+    #[serde(rename = "type")]
+    pub field_type: FormFieldType,
+    /// Whether the form cannot be submitted without this value
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    pub required: bool,
+    /// Allowed values of a choice field
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+}
+
+/// Kinds of value a [`FormField`] expects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FormFieldType {
+    /// A string
+    Text,
+    /// A JSON number
+    Number,
+    /// A JSON boolean
+    Boolean,
+    /// One of the field's options
+    Choice,
+}
+
+/// The values a user filled into a [`FormRequest`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FormResponse {
+    /// Values keyed by field name
+    pub values: Map<String, Value>,
+}
+
+impl FormRequest {
+    /// Create a form without fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the heading of the form.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Append an input to the form.
+    pub fn with_field(mut self, field: FormField) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Look up an input by name.
+    pub fn field(&self, name: &str) -> Option<&FormField> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+impl FormField {
+    /// Create an optional input.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Key of the value in the response.
+    /// * `field_type` - Kind of value expected.
+    pub fn new(name: impl Into<String>, field_type: FormFieldType) -> Self {
+        FormField {
+            name: name.into(),
+            label: None,
+            field_type,
+            required: false,
+            options: Vec::new(),
+        }
+    }
+
+    /// Create an optional text input.
+    pub fn text(name: impl Into<String>) -> Self {
+        Self::new(name, FormFieldType::Text)
+    }
+
+    /// Create an optional number input.
+    pub fn number(name: impl Into<String>) -> Self {
+        Self::new(name, FormFieldType::Number)
+    }
+
+    /// Create an optional yes/no input.
+    pub fn boolean(name: impl Into<String>) -> Self {
+        Self::new(name, FormFieldType::Boolean)
+    }
+
+    /// Create an optional input choosing one of the given options.
+    pub fn choice<S: Into<String>>(name: impl Into<String>, options: impl IntoIterator<Item = S>) -> Self {
+        FormField {
+            options: options.into_iter().map(Into::into).collect(),
+            ..Self::new(name, FormFieldType::Choice)
+        }
+    }
+
+    /// Set the human-readable label.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Make the input required.
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+}
+
+impl FormResponse {
+    /// Create a response without values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the value of a field.
+    pub fn with_value(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.values.insert(name.into(), value.into());
+        self
+    }
+
+    /// Get the value of a field.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.values.get(name)
+    }
+}
+
+impl From<FormRequest> for DataPart {
+    fn from(request: FormRequest) -> Self {
+        form_part(serde_json::to_value(request).expect("form requests always serialize"), FORM_REQUEST)
+    }
+}
+
+impl From<FormResponse> for DataPart {
+    fn from(response: FormResponse) -> Self {
+        form_part(serde_json::to_value(response).expect("form responses always serialize"), FORM_RESPONSE)
+    }
+}
+
+impl From<FormRequest> for Part {
+    fn from(request: FormRequest) -> Self {
+        Part::Data(request.into())
+    }
+}
+
+impl From<FormResponse> for Part {
+    fn from(response: FormResponse) -> Self {
+        Part::Data(response.into())
+    }
+}
+
+impl DataPart {
+    /// Parse the part as a form request.
+    ///
+    /// # Returns
+    ///
+    /// The request, or `None` if the part is not marked as one or its data
+    /// is not a valid form request.
+    pub fn as_form_request(&self) -> Option<FormRequest> {
+        self.form_data(FORM_REQUEST)
+    }
+
+    /// Parse the part as a form response.
+    ///
+    /// # Returns
+    ///
+    /// The response, or `None` if the part is not marked as one or its data
+    /// is not a valid form response.
+    pub fn as_form_response(&self) -> Option<FormResponse> {
+        self.form_data(FORM_RESPONSE)
+    }
+
+    fn form_data<T: serde::de::DeserializeOwned>(&self, marker: &str) -> Option<T> {
+        if get_meta(&self.metadata, FORM_KEY)?.as_str()? != marker {
+            return None;
+        }
+        T::deserialize(&self.data).ok()
+    }
+}
+
+impl Part {
+    /// Parse the part as a form request.
+    ///
+    /// # Returns
+    ///
+    /// The request, or `None` if the part is not a data part marked as a
+    /// valid form request.
+    pub fn as_form_request(&self) -> Option<FormRequest> {
+        match self {
+            Part::Data(part) => part.as_form_request(),
+            _ => None,
+        }
+    }
+
+    /// Parse the part as a form response.
+    ///
+    /// # Returns
+    ///
+    /// The response, or `None` if the part is not a data part marked as a
+    /// valid form response.
+    pub fn as_form_response(&self) -> Option<FormResponse> {
+        match self {
+            Part::Data(part) => part.as_form_response(),
+            _ => None,
+        }
+    }
+}
+
+fn form_part(data: Value, marker: &str) -> DataPart {
+    let mut metadata = Map::new();
+    metadata.insert(FORM_KEY.to_string(), Value::from(marker));
+    DataPart {
+        data,
+        metadata: Some(Value::Object(metadata)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn booking() -> FormRequest {
+        FormRequest::new()
+            .with_title("Book a flight")
+            .with_field(FormField::text("from").with_label("From").required())
+            .with_field(FormField::number("passengers"))
+            .with_field(FormField::boolean("flexible"))
+            .with_field(FormField::choice("class", ["economy", "business"]))
+    }
+
+    #[test]
+    fn test_form_request_round_trip() {
+        let part = Part::from(booking());
+        let json = serde_json::to_value(&part).unwrap();
+        assert_eq!(json["metadata"], json!({"a2a.form": "request"}));
+        assert_eq!(json["data"]["fields"][0], json!({"name": "from", "label": "From", "type": "text", "required": true}));
+        assert_eq!(json["data"]["fields"][3]["options"], json!(["economy", "business"]));
+
+        let part: Part = serde_json::from_value(json).unwrap();
+        let request = part.as_form_request().unwrap();
+        assert_eq!(request, booking());
+        assert_eq!(request.field("class").unwrap().field_type, FormFieldType::Choice);
+        assert!(part.as_form_response().is_none());
+    }
+
+    #[test]
+    fn test_form_response_round_trip() {
+        let response = FormResponse::new().with_value("from", "WAW").with_value("passengers", 2);
+        let part: Part = serde_json::from_str(&serde_json::to_string(&Part::from(response.clone())).unwrap()).unwrap();
+        assert_eq!(part.as_form_response(), Some(response));
+        assert_eq!(part.as_form_response().unwrap().get("passengers"), Some(&json!(2)));
+        assert!(part.as_form_request().is_none());
+    }
+
+    #[test]
+    fn test_other_parts_are_not_forms() {
+        assert!(Part::from("from: WAW").as_form_request().is_none());
+        assert!(Part::from(DataPart::from(json!({"fields": []}))).as_form_request().is_none());
+
+        let mut part = DataPart::from(booking());
+        part.metadata = Some(json!({"a2a.form": "survey"}));
+        assert!(part.as_form_request().is_none());
+        part.metadata = Some(json!({"a2a.form": "request"}));
+        part.data = json!({"fields": [{"name": "from"}]});
+        assert!(part.as_form_request().is_none());
+    }
+}
@@ -0,0 +1,1251 @@
+//! Protobuf types and conversions for the gRPC transport.
+//!
+//! Enabled by the `grpc` feature. The [`proto`] module contains the prost
+//! bindings for `proto/a2a.proto`, and this module implements conversions
+//! between them and the JSON-oriented types of this crate. Conversions that
+//! can fail (missing required fields, unspecified enum values, invalid base64
+//! or timestamps) are `TryFrom` with a `String` error; the rest are `From`.
+//!
+//! With the `grpc-client` feature, [`client::A2AGrpcClient`] calls the
+//! `A2AService` over tonic using these conversions.
+//!
+//! `google.protobuf.Struct` fields (metadata, data parts and extension
+//! params) map to JSON objects via [`value_to_struct`] and
+//! [`struct_to_value`].
+//!
+//! # Conversion losses
+//!
+//! The proto definitions are narrower than the JSON schema, so converting to
+//! proto and back does not reproduce every value:
+//!
+//! * `kind` fields are constants and are restored on the way back.
+//! * Unrecognized fields captured in `extra` are dropped.
+//! * Part metadata and file names have no proto equivalent and are dropped.
+//! * `Message::reference_task_ids` is dropped.
+//! * `Task::result`, `error`, `created_at`, `updated_at` and
+//!   `status_history` are dropped.
+//! * `AgentCard::icon_url` and `AgentCapabilities::state_transition_history`
+//!   are dropped. Skills get an empty `id` and no `tags` in proto, and skill
+//!   IDs and tags are dropped when converting back.
+//! * Name and description localizations of cards and skills are dropped.
+//! * An OAuth2 scheme keeps a single flow, the first present of
+//!   authorization code, client credentials, implicit and password.
+//! * Proto3 scalars have no presence: empty strings, empty lists and `false`
+//!   come back as `None`.
+//! * Metadata and extension params that are not JSON objects are dropped.
+//!   Data parts must hold an object and fail to convert otherwise.
+//! * Struct numbers are doubles: integers beyond 2^53 lose precision, and
+//!   whole floats come back as integers.
+//! * Timestamps are normalized to UTC (`Z`).
+
+#[cfg(feature = "grpc-client")]
+pub mod client;
+pub mod proto;
+
+use crate::*;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use prost_types::value::Kind;
+
+/// Largest integer a double represents exactly.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+// ============================================================================
+// Struct <-> JSON
+// ============================================================================
+
+/// Convert a JSON object into a `google.protobuf.Struct`.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value to convert.
+///
+/// # Returns
+///
+/// The struct, or `None` if the value is not an object.
+pub fn value_to_struct(value: serde_json::Value) -> Option<prost_types::Struct> {
+    match value {
+        serde_json::Value::Object(map) => Some(prost_types::Struct {
+            fields: map
+                .into_iter()
+                .map(|(key, value)| (key, json_to_proto(value)))
+                .collect(),
+        }),
+        _ => None,
+    }
+}
+
+/// Convert a `google.protobuf.Struct` into a JSON object.
+///
+/// # Arguments
+///
+/// * `value` - The struct to convert.
+///
+/// # Returns
+///
+/// The JSON object.
+pub fn struct_to_value(value: prost_types::Struct) -> serde_json::Value {
+    serde_json::Value::Object(
+        value
+            .fields
+            .into_iter()
+            .map(|(key, value)| (key, proto_to_json(value)))
+            .collect(),
+    )
+}
+
+fn json_to_proto(value: serde_json::Value) -> prost_types::Value {
+    let kind = match value {
+        serde_json::Value::Null => Kind::NullValue(prost_types::NullValue::NullValue as i32),
+        serde_json::Value::Bool(b) => Kind::BoolValue(b),
+        serde_json::Value::Number(n) => Kind::NumberValue(n.as_f64().unwrap_or_default()),
+        serde_json::Value::String(s) => Kind::StringValue(s),
+        serde_json::Value::Array(values) => Kind::ListValue(prost_types::ListValue {
+            values: values.into_iter().map(json_to_proto).collect(),
+        }),
+        serde_json::Value::Object(_) => {
+            Kind::StructValue(value_to_struct(value).unwrap_or_default())
+        }
+    };
+    prost_types::Value { kind: Some(kind) }
+}
+
+fn proto_to_json(value: prost_types::Value) -> serde_json::Value {
+    match value.kind {
+        None | Some(Kind::NullValue(_)) => serde_json::Value::Null,
+        Some(Kind::BoolValue(b)) => serde_json::Value::Bool(b),
+        Some(Kind::NumberValue(n)) if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER => {
+            serde_json::Value::from(n as i64)
+        }
+        Some(Kind::NumberValue(n)) => serde_json::Number::from_f64(n)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        Some(Kind::StringValue(s)) => serde_json::Value::String(s),
+        Some(Kind::ListValue(list)) => {
+            serde_json::Value::Array(list.values.into_iter().map(proto_to_json).collect())
+        }
+        Some(Kind::StructValue(s)) => struct_to_value(s),
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn non_empty(value: String) -> Option<String> {
+    if value.is_empty() { None } else { Some(value) }
+}
+
+fn non_empty_vec<T>(values: Vec<T>) -> Option<Vec<T>> {
+    if values.is_empty() { None } else { Some(values) }
+}
+
+fn true_or_none(value: bool) -> Option<bool> {
+    if value { Some(true) } else { None }
+}
+
+/// Collect the entries of a protobuf map, which has no order, sorted by key
+/// so that the converted value always serializes to the same bytes.
+fn sorted<V>(entries: impl IntoIterator<Item = (String, V)>) -> crate::Map<String, V> {
+    let mut entries: Vec<_> = entries.into_iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries.into_iter().collect()
+}
+
+fn parse_timestamp(value: &str) -> Result<prost_types::Timestamp, String> {
+    value
+        .parse()
+        .map_err(|e| format!("Invalid timestamp '{}': {}", value, e))
+}
+
+fn convert_all<T, U>(values: Vec<T>) -> Result<Vec<U>, String>
+where
+    U: TryFrom<T, Error = String>,
+{
+    values.into_iter().map(U::try_from).collect()
+}
+
+// ============================================================================
+// Parts and messages
+// ============================================================================
+
+impl TryFrom<Part> for proto::Part {
+    type Error = String;
+
+    fn try_from(part: Part) -> Result<Self, Self::Error> {
+        let part = match part {
+            Part::Text(text) => proto::part::Part::Text(text.text),
+            Part::File(file) => {
+                let (file, mime_type) = match file.file {
+                    FileContent::WithBytes(f) => {
+                        let bytes = STANDARD
+                            .decode(&f.bytes)
+                            .map_err(|e| format!("Invalid base64 file bytes: {}", e))?;
+                        (proto::file_part::File::FileWithBytes(bytes), f.mime_type)
+                    }
+                    FileContent::WithUri(f) => (proto::file_part::File::FileWithUri(f.uri), f.mime_type),
+                };
+                proto::part::Part::File(proto::FilePart {
+                    mime_type: mime_type.unwrap_or_default(),
+                    file: Some(file),
+                })
+            }
+            Part::Data(data) => proto::part::Part::Data(proto::DataPart {
+                data: Some(
+                    value_to_struct(data.data)
+                        .ok_or_else(|| "Data part must be a JSON object".to_string())?,
+                ),
+            }),
+            Part::Unknown(part) => return Err(format!("Parts of kind {} have no protobuf form", part.kind)),
+        };
+        Ok(Self { part: Some(part) })
+    }
+}
+
+impl TryFrom<proto::Part> for Part {
+    type Error = String;
+
+    fn try_from(part: proto::Part) -> Result<Self, Self::Error> {
+        match part.part.ok_or_else(|| "Part has no content".to_string())? {
+            proto::part::Part::Text(text) => Ok(Part::Text(TextPart {
+                text,
+                metadata: None,
+            })),
+            proto::part::Part::File(file) => {
+                let mime_type = non_empty(file.mime_type);
+                let file = match file.file.ok_or_else(|| "File part has no file".to_string())? {
+                    proto::file_part::File::FileWithBytes(bytes) => {
+                        FileContent::WithBytes(FileWithBytes {
+                            bytes: STANDARD.encode(bytes),
+                            name: None,
+                            mime_type,
+                        })
+                    }
+                    proto::file_part::File::FileWithUri(uri) => FileContent::WithUri(FileWithUri {
+                        uri,
+                        name: None,
+                        mime_type,
+                    }),
+                };
+                Ok(Part::File(Box::new(FilePart {
+                    file,
+                    metadata: None,
+                })))
+            }
+            proto::part::Part::Data(data) => Ok(Part::Data(DataPart {
+                data: struct_to_value(data.data.unwrap_or_default()),
+                metadata: None,
+            })),
+        }
+    }
+}
+
+impl From<MessageRole> for proto::Role {
+    fn from(role: MessageRole) -> Self {
+        match role {
+            MessageRole::User => proto::Role::User,
+            MessageRole::Agent => proto::Role::Agent,
+            // gRPC has no way to carry other roles
+            MessageRole::Other(_) => proto::Role::Unspecified,
+        }
+    }
+}
+
+impl TryFrom<proto::Role> for MessageRole {
+    type Error = String;
+
+    fn try_from(role: proto::Role) -> Result<Self, Self::Error> {
+        match role {
+            proto::Role::User => Ok(MessageRole::User),
+            proto::Role::Agent => Ok(MessageRole::Agent),
+            proto::Role::Unspecified => Err("Message role is unspecified".to_string()),
+        }
+    }
+}
+
+impl TryFrom<Message> for proto::Message {
+    type Error = String;
+
+    fn try_from(message: Message) -> Result<Self, Self::Error> {
+        Ok(Self {
+            message_id: message.message_id,
+            context_id: message.context_id.unwrap_or_default(),
+            task_id: message.task_id.unwrap_or_default(),
+            role: proto::Role::from(message.role) as i32,
+            content: convert_all(message.parts)?,
+            metadata: message.metadata.and_then(value_to_struct),
+            extensions: message.extensions.unwrap_or_default(),
+        })
+    }
+}
+
+impl TryFrom<proto::Message> for Message {
+    type Error = String;
+
+    fn try_from(message: proto::Message) -> Result<Self, Self::Error> {
+        let role = proto::Role::try_from(message.role)
+            .map_err(|_| format!("Unknown message role: {}", message.role))?;
+        Ok(Self {
+            kind: "message".to_string(),
+            message_id: message.message_id,
+            parts: convert_all(message.content)?,
+            role: MessageRole::try_from(role)?,
+            context_id: non_empty(message.context_id),
+            extensions: non_empty_vec(message.extensions),
+            metadata: message.metadata.map(struct_to_value),
+            reference_task_ids: None,
+            task_id: non_empty(message.task_id),
+            extra: ExtraFields::default(),
+        })
+    }
+}
+
+// ============================================================================
+// Tasks and artifacts
+// ============================================================================
+
+impl From<TaskState> for proto::TaskState {
+    fn from(state: TaskState) -> Self {
+        match state {
+            TaskState::Submitted => proto::TaskState::Submitted,
+            TaskState::Working => proto::TaskState::Working,
+            TaskState::InputRequired => proto::TaskState::InputRequired,
+            TaskState::Completed => proto::TaskState::Completed,
+            TaskState::Canceled => proto::TaskState::Cancelled,
+            TaskState::Failed => proto::TaskState::Failed,
+            TaskState::Rejected => proto::TaskState::Rejected,
+            TaskState::AuthRequired => proto::TaskState::AuthRequired,
+            TaskState::Unknown => proto::TaskState::Unspecified,
+        }
+    }
+}
+
+impl From<proto::TaskState> for TaskState {
+    fn from(state: proto::TaskState) -> Self {
+        match state {
+            proto::TaskState::Submitted => TaskState::Submitted,
+            proto::TaskState::Working => TaskState::Working,
+            proto::TaskState::InputRequired => TaskState::InputRequired,
+            proto::TaskState::Completed => TaskState::Completed,
+            proto::TaskState::Cancelled => TaskState::Canceled,
+            proto::TaskState::Failed => TaskState::Failed,
+            proto::TaskState::Rejected => TaskState::Rejected,
+            proto::TaskState::AuthRequired => TaskState::AuthRequired,
+            proto::TaskState::Unspecified => TaskState::Unknown,
+        }
+    }
+}
+
+impl TryFrom<TaskStatus> for proto::TaskStatus {
+    type Error = String;
+
+    fn try_from(status: TaskStatus) -> Result<Self, Self::Error> {
+        Ok(Self {
+            state: proto::TaskState::from(status.state) as i32,
+            update: status.message.map(proto::Message::try_from).transpose()?,
+            timestamp: status.timestamp.as_deref().map(parse_timestamp).transpose()?,
+        })
+    }
+}
+
+impl TryFrom<proto::TaskStatus> for TaskStatus {
+    type Error = String;
+
+    fn try_from(status: proto::TaskStatus) -> Result<Self, Self::Error> {
+        Ok(Self {
+            // Unknown state numbers from newer peers map to `Unknown`
+            state: proto::TaskState::try_from(status.state)
+                .unwrap_or(proto::TaskState::Unspecified)
+                .into(),
+            message: status.update.map(Message::try_from).transpose()?,
+            timestamp: status.timestamp.map(|t| t.to_string()),
+            extra: ExtraFields::default(),
+        })
+    }
+}
+
+impl TryFrom<Artifact> for proto::Artifact {
+    type Error = String;
+
+    fn try_from(artifact: Artifact) -> Result<Self, Self::Error> {
+        Ok(Self {
+            artifact_id: artifact.artifact_id,
+            name: artifact.name.unwrap_or_default(),
+            description: artifact.description.unwrap_or_default(),
+            parts: convert_all(artifact.parts)?,
+            metadata: artifact.metadata.and_then(value_to_struct),
+            extensions: artifact.extensions.unwrap_or_default(),
+        })
+    }
+}
+
+impl TryFrom<proto::Artifact> for Artifact {
+    type Error = String;
+
+    fn try_from(artifact: proto::Artifact) -> Result<Self, Self::Error> {
+        Ok(Self {
+            artifact_id: artifact.artifact_id,
+            parts: convert_all(artifact.parts)?,
+            description: non_empty(artifact.description),
+            extensions: non_empty_vec(artifact.extensions),
+            metadata: artifact.metadata.map(struct_to_value),
+            name: non_empty(artifact.name),
+            extra: ExtraFields::default(),
+        })
+    }
+}
+
+impl TryFrom<Task> for proto::Task {
+    type Error = String;
+
+    fn try_from(task: Task) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: task.id,
+            context_id: task.context_id,
+            status: Some(task.status.try_into()?),
+            artifacts: convert_all(task.artifacts.unwrap_or_default())?,
+            history: convert_all(task.history.unwrap_or_default())?,
+            metadata: task.metadata.and_then(value_to_struct),
+        })
+    }
+}
+
+impl TryFrom<proto::Task> for Task {
+    type Error = String;
+
+    fn try_from(task: proto::Task) -> Result<Self, Self::Error> {
+        let status = task.status.ok_or_else(|| "Task has no status".to_string())?;
+        Ok(Self {
+            id: task.id,
+            kind: "task".to_string(),
+            status: status.try_into()?,
+            context_id: task.context_id,
+            artifacts: non_empty_vec(convert_all(task.artifacts)?),
+            history: non_empty_vec(convert_all(task.history)?),
+            metadata: task.metadata.map(struct_to_value),
+            result: None,
+            error: None,
+            created_at: None,
+            updated_at: None,
+            status_history: None,
+            extra: ExtraFields::default(),
+        })
+    }
+}
+
+// ============================================================================
+// Streaming events
+// ============================================================================
+
+impl TryFrom<TaskStatusUpdateEvent> for proto::TaskStatusUpdateEvent {
+    type Error = String;
+
+    fn try_from(event: TaskStatusUpdateEvent) -> Result<Self, Self::Error> {
+        Ok(Self {
+            task_id: event.task_id,
+            context_id: event.context_id,
+            status: Some(event.status.try_into()?),
+            r#final: event.final_event,
+            metadata: event.metadata.and_then(value_to_struct),
+        })
+    }
+}
+
+impl TryFrom<proto::TaskStatusUpdateEvent> for TaskStatusUpdateEvent {
+    type Error = String;
+
+    fn try_from(event: proto::TaskStatusUpdateEvent) -> Result<Self, Self::Error> {
+        let status = event
+            .status
+            .ok_or_else(|| "Status update event has no status".to_string())?;
+        Ok(Self {
+            kind: "status-update".to_string(),
+            task_id: event.task_id,
+            context_id: event.context_id,
+            status: status.try_into()?,
+            final_event: event.r#final,
+            metadata: event.metadata.map(struct_to_value),
+            extra: ExtraFields::default(),
+        })
+    }
+}
+
+impl TryFrom<TaskArtifactUpdateEvent> for proto::TaskArtifactUpdateEvent {
+    type Error = String;
+
+    fn try_from(event: TaskArtifactUpdateEvent) -> Result<Self, Self::Error> {
+        Ok(Self {
+            task_id: event.task_id,
+            context_id: event.context_id,
+            artifact: Some(event.artifact.try_into()?),
+            append: event.append.unwrap_or(false),
+            last_chunk: event.last_chunk.unwrap_or(false),
+            metadata: event.metadata.and_then(value_to_struct),
+        })
+    }
+}
+
+impl TryFrom<proto::TaskArtifactUpdateEvent> for TaskArtifactUpdateEvent {
+    type Error = String;
+
+    fn try_from(event: proto::TaskArtifactUpdateEvent) -> Result<Self, Self::Error> {
+        let artifact = event
+            .artifact
+            .ok_or_else(|| "Artifact update event has no artifact".to_string())?;
+        Ok(Self {
+            kind: "artifact-update".to_string(),
+            task_id: event.task_id,
+            context_id: event.context_id,
+            artifact: artifact.try_into()?,
+            append: true_or_none(event.append),
+            last_chunk: true_or_none(event.last_chunk),
+            metadata: event.metadata.map(struct_to_value),
+            extra: ExtraFields::default(),
+        })
+    }
+}
+
+// ============================================================================
+// Send configuration
+// ============================================================================
+
+impl From<PushNotificationConfig> for proto::PushNotificationConfig {
+    fn from(mut config: PushNotificationConfig) -> Self {
+        // Fields are taken rather than moved, since with the `zeroize`
+        // feature the config has a `Drop` implementation that wipes it
+        Self {
+            id: config.id.take().unwrap_or_default(),
+            url: core::mem::take(&mut config.url),
+            token: config.token.take().unwrap_or_default(),
+            authentication: config.authentication.take().map(|mut auth| proto::AuthenticationInfo {
+                schemes: core::mem::take(&mut auth.schemes),
+                credentials: auth.credentials.take().unwrap_or_default(),
+            }),
+        }
+    }
+}
+
+impl From<proto::PushNotificationConfig> for PushNotificationConfig {
+    fn from(config: proto::PushNotificationConfig) -> Self {
+        Self {
+            url: config.url,
+            authentication: config
+                .authentication
+                .map(|auth| PushNotificationAuthenticationInfo {
+                    schemes: auth.schemes,
+                    credentials: non_empty(auth.credentials),
+                }),
+            id: non_empty(config.id),
+            token: non_empty(config.token),
+        }
+    }
+}
+
+/// A negative history length is invalid on both sides and is rejected
+/// rather than passed on.
+impl TryFrom<MessageSendConfiguration> for proto::SendMessageConfiguration {
+    type Error = String;
+
+    fn try_from(config: MessageSendConfiguration) -> Result<Self, Self::Error> {
+        let history_length = config.history_limit().map_err(|e| e.to_string())?;
+        Ok(Self {
+            accepted_output_modes: config.accepted_output_modes.unwrap_or_default(),
+            push_notification: config.push_notification_config.map(Into::into),
+            history_length: history_length.map_or(0, |length| length as i32),
+            blocking: config.blocking.unwrap_or(false),
+        })
+    }
+}
+
+impl TryFrom<proto::SendMessageConfiguration> for MessageSendConfiguration {
+    type Error = String;
+
+    fn try_from(config: proto::SendMessageConfiguration) -> Result<Self, Self::Error> {
+        let config = Self {
+            accepted_output_modes: non_empty_vec(config.accepted_output_modes),
+            blocking: true_or_none(config.blocking),
+            history_length: (config.history_length != 0).then_some(config.history_length),
+            push_notification_config: config.push_notification.map(Into::into),
+            extra: ExtraFields::default(),
+        };
+        config.history_limit().map_err(|e| e.to_string())?;
+        Ok(config)
+    }
+}
+
+impl TryFrom<SendMessageParams> for proto::SendMessageRequest {
+    type Error = String;
+
+    fn try_from(params: SendMessageParams) -> Result<Self, Self::Error> {
+        Ok(Self {
+            request: Some(params.message.try_into()?),
+            configuration: params.configuration.map(TryInto::try_into).transpose()?,
+            metadata: params.metadata.and_then(value_to_struct),
+        })
+    }
+}
+
+impl TryFrom<proto::SendMessageRequest> for SendMessageParams {
+    type Error = String;
+
+    fn try_from(request: proto::SendMessageRequest) -> Result<Self, Self::Error> {
+        let message = request
+            .request
+            .ok_or_else(|| "Send message request has no message".to_string())?;
+        Ok(Self {
+            message: message.try_into()?,
+            configuration: request.configuration.map(TryInto::try_into).transpose()?,
+            metadata: request.metadata.map(struct_to_value),
+            extra: ExtraFields::default(),
+        })
+    }
+}
+
+// ============================================================================
+// Results
+// ============================================================================
+
+impl TryFrom<SendMessageResult> for proto::SendMessageResponse {
+    type Error = String;
+
+    fn try_from(result: SendMessageResult) -> Result<Self, Self::Error> {
+        use proto::send_message_response::Payload;
+
+        let payload = match result {
+            SendMessageResult::Task(task) => Payload::Task(task.try_into()?),
+            SendMessageResult::Message(message) => Payload::Msg(message.try_into()?),
+        };
+        Ok(Self {
+            payload: Some(payload),
+        })
+    }
+}
+
+impl TryFrom<proto::SendMessageResponse> for SendMessageResult {
+    type Error = String;
+
+    fn try_from(response: proto::SendMessageResponse) -> Result<Self, Self::Error> {
+        use proto::send_message_response::Payload;
+
+        match response.payload.ok_or_else(|| "Send message response is empty".to_string())? {
+            Payload::Task(task) => Ok(SendMessageResult::Task(task.try_into()?)),
+            Payload::Msg(message) => Ok(SendMessageResult::Message(message.try_into()?)),
+        }
+    }
+}
+
+impl TryFrom<StreamingMessageResult> for proto::StreamResponse {
+    type Error = String;
+
+    fn try_from(result: StreamingMessageResult) -> Result<Self, Self::Error> {
+        use proto::stream_response::Payload;
+
+        let payload = match result {
+            StreamingMessageResult::Task(task) => Payload::Task(task.try_into()?),
+            StreamingMessageResult::Message(message) => Payload::Msg(message.try_into()?),
+            StreamingMessageResult::TaskStatusUpdate(event) => {
+                Payload::StatusUpdate(event.try_into()?)
+            }
+            StreamingMessageResult::TaskArtifactUpdate(event) => {
+                Payload::ArtifactUpdate(event.try_into()?)
+            }
+        };
+        Ok(Self {
+            payload: Some(payload),
+        })
+    }
+}
+
+impl TryFrom<proto::StreamResponse> for StreamingMessageResult {
+    type Error = String;
+
+    fn try_from(response: proto::StreamResponse) -> Result<Self, Self::Error> {
+        use proto::stream_response::Payload;
+
+        match response.payload.ok_or_else(|| "Stream response is empty".to_string())? {
+            Payload::Task(task) => Ok(StreamingMessageResult::Task(task.try_into()?)),
+            Payload::Msg(message) => Ok(StreamingMessageResult::Message(message.try_into()?)),
+            Payload::StatusUpdate(event) => {
+                Ok(StreamingMessageResult::TaskStatusUpdate(event.try_into()?))
+            }
+            Payload::ArtifactUpdate(event) => {
+                Ok(StreamingMessageResult::TaskArtifactUpdate(event.try_into()?))
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Agent card
+// ============================================================================
+
+impl From<SecurityScheme> for proto::SecurityScheme {
+    fn from(scheme: SecurityScheme) -> Self {
+        use proto::security_scheme::Scheme;
+
+        let scheme = match scheme {
+            SecurityScheme::ApiKey(s) => Scheme::ApiKeySecurityScheme(proto::ApiKeySecurityScheme {
+                description: s.description.unwrap_or_default(),
+                location: match s.in_ {
+                    ApiKeyLocation::Cookie => "cookie",
+                    ApiKeyLocation::Header => "header",
+                    ApiKeyLocation::Query => "query",
+                }
+                .to_string(),
+                name: s.name,
+            }),
+            SecurityScheme::Http(s) => Scheme::HttpAuthSecurityScheme(proto::HttpAuthSecurityScheme {
+                description: s.description.unwrap_or_default(),
+                scheme: s.scheme,
+                bearer_format: s.bearer_format.unwrap_or_default(),
+            }),
+            SecurityScheme::OAuth2(s) => Scheme::Oauth2SecurityScheme(proto::OAuth2SecurityScheme {
+                description: s.description.unwrap_or_default(),
+                flows: Some(s.flows.into()),
+            }),
+            SecurityScheme::OpenIdConnect(s) => {
+                Scheme::OpenIdConnectSecurityScheme(proto::OpenIdConnectSecurityScheme {
+                    description: s.description.unwrap_or_default(),
+                    open_id_connect_url: s.open_id_connect_url,
+                })
+            }
+        };
+        Self {
+            scheme: Some(scheme),
+        }
+    }
+}
+
+impl TryFrom<proto::SecurityScheme> for SecurityScheme {
+    type Error = String;
+
+    fn try_from(scheme: proto::SecurityScheme) -> Result<Self, Self::Error> {
+        use proto::security_scheme::Scheme;
+
+        match scheme.scheme.ok_or_else(|| "Security scheme has no type".to_string())? {
+            Scheme::ApiKeySecurityScheme(s) => {
+                let location = match s.location.as_str() {
+                    "cookie" => ApiKeyLocation::Cookie,
+                    "header" => ApiKeyLocation::Header,
+                    "query" => ApiKeyLocation::Query,
+                    other => return Err(format!("Invalid API key location: {}", other)),
+                };
+                let mut api_key = ApiKeySecurityScheme::new(location, s.name);
+                api_key.description = non_empty(s.description);
+                Ok(SecurityScheme::ApiKey(api_key))
+            }
+            Scheme::HttpAuthSecurityScheme(s) => {
+                let mut http = HttpSecurityScheme::new(s.scheme);
+                http.bearer_format = non_empty(s.bearer_format);
+                http.description = non_empty(s.description);
+                Ok(SecurityScheme::Http(http))
+            }
+            Scheme::Oauth2SecurityScheme(s) => {
+                let flows = s.flows.ok_or_else(|| "OAuth2 scheme has no flows".to_string())?;
+                let mut oauth2 = OAuth2SecurityScheme::new(flows.try_into()?);
+                oauth2.description = non_empty(s.description);
+                Ok(SecurityScheme::OAuth2(oauth2))
+            }
+            Scheme::OpenIdConnectSecurityScheme(s) => {
+                let mut oidc = OpenIdConnectSecurityScheme::new(s.open_id_connect_url);
+                oidc.description = non_empty(s.description);
+                Ok(SecurityScheme::OpenIdConnect(oidc))
+            }
+        }
+    }
+}
+
+impl From<OAuth2Flows> for proto::OAuthFlows {
+    fn from(flows: OAuth2Flows) -> Self {
+        use proto::o_auth_flows::Flow;
+
+        let flow = if let Some(f) = flows.authorization_code {
+            Some(Flow::AuthorizationCode(proto::AuthorizationCodeOAuthFlow {
+                authorization_url: f.authorization_url,
+                token_url: f.token_url,
+                refresh_url: f.refresh_url.unwrap_or_default(),
+                scopes: f.scopes.into_iter().collect(),
+            }))
+        } else if let Some(f) = flows.client_credentials {
+            Some(Flow::ClientCredentials(proto::ClientCredentialsOAuthFlow {
+                token_url: f.token_url,
+                refresh_url: f.refresh_url.unwrap_or_default(),
+                scopes: f.scopes.into_iter().collect(),
+            }))
+        } else if let Some(f) = flows.implicit {
+            Some(Flow::Implicit(proto::ImplicitOAuthFlow {
+                authorization_url: f.authorization_url,
+                refresh_url: f.refresh_url.unwrap_or_default(),
+                scopes: f.scopes.into_iter().collect(),
+            }))
+        } else {
+            flows.password.map(|f| {
+                Flow::Password(proto::PasswordOAuthFlow {
+                    token_url: f.token_url,
+                    refresh_url: f.refresh_url.unwrap_or_default(),
+                    scopes: f.scopes.into_iter().collect(),
+                })
+            })
+        };
+        Self { flow }
+    }
+}
+
+impl TryFrom<proto::OAuthFlows> for OAuth2Flows {
+    type Error = String;
+
+    fn try_from(flows: proto::OAuthFlows) -> Result<Self, Self::Error> {
+        use proto::o_auth_flows::Flow;
+
+        let mut result = OAuth2Flows {
+            implicit: None,
+            password: None,
+            client_credentials: None,
+            authorization_code: None,
+        };
+        match flows.flow.ok_or_else(|| "OAuth2 flows are empty".to_string())? {
+            Flow::AuthorizationCode(f) => {
+                let mut flow =
+                    AuthorizationCodeOAuthFlow::new(f.authorization_url, f.token_url, sorted(f.scopes));
+                flow.refresh_url = non_empty(f.refresh_url);
+                result.authorization_code = Some(flow);
+            }
+            Flow::ClientCredentials(f) => {
+                let mut flow = ClientCredentialsOAuthFlow::new(f.token_url, sorted(f.scopes));
+                flow.refresh_url = non_empty(f.refresh_url);
+                result.client_credentials = Some(flow);
+            }
+            Flow::Implicit(f) => {
+                let mut flow = ImplicitOAuthFlow::new(f.authorization_url, sorted(f.scopes));
+                flow.refresh_url = non_empty(f.refresh_url);
+                result.implicit = Some(flow);
+            }
+            Flow::Password(f) => {
+                let mut flow = PasswordOAuthFlow::new(f.token_url, sorted(f.scopes));
+                flow.refresh_url = non_empty(f.refresh_url);
+                result.password = Some(flow);
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl From<AgentExtension> for proto::AgentExtension {
+    fn from(extension: AgentExtension) -> Self {
+        Self {
+            uri: extension.uri,
+            description: extension.description.unwrap_or_default(),
+            required: extension.required.unwrap_or(false),
+            params: extension.params.and_then(value_to_struct),
+        }
+    }
+}
+
+impl From<proto::AgentExtension> for AgentExtension {
+    fn from(extension: proto::AgentExtension) -> Self {
+        Self {
+            uri: extension.uri,
+            required: true_or_none(extension.required),
+            description: non_empty(extension.description),
+            params: extension.params.map(struct_to_value),
+        }
+    }
+}
+
+impl From<AgentSkill> for proto::AgentSkill {
+    fn from(skill: AgentSkill) -> Self {
+        Self {
+            id: String::new(),
+            name: skill.name,
+            description: skill.description,
+            tags: Vec::new(),
+            examples: skill.examples.unwrap_or_default(),
+            input_modes: skill.input_modes.unwrap_or_default(),
+            output_modes: skill.output_modes.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<proto::AgentSkill> for AgentSkill {
+    fn from(skill: proto::AgentSkill) -> Self {
+        Self {
+            name: skill.name,
+            description: skill.description,
+            input_modes: non_empty_vec(skill.input_modes),
+            output_modes: non_empty_vec(skill.output_modes),
+            examples: non_empty_vec(skill.examples),
+            name_localizations: None,
+            description_localizations: None,
+        }
+    }
+}
+
+impl From<AgentCard> for proto::AgentCard {
+    fn from(card: AgentCard) -> Self {
+        let capabilities = card.capabilities;
+        Self {
+            protocol_version: card.protocol_version,
+            name: card.name,
+            description: card.description,
+            url: card.url,
+            preferred_transport: card.preferred_transport.unwrap_or_default(),
+            additional_interfaces: card
+                .additional_interfaces
+                .unwrap_or_default()
+                .into_iter()
+                .map(|i| proto::AgentInterface {
+                    url: i.url,
+                    transport: i.transport,
+                })
+                .collect(),
+            provider: card.provider.map(|p| proto::AgentProvider {
+                url: p.url,
+                organization: p.organization,
+            }),
+            version: card.version,
+            documentation_url: card.documentation_url.unwrap_or_default(),
+            capabilities: Some(proto::AgentCapabilities {
+                streaming: capabilities.streaming.unwrap_or(false),
+                push_notifications: capabilities.push_notifications.unwrap_or(false),
+                extensions: capabilities
+                    .extensions
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+            }),
+            security_schemes: card
+                .security_schemes
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, scheme)| (name, scheme.into()))
+                .collect(),
+            security: card
+                .security
+                .unwrap_or_default()
+                .into_iter()
+                .map(|requirement| proto::Security {
+                    schemes: requirement
+                        .into_iter()
+                        .map(|(name, list)| (name, proto::StringList { list }))
+                        .collect(),
+                })
+                .collect(),
+            default_input_modes: card.default_input_modes,
+            default_output_modes: card.default_output_modes,
+            skills: card.skills.into_iter().map(Into::into).collect(),
+            supports_authenticated_extended_card: card
+                .supports_authenticated_extended_card
+                .unwrap_or(false),
+        }
+    }
+}
+
+impl TryFrom<proto::AgentCard> for AgentCard {
+    type Error = String;
+
+    fn try_from(card: proto::AgentCard) -> Result<Self, Self::Error> {
+        let capabilities = card.capabilities.unwrap_or_default();
+        let security_schemes = card
+            .security_schemes
+            .into_iter()
+            .map(|(name, scheme)| Ok((name, scheme.try_into()?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        let security_schemes = sorted(security_schemes);
+
+        Ok(Self {
+            name: card.name,
+            description: card.description,
+            version: card.version,
+            protocol_version: card.protocol_version,
+            url: card.url,
+            preferred_transport: non_empty(card.preferred_transport),
+            capabilities: AgentCapabilities {
+                extensions: non_empty_vec(
+                    capabilities.extensions.into_iter().map(Into::into).collect(),
+                ),
+                push_notifications: true_or_none(capabilities.push_notifications),
+                state_transition_history: None,
+                streaming: true_or_none(capabilities.streaming),
+            },
+            default_input_modes: card.default_input_modes,
+            default_output_modes: card.default_output_modes,
+            skills: card.skills.into_iter().map(Into::into).collect(),
+            provider: card.provider.map(|p| AgentProvider {
+                organization: p.organization,
+                url: p.url,
+            }),
+            documentation_url: non_empty(card.documentation_url),
+            icon_url: None,
+            supports_authenticated_extended_card: true_or_none(
+                card.supports_authenticated_extended_card,
+            ),
+            additional_interfaces: non_empty_vec(
+                card.additional_interfaces
+                    .into_iter()
+                    .map(|i| AgentInterface {
+                        url: i.url,
+                        transport: i.transport,
+                    })
+                    .collect(),
+            ),
+            security: non_empty_vec(
+                card.security
+                    .into_iter()
+                    .map(|s| sorted(s.schemes.into_iter().map(|(name, l)| (name, l.list))))
+                    .collect(),
+            ),
+            security_schemes: if security_schemes.is_empty() {
+                None
+            } else {
+                Some(security_schemes)
+            },
+            #[cfg(feature = "spec-0-3")]
+            signatures: None,
+            name_localizations: None,
+            description_localizations: None,
+            extra: ExtraFields::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message as _;
+
+    /// Convert to proto, encode to protobuf bytes, decode, and convert back.
+    fn through_proto<T, P>(value: T) -> T
+    where
+        P: prost::Message + Default + TryFrom<T, Error = String>,
+        T: TryFrom<P, Error = String>,
+    {
+        let bytes = P::try_from(value).unwrap().encode_to_vec();
+        T::try_from(P::decode(bytes.as_slice()).unwrap()).unwrap()
+    }
+
+    fn task_json() -> serde_json::Value {
+        serde_json::json!({
+            "id": "task-1",
+            "kind": "task",
+            "contextId": "ctx-1",
+            "status": {
+                "state": "working",
+                "message": {
+                    "kind": "message",
+                    "messageId": "status-1",
+                    "parts": [{"kind": "text", "text": "Working on it"}],
+                    "role": "agent",
+                    "taskId": "task-1"
+                },
+                "timestamp": "2024-01-01T00:00:10Z"
+            },
+            "history": [{
+                "kind": "message",
+                "messageId": "msg-1",
+                "parts": [
+                    {"kind": "text", "text": "Translate this"},
+                    {"kind": "file", "file": {"bytes": "aGVsbG8=", "mimeType": "text/plain"}},
+                    {"kind": "file", "file": {"uri": "https://example.com/a.png"}},
+                    {"kind": "data", "data": {"count": 3, "ratio": 0.5, "tags": ["a", null]}}
+                ],
+                "role": "user",
+                "contextId": "ctx-1",
+                "extensions": ["https://example.com/ext/v1"],
+                "metadata": {"com.example": {"traceId": "abc"}}
+            }],
+            "artifacts": [{
+                "artifactId": "artifact-1",
+                "name": "translation",
+                "parts": [{"kind": "text", "text": "Bonjour"}]
+            }],
+            "metadata": {"priority": "high"}
+        })
+    }
+
+    #[test]
+    fn test_task_round_trips_through_proto() {
+        let task: Task = serde_json::from_value(task_json()).unwrap();
+        let back = through_proto::<_, proto::Task>(task);
+        assert_eq!(serde_json::to_value(&back).unwrap(), task_json());
+    }
+
+    #[test]
+    fn test_task_states_map_one_to_one() {
+        let mut seen = Vec::new();
+        for state in TaskState::ALL {
+            let proto_state = proto::TaskState::from(state.clone());
+            assert!(!seen.contains(&proto_state), "{:?}", proto_state);
+            seen.push(proto_state);
+            assert_eq!(&TaskState::from(proto_state), state);
+        }
+    }
+
+    #[test]
+    fn test_events_round_trip_through_proto() {
+        let status: TaskStatusUpdateEvent = serde_json::from_value(serde_json::json!({
+            "kind": "status-update",
+            "taskId": "task-1",
+            "contextId": "ctx-1",
+            "status": {"state": "input-required"},
+            "final": true,
+            "metadata": {"step": 2}
+        }))
+        .unwrap();
+        let json = serde_json::to_value(&status).unwrap();
+        let back = through_proto::<_, proto::TaskStatusUpdateEvent>(status);
+        assert_eq!(serde_json::to_value(&back).unwrap(), json);
+
+        let artifact: TaskArtifactUpdateEvent = serde_json::from_value(serde_json::json!({
+            "kind": "artifact-update",
+            "taskId": "task-1",
+            "contextId": "ctx-1",
+            "artifact": {"artifactId": "a-1", "parts": [{"kind": "text", "text": "chunk"}]},
+            "append": true,
+            "lastChunk": true
+        }))
+        .unwrap();
+        let json = serde_json::to_value(&artifact).unwrap();
+        let back = through_proto::<_, proto::TaskArtifactUpdateEvent>(artifact);
+        assert_eq!(serde_json::to_value(&back).unwrap(), json);
+    }
+
+    #[test]
+    fn test_agent_card_round_trips_through_proto() {
+        let card_json = serde_json::json!({
+            "name": "Test Agent",
+            "description": "A test agent",
+            "version": "1.0.0",
+            "protocolVersion": PROTOCOL_VERSION,
+            "url": "https://example.com/agent",
+            "preferredTransport": "GRPC",
+            "additionalInterfaces": [{"url": "https://example.com/rpc", "transport": "JSONRPC"}],
+            "capabilities": {
+                "streaming": true,
+                "extensions": [{
+                    "uri": "https://example.com/ext/v1",
+                    "required": true,
+                    "params": {"depth": 2}
+                }]
+            },
+            "defaultInputModes": ["text/plain"],
+            "defaultOutputModes": ["text/plain"],
+            "skills": [{
+                "name": "translate",
+                "description": "Translate text",
+                "examples": ["Translate hello to French"]
+            }],
+            "provider": {"organization": "Example", "url": "https://example.com"},
+            "security": [{"oauth": ["read", "write"]}],
+            "securitySchemes": {
+                "apiKey": {"type": "apiKey", "in": "header", "name": "X-API-Key"},
+                "bearer": {"type": "http", "scheme": "bearer", "bearerFormat": "JWT"},
+                "oauth": {
+                    "type": "oauth2",
+                    "flows": {
+                        "clientCredentials": {
+                            "tokenUrl": "https://example.com/token",
+                            "scopes": {"read": "Read access"}
+                        }
+                    }
+                },
+                "oidc": {
+                    "type": "openIdConnect",
+                    "openIdConnectUrl": "https://example.com/.well-known/openid-configuration"
+                }
+            }
+        });
+
+        let card: AgentCard = serde_json::from_value(card_json.clone()).unwrap();
+        let bytes = proto::AgentCard::from(card).encode_to_vec();
+        let back = AgentCard::try_from(proto::AgentCard::decode(bytes.as_slice()).unwrap()).unwrap();
+        assert_eq!(serde_json::to_value(&back).unwrap(), card_json);
+        // Protobuf maps have no order; the card gets its keys sorted
+        let names: Vec<_> = back.security_schemes.as_ref().unwrap().keys().cloned().collect();
+        assert_eq!(names, ["apiKey", "bearer", "oauth", "oidc"]);
+    }
+
+    #[test]
+    fn test_documented_conversion_losses() {
+        let mut task: Task = serde_json::from_value(task_json()).unwrap();
+        task.result = Some(serde_json::json!({"done": true}));
+        task.created_at = Some("2024-01-01T00:00:00Z".to_string());
+        task.extra.insert("futureField".to_string(), serde_json::json!(1));
+        if let Part::Text(text) = &mut task.history.as_mut().unwrap()[0].parts[0] {
+            text.metadata = Some(serde_json::json!({"lang": "en"}));
+        }
+        task.history.as_mut().unwrap()[0].reference_task_ids = Some(vec!["task-0".to_string()]);
+        task.metadata = Some(serde_json::json!({"big": 9_007_199_254_740_993_u64, "whole": 2.0}));
+        task.status.timestamp = Some("2024-01-01T02:00:10+02:00".to_string());
+
+        let back = through_proto::<_, proto::Task>(task);
+        assert!(back.result.is_none());
+        assert!(back.created_at.is_none());
+        assert!(back.extra.is_empty());
+        let message = &back.history.as_ref().unwrap()[0];
+        assert!(matches!(&message.parts[0], Part::Text(t) if t.metadata.is_none()));
+        assert!(message.reference_task_ids.is_none());
+        assert_eq!(
+            back.metadata,
+            Some(serde_json::json!({"big": 9_007_199_254_740_992_u64, "whole": 2}))
+        );
+        assert_eq!(back.status.timestamp.as_deref(), Some("2024-01-01T00:00:10Z"));
+
+        let mut flows: OAuth2Flows = serde_json::from_value(serde_json::json!({
+            "password": {"tokenUrl": "https://example.com/token", "scopes": {}},
+            "authorizationCode": {
+                "authorizationUrl": "https://example.com/auth",
+                "tokenUrl": "https://example.com/token",
+                "scopes": {}
+            }
+        }))
+        .unwrap();
+        flows = OAuth2Flows::try_from(proto::OAuthFlows::from(flows)).unwrap();
+        assert!(flows.authorization_code.is_some());
+        assert!(flows.password.is_none());
+    }
+
+    #[test]
+    fn test_invalid_conversions_are_rejected() {
+        let message = proto::Message {
+            message_id: "msg-1".to_string(),
+            role: proto::Role::Unspecified as i32,
+            ..Default::default()
+        };
+        assert!(Message::try_from(message).is_err());
+
+        assert!(Part::try_from(proto::Part { part: None }).is_err());
+
+        let bad_bytes = Part::File(Box::new(FilePart {
+            file: FileContent::WithBytes(FileWithBytes {
+                bytes: "not base64!".to_string(),
+                name: None,
+                mime_type: None,
+            }),
+            metadata: None,
+        }));
+        assert!(proto::Part::try_from(bad_bytes).is_err());
+
+        let scalar_data = Part::Data(DataPart {
+            data: serde_json::json!([1, 2, 3]),
+            metadata: None,
+        });
+        assert!(proto::Part::try_from(scalar_data).is_err());
+
+        assert!(Task::try_from(proto::Task::default()).is_err());
+
+        let negative = MessageSendConfiguration {
+            history_length: Some(-1),
+            ..Default::default()
+        };
+        let error = proto::SendMessageConfiguration::try_from(negative).unwrap_err();
+        assert!(error.contains("historyLength must not be negative, got -1"), "{}", error);
+        let negative = proto::SendMessageConfiguration {
+            history_length: -5,
+            ..Default::default()
+        };
+        assert!(MessageSendConfiguration::try_from(negative).is_err());
+        let config = MessageSendConfiguration::default().with_history_length(3);
+        assert_eq!(proto::SendMessageConfiguration::try_from(config).unwrap().history_length, 3);
+    }
+}
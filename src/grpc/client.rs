@@ -0,0 +1,1103 @@
+//! Tonic client for the A2A gRPC service.
+//!
+//! Enabled by the `grpc-client` feature. [`A2AGrpcClient`] takes and returns
+//! the protocol types of this crate and converts them with the conversions in
+//! [`crate::grpc`]. Calls fail with a [`ClientError`]: gRPC status codes
+//! of agent errors are translated back into [`A2AError`] by
+//! [`status_to_error`], while unreachable agents, deadlines and undecodable
+//! responses get their own variants. Servers can produce compatible statuses
+//! with [`error_to_status`].
+
+use crate::client::{ClientError, TransportErrorKind};
+use crate::grpc::proto;
+use crate::*;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use tokio_stream::{Stream, StreamExt};
+use tonic::codegen::Bytes;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, Endpoint};
+
+/// Stream of results returned by streaming calls.
+pub type StreamingMessageStream =
+    Pin<Box<dyn Stream<Item = Result<StreamingMessageResult, ClientError>> + Send>>;
+
+/// Transport name announced by agents that serve gRPC.
+pub const GRPC_TRANSPORT: &str = "GRPC";
+
+/// Metadata key carrying the activated extensions, the gRPC form of
+/// [`extensions::EXTENSIONS_HEADER`].
+const EXTENSIONS_METADATA_KEY: &str = "x-a2a-extensions";
+
+/// Client for the `a2a.v1.A2AService` gRPC service.
+#[derive(Debug, Clone)]
+pub struct A2AGrpcClient {
+    inner: tonic::client::Grpc<Channel>,
+    extensions: Vec<String>,
+    timeout: Option<Duration>,
+}
+
+impl A2AGrpcClient {
+    /// Create a client over an existing channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The tonic channel to the agent.
+    ///
+    /// # Returns
+    ///
+    /// A new `A2AGrpcClient`.
+    pub fn new(channel: Channel) -> Self {
+        Self {
+            inner: tonic::client::Grpc::new(channel),
+            extensions: Vec::new(),
+            timeout: None,
+        }
+    }
+
+    /// Activate extensions for every call made by this client.
+    ///
+    /// The URIs are sent in the `x-a2a-extensions` metadata of each call and
+    /// added to the `extensions` of every message sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `uris` - The extension URIs to activate.
+    ///
+    /// # Returns
+    ///
+    /// The client with the extensions activated.
+    pub fn with_extensions<I, S>(mut self, uris: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for uri in uris {
+            let uri = uri.into();
+            if !self.extensions.contains(&uri) {
+                self.extensions.push(uri);
+            }
+        }
+        self
+    }
+
+    /// The extension URIs activated for this client.
+    pub fn extensions(&self) -> &[String] {
+        &self.extensions
+    }
+
+    /// Give up on calls that take longer than `timeout`.
+    ///
+    /// The deadline is also sent to the agent as `grpc-timeout`. Streaming
+    /// calls must open their stream within it; the stream itself may last
+    /// longer. Calls past the deadline fail with [`ClientError::Timeout`].
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The longest a call may take.
+    ///
+    /// # Returns
+    ///
+    /// The client with the timeout set.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Connect to an agent at the given URL.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The gRPC endpoint, e.g. `http://localhost:50051`.
+    ///
+    /// # Returns
+    ///
+    /// A connected client, or a `Transport` error if the URL is invalid or
+    /// the connection fails.
+    pub async fn connect(url: &str) -> Result<Self, ClientError> {
+        let channel = Endpoint::from_shared(url.to_string())
+            .map_err(|e| ClientError::transport(TransportErrorKind::InvalidEndpoint, e))?
+            .connect()
+            .await
+            .map_err(|e| ClientError::transport(TransportErrorKind::of(&e, TransportErrorKind::Connect), e))?;
+        Ok(Self::new(channel))
+    }
+
+    /// Connect to the gRPC interface announced by an agent card.
+    ///
+    /// # Arguments
+    ///
+    /// * `card` - The agent card.
+    ///
+    /// # Returns
+    ///
+    /// A connected client, or an `UnsupportedOperation` error if the card
+    /// does not announce a `GRPC` interface.
+    pub async fn from_card(card: &AgentCard) -> Result<Self, ClientError> {
+        let url = card.url_for_transport(GRPC_TRANSPORT).ok_or_else(|| {
+            A2AError::UnsupportedOperation(Box::new(UnsupportedOperationError {
+                code: -32004,
+                message: format!("Agent '{}' does not announce a gRPC interface", card.name),
+                data: None,
+            }))
+        })?;
+        Self::connect(url).await
+    }
+
+    /// Send a message.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The message and its configuration.
+    ///
+    /// # Returns
+    ///
+    /// The task the agent created or continued, or the message it answered
+    /// with instead; read either through the accessors of
+    /// [`SendMessageResult`], such as [`SendMessageResult::task`].
+    pub async fn send_message(
+        &mut self,
+        mut params: SendMessageParams,
+    ) -> Result<SendMessageResult, ClientError> {
+        extensions::activate_on_message(&mut params.message, &self.extensions);
+        let request = proto::SendMessageRequest::try_from(params).map_err(invalid_params)?;
+        let response: proto::SendMessageResponse = self
+            .unary("/a2a.v1.A2AService/SendMessage", request)
+            .await?;
+        SendMessageResult::try_from(response).map_err(decode_error)
+    }
+
+    /// Send a message and stream the resulting updates.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The message and its configuration.
+    ///
+    /// # Returns
+    ///
+    /// A stream of tasks, messages and task update events.
+    pub async fn send_streaming_message(
+        &mut self,
+        mut params: SendMessageParams,
+    ) -> Result<StreamingMessageStream, ClientError> {
+        extensions::activate_on_message(&mut params.message, &self.extensions);
+        let request = proto::SendMessageRequest::try_from(params).map_err(invalid_params)?;
+        self.server_streaming("/a2a.v1.A2AService/SendStreamingMessage", request)
+            .await
+    }
+
+    /// Get a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The ID of the task.
+    ///
+    /// # Returns
+    ///
+    /// The task.
+    pub async fn get_task(&mut self, params: GetTaskParams) -> Result<Task, ClientError> {
+        let request = proto::GetTaskRequest {
+            name: task_name(&params.task_id),
+            history_length: params.history_length.map_or(0, |n| n.min(i32::MAX as u32) as i32),
+        };
+        let task: proto::Task = self.unary("/a2a.v1.A2AService/GetTask", request).await?;
+        Task::try_from(task).map_err(decode_error)
+    }
+
+    /// Cancel a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The ID of the task.
+    ///
+    /// # Returns
+    ///
+    /// The task after cancellation.
+    pub async fn cancel_task(&mut self, params: CancelTaskParams) -> Result<Task, ClientError> {
+        let request = proto::CancelTaskRequest {
+            name: task_name(&params.task_id),
+        };
+        let task: proto::Task = self.unary("/a2a.v1.A2AService/CancelTask", request).await?;
+        Task::try_from(task).map_err(decode_error)
+    }
+
+    /// Resubscribe to the updates of a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The ID of the task.
+    ///
+    /// # Returns
+    ///
+    /// A stream of tasks, messages and task update events.
+    pub async fn resubscribe(
+        &mut self,
+        params: TaskResubscriptionParams,
+    ) -> Result<StreamingMessageStream, ClientError> {
+        let request = proto::TaskSubscriptionRequest {
+            name: task_name(&params.task_id),
+        };
+        self.server_streaming("/a2a.v1.A2AService/TaskSubscription", request)
+            .await
+    }
+
+    /// Set the push notification configuration of a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The task ID and configuration. The configuration ID, if
+    ///   any, is used as the ID of the created configuration.
+    ///
+    /// # Returns
+    ///
+    /// The task and configuration IDs of the stored configuration.
+    pub async fn set_task_push_notification_config(
+        &mut self,
+        params: SetTaskPushNotificationConfigParams,
+    ) -> Result<PushNotificationConfigResult, ClientError> {
+        let request = proto::CreateTaskPushNotificationConfigRequest {
+            parent: task_name(&params.task_id),
+            config_id: params.config.id.clone().unwrap_or_default(),
+            config: Some(proto::TaskPushNotificationConfig {
+                name: String::new(),
+                push_notification_config: Some(params.config.into()),
+            }),
+        };
+        let config: proto::TaskPushNotificationConfig = self
+            .unary("/a2a.v1.A2AService/CreateTaskPushNotificationConfig", request)
+            .await?;
+        let (task_id, config_id) = parse_config_name(&config.name)?;
+        Ok(PushNotificationConfigResult { task_id, config_id })
+    }
+
+    /// Get a push notification configuration of a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The task and configuration IDs.
+    ///
+    /// # Returns
+    ///
+    /// The push notification configuration.
+    pub async fn get_task_push_notification_config(
+        &mut self,
+        params: GetTaskPushNotificationConfigParams,
+    ) -> Result<PushNotificationConfig, ClientError> {
+        let request = proto::GetTaskPushNotificationConfigRequest {
+            name: config_name(&params.task_id, &params.config_id),
+        };
+        let config: proto::TaskPushNotificationConfig = self
+            .unary("/a2a.v1.A2AService/GetTaskPushNotificationConfig", request)
+            .await?;
+        config
+            .push_notification_config
+            .map(Into::into)
+            .ok_or_else(|| decode_error("Push notification config is empty".to_string()))
+    }
+
+    /// List the push notification configurations of a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The ID of the task.
+    ///
+    /// # Returns
+    ///
+    /// The configuration IDs and URLs.
+    pub async fn list_task_push_notification_configs(
+        &mut self,
+        params: ListTaskPushNotificationConfigParams,
+    ) -> Result<Vec<PushNotificationConfigInfo>, ClientError> {
+        let request = proto::ListTaskPushNotificationConfigRequest {
+            parent: task_name(&params.task_id),
+            page_size: 0,
+            page_token: String::new(),
+        };
+        let response: proto::ListTaskPushNotificationConfigResponse = self
+            .unary("/a2a.v1.A2AService/ListTaskPushNotificationConfig", request)
+            .await?;
+
+        response
+            .configs
+            .into_iter()
+            .map(|config| {
+                let (_, config_id) = parse_config_name(&config.name)?;
+                let url = config
+                    .push_notification_config
+                    .map(|c| c.url)
+                    .unwrap_or_default();
+                Ok(PushNotificationConfigInfo { config_id, url })
+            })
+            .collect()
+    }
+
+    /// Delete a push notification configuration of a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The task and configuration IDs.
+    pub async fn delete_task_push_notification_config(
+        &mut self,
+        params: DeleteTaskPushNotificationConfigParams,
+    ) -> Result<(), ClientError> {
+        let request = proto::DeleteTaskPushNotificationConfigRequest {
+            name: config_name(&params.task_id, &params.config_id),
+        };
+        self.unary("/a2a.v1.A2AService/DeleteTaskPushNotificationConfig", request)
+            .await
+    }
+
+    /// Get the agent card served over gRPC.
+    ///
+    /// # Returns
+    ///
+    /// The agent card.
+    pub async fn get_agent_card(&mut self) -> Result<AgentCard, ClientError> {
+        let card: proto::AgentCard = self
+            .unary("/a2a.v1.A2AService/GetAgentCard", proto::GetAgentCardRequest {})
+            .await?;
+        AgentCard::try_from(card).map_err(decode_error)
+    }
+
+    async fn unary<Req, Resp>(&mut self, path: &'static str, request: Req) -> Result<Resp, ClientError>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        let request = self.request(request)?;
+        let timeout = self.timeout;
+        within(timeout, async {
+            self.ready().await?;
+            let codec = tonic_prost::ProstCodec::default();
+            self.inner
+                .unary(request, PathAndQuery::from_static(path), codec)
+                .await
+                .map(tonic::Response::into_inner)
+                .map_err(status_to_client_error)
+        })
+        .await
+    }
+
+    async fn server_streaming<Req>(
+        &mut self,
+        path: &'static str,
+        request: Req,
+    ) -> Result<StreamingMessageStream, ClientError>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+    {
+        let request = self.request(request)?;
+        let timeout = self.timeout;
+        let stream = within(timeout, async {
+            self.ready().await?;
+            let codec = tonic_prost::ProstCodec::<Req, proto::StreamResponse>::default();
+            self.inner
+                .server_streaming(request, PathAndQuery::from_static(path), codec)
+                .await
+                .map_err(status_to_client_error)
+        })
+        .await?
+        .into_inner();
+
+        Ok(Box::pin(stream.map(|item| {
+            let response = item.map_err(status_to_client_error)?;
+            StreamingMessageResult::try_from(response).map_err(decode_error)
+        })))
+    }
+
+    fn request<Req>(&self, message: Req) -> Result<tonic::Request<Req>, A2AError> {
+        let mut request = tonic::Request::new(message);
+        if let Some(timeout) = self.timeout {
+            request.set_timeout(timeout);
+        }
+        if !self.extensions.is_empty() {
+            let value = extensions::render_extensions_header(&self.extensions)
+                .parse()
+                .map_err(|e| invalid_params(format!("Invalid extension URIs: {}", e)))?;
+            request.metadata_mut().insert(EXTENSIONS_METADATA_KEY, value);
+        }
+        Ok(request)
+    }
+
+    async fn ready(&mut self) -> Result<(), ClientError> {
+        self.inner
+            .ready()
+            .await
+            .map_err(|e| ClientError::transport(TransportErrorKind::of(&e, TransportErrorKind::Unavailable), e))
+    }
+}
+
+/// Run a call, failing with `Timeout` if it takes longer than `timeout`.
+async fn within<T>(
+    timeout: Option<Duration>,
+    call: impl Future<Output = Result<T, ClientError>>,
+) -> Result<T, ClientError> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, call).await.map_err(|_| ClientError::Timeout)?,
+        None => call.await,
+    }
+}
+
+/// Classify the status a call failed with.
+///
+/// Statuses carrying an A2A error, and those mapped to one by
+/// [`status_to_error`], are `Protocol` errors. `UNAVAILABLE` is a transport
+/// failure, `DEADLINE_EXCEEDED` and expired deadlines are timeouts,
+/// `UNAUTHENTICATED` and `PERMISSION_DENIED` authentication failures, and
+/// responses tonic could not decode are `Decode` errors.
+fn status_to_client_error(status: tonic::Status) -> ClientError {
+    if !status.details().is_empty() {
+        return ClientError::Protocol(status_to_error(&status));
+    }
+    match status.code() {
+        tonic::Code::Unavailable => {
+            let kind = TransportErrorKind::of(&status, TransportErrorKind::Unavailable);
+            ClientError::transport(kind, status)
+        }
+        tonic::Code::DeadlineExceeded => ClientError::Timeout,
+        // What tonic answers once the `grpc-timeout` deadline passes
+        tonic::Code::Cancelled if status.message() == tonic::TimeoutExpired(()).to_string() => ClientError::Timeout,
+        tonic::Code::Unauthenticated | tonic::Code::PermissionDenied => {
+            ClientError::Auth(status.message().to_string())
+        }
+        // The message prost gives undecodable responses
+        tonic::Code::Internal if status.message().starts_with("failed to decode Protobuf message") => {
+            ClientError::Decode(status.message().to_string())
+        }
+        _ => ClientError::Protocol(status_to_error(&status)),
+    }
+}
+
+/// Translate a gRPC status into an A2A error.
+///
+/// If the status details carry a JSON-encoded A2A error object (as produced
+/// by [`error_to_status`]), that error is returned as is. Otherwise the
+/// status code is mapped to the closest A2A error:
+///
+/// | gRPC code             | A2A error           |
+/// |-----------------------|---------------------|
+/// | `NOT_FOUND`           | `TaskNotFound`      |
+/// | `FAILED_PRECONDITION` | `TaskNotCancelable` |
+/// | `UNIMPLEMENTED`       | `UnsupportedOperation` |
+/// | `INVALID_ARGUMENT`    | `InvalidParams`     |
+/// | anything else         | `Internal`          |
+///
+/// # Arguments
+///
+/// * `status` - The gRPC status.
+///
+/// # Returns
+///
+/// The corresponding `A2AError`.
+pub fn status_to_error(status: &tonic::Status) -> A2AError {
+    if !status.details().is_empty()
+        && let Ok(error) = serde_json::from_slice::<A2AError>(status.details())
+    {
+        return error;
+    }
+
+    let message = status.message().to_string();
+    match status.code() {
+        tonic::Code::NotFound => A2AError::TaskNotFound(Box::new(TaskNotFoundError {
+            code: -32001,
+            message,
+            data: None,
+        })),
+        tonic::Code::FailedPrecondition => A2AError::TaskNotCancelable(Box::new(TaskNotCancelableError {
+            code: -32002,
+            message,
+            data: None,
+        })),
+        tonic::Code::Unimplemented => A2AError::UnsupportedOperation(Box::new(UnsupportedOperationError {
+            code: -32004,
+            message,
+            data: None,
+        })),
+        tonic::Code::InvalidArgument => invalid_params(message),
+        code => internal_error(format!("{:?}: {}", code, message)),
+    }
+}
+
+/// Translate an A2A error into a gRPC status.
+///
+/// The status code follows the table in [`status_to_error`], and the full
+/// error object is attached as JSON in the status details so that clients
+/// can recover it exactly.
+///
+/// # Arguments
+///
+/// * `error` - The A2A error.
+///
+/// # Returns
+///
+/// The corresponding `tonic::Status`.
+pub fn error_to_status(error: &A2AError) -> tonic::Status {
+    let (code, message) = match error {
+        A2AError::TaskNotFound(e) => (tonic::Code::NotFound, &e.message),
+        A2AError::TaskNotCancelable(e) => (tonic::Code::FailedPrecondition, &e.message),
+        A2AError::PushNotificationNotSupported(e) => (tonic::Code::Unimplemented, &e.message),
+        A2AError::UnsupportedOperation(e) => (tonic::Code::Unimplemented, &e.message),
+        A2AError::MethodNotFound(e) => (tonic::Code::Unimplemented, &e.message),
+        A2AError::ContentTypeNotSupported(e) => (tonic::Code::InvalidArgument, &e.message),
+        A2AError::InvalidParams(e) => (tonic::Code::InvalidArgument, &e.message),
+        A2AError::InvalidRequest(e) => (tonic::Code::InvalidArgument, &e.message),
+        A2AError::JSONParse(e) => (tonic::Code::InvalidArgument, &e.message),
+        A2AError::InvalidAgentResponse(e) => (tonic::Code::Internal, &e.message),
+        A2AError::Internal(e) => (tonic::Code::Internal, &e.message),
+    };
+    let details = serde_json::to_vec(error).unwrap_or_default();
+    tonic::Status::with_details(code, message.clone(), Bytes::from(details))
+}
+
+fn task_name(task_id: &str) -> String {
+    format!("tasks/{}", task_id)
+}
+
+fn config_name(task_id: &str, config_id: &str) -> String {
+    format!("tasks/{}/pushNotificationConfigs/{}", task_id, config_id)
+}
+
+fn parse_config_name(name: &str) -> Result<(String, String), ClientError> {
+    name.strip_prefix("tasks/")
+        .and_then(|rest| rest.split_once("/pushNotificationConfigs/"))
+        .map(|(task_id, config_id)| (task_id.to_string(), config_id.to_string()))
+        .ok_or_else(|| decode_error(format!("Invalid push notification config name: {}", name)))
+}
+
+fn internal_error(message: String) -> A2AError {
+    A2AError::Internal(Box::new(InternalError {
+        code: -32603,
+        message,
+        data: None,
+    }))
+}
+
+fn invalid_params(message: String) -> A2AError {
+    A2AError::InvalidParams(Box::new(InvalidParamsError {
+        code: -32602,
+        message,
+        data: None,
+    }))
+}
+
+fn decode_error(message: String) -> ClientError {
+    ClientError::Decode(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use std::future::{Ready, ready};
+    use std::task::{Context, Poll};
+    use tonic::Status;
+    use tonic::codegen::{BoxFuture, Service, StdError, http};
+    use tonic::server::{Grpc, NamedService, ServerStreamingService, UnaryService};
+    use tonic_prost::ProstCodec;
+
+    /// Unary handler backed by a closure.
+    struct Unary<F>(F);
+
+    impl<Req, Resp, F> UnaryService<Req> for Unary<F>
+    where
+        F: FnMut(Req) -> Result<Resp, Status>,
+    {
+        type Response = Resp;
+        type Future = Ready<Result<tonic::Response<Resp>, Status>>;
+
+        fn call(&mut self, request: tonic::Request<Req>) -> Self::Future {
+            ready((self.0)(request.into_inner()).map(tonic::Response::new))
+        }
+    }
+
+    /// Server streaming handler that replays a fixed list of items.
+    struct Replay<F>(F);
+
+    impl<Req, Resp, F> ServerStreamingService<Req> for Replay<F>
+    where
+        F: FnMut(Req) -> Result<Vec<Result<Resp, Status>>, Status>,
+        Resp: Send + 'static,
+    {
+        type Response = Resp;
+        type ResponseStream = tokio_stream::Iter<std::vec::IntoIter<Result<Resp, Status>>>;
+        type Future = Ready<Result<tonic::Response<Self::ResponseStream>, Status>>;
+
+        fn call(&mut self, request: tonic::Request<Req>) -> Self::Future {
+            ready(
+                (self.0)(request.into_inner())
+                    .map(|items| tonic::Response::new(tokio_stream::iter(items))),
+            )
+        }
+    }
+
+    fn proto_message(text: &str) -> proto::Message {
+        proto::Message {
+            message_id: "reply-1".to_string(),
+            role: proto::Role::Agent as i32,
+            content: vec![proto::Part {
+                part: Some(proto::part::Part::Text(text.to_string())),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn proto_task(state: proto::TaskState) -> proto::Task {
+        proto::Task {
+            id: "task-1".to_string(),
+            context_id: "ctx-1".to_string(),
+            status: Some(proto::TaskStatus {
+                state: state as i32,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn status_event(state: proto::TaskState, r#final: bool) -> proto::StreamResponse {
+        proto::StreamResponse {
+            payload: Some(proto::stream_response::Payload::StatusUpdate(
+                proto::TaskStatusUpdateEvent {
+                    task_id: "task-1".to_string(),
+                    context_id: "ctx-1".to_string(),
+                    status: Some(proto::TaskStatus {
+                        state: state as i32,
+                        ..Default::default()
+                    }),
+                    r#final,
+                    metadata: None,
+                },
+            )),
+        }
+    }
+
+    /// In-process A2A service answering with scripted responses.
+    #[derive(Clone)]
+    struct ScriptedAgent;
+
+    impl NamedService for ScriptedAgent {
+        const NAME: &'static str = "a2a.v1.A2AService";
+    }
+
+    impl<B> Service<http::Request<B>> for ScriptedAgent
+    where
+        B: tonic::codegen::Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/a2a.v1.A2AService/SendMessage" => Box::pin(async move {
+                    let header = req
+                        .headers()
+                        .get("x-a2a-extensions")
+                        .and_then(|value| value.to_str().ok())
+                        .map(extensions::parse_extensions_header)
+                        .unwrap_or_default();
+                    let handler = Unary(move |request: proto::SendMessageRequest| {
+                        let message = request.request.unwrap_or_default();
+                        if message.extensions != header {
+                            return Err(Status::invalid_argument("extension metadata does not match message"));
+                        }
+                        let text = match message.content.into_iter().next() {
+                            Some(proto::Part {
+                                part: Some(proto::part::Part::Text(text)),
+                            }) => text,
+                            _ => return Err(Status::invalid_argument("expected text")),
+                        };
+                        let mut reply = proto_message(&format!("echo: {}", text));
+                        reply.extensions = message.extensions;
+                        Ok(proto::SendMessageResponse {
+                            payload: Some(proto::send_message_response::Payload::Msg(reply)),
+                        })
+                    });
+                    Ok(Grpc::new(ProstCodec::default()).unary(handler, req).await)
+                }),
+                "/a2a.v1.A2AService/SendStreamingMessage" => Box::pin(async move {
+                    let handler = Replay(|_: proto::SendMessageRequest| {
+                        Ok(vec![
+                            Ok(proto::StreamResponse {
+                                payload: Some(proto::stream_response::Payload::Task(proto_task(
+                                    proto::TaskState::Submitted,
+                                ))),
+                            }),
+                            Ok(status_event(proto::TaskState::Working, false)),
+                            Ok(proto::StreamResponse {
+                                payload: Some(proto::stream_response::Payload::ArtifactUpdate(
+                                    proto::TaskArtifactUpdateEvent {
+                                        task_id: "task-1".to_string(),
+                                        context_id: "ctx-1".to_string(),
+                                        artifact: Some(proto::Artifact {
+                                            artifact_id: "artifact-1".to_string(),
+                                            parts: proto_message("Bonjour").content,
+                                            ..Default::default()
+                                        }),
+                                        last_chunk: true,
+                                        ..Default::default()
+                                    },
+                                )),
+                            }),
+                            Ok(status_event(proto::TaskState::Completed, true)),
+                        ])
+                    });
+                    Ok(Grpc::new(ProstCodec::default()).server_streaming(handler, req).await)
+                }),
+                "/a2a.v1.A2AService/TaskSubscription" => Box::pin(async move {
+                    let handler = Replay(|_: proto::TaskSubscriptionRequest| {
+                        Ok(vec![
+                            Ok(status_event(proto::TaskState::Working, false)),
+                            Err(Status::unavailable("agent restarting")),
+                        ])
+                    });
+                    Ok(Grpc::new(ProstCodec::default()).server_streaming(handler, req).await)
+                }),
+                "/a2a.v1.A2AService/GetTask" => Box::pin(async move {
+                    let handler = Unary(|request: proto::GetTaskRequest| {
+                        match request.name.as_str() {
+                            "tasks/task-1" => Ok(proto_task(proto::TaskState::Working)),
+                            "tasks/no-status" => Ok(proto::Task {
+                                status: None,
+                                ..proto_task(proto::TaskState::Working)
+                            }),
+                            "tasks/secret" => Err(Status::unauthenticated("token expired")),
+                            name => Err(Status::not_found(format!("{} not found", name))),
+                        }
+                    });
+                    Ok(Grpc::new(ProstCodec::default()).unary(handler, req).await)
+                }),
+                "/a2a.v1.A2AService/CancelTask" => Box::pin(async move {
+                    let handler = Unary(|_: proto::CancelTaskRequest| -> Result<proto::Task, _> {
+                        Err(error_to_status(&A2AError::TaskNotCancelable(Box::new(
+                            TaskNotCancelableError {
+                                code: -32002,
+                                message: "Task is already completed".to_string(),
+                                data: Some(serde_json::json!({"state": "completed"})),
+                            },
+                        ))))
+                    });
+                    Ok(Grpc::new(ProstCodec::default()).unary(handler, req).await)
+                }),
+                "/a2a.v1.A2AService/CreateTaskPushNotificationConfig" => Box::pin(async move {
+                    let handler = Unary(|request: proto::CreateTaskPushNotificationConfigRequest| {
+                        Ok(proto::TaskPushNotificationConfig {
+                            name: format!(
+                                "{}/pushNotificationConfigs/{}",
+                                request.parent, request.config_id
+                            ),
+                            push_notification_config: request
+                                .config
+                                .and_then(|c| c.push_notification_config),
+                        })
+                    });
+                    Ok(Grpc::new(ProstCodec::default()).unary(handler, req).await)
+                }),
+                "/a2a.v1.A2AService/ListTaskPushNotificationConfig" => Box::pin(async move {
+                    let handler = Unary(|request: proto::ListTaskPushNotificationConfigRequest| {
+                        Ok(proto::ListTaskPushNotificationConfigResponse {
+                            configs: vec![proto::TaskPushNotificationConfig {
+                                name: format!("{}/pushNotificationConfigs/cfg-1", request.parent),
+                                push_notification_config: Some(proto::PushNotificationConfig {
+                                    url: "https://example.com/hook".to_string(),
+                                    ..Default::default()
+                                }),
+                            }],
+                            next_page_token: String::new(),
+                        })
+                    });
+                    Ok(Grpc::new(ProstCodec::default()).unary(handler, req).await)
+                }),
+                "/a2a.v1.A2AService/DeleteTaskPushNotificationConfig" => Box::pin(async move {
+                    let handler = Unary(|_: proto::DeleteTaskPushNotificationConfigRequest| Ok(()));
+                    Ok(Grpc::new(ProstCodec::default()).unary(handler, req).await)
+                }),
+                _ => Box::pin(async move { Ok(Status::unimplemented("not scripted").into_http()) }),
+            }
+        }
+    }
+
+    /// A2A service that answers too late to every call.
+    #[derive(Clone)]
+    struct SlowAgent;
+
+    impl NamedService for SlowAgent {
+        const NAME: &'static str = "a2a.v1.A2AService";
+    }
+
+    impl<B: Send + 'static> Service<http::Request<B>> for SlowAgent {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _req: http::Request<B>) -> Self::Future {
+            Box::pin(async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(Status::unavailable("too late").into_http())
+            })
+        }
+    }
+
+    async fn start_service<S>(service: S) -> String
+    where
+        S: Service<http::Request<tonic::body::Body>, Response = http::Response<tonic::body::Body>, Error = Infallible>
+            + NamedService
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        S::Future: Send + 'static,
+    {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+        format!("http://{}", addr)
+    }
+
+    async fn start_agent() -> String {
+        start_service(ScriptedAgent).await
+    }
+
+    fn get_params(task_id: &str) -> GetTaskParams {
+        GetTaskParams {
+            task_id: task_id.to_string(),
+            history_length: None,
+            extra: ExtraFields::default(),
+        }
+    }
+
+    fn user_message(text: &str) -> SendMessageParams {
+        SendMessageParams {
+            message: Message {
+                kind: "message".to_string(),
+                message_id: "msg-1".to_string(),
+                parts: vec![Part::Text(TextPart {
+                    text: text.to_string(),
+                    metadata: None,
+                })],
+                role: MessageRole::User,
+                context_id: None,
+                extensions: None,
+                metadata: None,
+                reference_task_ids: None,
+                task_id: None,
+                extra: ExtraFields::default(),
+            },
+            configuration: None,
+            metadata: None,
+            extra: ExtraFields::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unary_calls() {
+        let mut client = A2AGrpcClient::connect(&start_agent().await).await.unwrap();
+
+        let result = client.send_message(user_message("hello")).await.unwrap();
+        assert_eq!(result.text().as_deref(), Some("echo: hello"));
+        assert_eq!(result.task_id(), None);
+        assert_eq!(result.into_message().unwrap().role, MessageRole::Agent);
+
+        let task = client
+            .get_task(GetTaskParams {
+                task_id: "task-1".to_string(),
+                history_length: None,
+                extra: ExtraFields::default(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(task.status.state, TaskState::Working);
+
+        let config = PushNotificationConfig {
+            url: "https://example.com/hook".to_string(),
+            authentication: None,
+            id: Some("cfg-1".to_string()),
+            token: None,
+        };
+        let result = client
+            .set_task_push_notification_config(SetTaskPushNotificationConfigParams {
+                task_id: "task-1".to_string(),
+                config,
+                extra: ExtraFields::default(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.task_id, "task-1");
+        assert_eq!(result.config_id, "cfg-1");
+
+        let configs = client
+            .list_task_push_notification_configs(ListTaskPushNotificationConfigParams {
+                task_id: "task-1".to_string(),
+                extra: ExtraFields::default(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].config_id, "cfg-1");
+
+        client
+            .delete_task_push_notification_config(DeleteTaskPushNotificationConfigParams {
+                task_id: "task-1".to_string(),
+                config_id: "cfg-1".to_string(),
+                extra: ExtraFields::default(),
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_extensions_are_sent_as_metadata() {
+        let client = A2AGrpcClient::connect(&start_agent().await).await.unwrap();
+        let mut client = client.with_extensions(["https://example.com/tracing", "https://example.com/tracing"]);
+        assert_eq!(client.extensions(), ["https://example.com/tracing"]);
+
+        match client.send_message(user_message("hello")).await.unwrap() {
+            SendMessageResult::Message(message) => {
+                assert_eq!(message.extensions.as_deref().unwrap(), ["https://example.com/tracing"]);
+            }
+            other => panic!("Expected message result, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_calls() {
+        let mut client = A2AGrpcClient::connect(&start_agent().await).await.unwrap();
+
+        let results: Vec<_> = client
+            .send_streaming_message(user_message("translate"))
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(results.len(), 4);
+        assert!(matches!(results[0], Ok(StreamingMessageResult::Task(_))));
+        assert!(matches!(results[2], Ok(StreamingMessageResult::TaskArtifactUpdate(ref e)) if e.last_chunk == Some(true)));
+        assert!(matches!(results[3], Ok(StreamingMessageResult::TaskStatusUpdate(ref e)) if e.final_event));
+
+        let mut stream = client
+            .resubscribe(TaskResubscriptionParams {
+                task_id: "task-1".to_string(),
+                extra: ExtraFields::default(),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(stream.next().await, Some(Ok(StreamingMessageResult::TaskStatusUpdate(_)))));
+        match stream.next().await {
+            Some(Err(error)) => {
+                assert_eq!(error.transport_kind(), Some(TransportErrorKind::Unavailable));
+                assert!(error.is_retryable());
+            }
+            other => panic!("Expected a transport error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_translated() {
+        let mut client = A2AGrpcClient::connect(&start_agent().await).await.unwrap();
+
+        // Plain status codes map to the closest A2A error
+        let error = client
+            .get_task(GetTaskParams {
+                task_id: "missing".to_string(),
+                history_length: None,
+                extra: ExtraFields::default(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ClientError::Protocol(A2AError::TaskNotFound(ref e)) if e.code == -32001));
+
+        // Error details carry the full A2A error
+        let error = client
+            .cancel_task(CancelTaskParams {
+                task_id: "task-1".to_string(),
+                extra: ExtraFields::default(),
+            })
+            .await
+            .unwrap_err();
+        match error {
+            ClientError::Protocol(A2AError::TaskNotCancelable(e)) => {
+                assert_eq!(e.message, "Task is already completed");
+                assert_eq!(e.data, Some(serde_json::json!({"state": "completed"})));
+            }
+            other => panic!("Expected TaskNotCancelable, got {:?}", other),
+        }
+
+        let error = client.get_agent_card().await.unwrap_err();
+        assert!(matches!(error.as_protocol(), Some(A2AError::UnsupportedOperation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_classified() {
+        // Connection refused: nothing listens on the port any more
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let error = A2AGrpcClient::connect(&url).await.unwrap_err();
+        assert_eq!(error.transport_kind(), Some(TransportErrorKind::ConnectionRefused));
+        assert!(error.is_retryable());
+
+        let error = A2AGrpcClient::connect("not a url").await.unwrap_err();
+        assert_eq!(error.transport_kind(), Some(TransportErrorKind::InvalidEndpoint));
+        assert!(!error.is_retryable());
+
+        // Timeout
+        let client = A2AGrpcClient::connect(&start_service(SlowAgent).await).await.unwrap();
+        let mut client = client.with_timeout(Duration::from_millis(50));
+        let error = client.get_task(get_params("task-1")).await.unwrap_err();
+        assert!(matches!(error, ClientError::Timeout));
+        assert!(error.is_retryable());
+
+        // Undecodable response, protocol error and refused credentials
+        let mut client = A2AGrpcClient::connect(&start_agent().await).await.unwrap();
+        let error = client.get_task(get_params("no-status")).await.unwrap_err();
+        assert!(matches!(error, ClientError::Decode(ref message) if message == "Task has no status"));
+        let error = client.get_task(get_params("missing")).await.unwrap_err();
+        assert!(matches!(error.as_protocol(), Some(A2AError::TaskNotFound(_))));
+        assert!(!error.is_retryable());
+        let error = client.get_task(get_params("secret")).await.unwrap_err();
+        assert!(matches!(error, ClientError::Auth(ref message) if message == "token expired"));
+    }
+
+    #[tokio::test]
+    async fn test_from_card_selects_grpc_interface() {
+        let url = start_agent().await;
+        let mut card = AgentCard::new(
+            "Test Agent".to_string(),
+            "A test agent".to_string(),
+            "1.0.0".to_string(),
+            "http://127.0.0.1:1/rpc".to_string(),
+            AgentCapabilities {
+                extensions: None,
+                push_notifications: None,
+                state_transition_history: None,
+                streaming: None,
+            },
+            vec!["text/plain".to_string()],
+            vec!["text/plain".to_string()],
+            vec![],
+        );
+
+        let error = A2AGrpcClient::from_card(&card).await.unwrap_err();
+        assert!(matches!(error, ClientError::Protocol(A2AError::UnsupportedOperation(_))));
+
+        card.additional_interfaces = Some(vec![AgentInterface {
+            url,
+            transport: "GRPC".to_string(),
+        }]);
+        let mut client = A2AGrpcClient::from_card(&card).await.unwrap();
+        assert!(client.send_message(user_message("hi")).await.is_ok());
+    }
+}
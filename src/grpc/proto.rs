@@ -0,0 +1,540 @@
+//! Protobuf bindings for `proto/a2a.proto` (package `a2a.v1`).
+//!
+//! These types follow the layout prost-build generates for the proto file.
+//! They are maintained by hand so that building the crate does not require
+//! protoc; keep them in sync when the proto definitions change.
+
+use std::collections::HashMap;
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SendMessageRequest {
+    #[prost(message, optional, tag = "1")]
+    pub request: Option<Message>,
+    #[prost(message, optional, tag = "2")]
+    pub configuration: Option<SendMessageConfiguration>,
+    #[prost(message, optional, tag = "3")]
+    pub metadata: Option<prost_types::Struct>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SendMessageResponse {
+    #[prost(oneof = "send_message_response::Payload", tags = "1, 2")]
+    pub payload: Option<send_message_response::Payload>,
+}
+
+/// Nested types of [`SendMessageResponse`].
+pub mod send_message_response {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Payload {
+        #[prost(message, tag = "1")]
+        Task(super::Task),
+        #[prost(message, tag = "2")]
+        Msg(super::Message),
+    }
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamResponse {
+    #[prost(oneof = "stream_response::Payload", tags = "1, 2, 3, 4")]
+    pub payload: Option<stream_response::Payload>,
+}
+
+/// Nested types of [`StreamResponse`].
+pub mod stream_response {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Payload {
+        #[prost(message, tag = "1")]
+        Task(super::Task),
+        #[prost(message, tag = "2")]
+        Msg(super::Message),
+        #[prost(message, tag = "3")]
+        StatusUpdate(super::TaskStatusUpdateEvent),
+        #[prost(message, tag = "4")]
+        ArtifactUpdate(super::TaskArtifactUpdateEvent),
+    }
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTaskRequest {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(int32, tag = "2")]
+    pub history_length: i32,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelTaskRequest {
+    #[prost(string, tag = "1")]
+    pub name: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TaskSubscriptionRequest {
+    #[prost(string, tag = "1")]
+    pub name: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TaskPushNotificationConfig {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(message, optional, tag = "2")]
+    pub push_notification_config: Option<PushNotificationConfig>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateTaskPushNotificationConfigRequest {
+    #[prost(string, tag = "1")]
+    pub parent: String,
+    #[prost(string, tag = "2")]
+    pub config_id: String,
+    #[prost(message, optional, tag = "3")]
+    pub config: Option<TaskPushNotificationConfig>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTaskPushNotificationConfigRequest {
+    #[prost(string, tag = "1")]
+    pub name: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListTaskPushNotificationConfigRequest {
+    #[prost(string, tag = "1")]
+    pub parent: String,
+    #[prost(int32, tag = "2")]
+    pub page_size: i32,
+    #[prost(string, tag = "3")]
+    pub page_token: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListTaskPushNotificationConfigResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub configs: Vec<TaskPushNotificationConfig>,
+    #[prost(string, tag = "2")]
+    pub next_page_token: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteTaskPushNotificationConfigRequest {
+    #[prost(string, tag = "1")]
+    pub name: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAgentCardRequest {}
+
+/// Configuration of a send message request.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SendMessageConfiguration {
+    #[prost(string, repeated, tag = "1")]
+    pub accepted_output_modes: Vec<String>,
+    #[prost(message, optional, tag = "2")]
+    pub push_notification: Option<PushNotificationConfig>,
+    #[prost(int32, tag = "3")]
+    pub history_length: i32,
+    #[prost(bool, tag = "4")]
+    pub blocking: bool,
+}
+
+/// A unit of work processed by an agent.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Task {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub context_id: String,
+    #[prost(message, optional, tag = "3")]
+    pub status: Option<TaskStatus>,
+    #[prost(message, repeated, tag = "4")]
+    pub artifacts: Vec<Artifact>,
+    #[prost(message, repeated, tag = "5")]
+    pub history: Vec<Message>,
+    #[prost(message, optional, tag = "6")]
+    pub metadata: Option<prost_types::Struct>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TaskState {
+    Unspecified = 0,
+    Submitted = 1,
+    Working = 2,
+    Completed = 3,
+    Failed = 4,
+    Cancelled = 5,
+    InputRequired = 6,
+    Rejected = 7,
+    AuthRequired = 8,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TaskStatus {
+    #[prost(enumeration = "TaskState", tag = "1")]
+    pub state: i32,
+    #[prost(message, optional, tag = "2")]
+    pub update: Option<Message>,
+    #[prost(message, optional, tag = "3")]
+    pub timestamp: Option<prost_types::Timestamp>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Part {
+    #[prost(oneof = "part::Part", tags = "1, 2, 3")]
+    pub part: Option<part::Part>,
+}
+
+/// Nested types of [`Part`].
+pub mod part {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Part {
+        #[prost(string, tag = "1")]
+        Text(String),
+        #[prost(message, tag = "2")]
+        File(super::FilePart),
+        #[prost(message, tag = "3")]
+        Data(super::DataPart),
+    }
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FilePart {
+    #[prost(string, tag = "3")]
+    pub mime_type: String,
+    #[prost(oneof = "file_part::File", tags = "1, 2")]
+    pub file: Option<file_part::File>,
+}
+
+/// Nested types of [`FilePart`].
+pub mod file_part {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum File {
+        #[prost(string, tag = "1")]
+        FileWithUri(String),
+        #[prost(bytes = "vec", tag = "2")]
+        FileWithBytes(Vec<u8>),
+    }
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DataPart {
+    #[prost(message, optional, tag = "1")]
+    pub data: Option<prost_types::Struct>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Role {
+    Unspecified = 0,
+    User = 1,
+    Agent = 2,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Message {
+    #[prost(string, tag = "1")]
+    pub message_id: String,
+    #[prost(string, tag = "2")]
+    pub context_id: String,
+    #[prost(string, tag = "3")]
+    pub task_id: String,
+    #[prost(enumeration = "Role", tag = "4")]
+    pub role: i32,
+    #[prost(message, repeated, tag = "5")]
+    pub content: Vec<Part>,
+    #[prost(message, optional, tag = "6")]
+    pub metadata: Option<prost_types::Struct>,
+    #[prost(string, repeated, tag = "7")]
+    pub extensions: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Artifact {
+    #[prost(string, tag = "1")]
+    pub artifact_id: String,
+    #[prost(string, tag = "3")]
+    pub name: String,
+    #[prost(string, tag = "4")]
+    pub description: String,
+    #[prost(message, repeated, tag = "5")]
+    pub parts: Vec<Part>,
+    #[prost(message, optional, tag = "6")]
+    pub metadata: Option<prost_types::Struct>,
+    #[prost(string, repeated, tag = "7")]
+    pub extensions: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TaskStatusUpdateEvent {
+    #[prost(string, tag = "1")]
+    pub task_id: String,
+    #[prost(string, tag = "2")]
+    pub context_id: String,
+    #[prost(message, optional, tag = "3")]
+    pub status: Option<TaskStatus>,
+    #[prost(bool, tag = "4")]
+    pub r#final: bool,
+    #[prost(message, optional, tag = "5")]
+    pub metadata: Option<prost_types::Struct>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TaskArtifactUpdateEvent {
+    #[prost(string, tag = "1")]
+    pub task_id: String,
+    #[prost(string, tag = "2")]
+    pub context_id: String,
+    #[prost(message, optional, tag = "3")]
+    pub artifact: Option<Artifact>,
+    #[prost(bool, tag = "4")]
+    pub append: bool,
+    #[prost(bool, tag = "5")]
+    pub last_chunk: bool,
+    #[prost(message, optional, tag = "6")]
+    pub metadata: Option<prost_types::Struct>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PushNotificationConfig {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub url: String,
+    #[prost(string, tag = "3")]
+    pub token: String,
+    #[prost(message, optional, tag = "4")]
+    pub authentication: Option<AuthenticationInfo>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AuthenticationInfo {
+    #[prost(string, repeated, tag = "1")]
+    pub schemes: Vec<String>,
+    #[prost(string, tag = "2")]
+    pub credentials: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AgentInterface {
+    #[prost(string, tag = "1")]
+    pub url: String,
+    #[prost(string, tag = "2")]
+    pub transport: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AgentCard {
+    #[prost(string, tag = "16")]
+    pub protocol_version: String,
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub description: String,
+    #[prost(string, tag = "3")]
+    pub url: String,
+    #[prost(string, tag = "14")]
+    pub preferred_transport: String,
+    #[prost(message, repeated, tag = "15")]
+    pub additional_interfaces: Vec<AgentInterface>,
+    #[prost(message, optional, tag = "4")]
+    pub provider: Option<AgentProvider>,
+    #[prost(string, tag = "5")]
+    pub version: String,
+    #[prost(string, tag = "6")]
+    pub documentation_url: String,
+    #[prost(message, optional, tag = "7")]
+    pub capabilities: Option<AgentCapabilities>,
+    #[prost(map = "string, message", tag = "8")]
+    pub security_schemes: HashMap<String, SecurityScheme>,
+    #[prost(message, repeated, tag = "9")]
+    pub security: Vec<Security>,
+    #[prost(string, repeated, tag = "10")]
+    pub default_input_modes: Vec<String>,
+    #[prost(string, repeated, tag = "11")]
+    pub default_output_modes: Vec<String>,
+    #[prost(message, repeated, tag = "12")]
+    pub skills: Vec<AgentSkill>,
+    #[prost(bool, tag = "13")]
+    pub supports_authenticated_extended_card: bool,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AgentProvider {
+    #[prost(string, tag = "1")]
+    pub url: String,
+    #[prost(string, tag = "2")]
+    pub organization: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AgentCapabilities {
+    #[prost(bool, tag = "1")]
+    pub streaming: bool,
+    #[prost(bool, tag = "2")]
+    pub push_notifications: bool,
+    #[prost(message, repeated, tag = "3")]
+    pub extensions: Vec<AgentExtension>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AgentExtension {
+    #[prost(string, tag = "1")]
+    pub uri: String,
+    #[prost(string, tag = "2")]
+    pub description: String,
+    #[prost(bool, tag = "3")]
+    pub required: bool,
+    #[prost(message, optional, tag = "4")]
+    pub params: Option<prost_types::Struct>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AgentSkill {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(string, tag = "3")]
+    pub description: String,
+    #[prost(string, repeated, tag = "4")]
+    pub tags: Vec<String>,
+    #[prost(string, repeated, tag = "5")]
+    pub examples: Vec<String>,
+    #[prost(string, repeated, tag = "6")]
+    pub input_modes: Vec<String>,
+    #[prost(string, repeated, tag = "7")]
+    pub output_modes: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StringList {
+    #[prost(string, repeated, tag = "1")]
+    pub list: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Security {
+    #[prost(map = "string, message", tag = "1")]
+    pub schemes: HashMap<String, StringList>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SecurityScheme {
+    #[prost(oneof = "security_scheme::Scheme", tags = "1, 2, 3, 4")]
+    pub scheme: Option<security_scheme::Scheme>,
+}
+
+/// Nested types of [`SecurityScheme`].
+pub mod security_scheme {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Scheme {
+        #[prost(message, tag = "1")]
+        ApiKeySecurityScheme(super::ApiKeySecurityScheme),
+        #[prost(message, tag = "2")]
+        HttpAuthSecurityScheme(super::HttpAuthSecurityScheme),
+        #[prost(message, tag = "3")]
+        Oauth2SecurityScheme(super::OAuth2SecurityScheme),
+        #[prost(message, tag = "4")]
+        OpenIdConnectSecurityScheme(super::OpenIdConnectSecurityScheme),
+    }
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApiKeySecurityScheme {
+    #[prost(string, tag = "1")]
+    pub description: String,
+    #[prost(string, tag = "2")]
+    pub location: String,
+    #[prost(string, tag = "3")]
+    pub name: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HttpAuthSecurityScheme {
+    #[prost(string, tag = "1")]
+    pub description: String,
+    #[prost(string, tag = "2")]
+    pub scheme: String,
+    #[prost(string, tag = "3")]
+    pub bearer_format: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OAuth2SecurityScheme {
+    #[prost(string, tag = "1")]
+    pub description: String,
+    #[prost(message, optional, tag = "2")]
+    pub flows: Option<OAuthFlows>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpenIdConnectSecurityScheme {
+    #[prost(string, tag = "1")]
+    pub description: String,
+    #[prost(string, tag = "2")]
+    pub open_id_connect_url: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OAuthFlows {
+    #[prost(oneof = "o_auth_flows::Flow", tags = "1, 2, 3, 4")]
+    pub flow: Option<o_auth_flows::Flow>,
+}
+
+/// Nested types of [`OAuthFlows`].
+pub mod o_auth_flows {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Flow {
+        #[prost(message, tag = "1")]
+        AuthorizationCode(super::AuthorizationCodeOAuthFlow),
+        #[prost(message, tag = "2")]
+        ClientCredentials(super::ClientCredentialsOAuthFlow),
+        #[prost(message, tag = "3")]
+        Implicit(super::ImplicitOAuthFlow),
+        #[prost(message, tag = "4")]
+        Password(super::PasswordOAuthFlow),
+    }
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AuthorizationCodeOAuthFlow {
+    #[prost(string, tag = "1")]
+    pub authorization_url: String,
+    #[prost(string, tag = "2")]
+    pub token_url: String,
+    #[prost(string, tag = "3")]
+    pub refresh_url: String,
+    #[prost(map = "string, string", tag = "4")]
+    pub scopes: HashMap<String, String>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClientCredentialsOAuthFlow {
+    #[prost(string, tag = "1")]
+    pub token_url: String,
+    #[prost(string, tag = "2")]
+    pub refresh_url: String,
+    #[prost(map = "string, string", tag = "3")]
+    pub scopes: HashMap<String, String>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImplicitOAuthFlow {
+    #[prost(string, tag = "1")]
+    pub authorization_url: String,
+    #[prost(string, tag = "2")]
+    pub refresh_url: String,
+    #[prost(map = "string, string", tag = "3")]
+    pub scopes: HashMap<String, String>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PasswordOAuthFlow {
+    #[prost(string, tag = "1")]
+    pub token_url: String,
+    #[prost(string, tag = "2")]
+    pub refresh_url: String,
+    #[prost(map = "string, string", tag = "3")]
+    pub scopes: HashMap<String, String>,
+}
@@ -0,0 +1,317 @@
+//! Helper functions for working with the A2A protocol.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::lenient::ParseWarning;
+use crate::{A2AError, A2ARequest, IncomingError, IncomingRequest, InternalError, JSONParseError, LazyRequest};
+#[cfg(feature = "std")]
+use crate::{
+    A2AResponsePayload, InvalidAgentResponseError, JsonRpcId, PushNotificationConfig, PushNotificationConfigResult,
+    RequestMethod, SendMessageResult, StreamingMessageResult, Task,
+};
+#[cfg(all(feature = "std", any(feature = "spec-0-3", feature = "ext-methods")))]
+use crate::ListTasksResult;
+use serde::{Deserialize, Serialize};
+
+/// Parse a JSON string into an A2A request.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string to parse.
+///
+/// # Returns
+///
+/// A `Result` containing either the parsed request or an error.
+pub fn parse_request(json: &str) -> Result<serde_json::Value, A2AError> {
+    serde_json::from_str(json).map_err(|e| A2AError::JSONParse(Box::new(JSONParseError {
+        code: -32700,
+        message: format!("Invalid JSON payload: {}", e),
+        data: None,
+    })))
+}
+
+/// Parse a JSON string into a typed A2A request.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string to parse.
+///
+/// # Returns
+///
+/// A `Result` containing either the request or an error. Invalid JSON is
+/// a `JSONParse` error; see `TryFrom<serde_json::Value>` for
+/// [`A2ARequest`] for the other cases.
+pub fn parse_a2a_request(json: &str) -> Result<A2ARequest, A2AError> {
+    A2ARequest::try_from(parse_request(json)?)
+}
+
+/// Parse a JSON string into a typed A2A request, reporting what is
+/// suspicious about it.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string to parse.
+///
+/// # Returns
+///
+/// A `Result` containing either the request and its warnings, as for
+/// [`A2ARequest::from_value_lenient`], or an error, as for
+/// [`parse_a2a_request`].
+pub fn parse_request_lenient(json: &str) -> Result<(A2ARequest, Vec<ParseWarning>), A2AError> {
+    A2ARequest::from_value_lenient(parse_request(json)?)
+}
+
+/// Parse a JSON string into a typed A2A request that may be a
+/// notification, i.e. lack an `id`.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string to parse.
+///
+/// # Returns
+///
+/// A `Result` containing either the request and its ID or an error, as
+/// for [`parse_a2a_request`], with the request's ID so that notifications
+/// are refused without an answer.
+pub fn parse_incoming_request(json: &str) -> Result<IncomingRequest, IncomingError> {
+    IncomingRequest::try_from(parse_request(json).map_err(IncomingError::unidentified)?)
+}
+
+/// Parse the envelope of a JSON-RPC request, leaving the parameters raw.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string to parse.
+///
+/// # Returns
+///
+/// A `Result` containing either the request envelope or an error.
+pub fn parse_envelope(json: &str) -> Result<LazyRequest, A2AError> {
+    serde_json::from_str(json).map_err(|e| A2AError::JSONParse(Box::new(JSONParseError {
+        code: -32700,
+        message: format!("Invalid JSON payload: {}", e),
+        data: None,
+    })))
+}
+
+/// Parse a JSON string into a type that borrows from it.
+///
+/// Intended for the types in [`crate::borrowed`], which avoid copying
+/// strings out of the input.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string to parse.
+///
+/// # Returns
+///
+/// A `Result` containing either the parsed value or an error.
+pub fn parse_request_borrowed<'a, T: Deserialize<'a>>(json: &'a str) -> Result<T, A2AError> {
+    serde_json::from_str(json).map_err(|e| A2AError::JSONParse(Box::new(JSONParseError {
+        code: -32700,
+        message: format!("Invalid JSON payload: {}", e),
+        data: None,
+    })))
+}
+
+/// Parse a JSON buffer into an owned type using simd-json.
+///
+/// simd-json parses in place, so the buffer is modified and should not
+/// be reused as JSON afterwards.
+///
+/// # Arguments
+///
+/// * `json` - The JSON bytes to parse.
+///
+/// # Returns
+///
+/// A `Result` containing either the parsed value or an error.
+#[cfg(feature = "simd")]
+pub fn parse_request_simd<T: serde::de::DeserializeOwned>(json: &mut [u8]) -> Result<T, A2AError> {
+    simd_json::serde::from_slice(json).map_err(|e| A2AError::JSONParse(Box::new(JSONParseError {
+        code: -32700,
+        message: format!("Invalid JSON payload: {}", e),
+        data: None,
+    })))
+}
+
+/// Parse the JSON-RPC response to a request into its typed result.
+///
+/// The result is checked against what the method answers with, e.g. a
+/// `tasks/get` answered with a message is rejected. For `message/stream`
+/// and `tasks/resubscribe`, pass the `data` of one server-sent event.
+///
+/// # Arguments
+///
+/// * `method` - The method of the request.
+/// * `json` - The JSON string of the response.
+/// * `expected_id` - The ID of the request, to check the response
+///   answers it. Error responses with a `null` ID, sent when the server
+///   could not read the request, pass the check.
+///
+/// # Returns
+///
+/// The typed result, or an error: `JSONParse` for invalid JSON, the
+/// error of an error response, or `InvalidAgentResponse` for a response
+/// with the wrong ID or a result that does not fit the method.
+#[cfg(feature = "std")]
+pub fn parse_response_for(
+    method: RequestMethod,
+    json: &str,
+    expected_id: Option<&JsonRpcId>,
+) -> Result<A2AResponsePayload, A2AError> {
+    use crate::response::ResponseValidator;
+    use serde_json::Value;
+
+    let envelope = parse_request(json)?;
+    let id = envelope.get("id").unwrap_or(&Value::Null);
+    let error = envelope.get("error");
+    if let Some(expected) = expected_id
+        && !(error.is_some() && id.is_null())
+    {
+        ResponseValidator::check_id(expected, id)?;
+    }
+
+    if let Some(error) = error {
+        let error = A2AError::deserialize(error)
+            .map_err(|e| invalid_response(format!("Malformed error response: {}", e)))?;
+        return Err(error);
+    }
+    let result = envelope
+        .get("result")
+        .ok_or_else(|| invalid_response("Response has neither result nor error".to_string()))?;
+    ResponseValidator::check_kind(method, result)?;
+
+    let payload = match method {
+        RequestMethod::MessageSend => SendMessageResult::deserialize(result).map(A2AResponsePayload::SendMessage),
+        RequestMethod::TasksGet | RequestMethod::TasksCancel => Task::deserialize(result).map(A2AResponsePayload::Task),
+        RequestMethod::TasksPushNotificationConfigSet => {
+            PushNotificationConfigResult::deserialize(result).map(A2AResponsePayload::PushConfigSet)
+        }
+        RequestMethod::TasksPushNotificationConfigGet => {
+            PushNotificationConfig::deserialize(result).map(A2AResponsePayload::PushConfig)
+        }
+        RequestMethod::TasksPushNotificationConfigList => {
+            Vec::deserialize(result).map(A2AResponsePayload::PushConfigList)
+        }
+        // The spec answers with null; earlier agents answered with true
+        RequestMethod::TasksPushNotificationConfigDelete => match result {
+            Value::Null | Value::Bool(true) => Ok(A2AResponsePayload::Deleted),
+            other => return Err(invalid_response(format!("{} result is {}, expected null", method, other))),
+        },
+        RequestMethod::MessageStream | RequestMethod::TasksResubscribe => {
+            StreamingMessageResult::deserialize(result).map(A2AResponsePayload::Streaming)
+        }
+        #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+        RequestMethod::TasksList => ListTasksResult::deserialize(result).map(A2AResponsePayload::TaskList),
+    };
+    payload.map_err(|e| invalid_response(format!("Malformed {} result: {}", method, e)))
+}
+
+#[cfg(feature = "std")]
+fn invalid_response(message: String) -> A2AError {
+    A2AError::InvalidAgentResponse(Box::new(InvalidAgentResponseError {
+        code: -32006,
+        message,
+        data: None,
+    }))
+}
+
+/// Serialize an A2A response to a JSON string.
+///
+/// # Arguments
+///
+/// * `response` - The response to serialize.
+///
+/// # Returns
+///
+/// A `Result` containing either the serialized JSON string or an error.
+pub fn serialize_response<T: Serialize>(response: &T) -> Result<String, A2AError> {
+    into_string(serialize_response_to_vec(response)?)
+}
+
+/// Serialize an A2A response to an indented JSON string, for logs and debugging.
+///
+/// # Arguments
+///
+/// * `response` - The response to serialize.
+///
+/// # Returns
+///
+/// A `Result` containing either the serialized JSON string or an error.
+#[cfg(feature = "std")]
+pub fn serialize_response_pretty<T: Serialize>(response: &T) -> Result<String, A2AError> {
+    let mut out = Vec::with_capacity(128);
+    serialize_with(response, &mut serde_json::Serializer::pretty(&mut out))?;
+    into_string(out)
+}
+
+/// Serialize an A2A response to a byte vector.
+///
+/// # Arguments
+///
+/// * `response` - The response to serialize.
+///
+/// # Returns
+///
+/// A `Result` containing either the serialized JSON bytes or an error.
+pub fn serialize_response_to_vec<T: Serialize>(response: &T) -> Result<Vec<u8>, A2AError> {
+    #[cfg(feature = "std")]
+    {
+        let mut out = Vec::with_capacity(128);
+        serialize_response_to_writer(response, &mut out)?;
+        Ok(out)
+    }
+    // serde_json has no streaming serializer without std, so failures
+    // carry no path
+    #[cfg(not(feature = "std"))]
+    serde_json::to_vec(response).map_err(|e| A2AError::Internal(Box::new(InternalError {
+        code: -32603,
+        message: format!("Internal error: {}", e),
+        data: None,
+    })))
+}
+
+/// Serialize an A2A response directly into a writer.
+///
+/// The writer is not buffered or flushed; wrap sockets and files in a
+/// `BufWriter`.
+///
+/// # Arguments
+///
+/// * `response` - The response to serialize.
+/// * `writer` - The destination for the JSON bytes.
+///
+/// # Returns
+///
+/// `Ok(())` on success, or an `Internal` error if serialization or a write fails.
+#[cfg(feature = "std")]
+pub fn serialize_response_to_writer<T: Serialize, W: std::io::Write>(response: &T, writer: W) -> Result<(), A2AError> {
+    serialize_with(response, &mut serde_json::Serializer::new(writer))
+}
+
+/// Serialize with path tracking, mapping failures to `Internal` errors.
+///
+/// The path of the value that failed to serialize is included in the
+/// message and in `data.path`.
+#[cfg(feature = "std")]
+fn serialize_with<T, W, F>(response: &T, serializer: &mut serde_json::Serializer<W, F>) -> Result<(), A2AError>
+where
+    T: Serialize,
+    W: std::io::Write,
+    F: serde_json::ser::Formatter,
+{
+    serde_path_to_error::serialize(response, serializer).map_err(|e| A2AError::Internal(Box::new(InternalError {
+        code: -32603,
+        message: format!("Internal error: {}", e),
+        data: Some(serde_json::json!({"path": e.path().to_string()})),
+    })))
+}
+
+fn into_string(bytes: Vec<u8>) -> Result<String, A2AError> {
+    String::from_utf8(bytes).map_err(|e| A2AError::Internal(Box::new(InternalError {
+        code: -32603,
+        message: format!("Internal error: {}", e),
+        data: None,
+    })))
+}
@@ -0,0 +1,437 @@
+//! Typed identifiers.
+//!
+//! Task, message, context, artifact and push notification config ids are all
+//! plain strings on the wire, which makes them easy to mix up. The newtypes
+//! in this module serialize transparently as strings but are distinct types,
+//! so passing a [`ContextId`] where a [`TaskId`] is expected fails to compile.
+//!
+//! The protocol structs keep their `String` fields for now; typed accessors
+//! such as [`Task::typed_id`] bridge the two until the fields themselves move
+//! to the newtypes in the next breaking release.
+//!
+//! ```
+//! use a2a_rs::ids::{ContextId, TaskId};
+//!
+//! let task_id = TaskId::new("task-123").unwrap();
+//! let context_id = ContextId::from("ctx-456");
+//! assert_eq!(task_id.as_str(), "task-123");
+//! assert_eq!(context_id.to_string(), "ctx-456");
+//! assert!(TaskId::new("not a valid id!").is_err());
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::{
+    Artifact, JsonRpcId, Message, PushNotificationConfig, Task, TaskArtifactUpdateEvent, TaskStatusUpdateEvent,
+};
+use serde::{Deserialize, Serialize};
+use core::borrow::Borrow;
+use core::fmt;
+
+/// Maximum length of ids without a dedicated validator.
+const MAX_ID_LEN: usize = 255;
+
+/// Validate an id that the protocol does not constrain beyond being present.
+fn validate_opaque_id(label: &str, id: &str) -> Result<(), String> {
+    if id.is_empty() {
+        return Err(format!("{} cannot be empty", label));
+    }
+
+    if id.len() > MAX_ID_LEN {
+        return Err(format!("{} is too long (max {} characters)", label, MAX_ID_LEN));
+    }
+
+    Ok(())
+}
+
+macro_rules! id_type {
+    ($(#[$doc:meta])* $name:ident, $validate:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Create a validated id.
+            ///
+            /// # Arguments
+            ///
+            /// * `id` - The raw id.
+            ///
+            /// # Returns
+            ///
+            /// The id, or `Err(String)` with an error message if it is invalid.
+            pub fn new(id: impl Into<String>) -> Result<Self, String> {
+                let id = id.into();
+                let validate: fn(&str) -> Result<(), String> = $validate;
+                validate(&id)?;
+                Ok(Self(id))
+            }
+
+            /// Get the id as a string slice.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Consume the id and return the underlying string.
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        /// Wraps the string without validation, for ids received from a peer.
+        impl From<String> for $name {
+            fn from(id: String) -> Self {
+                Self(id)
+            }
+        }
+
+        /// Wraps the string without validation, for ids received from a peer.
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                Self(id.to_string())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+    };
+}
+
+id_type!(
+    /// Identifier of a task.
+    TaskId,
+    crate::validation::validate_task_id
+);
+
+id_type!(
+    /// Identifier of a message, created by the message creator.
+    MessageId,
+    crate::validation::validate_message_id
+);
+
+id_type!(
+    /// Server-generated identifier grouping related tasks and messages.
+    ContextId,
+    |id| validate_opaque_id("Context ID", id)
+);
+
+id_type!(
+    /// Identifier of an artifact within a task.
+    ArtifactId,
+    |id| validate_opaque_id("Artifact ID", id)
+);
+
+id_type!(
+    /// Identifier of a push notification config within a task.
+    ConfigId,
+    |id| validate_opaque_id("Config ID", id)
+);
+
+// ============================================================================
+// TYPED ACCESSORS
+// ============================================================================
+
+impl Message {
+    /// Get the message ID as a [`MessageId`].
+    pub fn typed_message_id(&self) -> MessageId {
+        MessageId::from(self.message_id.clone())
+    }
+
+    /// Get the context ID as a [`ContextId`], if set.
+    pub fn typed_context_id(&self) -> Option<ContextId> {
+        self.context_id.clone().map(ContextId::from)
+    }
+
+    /// Get the task ID as a [`TaskId`], if set.
+    pub fn typed_task_id(&self) -> Option<TaskId> {
+        self.task_id.clone().map(TaskId::from)
+    }
+}
+
+impl Task {
+    /// Get the task ID as a [`TaskId`].
+    pub fn typed_id(&self) -> TaskId {
+        TaskId::from(self.id.clone())
+    }
+
+    /// Get the context ID as a [`ContextId`].
+    pub fn typed_context_id(&self) -> ContextId {
+        ContextId::from(self.context_id.clone())
+    }
+}
+
+impl Artifact {
+    /// Get the artifact ID as an [`ArtifactId`].
+    pub fn typed_artifact_id(&self) -> ArtifactId {
+        ArtifactId::from(self.artifact_id.clone())
+    }
+}
+
+impl TaskStatusUpdateEvent {
+    /// Get the task ID as a [`TaskId`].
+    pub fn typed_task_id(&self) -> TaskId {
+        TaskId::from(self.task_id.clone())
+    }
+
+    /// Get the context ID as a [`ContextId`].
+    pub fn typed_context_id(&self) -> ContextId {
+        ContextId::from(self.context_id.clone())
+    }
+}
+
+impl TaskArtifactUpdateEvent {
+    /// Get the task ID as a [`TaskId`].
+    pub fn typed_task_id(&self) -> TaskId {
+        TaskId::from(self.task_id.clone())
+    }
+
+    /// Get the context ID as a [`ContextId`].
+    pub fn typed_context_id(&self) -> ContextId {
+        ContextId::from(self.context_id.clone())
+    }
+}
+
+impl PushNotificationConfig {
+    /// Get the config ID as a [`ConfigId`], if set.
+    pub fn typed_id(&self) -> Option<ConfigId> {
+        self.id.clone().map(ConfigId::from)
+    }
+}
+
+// ============================================================================
+// ID SOURCES
+// ============================================================================
+
+/// Mints the IDs a server assigns to what it creates.
+///
+/// Sources decide the shape of IDs, e.g. random UUIDs with
+/// [`UuidV4Source`], time-ordered IDs for sortable storage keys, or
+/// numbered IDs with [`SequentialSource`] for exact assertions in tests.
+/// Code minting IDs takes a source as `&mut impl IdSource` or
+/// `&mut dyn IdSource`.
+pub trait IdSource {
+    /// Mint the ID of a new task.
+    fn new_task_id(&mut self) -> TaskId;
+
+    /// Mint the ID of a new context.
+    fn new_context_id(&mut self) -> ContextId;
+
+    /// Mint the ID of a new message.
+    fn new_message_id(&mut self) -> MessageId;
+
+    /// Mint the ID of a new push notification config.
+    fn new_config_id(&mut self) -> ConfigId;
+
+    /// Mint the ID of a new JSON-RPC request.
+    ///
+    /// Defaults to a string minted like a message ID.
+    fn new_request_id(&mut self) -> JsonRpcId {
+        JsonRpcId::String(self.new_message_id().into_inner())
+    }
+}
+
+/// Numbers the IDs of each kind from 1: `task-1`, `ctx-1`, `msg-1`,
+/// `config-1`, and request IDs `1`, `2`, ... as integers.
+///
+/// The numbers restart with the source, so IDs repeat across processes;
+/// servers that keep tasks across restarts need globally unique IDs.
+#[derive(Debug, Clone, Default)]
+pub struct SequentialSource {
+    tasks: u64,
+    contexts: u64,
+    messages: u64,
+    configs: u64,
+    requests: i64,
+}
+
+impl SequentialSource {
+    /// Create a source starting at 1.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IdSource for SequentialSource {
+    fn new_task_id(&mut self) -> TaskId {
+        self.tasks += 1;
+        TaskId::from(format!("task-{}", self.tasks))
+    }
+
+    fn new_context_id(&mut self) -> ContextId {
+        self.contexts += 1;
+        ContextId::from(format!("ctx-{}", self.contexts))
+    }
+
+    fn new_message_id(&mut self) -> MessageId {
+        self.messages += 1;
+        MessageId::from(format!("msg-{}", self.messages))
+    }
+
+    fn new_config_id(&mut self) -> ConfigId {
+        self.configs += 1;
+        ConfigId::from(format!("config-{}", self.configs))
+    }
+
+    fn new_request_id(&mut self) -> JsonRpcId {
+        self.requests += 1;
+        JsonRpcId::Integer(self.requests)
+    }
+}
+
+/// Mints random version 4 UUIDs of every kind, unique across processes.
+#[cfg(feature = "uuid")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidV4Source;
+
+#[cfg(feature = "uuid")]
+impl IdSource for UuidV4Source {
+    fn new_task_id(&mut self) -> TaskId {
+        TaskId::from(uuid::Uuid::new_v4().to_string())
+    }
+
+    fn new_context_id(&mut self) -> ContextId {
+        ContextId::from(uuid::Uuid::new_v4().to_string())
+    }
+
+    fn new_message_id(&mut self) -> MessageId {
+        MessageId::from(uuid::Uuid::new_v4().to_string())
+    }
+
+    fn new_config_id(&mut self) -> ConfigId {
+        ConfigId::from(uuid::Uuid::new_v4().to_string())
+    }
+}
+
+/// An [`IdSource`] shared by the clones of a server component, which mint
+/// IDs through `&self`.
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub(crate) struct SharedIdSource(std::sync::Arc<std::sync::Mutex<Box<dyn IdSource + Send>>>);
+
+#[cfg(feature = "std")]
+impl SharedIdSource {
+    pub(crate) fn new(source: impl IdSource + Send + 'static) -> Self {
+        Self(std::sync::Arc::new(std::sync::Mutex::new(Box::new(source))))
+    }
+
+    /// Mint an ID with the source locked.
+    pub(crate) fn mint<T>(&self, mint: impl FnOnce(&mut dyn IdSource) -> T) -> T {
+        let mut source = self.0.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        mint(&mut **source)
+    }
+}
+
+/// The source of server components configured with none: UUIDs with the
+/// `uuid` feature, sequential IDs without it.
+#[cfg(feature = "std")]
+pub(crate) fn default_source() -> SharedIdSource {
+    #[cfg(feature = "uuid")]
+    return SharedIdSource::new(UuidV4Source);
+    #[cfg(not(feature = "uuid"))]
+    SharedIdSource::new(SequentialSource::new())
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for SharedIdSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedIdSource")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Map;
+
+    #[test]
+    fn test_ids_serialize_as_plain_strings() {
+        let id = TaskId::new("task-1").unwrap();
+        assert_eq!(serde_json::to_value(&id).unwrap(), serde_json::json!("task-1"));
+
+        let parsed: ContextId = serde_json::from_value(serde_json::json!("ctx/1")).unwrap();
+        assert_eq!(parsed.to_string(), "ctx/1");
+        assert_eq!(String::from(parsed), "ctx/1");
+    }
+
+    #[test]
+    fn test_constructors_validate() {
+        assert!(TaskId::new("task_1-a").is_ok());
+        assert!(TaskId::new("task 1").is_err());
+        assert!(MessageId::new("").is_err());
+        assert!(ContextId::new("urn:ctx:1").is_ok());
+        assert!(ArtifactId::new("").is_err());
+        assert!(ConfigId::new("x".repeat(256)).is_err());
+    }
+
+    #[test]
+    fn test_ids_key_maps_and_look_up_by_str() {
+        let mut tasks = Map::new();
+        tasks.insert(TaskId::from("task-1"), 1);
+        assert_eq!(tasks.get("task-1"), Some(&1));
+    }
+
+    #[test]
+    fn test_typed_accessors() {
+        let task: Task = serde_json::from_value(serde_json::json!({
+            "id": "task-1",
+            "kind": "task",
+            "contextId": "ctx-1",
+            "status": {"state": "submitted"}
+        }))
+        .unwrap();
+
+        assert_eq!(task.typed_id().as_str(), "task-1");
+        assert_eq!(task.typed_context_id().as_str(), "ctx-1");
+    }
+
+    #[test]
+    fn test_sequential_source_numbers_each_kind() {
+        let mut ids = SequentialSource::new();
+        assert_eq!(ids.new_task_id(), TaskId::from("task-1"));
+        assert_eq!(ids.new_task_id(), TaskId::from("task-2"));
+        assert_eq!(ids.new_context_id(), ContextId::from("ctx-1"));
+        assert_eq!(ids.new_message_id(), MessageId::from("msg-1"));
+        assert_eq!(ids.new_config_id(), ConfigId::from("config-1"));
+        assert_eq!(ids.new_request_id(), JsonRpcId::Integer(1));
+        assert_eq!(ids.new_request_id(), JsonRpcId::Integer(2));
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_uuid_source_mints_distinct_uuids() {
+        let mut ids = UuidV4Source;
+        let task_id = ids.new_task_id();
+        assert!(uuid::Uuid::parse_str(task_id.as_str()).is_ok());
+        assert_ne!(task_id, ids.new_task_id());
+        assert!(uuid::Uuid::parse_str(ids.new_config_id().as_str()).is_ok());
+        // Request IDs default to strings minted like message IDs
+        let JsonRpcId::String(request_id) = ids.new_request_id() else {
+            panic!("expected a string request ID");
+        };
+        assert!(uuid::Uuid::parse_str(&request_id).is_ok());
+    }
+}
@@ -0,0 +1,254 @@
+//! Image file parts.
+//!
+//! Agents exchanging images conventionally describe them in the metadata of
+//! the file part: `width` and `height` in pixels, and `alt`, a text
+//! alternative for readers that cannot see the image. [`FilePart::image_from_bytes`]
+//! builds such a part and [`FilePart::image_meta`] reads the description back.
+//!
+//! With the `image-meta` feature, the dimensions of PNG and JPEG images are
+//! read from their headers when the caller does not supply them.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::metadata::{get_meta, set_meta};
+use crate::validation::check_media_kind;
+use crate::{FileContent, FilePart, FileWithBytes};
+use serde_json::Value;
+
+/// Metadata key of the image width in pixels.
+pub const WIDTH_KEY: &str = "width";
+/// Metadata key of the image height in pixels.
+pub const HEIGHT_KEY: &str = "height";
+/// Metadata key of the text alternative of the image.
+pub const ALT_KEY: &str = "alt";
+
+/// The conventional description of an image in file part metadata.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImageMeta {
+    /// Width in pixels
+    pub width: Option<u32>,
+    /// Height in pixels
+    pub height: Option<u32>,
+    /// Text alternative of the image
+    pub alt: Option<String>,
+}
+
+impl FilePart {
+    /// Create a file part holding an image, described in its metadata.
+    ///
+    /// With the `image-meta` feature, a missing width or height is read
+    /// from the header of PNG and JPEG images.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The raw image, base64 encoded into the part.
+    /// * `mime` - The media type, which must be `image/*`.
+    /// * `meta` - The description of the image.
+    ///
+    /// # Returns
+    ///
+    /// The file part, or an error if the media type is not an image type.
+    pub fn image_from_bytes(bytes: &[u8], mime: &str, meta: ImageMeta) -> Result<Self, String> {
+        check_media_kind(Some(mime), "image")?;
+        #[cfg(feature = "image-meta")]
+        let meta = match sniff_dimensions(bytes) {
+            Some((width, height)) => ImageMeta {
+                width: meta.width.or(Some(width)),
+                height: meta.height.or(Some(height)),
+                ..meta
+            },
+            None => meta,
+        };
+
+        let mut metadata = None;
+        if let Some(width) = meta.width {
+            set_meta(&mut metadata, WIDTH_KEY, Value::from(width));
+        }
+        if let Some(height) = meta.height {
+            set_meta(&mut metadata, HEIGHT_KEY, Value::from(height));
+        }
+        if let Some(alt) = meta.alt {
+            set_meta(&mut metadata, ALT_KEY, Value::String(alt));
+        }
+        Ok(FilePart {
+            file: FileContent::WithBytes(FileWithBytes {
+                bytes: crate::security::base64_encode(bytes),
+                name: None,
+                mime_type: Some(mime.to_string()),
+            }),
+            metadata,
+        })
+    }
+
+    /// Read the description of an image from the metadata of the part.
+    ///
+    /// # Returns
+    ///
+    /// The description, with `None` for absent keys, or an error if the
+    /// media type of the file is missing or not `image/*`, or a key holds
+    /// a value of the wrong type.
+    pub fn image_meta(&self) -> Result<ImageMeta, String> {
+        let mime = match &self.file {
+            FileContent::WithBytes(file) => file.mime_type.as_deref(),
+            FileContent::WithUri(file) => file.mime_type.as_deref(),
+        };
+        check_media_kind(mime, "image")?;
+
+        let dimension = |key: &str| match get_meta(&self.metadata, key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => value
+                .as_u64()
+                .and_then(|value| u32::try_from(value).ok())
+                .map(Some)
+                .ok_or_else(|| format!("Image metadata `{}` must be a number of pixels, got {}", key, value)),
+        };
+        let alt = match get_meta(&self.metadata, ALT_KEY) {
+            None | Some(Value::Null) => None,
+            Some(Value::String(alt)) => Some(alt.clone()),
+            Some(value) => return Err(format!("Image metadata `{}` must be a string, got {}", ALT_KEY, value)),
+        };
+        Ok(ImageMeta {
+            width: dimension(WIDTH_KEY)?,
+            height: dimension(HEIGHT_KEY)?,
+            alt,
+        })
+    }
+}
+
+/// Read the width and height of a PNG or JPEG image from its header.
+///
+/// # Arguments
+///
+/// * `bytes` - The raw image.
+///
+/// # Returns
+///
+/// The width and height in pixels, or `None` if the image is of another
+/// format or its header is truncated.
+#[cfg(feature = "image-meta")]
+pub fn sniff_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+    let be16 = |at: usize| bytes.get(at..at + 2).map(|b| u32::from(u16::from_be_bytes([b[0], b[1]])));
+    let be32 = |at: usize| bytes.get(at..at + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+
+    // The first chunk of a PNG is IHDR, starting with the dimensions
+    if bytes.starts_with(PNG_SIGNATURE) {
+        return (bytes.get(12..16)? == b"IHDR").then_some((be32(16)?, be32(20)?));
+    }
+
+    // Walk the JPEG segments up to the start of frame
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut at = 2;
+    loop {
+        if *bytes.get(at)? != 0xFF {
+            return None;
+        }
+        let marker = *bytes.get(at + 1)?;
+        match marker {
+            // Fill bytes before a marker
+            0xFF => at += 1,
+            // Markers without a length
+            0x01 | 0xD0..=0xD7 => at += 2,
+            // End of image, or start of scan before any frame
+            0xD9 | 0xDA => return None,
+            // Start of frame, except DHT, JPG and DAC, which share the range
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                return Some((be16(at + 7)?, be16(at + 5)?));
+            }
+            _ => at += 2 + usize::try_from(be16(at + 2)?).ok()?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Part;
+
+    /// A 3×2 RGB PNG.
+    const PNG: &[u8] = &[
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52, 0x00, 0x00,
+        0x00, 0x03, 0x00, 0x00, 0x00, 0x02, 0x08, 0x02, 0x00, 0x00, 0x00, 0x12, 0x16, 0xf1, 0x4d, 0x00, 0x00, 0x00,
+        0x10, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0xf8, 0xcf, 0xc0, 0x00, 0x41, 0x0c, 0x70, 0x16, 0x00, 0x41,
+        0xd2, 0x05, 0xfb, 0x6f, 0xf1, 0x16, 0xc7, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60,
+        0x82,
+    ];
+
+    #[test]
+    fn test_image_meta_round_trip() {
+        let meta = ImageMeta {
+            width: Some(640),
+            height: Some(480),
+            alt: Some("A red square".to_string()),
+        };
+        let part = FilePart::image_from_bytes(PNG, "image/png", meta.clone()).unwrap();
+        assert_eq!(part.metadata, Some(serde_json::json!({"width": 640, "height": 480, "alt": "A red square"})));
+        let FileContent::WithBytes(file) = &part.file else {
+            panic!("expected bytes");
+        };
+        assert!(file.bytes.starts_with("iVBORw0KGgo"));
+        assert_eq!(file.mime_type.as_deref(), Some("image/png"));
+
+        let json = serde_json::to_string(&Part::from(part)).unwrap();
+        let part = FilePart::try_from(serde_json::from_str::<Part>(&json).unwrap()).unwrap();
+        assert_eq!(part.image_meta().unwrap(), meta);
+    }
+
+    #[test]
+    fn test_image_mime_is_checked() {
+        let error = FilePart::image_from_bytes(PNG, "application/pdf", ImageMeta::default()).unwrap_err();
+        assert!(error.contains("application/pdf"));
+        assert!(FilePart::image_from_bytes(PNG, "image/", ImageMeta::default()).is_err());
+        assert!(FilePart::image_from_bytes(PNG, "IMAGE/PNG", ImageMeta::default()).is_ok());
+
+        let mut part = FilePart::image_from_bytes(PNG, "image/png", ImageMeta::default()).unwrap();
+        let FileContent::WithBytes(file) = &mut part.file else {
+            panic!("expected bytes");
+        };
+        file.mime_type = None;
+        assert!(part.image_meta().is_err());
+    }
+
+    #[test]
+    fn test_malformed_image_meta() {
+        let mut part = FilePart::image_from_bytes(PNG, "image/png", ImageMeta::default()).unwrap();
+        part.metadata = Some(serde_json::json!({"width": "wide", "alt": null}));
+        assert!(part.image_meta().unwrap_err().contains("`width`"));
+        part.metadata = Some(serde_json::json!({"width": -1}));
+        assert!(part.image_meta().is_err());
+        part.metadata = Some(serde_json::json!({"alt": 7}));
+        assert!(part.image_meta().unwrap_err().contains("`alt`"));
+        part.metadata = None;
+        assert_eq!(part.image_meta().unwrap(), ImageMeta::default());
+    }
+
+    #[cfg(feature = "image-meta")]
+    #[test]
+    fn test_sniffed_dimensions() {
+        assert_eq!(sniff_dimensions(PNG), Some((3, 2)));
+        assert_eq!(sniff_dimensions(&PNG[..20]), None);
+        assert_eq!(sniff_dimensions(b"GIF89a"), None);
+
+        // SOI, an APP0 segment, then a baseline frame of 640×480
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x4A, 0x46, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x01, 0xE0, 0x02, 0x80, 0x03,
+        ];
+        assert_eq!(sniff_dimensions(&jpeg), Some((640, 480)));
+        assert_eq!(sniff_dimensions(&jpeg[..12]), None);
+
+        let part = FilePart::image_from_bytes(PNG, "image/png", ImageMeta {
+            width: None,
+            height: Some(20),
+            alt: None,
+        })
+        .unwrap();
+        assert_eq!(part.image_meta().unwrap(), ImageMeta {
+            width: Some(3),
+            height: Some(20),
+            alt: None,
+        });
+    }
+}
@@ -0,0 +1,136 @@
+//! Tolerant deserialization of integer parameters.
+//!
+//! Some SDKs write integers such as `historyLength` as JSON floats (`10.0`),
+//! and foreign payloads may carry values that do not fit the Rust field.
+//! The helpers here accept integral floats and saturate values outside the
+//! range of the field instead of failing the whole request. Fractional and
+//! non-finite numbers are still rejected.
+
+use core::fmt;
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+
+/// Deserialize an optional `i32`, saturating at its bounds.
+///
+/// Negative values are kept; callers that need a count reject them when
+/// validating.
+pub(crate) fn optional_i32<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i32>, D::Error> {
+    let value = deserializer.deserialize_option(OptionalIntegral)?;
+    Ok(value.map(|n| n.clamp(i32::MIN.into(), i32::MAX.into()) as i32))
+}
+
+/// Deserialize an optional `u32`, saturating at `u32::MAX` and rejecting
+/// negative values.
+pub(crate) fn optional_u32<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u32>, D::Error> {
+    match deserializer.deserialize_option(OptionalIntegral)? {
+        Some(n) if n < 0 => Err(de::Error::invalid_value(
+            Unexpected::Signed(n.max(i64::MIN.into()) as i64),
+            &"a non-negative integer",
+        )),
+        value => Ok(value.map(|n| n.min(u32::MAX.into()) as u32)),
+    }
+}
+
+/// Whether a float is a finite whole number.
+pub(crate) fn is_integral(value: f64) -> bool {
+    // Every float of at least 2^53 in magnitude is whole
+    const EXACT: f64 = 9_007_199_254_740_992.0;
+    value.is_finite() && (!(-EXACT..EXACT).contains(&value) || value == value as i64 as f64)
+}
+
+/// Reads `null` or any integral number, widened to `i128`.
+struct OptionalIntegral;
+
+impl<'de> Visitor<'de> for OptionalIntegral {
+    type Value = Option<i128>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an integer or null")
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(Integral).map(Some)
+    }
+}
+
+/// Reads an integer, or a float without a fractional part.
+struct Integral;
+
+impl<'de> Visitor<'de> for Integral {
+    type Value = i128;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an integer")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_i128<E: de::Error>(self, value: i128) -> Result<Self::Value, E> {
+        Ok(value)
+    }
+
+    fn visit_u128<E: de::Error>(self, value: u128) -> Result<Self::Value, E> {
+        Ok(value.min(i128::MAX as u128) as i128)
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+        if !is_integral(value) {
+            return Err(E::invalid_value(Unexpected::Float(value), &self));
+        }
+        // Float to integer casts saturate
+        Ok(value as i128)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use crate::alloc_prelude::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize)]
+    struct Params {
+        #[serde(default, deserialize_with = "optional_i32")]
+        signed: Option<i32>,
+        #[serde(default, deserialize_with = "optional_u32")]
+        unsigned: Option<u32>,
+    }
+
+    fn parse(value: serde_json::Value) -> Result<(Option<i32>, Option<u32>), String> {
+        serde_json::from_value::<Params>(value).map(|p| (p.signed, p.unsigned)).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_integral_floats_and_saturation() {
+        assert_eq!(parse(json!({})), Ok((None, None)));
+        assert_eq!(parse(json!({"signed": null, "unsigned": null})), Ok((None, None)));
+        assert_eq!(parse(json!({"signed": 10.0, "unsigned": 10.0})), Ok((Some(10), Some(10))));
+        assert_eq!(parse(json!({"signed": -5, "unsigned": 0})), Ok((Some(-5), Some(0))));
+        assert_eq!(parse(json!({"signed": 2147483648_u64})), Ok((Some(i32::MAX), None)));
+        assert_eq!(parse(json!({"signed": -1e300, "unsigned": 1e300})), Ok((Some(i32::MIN), Some(u32::MAX))));
+        assert_eq!(parse(json!({"unsigned": u64::MAX})), Ok((None, Some(u32::MAX))));
+    }
+
+    #[test]
+    fn test_rejects_fractions_negatives_and_non_numbers() {
+        assert!(parse(json!({"signed": 10.5})).unwrap_err().contains("floating point `10.5`, expected an integer"));
+        assert!(parse(json!({"unsigned": -5})).unwrap_err().contains("integer `-5`, expected a non-negative integer"));
+        assert!(parse(json!({"unsigned": -0.5})).is_err());
+        assert!(parse(json!({"signed": "10"})).unwrap_err().contains("expected an integer"));
+    }
+}
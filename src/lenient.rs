@@ -0,0 +1,642 @@
+//! Salvaging tasks and agent cards from partially invalid JSON.
+//!
+//! Strict deserialization rejects a whole task when a single history
+//! message is malformed. [`Task::from_value_lenient`] and
+//! [`AgentCard::from_value_lenient`] instead drop the broken entries of
+//! lists and maps and the broken optional fields, and report each with a
+//! [`ParseWarning`]. Only the fields a usable value cannot do without must
+//! be valid. Strict parsing remains the default everywhere else.
+//!
+//! Input that parses but looks suspicious is reported too: unknown fields,
+//! unknown roles and part kinds, legacy method names and explicit nulls
+//! for required fields. [`A2ARequest::from_value_lenient`] and
+//! [`helpers::parse_request_lenient`](crate::helpers::parse_request_lenient)
+//! report these for requests. With the `tracing` feature, every warning is
+//! also emitted as a `WARN` event, its detail redacted with
+//! [`redaction::redact_message`](crate::redaction::redact_message).
+//!
+//! ```
+//! use a2a_rs::Task;
+//! use a2a_rs::lenient::WarningKind;
+//! use serde_json::json;
+//!
+//! let (task, warnings) = Task::from_value_lenient(json!({
+//!     "kind": "task",
+//!     "id": "task-1",
+//!     "contextId": "ctx-1",
+//!     "status": {"state": "completed"},
+//!     "history": [
+//!         {"kind": "message", "messageId": "msg-1", "role": "user", "parts": [{"kind": "text", "text": "hi"}]},
+//!         {"kind": "message", "messageId": "msg-2"}
+//!     ]
+//! }))
+//! .unwrap();
+//! assert_eq!(task.history.unwrap().len(), 1);
+//! assert_eq!(warnings[0].path, "/history/1");
+//! assert_eq!(warnings[0].kind, WarningKind::DroppedInvalidItem);
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::strict::{KnownFields, PathSegment};
+use crate::{
+    A2AError, A2AErrorResponse, A2ARequest, AgentCard, AgentInterface, AgentProvider, AgentSkill, Artifact, Message,
+    RequestMethod, SecurityScheme, Task, TaskStatus,
+};
+use core::fmt;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// What a [`ParseWarning`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningKind {
+    /// A field the type does not know, kept in its `extra` fields.
+    UnknownField,
+    /// A value this version does not know, such as a `system` role or a
+    /// `video` part, kept as sent.
+    UnknownEnumValue,
+    /// A name older peers use instead of the current one, such as the
+    /// `sendMessage` method.
+    LegacyAlias,
+    /// An invalid list entry or optional field, left out.
+    DroppedInvalidItem,
+    /// An explicit `null` for a required field, replaced by its default.
+    NullForRequired,
+    /// A history message reusing the ID of an earlier one, kept. Identical
+    /// copies can be removed with [`Task::dedup_history`].
+    DuplicateMessageId,
+}
+
+/// Something accepted or dropped while parsing leniently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// The JSON pointer of the value, e.g. `/history/2`.
+    pub path: String,
+    /// What is wrong with it.
+    pub kind: WarningKind,
+    /// A description for logs, e.g. why it could not be parsed.
+    pub detail: String,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.detail)
+    }
+}
+
+/// Collects the warnings about one document.
+///
+/// The lenient entry points return the collected warnings as a
+/// `Vec<ParseWarning>`; code parsing its own documents can use the
+/// collector directly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseWarnings {
+    warnings: Vec<ParseWarning>,
+}
+
+impl ParseWarnings {
+    /// Create an empty collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a warning.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The JSON pointer of the value.
+    /// * `kind` - What is wrong with it.
+    /// * `detail` - A description for logs.
+    pub fn push(&mut self, path: impl Into<String>, kind: WarningKind, detail: impl Into<String>) {
+        self.warnings.push(ParseWarning {
+            path: path.into(),
+            kind,
+            detail: detail.into(),
+        });
+    }
+
+    /// Record the fields of `value` that `T` does not know.
+    ///
+    /// Fields of parts of unknown kinds are not reported.
+    pub fn unknown_fields<T: KnownFields>(&mut self, value: &Value) {
+        for path in crate::strict::unknown_field_paths::<T>(value) {
+            let path = pointer(&path);
+            // Strict parsing reports the kind of unknown parts as a field;
+            // those are enum values, recorded by `unknown_enum_values`
+            if path.ends_with("/kind") && self.warnings.iter().any(|w| w.path == path) {
+                continue;
+            }
+            self.push(path, WarningKind::UnknownField, "unknown field");
+        }
+    }
+
+    /// Record the message roles and part kinds in `value` that this
+    /// version does not know.
+    ///
+    /// Metadata and the data of data parts are free-form and not searched.
+    pub fn unknown_enum_values(&mut self, value: &Value) {
+        self.enum_values(value, "");
+    }
+
+    fn enum_values(&mut self, value: &Value, path: &str) {
+        match value {
+            Value::Object(object) => {
+                if object.contains_key("messageId")
+                    && let Some(Value::String(role)) = object.get("role")
+                    && !matches!(role.as_str(), "user" | "agent")
+                {
+                    self.push(format!("{}/role", path), WarningKind::UnknownEnumValue, format!("unknown role `{}`", role));
+                }
+                if let Some(Value::Array(parts)) = object.get("parts") {
+                    for (index, part) in parts.iter().enumerate() {
+                        if let Some(Value::String(kind)) = part.get("kind")
+                            && !matches!(kind.as_str(), "text" | "file" | "data")
+                        {
+                            self.push(
+                                format!("{}/parts/{}/kind", path, index),
+                                WarningKind::UnknownEnumValue,
+                                format!("unknown part kind `{}`", kind),
+                            );
+                        }
+                    }
+                }
+                for (key, item) in object {
+                    if key != "metadata" && key != "data" {
+                        self.enum_values(item, &format!("{}/{}", path, escape(key)));
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.enum_values(item, &format!("{}/{}", path, index));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Record the messages of the task history in `value` whose ID an
+    /// earlier message already has.
+    pub fn duplicate_message_ids(&mut self, value: &Value) {
+        let Some(Value::Array(history)) = value.get("history") else {
+            return;
+        };
+        for (index, message) in history.iter().enumerate() {
+            let Some(Value::String(id)) = message.get("messageId") else {
+                continue;
+            };
+            let same_id = |earlier: &Value| earlier.get("messageId") == message.get("messageId");
+            let Some(first) = history[..index].iter().position(same_id) else {
+                continue;
+            };
+            let detail = if history[first] == *message {
+                format!("message `{}` repeats /history/{}", id, first)
+            } else {
+                format!("message `{}` differs from /history/{} with the same ID", id, first)
+            };
+            self.push(format!("/history/{}/messageId", index), WarningKind::DuplicateMessageId, detail);
+        }
+    }
+
+    /// The warnings recorded so far.
+    pub fn as_slice(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Check if no warning was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    /// Take the recorded warnings.
+    pub fn into_vec(self) -> Vec<ParseWarning> {
+        self.warnings
+    }
+
+    /// Hand the warnings to the caller of a lenient entry point, logging
+    /// them with the `tracing` feature.
+    ///
+    /// Details quote the values that failed to parse, which may be what a
+    /// user wrote, so the logged details are redacted; the returned ones
+    /// are not.
+    fn report(self) -> Vec<ParseWarning> {
+        #[cfg(feature = "tracing")]
+        {
+            let policy = crate::redaction::RedactionPolicy::default();
+            for warning in &self.warnings {
+                let detail = crate::redaction::redact_message(&warning.detail, &policy);
+                tracing::warn!(path = %warning.path, kind = ?warning.kind, "{}", detail);
+            }
+        }
+        self.warnings
+    }
+
+    fn check<T: DeserializeOwned>(&mut self, value: &Value, path: impl FnOnce() -> String) -> bool {
+        match T::deserialize(value) {
+            Ok(_) => true,
+            Err(e) => {
+                self.push(path(), WarningKind::DroppedInvalidItem, e.to_string());
+                false
+            }
+        }
+    }
+
+    /// Drop an optional field that does not parse.
+    fn field<T: DeserializeOwned>(&mut self, object: &mut Map<String, Value>, parent: &str, key: &str) {
+        if let Some(value) = object.get(key)
+            && !value.is_null()
+            && !self.check::<T>(value, || format!("{}/{}", parent, escape(key)))
+        {
+            object.remove(key);
+        }
+    }
+
+    /// Drop the entries of a list that do not parse, or the whole field if
+    /// it is optional and not a list.
+    fn items<T: DeserializeOwned>(&mut self, object: &mut Map<String, Value>, key: &str, optional: bool) {
+        match object.get_mut(key) {
+            Some(Value::Array(items)) => {
+                let mut index = 0;
+                items.retain(|item| {
+                    index += 1;
+                    self.check::<T>(item, || format!("/{}/{}", escape(key), index - 1))
+                });
+            }
+            Some(Value::Null) | None => {}
+            Some(_) if optional => self.field::<Vec<T>>(object, "", key),
+            Some(_) => {}
+        }
+    }
+
+    /// Drop the entries of an optional map that do not parse, or the whole
+    /// field if it is not a map.
+    fn entries<T: DeserializeOwned>(&mut self, object: &mut Map<String, Value>, key: &str) {
+        match object.get_mut(key) {
+            Some(Value::Object(entries)) => {
+                entries.retain(|name, entry| self.check::<T>(entry, || format!("/{}/{}", escape(key), escape(name))));
+            }
+            Some(Value::Null) | None => {}
+            Some(_) => self.field::<Map<String, Value>>(object, "", key),
+        }
+    }
+
+    /// Replace an explicit `null` for a required field by its default.
+    fn null_for_required(&mut self, object: &mut Map<String, Value>, key: &str, default: Value) {
+        if let Some(value @ Value::Null) = object.get_mut(key) {
+            self.push(format!("/{}", escape(key)), WarningKind::NullForRequired, format!("null read as {}", default));
+            *value = default;
+        }
+    }
+}
+
+/// Convert a path of [`crate::strict::unknown_field_paths`] into a JSON
+/// pointer.
+fn pointer(path: &[PathSegment]) -> String {
+    let mut pointer = String::new();
+    for segment in path {
+        pointer.push('/');
+        match segment {
+            PathSegment::Key(key) => pointer.push_str(&escape(key)),
+            PathSegment::Index(index) => pointer.push_str(&index.to_string()),
+        }
+    }
+    pointer
+}
+
+/// Escape a key for use in a JSON pointer, as RFC 6901 says: `~` becomes
+/// `~0` and `/` becomes `~1`.
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn salvage<T: DeserializeOwned + KnownFields>(
+    mut value: Value,
+    prune: impl FnOnce(&mut Map<String, Value>, &mut ParseWarnings),
+) -> Result<(T, Vec<ParseWarning>), serde_json::Error> {
+    // Suspicious values are looked for before pruning, so their paths
+    // match the input
+    let mut suspicious = ParseWarnings::new();
+    suspicious.unknown_enum_values(&value);
+    suspicious.unknown_fields::<T>(&value);
+    suspicious.duplicate_message_ids(&value);
+
+    let mut warnings = ParseWarnings::new();
+    if let Value::Object(object) = &mut value {
+        prune(object, &mut warnings);
+    }
+    let parsed = serde_json::from_value(value)?;
+
+    // What was inside a dropped value does not matter any more
+    let dropped: Vec<String> = warnings.warnings.iter().map(|w| format!("{}/", w.path)).collect();
+    warnings.warnings.extend(
+        suspicious
+            .into_vec()
+            .into_iter()
+            .filter(|w| !dropped.iter().any(|prefix| w.path.starts_with(prefix.as_str()))),
+    );
+    Ok((parsed, warnings.report()))
+}
+
+impl Task {
+    /// Parse a task, dropping what cannot be parsed instead of failing.
+    ///
+    /// Malformed entries of `history`, `artifacts` and `statusHistory` are
+    /// skipped, and malformed optional fields, including the status
+    /// message, are left out. A `null` kind is read as `task`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The task as JSON.
+    ///
+    /// # Returns
+    ///
+    /// The task and a warning for each value dropped or suspicious, or an
+    /// error if `id`, `kind`, `contextId` or `status` is missing or invalid.
+    pub fn from_value_lenient(value: Value) -> Result<(Task, Vec<ParseWarning>), serde_json::Error> {
+        salvage(value, |task, salvage| {
+            salvage.null_for_required(task, "kind", Value::from("task"));
+            if let Some(Value::Object(status)) = task.get_mut("status") {
+                salvage.field::<Message>(status, "/status", "message");
+            }
+            salvage.items::<Message>(task, "history", true);
+            salvage.items::<Artifact>(task, "artifacts", true);
+            salvage.items::<TaskStatus>(task, "statusHistory", true);
+            salvage.field::<A2AErrorResponse>(task, "", "error");
+            salvage.field::<String>(task, "", "createdAt");
+            salvage.field::<String>(task, "", "updatedAt");
+        })
+    }
+}
+
+impl AgentCard {
+    /// Parse an agent card, dropping what cannot be parsed instead of
+    /// failing.
+    ///
+    /// Malformed skills, security schemes and additional interfaces are
+    /// skipped, and malformed optional fields are left out. `null` skills
+    /// and default modes are read as empty lists.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The agent card as JSON.
+    ///
+    /// # Returns
+    ///
+    /// The card and a warning for each value dropped or suspicious, or an
+    /// error if a required field such as `name`, `url`, `capabilities` or
+    /// the list of `skills` is missing or invalid.
+    pub fn from_value_lenient(value: Value) -> Result<(AgentCard, Vec<ParseWarning>), serde_json::Error> {
+        salvage(value, |card, salvage| {
+            for key in ["skills", "defaultInputModes", "defaultOutputModes"] {
+                salvage.null_for_required(card, key, Value::Array(Vec::new()));
+            }
+            salvage.items::<AgentSkill>(card, "skills", false);
+            salvage.entries::<SecurityScheme>(card, "securitySchemes");
+            salvage.items::<AgentInterface>(card, "additionalInterfaces", true);
+            salvage.field::<AgentProvider>(card, "", "provider");
+            salvage.field::<Vec<crate::Map<String, Vec<String>>>>(card, "", "security");
+            for key in ["preferredTransport", "documentationUrl", "iconUrl"] {
+                salvage.field::<String>(card, "", key);
+            }
+            salvage.field::<bool>(card, "", "supportsAuthenticatedExtendedCard");
+        })
+    }
+}
+
+impl A2ARequest {
+    /// Parse a request, reporting what is suspicious about it.
+    ///
+    /// Nothing is dropped: the request must be as valid as for
+    /// `A2ARequest::try_from`. A `null` `jsonrpc` is read as `2.0`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The request as JSON.
+    ///
+    /// # Returns
+    ///
+    /// The request and a warning for each legacy method name, unknown
+    /// field, role or part kind, or the error of `A2ARequest::try_from`.
+    pub fn from_value_lenient(mut value: Value) -> Result<(A2ARequest, Vec<ParseWarning>), A2AError> {
+        let mut warnings = ParseWarnings::new();
+        if let Value::Object(request) = &mut value {
+            warnings.null_for_required(request, "jsonrpc", Value::from("2.0"));
+            if let Some(Value::String(name)) = request.get("method")
+                && RequestMethod::is_legacy_alias(name)
+                && let Some(method) = RequestMethod::from_str(name)
+            {
+                warnings.push("/method", WarningKind::LegacyAlias, format!("`{}` is an old name of `{}`", name, method));
+            }
+        }
+        warnings.unknown_enum_values(&value);
+        warnings.unknown_fields::<A2ARequest>(&value);
+        let request = A2ARequest::try_from(value)?;
+        Ok((request, warnings.report()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn message(id: &str) -> Value {
+        json!({"kind": "message", "messageId": id, "role": "user", "parts": [{"kind": "text", "text": "hi"}]})
+    }
+
+    #[test]
+    fn test_task_with_one_bad_message() {
+        let value = json!({
+            "kind": "task",
+            "id": "task-1",
+            "contextId": "ctx-1",
+            "status": {"state": "working", "message": {"kind": "message", "parts": 3}},
+            "history": [message("msg-1"), {"kind": "message", "messageId": "msg-2", "role": "user"}, message("msg-3")],
+            "artifacts": [{"artifactId": "a-1", "parts": []}, "not an artifact"],
+            "statusHistory": [{"state": "submitted"}, {"state": 5}],
+            "createdAt": 12
+        });
+        assert!(serde_json::from_value::<Task>(value.clone()).is_err());
+
+        let (task, warnings) = Task::from_value_lenient(value).unwrap();
+        let history = task.history.unwrap();
+        assert_eq!(history.iter().map(|m| m.message_id.as_str()).collect::<Vec<_>>(), ["msg-1", "msg-3"]);
+        assert_eq!(task.artifacts.unwrap().len(), 1);
+        assert_eq!(task.status_history.unwrap().len(), 1);
+        assert_eq!(task.status.message, None);
+        assert_eq!(task.created_at, None);
+        let paths: Vec<&str> = warnings.iter().map(|w| w.path.as_str()).collect();
+        assert_eq!(paths, ["/status/message", "/history/1", "/artifacts/1", "/statusHistory/1", "/createdAt"]);
+        assert!(warnings[1].to_string().starts_with("/history/1: missing field"));
+
+        // A valid task parses without warnings, and mandatory fields stay mandatory
+        let (_, warnings) = Task::from_value_lenient(json!({
+            "kind": "task", "id": "t", "contextId": "c", "status": {"state": "completed"}, "history": [message("m")]
+        }))
+        .unwrap();
+        assert!(warnings.is_empty());
+        assert!(Task::from_value_lenient(json!({"kind": "task", "id": "t", "status": {"state": "completed"}})).is_err());
+    }
+
+    #[test]
+    fn test_agent_card_with_broken_skill_and_scheme() {
+        let value = json!({
+            "name": "Agent",
+            "description": "An agent",
+            "version": "1.0.0",
+            "protocolVersion": "0.3.0",
+            "url": "https://agent.example.com/a2a",
+            "capabilities": {},
+            "defaultInputModes": ["text/plain"],
+            "defaultOutputModes": ["text/plain"],
+            "skills": [
+                {"id": "s-1", "name": "Search", "description": "Searches", "tags": []},
+                {"id": "s-2"}
+            ],
+            "securitySchemes": {
+                "key": {"type": "apiKey", "name": "X-Key", "in": "header"},
+                "broken": {"type": "apiKey"}
+            },
+            "iconUrl": false
+        });
+        assert!(serde_json::from_value::<AgentCard>(value.clone()).is_err());
+
+        let (card, warnings) = AgentCard::from_value_lenient(value.clone()).unwrap();
+        assert_eq!(card.skills.len(), 1);
+        assert!(card.security_schemes.unwrap().contains_key("key"));
+        assert_eq!(card.icon_url, None);
+        let paths: Vec<&str> = warnings.iter().map(|w| w.path.as_str()).collect();
+        assert_eq!(paths, ["/skills/1", "/securitySchemes/broken", "/iconUrl", "/skills/0/id", "/skills/0/tags"]);
+        assert!(warnings[3..].iter().all(|w| w.kind == WarningKind::UnknownField));
+
+        // Map keys are escaped in the pointers of dropped entries
+        let mut value = value;
+        value["securitySchemes"] = json!({"team/a~b": {"type": "apiKey"}});
+        let (_, warnings) = AgentCard::from_value_lenient(value).unwrap();
+        let warnings: Vec<_> = warnings.into_iter().filter(|w| w.path.starts_with("/securitySchemes")).collect();
+        assert_eq!(warnings[0].path, "/securitySchemes/team~1a~0b");
+        assert_eq!(warnings[0].kind, WarningKind::DroppedInvalidItem);
+    }
+
+    #[test]
+    fn test_duplicate_message_id_warnings() {
+        let asking = |text: &str| {
+            json!({"kind": "message", "messageId": "msg-1", "role": "user", "parts": [{"kind": "text", "text": text}]})
+        };
+        let value = json!({
+            "kind": "task",
+            "id": "task-1",
+            "contextId": "ctx-1",
+            "status": {"state": "working"},
+            "history": [asking("Book a flight"), asking("Book a flight"), asking("Book a train")]
+        });
+
+        let (mut task, warnings) = Task::from_value_lenient(value).unwrap();
+        assert_eq!(task.history.as_ref().unwrap().len(), 3);
+        let found: Vec<(&str, WarningKind)> = warnings.iter().map(|w| (w.path.as_str(), w.kind)).collect();
+        assert_eq!(
+            found,
+            [
+                ("/history/1/messageId", WarningKind::DuplicateMessageId),
+                ("/history/2/messageId", WarningKind::DuplicateMessageId),
+            ]
+        );
+        assert!(warnings[0].detail.contains("repeats /history/0"));
+        assert!(warnings[1].detail.contains("differs from /history/0"));
+
+        // Only the identical copy goes
+        assert_eq!(task.dedup_history(), 1);
+        assert_eq!(task.history.unwrap()[1].parts[0], crate::Part::from("Book a train"));
+    }
+
+    #[test]
+    fn test_messy_task_warnings() {
+        let value = json!({
+            "kind": null,
+            "id": "task-1",
+            "contextId": "ctx-1",
+            "priority": "high",
+            "status": {"state": "working", "progress": 0.5},
+            "history": [
+                {"kind": "message", "messageId": "msg-1", "role": "system", "parts": [{"kind": "text", "text": "Be brief"}]},
+                {"kind": "message", "messageId": "msg-2", "role": "robot", "parts": "broken"},
+                {
+                    "kind": "message",
+                    "messageId": "msg-3",
+                    "role": "agent",
+                    "parts": [{"kind": "text", "text": "hi"}, {"kind": "video", "url": "https://example.com/v.mp4"}],
+                    "metadata": {"role": "not checked", "parts": [{"kind": "not checked"}]}
+                }
+            ],
+            "artifacts": [{"artifactId": "a-1", "parts": [{"kind": "data", "data": {"kind": "opaque"}, "mimeType": "x"}]}]
+        });
+
+        let (task, warnings) = Task::from_value_lenient(value).unwrap();
+        assert_eq!(task.kind, "task");
+        assert_eq!(task.history.as_ref().unwrap().len(), 2);
+        let found: Vec<(&str, WarningKind)> = warnings.iter().map(|w| (w.path.as_str(), w.kind)).collect();
+        assert_eq!(
+            found,
+            [
+                ("/kind", WarningKind::NullForRequired),
+                ("/history/1", WarningKind::DroppedInvalidItem),
+                ("/history/0/role", WarningKind::UnknownEnumValue),
+                ("/history/2/parts/1/kind", WarningKind::UnknownEnumValue),
+                ("/priority", WarningKind::UnknownField),
+                ("/status/progress", WarningKind::UnknownField),
+                ("/artifacts/0/parts/0/mimeType", WarningKind::UnknownField),
+            ]
+        );
+        assert_eq!(warnings[2].detail, "unknown role `system`");
+        assert_eq!(warnings[3].to_string(), "/history/2/parts/1/kind: unknown part kind `video`");
+    }
+
+    #[test]
+    fn test_request_warnings() {
+        let value = json!({
+            "jsonrpc": null,
+            "id": "1",
+            "method": "sendMessage",
+            "params": {
+                "message": {"kind": "message", "messageId": "m-1", "role": "system", "parts": [{"kind": "text", "text": "hi"}]},
+                "priority": 1
+            }
+        });
+        let (request, warnings) = A2ARequest::from_value_lenient(value).unwrap();
+        assert_eq!(request.method(), RequestMethod::MessageSend);
+        let found: Vec<(&str, WarningKind)> = warnings.iter().map(|w| (w.path.as_str(), w.kind)).collect();
+        assert_eq!(
+            found,
+            [
+                ("/jsonrpc", WarningKind::NullForRequired),
+                ("/method", WarningKind::LegacyAlias),
+                ("/params/message/role", WarningKind::UnknownEnumValue),
+                ("/params/priority", WarningKind::UnknownField),
+            ]
+        );
+        assert_eq!(warnings[1].detail, "`sendMessage` is an old name of `message/send`");
+
+        let (_, warnings) = crate::helpers::parse_request_lenient(
+            r#"{"jsonrpc": "2.0", "id": "1", "method": "tasks/get", "params": {"taskId": "t"}}"#,
+        )
+        .unwrap();
+        assert!(warnings.is_empty());
+        assert!(crate::helpers::parse_request_lenient(r#"{"jsonrpc": "2.0", "id": 1, "method": "nope"}"#).is_err());
+    }
+
+    #[test]
+    fn test_pointers_of_unknown_fields() {
+        let key = |key: &str| PathSegment::Key(key.to_string());
+        let path = [key("params"), key("message"), key("parts"), PathSegment::Index(1), key("extra")];
+        assert_eq!(pointer(&path), "/params/message/parts/1/extra");
+        assert_eq!(pointer(&[key("securitySchemes"), key("a/b~c")]), "/securitySchemes/a~1b~0c");
+
+        // Dots and brackets in keys are part of the key
+        let mut warnings = ParseWarnings::new();
+        warnings.unknown_fields::<Message>(&json!({
+            "kind": "message", "messageId": "m", "role": "user", "parts": [], "x.y[0]": 1, "a/b": 2
+        }));
+        let paths: Vec<&str> = warnings.as_slice().iter().map(|w| w.path.as_str()).collect();
+        assert_eq!(paths, ["/a~1b", "/x.y[0]"]);
+    }
+}
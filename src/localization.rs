@@ -0,0 +1,214 @@
+//! Localized agent cards.
+//!
+//! A card and each of its skills may carry their name and description in
+//! other languages, keyed by BCP-47 language tag. The translations travel
+//! under the `x-nameLocalizations` and `x-descriptionLocalizations`
+//! extension keys, which peers that follow the spec strictly ignore, and are
+//! left out entirely when there are none. [`AgentCard::localized`] picks the
+//! strings for a language, so one card serves every locale.
+//!
+//! ```
+//! use a2a_rs::{AgentCapabilities, AgentCard};
+//!
+//! let mut card = AgentCard::new(
+//!     "Weather".to_string(),
+//!     "Forecasts for any city".to_string(),
+//!     "1.0.0".to_string(),
+//!     "https://example.com/agent".to_string(),
+//!     AgentCapabilities::default(),
+//!     vec!["text/plain".to_string()],
+//!     vec!["text/plain".to_string()],
+//!     vec![],
+//! );
+//! card.name_localizations = Some([("de".to_string(), "Wetter".to_string())].into_iter().collect());
+//!
+//! assert_eq!(card.localized("de-AT").name, "Wetter");
+//! assert_eq!(card.localized("fr").name, "Weather");
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::{AgentCard, Map};
+use alloc::borrow::Cow;
+
+/// Check if there are no localizations, for `skip_serializing_if`.
+pub(crate) fn is_empty(localizations: &Option<Map<String, String>>) -> bool {
+    localizations.as_ref().is_none_or(Map::is_empty)
+}
+
+/// Find the localization for a language.
+///
+/// Language tags compare case-insensitively. Without an entry for the
+/// whole tag, the lookup falls back through ever shorter prefixes, as in
+/// RFC 4647: `de-CH-1996` tries `de-CH` and then `de`.
+///
+/// # Arguments
+///
+/// * `localizations` - The localized strings by language tag.
+/// * `lang` - The language tag, e.g. from an `Accept-Language` header.
+///
+/// # Returns
+///
+/// The localized string, or `None` if no prefix of the tag has one.
+pub fn lookup<'a>(localizations: Option<&'a Map<String, String>>, lang: &str) -> Option<&'a str> {
+    let localizations = localizations?;
+    let mut tag = lang;
+    loop {
+        let found = localizations.get(tag).or_else(|| {
+            localizations.iter().find(|(key, _)| key.eq_ignore_ascii_case(tag)).map(|(_, value)| value)
+        });
+        if let Some(value) = found {
+            return Some(value);
+        }
+        tag = &tag[..tag.rfind('-')?];
+        // A single letter subtag introduces an extension; drop it with its subtags
+        if let Some(end) = tag.rfind('-').filter(|end| tag.len() - end == 2) {
+            tag = &tag[..end];
+        }
+    }
+}
+
+/// Check the language tags of the card's and its skills' localizations.
+pub(crate) fn validate(card: &AgentCard) -> Result<(), String> {
+    let card_localizations = [&card.name_localizations, &card.description_localizations];
+    let skill_localizations =
+        card.skills.iter().flat_map(|skill| [&skill.name_localizations, &skill.description_localizations]);
+    for localizations in card_localizations.into_iter().chain(skill_localizations) {
+        for tag in localizations.iter().flat_map(Map::keys) {
+            crate::validation::validate_language_tag(tag).map_err(|e| format!("Localization is invalid: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+impl AgentCard {
+    /// Get the card as it reads in a language.
+    ///
+    /// The names and descriptions of the card and its skills are replaced
+    /// by their localizations for the language, found by [`lookup`]; those
+    /// without one stay in the default language.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The language tag, e.g. `de-AT`.
+    ///
+    /// # Returns
+    ///
+    /// The card itself if nothing is localized for the language, else a
+    /// localized copy.
+    pub fn localized(&self, lang: &str) -> Cow<'_, AgentCard> {
+        let name = lookup(self.name_localizations.as_ref(), lang);
+        let description = lookup(self.description_localizations.as_ref(), lang);
+        let skills: Vec<_> = self
+            .skills
+            .iter()
+            .map(|skill| {
+                let name = lookup(skill.name_localizations.as_ref(), lang);
+                (name, lookup(skill.description_localizations.as_ref(), lang))
+            })
+            .collect();
+        let skills_localized = skills.iter().any(|(name, description)| name.is_some() || description.is_some());
+        if name.is_none() && description.is_none() && !skills_localized {
+            return Cow::Borrowed(self);
+        }
+
+        let mut card = self.clone();
+        if let Some(name) = name {
+            card.name = name.to_string();
+        }
+        if let Some(description) = description {
+            card.description = description.to_string();
+        }
+        for (skill, (name, description)) in card.skills.iter_mut().zip(skills) {
+            if let Some(name) = name {
+                skill.name = name.to_string();
+            }
+            if let Some(description) = description {
+                skill.description = description.to_string();
+            }
+        }
+        Cow::Owned(card)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AgentCapabilities, AgentSkill};
+
+    fn localizations(entries: &[(&str, &str)]) -> Option<Map<String, String>> {
+        Some(entries.iter().map(|(tag, text)| (tag.to_string(), text.to_string())).collect())
+    }
+
+    #[test]
+    fn test_lookup_falls_back_through_shorter_tags() {
+        let names = localizations(&[("de", "Wetter"), ("de-CH", "Wätter"), ("zh-Hant", "天氣")]);
+        let names = names.as_ref();
+        assert_eq!(lookup(names, "de"), Some("Wetter"));
+        assert_eq!(lookup(names, "de-AT"), Some("Wetter"));
+        assert_eq!(lookup(names, "DE-ch"), Some("Wätter"));
+        assert_eq!(lookup(names, "de-CH-1996"), Some("Wätter"));
+        assert_eq!(lookup(names, "zh-Hant-TW"), Some("天氣"));
+        assert_eq!(lookup(names, "de-x-formal"), Some("Wetter"));
+        assert_eq!(lookup(names, "zh"), None);
+        assert_eq!(lookup(names, "fr"), None);
+        assert_eq!(lookup(None, "de"), None);
+    }
+
+    #[test]
+    fn test_localized_card() {
+        let mut skill = AgentSkill::minimal("forecast".to_string(), "Forecasts the weather".to_string());
+        skill.description_localizations = localizations(&[("de", "Sagt das Wetter voraus")]);
+        let mut card = AgentCard::new(
+            "Weather".to_string(),
+            "Forecasts for any city".to_string(),
+            "1.0.0".to_string(),
+            "https://example.com/agent".to_string(),
+            AgentCapabilities::default(),
+            vec!["text/plain".to_string()],
+            vec!["text/plain".to_string()],
+            vec![skill, AgentSkill::minimal("alerts".to_string(), "Storm alerts".to_string())],
+        );
+        card.name_localizations = localizations(&[("de", "Wetter"), ("de-AT", "Wetterdienst")]);
+        card.description_localizations = localizations(&[("fr", "Prévisions pour toutes les villes")]);
+
+        assert!(matches!(card.localized("en-US"), Cow::Borrowed(_)));
+
+        let german = card.localized("de-AT");
+        assert_eq!(german.name, "Wetterdienst");
+        assert_eq!(german.description, "Forecasts for any city");
+        assert_eq!(german.skills[0].name, "forecast");
+        assert_eq!(german.skills[0].description, "Sagt das Wetter voraus");
+        assert_eq!(german.skills[1].description, "Storm alerts");
+        assert_eq!(card.localized("de-DE").name, "Wetter");
+
+        let french = card.localized("fr-CA");
+        assert_eq!(french.name, "Weather");
+        assert_eq!(french.description, "Prévisions pour toutes les villes");
+    }
+
+    #[test]
+    fn test_language_tags_are_validated() {
+        let mut card = AgentCard::new(
+            "Weather".to_string(),
+            "Forecasts for any city".to_string(),
+            "1.0.0".to_string(),
+            "https://example.com/agent".to_string(),
+            AgentCapabilities::default(),
+            vec!["text/plain".to_string()],
+            vec!["text/plain".to_string()],
+            vec![AgentSkill::minimal("forecast".to_string(), "Forecasts the weather".to_string())],
+        );
+        card.name_localizations = localizations(&[("de-AT", "Wetter"), ("x-klingon", "muD")]);
+        assert!(card.validate().is_ok());
+
+        card.skills[0].description_localizations = localizations(&[("de_AT", "Wetter")]);
+        assert_eq!(
+            card.validate().unwrap_err(),
+            "Localization is invalid: Language tag 'de_AT' must start with a language subtag of 2-8 letters"
+        );
+        for tag in ["", "d", "de-", "de--AT", "de-Österreich", "x", "deutschland1"] {
+            assert!(crate::validation::validate_language_tag(tag).is_err(), "{}", tag);
+        }
+    }
+}
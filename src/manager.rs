@@ -0,0 +1,1410 @@
+//! Server-side task management.
+//!
+//! [`TaskManager`] answers task queries from a [`TaskStore`] and a
+//! [`PushNotificationConfigStore`], and enforces a [`RetentionPolicy`] so
+//! finished tasks do not accumulate forever. Call [`TaskManager::sweep`]
+//! periodically, or with the `async` feature let
+//! [`TaskManager::spawn_sweeper`] do it on a tokio task. Tasks saved through
+//! [`TaskManager::save_task`] have their changes recorded to an optional
+//! [`EventSink`]. With the `async` feature, [`TaskManager::shutdown`] fails
+//! the tasks a graceful shutdown leaves unfinished.
+
+use crate::audit::{EventSink, TaskEvent};
+use crate::clock::{Clock, SystemClock, parse_timestamp};
+use crate::ids::{IdSource, SharedIdSource, TaskId, default_source};
+#[cfg(feature = "async")]
+use crate::push::{PushDelivery, PushNotificationPayload};
+use crate::rejection::RejectionReason;
+#[cfg(feature = "async")]
+use crate::shutdown::{ShutdownController, ShutdownReport};
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+use crate::ids::ContextId;
+#[cfg(feature = "async")]
+use crate::store::is_conflict;
+use crate::store::{PushNotificationConfigStore, TaskStore, conflict_error};
+use crate::{
+    A2AError, A2AErrorResponse, AgentCard, DuplicateMessages, GetTaskParams, InvalidParamsError, Message,
+    MessageSendConfiguration, SendMessageResult, StreamingMessageResult, Task, TaskState, TaskStatus,
+    TaskStatusUpdateEvent,
+};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// Number of tasks read per page while sweeping.
+const SWEEP_PAGE_SIZE: usize = 500;
+
+/// The status message of tasks interrupted by a shutdown.
+#[cfg(feature = "async")]
+const SHUT_DOWN_MESSAGE: &str = "Server shut down before the task finished";
+
+/// How often a shutdown tries to save an interrupted task that handlers
+/// still running keep saving.
+#[cfg(feature = "async")]
+const SHUTDOWN_ATTEMPTS: usize = 3;
+
+/// Number of tasks per `tasks/list` page if the request sets none.
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+pub const DEFAULT_LIST_PAGE_SIZE: usize = 50;
+
+/// Largest `tasks/list` page size accepted.
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+pub const MAX_LIST_PAGE_SIZE: usize = 1000;
+
+/// How long tasks are kept.
+///
+/// Only tasks in a terminal state are ever removed. The default keeps all
+/// tasks forever.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// How long a task is kept after reaching a terminal state.
+    pub retain_terminal_for: Duration,
+    /// The number of tasks above which the oldest terminal tasks are removed,
+    /// even if they have not expired.
+    pub max_tasks: usize,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            retain_terminal_for: Duration::MAX,
+            max_tasks: usize::MAX,
+        }
+    }
+}
+
+/// What a sweep removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SweepReport {
+    /// Terminal tasks removed because they expired.
+    pub expired: Vec<TaskId>,
+    /// Terminal tasks removed to get back under `max_tasks`.
+    pub evicted: Vec<TaskId>,
+    /// The number of push notification configs removed with the tasks.
+    pub removed_configs: usize,
+}
+
+impl SweepReport {
+    /// The total number of tasks removed.
+    pub fn removed_tasks(&self) -> usize {
+        self.expired.len() + self.evicted.len()
+    }
+}
+
+/// Decides the context ID of inbound messages.
+///
+/// A message keeps its own context ID, continues the context of the task it
+/// names, or starts a new context with a generated ID. Everything the server
+/// sends about the message is then stamped with that ID.
+#[derive(Debug)]
+pub struct ContextAssigner {
+    ids: SharedIdSource,
+}
+
+impl Default for ContextAssigner {
+    fn default() -> Self {
+        Self::shared(default_source())
+    }
+}
+
+impl ContextAssigner {
+    /// Create an assigner that generates UUID context IDs with the `uuid`
+    /// feature, and numbers them sequentially without it.
+    ///
+    /// Sequential IDs restart with the process, so servers that keep tasks
+    /// across restarts should enable `uuid` or mint from their own
+    /// [`IdSource`] with [`ContextAssigner::with_id_source`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an assigner that mints new context IDs from an [`IdSource`].
+    pub fn with_id_source(ids: impl IdSource + Send + 'static) -> Self {
+        Self::shared(SharedIdSource::new(ids))
+    }
+
+    /// Create an assigner minting from a source shared with its manager.
+    fn shared(ids: SharedIdSource) -> Self {
+        Self { ids }
+    }
+
+    /// Assign the context ID of an inbound message and set it on the
+    /// message.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The inbound message.
+    /// * `tasks` - The store holding the task the message names, if any.
+    ///
+    /// # Returns
+    ///
+    /// The message's own context ID, else the context ID of its task, else a
+    /// new one. A `TaskNotFound` error if the named task does not exist, or
+    /// an `InvalidParams` error if the message names a task of another
+    /// context.
+    pub async fn assign<S: TaskStore>(&self, message: &mut Message, tasks: &S) -> Result<String, A2AError> {
+        let task_context = match &message.task_id {
+            Some(task_id) => Some(tasks.get_task(&TaskId::from(task_id.as_str())).await?.context_id),
+            None => None,
+        };
+        let context_id = match (message.context_id.as_ref(), task_context) {
+            (Some(own), Some(task_context)) if *own != task_context => {
+                return Err(invalid_params(format!(
+                    "Message {} is in context {}, but its task is in context {}",
+                    message.message_id, own, task_context
+                )));
+            }
+            (Some(own), _) => own.clone(),
+            (None, Some(task_context)) => task_context,
+            (None, None) => self.generate(),
+        };
+        message.context_id = Some(context_id.clone());
+        Ok(context_id)
+    }
+
+    /// Set the context ID on a task, message or event sent about a message.
+    pub fn stamp(result: &mut StreamingMessageResult, context_id: &str) {
+        match result {
+            StreamingMessageResult::Task(task) => task.context_id = context_id.to_string(),
+            StreamingMessageResult::Message(message) => message.context_id = Some(context_id.to_string()),
+            StreamingMessageResult::TaskStatusUpdate(event) => event.context_id = context_id.to_string(),
+            StreamingMessageResult::TaskArtifactUpdate(event) => event.context_id = context_id.to_string(),
+        }
+    }
+
+    fn generate(&self) -> String {
+        self.ids.mint(|ids| ids.new_context_id().into_inner())
+    }
+}
+
+/// Answers task queries and applies the retention policy.
+#[derive(Debug, Clone)]
+pub struct TaskManager<S, C> {
+    tasks: S,
+    configs: C,
+    retention: RetentionPolicy,
+    clock: Arc<dyn Clock>,
+    events: Option<Arc<dyn EventSink>>,
+    contexts: Arc<ContextAssigner>,
+    ids: SharedIdSource,
+}
+
+impl<S: TaskStore, C: PushNotificationConfigStore> TaskManager<S, C> {
+    /// Create a manager that keeps all tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - The task store.
+    /// * `configs` - The push notification config store.
+    pub fn new(tasks: S, configs: C) -> Self {
+        let ids = default_source();
+        Self {
+            tasks,
+            configs,
+            retention: RetentionPolicy::default(),
+            clock: Arc::new(SystemClock),
+            events: None,
+            contexts: Arc::new(ContextAssigner::shared(ids.clone())),
+            ids,
+        }
+    }
+
+    /// Set the retention policy.
+    pub fn with_retention(mut self, retention: RetentionPolicy) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Set the clock that scheduled sweeps read the time from.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Record the changes of saved tasks to an event sink.
+    pub fn with_event_sink(mut self, events: impl EventSink + 'static) -> Self {
+        self.events = Some(Arc::new(events));
+        self
+    }
+
+    /// Set how the context IDs of inbound messages are assigned.
+    ///
+    /// The assigner mints new context IDs from its own source instead of
+    /// the manager's.
+    pub fn with_context_assigner(mut self, contexts: ContextAssigner) -> Self {
+        self.contexts = Arc::new(contexts);
+        self
+    }
+
+    /// Mint every ID the manager creates from an [`IdSource`]: the IDs of
+    /// new tasks, of new contexts and of the status messages it writes,
+    /// e.g. the prompt of [`TaskManager::require_input`].
+    ///
+    /// This replaces the context assigner with one minting from the same
+    /// source; set a different one afterwards with
+    /// [`TaskManager::with_context_assigner`].
+    pub fn with_id_source(mut self, ids: impl IdSource + Send + 'static) -> Self {
+        self.ids = SharedIdSource::new(ids);
+        self.contexts = Arc::new(ContextAssigner::shared(self.ids.clone()));
+        self
+    }
+
+    /// The task store.
+    pub fn tasks(&self) -> &S {
+        &self.tasks
+    }
+
+    /// The push notification config store.
+    pub fn configs(&self) -> &C {
+        &self.configs
+    }
+
+    /// The retention policy.
+    pub fn retention(&self) -> &RetentionPolicy {
+        &self.retention
+    }
+
+    /// Handle `tasks/get`.
+    ///
+    /// # Returns
+    ///
+    /// The task with its history trimmed to `history_length` messages, or a
+    /// `TaskNotFound` error, including for tasks removed by a sweep.
+    pub async fn get_task(&self, params: &GetTaskParams) -> Result<Task, A2AError> {
+        let task = self.tasks.get_task(&TaskId::from(params.task_id.as_str())).await?;
+        Ok(trim_history(task, params.history_length))
+    }
+
+    /// Handle `tasks/list` from the task store.
+    ///
+    /// Tasks are listed in task ID order, filtered by context and state, with
+    /// their histories trimmed to `history_length` messages. Page tokens are
+    /// cursors of the store.
+    ///
+    /// # Returns
+    ///
+    /// Up to `page_size` tasks, [`DEFAULT_LIST_PAGE_SIZE`] if unset, with the
+    /// token of the next page if the store may hold more, an `InvalidParams`
+    /// error if `page_size` is 0 or above [`MAX_LIST_PAGE_SIZE`], or the
+    /// store error.
+    #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+    pub async fn list_tasks(&self, params: &crate::ListTasksParams) -> Result<crate::ListTasksResult, A2AError> {
+        let page_size = params.page_size.map_or(DEFAULT_LIST_PAGE_SIZE, |size| size as usize);
+        if page_size == 0 || page_size > MAX_LIST_PAGE_SIZE {
+            return Err(invalid_params(format!("pageSize must be between 1 and {}", MAX_LIST_PAGE_SIZE)));
+        }
+        let context_id = params.context_id.as_deref().map(ContextId::from);
+
+        // Only ask the store for as many tasks as are still missing, so its
+        // cursor always points right after the last task looked at
+        let mut tasks = Vec::new();
+        let mut cursor = params.page_token.clone();
+        loop {
+            let limit = page_size - tasks.len();
+            let page = match &context_id {
+                Some(context_id) => self.tasks.list_tasks_by_context(context_id, cursor.as_deref(), limit).await?,
+                None => self.tasks.list_tasks(cursor.as_deref(), limit).await?,
+            };
+            tasks.extend(
+                page.items
+                    .into_iter()
+                    .filter(|task| params.status.as_ref().is_none_or(|state| &task.status.state == state))
+                    .map(|task| trim_history(task, params.history_length)),
+            );
+            cursor = page.next_cursor;
+            if cursor.is_none() || tasks.len() == page_size {
+                break;
+            }
+        }
+
+        Ok(crate::ListTasksResult {
+            tasks,
+            next_page_token: cursor,
+            extra: crate::ExtraFields::default(),
+        })
+    }
+
+    /// Store a new or changed task and record the changes.
+    ///
+    /// A task already in the store is updated, so its `updatedAt` must match
+    /// the stored task. A finished task keeps its status: a handler that
+    /// outlived a [shutdown](TaskManager::shutdown) cannot overwrite the
+    /// `failed` status it left. The events between the stored and the new
+    /// version, timed by the manager's clock, go to the event sink, if any.
+    ///
+    /// # Returns
+    ///
+    /// The stored task, a conflict error if the stored task is finished and
+    /// the new one has another status, or the store error.
+    pub async fn save_task(&self, task: Task) -> Result<Task, A2AError> {
+        let previous = match self.tasks.get_task(&task.typed_id()).await {
+            Ok(previous) => Some(previous),
+            Err(A2AError::TaskNotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+        if let Some(previous) = &previous
+            && previous.status.state.is_terminal()
+            && task.status != previous.status
+        {
+            let message = format!("already finished as {}, its status cannot change", previous.status.state);
+            return Err(conflict_error(&task.id, &message));
+        }
+        let stored = match previous {
+            Some(_) => self.tasks.update_task(task).await?,
+            None => self.tasks.create_task(task).await?,
+        };
+
+        if let Some(events) = &self.events {
+            let id = stored.typed_id();
+            for event in TaskEvent::between(previous.as_ref(), &stored, self.clock.now()) {
+                events.record(&id, event);
+            }
+        }
+        Ok(stored)
+    }
+
+    /// Assign the context ID of an inbound message with the manager's
+    /// [`ContextAssigner`].
+    ///
+    /// Handlers create the message's task in this context and
+    /// [stamp](ContextAssigner::stamp) it on every event they send, so the
+    /// context IDs the client sees always match.
+    pub async fn assign_context(&self, message: &mut Message) -> Result<String, A2AError> {
+        self.contexts.assign(message, &self.tasks).await
+    }
+
+    /// Create a task for an inbound message that starts one.
+    ///
+    /// The message's context is assigned with the manager's
+    /// [`ContextAssigner`], then the task is built by
+    /// [`Task::from_message`] with an ID from the manager's [`IdSource`],
+    /// stamped by the manager's clock and saved with
+    /// [`TaskManager::save_task`].
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message starting the task.
+    ///
+    /// # Returns
+    ///
+    /// The created task, an `InvalidParams` error if the message continues
+    /// a task, or the store error.
+    pub async fn create_task(&self, mut message: Message) -> Result<Task, A2AError> {
+        if let Some(task_id) = &message.task_id {
+            return Err(invalid_params(format!(
+                "Message {} continues task {}, it cannot start a new one",
+                message.message_id, task_id
+            )));
+        }
+        self.assign_context(&mut message).await?;
+        let task = self.ids.mint(|ids| Task::from_message_at(message, ids, self.clock.now()));
+        self.save_task(task).await
+    }
+
+    /// Answer an inbound message directly, without creating a task.
+    ///
+    /// For requests that need no tracking, such as a trivial question
+    /// answered at once. The request's context is assigned with the
+    /// manager's [`ContextAssigner`] and the reply is bound to it, and to
+    /// the task the request continues, if any. Nothing is saved: the client
+    /// gets no task to poll or cancel.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The inbound message.
+    /// * `reply` - The agent's answer.
+    ///
+    /// # Returns
+    ///
+    /// The reply as a `message/send` result, or the error of
+    /// [`ContextAssigner::assign`].
+    pub async fn reply(&self, mut request: Message, mut reply: Message) -> Result<SendMessageResult, A2AError> {
+        let context_id = self.assign_context(&mut request).await?;
+        reply.context_id = Some(context_id);
+        reply.task_id = request.task_id;
+        Ok(reply.into())
+    }
+
+    /// Resume a task that is waiting for input with the user's answer.
+    ///
+    /// The agent's question, the status message of the `input-required`
+    /// status, is moved to the history. The message is bound to the task
+    /// and its context and appended to the history after it, unless it is
+    /// already there, and the task goes back to `working`. The change is saved
+    /// with [`TaskManager::save_task`].
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task waiting for input.
+    /// * `message` - The user's answer.
+    ///
+    /// # Returns
+    ///
+    /// The resumed task, a `TaskNotFound` error, or an `InvalidParams` error
+    /// if the task is not in the `input-required` state or the message
+    /// belongs to another task or context.
+    pub async fn provide_input(&self, task_id: &TaskId, mut message: Message) -> Result<Task, A2AError> {
+        let mut task = self.tasks.get_task(task_id).await?;
+        if task.status.state != TaskState::InputRequired {
+            return Err(invalid_params(format!(
+                "Task {} is not waiting for input, it is {}",
+                task.id, task.status.state
+            )));
+        }
+        if message.task_id.as_ref().is_some_and(|id| *id != task.id)
+            || message.context_id.as_ref().is_some_and(|id| *id != task.context_id)
+        {
+            return Err(invalid_params(format!("Message {} does not belong to task {}", message.message_id, task.id)));
+        }
+
+        message.task_id = Some(task.id.clone());
+        message.context_id = Some(task.context_id.clone());
+        // Keep the question in the history, before the answer
+        if let Some(question) = task.status.message.clone() {
+            task.add_to_history(question, DuplicateMessages::SkipIdentical);
+        }
+        task.add_to_history(message, DuplicateMessages::SkipIdentical);
+        let now = self.clock.now();
+        let status = TaskStatusUpdateEvent::working_at(task.id.clone(), task.context_id.clone(), now).status;
+        task.set_status_checked(status).map_err(invalid_params)?;
+        self.save_task(task).await
+    }
+
+    /// Ask the user for input.
+    ///
+    /// The task moves to `input-required`, timed by the manager's clock,
+    /// with the prompt as its status message. The change is saved with
+    /// [`TaskManager::save_task`].
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task that needs input.
+    /// * `prompt` - What the agent asks.
+    ///
+    /// # Returns
+    ///
+    /// The updated task, a `TaskNotFound` error, or an `InvalidParams` error
+    /// if the task cannot wait for input in its state.
+    pub async fn require_input(&self, task_id: &TaskId, prompt: &str) -> Result<Task, A2AError> {
+        let mut task = self.tasks.get_task(task_id).await?;
+        let status = self.agent_status(TaskState::InputRequired, &task, prompt);
+        task.set_status_checked(status).map_err(invalid_params)?;
+        self.save_task(task).await
+    }
+
+    /// Fail a task with an error.
+    ///
+    /// The task moves to `failed`, timed by the manager's clock, with the
+    /// error message as its status message. The error is kept in its
+    /// `error` field, from where clients get it back with
+    /// [`A2AErrorResponse::to_a2a_error`](crate::A2AErrorResponse::to_a2a_error).
+    /// The change is saved with [`TaskManager::save_task`].
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task that failed.
+    /// * `error` - Why it failed.
+    ///
+    /// # Returns
+    ///
+    /// The failed task, a `TaskNotFound` error, or an `InvalidParams` error
+    /// if the task already finished.
+    pub async fn fail(&self, task_id: &TaskId, error: A2AError) -> Result<Task, A2AError> {
+        let mut task = self.tasks.get_task(task_id).await?;
+        if task.status.state.is_terminal() {
+            return Err(invalid_params(format!("Task {} already finished, it is {}", task.id, task.status.state)));
+        }
+        let error = A2AErrorResponse::from(error);
+        let status = self.agent_status(TaskState::Failed, &task, &error.message);
+        task.set_status_checked(status).map_err(invalid_params)?;
+        task.error = Some(error);
+        self.save_task(task).await
+    }
+
+    /// Reject a task with a structured reason.
+    ///
+    /// The task moves to `rejected`, timed by the manager's clock. The
+    /// status message holds the reason's text and, as a data part under
+    /// [`REJECTION_REASON_KEY`](crate::rejection::REJECTION_REASON_KEY), the
+    /// reason itself, which clients read with
+    /// [`Task::rejection_reason`]. The change is saved with
+    /// [`TaskManager::save_task`].
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task to reject.
+    /// * `reason` - Why the agent will not do the work.
+    ///
+    /// # Returns
+    ///
+    /// The rejected task, a `TaskNotFound` error, or an `InvalidParams` error
+    /// unless the task is `submitted` or `auth-required`.
+    pub async fn reject(&self, task_id: &TaskId, reason: RejectionReason) -> Result<Task, A2AError> {
+        let mut task = self.tasks.get_task(task_id).await?;
+        if task.status.state.is_terminal() {
+            return Err(invalid_params(format!("Task {} already finished, it is {}", task.id, task.status.state)));
+        }
+        let mut status = self.agent_status(TaskState::Rejected, &task, &reason.message);
+        if let Some(message) = status.message.as_mut() {
+            message.parts.push(reason.to_part());
+        }
+        task.set_status_checked(status).map_err(invalid_params)?;
+        self.save_task(task).await
+    }
+
+    /// Reject a new task if its client accepts none of the agent's output
+    /// modes.
+    ///
+    /// Call this once the task is created: the client then gets the task,
+    /// rejected with a
+    /// [`RejectionReason::CONTENT_TYPE_NOT_SUPPORTED`] reason, instead of a
+    /// `ContentTypeNotSupported` error for a task it never hears of again.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The new task.
+    /// * `card` - The card of the agent.
+    /// * `configuration` - The configuration of the request that created
+    ///   the task.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the output modes agree, else the rejected task; or the
+    /// error of [`TaskManager::reject`].
+    pub async fn negotiate_output(
+        &self,
+        task_id: &TaskId,
+        card: &AgentCard,
+        configuration: Option<&MessageSendConfiguration>,
+    ) -> Result<Option<Task>, A2AError> {
+        match RejectionReason::for_output_modes(card, configuration) {
+            Some(reason) => self.reject(task_id, reason).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Build a status of the task with an agent message, timed by the
+    /// manager's clock.
+    fn agent_status(&self, state: TaskState, task: &Task, text: &str) -> TaskStatus {
+        let now = self.clock.now();
+        self.ids.mint(|ids| TaskStatus::with_agent_text_from_at(ids, state, &task.id, &task.context_id, text, now))
+    }
+
+    /// Remove expired terminal tasks and their push notification configs.
+    ///
+    /// A terminal task expires once `retain_terminal_for` has passed since
+    /// its status timestamp, or since its `updatedAt` if the status has no
+    /// timestamp. Tasks without either are never expired, but are the first
+    /// evicted when the store holds more than `max_tasks` tasks. Tasks that
+    /// are not terminal are never removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time.
+    ///
+    /// # Returns
+    ///
+    /// What was removed, or the first store error.
+    pub async fn sweep(&self, now: SystemTime) -> Result<SweepReport, A2AError> {
+        let mut report = SweepReport::default();
+        let mut total = 0;
+        let mut kept_terminal = Vec::new();
+
+        let mut cursor = None;
+        loop {
+            let page = self.tasks.list_tasks(cursor.as_deref(), SWEEP_PAGE_SIZE).await?;
+            for task in page.items {
+                total += 1;
+                if !task.status.state.is_terminal() {
+                    continue;
+                }
+
+                let finished_at = task
+                    .status
+                    .timestamp
+                    .as_deref()
+                    .or(task.updated_at.as_deref())
+                    .and_then(parse_timestamp);
+                let expired = finished_at.is_some_and(|finished_at| {
+                    now.duration_since(finished_at).is_ok_and(|age| age >= self.retention.retain_terminal_for)
+                });
+
+                if expired {
+                    report.expired.push(task.typed_id());
+                } else {
+                    kept_terminal.push((finished_at, task.typed_id()));
+                }
+            }
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        let remaining = total - report.expired.len();
+        if remaining > self.retention.max_tasks {
+            // Oldest first; tasks without a timestamp sort before all others
+            kept_terminal.sort();
+            report.evicted = kept_terminal
+                .into_iter()
+                .take(remaining - self.retention.max_tasks)
+                .map(|(_, id)| id)
+                .collect();
+        }
+
+        for id in report.expired.iter().chain(&report.evicted) {
+            match self.tasks.delete_task(id).await {
+                Ok(()) | Err(A2AError::TaskNotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+            report.removed_configs += self.configs.delete_configs_for_task(id).await?;
+        }
+
+        Ok(report)
+    }
+
+    /// Sweep at the current time of the manager's clock.
+    ///
+    /// # Returns
+    ///
+    /// What was removed, or the first store error.
+    pub async fn sweep_now(&self) -> Result<SweepReport, A2AError> {
+        self.sweep(self.clock.now()).await
+    }
+}
+
+/// Keep only the last `length` messages of a task's history.
+fn trim_history(mut task: Task, length: Option<u32>) -> Task {
+    if let (Some(length), Some(history)) = (length, task.history.as_mut()) {
+        let excess = history.len().saturating_sub(length as usize);
+        history.drain(..excess);
+    }
+    task
+}
+
+fn invalid_params(message: String) -> A2AError {
+    A2AError::InvalidParams(Box::new(InvalidParamsError {
+        code: -32602,
+        message,
+        data: None,
+    }))
+}
+
+#[cfg(feature = "async")]
+impl<S, C> TaskManager<S, C>
+where
+    S: TaskStore + 'static,
+    C: PushNotificationConfigStore + 'static,
+{
+    /// Sweep on a tokio task at a fixed interval.
+    ///
+    /// Sweep errors are ignored and retried at the next interval. Abort the
+    /// returned handle to stop sweeping.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The time between sweeps.
+    pub fn spawn_sweeper(self: std::sync::Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let _ = self.sweep_now().await;
+            }
+        })
+    }
+
+    /// Shut down gracefully.
+    ///
+    /// Stops admitting requests and waits for those in flight with
+    /// [`ShutdownController::begin_shutdown`]. The tasks of the requests
+    /// still running when the grace period ends are failed, or canceled if
+    /// they were never started, so no task is left hanging in a state that
+    /// nothing will ever change again. Each change is saved with
+    /// [`TaskManager::save_task`], which then refuses the handlers' own
+    /// saves of the finished tasks. A handler saving the task between the
+    /// read and the save makes the save conflict; the task is read again and
+    /// the change retried.
+    ///
+    /// # Arguments
+    ///
+    /// * `controller` - The controller the server's handlers are admitted by.
+    /// * `grace` - How long to wait for the requests in flight.
+    ///
+    /// # Returns
+    ///
+    /// The interrupted tasks with the push notifications to deliver about
+    /// them, or the first store error.
+    pub async fn shutdown(&self, controller: &ShutdownController, grace: Duration) -> Result<ShutdownReport, A2AError> {
+        let drain = controller.begin_shutdown(grace).await;
+        let mut report = ShutdownReport::default();
+        for task_id in &drain.stragglers {
+            let Some(task) = self.interrupt(task_id).await? else {
+                continue;
+            };
+
+            let mut cursor = None;
+            loop {
+                let page = self.configs.list_configs(task_id, cursor.as_deref(), SWEEP_PAGE_SIZE).await?;
+                for config in page.items {
+                    let payload = PushNotificationPayload::for_task(&task, PushDelivery::FullTask);
+                    report.notifications.push((config, payload));
+                }
+                match page.next_cursor {
+                    Some(next) => cursor = Some(next),
+                    None => break,
+                }
+            }
+            report.interrupted.push(task);
+        }
+        report.drain = drain;
+        Ok(report)
+    }
+
+    /// Fail or cancel a task left unfinished by a shutdown, retrying when a
+    /// handler still running saves it first.
+    ///
+    /// # Returns
+    ///
+    /// The interrupted task, `None` if it is gone or finished, or the store
+    /// error, a conflict after [`SHUTDOWN_ATTEMPTS`] tries.
+    async fn interrupt(&self, task_id: &TaskId) -> Result<Option<Task>, A2AError> {
+        let mut attempts = 0;
+        loop {
+            let mut task = match self.tasks.get_task(task_id).await {
+                Ok(task) if !task.status.state.is_terminal() => task,
+                Ok(_) | Err(A2AError::TaskNotFound(_)) => return Ok(None),
+                Err(e) => return Err(e),
+            };
+            if task.status.state.successors().contains(&TaskState::Failed) {
+                let error = A2AErrorResponse::from(A2AError::Internal(Box::new(crate::InternalError {
+                    code: -32603,
+                    message: SHUT_DOWN_MESSAGE.to_string(),
+                    data: None,
+                })));
+                let status = self.agent_status(TaskState::Failed, &task, SHUT_DOWN_MESSAGE);
+                task.set_status_checked(status).map_err(invalid_params)?;
+                task.error = Some(error);
+            } else {
+                let status = self.agent_status(TaskState::Canceled, &task, SHUT_DOWN_MESSAGE);
+                task.set_status_checked(status).map_err(invalid_params)?;
+            }
+            attempts += 1;
+            match self.save_task(task).await {
+                Ok(task) => return Ok(Some(task)),
+                Err(e) if is_conflict(&e) && attempts < SHUTDOWN_ATTEMPTS => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{MockClock, format_time};
+    use crate::store::{InMemoryPushNotificationConfigStore, InMemoryTaskStore};
+    use crate::{ExtraFields, PushNotificationConfig};
+    use std::time::UNIX_EPOCH;
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    /// Seconds since the epoch as an RFC 3339 timestamp in 1970.
+    fn at(seconds: u64) -> String {
+        format!("1970-01-01T{:02}:{:02}:{:02}Z", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    }
+
+    fn task(id: &str, state: TaskState, finished_at: Option<u64>) -> Task {
+        let mut task = Task::minimal(id.to_string(), "ctx".to_string());
+        task.status.state = state;
+        task.status.timestamp = finished_at.map(at);
+        task
+    }
+
+    fn get(id: &str) -> GetTaskParams {
+        GetTaskParams {
+            task_id: id.to_string(),
+            history_length: None,
+            extra: ExtraFields::default(),
+        }
+    }
+
+    async fn manager(retention: RetentionPolicy) -> TaskManager<InMemoryTaskStore, InMemoryPushNotificationConfigStore> {
+        let manager = TaskManager::new(InMemoryTaskStore::new(), InMemoryPushNotificationConfigStore::new())
+            .with_retention(retention);
+
+        for task in [
+            task("done-old", TaskState::Completed, Some(0)),
+            task("done-boundary", TaskState::Failed, Some(3600)),
+            task("done-recent", TaskState::Canceled, Some(3601)),
+            task("working-old", TaskState::Working, Some(0)),
+            task("input-old", TaskState::InputRequired, Some(0)),
+        ] {
+            manager.tasks().insert(task);
+        }
+
+        let config = PushNotificationConfig {
+            url: "https://example.com/hook".to_string(),
+            authentication: None,
+            id: None,
+            token: None,
+        };
+        manager.configs().set_config(&TaskId::from("done-old"), config.clone()).await.unwrap();
+        manager.configs().set_config(&TaskId::from("working-old"), config).await.unwrap();
+        manager
+    }
+
+    #[tokio::test]
+    async fn test_sweep_respects_retention_boundary() {
+        let clock = MockClock::new(UNIX_EPOCH + HOUR);
+        let manager = manager(RetentionPolicy {
+            retain_terminal_for: HOUR,
+            ..RetentionPolicy::default()
+        })
+        .await
+        .with_clock(clock.clone());
+
+        // One hour after the oldest tasks finished, only they have expired
+        let report = manager.sweep_now().await.unwrap();
+        assert_eq!(report.expired, [TaskId::from("done-old")]);
+        assert_eq!(report.removed_configs, 1);
+
+        // Exactly at the boundary a task expires; a second later it is still kept
+        clock.advance(HOUR);
+        let report = manager.sweep_now().await.unwrap();
+        assert_eq!(report.expired, [TaskId::from("done-boundary")]);
+        assert!(report.evicted.is_empty());
+
+        assert!(matches!(manager.get_task(&get("done-old")).await, Err(A2AError::TaskNotFound(_))));
+        assert!(manager.get_task(&get("done-recent")).await.is_ok());
+
+        // Active tasks are never swept, however old
+        clock.set(UNIX_EPOCH + 1000 * HOUR);
+        let report = manager.sweep_now().await.unwrap();
+        assert_eq!(report.expired, [TaskId::from("done-recent")]);
+        assert_eq!(manager.tasks().len(), 2);
+        assert!(manager.get_task(&get("working-old")).await.is_ok());
+        assert!(manager.get_task(&get("input-old")).await.is_ok());
+        assert_eq!(manager.configs().list_configs(&TaskId::from("working-old"), None, 10).await.unwrap().items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sweep_evicts_oldest_terminal_tasks_over_limit() {
+        let manager = manager(RetentionPolicy {
+            max_tasks: 3,
+            ..RetentionPolicy::default()
+        })
+        .await;
+        manager.tasks().insert(task("done-untimed", TaskState::Rejected, None));
+
+        let report = manager.sweep(UNIX_EPOCH).await.unwrap();
+        assert!(report.expired.is_empty());
+        assert_eq!(report.evicted, [TaskId::from("done-untimed"), TaskId::from("done-old"), TaskId::from("done-boundary")]);
+        assert_eq!(report.removed_tasks(), 3);
+
+        // With only active tasks left over the limit, nothing more can go
+        let manager = manager.with_retention(RetentionPolicy {
+            max_tasks: 0,
+            ..RetentionPolicy::default()
+        });
+        let report = manager.sweep(UNIX_EPOCH).await.unwrap();
+        assert_eq!(report.evicted, [TaskId::from("done-recent")]);
+        assert_eq!(manager.tasks().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_task_trims_history() {
+        let manager = manager(RetentionPolicy::default()).await;
+        let mut task = task("chatty", TaskState::Working, None);
+        task.history = Some((1..=4).map(|n| crate::Message::user(format!("msg-{}", n), ["hi"])).collect());
+        manager.tasks().insert(task);
+
+        let mut params = get("chatty");
+        params.history_length = Some(2);
+        let task = manager.get_task(&params).await.unwrap();
+        let ids: Vec<_> = task.history.unwrap().into_iter().map(|m| m.message_id).collect();
+        assert_eq!(ids, ["msg-3", "msg-4"]);
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_event_log() {
+        use crate::audit::{DeliveryOutcome, EventSink, TaskEventLog};
+        use crate::{Artifact, Part};
+
+        let log = Arc::new(TaskEventLog::default());
+        let clock = MockClock::new(UNIX_EPOCH);
+        let manager = TaskManager::new(InMemoryTaskStore::new(), InMemoryPushNotificationConfigStore::new())
+            .with_clock(clock.clone())
+            .with_event_sink(log.clone());
+        let id = TaskId::from("task-1");
+
+        let mut task = Task::minimal("task-1".to_string(), "ctx".to_string());
+        task.status.state = TaskState::Submitted;
+        task.history = Some(vec![Message::user("msg-1".to_string(), ["Summarize this"])]);
+        let mut task = manager.save_task(task).await.unwrap();
+
+        clock.advance(Duration::from_secs(1));
+        task.status.state = TaskState::Working;
+        let mut task = manager.save_task(task).await.unwrap();
+
+        clock.advance(Duration::from_secs(1));
+        task.artifacts = Some(vec![Artifact {
+            artifact_id: "summary".to_string(),
+            name: None,
+            description: None,
+            parts: vec![Part::from("The summary")],
+            metadata: None,
+            extensions: None,
+            extra: ExtraFields::default(),
+        }]);
+        task.history.as_mut().unwrap().push(Message::agent("msg-2".to_string(), ["Done"]));
+        task.status.state = TaskState::Completed;
+        manager.save_task(task).await.unwrap();
+        log.record(&id, TaskEvent::PushDelivered {
+            config_id: Some("hook-1".to_string()),
+            at: format_time(clock.now()),
+            outcome: DeliveryOutcome::Delivered,
+        });
+
+        let exported: serde_json::Value = serde_json::from_str(&log.export_json(&id)).unwrap();
+        assert_eq!(
+            exported,
+            serde_json::json!([
+                {"event": "status-changed", "from": null, "to": "submitted", "at": "1970-01-01T00:00:00.000000Z"},
+                {"event": "message-added", "role": "user", "messageId": "msg-1", "at": "1970-01-01T00:00:00.000000Z"},
+                {"event": "status-changed", "from": "submitted", "to": "working", "at": "1970-01-01T00:00:01.000000Z"},
+                {"event": "status-changed", "from": "working", "to": "completed", "at": "1970-01-01T00:00:02.000000Z"},
+                {"event": "message-added", "role": "agent", "messageId": "msg-2", "at": "1970-01-01T00:00:02.000000Z"},
+                {"event": "artifact-added", "artifactId": "summary", "at": "1970-01-01T00:00:02.000000Z"},
+                {"event": "push-delivered", "configId": "hook-1", "at": "1970-01-01T00:00:02.000000Z", "outcome": {"result": "delivered"}}
+            ])
+        );
+        let events: Vec<TaskEvent> = serde_json::from_value(exported).unwrap();
+        assert_eq!(events, log.events(&id));
+
+        let states: Vec<TaskState> = log.status_history(&id).into_iter().map(|status| status.state).collect();
+        assert_eq!(states, [TaskState::Submitted, TaskState::Working, TaskState::Completed]);
+    }
+
+    #[tokio::test]
+    async fn test_two_input_rounds() {
+        use crate::{MessageRole, SendMessageParams};
+
+        let manager = TaskManager::new(InMemoryTaskStore::new(), InMemoryPushNotificationConfigStore::new());
+        let id = TaskId::from("trip");
+
+        // The agent asks a question and waits
+        async fn ask(manager: &TaskManager<InMemoryTaskStore, InMemoryPushNotificationConfigStore>, question: &str) -> Task {
+            let mut task = manager.tasks().get_task(&TaskId::from("trip")).await.unwrap();
+            task.status.state = TaskState::InputRequired;
+            task.status.message = Some(Message::agent(format!("ask-{}", question.len()), [question]));
+            manager.save_task(task).await.unwrap()
+        }
+
+        let mut task = Task::minimal("trip".to_string(), "ctx-trip".to_string());
+        task.status.state = TaskState::Working;
+        task.history = Some(vec![Message::user("msg-1".to_string(), ["Book a flight"])]);
+        manager.save_task(task).await.unwrap();
+
+        let mut task = ask(&manager, "Where to?").await;
+        for (round, answer) in [(2, "Lisbon"), (3, "Next Friday")] {
+            let question = task.input_request().unwrap();
+            assert_eq!(question.role, MessageRole::Agent);
+
+            let reply = SendMessageParams::reply_to_task(&task, format!("msg-{}", round), [answer]);
+            assert_eq!(reply.message.task_id.as_deref(), Some("trip"));
+            assert_eq!(reply.message.context_id.as_deref(), Some("ctx-trip"));
+            task = manager.provide_input(&id, reply.message).await.unwrap();
+            assert_eq!(task.status.state, TaskState::Working);
+            assert_eq!(task.input_request(), None);
+
+            if round == 2 {
+                task = ask(&manager, "When?").await;
+            }
+        }
+
+        let history: Vec<&str> = task.history.iter().flatten().map(|m| m.message_id.as_str()).collect();
+        assert_eq!(history, ["msg-1", "ask-9", "msg-2", "ask-5", "msg-3"]);
+
+        // Only a task waiting for input accepts an answer, and only for itself
+        let error = manager.provide_input(&id, Message::user("msg-4".to_string(), ["Hello?"])).await.unwrap_err();
+        assert_eq!(A2AErrorResponse::from(error).message, "Task trip is not waiting for input, it is working");
+        ask(&manager, "Window or aisle?").await;
+        let mut stray = Message::user("msg-5".to_string(), ["Aisle"]);
+        stray.context_id = Some("ctx-other".to_string());
+        assert!(matches!(manager.provide_input(&id, stray).await, Err(A2AError::InvalidParams(_))));
+        assert!(matches!(
+            manager.provide_input(&TaskId::from("missing"), Message::user("m".to_string(), ["x"])).await,
+            Err(A2AError::TaskNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_task_from_message() {
+        let manager = TaskManager::new(InMemoryTaskStore::new(), InMemoryPushNotificationConfigStore::new())
+            .with_clock(MockClock::new(UNIX_EPOCH + HOUR))
+            .with_id_source(crate::ids::SequentialSource::new());
+
+        let task = manager.create_task(Message::user("msg-1".to_string(), ["Hello"])).await.unwrap();
+        assert_eq!((task.id.as_str(), task.context_id.as_str()), ("task-1", "ctx-1"));
+        assert_eq!(task.status.state, TaskState::Submitted);
+        assert_eq!(task.status.timestamp, Some(format_time(UNIX_EPOCH + HOUR)));
+        let message = &task.history.as_ref().unwrap()[0];
+        assert_eq!(message.task_id.as_deref(), Some("task-1"));
+        assert_eq!(message.context_id.as_deref(), Some("ctx-1"));
+        assert_eq!(manager.tasks().get_task(&TaskId::from("task-1")).await.unwrap(), task);
+
+        // A follow-up stays in the context, a continuation is refused
+        let mut follow_up = Message::user("msg-2".to_string(), ["And another"]);
+        follow_up.context_id = Some("ctx-1".to_string());
+        let second = manager.create_task(follow_up).await.unwrap();
+        assert_eq!((second.id.as_str(), second.context_id.as_str()), ("task-2", "ctx-1"));
+        let mut continuation = Message::user("msg-3".to_string(), ["More"]);
+        continuation.task_id = Some("task-1".to_string());
+        assert!(matches!(manager.create_task(continuation).await, Err(A2AError::InvalidParams(_))));
+
+        // Contexts, tasks and status messages are all minted by the one source
+        let third = manager.create_task(Message::user("msg-4".to_string(), ["New topic"])).await.unwrap();
+        assert_eq!((third.id.as_str(), third.context_id.as_str()), ("task-3", "ctx-2"));
+        let reason = RejectionReason::new(RejectionReason::POLICY_VIOLATION, "Off topic");
+        let rejected = manager.reject(&third.typed_id(), reason).await.unwrap();
+        assert_eq!(rejected.status.message.unwrap().message_id, "msg-1");
+    }
+
+    #[tokio::test]
+    async fn test_message_reply_end_to_end() {
+        use crate::tracker::{RequestTracker, ResponseResult};
+        use crate::{A2ARequest, IncomingRequest, SendMessageRequest};
+
+        let manager = TaskManager::new(InMemoryTaskStore::new(), InMemoryPushNotificationConfigStore::new())
+            .with_id_source(crate::ids::SequentialSource::new());
+        let mut tracker = RequestTracker::new();
+        let id = tracker.start(crate::RequestMethod::MessageSend, None);
+        let mut request = SendMessageRequest::new(
+            "1".to_string(),
+            "msg-1".to_string(),
+            "What is 2 + 2?".to_string(),
+            crate::MessageRole::User,
+            None,
+            None,
+        );
+        request.id = id;
+
+        // The handler answers with a message instead of a task
+        let incoming = IncomingRequest::try_from(serde_json::to_value(&request).unwrap()).unwrap();
+        let A2ARequest::SendMessage(send) = incoming.request() else { unreachable!() };
+        let reply = Message::agent("msg-2".to_string(), ["4"]);
+        let result = manager.reply(send.params.message.clone(), reply).await.unwrap();
+        let response = incoming.dispatch(|_| Ok(result), &mut |_: &A2ARequest, _| unreachable!()).unwrap();
+
+        let ResponseResult::SendMessage(result) = tracker.handle_response(&response).unwrap().result.unwrap() else {
+            panic!("Expected a message/send result");
+        };
+        let outcome = result;
+        assert_eq!(outcome.text().as_deref(), Some("4"));
+        assert_eq!(outcome.context_id(), Some("ctx-1"));
+        assert!(outcome.is_final());
+        // No task was created, and the client has none to look up
+        assert_eq!(outcome.task_id(), None);
+        assert!(manager.tasks().list_tasks(None, 10).await.unwrap().items.is_empty());
+        assert_eq!(outcome.into_message().unwrap().message_id, "msg-2");
+
+        // A reply within a task refers to it
+        manager.tasks().insert(Task::minimal("task-1".to_string(), "ctx-9".to_string()));
+        let mut follow_up = Message::user("msg-3".to_string(), ["And 3 + 3?"]);
+        follow_up.task_id = Some("task-1".to_string());
+        let reply = Message::agent("msg-4".to_string(), ["6"]);
+        let outcome = manager.reply(follow_up, reply).await.unwrap();
+        assert_eq!((outcome.task_id(), outcome.context_id()), (Some("task-1"), Some("ctx-9")));
+        assert_eq!(outcome.task(), None);
+    }
+
+    #[tokio::test]
+    async fn test_context_assignment() {
+        use crate::SendMessageParams;
+
+        let params = |message: Message| SendMessageParams {
+            message,
+            configuration: None,
+            metadata: None,
+            extra: ExtraFields::default(),
+        };
+        let manager = TaskManager::new(InMemoryTaskStore::new(), InMemoryPushNotificationConfigStore::new())
+            .with_id_source(crate::ids::SequentialSource::new());
+
+        // A new conversation gets a generated context
+        let mut first = params(Message::user("msg-1".to_string(), ["Hello"]));
+        first.ensure_context(None);
+        assert_eq!(first.message.context_id, None);
+        let context_id = manager.assign_context(&mut first.message).await.unwrap();
+        assert_eq!(context_id, "ctx-1");
+        assert_eq!(first.message.context_id.as_deref(), Some("ctx-1"));
+
+        // The created task and its events are stamped with it
+        let mut created = StreamingMessageResult::Task(Task::minimal("task-1".to_string(), String::new()));
+        ContextAssigner::stamp(&mut created, &context_id);
+        let StreamingMessageResult::Task(task) = created else { unreachable!() };
+        manager.save_task(task.clone()).await.unwrap();
+        let mut event = StreamingMessageResult::TaskStatusUpdate(TaskStatusUpdateEvent::working(task.id, String::new()));
+        ContextAssigner::stamp(&mut event, &context_id);
+        assert!(matches!(&event, StreamingMessageResult::TaskStatusUpdate(e) if e.context_id == "ctx-1"));
+
+        // The client echoes the context, which is kept
+        let mut second = params(Message::user("msg-2".to_string(), ["Again"]));
+        second.ensure_context(Some(&context_id));
+        second.ensure_context(Some("ctx-ignored"));
+        assert_eq!(manager.assign_context(&mut second.message).await.unwrap(), "ctx-1");
+
+        // A message naming only its task continues the task's context
+        let mut message = Message::user("msg-3".to_string(), ["More"]);
+        message.task_id = Some("task-1".to_string());
+        assert_eq!(manager.assign_context(&mut message).await.unwrap(), "ctx-1");
+        assert_eq!(message.context_id.as_deref(), Some("ctx-1"));
+
+        // Contradictions and unknown tasks are rejected
+        message.context_id = Some("ctx-other".to_string());
+        assert!(matches!(manager.assign_context(&mut message).await, Err(A2AError::InvalidParams(_))));
+        assert_eq!(message.context_id.as_deref(), Some("ctx-other"));
+        message.task_id = Some("missing".to_string());
+        assert!(matches!(manager.assign_context(&mut message).await, Err(A2AError::TaskNotFound(_))));
+
+        let mut message = Message::user("msg-4".to_string(), ["New topic"]);
+        assert_eq!(manager.assign_context(&mut message).await.unwrap(), "ctx-2");
+        #[cfg(feature = "uuid")]
+        {
+            let assigner = ContextAssigner::with_id_source(crate::ids::UuidV4Source);
+            let mut message = Message::user("msg-5".to_string(), ["Hi"]);
+            assert_eq!(assigner.assign(&mut message, manager.tasks()).await.unwrap().len(), 36);
+            // UUIDs are the default with the feature
+            let mut message = Message::user("msg-5".to_string(), ["Hi"]);
+            assert_eq!(ContextAssigner::new().assign(&mut message, manager.tasks()).await.unwrap().len(), 36);
+        }
+
+        // An assigner with an ID source continues the source's numbering
+        let mut ids = crate::ids::SequentialSource::new();
+        ids.new_context_id();
+        let assigner = ContextAssigner::with_id_source(ids);
+        let mut message = Message::user("msg-6".to_string(), ["Hi"]);
+        assert_eq!(assigner.assign(&mut message, manager.tasks()).await.unwrap(), "ctx-2");
+    }
+
+    #[tokio::test]
+    async fn test_fail_keeps_the_error() {
+        let manager = TaskManager::new(InMemoryTaskStore::new(), InMemoryPushNotificationConfigStore::new())
+            .with_clock(MockClock::new(UNIX_EPOCH + HOUR));
+        let id = TaskId::from("task-1");
+        manager.save_task(task("task-1", TaskState::Working, None)).await.unwrap();
+
+        let error = A2AError::ContentTypeNotSupported(Box::new(crate::ContentTypeNotSupportedError {
+            code: -32005,
+            message: "No video".to_string(),
+            data: Some(serde_json::json!({"mimeType": "video/mp4"})),
+        }));
+        let task = manager.fail(&id, error.clone()).await.unwrap();
+        assert_eq!(task.status.state, TaskState::Failed);
+        assert_eq!(task.status.timestamp.as_deref(), Some("1970-01-01T01:00:00.000000Z"));
+        assert_eq!(task.status_history.unwrap()[0].state, TaskState::Working);
+        let message = task.status.message.unwrap();
+        assert_eq!(message.validate(), Ok(()));
+        assert!(matches!(&message.parts[0], crate::Part::Text(part) if part.text == "No video"));
+
+        let stored = manager.get_task(&get("task-1")).await.unwrap();
+        assert_eq!(stored.error.unwrap().to_a2a_error(), error);
+
+        // A finished task cannot fail again
+        let again = manager.fail(&id, error.clone()).await.unwrap_err();
+        assert!(matches!(&again, A2AError::InvalidParams(e) if e.message == "Task task-1 already finished, it is failed"));
+        assert!(matches!(manager.fail(&TaskId::from("missing"), error).await, Err(A2AError::TaskNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_reject_records_the_reason() {
+        let manager = TaskManager::new(InMemoryTaskStore::new(), InMemoryPushNotificationConfigStore::new())
+            .with_clock(MockClock::new(UNIX_EPOCH + HOUR));
+        let reason = RejectionReason::new(RejectionReason::POLICY_VIOLATION, "Medical advice is out of scope");
+        for (id, state) in [("task-1", TaskState::Submitted), ("task-2", TaskState::AuthRequired)] {
+            manager.save_task(task(id, state.clone(), None)).await.unwrap();
+            let task = manager.reject(&TaskId::from(id), reason.clone()).await.unwrap();
+            assert_eq!(task.status.state, TaskState::Rejected);
+            assert_eq!(task.status.timestamp.as_deref(), Some("1970-01-01T01:00:00.000000Z"));
+            assert_eq!(task.status_history.as_ref().unwrap()[0].state, state);
+            let message = task.status.message.as_ref().unwrap();
+            assert_eq!(message.validate(), Ok(()));
+            assert!(matches!(&message.parts[0], crate::Part::Text(part) if part.text == reason.message));
+
+            let stored = manager.get_task(&get(id)).await.unwrap();
+            assert_eq!(stored.rejection_reason(), Some(reason.clone()));
+        }
+
+        // Work under way or finished cannot be rejected
+        manager.save_task(task("task-3", TaskState::Working, None)).await.unwrap();
+        let error = manager.reject(&TaskId::from("task-3"), reason.clone()).await.unwrap_err();
+        assert!(matches!(error, A2AError::InvalidParams(_)));
+        let error = manager.reject(&TaskId::from("task-1"), reason.clone()).await.unwrap_err();
+        assert!(matches!(&error, A2AError::InvalidParams(e) if e.message == "Task task-1 already finished, it is rejected"));
+        assert!(matches!(manager.reject(&TaskId::from("missing"), reason).await, Err(A2AError::TaskNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_output_rejects_unaccepted_modes() {
+        let manager = TaskManager::new(InMemoryTaskStore::new(), InMemoryPushNotificationConfigStore::new());
+        let card = crate::AgentCard::new(
+            "Agent".to_string(),
+            "An agent".to_string(),
+            "1.0.0".to_string(),
+            "https://example.com/agent".to_string(),
+            crate::AgentCapabilities::default(),
+            vec!["text/plain".to_string()],
+            vec!["text/plain".to_string()],
+            vec![],
+        );
+        let accepting = |mode: &str| MessageSendConfiguration {
+            accepted_output_modes: Some(vec![mode.to_string()]),
+            ..MessageSendConfiguration::default()
+        };
+        let id = TaskId::from("task-1");
+        manager.save_task(task("task-1", TaskState::Submitted, None)).await.unwrap();
+
+        assert_eq!(manager.negotiate_output(&id, &card, None).await.unwrap(), None);
+        assert_eq!(manager.negotiate_output(&id, &card, Some(&accepting("text/plain"))).await.unwrap(), None);
+        let rejected = manager.negotiate_output(&id, &card, Some(&accepting("video/mp4"))).await.unwrap().unwrap();
+        assert_eq!(rejected.status.state, TaskState::Rejected);
+        assert_eq!(rejected.rejection_reason().unwrap().code, RejectionReason::CONTENT_TYPE_NOT_SUPPORTED);
+    }
+
+    #[tokio::test]
+    async fn test_require_input_sets_the_prompt() {
+        let manager = TaskManager::new(InMemoryTaskStore::new(), InMemoryPushNotificationConfigStore::new())
+            .with_clock(MockClock::new(UNIX_EPOCH + HOUR))
+            .with_id_source(crate::ids::SequentialSource::new());
+        let id = TaskId::from("task-1");
+        manager.save_task(task("task-1", TaskState::Working, None)).await.unwrap();
+
+        let task = manager.require_input(&id, "Which date?").await.unwrap();
+        assert_eq!(task.status.state, TaskState::InputRequired);
+        assert_eq!(task.status.timestamp.as_deref(), Some("1970-01-01T01:00:00.000000Z"));
+        let message = task.status.message.unwrap();
+        assert_eq!(message.validate(), Ok(()));
+        assert_eq!(message.role, crate::MessageRole::Agent);
+        assert_eq!(message.message_id, "msg-1");
+        assert_eq!((message.task_id.as_deref(), message.context_id.as_deref()), (Some("task-1"), Some(&*task.context_id)));
+
+        let task = manager.fail(&id, A2AError::UnsupportedOperation(Box::new(crate::UnsupportedOperationError {
+            code: -32004,
+            message: "Gave up".to_string(),
+            data: None,
+        })))
+        .await
+        .unwrap();
+        assert_eq!(task.status.message.unwrap().message_id, "msg-2");
+        assert!(matches!(manager.require_input(&id, "Again?").await, Err(A2AError::InvalidParams(_))));
+    }
+
+    #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+    #[tokio::test]
+    async fn test_list_tasks_filters_and_pages() {
+        let manager = manager(RetentionPolicy::default()).await;
+        for (id, context, state) in [
+            ("a-1", "ctx-a", TaskState::Working),
+            ("a-2", "ctx-a", TaskState::Completed),
+            ("a-3", "ctx-a", TaskState::Working),
+            ("a-4", "ctx-a", TaskState::Working),
+            ("b-1", "ctx-b", TaskState::Working),
+        ] {
+            let mut task = Task::minimal(id.to_string(), context.to_string());
+            task.status.state = state;
+            task.history = Some(vec![Message::user(format!("{}-m1", id), ["hi"]), Message::user(format!("{}-m2", id), ["hi"])]);
+            manager.tasks().insert(task);
+        }
+
+        let ids = |result: &crate::ListTasksResult| result.tasks.iter().map(|t| t.id.clone()).collect::<Vec<_>>();
+
+        // Working tasks of one context, two per page, across a task of another state
+        let mut params = crate::ListTasksParams {
+            context_id: Some("ctx-a".to_string()),
+            status: Some(TaskState::Working),
+            page_size: Some(2),
+            history_length: Some(1),
+            ..Default::default()
+        };
+        let first = manager.list_tasks(&params).await.unwrap();
+        assert_eq!(ids(&first), ["a-1", "a-3"]);
+        assert_eq!(first.tasks[0].history.as_ref().unwrap().len(), 1);
+        params.page_token = first.next_page_token.clone();
+        let second = manager.list_tasks(&params).await.unwrap();
+        assert_eq!(ids(&second), ["a-4"]);
+        assert_eq!(second.next_page_token, None);
+
+        // Without filters every task is listed, the sweep fixtures included
+        let all = manager.list_tasks(&Default::default()).await.unwrap();
+        assert_eq!(all.tasks.len(), 10);
+        assert_eq!(all.tasks[0].history.as_ref().unwrap().len(), 2);
+        let by_state: crate::ListTasksParams = serde_json::from_value(serde_json::json!({"state": "completed"})).unwrap();
+        assert_eq!(ids(&manager.list_tasks(&by_state).await.unwrap()), ["a-2", "done-old"]);
+
+        let too_small = crate::ListTasksParams { page_size: Some(0), ..Default::default() };
+        let error = manager.list_tasks(&too_small).await.unwrap_err();
+        assert!(matches!(error, A2AError::InvalidParams(_)));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_shutdown_interrupts_handlers_that_outlive_the_grace_period() {
+        let manager = manager(RetentionPolicy::default()).await;
+        manager.tasks().insert(task("fresh", TaskState::Submitted, None));
+        let clock = MockClock::new(UNIX_EPOCH);
+        let controller = ShutdownController::new().with_clock(clock.clone());
+
+        // Handlers that ignore the shutdown, and one that wraps up when told to
+        let mut stuck = Vec::new();
+        for id in ["working-old", "fresh", "done-old"] {
+            let mut guard = controller.admit().unwrap();
+            guard.track_task(TaskId::from(id));
+            stuck.push(guard);
+        }
+        let mut guard = controller.admit().unwrap();
+        guard.track_task(TaskId::from("input-old"));
+        let cooperative = tokio::spawn(async move { guard.cancelled().await });
+        let before_shutdown = manager.get_task(&get("working-old")).await.unwrap();
+
+        let grace = Duration::from_secs(30);
+        let expire = async {
+            tokio::task::yield_now().await;
+            clock.advance(grace);
+        };
+        let (report, ()) = tokio::join!(manager.shutdown(&controller, grace), expire);
+        let report = report.unwrap();
+        cooperative.await.unwrap();
+        assert!(!report.drain.drained);
+        assert_eq!(report.drain.stragglers, [TaskId::from("working-old"), TaskId::from("fresh"), TaskId::from("done-old")]);
+        assert!(controller.admit().unwrap_err().is_retryable());
+
+        // Started work fails, work never started is canceled, finished tasks are left alone
+        let states: Vec<_> = report.interrupted.iter().map(|t| (t.id.as_str(), t.status.state.clone())).collect();
+        assert_eq!(states, [("working-old", TaskState::Failed), ("fresh", TaskState::Canceled)]);
+        let failed = manager.get_task(&get("working-old")).await.unwrap();
+        assert_eq!(failed.error.as_ref().unwrap().message, SHUT_DOWN_MESSAGE);
+        assert_eq!(manager.get_task(&get("fresh")).await.unwrap().status.state, TaskState::Canceled);
+        assert_eq!(manager.get_task(&get("input-old")).await.unwrap().status.state, TaskState::InputRequired);
+
+        // The stuck handlers finishing late cannot overwrite the failure,
+        // whether they read the task before the shutdown or after it
+        let mut stale = before_shutdown;
+        stale.status.state = TaskState::Completed;
+        assert!(is_conflict(&manager.save_task(stale).await.unwrap_err()));
+        let mut late = failed;
+        late.status = TaskStatus::new(TaskState::Completed);
+        assert!(is_conflict(&manager.save_task(late).await.unwrap_err()));
+        assert_eq!(manager.get_task(&get("working-old")).await.unwrap().status.state, TaskState::Failed);
+        drop(stuck);
+
+        // Only working-old has a push notification config
+        assert_eq!(report.notifications.len(), 1);
+        assert_eq!(report.notifications[0].0.url, "https://example.com/hook");
+        assert_eq!(report.notifications[0].1.task_id(), "working-old");
+        let events = report.final_events();
+        assert!(events.iter().all(|event| event.final_event && event.status.state.is_terminal()));
+        assert_eq!(events.len(), 2);
+    }
+}
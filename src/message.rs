@@ -0,0 +1,537 @@
+//! Messages and the parts they are made of.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::ExtraFields;
+use serde::{Deserialize, Serialize};
+
+/// Represents a single message exchanged between user and agent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /// Event type
+    pub kind: String, // Always "message"
+    /// Identifier created by the message creator
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    /// Message content
+    pub parts: Vec<Part>,
+    /// Message sender's role
+    pub role: MessageRole,
+    /// The context the message is associated with
+    #[serde(rename = "contextId", skip_serializing_if = "Option::is_none")]
+    pub context_id: Option<String>,
+    /// The URIs of extensions that are present or contributed to this Message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<Vec<String>>,
+    /// Extension metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::object))]
+    pub metadata: Option<serde_json::Value>,
+    /// List of tasks referenced as context by this message
+    #[serde(rename = "referenceTaskIds", skip_serializing_if = "Option::is_none")]
+    pub reference_task_ids: Option<Vec<String>>,
+    /// Identifier of task the message is related to
+    #[serde(rename = "taskId", skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+impl Message {
+    /// Create a message with only the required fields set.
+    ///
+    /// # Arguments
+    ///
+    /// * `message_id` - The message ID.
+    /// * `role` - The sender's role.
+    /// * `parts` - The message content.
+    ///
+    /// # Returns
+    ///
+    /// A new `Message` with no context, task, extensions or metadata.
+    pub fn minimal(message_id: String, role: MessageRole, parts: Vec<Part>) -> Self {
+        Self {
+            kind: "message".to_string(),
+            message_id,
+            parts,
+            role,
+            context_id: None,
+            extensions: None,
+            metadata: None,
+            reference_task_ids: None,
+            task_id: None,
+            extra: ExtraFields::default(),
+        }
+    }
+
+    /// Validate the message: its kind, its ID, that it has parts, and the
+    /// extension URIs it lists.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.kind != "message" {
+            return Err("Message kind must be 'message'".to_string());
+        }
+        crate::validation::validate_message_id(&self.message_id)?;
+        if self.parts.is_empty() {
+            return Err("Message must contain at least one part".to_string());
+        }
+        self.validate_extensions()
+    }
+
+    /// Validate the syntax of the extension URIs the message lists.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if every URI is valid, otherwise the first error.
+    pub fn validate_extensions(&self) -> Result<(), String> {
+        self.extensions.iter().flatten().try_for_each(|uri| crate::validation::validate_extension_uri(uri))
+    }
+
+    /// Create a user message from anything convertible to parts.
+    ///
+    /// # Arguments
+    ///
+    /// * `message_id` - The message ID.
+    /// * `parts` - The message content, e.g. a list of strings.
+    ///
+    /// # Returns
+    ///
+    /// A new `Message` with the user role.
+    pub fn user<P: Into<Part>>(message_id: String, parts: impl IntoIterator<Item = P>) -> Self {
+        Self::minimal(message_id, MessageRole::User, parts.into_iter().map(Into::into).collect())
+    }
+
+    /// Create an agent message from anything convertible to parts.
+    ///
+    /// # Arguments
+    ///
+    /// * `message_id` - The message ID.
+    /// * `parts` - The message content, e.g. a list of strings.
+    ///
+    /// # Returns
+    ///
+    /// A new `Message` with the agent role.
+    pub fn agent<P: Into<Part>>(message_id: String, parts: impl IntoIterator<Item = P>) -> Self {
+        Self::minimal(message_id, MessageRole::Agent, parts.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Message sender's role
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "lowercase")]
+pub enum MessageRole {
+    Agent,
+    User,
+    /// A role this version does not know, such as `system` from a newer or
+    /// non-conforming peer, kept as sent so the message serializes back
+    /// unchanged. It is neither the user nor the agent.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::other_role))] String),
+}
+
+impl MessageRole {
+    /// Get the role as it appears on the wire.
+    ///
+    /// # Returns
+    ///
+    /// `agent`, `user`, or the unknown role as sent.
+    pub fn as_str(&self) -> &str {
+        match self {
+            MessageRole::Agent => "agent",
+            MessageRole::User => "user",
+            MessageRole::Other(role) => role,
+        }
+    }
+}
+
+impl core::fmt::Display for MessageRole {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Represents a part of a message, which can be text, a file, or structured data.
+///
+/// Parts of other kinds, e.g. from a vendor extension, deserialize to
+/// [`Part::Unknown`] instead of failing the whole message.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(tag = "kind")]
+pub enum Part {
+    #[serde(rename = "text")]
+    Text(TextPart),
+    /// Boxed so the common text and data parts do not pay for the larger
+    /// file payload.
+    #[serde(rename = "file")]
+    File(Box<FilePart>),
+    #[serde(rename = "data")]
+    Data(DataPart),
+    /// A part of a kind this version does not know, kept as sent so it
+    /// serializes back unchanged. Consumers that need to understand every
+    /// part can check [`Part::is_known`]. Not part of the schema, which
+    /// describes the protocol.
+    #[serde(untagged)]
+    #[cfg_attr(feature = "schema", schemars(skip))]
+    Unknown(Box<UnknownPart>),
+}
+
+impl<'de> Deserialize<'de> for Part {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(PartVisitor)
+    }
+}
+
+/// Dispatches a part on its `kind`. Only the fields sent before `kind` are
+/// buffered; the rest stream straight into the part.
+struct PartVisitor;
+
+impl<'de> serde::de::Visitor<'de> for PartVisitor {
+    type Value = Part;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("a part object")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Part, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        use serde::de::Error;
+        use serde::de::value::MapAccessDeserializer;
+
+        let mut before = Vec::new();
+        let kind = loop {
+            match map.next_key::<String>()? {
+                Some(key) if key == "kind" => break map.next_value::<String>()?,
+                Some(key) => before.push((key, map.next_value::<serde_json::Value>()?)),
+                None => return Err(A::Error::missing_field("kind")),
+            }
+        };
+        // Malformed parts of a known kind are errors, not unknown parts
+        let part = |before: Vec<(String, serde_json::Value)>| {
+            MapAccessDeserializer::new(Resumed {
+                buffered: before.into_iter(),
+                pending: None,
+                map,
+            })
+        };
+        match kind.as_str() {
+            "text" => TextPart::deserialize(part(before)).map(Part::Text),
+            "file" => FilePart::deserialize(part(before)).map(|file| Part::File(Box::new(file))),
+            "data" => DataPart::deserialize(part(before)).map(Part::Data),
+            _ => {
+                before.push(("kind".to_string(), serde_json::Value::String(kind)));
+                UnknownPart::deserialize(part(before)).map(|part| Part::Unknown(Box::new(part)))
+            }
+        }
+    }
+}
+
+/// The fields of a part with the ones read before its `kind` put back.
+struct Resumed<A> {
+    buffered: alloc::vec::IntoIter<(String, serde_json::Value)>,
+    pending: Option<serde_json::Value>,
+    map: A,
+}
+
+impl<'de, A: serde::de::MapAccess<'de>> serde::de::MapAccess<'de> for Resumed<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error>
+    where
+        K: serde::de::DeserializeSeed<'de>,
+    {
+        use serde::de::IntoDeserializer;
+
+        match self.buffered.next() {
+            Some((key, value)) => {
+                self.pending = Some(value);
+                seed.deserialize(key.into_deserializer()).map(Some)
+            }
+            None => self.map.next_key_seed(seed),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, A::Error>
+    where
+        V: serde::de::DeserializeSeed<'de>,
+    {
+        use serde::de::Error;
+
+        match self.pending.take() {
+            Some(value) => seed.deserialize(value).map_err(A::Error::custom),
+            None => self.map.next_value_seed(seed),
+        }
+    }
+}
+
+impl Part {
+    /// Check if the part is of a kind this version knows, i.e. not
+    /// [`Part::Unknown`].
+    pub fn is_known(&self) -> bool {
+        !matches!(self, Part::Unknown(_))
+    }
+
+    /// Get the kind of the part as it appears on the wire.
+    pub fn kind(&self) -> &str {
+        match self {
+            Part::Text(_) => "text",
+            Part::File(_) => "file",
+            Part::Data(_) => "data",
+            Part::Unknown(part) => &part.kind,
+        }
+    }
+}
+
+/// A part of a kind this version does not know.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct UnknownPart {
+    /// The kind of the part, e.g. `video`
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::other_part_kind))]
+    pub kind: String,
+    /// Optional metadata associated with the part
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::object))]
+    pub metadata: Option<serde_json::Value>,
+    /// Every other field of the part, as sent
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::part_body))]
+    pub body: serde_json::Map<String, serde_json::Value>,
+}
+
+// Parts are stored by the thousand in histories and artifacts; keep them no
+// larger than the biggest unboxed variant plus its tag.
+const _: () = assert!(
+    core::mem::size_of::<Part>() <= core::mem::size_of::<DataPart>() + core::mem::size_of::<usize>()
+        && core::mem::size_of::<TextPart>() <= core::mem::size_of::<DataPart>()
+);
+
+/// Represents a text segment within parts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct TextPart {
+    /// Text content
+    pub text: String,
+    /// Optional metadata associated with the part
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::object))]
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Represents a File segment within parts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct FilePart {
+    /// File content either as url or bytes
+    pub file: FileContent,
+    /// Optional metadata associated with the part
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::object))]
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Represents a structured data segment within a message part.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct DataPart {
+    /// Structured data content
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::value))]
+    pub data: serde_json::Value,
+    /// Optional metadata associated with the part
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::object))]
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// File content variants
+///
+/// Deserialization picks the variant by which of `bytes` and `uri` is
+/// present, and rejects files with both or neither.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schema", schemars(transform = crate::schema::one_of))]
+#[serde(untagged)]
+pub enum FileContent {
+    WithBytes(FileWithBytes),
+    WithUri(FileWithUri),
+}
+
+impl<'de> Deserialize<'de> for FileContent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(Deserialize)]
+        struct Fields {
+            bytes: Option<String>,
+            uri: Option<String>,
+            name: Option<String>,
+            #[serde(rename = "mimeType")]
+            mime_type: Option<String>,
+        }
+
+        let Fields { bytes, uri, name, mime_type } = Fields::deserialize(deserializer)?;
+        match (bytes, uri) {
+            (Some(bytes), None) => Ok(FileContent::WithBytes(FileWithBytes { bytes, name, mime_type })),
+            (None, Some(uri)) => Ok(FileContent::WithUri(FileWithUri { uri, name, mime_type })),
+            (Some(_), Some(_)) => Err(D::Error::custom("file has both `bytes` and `uri`, expected exactly one")),
+            (None, None) => Err(D::Error::custom("file has neither `bytes` nor `uri`, expected exactly one")),
+        }
+    }
+}
+
+/// File with base64 encoded bytes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct FileWithBytes {
+    /// base64 encoded content of the file
+    pub bytes: String,
+    /// Optional name for the file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Optional mimeType for the file
+    #[serde(rename = "mimeType", skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// File with URI reference
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct FileWithUri {
+    /// URL for the File content
+    pub uri: String,
+    /// Optional name for the file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Optional mimeType for the file
+    #[serde(rename = "mimeType", skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+// ============================================================================
+// CONVERSIONS
+// ============================================================================
+
+impl From<TextPart> for Part {
+    fn from(part: TextPart) -> Self {
+        Part::Text(part)
+    }
+}
+
+impl From<FilePart> for Part {
+    fn from(part: FilePart) -> Self {
+        Part::File(Box::new(part))
+    }
+}
+
+impl From<DataPart> for Part {
+    fn from(part: DataPart) -> Self {
+        Part::Data(part)
+    }
+}
+
+impl From<String> for Part {
+    fn from(text: String) -> Self {
+        Part::Text(TextPart::from(text))
+    }
+}
+
+impl From<&str> for Part {
+    fn from(text: &str) -> Self {
+        Part::Text(TextPart::from(text))
+    }
+}
+
+impl From<String> for TextPart {
+    fn from(text: String) -> Self {
+        TextPart { text, metadata: None }
+    }
+}
+
+impl From<&str> for TextPart {
+    fn from(text: &str) -> Self {
+        TextPart::from(text.to_string())
+    }
+}
+
+impl From<serde_json::Value> for DataPart {
+    fn from(data: serde_json::Value) -> Self {
+        DataPart { data, metadata: None }
+    }
+}
+
+impl From<FileContent> for FilePart {
+    fn from(file: FileContent) -> Self {
+        FilePart { file, metadata: None }
+    }
+}
+
+impl From<FileWithBytes> for FileContent {
+    fn from(file: FileWithBytes) -> Self {
+        FileContent::WithBytes(file)
+    }
+}
+
+impl From<FileWithUri> for FileContent {
+    fn from(file: FileWithUri) -> Self {
+        FileContent::WithUri(file)
+    }
+}
+
+/// Fails with the original part if it is not a text part.
+impl TryFrom<Part> for TextPart {
+    type Error = Part;
+
+    fn try_from(part: Part) -> Result<Self, Self::Error> {
+        match part {
+            Part::Text(part) => Ok(part),
+            other => Err(other),
+        }
+    }
+}
+
+/// Fails with the original part if it is not a file part.
+impl TryFrom<Part> for FilePart {
+    type Error = Part;
+
+    fn try_from(part: Part) -> Result<Self, Self::Error> {
+        match part {
+            Part::File(part) => Ok(*part),
+            other => Err(other),
+        }
+    }
+}
+
+/// Fails with the original part if it is not a data part.
+impl TryFrom<Part> for DataPart {
+    type Error = Part;
+
+    fn try_from(part: Part) -> Result<Self, Self::Error> {
+        match part {
+            Part::Data(part) => Ok(part),
+            other => Err(other),
+        }
+    }
+}
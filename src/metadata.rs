@@ -0,0 +1,324 @@
+//! Helpers for working with extension metadata.
+//!
+//! Many A2A types carry an optional `metadata` object. The functions in this
+//! module operate on the raw `Option<serde_json::Value>` field, and the
+//! [`MetadataExt`] trait exposes the same operations as methods on every type
+//! that carries metadata.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::A2AError;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// Policy used by [`merge_meta`] when both sides define the same non-object key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Keep the value already present in the metadata.
+    KeepExisting,
+    /// Replace the existing value with the incoming one.
+    Overwrite,
+}
+
+/// Build a namespaced metadata key.
+///
+/// Namespaces should be reverse-DNS names owned by the producer of the
+/// metadata, so that keys from different extensions cannot collide.
+///
+/// # Arguments
+///
+/// * `ns` - The namespace, e.g. `com.example`.
+/// * `key` - The key within the namespace.
+///
+/// # Returns
+///
+/// The namespaced key, e.g. `com.example.traceId`.
+pub fn namespaced(ns: &str, key: &str) -> String {
+    format!("{}.{}", ns, key)
+}
+
+/// Build the metadata key holding data scoped to an extension.
+///
+/// # Arguments
+///
+/// * `uri` - The extension URI.
+///
+/// # Returns
+///
+/// The key, e.g. `extensions.https://example.com/ext/v1`.
+pub fn extension_key(uri: &str) -> String {
+    namespaced("extensions", uri)
+}
+
+/// Ensure the metadata is a JSON object and return it.
+///
+/// Missing metadata and non-object values (including `null`) are replaced by
+/// an empty object, since the protocol requires metadata to be an object.
+fn ensure_object(metadata: &mut Option<Value>) -> &mut Map<String, Value> {
+    if !matches!(metadata, Some(Value::Object(_))) {
+        *metadata = Some(Value::Object(Map::new()));
+    }
+
+    match metadata {
+        Some(Value::Object(map)) => map,
+        _ => unreachable!("metadata was just set to an object"),
+    }
+}
+
+/// Set a metadata key, creating the metadata object if needed.
+///
+/// # Arguments
+///
+/// * `metadata` - The metadata field to modify.
+/// * `key` - The key to set.
+/// * `value` - The value to store.
+pub fn set_meta(metadata: &mut Option<Value>, key: impl Into<String>, value: Value) {
+    ensure_object(metadata).insert(key.into(), value);
+}
+
+/// Get a metadata value by key.
+///
+/// # Arguments
+///
+/// * `metadata` - The metadata field to read.
+/// * `key` - The key to look up.
+///
+/// # Returns
+///
+/// The value, or `None` if the key is absent or the metadata is not an object.
+pub fn get_meta<'a>(metadata: &'a Option<Value>, key: &str) -> Option<&'a Value> {
+    metadata.as_ref()?.as_object()?.get(key)
+}
+
+/// Remove a metadata key.
+///
+/// # Arguments
+///
+/// * `metadata` - The metadata field to modify.
+/// * `key` - The key to remove.
+///
+/// # Returns
+///
+/// The removed value, if any.
+pub fn remove_meta(metadata: &mut Option<Value>, key: &str) -> Option<Value> {
+    metadata.as_mut()?.as_object_mut()?.remove(key)
+}
+
+/// Deep merge an object into the metadata.
+///
+/// Nested objects are merged recursively. When both sides define a key whose
+/// values are not both objects, `policy` decides which value wins. Non-object
+/// `other` values are ignored.
+///
+/// # Arguments
+///
+/// * `metadata` - The metadata field to merge into.
+/// * `other` - The object to merge.
+/// * `policy` - How to resolve conflicting keys.
+pub fn merge_meta(metadata: &mut Option<Value>, other: Value, policy: MergePolicy) {
+    if let Value::Object(other) = other {
+        merge_objects(ensure_object(metadata), other, policy);
+    }
+}
+
+fn merge_objects(target: &mut Map<String, Value>, other: Map<String, Value>, policy: MergePolicy) {
+    for (key, incoming) in other {
+        match target.get_mut(&key) {
+            Some(Value::Object(existing)) if incoming.is_object() => {
+                if let Value::Object(incoming) = incoming {
+                    merge_objects(existing, incoming, policy);
+                }
+            }
+            Some(existing) => {
+                if policy == MergePolicy::Overwrite {
+                    *existing = incoming;
+                }
+            }
+            None => {
+                target.insert(key, incoming);
+            }
+        }
+    }
+}
+
+/// Method-style access to the metadata of A2A types.
+pub trait MetadataExt {
+    /// Borrow the raw metadata field.
+    fn metadata_field(&self) -> &Option<Value>;
+
+    /// Mutably borrow the raw metadata field.
+    fn metadata_field_mut(&mut self) -> &mut Option<Value>;
+
+    /// Set a metadata key. See [`set_meta`].
+    fn set_metadata(&mut self, key: impl Into<String>, value: Value) {
+        set_meta(self.metadata_field_mut(), key, value);
+    }
+
+    /// Get a metadata value by key. See [`get_meta`].
+    fn get_metadata(&self, key: &str) -> Option<&Value> {
+        get_meta(self.metadata_field(), key)
+    }
+
+    /// Remove a metadata key. See [`remove_meta`].
+    fn remove_metadata(&mut self, key: &str) -> Option<Value> {
+        remove_meta(self.metadata_field_mut(), key)
+    }
+
+    /// Deep merge an object into the metadata. See [`merge_meta`].
+    fn merge_metadata(&mut self, other: Value, policy: MergePolicy) {
+        merge_meta(self.metadata_field_mut(), other, policy);
+    }
+
+    /// Get the data stored for an extension, deserialized into `T`.
+    ///
+    /// The data lives under the [`extension_key`] for `uri`. Returns
+    /// `Ok(None)` if there is none, or an `InvalidParams` error if it does
+    /// not match `T`.
+    fn extension_data_as<T: DeserializeOwned>(&self, uri: &str) -> Result<Option<T>, A2AError> {
+        self.get_metadata(&extension_key(uri))
+            .map(|value| crate::extensions::from_extension_value(uri, value))
+            .transpose()
+    }
+
+    /// Store data for an extension under its [`extension_key`].
+    ///
+    /// Returns an `Internal` error if `data` cannot be serialized.
+    fn set_extension_data<T: Serialize>(&mut self, uri: &str, data: &T) -> Result<(), A2AError> {
+        let value = crate::extensions::to_extension_value(uri, data)?;
+        self.set_metadata(extension_key(uri), value);
+        Ok(())
+    }
+}
+
+macro_rules! impl_metadata_ext {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl MetadataExt for $ty {
+                fn metadata_field(&self) -> &Option<Value> {
+                    &self.metadata
+                }
+
+                fn metadata_field_mut(&mut self) -> &mut Option<Value> {
+                    &mut self.metadata
+                }
+            }
+        )*
+    };
+}
+
+impl_metadata_ext!(
+    crate::Message,
+    crate::TextPart,
+    crate::FilePart,
+    crate::DataPart,
+    crate::Task,
+    crate::Artifact,
+    crate::TaskArtifactUpdateEvent,
+    crate::TaskStatusUpdateEvent,
+    crate::TaskIdParams,
+    crate::SendMessageParams,
+);
+
+impl MetadataExt for crate::Part {
+    fn metadata_field(&self) -> &Option<Value> {
+        match self {
+            crate::Part::Text(part) => &part.metadata,
+            crate::Part::File(part) => &part.metadata,
+            crate::Part::Data(part) => &part.metadata,
+            crate::Part::Unknown(part) => &part.metadata,
+        }
+    }
+
+    fn metadata_field_mut(&mut self) -> &mut Option<Value> {
+        match self {
+            crate::Part::Text(part) => &mut part.metadata,
+            crate::Part::File(part) => &mut part.metadata,
+            crate::Part::Data(part) => &mut part.metadata,
+            crate::Part::Unknown(part) => &mut part.metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Part, TextPart};
+    use serde_json::json;
+
+    #[test]
+    fn test_set_and_get_meta() {
+        let mut metadata = None;
+        set_meta(&mut metadata, "traceId", json!("abc"));
+
+        assert_eq!(get_meta(&metadata, "traceId"), Some(&json!("abc")));
+        assert_eq!(get_meta(&metadata, "missing"), None);
+        assert_eq!(remove_meta(&mut metadata, "traceId"), Some(json!("abc")));
+        assert_eq!(metadata, Some(json!({})));
+    }
+
+    #[test]
+    fn test_non_object_metadata_is_upgraded() {
+        let mut metadata = Some(json!("legacy"));
+        assert_eq!(get_meta(&metadata, "traceId"), None);
+
+        set_meta(&mut metadata, "traceId", json!(1));
+        assert_eq!(metadata, Some(json!({"traceId": 1})));
+
+        let mut metadata = Some(Value::Null);
+        merge_meta(&mut metadata, json!({"a": 1}), MergePolicy::KeepExisting);
+        assert_eq!(metadata, Some(json!({"a": 1})));
+    }
+
+    #[test]
+    fn test_merge_nested_objects() {
+        let mut metadata = Some(json!({
+            "com.example": {"traceId": "t1", "span": {"id": 1}},
+            "priority": "low"
+        }));
+
+        let incoming = json!({
+            "com.example": {"traceId": "t2", "span": {"parent": 0}},
+            "priority": "high",
+            "added": true
+        });
+
+        let mut kept = metadata.clone();
+        merge_meta(&mut kept, incoming.clone(), MergePolicy::KeepExisting);
+        assert_eq!(
+            kept,
+            Some(json!({
+                "com.example": {"traceId": "t1", "span": {"id": 1, "parent": 0}},
+                "priority": "low",
+                "added": true
+            }))
+        );
+
+        merge_meta(&mut metadata, incoming, MergePolicy::Overwrite);
+        assert_eq!(
+            metadata,
+            Some(json!({
+                "com.example": {"traceId": "t2", "span": {"id": 1, "parent": 0}},
+                "priority": "high",
+                "added": true
+            }))
+        );
+    }
+
+    #[test]
+    fn test_metadata_ext_on_part() {
+        let mut part = Part::Text(TextPart {
+            text: "hello".to_string(),
+            metadata: None,
+        });
+
+        let key = namespaced("com.example", "traceId");
+        assert_eq!(key, "com.example.traceId");
+
+        part.set_metadata(key.clone(), json!("abc"));
+        assert_eq!(part.get_metadata(&key), Some(&json!("abc")));
+
+        let json = serde_json::to_value(&part).unwrap();
+        assert_eq!(json["metadata"]["com.example.traceId"], "abc");
+    }
+}
@@ -0,0 +1,135 @@
+//! OAuth2 scope checks.
+//!
+//! An agent card's `security` requirements name the scopes a request needs
+//! for each OAuth2 scheme. [`check_scopes`] compares them with the scopes a
+//! token was granted and reports what is missing.
+//!
+//! ```
+//! use a2a_rs::oauth::{ScopePolicy, check_scopes, check_scopes_with};
+//!
+//! let required = ["tasks:read".to_string(), "tasks:write".to_string()];
+//! let check = check_scopes(&required, &["tasks:read".to_string()]);
+//! assert!(!check.satisfied);
+//! assert_eq!(check.missing, ["tasks:write"]);
+//!
+//! // With the hierarchical policy, `tasks` covers `tasks:read` and `tasks:write`
+//! assert!(check_scopes_with(&required, &["tasks".to_string()], ScopePolicy::Hierarchical).satisfied);
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+
+/// Separates the levels of a hierarchical scope such as `tasks:read`.
+pub const SCOPE_SEPARATOR: char = ':';
+
+/// How granted scopes are matched against required ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScopePolicy {
+    /// A required scope must be granted verbatim.
+    #[default]
+    Exact,
+    /// A granted scope also covers every scope below it, so `tasks` implies
+    /// `tasks:read` and `tasks:read` implies `tasks:read:own`.
+    Hierarchical,
+}
+
+impl ScopePolicy {
+    /// Check if a granted scope covers a required one.
+    pub fn covers(&self, granted: &str, required: &str) -> bool {
+        if granted == required {
+            return true;
+        }
+        match self {
+            ScopePolicy::Exact => false,
+            ScopePolicy::Hierarchical => required
+                .strip_prefix(granted)
+                .is_some_and(|rest| rest.starts_with(SCOPE_SEPARATOR)),
+        }
+    }
+}
+
+/// The outcome of comparing required scopes with granted ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeCheck {
+    /// Whether every required scope is covered.
+    pub satisfied: bool,
+    /// The required scopes that are not covered, in the order required.
+    pub missing: Vec<String>,
+}
+
+/// Check granted scopes against required ones, matching exactly.
+///
+/// # Arguments
+///
+/// * `required` - The scopes a security requirement demands.
+/// * `granted` - The scopes of the token.
+///
+/// # Returns
+///
+/// Whether the requirement is satisfied, and the missing scopes.
+pub fn check_scopes(required: &[String], granted: &[String]) -> ScopeCheck {
+    check_scopes_with(required, granted, ScopePolicy::Exact)
+}
+
+/// Check granted scopes against required ones under a matching policy.
+///
+/// # Arguments
+///
+/// * `required` - The scopes a security requirement demands.
+/// * `granted` - The scopes of the token.
+/// * `policy` - How a granted scope covers a required one.
+///
+/// # Returns
+///
+/// Whether the requirement is satisfied, and the missing scopes.
+pub fn check_scopes_with(required: &[String], granted: &[String], policy: ScopePolicy) -> ScopeCheck {
+    let mut missing: Vec<String> = Vec::new();
+    for scope in required {
+        if !granted.iter().any(|g| policy.covers(g, scope)) && !missing.contains(scope) {
+            missing.push(scope.clone());
+        }
+    }
+    ScopeCheck {
+        satisfied: missing.is_empty(),
+        missing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scopes(scopes: &[&str]) -> Vec<String> {
+        scopes.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_exact_matching() {
+        let required = scopes(&["tasks:read", "tasks:write"]);
+        let check = check_scopes(&required, &scopes(&["tasks:write", "tasks:read", "other"]));
+        assert!(check.satisfied);
+        assert!(check.missing.is_empty());
+
+        let check = check_scopes(&required, &scopes(&["tasks:read"]));
+        assert_eq!(check, ScopeCheck { satisfied: false, missing: scopes(&["tasks:write"]) });
+        assert_eq!(check_scopes(&required, &[]).missing, required);
+        assert!(check_scopes(&[], &[]).satisfied);
+
+        // A parent scope is not enough without the hierarchical policy
+        assert_eq!(check_scopes(&required, &scopes(&["tasks"])).missing, required);
+    }
+
+    #[test]
+    fn test_hierarchical_matching() {
+        let required = scopes(&["tasks:read", "tasks:write:own", "admin"]);
+        let check = check_scopes_with(&required, &scopes(&["tasks"]), ScopePolicy::Hierarchical);
+        assert_eq!(check.missing, scopes(&["admin"]));
+
+        let check = check_scopes_with(&required, &scopes(&["tasks:write", "admin"]), ScopePolicy::Hierarchical);
+        assert_eq!(check.missing, scopes(&["tasks:read"]));
+
+        // Only whole levels count: `task` does not cover `tasks:read`
+        assert!(!ScopePolicy::Hierarchical.covers("task", "tasks:read"));
+        assert!(!ScopePolicy::Hierarchical.covers("tasks:read", "tasks"));
+    }
+}
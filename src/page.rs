@@ -0,0 +1,80 @@
+//! Cursor-based pagination.
+//!
+//! The wire protocol returns complete lists, but stores and admin APIs need
+//! to page through task lists, push notification configs and long task
+//! histories. A [`Page`] carries one slice of items and an opaque cursor for
+//! the next one. Cursors identify the last item seen rather than an offset,
+//! so items appended between requests neither shift nor repeat earlier
+//! pages.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::{A2AError, InvalidParamsError};
+use serde::{Deserialize, Serialize};
+
+/// One page of a longer list.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    /// The items on this page.
+    pub items: Vec<T>,
+    /// The cursor for the next page, or `None` if this is the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Create a page.
+    pub fn new(items: Vec<T>, next_cursor: Option<String>) -> Self {
+        Self { items, next_cursor }
+    }
+
+    /// Check if there are no further pages.
+    pub fn is_last(&self) -> bool {
+        self.next_cursor.is_none()
+    }
+
+    /// Convert the items, keeping the cursor.
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+
+    /// Build a page from up to `limit + 1` items sorted in cursor order.
+    ///
+    /// The extra item only signals that another page exists and is dropped.
+    /// The cursor is derived from the last item kept.
+    #[cfg(feature = "std")]
+    pub(crate) fn from_overfetch(mut items: Vec<T>, limit: usize, cursor: impl Fn(&T) -> String) -> Self {
+        let has_more = items.len() > limit;
+        items.truncate(limit);
+        let next_cursor = if has_more { items.last().map(cursor) } else { None };
+        Self { items, next_cursor }
+    }
+}
+
+/// Check the requested size of a page.
+///
+/// A page of zero items could not carry a cursor derived from its last
+/// item, and would report itself as the last page with items remaining.
+pub(crate) fn check_limit(limit: usize) -> Result<(), A2AError> {
+    if limit == 0 {
+        return Err(A2AError::InvalidParams(Box::new(InvalidParamsError {
+            code: -32602,
+            message: "Page limit must be at least 1".to_string(),
+            data: None,
+        })));
+    }
+    Ok(())
+}
+
+/// Error for a cursor that does not identify a position in the list.
+pub(crate) fn invalid_cursor(cursor: &str) -> A2AError {
+    A2AError::InvalidParams(Box::new(InvalidParamsError {
+        code: -32602,
+        message: format!("Invalid cursor: {}", cursor),
+        data: None,
+    }))
+}
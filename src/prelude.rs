@@ -0,0 +1,28 @@
+//! The types most applications need, for glob import.
+//!
+//! Wire details such as the per-method response types, error payload structs
+//! and OAuth2 flow definitions stay at the crate root.
+//!
+//! ```
+//! use a2a_rs::prelude::*;
+//!
+//! let request = SendMessageRequest::new("1".into(), "msg-1".into(), "Hello".into(), MessageRole::User, None, None);
+//! let body = serialize_response(&request).unwrap();
+//! // ... POST `body` to the agent and read its reply ...
+//! let reply = r#"{"jsonrpc":"2.0","id":"1","result":{"kind":"message","messageId":"msg-2","role":"agent","parts":[]}}"#;
+//! let response: SendMessageResponse = serde_json::from_str(reply).unwrap();
+//! assert!(matches!(response, SendMessageResponse::Success(_)));
+//! ```
+
+pub use crate::agent::{AgentCapabilities, AgentCard, AgentExtension, AgentProvider, AgentSkill, Capability};
+pub use crate::events::{TaskArtifactUpdateEvent, TaskStatusUpdateEvent};
+pub use crate::helpers::{parse_a2a_request, parse_envelope, parse_request, serialize_response};
+pub use crate::ids::{ArtifactId, ConfigId, ContextId, MessageId, TaskId};
+pub use crate::message::{DataPart, FileContent, FilePart, FileWithBytes, FileWithUri, Message, MessageRole, Part, TextPart};
+pub use crate::metadata::MetadataExt;
+pub use crate::rpc::{
+    A2AError, A2ARequest, CancelTaskRequest, GetTaskRequest, JsonRpcId, MessageSendConfiguration, RequestMethod,
+    SendMessageParams, SendMessageRequest, SendMessageResponse, SendMessageResult, StreamingMessageResult,
+};
+pub use crate::security::SecurityScheme;
+pub use crate::task::{Artifact, Task, TaskState, TaskStatus};
@@ -0,0 +1,651 @@
+//! Push notification URL checks.
+//!
+//! An agent delivers push notifications to whatever URL the client
+//! registered, so an unchecked config lets a client aim the agent at
+//! `http://169.254.169.254/` or an admin port on `localhost`. A
+//! [`UrlPolicy`] rejects such URLs: hosts that are loopback, private,
+//! link-local or otherwise internal IP addresses (however the address is
+//! spelled), `localhost` names and ports other than 80 and 443, unless the
+//! host or port was explicitly allowed. Servers check registered configs
+//! with [`PushNotificationConfig::validate_with`] and answer violations with
+//! the `InvalidParams` error that [`UrlPolicyViolation`] converts into.
+//!
+//! With the `async` feature, `UrlPolicy::resolve` also resolves the host
+//! name and checks every address it resolves to. Deliver to the returned
+//! addresses rather than resolving the name again, or the name can be
+//! re-pointed at an internal address after the check.
+//!
+//! ```
+//! use a2a_rs::push::{UrlPolicy, UrlPolicyViolation};
+//!
+//! let policy = UrlPolicy::new().allow_host("hooks.internal");
+//! assert!(policy.check("https://example.com/webhook").is_ok());
+//! assert!(policy.check("http://hooks.internal/a2a").is_ok());
+//! assert!(matches!(policy.check("http://169.254.169.254/"), Err(UrlPolicyViolation::InternalAddress(_))));
+//! assert_eq!(policy.check("http://example.com:8500/"), Err(UrlPolicyViolation::Port(8500)));
+//! ```
+//!
+//! The body of a notification is a [`PushNotificationPayload`]: the whole
+//! task, or with [`PushDelivery::StatusUpdate`] only a status update event,
+//! told apart by their `kind`:
+//!
+//! ```json
+//! {"kind": "task", "id": "task-1", "contextId": "ctx-1", "status": {"state": "completed"}}
+//! {"kind": "status-update", "taskId": "task-1", "contextId": "ctx-1", "status": {"state": "completed"}, "final": true}
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::{A2AError, InvalidParamsError, PushNotificationConfig, Task, TaskStatusUpdateEvent};
+use core::fmt;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use serde::{Deserialize, Serialize};
+
+/// Which push notification URLs an agent delivers to.
+///
+/// The default allows ports 80 and 443 on any host that is not internal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlPolicy {
+    allowed_hosts: Vec<String>,
+    allowed_ports: Vec<u16>,
+}
+
+impl Default for UrlPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_hosts: Vec::new(),
+            allowed_ports: Self::DEFAULT_PORTS.to_vec(),
+        }
+    }
+}
+
+impl UrlPolicy {
+    /// The ports allowed by default.
+    pub const DEFAULT_PORTS: [u16; 2] = [443, 80];
+
+    /// Create the default policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow a host even if it is internal, such as a webhook relay on the
+    /// private network.
+    ///
+    /// The host is compared case-insensitively with the URL's host; an IP
+    /// address also matches other spellings of the same address. The port
+    /// restriction still applies.
+    pub fn allow_host(mut self, host: impl Into<String>) -> Self {
+        let host = host.into();
+        let host = host.trim_start_matches('[').trim_end_matches(']').trim_end_matches('.');
+        self.allowed_hosts.push(host.to_ascii_lowercase());
+        self
+    }
+
+    /// Allow another port besides 80 and 443.
+    pub fn allow_port(mut self, port: u16) -> Self {
+        if !self.allowed_ports.contains(&port) {
+            self.allowed_ports.push(port);
+        }
+        self
+    }
+
+    /// Check a URL without resolving its host.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The push notification URL.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the policy allows the URL, otherwise the violation.
+    pub fn check(&self, url: &str) -> Result<(), UrlPolicyViolation> {
+        self.check_parsed(&parse_url(url)?)
+    }
+
+    fn check_parsed(&self, url: &ParsedUrl) -> Result<(), UrlPolicyViolation> {
+        if !self.allowed_ports.contains(&url.port) {
+            return Err(UrlPolicyViolation::Port(url.port));
+        }
+        if self.is_allowed_host(&url.host) {
+            return Ok(());
+        }
+        match &url.host {
+            Host::Ip(ip) if is_internal_ip(*ip) => Err(UrlPolicyViolation::InternalAddress(*ip)),
+            Host::Name(name) if name == "localhost" || name.ends_with(".localhost") => {
+                Err(UrlPolicyViolation::InternalHost(name.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn is_allowed_host(&self, host: &Host) -> bool {
+        self.allowed_hosts.iter().any(|allowed| match host {
+            Host::Name(name) => allowed == name,
+            Host::Ip(ip) => allowed.parse::<IpAddr>().is_ok_and(|allowed| allowed == *ip),
+        })
+    }
+
+    /// Check the addresses a host name resolved to.
+    #[cfg_attr(not(feature = "async"), allow(dead_code))]
+    fn check_addresses(&self, host: &str, addresses: &[IpAddr]) -> Result<(), UrlPolicyViolation> {
+        if self.is_allowed_host(&Host::Name(host.to_string())) {
+            return Ok(());
+        }
+        if addresses.is_empty() {
+            return Err(UrlPolicyViolation::Unresolvable {
+                host: host.to_string(),
+                message: "no addresses".to_string(),
+            });
+        }
+        match addresses.iter().find(|address| is_internal_ip(**address)) {
+            Some(address) => Err(UrlPolicyViolation::ResolvesToInternal {
+                host: host.to_string(),
+                address: *address,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Check a URL, then resolve its host and check every address.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The push notification URL.
+    ///
+    /// # Returns
+    ///
+    /// The socket addresses to deliver to, or the violation.
+    #[cfg(feature = "async")]
+    pub async fn resolve(&self, url: &str) -> Result<Vec<std::net::SocketAddr>, UrlPolicyViolation> {
+        let url = parse_url(url)?;
+        self.check_parsed(&url)?;
+        let name = match &url.host {
+            Host::Ip(ip) => return Ok(vec![std::net::SocketAddr::new(*ip, url.port)]),
+            Host::Name(name) => name,
+        };
+        let addresses: Vec<std::net::SocketAddr> = tokio::net::lookup_host((name.as_str(), url.port))
+            .await
+            .map_err(|e| UrlPolicyViolation::Unresolvable {
+                host: name.clone(),
+                message: e.to_string(),
+            })?
+            .collect();
+        let ips: Vec<IpAddr> = addresses.iter().map(|address| address.ip()).collect();
+        self.check_addresses(name, &ips)?;
+        Ok(addresses)
+    }
+}
+
+/// Why a [`UrlPolicy`] rejected a URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlPolicyViolation {
+    /// The URL cannot be parsed.
+    Malformed(String),
+    /// The scheme is not `http` or `https`.
+    Scheme(String),
+    /// The port is not allowed.
+    Port(u16),
+    /// The host is an internal IP address.
+    InternalAddress(IpAddr),
+    /// The host name refers to the local machine.
+    InternalHost(String),
+    /// The host name resolves to an internal IP address.
+    ResolvesToInternal { host: String, address: IpAddr },
+    /// The host name cannot be resolved.
+    Unresolvable { host: String, message: String },
+}
+
+impl fmt::Display for UrlPolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlPolicyViolation::Malformed(message) => write!(f, "Malformed URL: {}", message),
+            UrlPolicyViolation::Scheme(scheme) => write!(f, "URL scheme '{}' is not allowed", scheme),
+            UrlPolicyViolation::Port(port) => write!(f, "URL port {} is not allowed", port),
+            UrlPolicyViolation::InternalAddress(ip) => write!(f, "URL host {} is an internal address", ip),
+            UrlPolicyViolation::InternalHost(host) => write!(f, "URL host '{}' is an internal host", host),
+            UrlPolicyViolation::ResolvesToInternal { host, address } => {
+                write!(f, "URL host '{}' resolves to internal address {}", host, address)
+            }
+            UrlPolicyViolation::Unresolvable { host, message } => {
+                write!(f, "URL host '{}' cannot be resolved: {}", host, message)
+            }
+        }
+    }
+}
+
+impl core::error::Error for UrlPolicyViolation {}
+
+impl From<UrlPolicyViolation> for A2AError {
+    fn from(violation: UrlPolicyViolation) -> Self {
+        A2AError::InvalidParams(Box::new(InvalidParamsError {
+            code: -32602,
+            message: format!("Push notification URL rejected: {}", violation),
+            data: None,
+        }))
+    }
+}
+
+impl PushNotificationConfig {
+    /// Check the config's URL against a policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The URLs the agent delivers to.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the policy allows the URL, otherwise the violation.
+    pub fn validate_with(&self, policy: &UrlPolicy) -> Result<(), UrlPolicyViolation> {
+        policy.check(&self.url)
+    }
+}
+
+/// What a push notification carries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PushDelivery {
+    /// The whole task, as `tasks/get` would return it.
+    #[default]
+    FullTask,
+    /// Only a status update event, for receivers that fetch the task
+    /// themselves or only track progress.
+    StatusUpdate,
+}
+
+/// The body of a push notification.
+///
+/// Deserialization picks the variant by `kind` and rejects other kinds, so
+/// a receiver never mistakes one shape for the other.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
+pub enum PushNotificationPayload {
+    /// The whole task, with `kind` `task`.
+    Task(Task),
+    /// A status update event, with `kind` `status-update`.
+    StatusUpdate(TaskStatusUpdateEvent),
+}
+
+impl<'de> Deserialize<'de> for PushNotificationPayload {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+        match value.get("kind").and_then(serde_json::Value::as_str) {
+            Some("task") => Task::deserialize(value).map(PushNotificationPayload::Task).map_err(D::Error::custom),
+            Some("status-update") => TaskStatusUpdateEvent::deserialize(value)
+                .map(PushNotificationPayload::StatusUpdate)
+                .map_err(D::Error::custom),
+            Some(kind) => Err(D::Error::custom(format!("unknown push notification kind '{}'", kind))),
+            None => Err(D::Error::missing_field("kind")),
+        }
+    }
+}
+
+impl PushNotificationPayload {
+    /// Build the notification for a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task as it is now.
+    /// * `delivery` - Whether to send the whole task or only its status.
+    ///
+    /// # Returns
+    ///
+    /// The task, or an event carrying its status that is final once the
+    /// task is in a terminal state.
+    pub fn for_task(task: &Task, delivery: PushDelivery) -> Self {
+        match delivery {
+            PushDelivery::FullTask => PushNotificationPayload::Task(task.clone()),
+            PushDelivery::StatusUpdate => PushNotificationPayload::StatusUpdate(TaskStatusUpdateEvent::new(
+                task.id.clone(),
+                task.context_id.clone(),
+                task.status.clone(),
+                task.status.state.is_terminal(),
+            )),
+        }
+    }
+
+    /// The ID of the task the notification is about.
+    pub fn task_id(&self) -> &str {
+        match self {
+            PushNotificationPayload::Task(task) => &task.id,
+            PushNotificationPayload::StatusUpdate(event) => &event.task_id,
+        }
+    }
+
+    /// Serialize the notification body.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("push notification payloads always serialize")
+    }
+
+    /// Parse a notification body.
+    ///
+    /// # Returns
+    ///
+    /// The payload, or an error if the body is not JSON, has a `kind` other
+    /// than `task` or `status-update`, or does not match its kind.
+    pub fn from_json(body: &[u8]) -> Result<Self, serde_json::Error> {
+        serde_json::from_slice(body)
+    }
+}
+
+/// Check if an address is loopback, private, link-local, unspecified,
+/// shared, multicast or reserved.
+///
+/// IPv6 addresses embedding an IPv4 address are judged by the embedded
+/// address: IPv4-mapped (`::ffff:a.b.c.d`), IPv4-compatible
+/// (`::a.b.c.d`), the `64:ff9b::/96` NAT64 prefix, 6to4 (`2002::/16`) and
+/// Teredo (`2001::/32`), whose server and client addresses are both
+/// checked.
+pub fn is_internal_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_internal_ipv4(ip),
+        IpAddr::V6(ip) => is_internal_ipv6(ip),
+    }
+}
+
+fn is_internal_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    a == 0
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 192 && b == 0 && ip.octets()[2] == 0)
+        || ip.is_multicast()
+        || a >= 240
+}
+
+fn is_internal_ipv6(ip: Ipv6Addr) -> bool {
+    if let Some(v4) = ip.to_ipv4_mapped() {
+        return is_internal_ipv4(v4);
+    }
+    let segments = ip.segments();
+    let v4 = |high: u16, low: u16| Ipv4Addr::from((u32::from(high) << 16) | u32::from(low));
+    match segments {
+        // IPv4-compatible, deprecated but still routed by some stacks;
+        // `::` and `::1` are caught as unspecified and loopback here too
+        [0, 0, 0, 0, 0, 0, high, low] | [0x64, 0xff9b, 0, 0, 0, 0, high, low] => {
+            return is_internal_ipv4(v4(high, low));
+        }
+        [0x2002, high, low, ..] => return is_internal_ipv4(v4(high, low)),
+        // Teredo: the server address follows the prefix, the client
+        // address is stored inverted in the last 32 bits
+        [0x2001, 0, server_high, server_low, .., client_high, client_low] => {
+            return is_internal_ipv4(v4(server_high, server_low))
+                || is_internal_ipv4(v4(!client_high, !client_low));
+        }
+        _ => {}
+    }
+    ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        || (segments[0] & 0xfe00) == 0xfc00
+        || (segments[0] & 0xffc0) == 0xfe80
+        || (segments[0] & 0xffc0) == 0xfec0
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Host {
+    Ip(IpAddr),
+    Name(String),
+}
+
+struct ParsedUrl {
+    host: Host,
+    port: u16,
+}
+
+fn malformed(message: &str) -> UrlPolicyViolation {
+    UrlPolicyViolation::Malformed(message.to_string())
+}
+
+/// Extract the host and port of an HTTP(S) URL the way browsers and HTTP
+/// clients do, so that the checked host is the one that gets connected to.
+fn parse_url(url: &str) -> Result<ParsedUrl, UrlPolicyViolation> {
+    if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(malformed("URL cannot contain whitespace or control characters"));
+    }
+    let (scheme, rest) = url.split_once("://").ok_or_else(|| malformed("URL must have a scheme"))?;
+    let default_port = match scheme.to_ascii_lowercase().as_str() {
+        "http" => 80,
+        "https" => 443,
+        _ => return Err(UrlPolicyViolation::Scheme(scheme.to_string())),
+    };
+
+    // HTTP clients treat `\` like `/`
+    let authority = rest.split(['/', '\\', '?', '#']).next().unwrap_or_default();
+    let host_port = authority.rsplit_once('@').map_or(authority, |(_, host_port)| host_port);
+
+    let (host, port) = if let Some(bracketed) = host_port.strip_prefix('[') {
+        let (host, after) = bracketed.split_once(']').ok_or_else(|| malformed("unterminated IPv6 address"))?;
+        let ip: Ipv6Addr = host.parse().map_err(|_| malformed("invalid IPv6 address"))?;
+        let port = match after {
+            "" => None,
+            _ => Some(after.strip_prefix(':').ok_or_else(|| malformed("invalid characters after IPv6 address"))?),
+        };
+        (Host::Ip(IpAddr::V6(ip)), port)
+    } else {
+        let (host, port) = match host_port.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (host_port, None),
+        };
+        (parse_host(host)?, port)
+    };
+
+    let port = match port {
+        None | Some("") => default_port,
+        Some(port) if port.bytes().all(|b| b.is_ascii_digit()) => {
+            port.parse().map_err(|_| malformed("port out of range"))?
+        }
+        Some(_) => return Err(malformed("invalid port")),
+    };
+    Ok(ParsedUrl { host, port })
+}
+
+fn parse_host(host: &str) -> Result<Host, UrlPolicyViolation> {
+    let host = host.strip_suffix('.').unwrap_or(host).to_ascii_lowercase();
+    if host.is_empty() {
+        return Err(malformed("URL must contain a host"));
+    }
+    if !host.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_')) {
+        return Err(malformed("host contains invalid characters"));
+    }
+
+    // A host whose last label is a number is an IPv4 address, possibly in
+    // decimal (`2130706433`), hex (`0x7f.1`), octal (`0177.0.0.1`) or
+    // shortened (`127.1`) form
+    let last = host.rsplit('.').next().unwrap_or_default();
+    if !last.is_empty() && (last.bytes().all(|b| b.is_ascii_digit()) || parse_ipv4_number(last).is_some()) {
+        return parse_ipv4(&host).map(|ip| Host::Ip(IpAddr::V4(ip))).ok_or_else(|| malformed("invalid IPv4 address"));
+    }
+    Ok(Host::Name(host))
+}
+
+fn parse_ipv4(host: &str) -> Option<Ipv4Addr> {
+    let parts = host.split('.').map(parse_ipv4_number).collect::<Option<Vec<u64>>>()?;
+    let (last, init) = parts.split_last()?;
+    if init.len() > 3 || init.iter().any(|part| *part > 255) || *last >= 256u64.pow(4 - init.len() as u32) {
+        return None;
+    }
+    let address = init
+        .iter()
+        .enumerate()
+        .fold(*last, |address, (i, part)| address + (part << (8 * (3 - i))));
+    Some(Ipv4Addr::from(address as u32))
+}
+
+fn parse_ipv4_number(part: &str) -> Option<u64> {
+    let (digits, radix) = if let Some(hex) = part.strip_prefix("0x").or_else(|| part.strip_prefix("0X")) {
+        (hex, 16)
+    } else if part.len() > 1 && part.starts_with('0') {
+        (&part[1..], 8)
+    } else {
+        (part, 10)
+    };
+    if digits.is_empty() {
+        return (radix != 10).then_some(0);
+    }
+    // At most five bytes' worth of digits, so parsing cannot overflow
+    if digits.len() > 16 || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    u64::from_str_radix(digits, radix).ok().filter(|n| *n <= u64::from(u32::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(url: &str) -> Result<(), UrlPolicyViolation> {
+        UrlPolicy::new().check(url)
+    }
+
+    fn internal(ip: &str) -> Result<(), UrlPolicyViolation> {
+        Err(UrlPolicyViolation::InternalAddress(ip.parse().unwrap()))
+    }
+
+    #[test]
+    fn test_ipv4_literals() {
+        assert!(check("https://93.184.216.34/hook").is_ok());
+        assert_eq!(check("http://169.254.169.254/latest/meta-data/"), internal("169.254.169.254"));
+        assert_eq!(check("http://127.0.0.1/"), internal("127.0.0.1"));
+        assert_eq!(check("https://10.1.2.3/"), internal("10.1.2.3"));
+        assert_eq!(check("https://172.16.0.1/"), internal("172.16.0.1"));
+        assert_eq!(check("https://192.168.1.1./"), internal("192.168.1.1"));
+        assert_eq!(check("https://100.64.0.1/"), internal("100.64.0.1"));
+        assert_eq!(check("http://0.0.0.0/"), internal("0.0.0.0"));
+        // The userinfo is not the host
+        assert_eq!(check("http://example.com@127.0.0.1/"), internal("127.0.0.1"));
+        // ...but a backslash ends the host, as in HTTP clients
+        assert!(check("http://example.com\\@127.0.0.1/").is_ok());
+    }
+
+    #[test]
+    fn test_encoded_ipv4_literals() {
+        assert_eq!(check("http://2852039166/"), internal("169.254.169.254"));
+        assert_eq!(check("http://0xA9FEA9FE/"), internal("169.254.169.254"));
+        assert_eq!(check("http://0251.0376.0251.0376/"), internal("169.254.169.254"));
+        assert_eq!(check("http://127.1/"), internal("127.0.0.1"));
+        assert_eq!(check("http://0x7f.0.0.1/"), internal("127.0.0.1"));
+        assert_eq!(check("http://10.0x10203/"), internal("10.1.2.3"));
+        assert_eq!(check("http://0/"), internal("0.0.0.0"));
+        assert!(check("http://1572395042/").is_ok());
+
+        for url in ["http://4294967296/", "http://1.2.3.4.5/", "http://256.0.0.1/", "http://08.0.0.1/", "http://a.0x/"] {
+            assert!(matches!(check(url), Err(UrlPolicyViolation::Malformed(_))), "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_ipv6_literals() {
+        assert!(check("https://[2606:2800:220:1:248:1893:25c8:1946]/").is_ok());
+        assert_eq!(check("http://[::1]/"), internal("::1"));
+        assert_eq!(check("http://[::]/"), internal("::"));
+        assert_eq!(check("http://[fe80::1]/"), internal("fe80::1"));
+        assert_eq!(check("http://[fd00:ec2::254]/"), internal("fd00:ec2::254"));
+        assert_eq!(check("http://[::ffff:169.254.169.254]/"), internal("::ffff:169.254.169.254"));
+        assert_eq!(check("http://[64:ff9b::7f00:1]/"), internal("64:ff9b::7f00:1"));
+        assert_eq!(check("http://[::10.0.0.1]/"), internal("::10.0.0.1"));
+        assert_eq!(check("http://[2002:a9fe:a9fe::1]/"), internal("2002:a9fe:a9fe::1"));
+        assert!(check("http://[2002:5db8:d822::1]/").is_ok());
+        // Teredo with a private server, and with a public server and a
+        // client of 192.168.1.1, stored as 3f57:fefe
+        assert_eq!(check("http://[2001:0:a00:1::1]/"), internal("2001:0:a00:1::1"));
+        assert_eq!(check("http://[2001:0:5db8:d822::3f57:fefe]/"), internal("2001:0:5db8:d822::3f57:fefe"));
+        assert!(check("http://[2001:0:5db8:d822::a247:27dd]/").is_ok());
+        assert_eq!(check("https://[::1]:443/"), internal("::1"));
+        assert!(matches!(check("http://[fe80::1%25eth0]/"), Err(UrlPolicyViolation::Malformed(_))));
+        assert!(matches!(check("http://[::1/"), Err(UrlPolicyViolation::Malformed(_))));
+    }
+
+    #[test]
+    fn test_hosts_ports_and_schemes() {
+        assert!(check("https://hooks.example.com/a2a?x=1").is_ok());
+        assert!(check("HTTP://Example.COM:80").is_ok());
+        assert_eq!(check("http://localhost:8500/"), Err(UrlPolicyViolation::Port(8500)));
+        assert_eq!(check("http://localhost/"), Err(UrlPolicyViolation::InternalHost("localhost".to_string())));
+        assert_eq!(check("http://api.LOCALHOST./"), Err(UrlPolicyViolation::InternalHost("api.localhost".to_string())));
+        assert_eq!(check("https://example.com:8443/"), Err(UrlPolicyViolation::Port(8443)));
+        assert!(UrlPolicy::new().allow_port(8443).check("https://example.com:8443/").is_ok());
+        assert_eq!(check("ftp://example.com/"), Err(UrlPolicyViolation::Scheme("ftp".to_string())));
+        assert!(matches!(check("example.com/hook"), Err(UrlPolicyViolation::Malformed(_))));
+        assert!(matches!(check("https:///hook"), Err(UrlPolicyViolation::Malformed(_))));
+        assert!(matches!(check("https://exa mple.com/"), Err(UrlPolicyViolation::Malformed(_))));
+        assert!(matches!(check("https://%6c%6fcalhost/"), Err(UrlPolicyViolation::Malformed(_))));
+        assert!(matches!(check("https://example.com:99999/"), Err(UrlPolicyViolation::Malformed(_))));
+    }
+
+    #[test]
+    fn test_allowlisted_internal_host() {
+        let policy = UrlPolicy::new().allow_host("Hooks.Internal").allow_host("10.0.0.5").allow_host("[fd00::5]");
+        assert!(policy.check("http://hooks.internal/a2a").is_ok());
+        assert!(policy.check("http://10.0.0.5/a2a").is_ok());
+        assert!(policy.check("http://167772165/a2a").is_ok());
+        assert!(policy.check("http://[fd00::5]/a2a").is_ok());
+        assert_eq!(policy.check("http://10.0.0.6/a2a"), internal("10.0.0.6"));
+        assert_eq!(policy.check("http://hooks.internal:9000/"), Err(UrlPolicyViolation::Port(9000)));
+
+        assert!(policy.check_addresses("hooks.internal", &["10.0.0.9".parse().unwrap()]).is_ok());
+        assert_eq!(
+            policy.check_addresses("rebind.example", &["93.184.216.34".parse().unwrap(), "127.0.0.1".parse().unwrap()]),
+            Err(UrlPolicyViolation::ResolvesToInternal {
+                host: "rebind.example".to_string(),
+                address: "127.0.0.1".parse().unwrap(),
+            })
+        );
+        assert!(policy.check_addresses("example.com", &["93.184.216.34".parse().unwrap()]).is_ok());
+    }
+
+    #[test]
+    fn test_configs_and_errors() {
+        let config = PushNotificationConfig {
+            url: "http://169.254.169.254/".to_string(),
+            authentication: None,
+            id: None,
+            token: None,
+        };
+        let violation = config.validate_with(&UrlPolicy::new()).unwrap_err();
+        let error = A2AError::from(violation);
+        assert!(matches!(&error, A2AError::InvalidParams(e) if e.code == -32602
+            && e.message == "Push notification URL rejected: URL host 169.254.169.254 is an internal address"));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_resolve_literals() {
+        let policy = UrlPolicy::new();
+        assert_eq!(
+            policy.resolve("https://93.184.216.34/").await.unwrap(),
+            vec!["93.184.216.34:443".parse().unwrap()]
+        );
+        assert_eq!(policy.resolve("http://[::1]/").await, internal("::1").map(|_| vec![]));
+    }
+
+    #[test]
+    fn test_payload_round_trips_and_kinds() {
+        let mut task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+        task.status.state = crate::TaskState::Completed;
+
+        let full = PushNotificationPayload::for_task(&task, PushDelivery::default());
+        let json = full.to_json();
+        assert!(json.contains(r#""kind":"task""#));
+        assert_eq!(PushNotificationPayload::from_json(json.as_bytes()).unwrap(), full);
+
+        let update = PushNotificationPayload::for_task(&task, PushDelivery::StatusUpdate);
+        let json = update.to_json();
+        assert!(json.contains(r#""kind":"status-update""#) && json.contains(r#""final":true"#));
+        assert_eq!(PushNotificationPayload::from_json(json.as_bytes()).unwrap(), update);
+        assert_eq!(update.task_id(), "task-1");
+
+        let unknown = br#"{"kind": "artifact-update", "taskId": "task-1", "contextId": "ctx-1"}"#;
+        let error = PushNotificationPayload::from_json(unknown).unwrap_err();
+        assert!(error.to_string().contains("unknown push notification kind 'artifact-update'"));
+        assert!(PushNotificationPayload::from_json(br#"{"id": "task-1"}"#).is_err());
+        // A body of the right kind but the wrong shape is not the other kind
+        assert!(PushNotificationPayload::from_json(br#"{"kind": "status-update", "id": "task-1"}"#).is_err());
+    }
+}
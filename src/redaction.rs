@@ -0,0 +1,393 @@
+//! Redacting requests, responses and events for logs.
+//!
+//! Logs of A2A traffic are useful to operators, but must not contain what
+//! users wrote, the files they sent or the credentials of push notification
+//! configs. The functions here serialize a value to JSON and then:
+//!
+//! - replace the text of text parts, and the data of data parts, as the
+//!   [`RedactionPolicy`] says: hashed, truncated or dropped,
+//! - reduce files to `{name, mimeType, size}`, or `{name, mimeType, uri}`
+//!   without the URI's query and fragment,
+//! - remove credential fields such as `token`, `credentials` and `password`
+//!   wherever they occur,
+//! - keep only allowlisted keys of `metadata` objects.
+//!
+//! IDs, methods, states and the rest of the structure are left alone, so a
+//! redacted log still shows what happened to which task.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::hash::fnv1a;
+use crate::{A2ARequest, StreamingMessageResult};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Field names removed wherever they occur, compared case-insensitively.
+pub const CREDENTIAL_FIELDS: &[&str] = &[
+    "token",
+    "credentials",
+    "password",
+    "secret",
+    "clientSecret",
+    "client_secret",
+    "accessToken",
+    "access_token",
+    "refreshToken",
+    "refresh_token",
+    "apiKey",
+    "api_key",
+    "authorization",
+];
+
+/// What happens to user-written content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContentRedaction {
+    /// Replace content with its length and a 64-bit FNV-1a hash, so that
+    /// repeated content can be recognized across log lines. The hash is not
+    /// keyed; short or guessable content can be recovered by trying
+    /// candidates, so use [`ContentRedaction::Drop`] where that matters.
+    #[default]
+    Hash,
+    /// Keep the first characters.
+    Truncate(usize),
+    /// Replace content with its length only.
+    Drop,
+}
+
+/// How values are redacted.
+///
+/// The default hashes content and removes all metadata.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RedactionPolicy {
+    /// What happens to the text of text parts and the data of data parts.
+    pub content: ContentRedaction,
+    /// The `metadata` keys that are kept.
+    pub metadata_allowlist: Vec<String>,
+}
+
+/// Redact a request for logging.
+///
+/// # Arguments
+///
+/// * `request` - The request.
+/// * `policy` - How to redact it.
+///
+/// # Returns
+///
+/// The redacted JSON form of the request.
+pub fn redact_request(request: &A2ARequest, policy: &RedactionPolicy) -> Value {
+    redact(request, policy)
+}
+
+/// Redact a streaming event for logging.
+///
+/// # Arguments
+///
+/// * `event` - The event.
+/// * `policy` - How to redact it.
+///
+/// # Returns
+///
+/// The redacted JSON form of the event.
+pub fn redact_event(event: &StreamingMessageResult, policy: &RedactionPolicy) -> Value {
+    redact(event, policy)
+}
+
+/// Redact a response, or any other protocol value, for logging.
+///
+/// # Arguments
+///
+/// * `response` - The response, e.g. a `SendMessageResponse`.
+/// * `policy` - How to redact it.
+///
+/// # Returns
+///
+/// The redacted JSON form of the response, or `null` if it cannot be
+/// serialized.
+pub fn redact_response<T: Serialize>(response: &T, policy: &RedactionPolicy) -> Value {
+    redact(response, policy)
+}
+
+fn redact<T: Serialize + ?Sized>(value: &T, policy: &RedactionPolicy) -> Value {
+    let mut value = serde_json::to_value(value).unwrap_or(Value::Null);
+    redact_value(&mut value, policy);
+    value
+}
+
+/// Redact a JSON value in place.
+///
+/// # Arguments
+///
+/// * `value` - The JSON form of a protocol value.
+/// * `policy` - How to redact it.
+pub fn redact_value(value: &mut Value, policy: &RedactionPolicy) {
+    match value {
+        Value::Object(object) => redact_object(object, policy),
+        Value::Array(values) => values.iter_mut().for_each(|value| redact_value(value, policy)),
+        _ => {}
+    }
+}
+
+/// Redact the quoted values in a message for logging, such as the detail
+/// of a deserialization error.
+///
+/// Deserialization errors quote the value they reject, e.g. `invalid type:
+/// string "078-05-1120", expected u64`. Each double-quoted span is replaced
+/// as the policy's content redaction says; the rest of the message,
+/// including the field names serde quotes in backticks, is kept.
+///
+/// # Arguments
+///
+/// * `message` - The message.
+/// * `policy` - How to redact the quoted values.
+///
+/// # Returns
+///
+/// The message with its quoted values redacted.
+pub fn redact_message(message: &str, policy: &RedactionPolicy) -> String {
+    let mut redacted = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find('"') {
+        redacted.push_str(&rest[..=start]);
+        rest = &rest[start + 1..];
+        // Quotes inside the value are escaped
+        let mut escaped = false;
+        let end = rest.char_indices().find_map(|(i, c)| match c {
+            '\\' if !escaped => {
+                escaped = true;
+                None
+            }
+            '"' if !escaped => Some(i),
+            _ => {
+                escaped = false;
+                None
+            }
+        });
+        let end = end.unwrap_or(rest.len());
+        redacted.push_str(&redact_content(&rest[..end], policy.content));
+        rest = &rest[end..];
+        if let Some(after) = rest.strip_prefix('"') {
+            redacted.push('"');
+            rest = after;
+        }
+    }
+    redacted.push_str(rest);
+    redacted
+}
+
+fn redact_object(object: &mut Map<String, Value>, policy: &RedactionPolicy) {
+    object.retain(|key, _| !CREDENTIAL_FIELDS.iter().any(|field| field.eq_ignore_ascii_case(key)));
+
+    match object.get("kind").and_then(Value::as_str) {
+        Some("text") => {
+            if let Some(Value::String(text)) = object.get_mut("text") {
+                *text = redact_content(text, policy.content);
+            }
+        }
+        Some("data") => {
+            if let Some(data) = object.get_mut("data") {
+                let json = serde_json::to_string(data).unwrap_or_default();
+                *data = Value::String(redact_content(&json, policy.content));
+            }
+        }
+        Some("file") => {
+            if let Some(Value::Object(file)) = object.get_mut("file") {
+                redact_file(file);
+            }
+        }
+        _ => {}
+    }
+
+    for (key, value) in object.iter_mut() {
+        match (key.as_str(), value) {
+            ("metadata", Value::Object(metadata)) => {
+                metadata.retain(|key, _| policy.metadata_allowlist.iter().any(|allowed| allowed == key));
+                metadata.values_mut().for_each(|value| redact_value(value, policy));
+            }
+            ("text" | "file", _) => {}
+            (_, value) => redact_value(value, policy),
+        }
+    }
+}
+
+fn redact_file(file: &mut Map<String, Value>) {
+    let mut reduced = Map::new();
+    for key in ["name", "mimeType"] {
+        if let Some(value) = file.remove(key) {
+            reduced.insert(key.to_string(), value);
+        }
+    }
+    if let Some(Value::String(bytes)) = file.get("bytes") {
+        // Base64 encodes 3 bytes in 4 characters
+        let padding = bytes.bytes().rev().take_while(|b| *b == b'=').count();
+        let size = (bytes.len() / 4 * 3).saturating_sub(padding);
+        reduced.insert("size".to_string(), Value::from(size));
+    }
+    if let Some(Value::String(uri)) = file.get("uri") {
+        let uri = uri.split(['?', '#']).next().unwrap_or_default();
+        reduced.insert("uri".to_string(), Value::from(uri));
+    }
+    *file = reduced;
+}
+
+fn redact_content(content: &str, redaction: ContentRedaction) -> String {
+    let chars = content.chars().count();
+    match redaction {
+        ContentRedaction::Hash => format!("[{} chars, fnv1a {:016x}]", chars, fnv1a(content.as_bytes())),
+        ContentRedaction::Truncate(max) if chars <= max => content.to_string(),
+        ContentRedaction::Truncate(max) => {
+            let kept: String = content.chars().take(max).collect();
+            format!("{}… [{} chars]", kept, chars)
+        }
+        ContentRedaction::Drop => format!("[{} chars]", chars),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SECRET_TEXT: &str = "My social security number is 078-05-1120";
+
+    fn request(method: &str, params: Value) -> A2ARequest {
+        serde_json::from_value(json!({"jsonrpc": "2.0", "id": "req-1", "method": method, "params": params})).unwrap()
+    }
+
+    fn send_request() -> A2ARequest {
+        request(
+            "message/send",
+            json!({
+                "message": {
+                    "kind": "message",
+                    "messageId": "msg-1",
+                    "role": "user",
+                    "taskId": "task-1",
+                    "contextId": "ctx-1",
+                    "parts": [
+                        {"kind": "text", "text": SECRET_TEXT},
+                        {"kind": "file", "file": {"name": "scan.pdf", "mimeType": "application/pdf", "bytes": "JVBERi0xLjQKc2VjcmV0"}},
+                        {"kind": "file", "file": {"uri": "https://files.example.com/a.png?sig=SIGNATURE", "mimeType": "image/png"}},
+                        {"kind": "data", "data": {"ssn": "078-05-1120"}}
+                    ],
+                    "metadata": {"traceId": "trace-9", "userEmail": "someone@example.com"}
+                },
+                "configuration": {
+                    "acceptedOutputModes": ["text/plain"],
+                    "pushNotificationConfig": {
+                        "url": "https://hooks.example.com/a2a",
+                        "token": "PUSH-TOKEN",
+                        "authentication": {"schemes": ["Bearer"], "credentials": "PUSH-CREDENTIALS"}
+                    }
+                }
+            }),
+        )
+    }
+
+    #[test]
+    fn test_redact_message() {
+        let error = serde_json::from_value::<u64>(json!(SECRET_TEXT)).unwrap_err().to_string();
+        assert!(error.contains(SECRET_TEXT));
+        let redacted = redact_message(&error, &RedactionPolicy::default());
+        assert!(!redacted.contains("078-05-1120"), "{}", redacted);
+        assert!(redacted.starts_with("invalid type: string \"[40 chars, fnv1a "), "{}", redacted);
+        assert!(redacted.ends_with("\", expected u64"), "{}", redacted);
+
+        // Escaped quotes stay inside the value, backticks are kept
+        let policy = RedactionPolicy {
+            content: ContentRedaction::Drop,
+            ..RedactionPolicy::default()
+        };
+        assert_eq!(
+            redact_message(r#"string "say \"hi\"" for `text`, and "unterminated"#, &policy),
+            r#"string "[10 chars]" for `text`, and "[12 chars]"#
+        );
+    }
+
+    #[test]
+    fn test_no_secret_survives() {
+        let policy = RedactionPolicy {
+            metadata_allowlist: vec!["traceId".to_string()],
+            ..RedactionPolicy::default()
+        };
+        let redacted = redact_request(&send_request(), &policy);
+        let logged = redacted.to_string();
+        for secret in [SECRET_TEXT, "078-05-1120", "JVBERi0xLjQKc2VjcmV0", "SIGNATURE", "PUSH-TOKEN", "PUSH-CREDENTIALS", "someone@example.com"] {
+            assert!(!logged.contains(secret), "{} in {}", secret, logged);
+        }
+
+        // The structure survives
+        assert_eq!(redacted["method"], "message/send");
+        assert_eq!(redacted["id"], "req-1");
+        let message = &redacted["params"]["message"];
+        assert_eq!(message["messageId"], "msg-1");
+        assert_eq!(message["taskId"], "task-1");
+        assert_eq!(message["metadata"], json!({"traceId": "trace-9"}));
+        assert_eq!(message["parts"][0]["text"], format!("[40 chars, fnv1a {:016x}]", fnv1a(SECRET_TEXT.as_bytes())));
+        assert_eq!(message["parts"][1]["file"], json!({"name": "scan.pdf", "mimeType": "application/pdf", "size": 15}));
+        assert_eq!(message["parts"][2]["file"], json!({"mimeType": "image/png", "uri": "https://files.example.com/a.png"}));
+        let push = &redacted["params"]["configuration"]["pushNotificationConfig"];
+        assert_eq!(push, &json!({"url": "https://hooks.example.com/a2a", "authentication": {"schemes": ["Bearer"]}}));
+
+        let redacted = redact_request(
+            &request("tasks/pushNotificationConfig/set", json!({
+                "taskId": "task-1",
+                "config": {"url": "https://hooks.example.com/a2a", "token": "PUSH-TOKEN"}
+            })),
+            &policy,
+        );
+        assert!(!redacted.to_string().contains("PUSH-TOKEN"));
+        assert_eq!(redacted["params"]["taskId"], "task-1");
+    }
+
+    #[test]
+    fn test_content_policies() {
+        let truncate = RedactionPolicy {
+            content: ContentRedaction::Truncate(9),
+            ..RedactionPolicy::default()
+        };
+        let redacted = redact_request(&send_request(), &truncate);
+        assert_eq!(redacted["params"]["message"]["parts"][0]["text"], "My social… [40 chars]");
+        assert_eq!(redacted["params"]["message"]["metadata"], json!({}));
+
+        let drop = RedactionPolicy {
+            content: ContentRedaction::Drop,
+            ..RedactionPolicy::default()
+        };
+        let redacted = redact_request(&send_request(), &drop);
+        assert_eq!(redacted["params"]["message"]["parts"][0]["text"], "[40 chars]");
+        assert_eq!(redacted["params"]["message"]["parts"][3]["data"], "[21 chars]");
+        assert_eq!(redact_content("short", ContentRedaction::Truncate(9)), "short");
+    }
+
+    #[test]
+    fn test_events_and_responses() {
+        let event: StreamingMessageResult = serde_json::from_value(json!({
+            "kind": "status-update",
+            "taskId": "task-1",
+            "contextId": "ctx-1",
+            "final": true,
+            "status": {
+                "state": "completed",
+                "message": {"kind": "message", "messageId": "m-2", "role": "agent", "parts": [{"kind": "text", "text": SECRET_TEXT}]}
+            }
+        }))
+        .unwrap();
+        let redacted = redact_event(&event, &RedactionPolicy::default());
+        assert!(!redacted.to_string().contains(SECRET_TEXT));
+        assert_eq!(redacted["status"]["state"], "completed");
+        assert_eq!(redacted["final"], true);
+        assert_eq!(redacted["taskId"], "task-1");
+
+        let mut task = crate::Task::minimal("task-1".to_string(), "ctx-1".to_string());
+        task.artifacts = Some(vec![crate::Artifact::minimal("a-1".to_string(), vec![SECRET_TEXT.into()])]);
+        let response = crate::GetTaskResponse {
+            id: "req-2".to_string(),
+            jsonrpc: "2.0".to_string(),
+            result: task,
+        };
+        let redacted = redact_response(&response, &RedactionPolicy::default());
+        assert!(!redacted.to_string().contains(SECRET_TEXT));
+        assert_eq!(redacted["result"]["artifacts"][0]["artifactId"], "a-1");
+    }
+}
@@ -0,0 +1,290 @@
+//! Resolving the tasks a message refers to.
+//!
+//! A message can cite earlier tasks in `referenceTaskIds`, and the messages
+//! of those tasks can cite further tasks. [`Message::referenced_tasks`]
+//! resolves the direct references against any [`TaskLookup`], and
+//! [`ReferenceGraph`] follows them transitively, for example to put the
+//! earlier work into a prompt. Cycles are followed only once, and depth and
+//! size limits keep a long chain from pulling in the whole store.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::ids::TaskId;
+use crate::{Message, Task, TaskNotFoundError};
+use alloc::collections::BTreeSet;
+
+/// Finds tasks by ID without I/O.
+pub trait TaskLookup {
+    /// Get a task by ID, if known.
+    fn lookup_task(&self, id: &str) -> Option<&Task>;
+}
+
+impl TaskLookup for crate::Map<String, Task> {
+    fn lookup_task(&self, id: &str) -> Option<&Task> {
+        self.get(id)
+    }
+}
+
+impl TaskLookup for crate::Map<TaskId, Task> {
+    fn lookup_task(&self, id: &str) -> Option<&Task> {
+        self.get(id)
+    }
+}
+
+#[cfg(feature = "std")]
+impl TaskLookup for std::collections::HashMap<String, Task> {
+    fn lookup_task(&self, id: &str) -> Option<&Task> {
+        self.get(id)
+    }
+}
+
+#[cfg(feature = "std")]
+impl TaskLookup for std::collections::HashMap<TaskId, Task> {
+    fn lookup_task(&self, id: &str) -> Option<&Task> {
+        self.get(id)
+    }
+}
+
+impl TaskLookup for [Task] {
+    fn lookup_task(&self, id: &str) -> Option<&Task> {
+        self.iter().find(|task| task.id == id)
+    }
+}
+
+impl TaskLookup for Vec<Task> {
+    fn lookup_task(&self, id: &str) -> Option<&Task> {
+        self.as_slice().lookup_task(id)
+    }
+}
+
+fn not_found(id: &str) -> TaskNotFoundError {
+    TaskNotFoundError {
+        code: -32001,
+        message: format!("Task not found: {}", id),
+        data: None,
+    }
+}
+
+impl Message {
+    /// Resolve the tasks the message refers to.
+    ///
+    /// # Arguments
+    ///
+    /// * `lookup` - Where to find the tasks.
+    ///
+    /// # Returns
+    ///
+    /// One result per entry of `referenceTaskIds`, in order: the task, or a
+    /// `TaskNotFoundError` if the lookup does not know it.
+    pub fn referenced_tasks<'a, L: TaskLookup + ?Sized>(
+        &self,
+        lookup: &'a L,
+    ) -> Vec<Result<&'a Task, TaskNotFoundError>> {
+        self.reference_task_ids
+            .iter()
+            .flatten()
+            .map(|id| lookup.lookup_task(id).ok_or_else(|| not_found(id)))
+            .collect()
+    }
+}
+
+/// A task reached by following references, and how far from the message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferencedTask<'a> {
+    /// The task.
+    pub task: &'a Task,
+    /// 1 for tasks the message cites, 2 for tasks those tasks cite, and so on.
+    pub depth: usize,
+}
+
+/// The tasks a message refers to, directly and through the messages of the
+/// tasks it refers to.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReferenceGraph<'a> {
+    /// The tasks found, nearest first, each once.
+    pub tasks: Vec<ReferencedTask<'a>>,
+    /// Referenced task IDs the lookup does not know, each once.
+    pub missing: Vec<String>,
+    /// Whether references were left unfollowed because of the limits.
+    pub truncated: bool,
+}
+
+impl<'a> ReferenceGraph<'a> {
+    /// Start configuring a traversal.
+    pub fn builder() -> ReferenceGraphBuilder {
+        ReferenceGraphBuilder::default()
+    }
+
+    /// The tasks found, nearest first.
+    pub fn iter(&self) -> impl Iterator<Item = &'a Task> + '_ {
+        self.tasks.iter().map(|referenced| referenced.task)
+    }
+}
+
+/// Configures how far a [`ReferenceGraph`] follows references.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReferenceGraphBuilder {
+    max_depth: usize,
+    max_tasks: usize,
+}
+
+impl Default for ReferenceGraphBuilder {
+    fn default() -> Self {
+        Self {
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+            max_tasks: Self::DEFAULT_MAX_TASKS,
+        }
+    }
+}
+
+impl ReferenceGraphBuilder {
+    /// How many references deep the traversal goes by default.
+    pub const DEFAULT_MAX_DEPTH: usize = 4;
+    /// How many tasks the traversal collects by default.
+    pub const DEFAULT_MAX_TASKS: usize = 32;
+
+    /// Set how many references deep to go; 1 resolves only the message's
+    /// own references.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Set how many tasks to collect at most.
+    pub fn max_tasks(mut self, max_tasks: usize) -> Self {
+        self.max_tasks = max_tasks;
+        self
+    }
+
+    /// Follow the references of a message.
+    ///
+    /// A task's references are those of the messages in its history and
+    /// its status message. The task the message itself belongs to is never
+    /// included.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message whose references to follow.
+    /// * `lookup` - Where to find the tasks.
+    ///
+    /// # Returns
+    ///
+    /// The tasks reached, breadth first.
+    pub fn build<'a, L: TaskLookup + ?Sized>(&self, message: &Message, lookup: &'a L) -> ReferenceGraph<'a> {
+        let mut graph = ReferenceGraph::default();
+        let mut seen: BTreeSet<String> = message.task_id.iter().cloned().collect();
+        let mut frontier: Vec<String> = message.reference_task_ids.clone().unwrap_or_default();
+
+        for depth in 1.. {
+            if frontier.is_empty() {
+                break;
+            }
+            if depth > self.max_depth {
+                graph.truncated = frontier.iter().any(|id| !seen.contains(id));
+                break;
+            }
+
+            let mut next = Vec::new();
+            for id in frontier {
+                if !seen.insert(id.clone()) {
+                    continue;
+                }
+                let Some(task) = lookup.lookup_task(&id) else {
+                    graph.missing.push(id);
+                    continue;
+                };
+                if graph.tasks.len() == self.max_tasks {
+                    graph.truncated = true;
+                    return graph;
+                }
+                graph.tasks.push(ReferencedTask { task, depth });
+                next.extend(task_references(task).cloned());
+            }
+            frontier = next;
+        }
+        graph
+    }
+}
+
+/// The task IDs cited by the messages of a task.
+fn task_references(task: &Task) -> impl Iterator<Item = &String> {
+    task.history
+        .iter()
+        .flatten()
+        .chain(task.status.message.as_ref())
+        .flat_map(|message| message.reference_task_ids.iter().flatten())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Map;
+
+    fn task(id: &str, references: &[&str]) -> Task {
+        let mut task = Task::minimal(id.to_string(), "ctx-1".to_string());
+        let mut message = Message::user(format!("{}-msg", id), [format!("continuing from {:?}", references)]);
+        message.reference_task_ids = Some(references.iter().map(|id| id.to_string()).collect());
+        task.history = Some(vec![message]);
+        task
+    }
+
+    fn citing(references: &[&str]) -> Message {
+        let mut message = Message::user("msg-new".to_string(), ["What did we find?"]);
+        message.reference_task_ids = Some(references.iter().map(|id| id.to_string()).collect());
+        message
+    }
+
+    fn ids<'a>(graph: &ReferenceGraph<'a>) -> Vec<(&'a str, usize)> {
+        graph.tasks.iter().map(|r| (r.task.id.as_str(), r.depth)).collect()
+    }
+
+    #[test]
+    fn test_direct_references() {
+        let tasks: Map<String, Task> =
+            [task("task-a", &[]), task("task-b", &[])].into_iter().map(|t| (t.id.clone(), t)).collect();
+        let resolved = citing(&["task-b", "task-x", "task-a"]).referenced_tasks(&tasks);
+        assert_eq!(resolved.len(), 3);
+        assert_eq!(resolved[0].as_ref().unwrap().id, "task-b");
+        assert_eq!(resolved[1].as_ref().unwrap_err().message, "Task not found: task-x");
+        assert_eq!(resolved[1].as_ref().unwrap_err().code, -32001);
+        assert_eq!(resolved[2].as_ref().unwrap().id, "task-a");
+
+        assert!(Message::user("m".to_string(), ["hi"]).referenced_tasks(&tasks).is_empty());
+        let list = vec![task("task-a", &[])];
+        assert!(citing(&["task-a"]).referenced_tasks(list.as_slice())[0].is_ok());
+    }
+
+    #[test]
+    fn test_chain_of_three_tasks() {
+        let tasks = vec![task("task-1", &["task-2"]), task("task-2", &["task-3"]), task("task-3", &["task-gone"])];
+        let message = citing(&["task-1"]);
+
+        let graph = ReferenceGraph::builder().build(&message, &tasks);
+        assert_eq!(ids(&graph), [("task-1", 1), ("task-2", 2), ("task-3", 3)]);
+        assert_eq!(graph.missing, ["task-gone"]);
+        assert!(!graph.truncated);
+
+        let graph = ReferenceGraph::builder().max_depth(2).build(&message, &tasks);
+        assert_eq!(ids(&graph), [("task-1", 1), ("task-2", 2)]);
+        assert!(graph.truncated);
+
+        let graph = ReferenceGraph::builder().max_tasks(1).build(&message, &tasks);
+        assert_eq!(graph.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(), ["task-1"]);
+        assert!(graph.truncated);
+    }
+
+    #[test]
+    fn test_cycles_terminate() {
+        let tasks = vec![task("task-1", &["task-2"]), task("task-2", &["task-1", "task-2", "task-3"]), task("task-3", &["task-1"])];
+        let graph = ReferenceGraph::builder().max_depth(100).build(&citing(&["task-1", "task-3"]), &tasks);
+        assert_eq!(ids(&graph), [("task-1", 1), ("task-3", 1), ("task-2", 2)]);
+        assert!(graph.missing.is_empty());
+        assert!(!graph.truncated);
+
+        // A message never pulls in its own task
+        let mut message = citing(&["task-2"]);
+        message.task_id = Some("task-1".to_string());
+        let graph = ReferenceGraph::builder().build(&message, &tasks);
+        assert_eq!(ids(&graph), [("task-2", 1), ("task-3", 2)]);
+    }
+}
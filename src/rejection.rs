@@ -0,0 +1,186 @@
+//! Rejecting tasks with a structured reason.
+//!
+//! An agent that will not do the work it was asked for, because the
+//! request is unsupported or breaks a policy, moves the task from
+//! `submitted` or `auth-required` to the terminal `rejected` state. The
+//! status message explains why twice: as text for people, and as a data
+//! part holding a [`RejectionReason`] under [`REJECTION_REASON_KEY`] for
+//! programs. Servers reject with
+//! [`TaskManager::reject`](crate::manager::TaskManager::reject), and clients
+//! read the reason back with [`Task::rejection_reason`].
+//!
+//! ```
+//! use a2a_rs::rejection::RejectionReason;
+//! use a2a_rs::{Message, Task, TaskState};
+//!
+//! let reason = RejectionReason::new(RejectionReason::POLICY_VIOLATION, "Medical advice is out of scope");
+//! let mut task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+//! task.status.state = TaskState::Rejected;
+//! task.status.message = Some(Message::agent("msg-1".to_string(), [reason.message.clone()]));
+//! task.status.message.as_mut().unwrap().parts.push(reason.to_part());
+//!
+//! assert_eq!(task.rejection_reason(), Some(reason));
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::agent::media_types_match;
+use crate::{AgentCard, DataPart, MessageSendConfiguration, Part, Task, TaskState};
+use serde::{Deserialize, Serialize};
+
+/// The key of the status message data part holding the [`RejectionReason`].
+pub const REJECTION_REASON_KEY: &str = "rejectionReason";
+
+/// Why an agent rejected a task.
+///
+/// Serializes as `{"code": ..., "message": ...}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RejectionReason {
+    /// A machine-readable code, such as [`RejectionReason::UNSUPPORTED_REQUEST`].
+    pub code: String,
+    /// A description for people.
+    pub message: String,
+}
+
+impl RejectionReason {
+    /// The agent cannot do what was asked.
+    pub const UNSUPPORTED_REQUEST: &'static str = "unsupported-request";
+    /// The agent may not do what was asked.
+    pub const POLICY_VIOLATION: &'static str = "policy-violation";
+    /// The client accepts none of the agent's output modes; the rejected
+    /// counterpart of a `ContentTypeNotSupported` (-32005) error.
+    pub const CONTENT_TYPE_NOT_SUPPORTED: &'static str = "content-type-not-supported";
+
+    /// Create a rejection reason.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - A machine-readable code; agents may define their own.
+    /// * `message` - A description for people.
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Check the output modes a client accepts against an agent's card.
+    ///
+    /// The agent can answer in its default output modes and in the output
+    /// modes of its skills; wildcards such as `text/*` match on either
+    /// side.
+    ///
+    /// # Arguments
+    ///
+    /// * `card` - The card of the agent.
+    /// * `configuration` - The configuration of the `message/send` or
+    ///   `message/stream` request.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the client accepts one of the agent's output modes or
+    /// lists none, else a [`RejectionReason::CONTENT_TYPE_NOT_SUPPORTED`]
+    /// reason.
+    pub fn for_output_modes(card: &AgentCard, configuration: Option<&MessageSendConfiguration>) -> Option<Self> {
+        let accepted = configuration?.accepted_output_modes.as_deref().filter(|modes| !modes.is_empty())?;
+        let mut produced =
+            card.default_output_modes.iter().chain(card.skills.iter().flat_map(|skill| card.output_modes_for(skill)));
+        if produced.any(|mode| accepted.iter().any(|accepted| media_types_match(mode, accepted))) {
+            return None;
+        }
+        Some(Self::new(
+            Self::CONTENT_TYPE_NOT_SUPPORTED,
+            format!("None of the accepted output modes is supported: {}", accepted.join(", ")),
+        ))
+    }
+
+    /// The status message data part carrying the reason.
+    pub fn to_part(&self) -> Part {
+        Part::from(DataPart::from(serde_json::json!({ REJECTION_REASON_KEY: self })))
+    }
+}
+
+impl Task {
+    /// Why the agent rejected the task.
+    ///
+    /// # Returns
+    ///
+    /// The reason from the first status message data part holding one, or
+    /// `None` if the task is not rejected or the agent gave no structured
+    /// reason.
+    pub fn rejection_reason(&self) -> Option<RejectionReason> {
+        if self.status.state != TaskState::Rejected {
+            return None;
+        }
+        self.status.message.as_ref()?.parts.iter().find_map(|part| match part {
+            Part::Data(part) => serde_json::from_value(part.data.get(REJECTION_REASON_KEY)?.clone()).ok(),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AgentCapabilities, AgentSkill, Message};
+
+    fn card() -> AgentCard {
+        let mut skill = AgentSkill::minimal("chart".to_string(), "Draws charts".to_string());
+        skill.output_modes = Some(vec!["image/png".to_string()]);
+        AgentCard::new(
+            "Agent".to_string(),
+            "An agent".to_string(),
+            "1.0.0".to_string(),
+            "https://example.com/agent".to_string(),
+            AgentCapabilities::default(),
+            vec!["text/plain".to_string()],
+            vec!["text/plain".to_string()],
+            vec![skill],
+        )
+    }
+
+    fn accepting(modes: &[&str]) -> MessageSendConfiguration {
+        MessageSendConfiguration {
+            accepted_output_modes: Some(modes.iter().map(|mode| mode.to_string()).collect()),
+            ..MessageSendConfiguration::default()
+        }
+    }
+
+    #[test]
+    fn test_output_mode_negotiation() {
+        let card = card();
+        assert_eq!(RejectionReason::for_output_modes(&card, None), None);
+        assert_eq!(RejectionReason::for_output_modes(&card, Some(&accepting(&[]))), None);
+        assert_eq!(RejectionReason::for_output_modes(&card, Some(&accepting(&["text/*"]))), None);
+        assert_eq!(RejectionReason::for_output_modes(&card, Some(&accepting(&["video/mp4", "image/png"]))), None);
+
+        let reason = RejectionReason::for_output_modes(&card, Some(&accepting(&["video/mp4", "audio/ogg"]))).unwrap();
+        assert_eq!(reason.code, RejectionReason::CONTENT_TYPE_NOT_SUPPORTED);
+        assert_eq!(reason.message, "None of the accepted output modes is supported: video/mp4, audio/ogg");
+    }
+
+    #[test]
+    fn test_reason_only_read_from_rejected_tasks() {
+        let reason = RejectionReason::new(RejectionReason::UNSUPPORTED_REQUEST, "No video editing");
+        assert_eq!(
+            serde_json::to_value(reason.to_part()).unwrap(),
+            serde_json::json!({"kind": "data", "data": {"rejectionReason": {
+                "code": "unsupported-request",
+                "message": "No video editing"
+            }}})
+        );
+
+        let mut task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+        task.status.message = Some(Message::agent("msg-1".to_string(), [reason.to_part()]));
+        assert_eq!(task.rejection_reason(), None);
+        task.status.state = TaskState::Rejected;
+        assert_eq!(task.rejection_reason(), Some(reason));
+
+        // Malformed reasons are skipped
+        task.status.message = Some(Message::agent("msg-1".to_string(), [
+            Part::from(DataPart::from(serde_json::json!({"rejectionReason": "no"}))),
+            Part::from("Rejected"),
+        ]));
+        assert_eq!(task.rejection_reason(), None);
+    }
+}
@@ -0,0 +1,303 @@
+//! Checks that an agent's responses make sense for the request.
+//!
+//! A response can be well-formed JSON-RPC and still be wrong: a `tasks/get`
+//! answered with a message, a stream that switches to another task, or a
+//! final status update that leaves the task working. [`ResponseValidator`]
+//! detects these and reports them as `-32006` [`A2AError::InvalidAgentResponse`]
+//! errors whose `data` names the check with the expected and actual values.
+//! [`crate::client::ClientConversation`] and [`crate::tracker::RequestTracker`]
+//! run it on every result; the individual checks are public for other clients.
+
+use crate::{A2AError, InvalidAgentResponseError, JsonRpcId, RequestMethod, TaskState};
+use serde_json::{Value, json};
+
+/// How a response's `id` must match the request's.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdMatching {
+    /// The ids must be equal, including their JSON type.
+    #[default]
+    Strict,
+    /// A number also matches a string of the same digits, e.g. `7` and
+    /// `"7"`, for servers that echo ids as strings.
+    Lenient,
+    /// Any id is accepted, for servers known not to echo the id.
+    Ignore,
+}
+
+impl IdMatching {
+    /// Check if a response id answers a request id.
+    pub fn matches(self, expected: &JsonRpcId, actual: &Value) -> bool {
+        match (self, expected, actual) {
+            (IdMatching::Ignore, _, _) => true,
+            (_, JsonRpcId::String(expected), Value::String(actual)) => expected == actual,
+            (_, JsonRpcId::Integer(expected), Value::Number(actual)) => actual.as_i64() == Some(*expected),
+            (_, JsonRpcId::Null, Value::Null) => true,
+            (IdMatching::Lenient, JsonRpcId::Integer(expected), Value::String(actual)) => {
+                actual.parse::<i64>().ok() == Some(*expected)
+            }
+            (IdMatching::Lenient, JsonRpcId::String(expected), Value::Number(actual)) => {
+                actual.as_i64().is_some_and(|actual| expected.parse::<i64>().ok() == Some(actual))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Validates the results of one request.
+///
+/// Streaming requests receive many results; the validator remembers the
+/// task the first one concerns and requires the rest to agree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseValidator {
+    method: RequestMethod,
+    task_id: Option<String>,
+}
+
+impl ResponseValidator {
+    /// Create a validator for the results of a request.
+    pub fn new(method: RequestMethod) -> Self {
+        Self { method, task_id: None }
+    }
+
+    /// Create a validator for a request about a known task, such as
+    /// `tasks/get` or a message continuing the task.
+    pub fn for_task(method: RequestMethod, task_id: impl Into<String>) -> Self {
+        Self {
+            method,
+            task_id: Some(task_id.into()),
+        }
+    }
+
+    /// The method of the request.
+    pub fn method(&self) -> RequestMethod {
+        self.method
+    }
+
+    /// The task the results concern, once known.
+    pub fn task_id(&self) -> Option<&str> {
+        self.task_id.as_deref()
+    }
+
+    /// Run every applicable check on the next result.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The `result` member of the response.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the result is consistent, otherwise an
+    /// `InvalidAgentResponse` error describing the first failed check.
+    pub fn validate(&mut self, result: &Value) -> Result<(), A2AError> {
+        Self::check_kind(self.method, result)?;
+
+        let task_id = match result.get("kind").and_then(Value::as_str) {
+            Some("task") => result.get("id"),
+            Some("status-update" | "artifact-update") => result.get("taskId"),
+            _ => None,
+        };
+        if let Some(actual) = task_id.and_then(Value::as_str) {
+            match &self.task_id {
+                Some(expected) => Self::check_task_id(expected, actual)?,
+                None => self.task_id = Some(actual.to_string()),
+            }
+        }
+
+        if result.get("kind").and_then(Value::as_str) == Some("status-update")
+            && result.get("final").and_then(Value::as_bool) == Some(true)
+            && let Some(state) = result.pointer("/status/state")
+            && let Ok(state) = serde_json::from_value::<TaskState>(state.clone())
+        {
+            Self::check_final_state(&state)?;
+        }
+
+        Ok(())
+    }
+
+    /// Check the `kind` of a result against what the method answers with.
+    ///
+    /// Methods whose results carry no `kind` always pass.
+    pub fn check_kind(method: RequestMethod, result: &Value) -> Result<(), A2AError> {
+        let expected: &[&str] = match method {
+            RequestMethod::MessageSend => &["task", "message"],
+            RequestMethod::MessageStream | RequestMethod::TasksResubscribe => {
+                &["task", "message", "status-update", "artifact-update"]
+            }
+            RequestMethod::TasksGet | RequestMethod::TasksCancel => &["task"],
+            RequestMethod::TasksPushNotificationConfigSet
+            | RequestMethod::TasksPushNotificationConfigGet
+            | RequestMethod::TasksPushNotificationConfigList
+            | RequestMethod::TasksPushNotificationConfigDelete => return Ok(()),
+            #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+            RequestMethod::TasksList => return Ok(()),
+        };
+
+        let actual = result.get("kind").and_then(Value::as_str);
+        match actual {
+            Some(kind) if expected.contains(&kind) => Ok(()),
+            _ => Err(mismatch(
+                "kind",
+                format!(
+                    "{} result has kind {}, expected {}",
+                    method,
+                    actual.map_or("none".to_string(), |kind| format!("\"{}\"", kind)),
+                    expected.iter().map(|kind| format!("\"{}\"", kind)).collect::<Vec<_>>().join(" or ")
+                ),
+                json!(expected),
+                json!(actual),
+            )),
+        }
+    }
+
+    /// Check that a response answers the request with the expected ID.
+    pub fn check_id(expected: &JsonRpcId, actual: &Value) -> Result<(), A2AError> {
+        Self::check_id_with(expected, actual, IdMatching::Strict)
+    }
+
+    /// Check that a response answers the request, comparing the IDs as
+    /// configured.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - The ID of the request.
+    /// * `actual` - The `id` member of the response, `null` if it has none.
+    /// * `matching` - How the IDs must match.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the IDs match, otherwise an `InvalidAgentResponse` error
+    /// with both IDs in `data`.
+    pub fn check_id_with(expected: &JsonRpcId, actual: &Value, matching: IdMatching) -> Result<(), A2AError> {
+        if matching.matches(expected, actual) {
+            return Ok(());
+        }
+        let expected = serde_json::to_value(expected).unwrap_or(Value::Null);
+        Err(mismatch(
+            "id",
+            format!("Response has id {} but the request id is {}", actual, expected),
+            expected,
+            actual.clone(),
+        ))
+    }
+
+    /// Check that a result concerns the expected task.
+    pub fn check_task_id(expected: &str, actual: &str) -> Result<(), A2AError> {
+        if expected == actual {
+            return Ok(());
+        }
+        Err(mismatch(
+            "taskId",
+            format!("Response is for task {} but the request concerns task {}", actual, expected),
+            json!(expected),
+            json!(actual),
+        ))
+    }
+
+    /// Check the state of a status update marked `final`.
+    ///
+    /// A stream may end in a terminal state, or pause in `input-required`
+    /// or `auth-required` until the client answers; ending it while the
+    /// task is still running is a violation.
+    pub fn check_final_state(state: &TaskState) -> Result<(), A2AError> {
+        if state.is_terminal() || matches!(state, TaskState::InputRequired | TaskState::AuthRequired) {
+            return Ok(());
+        }
+        let actual = serde_json::to_value(state).unwrap_or(Value::Null);
+        Err(mismatch(
+            "finalState",
+            format!("Final status update has non-terminal state {}", actual),
+            json!(["completed", "canceled", "failed", "rejected", "input-required", "auth-required"]),
+            actual,
+        ))
+    }
+}
+
+fn mismatch(check: &str, message: String, expected: Value, actual: Value) -> A2AError {
+    A2AError::InvalidAgentResponse(Box::new(InvalidAgentResponseError {
+        code: -32006,
+        message,
+        data: Some(json!({"check": check, "expected": expected, "actual": actual})),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(error: A2AError) -> Value {
+        match error {
+            A2AError::InvalidAgentResponse(error) => {
+                assert_eq!(error.code, -32006);
+                error.data.unwrap()
+            }
+            other => panic!("expected an invalid agent response, got {:?}", other),
+        }
+    }
+
+    fn status_update(task_id: &str, state: &str, is_final: bool) -> Value {
+        json!({
+            "kind": "status-update",
+            "taskId": task_id,
+            "contextId": "ctx-1",
+            "status": {"state": state},
+            "final": is_final
+        })
+    }
+
+    #[test]
+    fn test_id_matching() {
+        let seven = JsonRpcId::Integer(7);
+        assert!(ResponseValidator::check_id(&seven, &json!(7)).is_ok());
+        let error = data(ResponseValidator::check_id(&seven, &json!("7")).unwrap_err());
+        assert_eq!(error, json!({"check": "id", "expected": 7, "actual": "7"}));
+
+        assert!(IdMatching::Lenient.matches(&seven, &json!("7")));
+        assert!(IdMatching::Lenient.matches(&JsonRpcId::String("7".to_string()), &json!(7)));
+        assert!(!IdMatching::Lenient.matches(&seven, &json!("8")));
+        assert!(!IdMatching::Lenient.matches(&seven, &Value::Null));
+        assert!(IdMatching::Ignore.matches(&seven, &json!("req-1")));
+        let error = ResponseValidator::check_id_with(&seven, &json!(8), IdMatching::Lenient).unwrap_err();
+        assert_eq!(data(error)["actual"], 8);
+    }
+
+    #[test]
+    fn test_get_answered_with_a_message() {
+        let message = json!({"kind": "message", "messageId": "m-1", "role": "agent", "parts": []});
+        let error = ResponseValidator::new(RequestMethod::TasksGet).validate(&message).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid agent response: tasks/get result has kind \"message\", expected \"task\"");
+        assert_eq!(data(error), json!({"check": "kind", "expected": ["task"], "actual": "message"}));
+
+        let error = ResponseValidator::check_kind(RequestMethod::TasksCancel, &json!({"id": "t"})).unwrap_err();
+        assert_eq!(data(error)["actual"], Value::Null);
+        assert!(ResponseValidator::check_kind(RequestMethod::MessageSend, &message).is_ok());
+        assert!(ResponseValidator::check_kind(RequestMethod::TasksPushNotificationConfigDelete, &json!(true)).is_ok());
+    }
+
+    #[test]
+    fn test_stream_switching_tasks() {
+        let mut validator = ResponseValidator::new(RequestMethod::MessageStream);
+        let task = json!({"kind": "task", "id": "task-1", "contextId": "ctx-1", "status": {"state": "submitted"}});
+        validator.validate(&task).unwrap();
+        assert_eq!(validator.task_id(), Some("task-1"));
+        validator.validate(&status_update("task-1", "working", false)).unwrap();
+
+        let error = validator.validate(&status_update("task-2", "working", false)).unwrap_err();
+        assert_eq!(data(error), json!({"check": "taskId", "expected": "task-1", "actual": "task-2"}));
+
+        let mut validator = ResponseValidator::for_task(RequestMethod::TasksGet, "task-9");
+        assert!(validator.validate(&task).is_err());
+    }
+
+    #[test]
+    fn test_final_update_with_running_task() {
+        let mut validator = ResponseValidator::new(RequestMethod::MessageStream);
+        let error = validator.validate(&status_update("task-1", "working", true)).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid agent response: Final status update has non-terminal state \"working\"");
+        assert_eq!(data(error)["actual"], "working");
+
+        for state in ["completed", "failed", "input-required", "auth-required"] {
+            assert!(validator.validate(&status_update("task-1", state, true)).is_ok(), "{}", state);
+        }
+        assert!(validator.validate(&status_update("task-1", "working", false)).is_ok());
+    }
+}
@@ -0,0 +1,559 @@
+//! HTTP+JSON (REST) binding of the A2A methods.
+//!
+//! Besides JSON-RPC and gRPC, the specification describes a binding where
+//! each method maps to an HTTP method and path:
+//!
+//! | A2A method                            | HTTP                                                  |
+//! |---------------------------------------|-------------------------------------------------------|
+//! | `message/send`                        | `POST /v1/message:send`                               |
+//! | `message/stream`                      | `POST /v1/message:stream`                             |
+//! | `tasks/get`                           | `GET /v1/tasks/{id}?historyLength={n}`                |
+//! | `tasks/cancel`                        | `POST /v1/tasks/{id}:cancel`                          |
+//! | `tasks/resubscribe`                   | `POST /v1/tasks/{id}:subscribe`                       |
+//! | `tasks/pushNotificationConfig/set`    | `POST /v1/tasks/{id}/pushNotificationConfigs`         |
+//! | `tasks/pushNotificationConfig/get`    | `GET /v1/tasks/{id}/pushNotificationConfigs/{cid}`    |
+//! | `tasks/pushNotificationConfig/list`   | `GET /v1/tasks/{id}/pushNotificationConfigs`          |
+//! | `tasks/pushNotificationConfig/delete` | `DELETE /v1/tasks/{id}/pushNotificationConfigs/{cid}` |
+//! | `tasks/list`                          | `GET /v1/tasks?contextId={c}&status={s}&pageSize={n}` |
+//!
+//! `tasks/list` needs the `spec-0-3` or the `ext-methods` feature.
+//!
+//! [`RestRoute::parse`] turns an incoming HTTP request into the same
+//! [`A2ARequest`] that a JSON-RPC payload deserializes to, so one handler can
+//! serve both bindings. [`RestRoute::from_method`] goes the other way for
+//! clients. REST requests carry no JSON-RPC ID; parsed requests use an empty
+//! string ID.
+
+use crate::{A2AError, A2ARequest, InvalidParamsError, JSONParseError, MethodNotFoundError, RequestMethod};
+use serde_json::Value;
+
+/// HTTP methods used by the REST binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HttpMethod {
+    /// `GET`
+    Get,
+    /// `POST`
+    Post,
+    /// `DELETE`
+    Delete,
+}
+
+impl HttpMethod {
+    /// Convert the HTTP method to a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Delete => "DELETE",
+        }
+    }
+
+    /// Convert a string to an HTTP method, ignoring case.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_ascii_uppercase().as_str() {
+            "GET" => Some(HttpMethod::Get),
+            "POST" => Some(HttpMethod::Post),
+            "DELETE" => Some(HttpMethod::Delete),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// An A2A method call expressed as an HTTP request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestRoute {
+    /// The HTTP method.
+    pub method: HttpMethod,
+    /// The request path, with path segments percent-encoded.
+    pub path: String,
+    /// The query string without the leading `?`, if any.
+    pub query: Option<String>,
+    /// The JSON request body, if any.
+    pub body: Option<Value>,
+}
+
+impl RestRoute {
+    /// Map an A2A method call to its HTTP request.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The A2A method.
+    /// * `params` - The JSON-RPC parameters of the call.
+    ///
+    /// # Returns
+    ///
+    /// The HTTP request, or an `InvalidParams` error if a parameter needed
+    /// to build the path or body is missing.
+    pub fn from_method(method: RequestMethod, params: &Value) -> Result<Self, A2AError> {
+        let route = match method {
+            RequestMethod::MessageSend => Self::new(HttpMethod::Post, "/v1/message:send".to_string())
+                .with_body(params.clone()),
+            RequestMethod::MessageStream => Self::new(HttpMethod::Post, "/v1/message:stream".to_string())
+                .with_body(params.clone()),
+            RequestMethod::TasksGet => {
+                let mut route = Self::new(HttpMethod::Get, task_path(params)?);
+                if let Some(history_length) = params.get("historyLength").and_then(Value::as_u64) {
+                    route.query = Some(format!("historyLength={}", history_length));
+                }
+                route
+            }
+            RequestMethod::TasksCancel => Self::new(HttpMethod::Post, format!("{}:cancel", task_path(params)?)),
+            RequestMethod::TasksResubscribe => {
+                Self::new(HttpMethod::Post, format!("{}:subscribe", task_path(params)?))
+            }
+            RequestMethod::TasksPushNotificationConfigSet => {
+                let config = params.get("config").cloned().ok_or_else(|| missing_param("config"))?;
+                Self::new(HttpMethod::Post, configs_path(params)?).with_body(config)
+            }
+            RequestMethod::TasksPushNotificationConfigGet => Self::new(HttpMethod::Get, config_path(params)?),
+            RequestMethod::TasksPushNotificationConfigList => Self::new(HttpMethod::Get, configs_path(params)?),
+            RequestMethod::TasksPushNotificationConfigDelete => {
+                Self::new(HttpMethod::Delete, config_path(params)?)
+            }
+            #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+            RequestMethod::TasksList => {
+                let mut route = Self::new(HttpMethod::Get, "/v1/tasks".to_string());
+                route.query = list_query(params);
+                route
+            }
+        };
+        Ok(route)
+    }
+
+    /// Map a typed request to its HTTP request.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request.
+    ///
+    /// # Returns
+    ///
+    /// The HTTP request, or an error if the request cannot be expressed.
+    pub fn from_request(request: &A2ARequest) -> Result<Self, A2AError> {
+        Self::from_method(request.method(), &request.params()?)
+    }
+
+    /// Parse an HTTP request into a typed A2A request.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The HTTP method, e.g. `GET`.
+    /// * `path` - The request path, e.g. `/v1/tasks/123`.
+    /// * `query` - The query string without the leading `?`, if any.
+    /// * `body` - The request body, if any.
+    ///
+    /// # Returns
+    ///
+    /// The typed request. Unknown routes produce a `MethodNotFound` error,
+    /// malformed bodies a `JSONParse` error and bad parameters an
+    /// `InvalidParams` error.
+    pub fn parse(
+        method: &str,
+        path: &str,
+        query: Option<&str>,
+        body: Option<&str>,
+    ) -> Result<A2ARequest, A2AError> {
+        let http_method = HttpMethod::from_str(method).ok_or_else(|| no_route(method, path))?;
+        let segments: Vec<&str> = path
+            .strip_prefix("/v1/")
+            .ok_or_else(|| no_route(method, path))?
+            .trim_end_matches('/')
+            .split('/')
+            .collect();
+
+        let (a2a_method, params) = match (http_method, segments.as_slice()) {
+            (HttpMethod::Post, ["message:send"]) => (RequestMethod::MessageSend, parse_body(body)?),
+            (HttpMethod::Post, ["message:stream"]) => (RequestMethod::MessageStream, parse_body(body)?),
+            (HttpMethod::Get, ["tasks", task]) if !is_custom_method(task) => {
+                let mut params = serde_json::json!({"taskId": decode_segment(task)?});
+                if let Some(history_length) = parse_history_length(query)? {
+                    params["historyLength"] = history_length.into();
+                }
+                (RequestMethod::TasksGet, params)
+            }
+            (HttpMethod::Post, ["tasks", task]) => match task.rsplit_once(':') {
+                Some((task, "cancel")) => (
+                    RequestMethod::TasksCancel,
+                    serde_json::json!({"taskId": decode_segment(task)?}),
+                ),
+                Some((task, "subscribe")) => (
+                    RequestMethod::TasksResubscribe,
+                    serde_json::json!({"taskId": decode_segment(task)?}),
+                ),
+                _ => return Err(no_route(method, path)),
+            },
+            (HttpMethod::Post, ["tasks", task, "pushNotificationConfigs"]) => (
+                RequestMethod::TasksPushNotificationConfigSet,
+                serde_json::json!({"taskId": decode_segment(task)?, "config": parse_body(body)?}),
+            ),
+            #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+            (HttpMethod::Get, ["tasks"]) => (RequestMethod::TasksList, parse_list_query(query)?),
+            (HttpMethod::Get, ["tasks", task, "pushNotificationConfigs"]) => (
+                RequestMethod::TasksPushNotificationConfigList,
+                serde_json::json!({"taskId": decode_segment(task)?}),
+            ),
+            (HttpMethod::Get, ["tasks", task, "pushNotificationConfigs", config]) => (
+                RequestMethod::TasksPushNotificationConfigGet,
+                serde_json::json!({"taskId": decode_segment(task)?, "configId": decode_segment(config)?}),
+            ),
+            (HttpMethod::Delete, ["tasks", task, "pushNotificationConfigs", config]) => (
+                RequestMethod::TasksPushNotificationConfigDelete,
+                serde_json::json!({"taskId": decode_segment(task)?, "configId": decode_segment(config)?}),
+            ),
+            _ => return Err(no_route(method, path)),
+        };
+
+        serde_json::from_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "",
+            "method": a2a_method,
+            "params": params,
+        }))
+        .map_err(|e| invalid_params(format!("Invalid parameters for {}: {}", a2a_method, e)))
+    }
+
+    fn new(method: HttpMethod, path: String) -> Self {
+        Self {
+            method,
+            path,
+            query: None,
+            body: None,
+        }
+    }
+
+    fn with_body(mut self, body: Value) -> Self {
+        self.body = Some(body);
+        self
+    }
+}
+
+fn string_param<'a>(params: &'a Value, name: &str) -> Result<&'a str, A2AError> {
+    params
+        .get(name)
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| missing_param(name))
+}
+
+fn task_path(params: &Value) -> Result<String, A2AError> {
+    Ok(format!("/v1/tasks/{}", encode_segment(string_param(params, "taskId")?)))
+}
+
+fn configs_path(params: &Value) -> Result<String, A2AError> {
+    Ok(format!("{}/pushNotificationConfigs", task_path(params)?))
+}
+
+fn config_path(params: &Value) -> Result<String, A2AError> {
+    Ok(format!(
+        "{}/{}",
+        configs_path(params)?,
+        encode_segment(string_param(params, "configId")?)
+    ))
+}
+
+/// Whether a task path segment ends in a custom method such as `:cancel`.
+fn is_custom_method(segment: &str) -> bool {
+    matches!(segment.rsplit_once(':'), Some((_, "cancel" | "subscribe")))
+}
+
+fn parse_body(body: Option<&str>) -> Result<Value, A2AError> {
+    let body = body.filter(|b| !b.trim().is_empty()).ok_or_else(|| invalid_params("Missing request body".to_string()))?;
+    serde_json::from_str(body).map_err(|e| A2AError::JSONParse(Box::new(JSONParseError {
+        code: -32700,
+        message: format!("Invalid JSON payload: {}", e),
+        data: None,
+    })))
+}
+
+fn parse_history_length(query: Option<&str>) -> Result<Option<u32>, A2AError> {
+    let Some(query) = query else {
+        return Ok(None);
+    };
+    for pair in query.split('&') {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        if key == "historyLength" {
+            return value
+                .parse()
+                .map(Some)
+                .map_err(|_| invalid_params(format!("Invalid historyLength: {}", value)));
+        }
+    }
+    Ok(None)
+}
+
+/// The filters of `tasks/list` whose values are numbers rather than strings.
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+const LIST_NUMBER_FILTERS: &[&str] = &["pageSize", "historyLength"];
+
+/// The filters of `tasks/list` in the order they are put in the query.
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+const LIST_FILTERS: &[&str] = &["contextId", "status", "pageSize", "pageToken", "historyLength"];
+
+/// Build the query string of a `tasks/list` route from its parameters.
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+fn list_query(params: &Value) -> Option<String> {
+    let pairs: Vec<String> = LIST_FILTERS
+        .iter()
+        .filter_map(|key| {
+            let value = match params.get(*key)? {
+                Value::String(value) => encode_segment(value),
+                Value::Number(value) => value.to_string(),
+                _ => return None,
+            };
+            Some(format!("{}={}", key, value))
+        })
+        .collect();
+    (!pairs.is_empty()).then(|| pairs.join("&"))
+}
+
+/// Parse the query string of a `tasks/list` route into its parameters.
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+fn parse_list_query(query: Option<&str>) -> Result<Value, A2AError> {
+    let mut params = serde_json::Map::new();
+    for pair in query.into_iter().flat_map(|query| query.split('&')).filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        if !LIST_FILTERS.contains(&key) {
+            continue;
+        }
+        let value = if LIST_NUMBER_FILTERS.contains(&key) {
+            let number: u32 = value.parse().map_err(|_| invalid_params(format!("Invalid {}: {}", key, value)))?;
+            Value::from(number)
+        } else {
+            Value::String(decode_segment(value)?)
+        };
+        params.insert(key.to_string(), value);
+    }
+    Ok(Value::Object(params))
+}
+
+/// Percent-encode a path segment, keeping only unreserved characters.
+fn encode_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+fn decode_segment(segment: &str) -> Result<String, A2AError> {
+    let invalid = || invalid_params(format!("Invalid path segment: {}", segment));
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = segment.get(i + 1..i + 3).ok_or_else(invalid)?;
+            decoded.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    let decoded = String::from_utf8(decoded).map_err(|_| invalid())?;
+    if decoded.is_empty() {
+        return Err(invalid());
+    }
+    Ok(decoded)
+}
+
+fn missing_param(name: &str) -> A2AError {
+    invalid_params(format!("Missing parameter: {}", name))
+}
+
+fn invalid_params(message: String) -> A2AError {
+    A2AError::InvalidParams(Box::new(InvalidParamsError {
+        code: -32602,
+        message,
+        data: None,
+    }))
+}
+
+fn no_route(method: &str, path: &str) -> A2AError {
+    A2AError::MethodNotFound(Box::new(MethodNotFoundError {
+        code: -32601,
+        message: format!("No A2A route for {} {}", method, path),
+        data: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, params: Value) -> A2ARequest {
+        serde_json::from_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "",
+            "method": method,
+            "params": params,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_every_method_round_trips() {
+        let message = serde_json::json!({
+            "message": {
+                "kind": "message",
+                "messageId": "msg-1",
+                "parts": [{"kind": "text", "text": "Hello"}],
+                "role": "user"
+            }
+        });
+        let config = serde_json::json!({"url": "https://example.com/hook", "token": "secret"});
+        let cases = [
+            ("message/send", message.clone(), HttpMethod::Post, "/v1/message:send", None),
+            ("message/stream", message, HttpMethod::Post, "/v1/message:stream", None),
+            (
+                "tasks/get",
+                serde_json::json!({"taskId": "task-1", "historyLength": 5}),
+                HttpMethod::Get,
+                "/v1/tasks/task-1",
+                Some("historyLength=5"),
+            ),
+            ("tasks/get", serde_json::json!({"taskId": "task-1"}), HttpMethod::Get, "/v1/tasks/task-1", None),
+            (
+                "tasks/cancel",
+                serde_json::json!({"taskId": "task-1"}),
+                HttpMethod::Post,
+                "/v1/tasks/task-1:cancel",
+                None,
+            ),
+            (
+                "tasks/resubscribe",
+                serde_json::json!({"taskId": "task-1"}),
+                HttpMethod::Post,
+                "/v1/tasks/task-1:subscribe",
+                None,
+            ),
+            (
+                "tasks/pushNotificationConfig/set",
+                serde_json::json!({"taskId": "task-1", "config": config}),
+                HttpMethod::Post,
+                "/v1/tasks/task-1/pushNotificationConfigs",
+                None,
+            ),
+            (
+                "tasks/pushNotificationConfig/get",
+                serde_json::json!({"taskId": "task-1", "configId": "cfg-1"}),
+                HttpMethod::Get,
+                "/v1/tasks/task-1/pushNotificationConfigs/cfg-1",
+                None,
+            ),
+            (
+                "tasks/pushNotificationConfig/list",
+                serde_json::json!({"taskId": "task-1"}),
+                HttpMethod::Get,
+                "/v1/tasks/task-1/pushNotificationConfigs",
+                None,
+            ),
+            (
+                "tasks/pushNotificationConfig/delete",
+                serde_json::json!({"taskId": "task-1", "configId": "cfg-1"}),
+                HttpMethod::Delete,
+                "/v1/tasks/task-1/pushNotificationConfigs/cfg-1",
+                None,
+            ),
+        ];
+
+        for (method, params, http_method, path, query) in cases {
+            let original = request(method, params);
+            let route = RestRoute::from_request(&original).unwrap();
+            assert_eq!(route.method, http_method, "{}", method);
+            assert_eq!(route.path, path, "{}", method);
+            assert_eq!(route.query.as_deref(), query, "{}", method);
+
+            let body = route.body.as_ref().map(|b| b.to_string());
+            let parsed = RestRoute::parse(route.method.as_str(), &route.path, query, body.as_deref()).unwrap();
+            assert_eq!(parsed.method().as_str(), method);
+            assert_eq!(parsed.id(), crate::JsonRpcId::String(String::new()));
+            assert_eq!(parsed.params().unwrap(), original.params().unwrap(), "{}", method);
+        }
+    }
+
+    #[test]
+    fn test_history_length_query() {
+        let parsed = RestRoute::parse("get", "/v1/tasks/task-1", Some("foo=bar&historyLength=10"), None).unwrap();
+        match parsed {
+            A2ARequest::GetTask(request) => {
+                assert_eq!(request.params.task_id, "task-1");
+                assert_eq!(request.params.history_length, Some(10));
+            }
+            other => panic!("Expected tasks/get, got {:?}", other),
+        }
+
+        let error = RestRoute::parse("GET", "/v1/tasks/task-1", Some("historyLength=-1"), None).unwrap_err();
+        assert!(matches!(error, A2AError::InvalidParams(_)));
+    }
+
+    #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+    #[test]
+    fn test_list_tasks_query() {
+        let original = request("tasks/list", serde_json::json!({"contextId": "ctx 1", "status": "working", "pageSize": 20}));
+        let route = RestRoute::from_request(&original).unwrap();
+        assert_eq!((route.method, route.path.as_str()), (HttpMethod::Get, "/v1/tasks"));
+        assert_eq!(route.query.as_deref(), Some("contextId=ctx%201&status=working&pageSize=20"));
+        let parsed = RestRoute::parse("GET", "/v1/tasks", route.query.as_deref(), None).unwrap();
+        assert_eq!(parsed.params().unwrap(), original.params().unwrap());
+
+        let unfiltered = RestRoute::parse("GET", "/v1/tasks/", None, None).unwrap();
+        assert_eq!(RestRoute::from_request(&unfiltered).unwrap().query, None);
+        let error = RestRoute::parse("GET", "/v1/tasks", Some("pageSize=many"), None).unwrap_err();
+        assert!(matches!(error, A2AError::InvalidParams(_)));
+    }
+
+    #[test]
+    fn test_path_segments_are_percent_encoded() {
+        let route = RestRoute::from_method(
+            RequestMethod::TasksPushNotificationConfigGet,
+            &serde_json::json!({"taskId": "a b/c:cancel", "configId": "ü"}),
+        )
+        .unwrap();
+        assert_eq!(route.path, "/v1/tasks/a%20b%2Fc%3Acancel/pushNotificationConfigs/%C3%BC");
+
+        match RestRoute::parse("GET", &route.path, None, None).unwrap() {
+            A2ARequest::GetTaskPushNotificationConfig(request) => {
+                assert_eq!(request.params.task_id, "a b/c:cancel");
+                assert_eq!(request.params.config_id, "ü");
+            }
+            other => panic!("Expected tasks/pushNotificationConfig/get, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unknown_routes_and_bad_input() {
+        for (method, path) in [
+            ("GET", "/v1/unknown"),
+            ("GET", "/tasks/task-1"),
+            ("PUT", "/v1/tasks/task-1"),
+            ("DELETE", "/v1/tasks/task-1"),
+            ("GET", "/v1/message:send"),
+            ("POST", "/v1/tasks/task-1:archive"),
+            ("GET", "/v1/tasks/task-1/pushNotificationConfigs/cfg-1/extra"),
+        ] {
+            let error = RestRoute::parse(method, path, None, None).unwrap_err();
+            assert!(matches!(error, A2AError::MethodNotFound(ref e) if e.code == -32601), "{} {}", method, path);
+        }
+
+        let error = RestRoute::parse("POST", "/v1/message:send", None, None).unwrap_err();
+        assert!(matches!(error, A2AError::InvalidParams(_)));
+        let error = RestRoute::parse("POST", "/v1/message:send", None, Some("{")).unwrap_err();
+        assert!(matches!(error, A2AError::JSONParse(_)));
+        let error = RestRoute::parse("POST", "/v1/message:send", None, Some("{}")).unwrap_err();
+        assert!(matches!(error, A2AError::InvalidParams(_)));
+        let error = RestRoute::from_method(RequestMethod::TasksGet, &serde_json::json!({})).unwrap_err();
+        assert!(matches!(error, A2AError::InvalidParams(_)));
+    }
+}
@@ -0,0 +1,1773 @@
+//! JSON-RPC requests, responses and errors for the A2A methods.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::{
+    AgentCard, Capability, ExtraFields, Message, MessageRole, Part, Task, TaskArtifactUpdateEvent, TaskStatusUpdateEvent,
+    TextPart, helpers,
+};
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+use crate::extensions::TASKS_LIST_EXTENSION_URI;
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+use crate::wire::ProtocolVersion;
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+use crate::TaskState;
+use serde::{Deserialize, Serialize};
+
+// The errors used to live here; keep their `rpc::` paths working.
+pub use crate::errors::*;
+
+/// JSON-RPC ID type that can be string, integer, or null
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schema", schemars(transform = crate::schema::one_of))]
+#[serde(untagged)]
+pub enum JsonRpcId {
+    String(String),
+    Integer(i64),
+    Null,
+}
+
+/// Request methods supported by the A2A protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum RequestMethod {
+    /// Send a message to an agent.
+    #[serde(rename = "message/send")]
+    MessageSend,
+    /// Send a streaming message to an agent.
+    #[serde(rename = "message/stream")]
+    MessageStream,
+    /// Get a task.
+    #[serde(rename = "tasks/get")]
+    TasksGet,
+    /// Cancel a task.
+    #[serde(rename = "tasks/cancel")]
+    TasksCancel,
+    /// Set a push notification config for a task.
+    #[serde(rename = "tasks/pushNotificationConfig/set")]
+    TasksPushNotificationConfigSet,
+    /// Get a push notification config for a task.
+    #[serde(rename = "tasks/pushNotificationConfig/get")]
+    TasksPushNotificationConfigGet,
+    /// List push notification configs for a task.
+    #[serde(rename = "tasks/pushNotificationConfig/list")]
+    TasksPushNotificationConfigList,
+    /// Delete a push notification config for a task.
+    #[serde(rename = "tasks/pushNotificationConfig/delete")]
+    TasksPushNotificationConfigDelete,
+    /// Resubscribe to a task.
+    #[serde(rename = "tasks/resubscribe")]
+    TasksResubscribe,
+    /// List tasks (A2A 0.3 draft).
+    #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+    #[serde(rename = "tasks/list")]
+    TasksList,
+}
+
+// Implement custom deserialization for RequestMethod
+impl<'de> Deserialize<'de> for RequestMethod {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        RequestMethod::from_str(&s)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown request method: {}", s)))
+    }
+}
+
+all_variants!(RequestMethod {
+    MessageSend,
+    MessageStream,
+    TasksGet,
+    TasksCancel,
+    TasksPushNotificationConfigSet,
+    TasksPushNotificationConfigGet,
+    TasksPushNotificationConfigList,
+    TasksPushNotificationConfigDelete,
+    TasksResubscribe,
+    #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+    TasksList,
+});
+
+impl RequestMethod {
+    /// The method names of older clients, with the method each stands for;
+    /// see [`legacy_aliases`](Self::legacy_aliases).
+    pub const LEGACY_ALIASES: &'static [(&'static str, RequestMethod)] = &[
+        // Protocol 0.1 names, renamed in 0.2. Sent by the sample client of the google/A2A repository
+        // for protocol 0.1 (samples/python/common/client), from `A2AClient.send_task()`,
+        // `send_task_streaming()`, `set_task_callback()` and `get_task_callback()`
+        ("tasks/send", RequestMethod::MessageSend),
+        ("tasks/sendSubscribe", RequestMethod::MessageStream),
+        ("tasks/pushNotification/set", RequestMethod::TasksPushNotificationConfigSet),
+        ("tasks/pushNotification/get", RequestMethod::TasksPushNotificationConfigGet),
+        // Accepted by a2a-rs 0.2.8 and earlier for clients of its own first releases; no
+        // reference SDK sends them
+        ("sendMessage", RequestMethod::MessageSend),
+        ("getTask", RequestMethod::TasksGet),
+        ("cancelTask", RequestMethod::TasksCancel),
+    ];
+
+    /// Get every request method, in declaration order.
+    ///
+    /// # Returns
+    ///
+    /// [`RequestMethod::ALL`], a slice containing each method exactly once.
+    pub fn all() -> &'static [RequestMethod] {
+        Self::ALL
+    }
+
+    /// Check if the method responds with a stream of events.
+    ///
+    /// Exactly the methods that require the streaming capability do.
+    pub fn is_streaming(&self) -> bool {
+        matches!(self.required_capability(), Some(Capability::Streaming))
+    }
+
+    /// Get the agent capability a server must declare to support the method.
+    ///
+    /// # Returns
+    ///
+    /// The required capability, or `None` if every agent must support the method.
+    pub fn required_capability(&self) -> Option<Capability> {
+        match self {
+            RequestMethod::MessageStream | RequestMethod::TasksResubscribe => Some(Capability::Streaming),
+            RequestMethod::TasksPushNotificationConfigSet
+            | RequestMethod::TasksPushNotificationConfigGet
+            | RequestMethod::TasksPushNotificationConfigList
+            | RequestMethod::TasksPushNotificationConfigDelete => Some(Capability::PushNotifications),
+            RequestMethod::MessageSend | RequestMethod::TasksGet | RequestMethod::TasksCancel => None,
+            #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+            RequestMethod::TasksList => None,
+        }
+    }
+
+    /// Check if the method requires a `params` member.
+    pub fn expects_params(&self) -> bool {
+        match self {
+            RequestMethod::MessageSend
+            | RequestMethod::MessageStream
+            | RequestMethod::TasksGet
+            | RequestMethod::TasksCancel
+            | RequestMethod::TasksPushNotificationConfigSet
+            | RequestMethod::TasksPushNotificationConfigGet
+            | RequestMethod::TasksPushNotificationConfigList
+            | RequestMethod::TasksPushNotificationConfigDelete
+            | RequestMethod::TasksResubscribe => true,
+            // Every filter is optional
+            #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+            RequestMethod::TasksList => false,
+        }
+    }
+
+    /// Convert the request method to a string.
+    ///
+    /// This is the method name table; [`from_str`](Self::from_str) reads it
+    /// back through [`RequestMethod::ALL`].
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            RequestMethod::MessageSend => "message/send",
+            RequestMethod::MessageStream => "message/stream",
+            RequestMethod::TasksGet => "tasks/get",
+            RequestMethod::TasksCancel => "tasks/cancel",
+            RequestMethod::TasksPushNotificationConfigSet => "tasks/pushNotificationConfig/set",
+            RequestMethod::TasksPushNotificationConfigGet => "tasks/pushNotificationConfig/get",
+            RequestMethod::TasksPushNotificationConfigList => "tasks/pushNotificationConfig/list",
+            RequestMethod::TasksPushNotificationConfigDelete => "tasks/pushNotificationConfig/delete",
+            RequestMethod::TasksResubscribe => "tasks/resubscribe",
+            #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+            RequestMethod::TasksList => "tasks/list",
+        }
+    }
+
+    /// Convert a string to a request method.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .map(|method| (method.as_str(), *method))
+            .chain(Self::LEGACY_ALIASES.iter().copied())
+            .find(|(name, _)| *name == s)
+            .map(|(_, method)| method)
+    }
+
+    /// Get the method names of older clients that [`from_str`](Self::from_str)
+    /// still accepts, with the method each stands for.
+    ///
+    /// Aliases are only ever parsed; requests always serialize with the
+    /// current names.
+    pub fn legacy_aliases() -> &'static [(&'static str, RequestMethod)] {
+        Self::LEGACY_ALIASES
+    }
+
+    /// Check if a method name is a legacy alias rather than a current name,
+    /// e.g. to log a deprecation warning for the client that sent it.
+    pub fn is_legacy_alias(s: &str) -> bool {
+        Self::legacy_aliases().iter().any(|(alias, _)| *alias == s)
+    }
+}
+
+// Every method and alias name parses back to exactly one method: names and
+// aliases are unique, and `ALL` is in declaration order.
+const _: () = {
+    const fn same(a: &str, b: &str) -> bool {
+        let (a, b) = (a.as_bytes(), b.as_bytes());
+        if a.len() != b.len() {
+            return false;
+        }
+        let mut i = 0;
+        while i < a.len() {
+            if a[i] != b[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    const fn name(i: usize) -> &'static str {
+        let methods = RequestMethod::ALL;
+        if i < methods.len() { methods[i].as_str() } else { RequestMethod::LEGACY_ALIASES[i - methods.len()].0 }
+    }
+
+    let methods = RequestMethod::ALL;
+    let names = methods.len() + RequestMethod::LEGACY_ALIASES.len();
+    let mut i = 0;
+    while i < names {
+        if i < methods.len() {
+            assert!(methods[i] as usize == i, "RequestMethod::ALL must be in declaration order");
+        }
+        let mut j = i + 1;
+        while j < names {
+            assert!(!same(name(i), name(j)), "two request methods share a name");
+            j += 1;
+        }
+        i += 1;
+    }
+};
+
+impl core::fmt::Display for RequestMethod {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl core::str::FromStr for RequestMethod {
+    type Err = A2AError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str(s).ok_or_else(|| A2AError::MethodNotFound(Box::new(MethodNotFoundError {
+            code: -32601,
+            message: format!("Method not found: {}", s),
+            data: None,
+        })))
+    }
+}
+
+/// Parameters containing only a task ID, used for simple task operations.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TaskIdParams {
+    /// Task id
+    pub id: String,
+    /// Extension metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// JSON-RPC error response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct JSONRPCErrorResponse {
+    /// The JSON-RPC ID.
+    pub id: JsonRpcId,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// The error details.
+    pub error: A2AError,
+}
+
+/// Configuration for the send message request
+///
+/// The default leaves every option unset, deferring to the server.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MessageSendConfiguration {
+    /// Accepted output modalities by the client
+    #[serde(rename = "acceptedOutputModes", skip_serializing_if = "Option::is_none")]
+    pub accepted_output_modes: Option<Vec<String>>,
+    /// If the server should treat the client as a blocking request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocking: Option<bool>,
+    /// Number of recent messages to be retrieved
+    ///
+    /// Integral floats such as `10.0` are accepted and values beyond the
+    /// `i32` range saturate. Negative values parse but are invalid; read
+    /// the field through [`MessageSendConfiguration::history_limit`].
+    #[serde(
+        rename = "historyLength",
+        default,
+        deserialize_with = "crate::integers::optional_i32",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub history_length: Option<i32>,
+    /// Where the server should send notifications when disconnected
+    #[serde(rename = "pushNotificationConfig", skip_serializing_if = "Option::is_none")]
+    pub push_notification_config: Option<PushNotificationConfig>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+impl MessageSendConfiguration {
+    /// Ask for the latest `length` history messages with the returned task.
+    ///
+    /// Lengths beyond `i32::MAX`, the largest the wire field holds, are
+    /// sent as `i32::MAX`.
+    pub fn with_history_length(mut self, length: u32) -> Self {
+        self.history_length = Some(length.min(i32::MAX as u32) as i32);
+        self
+    }
+
+    /// The requested `historyLength` as a number of messages, for
+    /// [`TaskView::with_history_length`](crate::TaskView::with_history_length).
+    ///
+    /// # Returns
+    ///
+    /// `None` if no length was requested, or an `InvalidParams` error if the
+    /// length is negative.
+    pub fn history_limit(&self) -> Result<Option<u32>, A2AError> {
+        match self.history_length {
+            Some(length) if length < 0 => Err(A2AError::InvalidParams(Box::new(InvalidParamsError {
+                code: -32602,
+                message: format!("historyLength must not be negative, got {}", length),
+                data: Some(serde_json::json!({"path": "configuration.historyLength"})),
+            }))),
+            length => Ok(length.map(|length| length as u32)),
+        }
+    }
+}
+
+/// Send message request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SendMessageRequest {
+    /// The method name.
+    pub method: RequestMethod,
+    /// The parameters for the request.
+    pub params: SendMessageParams,
+    /// The JSON-RPC ID.
+    pub id: JsonRpcId,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+impl SendMessageRequest {
+    /// Create a new send message request.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The JSON-RPC ID.
+    /// * `message_id` - The message ID.
+    /// * `text` - The text content of the message.
+    /// * `role` - The role of the message sender.
+    /// * `configuration` - Send message configuration (optional).
+    /// * `metadata` - Additional metadata (optional).
+    ///
+    /// # Returns
+    ///
+    /// A new `SendMessageRequest` with the specified parameters.
+    pub fn new(
+        id: String,
+        message_id: String,
+        text: String,
+        role: MessageRole,
+        configuration: Option<MessageSendConfiguration>,
+        metadata: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            method: RequestMethod::MessageSend,
+            params: SendMessageParams {
+                message: Message {
+                    kind: "message".to_string(),
+                    message_id,
+                    parts: vec![Part::Text(TextPart {
+                        text,
+                        metadata: None,
+                    })],
+                    role,
+                    context_id: None,
+                    extensions: None,
+                    metadata: None,
+                    reference_task_ids: None,
+                    task_id: None,
+                    extra: ExtraFields::default(),
+                },
+                configuration,
+                metadata,
+                extra: ExtraFields::default(),
+            },
+            id: JsonRpcId::String(id),
+            jsonrpc: "2.0".to_string(),
+            extra: ExtraFields::default(),
+        }
+    }
+}
+
+/// Send message parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SendMessageParams {
+    /// The message being sent to the server
+    pub message: Message,
+    /// Send message configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub configuration: Option<MessageSendConfiguration>,
+    /// Extension metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// Send message response (can be success or error).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(transform = crate::schema::one_of))]
+#[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
+pub enum SendMessageResponse {
+    Success(SendMessageSuccessResponse),
+    Error(JSONRPCErrorResponse),
+}
+
+/// Send message success response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SendMessageSuccessResponse {
+    /// The JSON-RPC ID.
+    pub id: JsonRpcId,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// The result of the request (Task or Message).
+    pub result: SendMessageResult,
+}
+
+/// Send message result (can be Task or Message).
+///
+/// Agents answer with a message when the request needs no tracking, e.g. a
+/// trivial question answered at once, and with a task when the work is
+/// stateful or long-running. Handlers return either, converted with
+/// `into()`. A message answer creates no task on the agent, so there is
+/// nothing to poll, cancel or resubscribe to afterwards: clients check for
+/// a task with [`SendMessageResult::task`] before calling `tasks/get`.
+///
+/// ```
+/// use a2a_rs::{Message, SendMessageResult};
+///
+/// let result = SendMessageResult::from(Message::agent("msg-2".to_string(), ["It is 4"]));
+/// assert!(result.is_final());
+/// assert_eq!(result.text().as_deref(), Some("It is 4"));
+/// assert_eq!(result.into_task(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(transform = crate::schema::one_of))]
+#[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
+pub enum SendMessageResult {
+    /// The task created or continued by the message.
+    Task(Task),
+    /// A direct answer; no task exists for it.
+    Message(Message),
+}
+
+impl SendMessageResult {
+    /// Get the task, if the agent answered with one.
+    pub fn task(&self) -> Option<&Task> {
+        match self {
+            SendMessageResult::Task(task) => Some(task),
+            SendMessageResult::Message(_) => None,
+        }
+    }
+
+    /// Get the message, if the agent answered with one.
+    pub fn message(&self) -> Option<&Message> {
+        match self {
+            SendMessageResult::Task(_) => None,
+            SendMessageResult::Message(message) => Some(message),
+        }
+    }
+
+    /// Take the task, or `None` if the agent answered with a message.
+    pub fn into_task(self) -> Option<Task> {
+        match self {
+            SendMessageResult::Task(task) => Some(task),
+            SendMessageResult::Message(_) => None,
+        }
+    }
+
+    /// Take the message, or `None` if the agent answered with a task.
+    pub fn into_message(self) -> Option<Message> {
+        match self {
+            SendMessageResult::Task(_) => None,
+            SendMessageResult::Message(message) => Some(message),
+        }
+    }
+
+    /// Get the ID of the task the answer belongs to.
+    ///
+    /// # Returns
+    ///
+    /// The task's ID, the task a message answer refers to, or `None` for a
+    /// message outside any task.
+    pub fn task_id(&self) -> Option<&str> {
+        match self {
+            SendMessageResult::Task(task) => Some(&task.id),
+            SendMessageResult::Message(message) => message.task_id.as_deref(),
+        }
+    }
+
+    /// Get the context ID of the answer, if the agent set one.
+    pub fn context_id(&self) -> Option<&str> {
+        match self {
+            SendMessageResult::Task(task) => Some(&task.context_id),
+            SendMessageResult::Message(message) => message.context_id.as_deref(),
+        }
+    }
+
+    /// Check if nothing more will come of the request: always for a
+    /// message, and for a task once it is in a terminal state.
+    pub fn is_final(&self) -> bool {
+        match self {
+            SendMessageResult::Task(task) => task.status.state.is_terminal(),
+            SendMessageResult::Message(_) => true,
+        }
+    }
+
+    /// Get the text of the answer.
+    ///
+    /// # Returns
+    ///
+    /// For a message, its text parts separated by newlines. For a task, its
+    /// [`Task::output_text`], or else the text of its status message, such
+    /// as the question of an `input-required` task. `None` if there is no
+    /// text.
+    pub fn text(&self) -> Option<String> {
+        match self {
+            SendMessageResult::Task(task) => {
+                task.output_text().or_else(|| task.status.message.as_ref().and_then(message_text))
+            }
+            SendMessageResult::Message(message) => message_text(message),
+        }
+    }
+}
+
+/// The text parts of a message separated by newlines, or `None` if it has
+/// none.
+fn message_text(message: &Message) -> Option<String> {
+    let texts: Vec<&str> = message
+        .parts
+        .iter()
+        .filter_map(|part| match part {
+            Part::Text(text) => Some(text.text.as_str()),
+            _ => None,
+        })
+        .collect();
+    (!texts.is_empty()).then(|| texts.join("\n"))
+}
+
+impl From<Task> for SendMessageResult {
+    fn from(task: Task) -> Self {
+        SendMessageResult::Task(task)
+    }
+}
+
+impl From<Message> for SendMessageResult {
+    fn from(message: Message) -> Self {
+        SendMessageResult::Message(message)
+    }
+}
+
+/// Streaming message result (a task, a message, or a task update event).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(transform = crate::schema::one_of))]
+#[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
+pub enum StreamingMessageResult {
+    Task(Task),
+    Message(Message),
+    TaskStatusUpdate(TaskStatusUpdateEvent),
+    TaskArtifactUpdate(TaskArtifactUpdateEvent),
+}
+
+/// The typed result of any A2A method, as returned by
+/// [`crate::helpers::parse_response_for`].
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
+pub enum A2AResponsePayload {
+    /// The result of `message/send`.
+    SendMessage(SendMessageResult),
+    /// The result of `tasks/get` or `tasks/cancel`.
+    Task(Task),
+    /// The result of `tasks/pushNotificationConfig/set`.
+    PushConfigSet(PushNotificationConfigResult),
+    /// The result of `tasks/pushNotificationConfig/get`.
+    PushConfig(PushNotificationConfig),
+    /// The result of `tasks/pushNotificationConfig/list`.
+    PushConfigList(Vec<PushNotificationConfigInfo>),
+    /// The result of `tasks/pushNotificationConfig/delete`.
+    Deleted,
+    /// One event of `message/stream` or `tasks/resubscribe`.
+    Streaming(StreamingMessageResult),
+    /// The result of `tasks/list`.
+    #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+    TaskList(ListTasksResult),
+}
+
+/// Send streaming message request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SendStreamingMessageRequest {
+    /// The method name.
+    pub method: RequestMethod,
+    /// The parameters for the request.
+    pub params: SendMessageParams,
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// Get task request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct GetTaskRequest {
+    /// The method name.
+    pub method: RequestMethod,
+    /// The parameters for the request.
+    pub params: GetTaskParams,
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+impl GetTaskRequest {
+    /// Create a new get task request.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The JSON-RPC ID.
+    /// * `task_id` - The ID of the task to get.
+    ///
+    /// # Returns
+    ///
+    /// A new `GetTaskRequest` with the specified parameters.
+    pub fn new(id: String, task_id: String) -> Self {
+        Self {
+            method: RequestMethod::TasksGet,
+            params: GetTaskParams {
+                task_id,
+                history_length: None,
+                extra: ExtraFields::default(),
+            },
+            id,
+            jsonrpc: "2.0".to_string(),
+            extra: ExtraFields::default(),
+        }
+    }
+}
+
+/// Get task parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct GetTaskParams {
+    /// The task ID.
+    pub task_id: String,
+    /// The number of most recent history messages to return
+    ///
+    /// Integral floats such as `10.0` are accepted, larger values saturate
+    /// at `u32::MAX` and negative values are rejected.
+    #[serde(default, deserialize_with = "crate::integers::optional_u32", skip_serializing_if = "Option::is_none")]
+    pub history_length: Option<u32>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// Get task response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct GetTaskResponse {
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// The result of the request.
+    pub result: Task,
+}
+
+/// Cancel task request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CancelTaskRequest {
+    /// The method name.
+    pub method: RequestMethod,
+    /// The parameters for the request.
+    pub params: CancelTaskParams,
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+impl CancelTaskRequest {
+    /// Create a new cancel task request.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The JSON-RPC ID.
+    /// * `task_id` - The ID of the task to cancel.
+    ///
+    /// # Returns
+    ///
+    /// A new `CancelTaskRequest` with the specified parameters.
+    pub fn new(id: String, task_id: String) -> Self {
+        Self {
+            method: RequestMethod::TasksCancel,
+            params: CancelTaskParams {
+                task_id,
+                extra: ExtraFields::default(),
+            },
+            id,
+            jsonrpc: "2.0".to_string(),
+            extra: ExtraFields::default(),
+        }
+    }
+}
+
+/// Cancel task parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CancelTaskParams {
+    /// The task ID.
+    pub task_id: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// Cancel task response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CancelTaskResponse {
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// The result of the request.
+    pub result: Task,
+}
+
+/// Set task push notification config request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SetTaskPushNotificationConfigRequest {
+    /// The method name.
+    pub method: RequestMethod,
+    /// The parameters for the request.
+    pub params: SetTaskPushNotificationConfigParams,
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// Set task push notification config parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SetTaskPushNotificationConfigParams {
+    /// The task ID.
+    pub task_id: String,
+    /// The push notification config.
+    pub config: PushNotificationConfig,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// Push notification authentication info
+///
+/// The default lists no schemes and carries no credentials.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PushNotificationAuthenticationInfo {
+    /// Supported authentication schemes - e.g. Basic, Bearer
+    pub schemes: Vec<String>,
+    /// Optional credentials
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<String>,
+}
+
+/// Push notification config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PushNotificationConfig {
+    /// URL for sending the push notifications
+    pub url: String,
+    /// Authentication details for push notifications
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication: Option<PushNotificationAuthenticationInfo>,
+    /// Push Notification ID - created by server to support multiple callbacks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Token unique to this task/session
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+// With the `zeroize` feature, credentials are wiped when a config is
+// dropped. Each clone owns its own buffers and is wiped separately. `Drop`
+// is only implemented with the feature, so without it fields can still be
+// moved out of a config.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for PushNotificationAuthenticationInfo {
+    fn zeroize(&mut self) {
+        self.credentials.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for PushNotificationAuthenticationInfo {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for PushNotificationAuthenticationInfo {}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for PushNotificationConfig {
+    fn zeroize(&mut self) {
+        self.token.zeroize();
+        if let Some(authentication) = &mut self.authentication {
+            authentication.zeroize();
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for PushNotificationConfig {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for PushNotificationConfig {}
+
+/// Set task push notification config response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SetTaskPushNotificationConfigResponse {
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// The result of the request.
+    pub result: PushNotificationConfigResult,
+}
+
+/// Push notification config result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PushNotificationConfigResult {
+    /// The task ID.
+    pub task_id: String,
+    /// The push notification config ID.
+    pub config_id: String,
+}
+
+/// Get task push notification config request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct GetTaskPushNotificationConfigRequest {
+    /// The method name.
+    pub method: RequestMethod,
+    /// The parameters for the request.
+    pub params: GetTaskPushNotificationConfigParams,
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// Get task push notification config parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct GetTaskPushNotificationConfigParams {
+    /// The task ID.
+    pub task_id: String,
+    /// The push notification config ID.
+    pub config_id: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// Get task push notification config response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct GetTaskPushNotificationConfigResponse {
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// The result of the request.
+    pub result: PushNotificationConfig,
+}
+
+/// Task resubscription request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TaskResubscriptionRequest {
+    /// The method name.
+    pub method: RequestMethod,
+    /// The parameters for the request.
+    pub params: TaskResubscriptionParams,
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// Task resubscription parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TaskResubscriptionParams {
+    /// The task ID.
+    pub task_id: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// Task resubscription response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TaskResubscriptionResponse {
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// The result of the request.
+    pub result: Task,
+}
+
+/// List tasks request (A2A 0.3 draft).
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ListTasksRequest {
+    /// The method name.
+    pub method: RequestMethod,
+    /// The parameters for the request; absent or `null` lists everything.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub params: ListTasksParams,
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+impl ListTasksRequest {
+    /// Create a list tasks request for an agent that serves `tasks/list`.
+    ///
+    /// `tasks/list` is not part of A2A 0.2.5, so it is only sent to agents
+    /// on protocol version 0.3 or later and to agents declaring the
+    /// [`TASKS_LIST_EXTENSION_URI`](crate::extensions::TASKS_LIST_EXTENSION_URI)
+    /// extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `card` - The agent card of the agent to ask.
+    /// * `id` - The JSON-RPC ID.
+    /// * `params` - The filters and page to list.
+    ///
+    /// # Returns
+    ///
+    /// The request, or an `UnsupportedOperation` error if the agent does not
+    /// serve `tasks/list`.
+    pub fn for_agent(card: &AgentCard, id: String, params: ListTasksParams) -> Result<Self, A2AError> {
+        let speaks_0_3 = ProtocolVersion::parse(&card.protocol_version)
+            .is_some_and(|version| version >= ProtocolVersion::V0_3_0);
+        let declares_extension = card
+            .capabilities
+            .extensions
+            .iter()
+            .flatten()
+            .any(|extension| extension.uri == TASKS_LIST_EXTENSION_URI);
+        if !speaks_0_3 && !declares_extension {
+            return Err(A2AError::UnsupportedOperation(Box::new(UnsupportedOperationError {
+                code: -32004,
+                message: "The agent does not serve tasks/list".to_string(),
+                data: Some(serde_json::json!({"method": "tasks/list"})),
+            })));
+        }
+
+        Ok(Self {
+            method: RequestMethod::TasksList,
+            params,
+            id,
+            jsonrpc: "2.0".to_string(),
+            extra: ExtraFields::default(),
+        })
+    }
+}
+
+/// Deserialize an explicit `null` like an absent field.
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// List tasks parameters; every filter is optional.
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ListTasksParams {
+    /// Only list tasks of this context.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_id: Option<String>,
+    /// Only list tasks in this state; also accepted as `state`.
+    #[serde(alias = "state", skip_serializing_if = "Option::is_none")]
+    pub status: Option<TaskState>,
+    /// The maximum number of tasks to return.
+    #[serde(default, deserialize_with = "crate::integers::optional_u32", skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+    /// The `nextPageToken` of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_token: Option<String>,
+    /// The number of most recent history messages to return per task
+    #[serde(default, deserialize_with = "crate::integers::optional_u32", skip_serializing_if = "Option::is_none")]
+    pub history_length: Option<u32>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// One page of tasks.
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ListTasksResult {
+    /// The tasks.
+    pub tasks: Vec<Task>,
+    /// The token of the next page, absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// List tasks response.
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ListTasksResponse {
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// The result of the request.
+    pub result: ListTasksResult,
+}
+
+/// List task push notification config request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ListTaskPushNotificationConfigRequest {
+    /// The method name.
+    pub method: RequestMethod,
+    /// The parameters for the request.
+    pub params: ListTaskPushNotificationConfigParams,
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// List task push notification config parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ListTaskPushNotificationConfigParams {
+    /// The task ID.
+    pub task_id: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// List task push notification config response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ListTaskPushNotificationConfigResponse {
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// The result of the request.
+    pub result: Vec<PushNotificationConfigInfo>,
+}
+
+/// Push notification config info.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PushNotificationConfigInfo {
+    /// The push notification config ID.
+    pub config_id: String,
+    /// The URL to send push notifications to.
+    pub url: String,
+}
+
+/// Delete task push notification config request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTaskPushNotificationConfigRequest {
+    /// The method name.
+    pub method: RequestMethod,
+    /// The parameters for the request.
+    pub params: DeleteTaskPushNotificationConfigParams,
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// Delete task push notification config parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTaskPushNotificationConfigParams {
+    /// The task ID.
+    pub task_id: String,
+    /// The push notification config ID.
+    pub config_id: String,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// Delete task push notification config response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTaskPushNotificationConfigResponse {
+    /// The JSON-RPC ID.
+    pub id: String,
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// The result of the request.
+    pub result: bool,
+}
+
+/// A JSON-RPC request for any A2A method.
+///
+/// Deserialization dispatches on the `method` field, so a single handler can
+/// accept every request type regardless of the binding it arrived over (see
+/// [`crate::rest::RestRoute::parse`] for the HTTP+JSON binding).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(transform = crate::schema::one_of))]
+#[serde(untagged)]
+#[non_exhaustive]
+pub enum A2ARequest {
+    /// `message/send` request.
+    SendMessage(SendMessageRequest),
+    /// `message/stream` request.
+    SendStreamingMessage(SendStreamingMessageRequest),
+    /// `tasks/get` request.
+    GetTask(GetTaskRequest),
+    /// `tasks/cancel` request.
+    CancelTask(CancelTaskRequest),
+    /// `tasks/pushNotificationConfig/set` request.
+    SetTaskPushNotificationConfig(SetTaskPushNotificationConfigRequest),
+    /// `tasks/pushNotificationConfig/get` request.
+    GetTaskPushNotificationConfig(GetTaskPushNotificationConfigRequest),
+    /// `tasks/pushNotificationConfig/list` request.
+    ListTaskPushNotificationConfig(ListTaskPushNotificationConfigRequest),
+    /// `tasks/pushNotificationConfig/delete` request.
+    DeleteTaskPushNotificationConfig(DeleteTaskPushNotificationConfigRequest),
+    /// `tasks/resubscribe` request.
+    TaskResubscription(TaskResubscriptionRequest),
+    /// `tasks/list` request.
+    #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+    ListTasks(ListTasksRequest),
+}
+
+impl<'de> Deserialize<'de> for A2ARequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let method = value.get("method")
+            .and_then(|m| m.as_str())
+            .ok_or_else(|| D::Error::missing_field("method"))?;
+        let method = RequestMethod::from_str(method)
+            .ok_or_else(|| D::Error::custom(format!("unknown request method: {}", method)))?;
+
+        match method {
+            RequestMethod::MessageSend => Ok(A2ARequest::SendMessage(
+                SendMessageRequest::deserialize(value).map_err(D::Error::custom)?
+            )),
+            RequestMethod::MessageStream => Ok(A2ARequest::SendStreamingMessage(
+                SendStreamingMessageRequest::deserialize(value).map_err(D::Error::custom)?
+            )),
+            RequestMethod::TasksGet => Ok(A2ARequest::GetTask(
+                GetTaskRequest::deserialize(value).map_err(D::Error::custom)?
+            )),
+            RequestMethod::TasksCancel => Ok(A2ARequest::CancelTask(
+                CancelTaskRequest::deserialize(value).map_err(D::Error::custom)?
+            )),
+            RequestMethod::TasksPushNotificationConfigSet => Ok(A2ARequest::SetTaskPushNotificationConfig(
+                SetTaskPushNotificationConfigRequest::deserialize(value).map_err(D::Error::custom)?
+            )),
+            RequestMethod::TasksPushNotificationConfigGet => Ok(A2ARequest::GetTaskPushNotificationConfig(
+                GetTaskPushNotificationConfigRequest::deserialize(value).map_err(D::Error::custom)?
+            )),
+            RequestMethod::TasksPushNotificationConfigList => Ok(A2ARequest::ListTaskPushNotificationConfig(
+                ListTaskPushNotificationConfigRequest::deserialize(value).map_err(D::Error::custom)?
+            )),
+            RequestMethod::TasksPushNotificationConfigDelete => Ok(A2ARequest::DeleteTaskPushNotificationConfig(
+                DeleteTaskPushNotificationConfigRequest::deserialize(value).map_err(D::Error::custom)?
+            )),
+            RequestMethod::TasksResubscribe => Ok(A2ARequest::TaskResubscription(
+                TaskResubscriptionRequest::deserialize(value).map_err(D::Error::custom)?
+            )),
+            #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+            RequestMethod::TasksList => Ok(A2ARequest::ListTasks(
+                ListTasksRequest::deserialize(value).map_err(D::Error::custom)?
+            )),
+        }
+    }
+}
+
+impl A2ARequest {
+    /// Get the method of the request.
+    pub fn method(&self) -> RequestMethod {
+        match self {
+            A2ARequest::SendMessage(r) => r.method,
+            A2ARequest::SendStreamingMessage(r) => r.method,
+            A2ARequest::GetTask(r) => r.method,
+            A2ARequest::CancelTask(r) => r.method,
+            A2ARequest::SetTaskPushNotificationConfig(r) => r.method,
+            A2ARequest::GetTaskPushNotificationConfig(r) => r.method,
+            A2ARequest::ListTaskPushNotificationConfig(r) => r.method,
+            A2ARequest::DeleteTaskPushNotificationConfig(r) => r.method,
+            A2ARequest::TaskResubscription(r) => r.method,
+            #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+            A2ARequest::ListTasks(r) => r.method,
+        }
+    }
+
+    /// Get the JSON-RPC ID of the request.
+    pub fn id(&self) -> JsonRpcId {
+        match self {
+            A2ARequest::SendMessage(r) => r.id.clone(),
+            A2ARequest::SendStreamingMessage(r) => JsonRpcId::String(r.id.clone()),
+            A2ARequest::GetTask(r) => JsonRpcId::String(r.id.clone()),
+            A2ARequest::CancelTask(r) => JsonRpcId::String(r.id.clone()),
+            A2ARequest::SetTaskPushNotificationConfig(r) => JsonRpcId::String(r.id.clone()),
+            A2ARequest::GetTaskPushNotificationConfig(r) => JsonRpcId::String(r.id.clone()),
+            A2ARequest::ListTaskPushNotificationConfig(r) => JsonRpcId::String(r.id.clone()),
+            A2ARequest::DeleteTaskPushNotificationConfig(r) => JsonRpcId::String(r.id.clone()),
+            A2ARequest::TaskResubscription(r) => JsonRpcId::String(r.id.clone()),
+            #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+            A2ARequest::ListTasks(r) => JsonRpcId::String(r.id.clone()),
+        }
+    }
+
+    /// Get the parameters of the request as JSON.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the parameters or an `Internal` error.
+    pub fn params(&self) -> Result<serde_json::Value, A2AError> {
+        let params = match self {
+            A2ARequest::SendMessage(r) => serde_json::to_value(&r.params),
+            A2ARequest::SendStreamingMessage(r) => serde_json::to_value(&r.params),
+            A2ARequest::GetTask(r) => serde_json::to_value(&r.params),
+            A2ARequest::CancelTask(r) => serde_json::to_value(&r.params),
+            A2ARequest::SetTaskPushNotificationConfig(r) => serde_json::to_value(&r.params),
+            A2ARequest::GetTaskPushNotificationConfig(r) => serde_json::to_value(&r.params),
+            A2ARequest::ListTaskPushNotificationConfig(r) => serde_json::to_value(&r.params),
+            A2ARequest::DeleteTaskPushNotificationConfig(r) => serde_json::to_value(&r.params),
+            A2ARequest::TaskResubscription(r) => serde_json::to_value(&r.params),
+            #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+            A2ARequest::ListTasks(r) => serde_json::to_value(&r.params),
+        };
+        params.map_err(|e| A2AError::Internal(Box::new(InternalError {
+            code: -32603,
+            message: format!("Internal error: {}", e),
+            data: None,
+        })))
+    }
+}
+
+/// A JSON-RPC request whose parameters are decoded on demand.
+///
+/// Only the envelope (`jsonrpc`, `id` and `method`) is parsed. The parameters
+/// are kept as the raw JSON text, so gateways can route and forward requests
+/// without paying for a full decode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LazyRequest {
+    /// The JSON-RPC version.
+    pub jsonrpc: String,
+    /// The JSON-RPC ID.
+    pub id: JsonRpcId,
+    /// The method name, which may not be a known A2A method.
+    pub method: String,
+    /// The raw parameters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Box<serde_json::value::RawValue>>,
+}
+
+impl LazyRequest {
+    /// Get the method of the request.
+    ///
+    /// # Returns
+    ///
+    /// The method, or a `MethodNotFound` error if it is not an A2A method.
+    pub fn method(&self) -> Result<RequestMethod, A2AError> {
+        self.method.parse()
+    }
+
+    /// Get the JSON-RPC ID of the request.
+    pub fn id(&self) -> &JsonRpcId {
+        &self.id
+    }
+
+    /// Decode the parameters into a typed value.
+    ///
+    /// The target may borrow from the raw parameters, e.g.
+    /// [`crate::borrowed::SendMessageParamsRef`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the parameters or an `InvalidParams`
+    /// error if they are missing or do not match the type.
+    pub fn params_as<'a, T: Deserialize<'a>>(&'a self) -> Result<T, A2AError> {
+        let params = self.params.as_deref().ok_or_else(|| A2AError::InvalidParams(Box::new(InvalidParamsError {
+            code: -32602,
+            message: "Missing parameters".to_string(),
+            data: None,
+        })))?;
+        serde_json::from_str(params.get()).map_err(|e| A2AError::InvalidParams(Box::new(InvalidParamsError {
+            code: -32602,
+            message: format!("Invalid parameters: {}", e),
+            data: None,
+        })))
+    }
+
+    /// Serialize the request for forwarding.
+    ///
+    /// The parameters are written back byte for byte as they were received.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the JSON string or an `Internal` error.
+    pub fn forward(&self) -> Result<String, A2AError> {
+        helpers::serialize_response(self)
+    }
+}
+
+/// A request as received, whose JSON-RPC ID may be absent.
+///
+/// A request without `id` is a notification: the server runs it but must
+/// not answer, not even with an error. [`IncomingRequest::id`] is the only
+/// ID of an incoming request; the typed request's own ID field, which a
+/// notification lacks, is left empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncomingRequest {
+    id: Option<JsonRpcId>,
+    request: A2ARequest,
+}
+
+/// A request that could not be read, with what is known of its ID.
+///
+/// Servers answer it with [`IncomingError::into_response`], which is
+/// `None` for a notification: JSON-RPC forbids answering those, even with
+/// an error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncomingError {
+    /// The JSON-RPC ID of the request, `None` for a notification, or
+    /// `Some(JsonRpcId::Null)` if the request is too malformed to tell.
+    pub id: Option<JsonRpcId>,
+    /// Why the request was refused.
+    pub error: A2AError,
+}
+
+impl IncomingError {
+    /// Create an error for a request whose ID could not be read.
+    pub fn unidentified(error: A2AError) -> Self {
+        Self {
+            id: Some(JsonRpcId::Null),
+            error,
+        }
+    }
+
+    /// Check if the request was a notification, which gets no answer.
+    pub fn is_notification(&self) -> bool {
+        self.id.is_none()
+    }
+
+    /// Build the error response.
+    ///
+    /// # Returns
+    ///
+    /// The JSON-RPC error response bytes, or `None` for a notification.
+    pub fn into_response(self) -> Option<Vec<u8>> {
+        self.id.map(|id| error_response_bytes(id, self.error))
+    }
+}
+
+impl From<IncomingError> for A2AError {
+    fn from(error: IncomingError) -> Self {
+        error.error
+    }
+}
+
+/// Receives the errors of notifications, which cannot be answered.
+pub trait NotificationErrorSink {
+    /// Record that a notification failed.
+    fn notification_failed(&mut self, request: &A2ARequest, error: A2AError);
+}
+
+impl<F: FnMut(&A2ARequest, A2AError)> NotificationErrorSink for F {
+    fn notification_failed(&mut self, request: &A2ARequest, error: A2AError) {
+        self(request, error)
+    }
+}
+
+impl IncomingRequest {
+    /// Get the JSON-RPC ID, or `None` for a notification.
+    pub fn id(&self) -> Option<&JsonRpcId> {
+        self.id.as_ref()
+    }
+
+    /// Get the typed request.
+    pub fn request(&self) -> &A2ARequest {
+        &self.request
+    }
+
+    /// Take the ID and the typed request.
+    pub fn into_parts(self) -> (Option<JsonRpcId>, A2ARequest) {
+        (self.id, self.request)
+    }
+
+    /// Check if the request is a notification, i.e. has no `id`.
+    pub fn is_notification(&self) -> bool {
+        self.id.is_none()
+    }
+
+    /// Run a handler on the request and build the response.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - Computes the result of the request.
+    /// * `errors` - Receives the error if the request is a notification
+    ///   and the handler fails.
+    ///
+    /// # Returns
+    ///
+    /// The JSON-RPC response bytes, or `None` for a notification.
+    pub fn dispatch<T, H, S>(&self, handler: H, errors: &mut S) -> Option<Vec<u8>>
+    where
+        T: Serialize,
+        H: FnOnce(&A2ARequest) -> Result<T, A2AError>,
+        S: NotificationErrorSink + ?Sized,
+    {
+        let result = handler(&self.request);
+        let Some(id) = &self.id else {
+            if let Err(error) = result {
+                errors.notification_failed(&self.request, error);
+            }
+            return None;
+        };
+        let response = result.and_then(|result| {
+            helpers::serialize_response_to_vec(&SuccessResponse { id, jsonrpc: "2.0", result: &result })
+        });
+        Some(response.unwrap_or_else(|error| error_response_bytes(id.clone(), error)))
+    }
+}
+
+/// A success response borrowing its result, so a result that fails to
+/// serialize becomes an error rather than a panic.
+#[derive(Serialize)]
+struct SuccessResponse<'a, T> {
+    id: &'a JsonRpcId,
+    jsonrpc: &'static str,
+    result: &'a T,
+}
+
+fn error_response_bytes(id: JsonRpcId, error: A2AError) -> Vec<u8> {
+    let response = JSONRPCErrorResponse {
+        id,
+        jsonrpc: "2.0".to_string(),
+        error,
+    };
+    serde_json::to_vec(&response).expect("error responses always serialize")
+}
+
+/// Reads notifications as well as requests; see [`A2ARequest`] for the
+/// errors.
+///
+/// Errors carry the request's ID. A notification is only refused without
+/// an answer if its envelope is valid: an invalid envelope without an ID
+/// is answered to the `null` ID, as JSON-RPC requires.
+impl TryFrom<serde_json::Value> for IncomingRequest {
+    type Error = IncomingError;
+
+    fn try_from(mut value: serde_json::Value) -> Result<Self, Self::Error> {
+        let id = match value.get("id") {
+            Some(id) => Some(JsonRpcId::deserialize(id).map_err(|e| {
+                IncomingError::unidentified(A2AError::InvalidRequest(Box::new(InvalidRequestError {
+                    code: -32600,
+                    message: format!("Invalid request: {}", e),
+                    data: Some(serde_json::json!({"path": "id"})),
+                })))
+            })?),
+            None => {
+                // The typed requests require an ID; `self.id` is what tells notifications apart
+                if let Some(object) = value.as_object_mut() {
+                    object.insert("id".to_string(), serde_json::Value::String(String::new()));
+                }
+                None
+            }
+        };
+        match A2ARequest::try_from(value) {
+            Ok(request) => Ok(Self { id, request }),
+            Err(error @ A2AError::InvalidRequest(_)) => Err(IncomingError {
+                id: Some(id.unwrap_or(JsonRpcId::Null)),
+                error,
+            }),
+            Err(error) => Err(IncomingError { id, error }),
+        }
+    }
+}
+
+// ============================================================================
+// CONVERSIONS FROM JSON VALUES
+// ============================================================================
+
+/// Map a failed conversion from a JSON value to a protocol error.
+///
+/// The path to the offending field is reported in `data.path`. For request
+/// envelopes, failures outside `params` are `InvalidRequest` errors; all
+/// other failures are `InvalidParams` errors.
+fn value_error(error: serde_path_to_error::Error<serde_json::Error>, envelope: bool) -> A2AError {
+    let path = error.path().to_string();
+    let data = Some(serde_json::json!({"path": path}));
+
+    if envelope && path != "params" && !path.starts_with("params.") {
+        A2AError::InvalidRequest(Box::new(InvalidRequestError {
+            code: -32600,
+            message: format!("Invalid request: {}", error.inner()),
+            data,
+        }))
+    } else {
+        A2AError::InvalidParams(Box::new(InvalidParamsError {
+            code: -32602,
+            message: format!("Invalid parameters: {}", error.inner()),
+            data,
+        }))
+    }
+}
+
+macro_rules! impl_try_from_value {
+    ($envelope:literal => $($ty:ty),+ $(,)?) => {
+        $(
+            impl TryFrom<serde_json::Value> for $ty {
+                type Error = A2AError;
+
+                fn try_from(value: serde_json::Value) -> Result<Self, A2AError> {
+                    serde_path_to_error::deserialize(value).map_err(|e| value_error(e, $envelope))
+                }
+            }
+
+            impl TryFrom<&serde_json::Value> for $ty {
+                type Error = A2AError;
+
+                fn try_from(value: &serde_json::Value) -> Result<Self, A2AError> {
+                    serde_path_to_error::deserialize(value).map_err(|e| value_error(e, $envelope))
+                }
+            }
+        )+
+    };
+}
+
+impl_try_from_value!(true =>
+    SendMessageRequest,
+    SendStreamingMessageRequest,
+    GetTaskRequest,
+    CancelTaskRequest,
+    SetTaskPushNotificationConfigRequest,
+    GetTaskPushNotificationConfigRequest,
+    ListTaskPushNotificationConfigRequest,
+    DeleteTaskPushNotificationConfigRequest,
+    TaskResubscriptionRequest,
+);
+
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+impl_try_from_value!(true => ListTasksRequest,);
+
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+impl_try_from_value!(false => ListTasksResponse, ListTasksResult,);
+
+impl_try_from_value!(false =>
+    SendMessageResponse,
+    SendMessageSuccessResponse,
+    GetTaskResponse,
+    CancelTaskResponse,
+    SetTaskPushNotificationConfigResponse,
+    GetTaskPushNotificationConfigResponse,
+    ListTaskPushNotificationConfigResponse,
+    DeleteTaskPushNotificationConfigResponse,
+    TaskResubscriptionResponse,
+    JSONRPCErrorResponse,
+    Task,
+    Message,
+    AgentCard,
+    TaskStatusUpdateEvent,
+    TaskArtifactUpdateEvent,
+    StreamingMessageResult,
+);
+
+/// Read the method of a request envelope held as a JSON value.
+fn request_method(value: &serde_json::Value) -> Result<RequestMethod, A2AError> {
+    match value.get("method").and_then(|m| m.as_str()) {
+        Some(method) => method.parse(),
+        None => Err(A2AError::InvalidRequest(Box::new(InvalidRequestError {
+            code: -32600,
+            message: "Invalid request: missing field `method`".to_string(),
+            data: Some(serde_json::json!({"path": "method"})),
+        }))),
+    }
+}
+
+macro_rules! dispatch_request {
+    ($value:expr) => {
+        match request_method(&$value)? {
+            RequestMethod::MessageSend => $value.try_into().map(A2ARequest::SendMessage),
+            RequestMethod::MessageStream => $value.try_into().map(A2ARequest::SendStreamingMessage),
+            RequestMethod::TasksGet => $value.try_into().map(A2ARequest::GetTask),
+            RequestMethod::TasksCancel => $value.try_into().map(A2ARequest::CancelTask),
+            RequestMethod::TasksPushNotificationConfigSet => $value.try_into().map(A2ARequest::SetTaskPushNotificationConfig),
+            RequestMethod::TasksPushNotificationConfigGet => $value.try_into().map(A2ARequest::GetTaskPushNotificationConfig),
+            RequestMethod::TasksPushNotificationConfigList => $value.try_into().map(A2ARequest::ListTaskPushNotificationConfig),
+            RequestMethod::TasksPushNotificationConfigDelete => {
+                $value.try_into().map(A2ARequest::DeleteTaskPushNotificationConfig)
+            }
+            RequestMethod::TasksResubscribe => $value.try_into().map(A2ARequest::TaskResubscription),
+            #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+            RequestMethod::TasksList => $value.try_into().map(A2ARequest::ListTasks),
+        }
+    };
+}
+
+/// Dispatches on `method`; an unknown method is a `MethodNotFound` error.
+impl TryFrom<serde_json::Value> for A2ARequest {
+    type Error = A2AError;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        dispatch_request!(value)
+    }
+}
+
+/// Dispatches on `method`; an unknown method is a `MethodNotFound` error.
+impl TryFrom<&serde_json::Value> for A2ARequest {
+    type Error = A2AError;
+
+    fn try_from(value: &serde_json::Value) -> Result<Self, Self::Error> {
+        dispatch_request!(value)
+    }
+}
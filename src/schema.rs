@@ -0,0 +1,251 @@
+//! JSON Schema generation for A2A protocol types.
+//!
+//! Enabled by the `schema` feature. Every public protocol type derives
+//! [`schemars::JsonSchema`], and [`export_all`] bundles their schemas into a
+//! single definitions document for OpenAPI or JSON Schema tooling.
+
+use crate::*;
+use schemars::{JsonSchema, Schema, SchemaGenerator, generate::SchemaSettings, json_schema};
+use std::borrow::Cow;
+
+/// Rewrite the `anyOf` produced for untagged enums into `oneOf`.
+///
+/// Used on untagged unions whose variants are mutually exclusive.
+pub(crate) fn one_of(schema: &mut Schema) {
+    if let Some(any_of) = schema.remove("anyOf") {
+        schema.insert("oneOf".to_string(), any_of);
+    }
+}
+
+impl JsonSchema for A2AError {
+    fn schema_name() -> Cow<'static, str> {
+        "A2AError".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        let variants = [
+            (-32700, generator.subschema_for::<JSONParseError>()),
+            (-32600, generator.subschema_for::<InvalidRequestError>()),
+            (-32601, generator.subschema_for::<MethodNotFoundError>()),
+            (-32602, generator.subschema_for::<InvalidParamsError>()),
+            (-32603, generator.subschema_for::<InternalError>()),
+            (-32001, generator.subschema_for::<TaskNotFoundError>()),
+            (-32002, generator.subschema_for::<TaskNotCancelableError>()),
+            (-32003, generator.subschema_for::<PushNotificationNotSupportedError>()),
+            (-32004, generator.subschema_for::<UnsupportedOperationError>()),
+            (-32005, generator.subschema_for::<ContentTypeNotSupportedError>()),
+            (-32006, generator.subschema_for::<InvalidAgentResponseError>()),
+        ];
+
+        let one_of: Vec<_> = variants
+            .into_iter()
+            .map(|(code, schema)| {
+                serde_json::json!({
+                    "allOf": [schema],
+                    "properties": { "code": { "const": code } }
+                })
+            })
+            .collect();
+
+        json_schema!({ "oneOf": one_of })
+    }
+}
+
+macro_rules! register {
+    ($generator:expr, $($ty:ty),* $(,)?) => {
+        $( $generator.subschema_for::<$ty>(); )*
+    };
+}
+
+/// Export the schemas of all public protocol types.
+///
+/// # Returns
+///
+/// A JSON Schema (draft 2020-12) document whose `$defs` contains one entry
+/// per protocol type, keyed by type name.
+pub fn export_all() -> serde_json::Value {
+    let mut generator = SchemaGenerator::new(SchemaSettings::draft2020_12());
+
+    register!(
+        generator,
+        JsonRpcId,
+        Message,
+        MessageRole,
+        Part,
+        TextPart,
+        FilePart,
+        DataPart,
+        FileContent,
+        FileWithBytes,
+        FileWithUri,
+        TaskState,
+        TaskStatus,
+        RequestMethod,
+        JSONParseError,
+        InvalidRequestError,
+        MethodNotFoundError,
+        InvalidParamsError,
+        InternalError,
+        TaskNotFoundError,
+        TaskNotCancelableError,
+        PushNotificationNotSupportedError,
+        UnsupportedOperationError,
+        ContentTypeNotSupportedError,
+        InvalidAgentResponseError,
+        A2AError,
+        SecuritySchemeType,
+        ApiKeyLocation,
+        ApiKeySecurityScheme,
+        HttpSecurityScheme,
+        OAuth2SecurityScheme,
+        OAuth2Flows,
+        AuthorizationCodeOAuthFlow,
+        ClientCredentialsOAuthFlow,
+        ImplicitOAuthFlow,
+        PasswordOAuthFlow,
+        OpenIdConnectSecurityScheme,
+        SecurityScheme,
+        AgentExtension,
+        AgentCapabilities,
+        AgentInterface,
+        AgentProvider,
+        AgentSkill,
+        AgentCard,
+        Task,
+        Artifact,
+        TaskArtifactUpdateEvent,
+        TaskStatusUpdateEvent,
+        TaskIdParams,
+        JSONRPCErrorResponse,
+        A2AErrorResponse,
+        MessageSendConfiguration,
+        SendMessageRequest,
+        SendMessageParams,
+        SendMessageResponse,
+        SendMessageSuccessResponse,
+        SendMessageResult,
+        StreamingMessageResult,
+        SendStreamingMessageRequest,
+        GetTaskRequest,
+        GetTaskParams,
+        GetTaskResponse,
+        CancelTaskRequest,
+        CancelTaskParams,
+        CancelTaskResponse,
+        SetTaskPushNotificationConfigRequest,
+        SetTaskPushNotificationConfigParams,
+        PushNotificationAuthenticationInfo,
+        PushNotificationConfig,
+        SetTaskPushNotificationConfigResponse,
+        PushNotificationConfigResult,
+        GetTaskPushNotificationConfigRequest,
+        GetTaskPushNotificationConfigParams,
+        GetTaskPushNotificationConfigResponse,
+        TaskResubscriptionRequest,
+        TaskResubscriptionParams,
+        TaskResubscriptionResponse,
+        ListTaskPushNotificationConfigRequest,
+        ListTaskPushNotificationConfigParams,
+        ListTaskPushNotificationConfigResponse,
+        PushNotificationConfigInfo,
+        DeleteTaskPushNotificationConfigRequest,
+        DeleteTaskPushNotificationConfigParams,
+        DeleteTaskPushNotificationConfigResponse,
+        A2ARequest,
+    );
+    #[cfg(feature = "spec-0-3")]
+    register!(generator, AgentCardSignature);
+    #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+    register!(generator, ListTasksRequest, ListTasksParams, ListTasksResult, ListTasksResponse);
+
+    serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$defs": generator.take_definitions(true),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a validator for one type from the exported bundle.
+    fn validator_for(name: &str) -> jsonschema::Validator {
+        let mut bundle = export_all();
+        bundle["$ref"] = serde_json::json!(format!("#/$defs/{}", name));
+        jsonschema::validator_for(&bundle).unwrap()
+    }
+
+    #[test]
+    fn test_export_all_contains_definitions() {
+        let bundle = export_all();
+        let defs = bundle["$defs"].as_object().unwrap();
+
+        for name in ["Message", "Part", "Task", "AgentCard", "A2AError", "SendMessageRequest"] {
+            assert!(defs.contains_key(name), "missing definition for {}", name);
+        }
+
+        // Internally tagged Part uses the kind discriminator
+        let part = serde_json::to_string(&defs["Part"]).unwrap();
+        assert!(part.contains("\"kind\""));
+
+        // Untagged unions are documented as oneOf
+        assert!(defs["FileContent"].get("oneOf").is_some());
+        assert!(defs["A2AError"].get("oneOf").is_some());
+
+        // Renamed fields use their wire names
+        let message = serde_json::to_string(&defs["Message"]).unwrap();
+        assert!(message.contains("messageId"));
+        assert!(!message.contains("message_id"));
+    }
+
+    #[test]
+    fn test_message_validates_against_schema() {
+        let validator = validator_for("Message");
+
+        let message = serde_json::json!({
+            "kind": "message",
+            "messageId": "msg-1",
+            "parts": [
+                {"kind": "text", "text": "Hello"},
+                {"kind": "file", "file": {"uri": "https://example.com/a.png"}},
+                {"kind": "data", "data": {"k": 1}}
+            ],
+            "role": "user"
+        });
+        assert!(validator.is_valid(&message));
+
+        let invalid = serde_json::json!({
+            "kind": "message",
+            "messageId": "msg-1",
+            "parts": [{"kind": "video", "url": "x"}],
+            "role": "user"
+        });
+        assert!(!validator.is_valid(&invalid));
+    }
+
+    #[test]
+    fn test_agent_card_validates_against_schema() {
+        let validator = validator_for("AgentCard");
+
+        let card = AgentCard::new(
+            "Test Agent".to_string(),
+            "A test agent".to_string(),
+            "1.0.0".to_string(),
+            "https://example.com/agent".to_string(),
+            AgentCapabilities {
+                extensions: None,
+                push_notifications: Some(true),
+                state_transition_history: None,
+                streaming: Some(true),
+            },
+            vec!["text/plain".to_string()],
+            vec!["text/plain".to_string()],
+            vec![],
+        );
+        assert!(validator.is_valid(&serde_json::to_value(&card).unwrap()));
+
+        let mut invalid = serde_json::to_value(&card).unwrap();
+        invalid.as_object_mut().unwrap().remove("capabilities");
+        assert!(!validator.is_valid(&invalid));
+    }
+}
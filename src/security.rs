@@ -0,0 +1,1005 @@
+//! Security schemes and OAuth2 flows declared by agent cards.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Security scheme types supported by the A2A protocol.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub enum SecuritySchemeType {
+    /// API key security scheme.
+    ApiKey,
+    /// HTTP security scheme.
+    Http,
+    /// OAuth2 security scheme.
+    OAuth2,
+    /// OpenID Connect security scheme.
+    OpenIdConnect,
+}
+
+/// Locations where an API key can be provided.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub enum ApiKeyLocation {
+    /// API key in a cookie.
+    Cookie,
+    /// API key in a header.
+    Header,
+    /// API key in a query parameter.
+    Query,
+}
+
+/// API Key security scheme.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ApiKeySecurityScheme {
+    /// The type of the security scheme.
+    #[serde(rename = "type")]
+    pub type_: String, // Always "apiKey"
+    /// The location of the API key.
+    #[serde(rename = "in")]
+    pub in_: ApiKeyLocation,
+    /// The name of the header, query, or cookie parameter.
+    pub name: String,
+    /// Description of this security scheme.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl ApiKeySecurityScheme {
+    /// Create a new API Key security scheme.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_` - The location of the API key.
+    /// * `name` - The name of the parameter.
+    ///
+    /// # Returns
+    ///
+    /// A new `ApiKeySecurityScheme`.
+    pub fn new(in_: ApiKeyLocation, name: String) -> Self {
+        Self {
+            type_: "apiKey".to_string(),
+            in_,
+            name,
+            description: None,
+        }
+    }
+
+    /// Validate the API Key security scheme.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.type_ != "apiKey" {
+            return Err("API Key security scheme type must be 'apiKey'".to_string());
+        }
+
+        if self.name.is_empty() {
+            return Err("API Key parameter name cannot be empty".to_string());
+        }
+
+        // Validate parameter name based on location
+        match self.in_ {
+            ApiKeyLocation::Header => {
+                if self.name.contains(' ') {
+                    return Err("Header names cannot contain spaces".to_string());
+                }
+                if self.name.to_lowercase() == "authorization" {
+                    return Err("Use HTTP security scheme for Authorization header".to_string());
+                }
+            }
+            ApiKeyLocation::Query => {
+                if self.name.contains(' ') || self.name.contains('&') || self.name.contains('=') {
+                    return Err("Query parameter names cannot contain spaces, &, or =".to_string());
+                }
+            }
+            ApiKeyLocation::Cookie => {
+                if self.name.contains(' ') || self.name.contains(';') || self.name.contains('=') {
+                    return Err("Cookie names cannot contain spaces, ;, or =".to_string());
+                }
+            }
+        }
+
+        // Validate description length if present
+        if let Some(desc) = &self.description
+            && desc.len() > 500
+        {
+            return Err("Security scheme description is too long (max 500 characters)".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Place an API key where this scheme expects it.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The API key.
+    ///
+    /// # Returns
+    ///
+    /// The header, query parameter or cookie to send, named as the scheme
+    /// declares. Values are not percent-encoded; leave that to the HTTP
+    /// client's query and cookie handling.
+    pub fn apply(&self, key: &str) -> CredentialPlacement {
+        let (name, value) = (self.name.clone(), key.to_string());
+        match self.in_ {
+            ApiKeyLocation::Header => CredentialPlacement::Header { name, value },
+            ApiKeyLocation::Query => CredentialPlacement::Query { name, value },
+            ApiKeyLocation::Cookie => CredentialPlacement::Cookie { name, value },
+        }
+    }
+}
+
+/// HTTP security scheme.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub struct HttpSecurityScheme {
+    /// The type of the security scheme.
+    #[serde(rename = "type")]
+    pub type_: String, // Always "http"
+    /// The name of the HTTP Authorization scheme.
+    pub scheme: String,
+    /// A hint to the client to identify how the bearer token is formatted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bearer_format: Option<String>,
+    /// Description of this security scheme.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl HttpSecurityScheme {
+    /// Create a new HTTP security scheme.
+    ///
+    /// # Arguments
+    ///
+    /// * `scheme` - The HTTP Authorization scheme name.
+    ///
+    /// # Returns
+    ///
+    /// A new `HttpSecurityScheme`.
+    pub fn new(scheme: String) -> Self {
+        Self {
+            type_: "http".to_string(),
+            scheme,
+            bearer_format: None,
+            description: None,
+        }
+    }
+
+    /// Create a new Bearer HTTP security scheme.
+    ///
+    /// # Arguments
+    ///
+    /// * `bearer_format` - Optional bearer token format hint.
+    ///
+    /// # Returns
+    ///
+    /// A new `HttpSecurityScheme` configured for Bearer tokens.
+    pub fn bearer(bearer_format: Option<String>) -> Self {
+        Self {
+            type_: "http".to_string(),
+            scheme: "bearer".to_string(),
+            bearer_format,
+            description: None,
+        }
+    }
+
+    /// Validate the HTTP security scheme.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.type_ != "http" {
+            return Err("HTTP security scheme type must be 'http'".to_string());
+        }
+
+        if self.scheme.is_empty() {
+            return Err("HTTP scheme name cannot be empty".to_string());
+        }
+
+        // Validate common HTTP authentication schemes
+        let valid_schemes = ["basic", "bearer", "digest", "negotiate", "ntlm"];
+        let scheme_lower = self.scheme.to_lowercase();
+
+        if !valid_schemes.contains(&scheme_lower.as_str()) && !scheme_lower.starts_with("x-") {
+            return Err(format!("Unknown HTTP authentication scheme: {}", self.scheme));
+        }
+
+        // Validate bearer format if present
+        if let Some(ref format) = self.bearer_format {
+            if self.scheme.to_lowercase() != "bearer" {
+                return Err("Bearer format can only be specified for bearer scheme".to_string());
+            }
+            if format.is_empty() {
+                return Err("Bearer format cannot be empty if specified".to_string());
+            }
+        }
+
+        // Validate description length if present
+        if let Some(desc) = &self.description
+            && desc.len() > 500
+        {
+            return Err("Security scheme description is too long (max 500 characters)".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Build the `Authorization` header value for a credential.
+    ///
+    /// The scheme name is matched case-insensitively and written in its
+    /// conventional casing, e.g. `Bearer` for a card declaring `bearer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `credential` - A token for `bearer`, or a username and password
+    ///   for `basic`.
+    ///
+    /// # Returns
+    ///
+    /// The header value, or `Err(String)` if the credential does not fit
+    /// the scheme or the scheme is not `basic` or `bearer`.
+    pub fn authorization_value(&self, credential: &Credential) -> Result<String, String> {
+        match (self.scheme.to_ascii_lowercase().as_str(), credential) {
+            ("bearer", Credential::Token(token)) => Ok(format!("Bearer {}", token)),
+            ("basic", Credential::Basic { username, password }) => {
+                if username.contains(':') {
+                    return Err("Basic auth username cannot contain ':'".to_string());
+                }
+                Ok(format!("Basic {}", base64_encode(format!("{}:{}", username, password).as_bytes())))
+            }
+            ("bearer", _) => Err("HTTP bearer authentication needs a token credential".to_string()),
+            ("basic", _) => Err("HTTP basic authentication needs a username and password".to_string()),
+            _ => Err(format!("Unsupported HTTP authentication scheme: {}", self.scheme)),
+        }
+    }
+}
+
+/// OAuth2 security scheme.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub struct OAuth2SecurityScheme {
+    /// The type of the security scheme.
+    #[serde(rename = "type")]
+    pub type_: String, // Always "oauth2"
+    /// The available flows for the OAuth2 security scheme.
+    pub flows: OAuth2Flows,
+    /// Description of this security scheme.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl OAuth2SecurityScheme {
+    /// Create a new OAuth2 security scheme.
+    ///
+    /// # Arguments
+    ///
+    /// * `flows` - The OAuth2 flows configuration.
+    ///
+    /// # Returns
+    ///
+    /// A new `OAuth2SecurityScheme`.
+    pub fn new(flows: OAuth2Flows) -> Self {
+        Self {
+            type_: "oauth2".to_string(),
+            flows,
+            description: None,
+        }
+    }
+
+    /// Validate the OAuth2 security scheme.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.type_ != "oauth2" {
+            return Err("OAuth2 security scheme type must be 'oauth2'".to_string());
+        }
+
+        // Validate that at least one flow is defined
+        if self.flows.implicit.is_none() 
+            && self.flows.password.is_none() 
+            && self.flows.client_credentials.is_none() 
+            && self.flows.authorization_code.is_none() {
+            return Err("OAuth2 security scheme must define at least one flow".to_string());
+        }
+
+        // Validate each defined flow
+        if let Some(ref flow) = self.flows.implicit {
+            flow.validate().map_err(|e| format!("Invalid implicit flow: {}", e))?;
+        }
+
+        if let Some(ref flow) = self.flows.password {
+            flow.validate().map_err(|e| format!("Invalid password flow: {}", e))?;
+        }
+
+        if let Some(ref flow) = self.flows.client_credentials {
+            flow.validate().map_err(|e| format!("Invalid client credentials flow: {}", e))?;
+        }
+
+        if let Some(ref flow) = self.flows.authorization_code {
+            flow.validate().map_err(|e| format!("Invalid authorization code flow: {}", e))?;
+        }
+
+        // Validate description length if present
+        if let Some(desc) = &self.description
+            && desc.len() > 500
+        {
+            return Err("Security scheme description is too long (max 500 characters)".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Check if the OAuth2 scheme supports client-only flows.
+    ///
+    /// # Returns
+    ///
+    /// `true` if client-only flows are supported.
+    pub fn supports_client_only_flows(&self) -> bool {
+        self.flows.client_credentials.is_some()
+    }
+
+    /// Check if the OAuth2 scheme requires user interaction.
+    ///
+    /// # Returns
+    ///
+    /// `true` if user interaction is required.
+    pub fn requires_user_interaction(&self) -> bool {
+        self.flows.implicit.is_some() || self.flows.authorization_code.is_some()
+    }
+
+    /// Get the scopes a flow offers.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The flow.
+    ///
+    /// # Returns
+    ///
+    /// The scopes with their descriptions, or `None` if the scheme does not
+    /// define the flow.
+    pub fn scopes_for_flow(&self, kind: OAuthFlowKind) -> Option<&crate::Map<String, String>> {
+        match kind {
+            OAuthFlowKind::AuthorizationCode => self.flows.authorization_code.as_ref().map(|flow| &flow.scopes),
+            OAuthFlowKind::ClientCredentials => self.flows.client_credentials.as_ref().map(|flow| &flow.scopes),
+            OAuthFlowKind::Implicit => self.flows.implicit.as_ref().map(|flow| &flow.scopes),
+            OAuthFlowKind::Password => self.flows.password.as_ref().map(|flow| &flow.scopes),
+        }
+    }
+
+    /// Check if any flow of the scheme declares a scope.
+    pub fn declares_scope(&self, scope: &str) -> bool {
+        OAuthFlowKind::ALL
+            .iter()
+            .filter_map(|kind| self.scopes_for_flow(*kind))
+            .any(|scopes| scopes.contains_key(scope))
+    }
+}
+
+/// The OAuth2 flows a scheme can define.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OAuthFlowKind {
+    /// The authorization code flow.
+    AuthorizationCode,
+    /// The client credentials flow.
+    ClientCredentials,
+    /// The implicit flow.
+    Implicit,
+    /// The resource owner password flow.
+    Password,
+}
+
+impl OAuthFlowKind {
+    /// Every flow kind.
+    pub const ALL: [OAuthFlowKind; 4] = [
+        OAuthFlowKind::AuthorizationCode,
+        OAuthFlowKind::ClientCredentials,
+        OAuthFlowKind::Implicit,
+        OAuthFlowKind::Password,
+    ];
+}
+
+/// OAuth2 flows.
+///
+/// The default has no flows configured.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub struct OAuth2Flows {
+    /// The implicit flow.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub implicit: Option<ImplicitOAuthFlow>,
+    /// The password flow.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<PasswordOAuthFlow>,
+    /// The client credentials flow.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_credentials: Option<ClientCredentialsOAuthFlow>,
+    /// The authorization code flow.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_code: Option<AuthorizationCodeOAuthFlow>,
+}
+
+/// Authorization Code OAuth flow.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizationCodeOAuthFlow {
+    /// The authorization URL to be used for this flow.
+    pub authorization_url: String,
+    /// The token URL to be used for this flow.
+    pub token_url: String,
+    /// The URL to be used for obtaining refresh tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_url: Option<String>,
+    /// The available scopes for the OAuth2 security scheme.
+    pub scopes: crate::Map<String, String>,
+}
+
+impl AuthorizationCodeOAuthFlow {
+    /// Create a new Authorization Code OAuth flow.
+    ///
+    /// # Arguments
+    ///
+    /// * `authorization_url` - The authorization URL.
+    /// * `token_url` - The token URL.
+    /// * `scopes` - The available scopes.
+    ///
+    /// # Returns
+    ///
+    /// A new `AuthorizationCodeOAuthFlow`.
+    pub fn new(
+        authorization_url: String,
+        token_url: String,
+        scopes: crate::Map<String, String>,
+    ) -> Self {
+        Self {
+            authorization_url,
+            token_url,
+            refresh_url: None,
+            scopes,
+        }
+    }
+
+    /// Validate the OAuth flow configuration.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        crate::validation::validate_url(&self.authorization_url)
+            .map_err(|e| format!("Invalid authorization URL: {}", e))?;
+
+        crate::validation::validate_url(&self.token_url)
+            .map_err(|e| format!("Invalid token URL: {}", e))?;
+
+        if let Some(ref refresh_url) = self.refresh_url {
+            crate::validation::validate_url(refresh_url)
+                .map_err(|e| format!("Invalid refresh URL: {}", e))?;
+        }
+
+        if self.scopes.is_empty() {
+            return Err("OAuth2 flow must define at least one scope".to_string());
+        }
+
+        // Validate scope names and descriptions
+        for (scope_name, scope_desc) in &self.scopes {
+            if scope_name.is_empty() {
+                return Err("OAuth2 scope name cannot be empty".to_string());
+            }
+            if scope_desc.is_empty() {
+                return Err("OAuth2 scope description cannot be empty".to_string());
+            }
+            if scope_name.contains(' ') {
+                return Err("OAuth2 scope names cannot contain spaces".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Client Credentials OAuth flow.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub struct ClientCredentialsOAuthFlow {
+    /// The token URL to be used for this flow.
+    pub token_url: String,
+    /// The URL to be used for obtaining refresh tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_url: Option<String>,
+    /// The available scopes for the OAuth2 security scheme.
+    pub scopes: crate::Map<String, String>,
+}
+
+impl ClientCredentialsOAuthFlow {
+    /// Create a new Client Credentials OAuth flow.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_url` - The token URL.
+    /// * `scopes` - The available scopes.
+    ///
+    /// # Returns
+    ///
+    /// A new `ClientCredentialsOAuthFlow`.
+    pub fn new(
+        token_url: String,
+        scopes: crate::Map<String, String>,
+    ) -> Self {
+        Self {
+            token_url,
+            refresh_url: None,
+            scopes,
+        }
+    }
+
+    /// Validate the OAuth flow configuration.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        crate::validation::validate_url(&self.token_url)
+            .map_err(|e| format!("Invalid token URL: {}", e))?;
+
+        if let Some(ref refresh_url) = self.refresh_url {
+            crate::validation::validate_url(refresh_url)
+                .map_err(|e| format!("Invalid refresh URL: {}", e))?;
+        }
+
+        if self.scopes.is_empty() {
+            return Err("OAuth2 flow must define at least one scope".to_string());
+        }
+
+        // Validate scope names and descriptions
+        for (scope_name, scope_desc) in &self.scopes {
+            if scope_name.is_empty() {
+                return Err("OAuth2 scope name cannot be empty".to_string());
+            }
+            if scope_desc.is_empty() {
+                return Err("OAuth2 scope description cannot be empty".to_string());
+            }
+            if scope_name.contains(' ') {
+                return Err("OAuth2 scope names cannot contain spaces".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Implicit OAuth flow.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub struct ImplicitOAuthFlow {
+    /// The authorization URL to be used for this flow.
+    pub authorization_url: String,
+    /// The URL to be used for obtaining refresh tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_url: Option<String>,
+    /// The available scopes for the OAuth2 security scheme.
+    pub scopes: crate::Map<String, String>,
+}
+
+impl ImplicitOAuthFlow {
+    /// Create a new Implicit OAuth flow.
+    ///
+    /// # Arguments
+    ///
+    /// * `authorization_url` - The authorization URL.
+    /// * `scopes` - The available scopes.
+    ///
+    /// # Returns
+    ///
+    /// A new `ImplicitOAuthFlow`.
+    pub fn new(
+        authorization_url: String,
+        scopes: crate::Map<String, String>,
+    ) -> Self {
+        Self {
+            authorization_url,
+            refresh_url: None,
+            scopes,
+        }
+    }
+
+    /// Validate the OAuth flow configuration.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        crate::validation::validate_url(&self.authorization_url)
+            .map_err(|e| format!("Invalid authorization URL: {}", e))?;
+
+        if let Some(ref refresh_url) = self.refresh_url {
+            crate::validation::validate_url(refresh_url)
+                .map_err(|e| format!("Invalid refresh URL: {}", e))?;
+        }
+
+        if self.scopes.is_empty() {
+            return Err("OAuth2 flow must define at least one scope".to_string());
+        }
+
+        // Validate scope names and descriptions
+        for (scope_name, scope_desc) in &self.scopes {
+            if scope_name.is_empty() {
+                return Err("OAuth2 scope name cannot be empty".to_string());
+            }
+            if scope_desc.is_empty() {
+                return Err("OAuth2 scope description cannot be empty".to_string());
+            }
+            if scope_name.contains(' ') {
+                return Err("OAuth2 scope names cannot contain spaces".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Password OAuth flow.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordOAuthFlow {
+    /// The token URL to be used for this flow.
+    pub token_url: String,
+    /// The URL to be used for obtaining refresh tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_url: Option<String>,
+    /// The available scopes for the OAuth2 security scheme.
+    pub scopes: crate::Map<String, String>,
+}
+
+impl PasswordOAuthFlow {
+    /// Create a new Password OAuth flow.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_url` - The token URL.
+    /// * `scopes` - The available scopes.
+    ///
+    /// # Returns
+    ///
+    /// A new `PasswordOAuthFlow`.
+    pub fn new(
+        token_url: String,
+        scopes: crate::Map<String, String>,
+    ) -> Self {
+        Self {
+            token_url,
+            refresh_url: None,
+            scopes,
+        }
+    }
+
+    /// Validate the OAuth flow configuration.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        crate::validation::validate_url(&self.token_url)
+            .map_err(|e| format!("Invalid token URL: {}", e))?;
+
+        if let Some(ref refresh_url) = self.refresh_url {
+            crate::validation::validate_url(refresh_url)
+                .map_err(|e| format!("Invalid refresh URL: {}", e))?;
+        }
+
+        if self.scopes.is_empty() {
+            return Err("OAuth2 flow must define at least one scope".to_string());
+        }
+
+        // Validate scope names and descriptions
+        for (scope_name, scope_desc) in &self.scopes {
+            if scope_name.is_empty() {
+                return Err("OAuth2 scope name cannot be empty".to_string());
+            }
+            if scope_desc.is_empty() {
+                return Err("OAuth2 scope description cannot be empty".to_string());
+            }
+            if scope_name.contains(' ') {
+                return Err("OAuth2 scope names cannot contain spaces".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// OpenID Connect security scheme.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub struct OpenIdConnectSecurityScheme {
+    /// The type of the security scheme.
+    #[serde(rename = "type")]
+    pub type_: String, // Always "openIdConnect"
+    /// OpenId Connect URL to discover OAuth2 configuration values.
+    pub open_id_connect_url: String,
+    /// Description of this security scheme.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl OpenIdConnectSecurityScheme {
+    /// Create a new OpenID Connect security scheme.
+    ///
+    /// # Arguments
+    ///
+    /// * `open_id_connect_url` - The OpenID Connect discovery URL.
+    ///
+    /// # Returns
+    ///
+    /// A new `OpenIdConnectSecurityScheme`.
+    pub fn new(open_id_connect_url: String) -> Self {
+        Self {
+            type_: "openIdConnect".to_string(),
+            open_id_connect_url,
+            description: None,
+        }
+    }
+
+    /// Validate the OpenID Connect security scheme.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.type_ != "openIdConnect" {
+            return Err("OpenID Connect security scheme type must be 'openIdConnect'".to_string());
+        }
+
+        // Validate the OpenID Connect URL
+        crate::validation::validate_url(&self.open_id_connect_url)
+            .map_err(|e| format!("Invalid OpenID Connect URL: {}", e))?;
+
+        // Validate that it's HTTPS (required for OpenID Connect)
+        if !self.open_id_connect_url.starts_with("https://") {
+            return Err("OpenID Connect URL must use HTTPS".to_string());
+        }
+
+        // Validate common OpenID Connect discovery endpoint patterns
+        if !self.open_id_connect_url.contains("/.well-known/openid_configuration") 
+            && !self.open_id_connect_url.contains("/.well-known/openid-configuration") {
+            return Err("OpenID Connect URL should point to a well-known configuration endpoint".to_string());
+        }
+
+        // Validate description length if present
+        if let Some(desc) = &self.description
+            && desc.len() > 500
+        {
+            return Err("Security scheme description is too long (max 500 characters)".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Get the base URL for the OpenID Connect provider.
+    ///
+    /// # Returns
+    ///
+    /// The base URL of the OpenID Connect provider.
+    pub fn get_provider_base_url(&self) -> String {
+        if let Some(pos) = self.open_id_connect_url.find("/.well-known/") {
+            self.open_id_connect_url[..pos].to_string()
+        } else {
+            self.open_id_connect_url.clone()
+        }
+    }
+}
+
+/// Security scheme.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schema", schemars(transform = crate::schema::one_of))]
+#[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
+pub enum SecurityScheme {
+    /// API key security scheme.
+    ApiKey(ApiKeySecurityScheme),
+    /// HTTP security scheme.
+    Http(HttpSecurityScheme),
+    /// OAuth2 security scheme.
+    OAuth2(OAuth2SecurityScheme),
+    /// OpenID Connect security scheme.
+    OpenIdConnect(OpenIdConnectSecurityScheme),
+}
+
+impl SecurityScheme {
+    /// Validate the security scheme configuration.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            SecurityScheme::ApiKey(scheme) => scheme.validate(),
+            SecurityScheme::Http(scheme) => scheme.validate(),
+            SecurityScheme::OAuth2(scheme) => scheme.validate(),
+            SecurityScheme::OpenIdConnect(scheme) => scheme.validate(),
+        }
+    }
+
+    /// Get the security scheme type as a string.
+    ///
+    /// # Returns
+    ///
+    /// The security scheme type.
+    pub fn scheme_type(&self) -> &str {
+        match self {
+            SecurityScheme::ApiKey(_) => "apiKey",
+            SecurityScheme::Http(_) => "http",
+            SecurityScheme::OAuth2(_) => "oauth2",
+            SecurityScheme::OpenIdConnect(_) => "openIdConnect",
+        }
+    }
+
+    /// Turn a credential into the value to send with requests.
+    ///
+    /// API keys go where the scheme says, HTTP schemes produce an
+    /// `Authorization` header, and OAuth2 and OpenID Connect access tokens
+    /// are sent as bearer tokens. Nothing here performs HTTP, so the result
+    /// can be applied with any client library.
+    ///
+    /// # Arguments
+    ///
+    /// * `credential` - The credential for this scheme.
+    ///
+    /// # Returns
+    ///
+    /// Where and what to send, or `Err(String)` if the credential does not
+    /// fit the scheme.
+    pub fn apply_credential(&self, credential: &Credential) -> Result<CredentialPlacement, String> {
+        match (self, credential) {
+            (SecurityScheme::ApiKey(scheme), Credential::Token(key)) => Ok(scheme.apply(key)),
+            (SecurityScheme::ApiKey(_), Credential::Basic { .. }) => {
+                Err("API key schemes need a token credential".to_string())
+            }
+            (SecurityScheme::Http(scheme), _) => Ok(CredentialPlacement::authorization(
+                scheme.authorization_value(credential)?,
+            )),
+            (SecurityScheme::OAuth2(_) | SecurityScheme::OpenIdConnect(_), Credential::Token(token)) => {
+                Ok(CredentialPlacement::authorization(format!("Bearer {}", token)))
+            }
+            (SecurityScheme::OAuth2(_) | SecurityScheme::OpenIdConnect(_), Credential::Basic { .. }) => {
+                Err(format!("{} schemes need an access token credential", self.scheme_type()))
+            }
+        }
+    }
+
+    /// Check if the security scheme requires user interaction.
+    ///
+    /// # Returns
+    ///
+    /// `true` if user interaction is required.
+    pub fn requires_user_interaction(&self) -> bool {
+        match self {
+            SecurityScheme::ApiKey(_) => false,
+            SecurityScheme::Http(_) => false,
+            SecurityScheme::OAuth2(scheme) => {
+                // OAuth2 flows that require user interaction
+                scheme.flows.implicit.is_some() || scheme.flows.authorization_code.is_some()
+            }
+            SecurityScheme::OpenIdConnect(_) => true,
+        }
+    }
+}
+
+/// A secret for authenticating with an agent.
+///
+/// The `Debug` output hides the secret. With the `zeroize` feature it is
+/// wiped from memory on drop.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Credential {
+    /// An API key, or a bearer, OAuth2 or OpenID Connect access token.
+    Token(String),
+    /// A username and password for HTTP basic authentication.
+    Basic {
+        /// The username, which cannot contain `:`.
+        username: String,
+        /// The password.
+        password: String,
+    },
+}
+
+impl core::fmt::Debug for Credential {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Credential::Token(_) => f.write_str("Token(***)"),
+            Credential::Basic { username, .. } => {
+                f.debug_struct("Basic").field("username", username).field("password", &"***").finish()
+            }
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Credential {
+    fn zeroize(&mut self) {
+        match self {
+            Credential::Token(token) => token.zeroize(),
+            Credential::Basic { username, password } => {
+                username.zeroize();
+                password.zeroize();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Credential {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Credential {}
+
+/// Where a credential goes in an HTTP request, and its value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialPlacement {
+    /// A request header.
+    Header { name: String, value: String },
+    /// A query parameter.
+    Query { name: String, value: String },
+    /// A cookie, sent as `name=value` in the `Cookie` header.
+    Cookie { name: String, value: String },
+}
+
+impl CredentialPlacement {
+    fn authorization(value: String) -> Self {
+        CredentialPlacement::Header {
+            name: "Authorization".to_string(),
+            value,
+        }
+    }
+}
+
+/// Standard base64 with padding, as HTTP basic authentication and file
+/// bytes require.
+pub(crate) fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], chunk.get(1).copied().unwrap_or(0), chunk.get(2).copied().unwrap_or(0)];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 63] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
@@ -0,0 +1,210 @@
+//! Cheaply clonable tasks.
+//!
+//! Servers hand the same task to a store, a response and push notifications,
+//! and cloning a [`Task`] deep-copies every message and artifact. A
+//! [`SharedTask`] keeps its history and artifacts behind [`Arc`]s, so
+//! cloning it copies a few pointers per message instead of their parts,
+//! while updating the status or appending to the history leaves existing
+//! entries shared. All types serialize exactly like the types they wrap.
+
+use crate::{A2AErrorResponse, Artifact, ExtraFields, Message, Task, TaskStatus};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::ops::Deref;
+use std::sync::Arc;
+
+macro_rules! shared_wrapper {
+    ($(#[$doc:meta])* $name:ident($inner:ty)) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $name(Arc<$inner>);
+
+        impl $name {
+            /// Get a mutable reference, copying the value first if it is
+            /// shared.
+            pub fn make_mut(&mut self) -> &mut $inner {
+                Arc::make_mut(&mut self.0)
+            }
+
+            /// Take the value out, copying it only if it is shared.
+            pub fn into_inner(self) -> $inner {
+                Arc::unwrap_or_clone(self.0)
+            }
+
+            /// Check if two values share the same allocation.
+            pub fn ptr_eq(&self, other: &Self) -> bool {
+                Arc::ptr_eq(&self.0, &other.0)
+            }
+        }
+
+        impl Deref for $name {
+            type Target = $inner;
+
+            fn deref(&self) -> &$inner {
+                &self.0
+            }
+        }
+
+        impl From<$inner> for $name {
+            fn from(value: $inner) -> Self {
+                Self(Arc::new(value))
+            }
+        }
+
+        impl From<$name> for $inner {
+            fn from(value: $name) -> Self {
+                value.into_inner()
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.0.serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                <$inner>::deserialize(deserializer).map(Self::from)
+            }
+        }
+    };
+}
+
+shared_wrapper! {
+    /// A [`Message`] whose clones share one allocation.
+    SharedMessage(Message)
+}
+
+shared_wrapper! {
+    /// An [`Artifact`] whose clones share one allocation.
+    SharedArtifact(Artifact)
+}
+
+/// A [`Task`] whose history and artifacts are shared between clones.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedTask {
+    /// The ID of the task
+    pub id: String,
+    /// Event type
+    pub kind: String, // Always "task"
+    /// The status of the task
+    pub status: TaskStatus,
+    /// Server-generated id for contextual alignment across interactions
+    #[serde(rename = "contextId")]
+    pub context_id: String,
+    /// Collection of artifacts created by the agent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<Vec<SharedArtifact>>,
+    /// Message history for the task
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<Vec<SharedMessage>>,
+    /// Extension metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+    /// The result of the task, if completed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    /// The error that occurred, if the task failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<A2AErrorResponse>,
+    /// The time the task was created
+    #[serde(rename = "createdAt", skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    /// The time the task was last updated
+    #[serde(rename = "updatedAt", skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    /// The history of status changes for this task
+    #[serde(rename = "statusHistory", skip_serializing_if = "Option::is_none")]
+    pub status_history: Option<Vec<TaskStatus>>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+impl From<Task> for SharedTask {
+    fn from(task: Task) -> Self {
+        Self {
+            id: task.id,
+            kind: task.kind,
+            status: task.status,
+            context_id: task.context_id,
+            artifacts: task.artifacts.map(|artifacts| artifacts.into_iter().map(SharedArtifact::from).collect()),
+            history: task.history.map(|history| history.into_iter().map(SharedMessage::from).collect()),
+            metadata: task.metadata,
+            result: task.result,
+            error: task.error,
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            status_history: task.status_history,
+            extra: task.extra,
+        }
+    }
+}
+
+impl From<SharedTask> for Task {
+    fn from(task: SharedTask) -> Self {
+        Self {
+            id: task.id,
+            kind: task.kind,
+            status: task.status,
+            context_id: task.context_id,
+            artifacts: task.artifacts.map(|artifacts| artifacts.into_iter().map(Artifact::from).collect()),
+            history: task.history.map(|history| history.into_iter().map(Message::from).collect()),
+            metadata: task.metadata,
+            result: task.result,
+            error: task.error,
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            status_history: task.status_history,
+            extra: task.extra,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Part, TaskState};
+
+    fn task() -> Task {
+        let mut task: Task = serde_json::from_value(serde_json::json!({
+            "kind": "task",
+            "id": "task-1",
+            "contextId": "ctx-1",
+            "status": {"state": "working"},
+            "futureField": true
+        }))
+        .unwrap();
+        task.history = Some(vec![Message::user("msg-1".to_string(), ["Render the report"])]);
+        task.artifacts = Some(vec![Artifact::minimal("report".to_string(), vec![Part::from("x".repeat(1024))])]);
+        task
+    }
+
+    #[test]
+    fn test_serializes_like_task() {
+        let task = task();
+        let shared = SharedTask::from(task.clone());
+        let json = serde_json::to_value(&shared).unwrap();
+        assert_eq!(json, serde_json::to_value(&task).unwrap());
+
+        let parsed: SharedTask = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, shared);
+        assert_eq!(Task::from(parsed), task);
+    }
+
+    #[test]
+    fn test_clones_share_messages_and_artifacts() {
+        let shared = SharedTask::from(task());
+        let mut clone = shared.clone();
+        assert_eq!(clone, shared);
+        assert!(clone.artifacts.as_ref().unwrap()[0].ptr_eq(&shared.artifacts.as_ref().unwrap()[0]));
+
+        // Changing the clone leaves the original alone and the rest shared
+        clone.status.state = TaskState::Completed;
+        clone.history.as_mut().unwrap()[0].make_mut().message_id = "msg-2".to_string();
+        assert_eq!(shared.status.state, TaskState::Working);
+        assert_eq!(shared.history.as_ref().unwrap()[0].message_id, "msg-1");
+        assert!(clone.artifacts.as_ref().unwrap()[0].ptr_eq(&shared.artifacts.as_ref().unwrap()[0]));
+        assert_ne!(clone, shared);
+    }
+}
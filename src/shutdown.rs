@@ -0,0 +1,349 @@
+//! Graceful shutdown of servers.
+//!
+//! A server asked to stop, e.g. on `SIGTERM`, should let the requests in
+//! flight finish, answer new ones with an error clients retry later or
+//! elsewhere, and leave no task hanging in `working`. A
+//! [`ShutdownController`] coordinates this:
+//!
+//! 1. Every request passes [`ShutdownController::admit`] before it is
+//!    handled, e.g. first thing in the handler given to
+//!    [`IncomingRequest::dispatch`](crate::IncomingRequest::dispatch). While
+//!    the server shuts down this fails with an `Internal` error marked
+//!    retryable; otherwise the [`RequestGuard`] it returns counts the
+//!    request as in flight until it is dropped.
+//! 2. Handlers name the task they work on with
+//!    [`RequestGuard::track_task`] and watch [`RequestGuard::cancelled`] to
+//!    wrap up early, ending their stream with a final status update.
+//! 3. The server's shutdown hook calls
+//!    [`TaskManager::shutdown`](crate::manager::TaskManager::shutdown), or
+//!    [`ShutdownController::begin_shutdown`] without a manager. It waits for
+//!    the requests in flight up to a grace period, then fails or cancels the
+//!    tasks still being worked on and collects the push notifications about
+//!    them. Handlers still running are not stopped, but the manager refuses
+//!    their later saves of those tasks, so the forced outcome stands.
+//!
+//! The controller does not depend on a web framework: clone it into the
+//! request handlers and call it from whatever shutdown hook the server has,
+//! such as the future given to axum's `with_graceful_shutdown`.
+//!
+//! ```
+//! use a2a_rs::shutdown::ShutdownController;
+//! use std::time::Duration;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let shutdown = ShutdownController::new();
+//! let mut guard = shutdown.admit().unwrap();
+//! guard.track_task("task-1".into());
+//! let handler = tokio::spawn(async move {
+//!     // Work until the server shuts down, then wrap up
+//!     guard.cancelled().await;
+//! });
+//!
+//! let drain = shutdown.begin_shutdown(Duration::from_secs(5)).await;
+//! assert!(drain.drained);
+//! assert!(shutdown.admit().unwrap_err().is_retryable());
+//! # handler.await.unwrap();
+//! # }
+//! ```
+
+use crate::clock::{Clock, SystemClock};
+use crate::ids::TaskId;
+use crate::push::PushNotificationPayload;
+use crate::{A2AError, InternalError, PushNotificationConfig, Task, TaskStatusUpdateEvent};
+use std::collections::BTreeMap;
+use std::future::{Future, poll_fn};
+use std::pin::pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::Poll;
+use std::time::Duration;
+use tokio::sync::{Notify, watch};
+
+/// Admits requests until the server shuts down, and waits for those in
+/// flight when it does.
+///
+/// Clones share the same state.
+#[derive(Debug, Clone)]
+pub struct ShutdownController {
+    inner: Arc<Inner>,
+    clock: Arc<dyn Clock>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    requests: Mutex<Requests>,
+    cancel: watch::Sender<bool>,
+    idle: Notify,
+}
+
+#[derive(Debug, Default)]
+struct Requests {
+    shutting_down: bool,
+    next_id: u64,
+    /// The requests in flight in admission order, with the task each works on.
+    in_flight: BTreeMap<u64, Option<TaskId>>,
+}
+
+impl Default for ShutdownController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShutdownController {
+    /// Create a controller that admits requests.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                requests: Mutex::new(Requests::default()),
+                cancel: watch::channel(false).0,
+                idle: Notify::new(),
+            }),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Set the clock that grace periods are measured with.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    fn requests(&self) -> MutexGuard<'_, Requests> {
+        self.inner.requests.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Check if [`begin_shutdown`](Self::begin_shutdown) was called.
+    pub fn is_shutting_down(&self) -> bool {
+        self.requests().shutting_down
+    }
+
+    /// The number of requests in flight.
+    pub fn in_flight(&self) -> usize {
+        self.requests().in_flight.len()
+    }
+
+    /// Admit a request.
+    ///
+    /// # Returns
+    ///
+    /// A guard counting the request as in flight until it is dropped, or,
+    /// once the server is shutting down, an `Internal` error with
+    /// `"retryable": true` in its data, which
+    /// [`ClientError::is_retryable`](crate::client::ClientError::is_retryable)
+    /// recognizes.
+    pub fn admit(&self) -> Result<RequestGuard, A2AError> {
+        let mut requests = self.requests();
+        if requests.shutting_down {
+            return Err(A2AError::Internal(Box::new(InternalError {
+                code: -32603,
+                message: "Server is shutting down, retry the request later".to_string(),
+                data: Some(serde_json::json!({A2AError::RETRYABLE_KEY: true, "reason": "shutdown"})),
+            })));
+        }
+        let id = requests.next_id;
+        requests.next_id += 1;
+        requests.in_flight.insert(id, None);
+        Ok(RequestGuard {
+            controller: self.clone(),
+            id,
+            cancelled: self.inner.cancel.subscribe(),
+        })
+    }
+
+    /// Stop admitting requests and wait for those in flight.
+    ///
+    /// Handlers are told to wrap up through [`RequestGuard::cancelled`]
+    /// right away. Calling this again waits again, with the new grace
+    /// period.
+    ///
+    /// # Arguments
+    ///
+    /// * `grace` - How long to wait for the requests in flight.
+    ///
+    /// # Returns
+    ///
+    /// Whether every request finished in time, and the tasks of those that
+    /// did not.
+    pub async fn begin_shutdown(&self, grace: Duration) -> Drain {
+        self.requests().shutting_down = true;
+        self.inner.cancel.send_replace(true);
+
+        let mut expired = self.clock.sleep(grace);
+        loop {
+            let mut idle = pin!(self.inner.idle.notified());
+            // Register before checking, so a request finishing in between is not missed
+            idle.as_mut().enable();
+            if self.requests().in_flight.is_empty() {
+                break;
+            }
+            let woken = poll_fn(|cx| match idle.as_mut().poll(cx) {
+                Poll::Ready(()) => Poll::Ready(true),
+                Poll::Pending => expired.as_mut().poll(cx).map(|()| false),
+            });
+            if !woken.await {
+                break;
+            }
+        }
+
+        let requests = self.requests();
+        Drain {
+            drained: requests.in_flight.is_empty(),
+            stragglers: requests.in_flight.values().flatten().cloned().collect(),
+            untracked: requests.in_flight.values().filter(|task| task.is_none()).count(),
+        }
+    }
+}
+
+/// A request in flight, admitted by [`ShutdownController::admit`].
+///
+/// Dropping the guard marks the request as finished.
+#[derive(Debug)]
+pub struct RequestGuard {
+    controller: ShutdownController,
+    id: u64,
+    cancelled: watch::Receiver<bool>,
+}
+
+impl RequestGuard {
+    /// Name the task the request works on, so a shutdown that runs out of
+    /// time can fail it.
+    pub fn track_task(&mut self, task_id: TaskId) {
+        if let Some(task) = self.controller.requests().in_flight.get_mut(&self.id) {
+            *task = Some(task_id);
+        }
+    }
+
+    /// Check if the server is shutting down and the handler should wrap up.
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+
+    /// Wait until the server starts shutting down.
+    ///
+    /// Select on this next to long-running work, e.g. with
+    /// `tokio::select!`, to end the work and its stream early.
+    pub async fn cancelled(&self) {
+        let mut cancelled = self.cancelled.clone();
+        // The sender lives as long as the controller this guard holds
+        let _ = cancelled.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+impl Drop for RequestGuard {
+    fn drop(&mut self) {
+        let mut requests = self.controller.requests();
+        requests.in_flight.remove(&self.id);
+        if requests.in_flight.is_empty() {
+            self.controller.inner.idle.notify_waiters();
+        }
+    }
+}
+
+/// The requests still in flight when a shutdown's grace period ended.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Drain {
+    /// Whether every request finished within the grace period.
+    pub drained: bool,
+    /// The tasks of the requests still in flight, in admission order.
+    pub stragglers: Vec<TaskId>,
+    /// The number of requests still in flight that named no task.
+    pub untracked: usize,
+}
+
+/// What [`TaskManager::shutdown`](crate::manager::TaskManager::shutdown)
+/// did.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShutdownReport {
+    /// The requests still in flight when the grace period ended.
+    pub drain: Drain,
+    /// The tasks failed or canceled because their requests did not finish.
+    pub interrupted: Vec<Task>,
+    /// The push notifications to deliver about the interrupted tasks, with
+    /// the config each goes to.
+    pub notifications: Vec<(PushNotificationConfig, PushNotificationPayload)>,
+}
+
+impl ShutdownReport {
+    /// The final status update events of the interrupted tasks, to end
+    /// their streams that are still open.
+    pub fn final_events(&self) -> Vec<TaskStatusUpdateEvent> {
+        self.interrupted
+            .iter()
+            .map(|task| TaskStatusUpdateEvent::new(task.id.clone(), task.context_id.clone(), task.status.clone(), true))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use std::time::UNIX_EPOCH;
+
+    const GRACE: Duration = Duration::from_secs(30);
+
+    #[tokio::test]
+    async fn test_handlers_that_wrap_up_drain_in_time() {
+        let shutdown = ShutdownController::new().with_clock(MockClock::new(UNIX_EPOCH));
+        let mut guard = shutdown.admit().unwrap();
+        guard.track_task(TaskId::from("task-1"));
+        assert!(!guard.is_cancelled());
+        let handler = tokio::spawn(async move {
+            guard.cancelled().await;
+            assert!(guard.is_cancelled());
+        });
+        assert_eq!(shutdown.in_flight(), 1);
+
+        // The clock never moves, so only the handler finishing ends the wait
+        let drain = shutdown.begin_shutdown(GRACE).await;
+        assert_eq!(drain, Drain { drained: true, stragglers: vec![], untracked: 0 });
+        assert_eq!(shutdown.in_flight(), 0);
+        handler.await.unwrap();
+
+        // Nothing in flight: a second call returns at once
+        assert!(shutdown.begin_shutdown(Duration::MAX).await.drained);
+    }
+
+    #[tokio::test]
+    async fn test_new_requests_are_refused_with_a_retryable_error() {
+        let shutdown = ShutdownController::new().with_clock(MockClock::new(UNIX_EPOCH));
+        assert!(shutdown.begin_shutdown(GRACE).await.drained);
+        assert!(shutdown.is_shutting_down());
+
+        let error = shutdown.admit().unwrap_err();
+        assert!(matches!(&error, A2AError::Internal(e) if e.code == -32603));
+        assert!(error.is_retryable());
+        assert_eq!(error.data().unwrap()["reason"], "shutdown");
+    }
+
+    #[tokio::test]
+    async fn test_stragglers_are_reported_after_the_grace_period() {
+        let clock = MockClock::new(UNIX_EPOCH);
+        let shutdown = ShutdownController::new().with_clock(clock.clone());
+        let mut stuck = shutdown.admit().unwrap();
+        stuck.track_task(TaskId::from("task-stuck"));
+        let untracked = shutdown.admit().unwrap();
+        let mut quick = shutdown.admit().unwrap();
+        quick.track_task(TaskId::from("task-quick"));
+        tokio::spawn(async move {
+            quick.cancelled().await;
+        });
+
+        let drain = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.begin_shutdown(GRACE).await }
+        });
+        tokio::task::yield_now().await;
+        clock.advance(GRACE - Duration::from_millis(1));
+        tokio::task::yield_now().await;
+        assert!(!drain.is_finished());
+        assert_eq!(shutdown.in_flight(), 2);
+
+        clock.advance(Duration::from_millis(1));
+        let drain = drain.await.unwrap();
+        assert_eq!(drain, Drain { drained: false, stragglers: vec![TaskId::from("task-stuck")], untracked: 1 });
+        drop((stuck, untracked));
+        assert_eq!(shutdown.in_flight(), 0);
+    }
+}
@@ -0,0 +1,528 @@
+//! Server-sent events decoding.
+//!
+//! `message/stream` and `tasks/resubscribe` answer with a `text/event-stream`
+//! body whose `data` fields carry JSON-RPC responses. [`SseDecoder`] turns
+//! arbitrarily split chunks of that body into complete [`SseEvent`]s,
+//! following the event stream parsing rules of the HTML specification. It
+//! does no I/O; feed it whatever the transport delivers.
+
+/// One dispatched server-sent event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    /// The `event` field, or `None` for the default `message` type.
+    pub event: Option<String>,
+    /// The `data` lines, joined with newlines.
+    pub data: String,
+    /// The last event ID seen on the stream when this event was dispatched.
+    pub id: Option<String>,
+    /// The reconnection time in milliseconds, if the event set one.
+    pub retry: Option<u64>,
+}
+
+/// Size limits of [`SseDecoder`], bounding the memory a hostile or broken
+/// stream can make it hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SseLimits {
+    /// Largest `data` of one event in bytes, counting the newlines joining
+    /// its lines.
+    pub max_event_bytes: usize,
+    /// Largest line other than a data line buffered while waiting for its
+    /// terminator. Data lines are bounded by `max_event_bytes`.
+    pub max_buffered_bytes: usize,
+    /// Most `data` lines in one event.
+    pub max_data_lines: usize,
+}
+
+impl Default for SseLimits {
+    /// 32 MiB events and lines, and 100,000 data lines per event.
+    fn default() -> Self {
+        Self {
+            max_event_bytes: 32 << 20,
+            max_buffered_bytes: 32 << 20,
+            max_data_lines: 100_000,
+        }
+    }
+}
+
+/// An event the decoder discarded because it exceeded one of its
+/// [`SseLimits`], holding the limit.
+///
+/// The rest of the event, up to its terminating blank line, is skipped;
+/// the events after it decode normally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SseOverflow {
+    /// The event's data exceeded `max_event_bytes`.
+    EventBytes(usize),
+    /// A line other than a data line grew past `max_buffered_bytes` before
+    /// its terminator arrived.
+    BufferedBytes(usize),
+    /// The event had more than `max_data_lines` data lines.
+    DataLines(usize),
+}
+
+impl std::fmt::Display for SseOverflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SseOverflow::EventBytes(limit) => write!(f, "Event data exceeds {} bytes", limit),
+            SseOverflow::BufferedBytes(limit) => write!(f, "Line exceeds {} bytes without a terminator", limit),
+            SseOverflow::DataLines(limit) => write!(f, "Event has more than {} data lines", limit),
+        }
+    }
+}
+
+impl std::error::Error for SseOverflow {}
+
+/// Incremental decoder for a `text/event-stream` body.
+///
+/// The line and data buffers are kept across events, so once they have
+/// grown to the size of the largest event, decoding allocates only the
+/// strings of the events it returns.
+#[derive(Debug, Clone, Default)]
+pub struct SseDecoder {
+    limits: SseLimits,
+    /// Bytes of the current, not yet terminated line.
+    line: Vec<u8>,
+    /// The previous chunk ended in `\r`, so a leading `\n` is part of that
+    /// line terminator.
+    after_cr: bool,
+    /// Whether the byte order mark check at the start of the stream is done.
+    started: bool,
+    /// The current line overflowed; its bytes are dropped up to its
+    /// terminator.
+    skip_line: bool,
+    /// The current event overflowed; its lines are dropped up to the blank
+    /// line ending it.
+    skip_event: bool,
+    event: Option<String>,
+    /// Raw bytes of the data lines, joined with newlines.
+    data: Vec<u8>,
+    data_lines: usize,
+    retry: Option<u64>,
+    last_event_id: Option<String>,
+    /// Events discarded for exceeding the limits since the decoder was
+    /// created.
+    dropped: u64,
+}
+
+impl SseDecoder {
+    /// Create a decoder for a new stream with the default [`SseLimits`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a decoder for a new stream with the given limits.
+    pub fn with_limits(limits: SseLimits) -> Self {
+        Self {
+            limits,
+            ..Self::default()
+        }
+    }
+
+    /// The limits of the decoder.
+    pub fn limits(&self) -> &SseLimits {
+        &self.limits
+    }
+
+    /// Decode the next chunk of the stream.
+    ///
+    /// Events exceeding the limits are left out and counted by
+    /// [`SseDecoder::dropped_events`]; use [`SseDecoder::feed_into`] to get
+    /// an [`SseOverflow`] in their place.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The chunk, split anywhere, including inside a UTF-8
+    ///   sequence or a `\r\n` terminator.
+    ///
+    /// # Returns
+    ///
+    /// The events completed by this chunk, in order.
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        self.feed_into(bytes, &mut events);
+        events.into_iter().filter_map(Result::ok).collect()
+    }
+
+    /// Decode the next chunk of the stream into a caller-owned vector,
+    /// which can be reused across chunks.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The chunk, split anywhere.
+    /// * `events` - Receives the events completed by this chunk, in order,
+    ///   with an [`SseOverflow`] in place of each event that exceeded the
+    ///   limits.
+    pub fn feed_into(&mut self, bytes: &[u8], events: &mut Vec<Result<SseEvent, SseOverflow>>) {
+        let mut bytes = bytes;
+
+        if !self.started && !bytes.is_empty() {
+            self.line.extend_from_slice(bytes);
+            if self.line.len() < 3 && b"\xEF\xBB\xBF".starts_with(&self.line) {
+                // Not enough bytes yet to rule out a byte order mark
+                return;
+            }
+            self.started = true;
+            let pending = std::mem::take(&mut self.line);
+            let pending = pending.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&pending);
+            self.feed_lines(pending, events);
+            return;
+        }
+
+        if self.after_cr && bytes.first() == Some(&b'\n') {
+            bytes = &bytes[1..];
+        }
+        self.after_cr = false;
+        self.feed_lines(bytes, events);
+    }
+
+    /// The number of events discarded for exceeding the limits, by
+    /// [`SseDecoder::feed`] and [`SseDecoder::feed_into`] alike, since the
+    /// decoder was created. [`SseDecoder::reset`] keeps the count.
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped
+    }
+
+    /// The most recent `id` field, for the `Last-Event-ID` header when
+    /// reconnecting.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    /// The bytes held for the current line and event between chunks, at
+    /// most `max_event_bytes` plus `max_buffered_bytes`.
+    pub fn buffered_bytes(&self) -> usize {
+        self.line.len() + self.data.len()
+    }
+
+    /// Discard any partially received event, keeping the last event ID and
+    /// the limits.
+    ///
+    /// Call this when the connection ends; per the specification an event
+    /// without its terminating blank line is never dispatched.
+    pub fn reset(&mut self) {
+        let mut line = std::mem::take(&mut self.line);
+        let mut data = std::mem::take(&mut self.data);
+        line.clear();
+        data.clear();
+        *self = Self {
+            limits: self.limits,
+            line,
+            data,
+            last_event_id: self.last_event_id.take(),
+            dropped: self.dropped,
+            ..Self::default()
+        };
+    }
+
+    fn feed_lines(&mut self, mut bytes: &[u8], events: &mut Vec<Result<SseEvent, SseOverflow>>) {
+        while let Some(end) = bytes.iter().position(|&b| b == b'\n' || b == b'\r') {
+            self.buffer(&bytes[..end], events);
+            let blank = !self.skip_line && self.line.is_empty();
+            self.skip_line = false;
+            if self.skip_event {
+                // Only the blank line ending the discarded event matters
+                if blank {
+                    self.skip_event = false;
+                }
+            } else {
+                let line = std::mem::take(&mut self.line);
+                self.process_line(&line, events);
+                self.line = line;
+            }
+            self.line.clear();
+
+            let crlf = bytes[end] == b'\r';
+            bytes = &bytes[end + 1..];
+            if crlf {
+                match bytes.first() {
+                    Some(b'\n') => bytes = &bytes[1..],
+                    Some(_) => {}
+                    None => self.after_cr = true,
+                }
+            }
+        }
+        self.buffer(bytes, events);
+    }
+
+    /// Append bytes to the current line, unless the line or its event
+    /// would exceed the limits.
+    fn buffer(&mut self, bytes: &[u8], events: &mut Vec<Result<SseEvent, SseOverflow>>) {
+        if bytes.is_empty() {
+            return;
+        }
+        if self.skip_line || self.skip_event {
+            self.skip_line = true;
+            return;
+        }
+
+        // The line grows by at most one chunk past the limits before it is
+        // dropped, so oversized lines are rejected as they arrive, not once
+        // they are complete
+        self.line.extend_from_slice(bytes);
+        match self.line.strip_prefix(b"data:") {
+            Some(value) => {
+                let value = value.strip_prefix(b" ").unwrap_or(value);
+                if self.data_len_with(value.len()) > self.limits.max_event_bytes {
+                    self.overflow(SseOverflow::EventBytes(self.limits.max_event_bytes), events);
+                }
+            }
+            None if self.line.len() > self.limits.max_buffered_bytes => {
+                self.overflow(SseOverflow::BufferedBytes(self.limits.max_buffered_bytes), events);
+            }
+            None => {}
+        }
+    }
+
+    /// Discard the current event and skip the rest of it.
+    fn overflow(&mut self, overflow: SseOverflow, events: &mut Vec<Result<SseEvent, SseOverflow>>) {
+        self.line.clear();
+        self.data.clear();
+        self.data_lines = 0;
+        self.event = None;
+        self.retry = None;
+        self.skip_line = true;
+        self.skip_event = true;
+        self.dropped += 1;
+        events.push(Err(overflow));
+    }
+
+    /// The length of the event's data after appending a line of `len` bytes.
+    fn data_len_with(&self, len: usize) -> usize {
+        self.data.len() + usize::from(self.data_lines > 0) + len
+    }
+
+    fn process_line(&mut self, line: &[u8], events: &mut Vec<Result<SseEvent, SseOverflow>>) {
+        if line.is_empty() {
+            self.dispatch(events);
+            return;
+        }
+
+        let (field, value) = match line.iter().position(|&b| b == b':') {
+            // Comment, typically a keep-alive
+            Some(0) => return,
+            Some(colon) => {
+                let value = &line[colon + 1..];
+                (&line[..colon], value.strip_prefix(b" ").unwrap_or(value))
+            }
+            None => (line, &b""[..]),
+        };
+
+        match field {
+            b"event" => self.event = Some(String::from_utf8_lossy(value).into_owned()),
+            b"data" => {
+                if self.data_lines == self.limits.max_data_lines {
+                    self.overflow(SseOverflow::DataLines(self.limits.max_data_lines), events);
+                    // The line is complete, so only the rest of the event is skipped
+                    self.skip_line = false;
+                    return;
+                }
+                if self.data_lines > 0 {
+                    self.data.push(b'\n');
+                }
+                self.data.extend_from_slice(value);
+                self.data_lines += 1;
+            }
+            b"id" if !value.contains(&0) => self.last_event_id = Some(String::from_utf8_lossy(value).into_owned()),
+            b"retry" => {
+                if let Some(retry) = std::str::from_utf8(value).ok().and_then(|value| value.parse().ok()) {
+                    self.retry = Some(retry);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn dispatch(&mut self, events: &mut Vec<Result<SseEvent, SseOverflow>>) {
+        let event = self.event.take();
+        let retry = self.retry.take();
+        if self.data_lines == 0 {
+            return;
+        }
+
+        self.data_lines = 0;
+        let data = String::from_utf8_lossy(&self.data).into_owned();
+        self.data.clear();
+        events.push(Ok(SseEvent {
+            event: event.filter(|e| !e.is_empty()),
+            data,
+            id: self.last_event_id.clone(),
+            retry,
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STREAM: &[u8] = b"\xEF\xBB\xBF: keep-alive\r\nid: 1\r\nevent: update\r\ndata: {\"a\":\r\ndata:  1}\r\n\r\nretry: 500\ndata: caf\xC3\xA9\n\n";
+
+    #[test]
+    fn test_decodes_fields_and_line_endings() {
+        let events = SseDecoder::new().feed(STREAM);
+        assert_eq!(
+            events,
+            [
+                SseEvent {
+                    event: Some("update".to_string()),
+                    data: "{\"a\":\n 1}".to_string(),
+                    id: Some("1".to_string()),
+                    retry: None,
+                },
+                SseEvent {
+                    event: None,
+                    data: "café".to_string(),
+                    id: Some("1".to_string()),
+                    retry: Some(500),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decoding_is_independent_of_chunking() {
+        let expected = SseDecoder::new().feed(STREAM);
+        for chunk_size in 1..STREAM.len() {
+            let mut decoder = SseDecoder::new();
+            let events: Vec<_> = STREAM.chunks(chunk_size).flat_map(|chunk| decoder.feed(chunk)).collect();
+            assert_eq!(events, expected, "chunk size {}", chunk_size);
+        }
+    }
+
+    #[test]
+    fn test_unterminated_event_is_discarded_on_reset() {
+        let mut decoder = SseDecoder::new();
+        assert!(decoder.feed(b"id: 7\n\ndata: partial").is_empty());
+        decoder.reset();
+        assert_eq!(decoder.last_event_id(), Some("7"));
+        assert_eq!(decoder.feed(b"data: next\n\n")[0].data, "next");
+    }
+
+    fn small_limits() -> SseLimits {
+        SseLimits {
+            max_event_bytes: 64,
+            max_buffered_bytes: 128,
+            max_data_lines: 4,
+        }
+    }
+
+    #[test]
+    fn test_huge_event_is_rejected_at_the_limit() {
+        let mut decoder = SseDecoder::new();
+        let limit = decoder.limits().max_event_bytes;
+        let chunk = vec![b'a'; 64 * 1024];
+        let mut events = Vec::new();
+        decoder.feed_into(b"data: ", &mut events);
+
+        // 100 MB in one event, never held beyond the limit
+        let mut rejected_after = None;
+        for sent in 1..=1600 {
+            decoder.feed_into(&chunk, &mut events);
+            assert!(decoder.buffered_bytes() <= limit + "data: ".len());
+            if rejected_after.is_none() && !events.is_empty() {
+                rejected_after = Some(sent * chunk.len());
+            }
+        }
+        assert_eq!(events, [Err(SseOverflow::EventBytes(limit))]);
+        assert!(rejected_after.unwrap() <= limit + chunk.len());
+        assert_eq!(decoder.buffered_bytes(), 0);
+
+        // The stream goes on after the discarded event
+        assert_eq!(decoder.feed(b"\n\ndata: next\n\n")[0].data, "next");
+    }
+
+    #[test]
+    fn test_unterminated_lines_are_bounded() {
+        let mut decoder = SseDecoder::with_limits(small_limits());
+        let mut events = Vec::new();
+        decoder.feed_into(b"data: ", &mut events);
+        for _ in 0..100 {
+            decoder.feed_into(&[b'x'; 50], &mut events);
+        }
+        assert_eq!(events, [Err(SseOverflow::EventBytes(64))]);
+
+        // A line that is not data is bounded by the buffer limit
+        let mut decoder = SseDecoder::with_limits(small_limits());
+        let mut events = Vec::new();
+        decoder.feed_into(b"event: ", &mut events);
+        for _ in 0..100 {
+            decoder.feed_into(&[b'x'; 50], &mut events);
+            assert!(decoder.buffered_bytes() <= 128);
+        }
+        assert_eq!(events, [Err(SseOverflow::BufferedBytes(128))]);
+
+        // Data lines of the discarded event do not leak into the next one
+        decoder.feed_into(b"\ndata: lost\n\ndata: kept\n\n", &mut events);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].as_ref().unwrap().data, "kept");
+        assert_eq!(events[1].as_ref().unwrap().event, None);
+    }
+
+    #[test]
+    fn test_data_line_and_event_size_limits() {
+        let mut decoder = SseDecoder::with_limits(small_limits());
+        let mut events = Vec::new();
+        decoder.feed_into(b"data: 1\ndata: 2\ndata: 3\ndata: 4\ndata: 5\ndata: 6\n\ndata: 1\ndata: 2\n\n", &mut events);
+        assert_eq!(events[0], Err(SseOverflow::DataLines(4)));
+        assert_eq!(events[1].as_ref().unwrap().data, "1\n2");
+        assert_eq!(events.len(), 2);
+
+        // Lines that each fit can still add up to too large an event
+        let line = format!("data: {}\n", "y".repeat(40));
+        let stream = format!("{}{}\ndata: small\n\n", line, line);
+        let mut decoder = SseDecoder::with_limits(small_limits());
+        assert_eq!(
+            decoder.feed(stream.as_bytes()),
+            [SseEvent {
+                data: "small".to_string(),
+                ..SseEvent::default()
+            }]
+        );
+
+        // feed() leaves dropped events out, but counts them
+        assert_eq!(decoder.dropped_events(), 1);
+        decoder.feed(stream.as_bytes());
+        decoder.reset();
+        assert_eq!(decoder.dropped_events(), 2);
+    }
+
+    #[test]
+    fn test_interleaved_keep_alives() {
+        let mut stream = Vec::new();
+        for n in 0..1000 {
+            stream.extend_from_slice(b": keep-alive\r\n");
+            if n % 100 == 0 {
+                stream.extend_from_slice(format!("data: {}\n:\ndata: more\n\n", n).as_bytes());
+            }
+        }
+        let mut decoder = SseDecoder::with_limits(small_limits());
+        let mut events = Vec::new();
+        for chunk in stream.chunks(7) {
+            decoder.feed_into(chunk, &mut events);
+            assert!(decoder.buffered_bytes() < 64);
+        }
+        let data: Vec<_> = events.iter().map(|event| event.as_ref().unwrap().data.as_str()).collect();
+        assert_eq!(data.len(), 10);
+        assert_eq!(data[3], "300\nmore");
+    }
+
+    #[test]
+    fn test_overflows_are_independent_of_chunking() {
+        let stream = format!("id: 1\ndata: {}\n\nevent: x\ndata: ok\n\n", "z".repeat(100));
+        let expected = {
+            let mut events = Vec::new();
+            SseDecoder::with_limits(small_limits()).feed_into(stream.as_bytes(), &mut events);
+            events
+        };
+        assert_eq!(expected[0], Err(SseOverflow::EventBytes(64)));
+        assert_eq!(expected[1].as_ref().unwrap().event.as_deref(), Some("x"));
+        for chunk_size in 1..stream.len() {
+            let mut decoder = SseDecoder::with_limits(small_limits());
+            let mut events = Vec::new();
+            for chunk in stream.as_bytes().chunks(chunk_size) {
+                decoder.feed_into(chunk, &mut events);
+            }
+            assert_eq!(events, expected, "chunk size {}", chunk_size);
+        }
+    }
+}
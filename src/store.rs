@@ -0,0 +1,969 @@
+//! Task storage.
+//!
+//! [`TaskStore`] is the async interface implemented by storage backends, and
+//! [`PushNotificationConfigStore`] the one for push notification configs.
+//! [`InMemoryTaskStore`] keeps tasks in a map keyed by [`TaskId`]. It is
+//! cheap to clone, and clones share the same tasks. [`FileTaskStore`] adds
+//! durability without a database by snapshotting the map to a JSON file.
+//! With the `sqlx` feature, [`sql::SqlTaskStore`] stores tasks in SQLite or
+//! Postgres.
+//!
+//! # Optimistic concurrency
+//!
+//! [`TaskStore`] backends stamp `createdAt` and `updatedAt` on every write.
+//! [`TaskStore::update_task`] only succeeds if the task's `updatedAt` still
+//! matches the stored one, so two writers that read the same task cannot
+//! silently overwrite each other; the loser gets an error for which
+//! [`is_conflict`] is true and should reload and retry.
+//!
+//! # Snapshot format
+//!
+//! Snapshots are a JSON object with a `version` number and a `tasks` array
+//! holding tasks in their wire format, so unknown task fields survive a
+//! save/load cycle. Unknown top-level fields are ignored, and snapshots with
+//! a newer `version` than [`SNAPSHOT_VERSION`] are rejected rather than
+//! misread.
+
+use crate::clock;
+use crate::ids::{ConfigId, ContextId, IdSource, SharedIdSource, TaskId};
+use crate::page::{self, Page};
+use crate::{A2AError, InternalError, InvalidParamsError, PushNotificationConfig, Task, TaskNotFoundError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "sqlx")]
+pub mod sql;
+
+/// The snapshot format version written by this crate.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+// ============================================================================
+// STORE INTERFACE
+// ============================================================================
+
+/// Async task storage.
+pub trait TaskStore: Send + Sync {
+    /// Get a task.
+    ///
+    /// # Returns
+    ///
+    /// The task, or a `TaskNotFound` error.
+    fn get_task(&self, id: &TaskId) -> impl Future<Output = Result<Task, A2AError>> + Send;
+
+    /// Store a new task, stamping its `createdAt` and `updatedAt`.
+    ///
+    /// # Returns
+    ///
+    /// The stored task, or a conflict error if a task with the same ID exists.
+    fn create_task(&self, task: Task) -> impl Future<Output = Result<Task, A2AError>> + Send;
+
+    /// Replace a stored task, stamping a new `updatedAt`.
+    ///
+    /// # Returns
+    ///
+    /// The stored task, a `TaskNotFound` error, or a conflict error if the
+    /// task's `updatedAt` does not match the stored task.
+    fn update_task(&self, task: Task) -> impl Future<Output = Result<Task, A2AError>> + Send;
+
+    /// Delete a task.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())`, or a `TaskNotFound` error.
+    fn delete_task(&self, id: &TaskId) -> impl Future<Output = Result<(), A2AError>> + Send;
+
+    /// List all tasks, ordered by task ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `cursor` - The `next_cursor` of the previous page, or `None` to start.
+    /// * `limit` - The maximum number of tasks to return, at least 1; a zero
+    ///   limit is an `InvalidParams` error.
+    fn list_tasks(&self, cursor: Option<&str>, limit: usize) -> impl Future<Output = Result<Page<Task>, A2AError>> + Send;
+
+    /// List the tasks of a context, ordered by task ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `context_id` - The context.
+    /// * `cursor` - The `next_cursor` of the previous page, or `None` to start.
+    /// * `limit` - The maximum number of tasks to return, at least 1; a zero
+    ///   limit is an `InvalidParams` error.
+    fn list_tasks_by_context(
+        &self,
+        context_id: &ContextId,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> impl Future<Output = Result<Page<Task>, A2AError>> + Send;
+}
+
+/// Async storage for the push notification configs of tasks.
+pub trait PushNotificationConfigStore: Send + Sync {
+    /// Add or replace a config of a task.
+    ///
+    /// A config without an `id` is assigned one. A config with the `id` of
+    /// an existing config of the task replaces it in place.
+    ///
+    /// # Returns
+    ///
+    /// The stored config, with its `id` set.
+    fn set_config(
+        &self,
+        task_id: &TaskId,
+        config: PushNotificationConfig,
+    ) -> impl Future<Output = Result<PushNotificationConfig, A2AError>> + Send;
+
+    /// Get a config of a task.
+    ///
+    /// # Returns
+    ///
+    /// The config, or an `InvalidParams` error if it does not exist.
+    fn get_config(
+        &self,
+        task_id: &TaskId,
+        config_id: &ConfigId,
+    ) -> impl Future<Output = Result<PushNotificationConfig, A2AError>> + Send;
+
+    /// List the configs of a task in the order they were added.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The task.
+    /// * `cursor` - The `next_cursor` of the previous page, or `None` to start.
+    /// * `limit` - The maximum number of configs to return, at least 1; a zero
+    ///   limit is an `InvalidParams` error.
+    fn list_configs(
+        &self,
+        task_id: &TaskId,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> impl Future<Output = Result<Page<PushNotificationConfig>, A2AError>> + Send;
+
+    /// Delete a config of a task.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())`, or an `InvalidParams` error if it does not exist.
+    fn delete_config(&self, task_id: &TaskId, config_id: &ConfigId) -> impl Future<Output = Result<(), A2AError>> + Send;
+
+    /// Delete all configs of a task.
+    ///
+    /// # Returns
+    ///
+    /// The number of configs deleted.
+    fn delete_configs_for_task(&self, task_id: &TaskId) -> impl Future<Output = Result<usize, A2AError>> + Send;
+}
+
+/// Check if an error reports a write conflict from a [`TaskStore`].
+pub fn is_conflict(error: &A2AError) -> bool {
+    matches!(error, A2AError::Internal(e) if e.data.as_ref().is_some_and(|data| data.get("conflict").is_some()))
+}
+
+pub(crate) fn conflict_error(id: &str, message: &str) -> A2AError {
+    A2AError::Internal(Box::new(InternalError {
+        code: -32603,
+        message: format!("Task {} {}", id, message),
+        data: Some(serde_json::json!({"conflict": id})),
+    }))
+}
+
+/// Return a fresh `updatedAt` timestamp.
+///
+/// Timestamps are RFC 3339 in UTC with microsecond precision, and strictly
+/// increase within the process so that consecutive writes never share one.
+pub(crate) fn next_timestamp() -> String {
+    static LAST: AtomicI64 = AtomicI64::new(0);
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_micros() as i64);
+    let mut last = LAST.load(Ordering::Relaxed);
+    loop {
+        let next = now.max(last + 1);
+        match LAST.compare_exchange_weak(last, next, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => return clock::format_timestamp(next),
+            Err(actual) => last = actual,
+        }
+    }
+}
+
+// ============================================================================
+// IN-MEMORY STORE
+// ============================================================================
+
+/// Thread-safe in-memory task storage.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryTaskStore {
+    tasks: Arc<RwLock<HashMap<TaskId, Task>>>,
+}
+
+impl InMemoryTaskStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert or replace a task.
+    ///
+    /// # Returns
+    ///
+    /// The task previously stored under the same ID, if any.
+    pub fn insert(&self, task: Task) -> Option<Task> {
+        self.write().insert(task.typed_id(), task)
+    }
+
+    /// Get a copy of a task.
+    pub fn get(&self, id: &TaskId) -> Option<Task> {
+        self.read().get(id).cloned()
+    }
+
+    /// Modify a task in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the task.
+    /// * `update` - Applied to the stored task while the store is locked.
+    ///
+    /// # Returns
+    ///
+    /// A copy of the updated task, or a `TaskNotFound` error.
+    pub fn update<F: FnOnce(&mut Task)>(&self, id: &TaskId, update: F) -> Result<Task, A2AError> {
+        let mut tasks = self.write();
+        let task = tasks.get_mut(id).ok_or_else(|| task_not_found(id))?;
+        update(task);
+        Ok(task.clone())
+    }
+
+    /// Remove a task.
+    ///
+    /// # Returns
+    ///
+    /// The removed task, if it was stored.
+    pub fn remove(&self, id: &TaskId) -> Option<Task> {
+        self.write().remove(id)
+    }
+
+    /// Get copies of all tasks in a context, ordered by task ID.
+    pub fn list_by_context(&self, context_id: &ContextId) -> Vec<Task> {
+        let mut tasks: Vec<Task> = self
+            .read()
+            .values()
+            .filter(|task| task.context_id == context_id.as_str())
+            .cloned()
+            .collect();
+        tasks.sort_by(|a, b| a.id.cmp(&b.id));
+        tasks
+    }
+
+    /// The number of stored tasks.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Check if the store holds no tasks.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Get copies of all tasks, ordered by task ID.
+    pub fn snapshot(&self) -> Vec<Task> {
+        let mut tasks: Vec<Task> = self.read().values().cloned().collect();
+        tasks.sort_by(|a, b| a.id.cmp(&b.id));
+        tasks
+    }
+
+    /// Replace the contents of the store.
+    pub fn replace_all(&self, tasks: impl IntoIterator<Item = Task>) {
+        let tasks = tasks.into_iter().map(|task| (task.typed_id(), task)).collect();
+        *self.write() = tasks;
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<TaskId, Task>> {
+        self.tasks.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<TaskId, Task>> {
+        self.tasks.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl TaskStore for InMemoryTaskStore {
+    async fn get_task(&self, id: &TaskId) -> Result<Task, A2AError> {
+        self.get(id).ok_or_else(|| task_not_found(id))
+    }
+
+    async fn create_task(&self, mut task: Task) -> Result<Task, A2AError> {
+        let mut tasks = self.write();
+        let id = task.typed_id();
+        if tasks.contains_key(&id) {
+            return Err(conflict_error(id.as_str(), "already exists"));
+        }
+
+        let now = next_timestamp();
+        task.created_at = Some(now.clone());
+        task.updated_at = Some(now);
+        tasks.insert(id, task.clone());
+        Ok(task)
+    }
+
+    async fn update_task(&self, mut task: Task) -> Result<Task, A2AError> {
+        let mut tasks = self.write();
+        let id = task.typed_id();
+        let stored = tasks.get_mut(&id).ok_or_else(|| task_not_found(&id))?;
+        if stored.updated_at != task.updated_at {
+            return Err(conflict_error(id.as_str(), "was modified concurrently"));
+        }
+
+        task.created_at = stored.created_at.clone();
+        task.updated_at = Some(next_timestamp());
+        *stored = task.clone();
+        Ok(task)
+    }
+
+    async fn delete_task(&self, id: &TaskId) -> Result<(), A2AError> {
+        self.remove(id).map(|_| ()).ok_or_else(|| task_not_found(id))
+    }
+
+    async fn list_tasks(&self, cursor: Option<&str>, limit: usize) -> Result<Page<Task>, A2AError> {
+        page::check_limit(limit)?;
+        let tasks: Vec<Task> = self
+            .snapshot()
+            .into_iter()
+            .filter(|task| cursor.is_none_or(|cursor| task.id.as_str() > cursor))
+            .take(limit.saturating_add(1))
+            .collect();
+        Ok(Page::from_overfetch(tasks, limit, |task| task.id.clone()))
+    }
+
+    async fn list_tasks_by_context(
+        &self,
+        context_id: &ContextId,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> Result<Page<Task>, A2AError> {
+        page::check_limit(limit)?;
+        let tasks: Vec<Task> = self
+            .list_by_context(context_id)
+            .into_iter()
+            .filter(|task| cursor.is_none_or(|cursor| task.id.as_str() > cursor))
+            .take(limit.saturating_add(1))
+            .collect();
+        Ok(Page::from_overfetch(tasks, limit, |task| task.id.clone()))
+    }
+}
+
+/// Thread-safe in-memory push notification config storage.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryPushNotificationConfigStore {
+    configs: Arc<RwLock<HashMap<TaskId, Vec<PushNotificationConfig>>>>,
+    next_id: Arc<AtomicI64>,
+    ids: Option<SharedIdSource>,
+}
+
+impl InMemoryPushNotificationConfigStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty store that mints the IDs of configs set without one
+    /// from an [`IdSource`], instead of numbering them `config-1`, ...
+    pub fn with_id_source(ids: impl IdSource + Send + 'static) -> Self {
+        Self {
+            ids: Some(SharedIdSource::new(ids)),
+            ..Self::default()
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<TaskId, Vec<PushNotificationConfig>>> {
+        self.configs.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<TaskId, Vec<PushNotificationConfig>>> {
+        self.configs.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl PushNotificationConfigStore for InMemoryPushNotificationConfigStore {
+    async fn set_config(&self, task_id: &TaskId, mut config: PushNotificationConfig) -> Result<PushNotificationConfig, A2AError> {
+        let id = config
+            .id
+            .get_or_insert_with(|| match &self.ids {
+                Some(ids) => ids.mint(|ids| ids.new_config_id().into_inner()),
+                None => format!("config-{}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1),
+            })
+            .clone();
+
+        let mut configs = self.write();
+        let configs = configs.entry(task_id.clone()).or_default();
+        match configs.iter_mut().find(|existing| existing.id.as_deref() == Some(id.as_str())) {
+            Some(existing) => *existing = config.clone(),
+            None => configs.push(config.clone()),
+        }
+        Ok(config)
+    }
+
+    async fn get_config(&self, task_id: &TaskId, config_id: &ConfigId) -> Result<PushNotificationConfig, A2AError> {
+        self.read()
+            .get(task_id)
+            .and_then(|configs| configs.iter().find(|config| config.id.as_deref() == Some(config_id.as_str())))
+            .cloned()
+            .ok_or_else(|| config_not_found(task_id, config_id))
+    }
+
+    async fn list_configs(
+        &self,
+        task_id: &TaskId,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> Result<Page<PushNotificationConfig>, A2AError> {
+        page::check_limit(limit)?;
+        let configs = self.read();
+        let configs = configs.get(task_id).map(Vec::as_slice).unwrap_or_default();
+
+        let start = match cursor {
+            None => 0,
+            Some(cursor) => {
+                configs
+                    .iter()
+                    .position(|config| config.id.as_deref() == Some(cursor))
+                    .ok_or_else(|| page::invalid_cursor(cursor))?
+                    + 1
+            }
+        };
+
+        let items = configs[start..].iter().take(limit.saturating_add(1)).cloned().collect();
+        Ok(Page::from_overfetch(items, limit, |config: &PushNotificationConfig| {
+            config.id.clone().unwrap_or_default()
+        }))
+    }
+
+    async fn delete_config(&self, task_id: &TaskId, config_id: &ConfigId) -> Result<(), A2AError> {
+        let mut configs = self.write();
+        let task_configs = configs.get_mut(task_id).ok_or_else(|| config_not_found(task_id, config_id))?;
+        let index = task_configs
+            .iter()
+            .position(|config| config.id.as_deref() == Some(config_id.as_str()))
+            .ok_or_else(|| config_not_found(task_id, config_id))?;
+
+        task_configs.remove(index);
+        if task_configs.is_empty() {
+            configs.remove(task_id);
+        }
+        Ok(())
+    }
+
+    async fn delete_configs_for_task(&self, task_id: &TaskId) -> Result<usize, A2AError> {
+        Ok(self.write().remove(task_id).map_or(0, |configs| configs.len()))
+    }
+}
+
+fn config_not_found(task_id: &TaskId, config_id: &ConfigId) -> A2AError {
+    A2AError::InvalidParams(Box::new(InvalidParamsError {
+        code: -32602,
+        message: format!("Push notification config {} not found for task {}", config_id, task_id),
+        data: None,
+    }))
+}
+
+pub(crate) fn task_not_found(id: &TaskId) -> A2AError {
+    A2AError::TaskNotFound(Box::new(TaskNotFoundError {
+        code: -32001,
+        message: format!("Task not found: {}", id),
+        data: None,
+    }))
+}
+
+// ============================================================================
+// SNAPSHOTS
+// ============================================================================
+
+/// Why a snapshot could not be read or written.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The file could not be read or written.
+    Io { path: PathBuf, error: std::io::Error },
+    /// The file is not a valid snapshot, e.g. because it was truncated.
+    Corrupt { path: PathBuf, message: String },
+    /// The snapshot was written by a newer format version.
+    UnsupportedVersion { path: PathBuf, version: u32 },
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Io { path, error } => write!(f, "Task snapshot {} could not be accessed: {}", path.display(), error),
+            SnapshotError::Corrupt { path, message } => write!(f, "Task snapshot {} is corrupt: {}", path.display(), message),
+            SnapshotError::UnsupportedVersion { path, version } => write!(
+                f,
+                "Task snapshot {} has unsupported version {} (max {})",
+                path.display(),
+                version,
+                SNAPSHOT_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SnapshotError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SnapshotOut<'a> {
+    version: u32,
+    tasks: &'a [Task],
+}
+
+#[derive(Deserialize)]
+struct SnapshotIn {
+    version: u32,
+    #[serde(default)]
+    tasks: Vec<Task>,
+}
+
+/// Write tasks to a snapshot file atomically.
+///
+/// The snapshot is written to a temporary file next to `path` and renamed
+/// over it, so readers never see a partially written snapshot. Callers must
+/// not save to the same path concurrently.
+///
+/// # Arguments
+///
+/// * `path` - The snapshot file.
+/// * `tasks` - The tasks to write.
+pub fn save_snapshot(path: &Path, tasks: &[Task]) -> Result<(), SnapshotError> {
+    let io_error = |error| SnapshotError::Io {
+        path: path.to_path_buf(),
+        error,
+    };
+
+    let json = serde_json::to_vec(&SnapshotOut {
+        version: SNAPSHOT_VERSION,
+        tasks,
+    })
+    .map_err(|e| SnapshotError::Corrupt {
+        path: path.to_path_buf(),
+        message: format!("Failed to serialize tasks: {}", e),
+    })?;
+
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let mut file = fs::File::create(&temp_path).map_err(io_error)?;
+    file.write_all(&json).map_err(io_error)?;
+    file.sync_all().map_err(io_error)?;
+    drop(file);
+    fs::rename(&temp_path, path).map_err(io_error)
+}
+
+/// Read tasks from a snapshot file.
+///
+/// # Arguments
+///
+/// * `path` - The snapshot file.
+///
+/// # Returns
+///
+/// The tasks, or an error if the file cannot be read, is corrupt or has an
+/// unsupported version.
+pub fn load_snapshot(path: &Path) -> Result<Vec<Task>, SnapshotError> {
+    let bytes = fs::read(path).map_err(|error| SnapshotError::Io {
+        path: path.to_path_buf(),
+        error,
+    })?;
+
+    let snapshot: SnapshotIn = serde_json::from_slice(&bytes).map_err(|e| SnapshotError::Corrupt {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+
+    if snapshot.version > SNAPSHOT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion {
+            path: path.to_path_buf(),
+            version: snapshot.version,
+        });
+    }
+
+    Ok(snapshot.tasks)
+}
+
+// ============================================================================
+// FILE-BACKED STORE
+// ============================================================================
+
+/// An [`InMemoryTaskStore`] persisted to a JSON snapshot file.
+///
+/// Tasks are only written when [`FileTaskStore::save`] is called or by the
+/// auto-save thread started with [`FileTaskStore::start_auto_save`].
+#[derive(Debug, Clone)]
+pub struct FileTaskStore {
+    store: InMemoryTaskStore,
+    path: PathBuf,
+    save_lock: Arc<Mutex<()>>,
+}
+
+impl FileTaskStore {
+    /// Open a store backed by a snapshot file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The snapshot file. A missing file yields an empty store.
+    ///
+    /// # Returns
+    ///
+    /// The store, or an error if the file exists but cannot be loaded.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, SnapshotError> {
+        let path = path.into();
+        let store = InMemoryTaskStore::new();
+
+        match load_snapshot(&path) {
+            Ok(tasks) => store.replace_all(tasks),
+            Err(SnapshotError::Io { error, .. }) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        Ok(Self {
+            store,
+            path,
+            save_lock: Arc::default(),
+        })
+    }
+
+    /// The in-memory store holding the tasks.
+    pub fn store(&self) -> &InMemoryTaskStore {
+        &self.store
+    }
+
+    /// The snapshot file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write the current tasks to the snapshot file.
+    ///
+    /// The tasks are copied before writing, so updates are not blocked while
+    /// the file is written. Concurrent saves through clones of this store
+    /// are serialized.
+    pub fn save(&self) -> Result<(), SnapshotError> {
+        let _guard = self.save_lock.lock().unwrap_or_else(PoisonError::into_inner);
+        save_snapshot(&self.path, &self.store.snapshot())
+    }
+
+    /// Save periodically on a background thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The time between saves.
+    ///
+    /// # Returns
+    ///
+    /// A handle that stops the thread, saving one last time, when stopped or
+    /// dropped.
+    pub fn start_auto_save(&self, interval: Duration) -> AutoSaveHandle {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let last_error = Arc::new(Mutex::new(None));
+        let store = self.clone();
+        let errors = last_error.clone();
+
+        let thread = std::thread::spawn(move || {
+            loop {
+                let stopping = !matches!(stopped.recv_timeout(interval), Err(RecvTimeoutError::Timeout));
+                let result = store.save();
+                if stopping {
+                    return result;
+                }
+                if let Err(e) = result {
+                    *errors.lock().unwrap_or_else(PoisonError::into_inner) = Some(e);
+                }
+            }
+        });
+
+        AutoSaveHandle {
+            stop: Some(stop),
+            thread: Some(thread),
+            last_error,
+        }
+    }
+}
+
+/// Controls the auto-save thread of a [`FileTaskStore`].
+#[derive(Debug)]
+pub struct AutoSaveHandle {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<Result<(), SnapshotError>>>,
+    last_error: Arc<Mutex<Option<SnapshotError>>>,
+}
+
+impl AutoSaveHandle {
+    /// Take the error of the most recent failed periodic save, if any.
+    pub fn take_error(&self) -> Option<SnapshotError> {
+        self.last_error.lock().unwrap_or_else(PoisonError::into_inner).take()
+    }
+
+    /// Stop the thread after a final save.
+    ///
+    /// # Returns
+    ///
+    /// The result of the final save.
+    pub fn stop(mut self) -> Result<(), SnapshotError> {
+        self.finish()
+    }
+
+    fn finish(&mut self) -> Result<(), SnapshotError> {
+        drop(self.stop.take());
+        match self.thread.take().map(JoinHandle::join) {
+            Some(Ok(result)) => result,
+            Some(Err(panic)) => std::panic::resume_unwind(panic),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for AutoSaveHandle {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            let _ = self.finish();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempPath(PathBuf);
+
+    impl TempPath {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("a2a-rs-{}-{}.json", name, std::process::id()));
+            let _ = fs::remove_file(&path);
+            Self(path)
+        }
+    }
+
+    impl Drop for TempPath {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn task(n: usize) -> Task {
+        let mut task = Task::minimal(format!("task-{:03}", n), format!("ctx-{}", n % 4));
+        task.metadata = Some(serde_json::json!({"n": n}));
+        task
+    }
+
+    #[test]
+    fn test_save_load_round_trip() {
+        let path = TempPath::new("round-trip");
+        let store = FileTaskStore::load(&path.0).unwrap();
+        assert!(store.store().is_empty());
+
+        for n in 0..100 {
+            store.store().insert(task(n));
+        }
+        let mut with_extra: Task = serde_json::from_value(serde_json::json!({
+            "kind": "task",
+            "id": "task-100",
+            "contextId": "ctx-0",
+            "status": {"state": "working"},
+            "futureField": {"keep": true}
+        }))
+        .unwrap();
+        with_extra.metadata = None;
+        store.store().insert(with_extra);
+        store.save().unwrap();
+
+        let reloaded = FileTaskStore::load(&path.0).unwrap();
+        assert_eq!(reloaded.store().snapshot(), store.store().snapshot());
+        assert_eq!(reloaded.store().len(), 101);
+        assert_eq!(reloaded.store().list_by_context(&ContextId::from("ctx-1")).len(), 25);
+
+        let kept = reloaded.store().get(&TaskId::from("task-100")).unwrap();
+        assert_eq!(serde_json::to_value(&kept).unwrap()["futureField"]["keep"], true);
+    }
+
+    #[test]
+    fn test_corrupt_snapshots_are_reported() {
+        let path = TempPath::new("corrupt");
+        save_snapshot(&path.0, &[task(1), task(2)]).unwrap();
+
+        let bytes = fs::read(&path.0).unwrap();
+        fs::write(&path.0, &bytes[..bytes.len() / 2]).unwrap();
+        assert!(matches!(FileTaskStore::load(&path.0), Err(SnapshotError::Corrupt { .. })));
+
+        fs::write(&path.0, r#"{"version": 99, "tasks": []}"#).unwrap();
+        let error = FileTaskStore::load(&path.0).unwrap_err();
+        assert!(matches!(error, SnapshotError::UnsupportedVersion { version: 99, .. }));
+        assert!(error.to_string().contains("unsupported version 99"));
+
+        // Unknown top-level fields from other writers are tolerated
+        fs::write(&path.0, r#"{"version": 1, "tasks": [], "writtenBy": "other"}"#).unwrap();
+        assert!(FileTaskStore::load(&path.0).unwrap().store().is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_updates_during_save() {
+        let path = TempPath::new("concurrent");
+        let store = FileTaskStore::load(&path.0).unwrap();
+        for n in 0..50 {
+            store.store().insert(task(n));
+        }
+
+        let writer = {
+            let tasks = store.store().clone();
+            std::thread::spawn(move || {
+                for round in 0..200 {
+                    let id = TaskId::from(format!("task-{:03}", round % 50));
+                    tasks.update(&id, |task| task.metadata = Some(serde_json::json!({"round": round}))).unwrap();
+                }
+            })
+        };
+
+        let auto_save = store.start_auto_save(Duration::from_millis(1));
+        for _ in 0..20 {
+            store.save().unwrap();
+            assert_eq!(load_snapshot(&path.0).unwrap().len(), 50);
+        }
+        writer.join().unwrap();
+        auto_save.stop().unwrap();
+
+        // The final auto-save captured the last update
+        let reloaded = FileTaskStore::load(&path.0).unwrap();
+        let last = reloaded.store().get(&TaskId::from("task-049")).unwrap();
+        assert_eq!(last.metadata, Some(serde_json::json!({"round": 199})));
+    }
+
+    #[test]
+    fn test_update_missing_task() {
+        let store = InMemoryTaskStore::new();
+        let result = store.update(&TaskId::from("missing"), |_| {});
+        assert!(matches!(result, Err(A2AError::TaskNotFound(_))));
+    }
+
+    #[test]
+    fn test_next_timestamp_increases() {
+        let (first, second) = (next_timestamp(), next_timestamp());
+        assert!(first < second);
+        assert!(clock::parse_timestamp(&first).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_optimistic_concurrency() {
+        let store = InMemoryTaskStore::new();
+        let created = store.create_task(task(1)).await.unwrap();
+        assert!(created.updated_at.is_some());
+        assert!(is_conflict(&store.create_task(task(1)).await.unwrap_err()));
+
+        let mut first = store.get_task(&created.typed_id()).await.unwrap();
+        let mut second = first.clone();
+        first.status.state = crate::TaskState::Working;
+        let updated = store.update_task(first).await.unwrap();
+        assert_ne!(updated.updated_at, created.updated_at);
+        assert_eq!(updated.created_at, created.created_at);
+
+        second.status.state = crate::TaskState::Canceled;
+        assert!(is_conflict(&store.update_task(second).await.unwrap_err()));
+
+        let listed = store.list_tasks_by_context(&ContextId::from("ctx-1"), None, 10).await.unwrap();
+        assert_eq!(listed.items, [updated]);
+        assert!(listed.is_last());
+
+        store.delete_task(&created.typed_id()).await.unwrap();
+        assert!(matches!(store.get_task(&created.typed_id()).await, Err(A2AError::TaskNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_task_pages_are_stable_across_inserts() {
+        let store = InMemoryTaskStore::new();
+        for n in [1, 3, 5] {
+            store.create_task(Task::minimal(format!("task-{}", n), "ctx".to_string())).await.unwrap();
+        }
+        let context = ContextId::from("ctx");
+
+        let first = store.list_tasks_by_context(&context, None, 2).await.unwrap();
+        assert_eq!(first.items.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(), ["task-1", "task-3"]);
+
+        // A task sorting before the cursor and one after it
+        store.create_task(Task::minimal("task-2".to_string(), "ctx".to_string())).await.unwrap();
+        store.create_task(Task::minimal("task-6".to_string(), "ctx".to_string())).await.unwrap();
+
+        let second = store.list_tasks_by_context(&context, first.next_cursor.as_deref(), 2).await.unwrap();
+        assert_eq!(second.items.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(), ["task-5", "task-6"]);
+        assert!(second.is_last());
+    }
+
+    #[tokio::test]
+    async fn test_zero_limit_is_rejected() {
+        let store = InMemoryTaskStore::new();
+        store.create_task(Task::minimal("task-1".to_string(), "ctx".to_string())).await.unwrap();
+        let configs = InMemoryPushNotificationConfigStore::new();
+
+        // An empty page would claim to be the last one with a task left
+        let invalid = |result: Result<_, A2AError>| matches!(result, Err(A2AError::InvalidParams(_)));
+        assert!(invalid(store.list_tasks(None, 0).await.map(|_| ())));
+        assert!(invalid(store.list_tasks_by_context(&ContextId::from("ctx"), None, 0).await.map(|_| ())));
+        assert!(invalid(configs.list_configs(&TaskId::from("task-1"), None, 0).await.map(|_| ())));
+        assert!(invalid(Task::minimal("t".to_string(), "c".to_string()).history_page(None, 0).map(|_| ())));
+        assert!(store.list_tasks(None, 1).await.unwrap().is_last());
+    }
+
+    #[tokio::test]
+    async fn test_push_config_pages() {
+        let store = InMemoryPushNotificationConfigStore::new();
+        let task_id = TaskId::from("task-1");
+        let config = |id: Option<&str>| PushNotificationConfig {
+            url: "https://example.com/hook".to_string(),
+            authentication: None,
+            id: id.map(str::to_string),
+            token: None,
+        };
+
+        let generated = store.set_config(&task_id, config(None)).await.unwrap();
+        assert_eq!(generated.id.as_deref(), Some("config-1"));
+        store.set_config(&task_id, config(Some("b"))).await.unwrap();
+        store.set_config(&task_id, config(Some("c"))).await.unwrap();
+
+        let first = store.list_configs(&task_id, None, 2).await.unwrap();
+        assert_eq!(first.items.len(), 2);
+
+        // Appending and replacing between pages neither repeats nor skips
+        store.set_config(&task_id, config(Some("d"))).await.unwrap();
+        store.set_config(&task_id, config(Some("b"))).await.unwrap();
+        let second = store.list_configs(&task_id, first.next_cursor.as_deref(), 2).await.unwrap();
+        assert_eq!(
+            second.items.iter().map(|c| c.id.as_deref().unwrap()).collect::<Vec<_>>(),
+            ["c", "d"]
+        );
+        assert!(second.is_last());
+
+        assert!(matches!(store.list_configs(&task_id, Some("zzz"), 2).await, Err(A2AError::InvalidParams(_))));
+        store.delete_config(&task_id, &ConfigId::from("c")).await.unwrap();
+        assert!(store.get_config(&task_id, &ConfigId::from("c")).await.is_err());
+        assert_eq!(store.delete_configs_for_task(&task_id).await.unwrap(), 3);
+        assert!(store.list_configs(&task_id, None, 10).await.unwrap().items.is_empty());
+
+        // A store with an ID source mints from it
+        let mut ids = crate::ids::SequentialSource::new();
+        ids.new_config_id();
+        let store = InMemoryPushNotificationConfigStore::with_id_source(ids);
+        let generated = store.set_config(&task_id, config(None)).await.unwrap();
+        assert_eq!(generated.id.as_deref(), Some("config-2"));
+    }
+}
@@ -0,0 +1,254 @@
+//! SQL task storage over SQLx.
+//!
+//! Enabled by the `sqlx` feature. [`SqlTaskStore`] works with SQLite and
+//! Postgres through SQLx's `Any` driver. Tasks are stored in a `tasks` table
+//! whose `payload` column holds the task in its wire format, so unknown
+//! fields are preserved. The remaining columns (`context_id`, `state`,
+//! `created_at` and `updated_at`) exist for querying and for optimistic
+//! concurrency.
+
+use super::{TaskStore, conflict_error, next_timestamp, task_not_found};
+use crate::ids::{ContextId, TaskId};
+use crate::page::{self, Page};
+use crate::{A2AError, InternalError, Task};
+use sqlx::any::AnyPoolOptions;
+use sqlx::{AnyPool, Executor, Row};
+
+/// Schema migrations, applied in order and recorded in `a2a_migrations`.
+const MIGRATIONS: &[(i64, &str)] = &[
+    (
+        1,
+        "CREATE TABLE tasks (
+            id VARCHAR(255) PRIMARY KEY,
+            context_id VARCHAR(255) NOT NULL,
+            state VARCHAR(32) NOT NULL,
+            payload TEXT NOT NULL,
+            created_at VARCHAR(32) NOT NULL,
+            updated_at VARCHAR(32) NOT NULL
+        )",
+    ),
+    (2, "CREATE INDEX tasks_context_id_idx ON tasks (context_id, id)"),
+];
+
+/// Task storage in an SQL database.
+#[derive(Debug, Clone)]
+pub struct SqlTaskStore {
+    pool: AnyPool,
+}
+
+impl SqlTaskStore {
+    /// Connect to a database and apply pending migrations.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The database URL, e.g. `sqlite://tasks.db?mode=rwc` or
+    ///   `postgres://user@localhost/agent`.
+    ///
+    /// # Returns
+    ///
+    /// The store, or an `Internal` error if connecting or migrating fails.
+    pub async fn connect(url: &str) -> Result<Self, A2AError> {
+        sqlx::any::install_default_drivers();
+        let pool = AnyPoolOptions::new().connect(url).await.map_err(database_error)?;
+        let store = Self::from_pool(pool);
+        store.migrate().await?;
+        Ok(store)
+    }
+
+    /// Use an existing connection pool.
+    ///
+    /// Migrations are not applied; call [`SqlTaskStore::migrate`] first if
+    /// the schema may be out of date.
+    pub fn from_pool(pool: AnyPool) -> Self {
+        Self { pool }
+    }
+
+    /// The underlying connection pool.
+    pub fn pool(&self) -> &AnyPool {
+        &self.pool
+    }
+
+    /// Apply pending schema migrations.
+    pub async fn migrate(&self) -> Result<(), A2AError> {
+        self.pool
+            .execute("CREATE TABLE IF NOT EXISTS a2a_migrations (version BIGINT PRIMARY KEY)")
+            .await
+            .map_err(database_error)?;
+
+        let applied: Vec<i64> = sqlx::query("SELECT version FROM a2a_migrations")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(database_error)?
+            .iter()
+            .map(|row| row.try_get(0))
+            .collect::<Result<_, _>>()
+            .map_err(database_error)?;
+
+        for (version, statement) in MIGRATIONS {
+            if applied.contains(version) {
+                continue;
+            }
+
+            let mut transaction = self.pool.begin().await.map_err(database_error)?;
+            transaction.execute(*statement).await.map_err(database_error)?;
+            sqlx::query("INSERT INTO a2a_migrations (version) VALUES ($1)")
+                .bind(*version)
+                .execute(&mut *transaction)
+                .await
+                .map_err(database_error)?;
+            transaction.commit().await.map_err(database_error)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TaskStore for SqlTaskStore {
+    async fn get_task(&self, id: &TaskId) -> Result<Task, A2AError> {
+        let row = sqlx::query("SELECT payload FROM tasks WHERE id = $1")
+            .bind(id.as_str())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(database_error)?
+            .ok_or_else(|| task_not_found(id))?;
+        decode_task(&row)
+    }
+
+    async fn create_task(&self, mut task: Task) -> Result<Task, A2AError> {
+        let now = next_timestamp();
+        task.created_at = Some(now.clone());
+        task.updated_at = Some(now.clone());
+
+        let result = sqlx::query(
+            "INSERT INTO tasks (id, context_id, state, payload, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(&task.id)
+        .bind(&task.context_id)
+        .bind(state_name(&task))
+        .bind(encode_task(&task)?)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(_) => Ok(task),
+            Err(e) if e.as_database_error().is_some_and(|e| e.is_unique_violation()) => {
+                Err(conflict_error(&task.id, "already exists"))
+            }
+            Err(e) => Err(database_error(e)),
+        }
+    }
+
+    async fn update_task(&self, mut task: Task) -> Result<Task, A2AError> {
+        let expected = task.updated_at.clone().unwrap_or_default();
+        let now = next_timestamp();
+        task.updated_at = Some(now.clone());
+
+        // Keep the stored creation time; it is rewritten into the payload below
+        let created_at: Option<String> = sqlx::query("SELECT created_at FROM tasks WHERE id = $1")
+            .bind(&task.id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(database_error)?
+            .map(|row| row.try_get(0))
+            .transpose()
+            .map_err(database_error)?;
+        task.created_at = Some(created_at.ok_or_else(|| task_not_found(&task.typed_id()))?);
+
+        let result = sqlx::query(
+            "UPDATE tasks SET context_id = $1, state = $2, payload = $3, updated_at = $4 WHERE id = $5 AND updated_at = $6",
+        )
+        .bind(&task.context_id)
+        .bind(state_name(&task))
+        .bind(encode_task(&task)?)
+        .bind(&now)
+        .bind(&task.id)
+        .bind(&expected)
+        .execute(&self.pool)
+        .await
+        .map_err(database_error)?;
+
+        if result.rows_affected() == 0 {
+            // Either deleted or modified since the task was read
+            return match self.get_task(&task.typed_id()).await {
+                Ok(_) => Err(conflict_error(&task.id, "was modified concurrently")),
+                Err(e) => Err(e),
+            };
+        }
+
+        Ok(task)
+    }
+
+    async fn delete_task(&self, id: &TaskId) -> Result<(), A2AError> {
+        let result = sqlx::query("DELETE FROM tasks WHERE id = $1")
+            .bind(id.as_str())
+            .execute(&self.pool)
+            .await
+            .map_err(database_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(task_not_found(id));
+        }
+        Ok(())
+    }
+
+    async fn list_tasks(&self, cursor: Option<&str>, limit: usize) -> Result<Page<Task>, A2AError> {
+        page::check_limit(limit)?;
+        let rows = sqlx::query("SELECT payload FROM tasks WHERE id > $1 ORDER BY id LIMIT $2")
+            .bind(cursor.unwrap_or_default())
+            .bind(i64::try_from(limit.saturating_add(1)).unwrap_or(i64::MAX))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(database_error)?;
+        let tasks = rows.iter().map(decode_task).collect::<Result<_, _>>()?;
+        Ok(Page::from_overfetch(tasks, limit, |task: &Task| task.id.clone()))
+    }
+
+    async fn list_tasks_by_context(
+        &self,
+        context_id: &ContextId,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> Result<Page<Task>, A2AError> {
+        page::check_limit(limit)?;
+        // Fetch one extra row to learn whether another page exists
+        let rows = sqlx::query("SELECT payload FROM tasks WHERE context_id = $1 AND id > $2 ORDER BY id LIMIT $3")
+            .bind(context_id.as_str())
+            .bind(cursor.unwrap_or_default())
+            .bind(i64::try_from(limit.saturating_add(1)).unwrap_or(i64::MAX))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(database_error)?;
+        let tasks = rows.iter().map(decode_task).collect::<Result<_, _>>()?;
+        Ok(Page::from_overfetch(tasks, limit, |task: &Task| task.id.clone()))
+    }
+}
+
+fn state_name(task: &Task) -> String {
+    match serde_json::to_value(&task.status.state) {
+        Ok(serde_json::Value::String(state)) => state,
+        _ => "unknown".to_string(),
+    }
+}
+
+fn encode_task(task: &Task) -> Result<String, A2AError> {
+    serde_json::to_string(task).map_err(|e| internal_error(format!("Failed to serialize task {}: {}", task.id, e)))
+}
+
+fn decode_task(row: &sqlx::any::AnyRow) -> Result<Task, A2AError> {
+    let payload: String = row.try_get(0).map_err(database_error)?;
+    serde_json::from_str(&payload).map_err(|e| internal_error(format!("Stored task is corrupt: {}", e)))
+}
+
+fn database_error(error: sqlx::Error) -> A2AError {
+    internal_error(format!("Task store database error: {}", error))
+}
+
+fn internal_error(message: String) -> A2AError {
+    A2AError::Internal(Box::new(InternalError {
+        code: -32603,
+        message,
+        data: None,
+    }))
+}
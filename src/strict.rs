@@ -0,0 +1,582 @@
+//! Opt-in strict deserialization that rejects unknown fields.
+//!
+//! The protocol types ignore unknown fields by default so that newer peers
+//! stay compatible. Security-sensitive deployments can use [`from_str`] or
+//! [`from_value`] instead, which fail when the payload contains any field the
+//! target type does not know about, listing the full path of each one.
+
+use crate::*;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// Types whose wire field names are known to the strict parser.
+///
+/// The implementations form the crate's registry of wire names, read with
+/// [`wire_fields`]. `tests/wire_fields.rs` checks it against the spec's
+/// JSON schema, with the crate's deliberate differences listed in
+/// `tests/wire_fields.json`.
+pub trait KnownFields {
+    /// The wire names of every field of this type.
+    const FIELDS: &'static [&'static str];
+
+    /// Collect the paths of unknown fields in `value`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The JSON value to inspect.
+    /// * `path` - The path of `value` within the document (empty for the root).
+    /// * `unknown` - Receives the path of every unknown field.
+    fn collect_unknown(value: &Value, path: &[PathSegment], unknown: &mut Vec<Vec<PathSegment>>);
+}
+
+/// One step of the path to a field: an object key or an array index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A key of an object, as written, dots and slashes included.
+    Key(String),
+    /// An index into an array.
+    Index(usize),
+}
+
+/// Append a step to a path.
+fn join(path: &[PathSegment], segment: PathSegment) -> Vec<PathSegment> {
+    let mut joined = path.to_vec();
+    joined.push(segment);
+    joined
+}
+
+/// Join a field name onto a path.
+fn join_key(path: &[PathSegment], key: &str) -> Vec<PathSegment> {
+    join(path, PathSegment::Key(key.to_string()))
+}
+
+/// Format a path with dots between keys and `[i]` for array indices.
+fn dotted(path: &[PathSegment]) -> String {
+    let mut dotted = String::new();
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) => {
+                if !dotted.is_empty() {
+                    dotted.push('.');
+                }
+                dotted.push_str(key);
+            }
+            PathSegment::Index(index) => dotted.push_str(&format!("[{}]", index)),
+        }
+    }
+    dotted
+}
+
+/// Report unknown keys of an object and return it for further inspection.
+fn check_object<'a>(
+    value: &'a Value,
+    path: &[PathSegment],
+    fields: &[&str],
+    unknown: &mut Vec<Vec<PathSegment>>,
+) -> Option<&'a Map<String, Value>> {
+    let obj = value.as_object()?;
+    for key in obj.keys() {
+        if !fields.contains(&key.as_str()) {
+            unknown.push(join_key(path, key));
+        }
+    }
+    Some(obj)
+}
+
+fn nested<T: KnownFields>(
+    obj: &Map<String, Value>,
+    key: &str,
+    path: &[PathSegment],
+    unknown: &mut Vec<Vec<PathSegment>>,
+) {
+    if let Some(value) = obj.get(key) {
+        T::collect_unknown(value, &join_key(path, key), unknown);
+    }
+}
+
+fn nested_array<T: KnownFields>(
+    obj: &Map<String, Value>,
+    key: &str,
+    path: &[PathSegment],
+    unknown: &mut Vec<Vec<PathSegment>>,
+) {
+    if let Some(Value::Array(items)) = obj.get(key) {
+        let path = join_key(path, key);
+        for (i, item) in items.iter().enumerate() {
+            T::collect_unknown(item, &join(&path, PathSegment::Index(i)), unknown);
+        }
+    }
+}
+
+fn nested_map<T: KnownFields>(
+    obj: &Map<String, Value>,
+    key: &str,
+    path: &[PathSegment],
+    unknown: &mut Vec<Vec<PathSegment>>,
+) {
+    if let Some(Value::Object(entries)) = obj.get(key) {
+        let path = join_key(path, key);
+        for (name, item) in entries {
+            T::collect_unknown(item, &join_key(&path, name), unknown);
+        }
+    }
+}
+
+/// Implement [`KnownFields`] for a struct.
+///
+/// Leaf fields are listed first; fields holding other known types follow the
+/// semicolon as `"name" => Type`, `"name" => [Type]` (arrays) or
+/// `"name" => {Type}` (string-keyed maps).
+macro_rules! known_fields {
+    ($ty:ty: $($field:literal),* $(; $($nested:literal => $shape:tt),*)?) => {
+        impl KnownFields for $ty {
+            const FIELDS: &'static [&'static str] = &[$($field,)* $($($nested,)*)?];
+
+            #[allow(unused_variables)]
+            fn collect_unknown(value: &Value, path: &[PathSegment], unknown: &mut Vec<Vec<PathSegment>>) {
+                if let Some(obj) = check_object(value, path, Self::FIELDS, unknown) {
+                    $($(known_fields!(@nested obj, $nested, $shape, path, unknown);)*)?
+                }
+            }
+        }
+    };
+    (@nested $obj:ident, $name:literal, [$inner:ty], $path:ident, $unknown:ident) => {
+        nested_array::<$inner>($obj, $name, $path, $unknown)
+    };
+    (@nested $obj:ident, $name:literal, {$inner:ty}, $path:ident, $unknown:ident) => {
+        nested_map::<$inner>($obj, $name, $path, $unknown)
+    };
+    (@nested $obj:ident, $name:literal, $inner:ty, $path:ident, $unknown:ident) => {
+        nested::<$inner>($obj, $name, $path, $unknown)
+    };
+}
+
+known_fields!(Message:
+    "kind", "messageId", "role", "contextId", "extensions", "metadata", "referenceTaskIds", "taskId";
+    "parts" => [Part]);
+known_fields!(TextPart: "kind", "text", "metadata");
+known_fields!(FilePart: "kind", "metadata"; "file" => FileContent);
+known_fields!(DataPart: "kind", "data", "metadata");
+known_fields!(FileContent: "bytes", "uri", "name", "mimeType");
+known_fields!(TaskStatus: "state", "timestamp"; "message" => Message);
+known_fields!(Artifact:
+    "artifactId", "description", "extensions", "metadata", "name";
+    "parts" => [Part]);
+known_fields!(A2AErrorResponse: "code", "message", "data");
+known_fields!(TaskStatusUpdateEvent: "taskId", "contextId", "kind", "final", "metadata"; "status" => TaskStatus);
+known_fields!(TaskArtifactUpdateEvent:
+    "taskId", "contextId", "kind", "append", "lastChunk", "metadata";
+    "artifact" => Artifact);
+known_fields!(Task:
+    "id", "kind", "contextId", "metadata", "result", "createdAt", "updatedAt";
+    "status" => TaskStatus,
+    "artifacts" => [Artifact],
+    "history" => [Message],
+    "error" => A2AErrorResponse,
+    "statusHistory" => [TaskStatus]);
+
+known_fields!(AgentExtension: "uri", "required", "description", "params");
+known_fields!(AgentCapabilities:
+    "pushNotifications", "stateTransitionHistory", "streaming";
+    "extensions" => [AgentExtension]);
+known_fields!(AgentInterface: "url", "transport");
+known_fields!(AgentProvider: "organization", "url");
+known_fields!(AgentSkill:
+    "name", "description", "inputModes", "outputModes", "examples", "x-nameLocalizations",
+    "x-descriptionLocalizations");
+known_fields!(ApiKeySecurityScheme: "type", "in", "name", "description");
+known_fields!(HttpSecurityScheme: "type", "scheme", "bearerFormat", "description");
+known_fields!(OpenIdConnectSecurityScheme: "type", "openIdConnectUrl", "description");
+known_fields!(OAuth2SecurityScheme: "type", "description"; "flows" => OAuth2Flows);
+known_fields!(OAuth2Flows:;
+    "implicit" => ImplicitOAuthFlow,
+    "password" => PasswordOAuthFlow,
+    "clientCredentials" => ClientCredentialsOAuthFlow,
+    "authorizationCode" => AuthorizationCodeOAuthFlow);
+known_fields!(AuthorizationCodeOAuthFlow: "authorizationUrl", "tokenUrl", "refreshUrl", "scopes");
+known_fields!(ClientCredentialsOAuthFlow: "tokenUrl", "refreshUrl", "scopes");
+known_fields!(ImplicitOAuthFlow: "authorizationUrl", "refreshUrl", "scopes");
+known_fields!(PasswordOAuthFlow: "tokenUrl", "refreshUrl", "scopes");
+#[cfg(not(feature = "spec-0-3"))]
+known_fields!(AgentCard:
+    "name", "description", "version", "protocolVersion", "url", "preferredTransport",
+    "defaultInputModes", "defaultOutputModes", "documentationUrl", "iconUrl",
+    "supportsAuthenticatedExtendedCard", "security", "x-nameLocalizations", "x-descriptionLocalizations";
+    "capabilities" => AgentCapabilities,
+    "skills" => [AgentSkill],
+    "provider" => AgentProvider,
+    "additionalInterfaces" => [AgentInterface],
+    "securitySchemes" => {SecurityScheme});
+#[cfg(feature = "spec-0-3")]
+known_fields!(AgentCard:
+    "name", "description", "version", "protocolVersion", "url", "preferredTransport",
+    "defaultInputModes", "defaultOutputModes", "documentationUrl", "iconUrl",
+    "supportsAuthenticatedExtendedCard", "security", "x-nameLocalizations", "x-descriptionLocalizations";
+    "capabilities" => AgentCapabilities,
+    "skills" => [AgentSkill],
+    "provider" => AgentProvider,
+    "additionalInterfaces" => [AgentInterface],
+    "securitySchemes" => {SecurityScheme},
+    "signatures" => [AgentCardSignature]);
+#[cfg(feature = "spec-0-3")]
+known_fields!(AgentCardSignature: "protected", "signature", "header");
+
+known_fields!(PushNotificationAuthenticationInfo: "schemes", "credentials");
+known_fields!(PushNotificationConfig:
+    "url", "id", "token";
+    "authentication" => PushNotificationAuthenticationInfo);
+known_fields!(MessageSendConfiguration:
+    "acceptedOutputModes", "blocking", "historyLength";
+    "pushNotificationConfig" => PushNotificationConfig);
+known_fields!(SendMessageParams:
+    "metadata";
+    "message" => Message,
+    "configuration" => MessageSendConfiguration);
+known_fields!(PushNotificationConfigResult: "taskId", "configId");
+known_fields!(PushNotificationConfigInfo: "configId", "url");
+known_fields!(TaskIdParams: "id", "metadata");
+known_fields!(GetTaskParams: "taskId", "historyLength");
+known_fields!(CancelTaskParams: "taskId");
+known_fields!(SetTaskPushNotificationConfigParams: "taskId"; "config" => PushNotificationConfig);
+known_fields!(GetTaskPushNotificationConfigParams: "taskId", "configId");
+known_fields!(TaskResubscriptionParams: "taskId");
+known_fields!(ListTaskPushNotificationConfigParams: "taskId");
+known_fields!(DeleteTaskPushNotificationConfigParams: "taskId", "configId");
+
+known_fields!(SendMessageRequest: "method", "id", "jsonrpc"; "params" => SendMessageParams);
+known_fields!(SendStreamingMessageRequest: "method", "id", "jsonrpc"; "params" => SendMessageParams);
+known_fields!(GetTaskRequest: "method", "id", "jsonrpc"; "params" => GetTaskParams);
+known_fields!(CancelTaskRequest: "method", "id", "jsonrpc"; "params" => CancelTaskParams);
+known_fields!(SetTaskPushNotificationConfigRequest:
+    "method", "id", "jsonrpc";
+    "params" => SetTaskPushNotificationConfigParams);
+known_fields!(GetTaskPushNotificationConfigRequest:
+    "method", "id", "jsonrpc";
+    "params" => GetTaskPushNotificationConfigParams);
+known_fields!(TaskResubscriptionRequest:
+    "method", "id", "jsonrpc";
+    "params" => TaskResubscriptionParams);
+known_fields!(ListTaskPushNotificationConfigRequest:
+    "method", "id", "jsonrpc";
+    "params" => ListTaskPushNotificationConfigParams);
+known_fields!(DeleteTaskPushNotificationConfigRequest:
+    "method", "id", "jsonrpc";
+    "params" => DeleteTaskPushNotificationConfigParams);
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+// `state` is the legacy name of `status`, still accepted by serde
+known_fields!(ListTasksParams: "contextId", "status", "state", "pageSize", "pageToken", "historyLength");
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+known_fields!(ListTasksRequest: "method", "id", "jsonrpc"; "params" => ListTasksParams);
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+known_fields!(ListTasksResult: "nextPageToken"; "tasks" => [Task]);
+
+impl KnownFields for Part {
+    const FIELDS: &'static [&'static str] = &["kind", "text", "file", "data", "metadata"];
+
+    fn collect_unknown(value: &Value, path: &[PathSegment], unknown: &mut Vec<Vec<PathSegment>>) {
+        match value.get("kind").and_then(Value::as_str) {
+            Some("text") => TextPart::collect_unknown(value, path, unknown),
+            Some("file") => FilePart::collect_unknown(value, path, unknown),
+            Some("data") => DataPart::collect_unknown(value, path, unknown),
+            // Parts of unknown kinds are kept by lenient parsing; strict
+            // parsing rejects their kind
+            Some(_) => unknown.push(join_key(path, "kind")),
+            // A missing kind is rejected by deserialization itself
+            None => {}
+        }
+    }
+}
+
+impl KnownFields for SecurityScheme {
+    const FIELDS: &'static [&'static str] = &[
+        "type",
+        "in",
+        "name",
+        "scheme",
+        "bearerFormat",
+        "flows",
+        "openIdConnectUrl",
+        "description",
+    ];
+
+    fn collect_unknown(value: &Value, path: &[PathSegment], unknown: &mut Vec<Vec<PathSegment>>) {
+        match value.get("type").and_then(Value::as_str) {
+            Some("apiKey") => ApiKeySecurityScheme::collect_unknown(value, path, unknown),
+            Some("http") => HttpSecurityScheme::collect_unknown(value, path, unknown),
+            Some("oauth2") => OAuth2SecurityScheme::collect_unknown(value, path, unknown),
+            Some("openIdConnect") => {
+                OpenIdConnectSecurityScheme::collect_unknown(value, path, unknown)
+            }
+            _ => {
+                check_object(value, path, Self::FIELDS, unknown);
+            }
+        }
+    }
+}
+
+/// Requests are checked against the request type of their `method`.
+impl KnownFields for A2ARequest {
+    const FIELDS: &'static [&'static str] = &["method", "id", "jsonrpc", "params"];
+
+    fn collect_unknown(value: &Value, path: &[PathSegment], unknown: &mut Vec<Vec<PathSegment>>) {
+        let Some(method) = value.get("method").and_then(Value::as_str).and_then(RequestMethod::from_str) else {
+            // An unknown method is rejected by deserialization itself
+            check_object(value, path, Self::FIELDS, unknown);
+            return;
+        };
+        match method {
+            RequestMethod::MessageSend => SendMessageRequest::collect_unknown(value, path, unknown),
+            RequestMethod::MessageStream => SendStreamingMessageRequest::collect_unknown(value, path, unknown),
+            RequestMethod::TasksGet => GetTaskRequest::collect_unknown(value, path, unknown),
+            RequestMethod::TasksCancel => CancelTaskRequest::collect_unknown(value, path, unknown),
+            RequestMethod::TasksPushNotificationConfigSet => {
+                SetTaskPushNotificationConfigRequest::collect_unknown(value, path, unknown)
+            }
+            RequestMethod::TasksPushNotificationConfigGet => {
+                GetTaskPushNotificationConfigRequest::collect_unknown(value, path, unknown)
+            }
+            RequestMethod::TasksPushNotificationConfigList => {
+                ListTaskPushNotificationConfigRequest::collect_unknown(value, path, unknown)
+            }
+            RequestMethod::TasksPushNotificationConfigDelete => {
+                DeleteTaskPushNotificationConfigRequest::collect_unknown(value, path, unknown)
+            }
+            RequestMethod::TasksResubscribe => TaskResubscriptionRequest::collect_unknown(value, path, unknown),
+            #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+            RequestMethod::TasksList => ListTasksRequest::collect_unknown(value, path, unknown),
+        }
+    }
+}
+
+/// The wire names of every field of `T`, as registered for strict parsing.
+///
+/// Variants of internally tagged unions, such as [`TextPart`], include the
+/// tag field.
+pub fn wire_fields<T: KnownFields>() -> &'static [&'static str] {
+    T::FIELDS
+}
+
+/// List the paths of all fields in `value` unknown to `T`.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value to inspect.
+///
+/// # Returns
+///
+/// The dotted paths (with `[i]` for array indices) of every unknown field.
+/// Keys containing dots make these ambiguous; [`unknown_field_paths`]
+/// returns the steps of each path instead.
+pub fn unknown_fields<T: KnownFields>(value: &Value) -> Vec<String> {
+    unknown_field_paths::<T>(value).iter().map(|path| dotted(path)).collect()
+}
+
+/// List the paths of all fields in `value` unknown to `T`, step by step.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value to inspect.
+///
+/// # Returns
+///
+/// The keys and array indices leading to every unknown field.
+pub fn unknown_field_paths<T: KnownFields>(value: &Value) -> Vec<Vec<PathSegment>> {
+    let mut unknown = Vec::new();
+    T::collect_unknown(value, &[], &mut unknown);
+    unknown
+}
+
+/// Strictly deserialize a JSON value, rejecting unknown fields.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value to deserialize.
+///
+/// # Returns
+///
+/// The deserialized value, or an `InvalidParams` error. When unknown fields
+/// are present, the error `data` lists their paths under `unknownFields`.
+pub fn from_value<T: DeserializeOwned + KnownFields>(value: Value) -> Result<T, A2AError> {
+    let unknown = unknown_fields::<T>(&value);
+    if !unknown.is_empty() {
+        return Err(A2AError::InvalidParams(Box::new(InvalidParamsError {
+            code: -32602,
+            message: format!("Unknown fields: {}", unknown.join(", ")),
+            data: Some(serde_json::json!({ "unknownFields": unknown })),
+        })));
+    }
+
+    serde_json::from_value(value).map_err(|e| A2AError::InvalidParams(Box::new(InvalidParamsError {
+        code: -32602,
+        message: format!("Invalid parameters: {}", e),
+        data: None,
+    })))
+}
+
+/// Strictly deserialize a JSON string, rejecting unknown fields.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string to parse.
+///
+/// # Returns
+///
+/// The deserialized value, a `JSONParse` error for malformed JSON, or an
+/// `InvalidParams` error as described in [`from_value`].
+pub fn from_str<T: DeserializeOwned + KnownFields>(json: &str) -> Result<T, A2AError> {
+    let value = serde_json::from_str(json).map_err(|e| A2AError::JSONParse(Box::new(JSONParseError {
+        code: -32700,
+        message: format!("Invalid JSON payload: {}", e),
+        data: None,
+    })))?;
+    from_value(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = r#"{
+        "kind": "message",
+        "messageId": "msg-1",
+        "parts": [{"kind": "text", "text": "Hello"}],
+        "role": "user"
+    }"#;
+
+    #[test]
+    fn test_strict_accepts_known_fields() {
+        let message: Message = from_str(MESSAGE).unwrap();
+        assert_eq!(message.message_id, "msg-1");
+
+        // Metadata and data parts are opaque and never reported
+        let value = serde_json::json!({
+            "kind": "message",
+            "messageId": "msg-1",
+            "parts": [{"kind": "data", "data": {"anything": 1}, "metadata": {"x": 1}}],
+            "role": "user",
+            "metadata": {"free": "form"}
+        });
+        assert!(unknown_fields::<Message>(&value).is_empty());
+
+        let params: GetTaskParams = from_str(r#"{"taskId": "task-1", "historyLength": 2}"#).unwrap();
+        assert_eq!(params.history_length, Some(2));
+    }
+
+    #[test]
+    fn test_strict_requests_follow_their_method() {
+        let value = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tasks/get",
+            "params": {"taskId": "task-1", "verbose": true},
+            "trace": "abc"
+        });
+        assert_eq!(unknown_fields::<A2ARequest>(&value), ["trace", "params.verbose"]);
+        assert!(from_value::<A2ARequest>(value).is_err());
+    }
+
+    #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+    #[test]
+    fn test_strict_accepts_the_legacy_state_filter() {
+        let value = serde_json::json!({"contextId": "ctx-1", "state": "working"});
+        let params = from_value::<ListTasksParams>(value).unwrap();
+        assert_eq!(params.status, Some(TaskState::Working));
+    }
+
+    #[test]
+    fn test_strict_reports_top_level_field() {
+        let mut value: Value = serde_json::from_str(MESSAGE).unwrap();
+        value["priority"] = serde_json::json!("high");
+
+        assert_eq!(unknown_fields::<Message>(&value), vec!["priority"]);
+        match from_value::<Message>(value) {
+            Err(A2AError::InvalidParams(e)) => {
+                assert_eq!(e.code, -32602);
+                assert_eq!(e.data.unwrap()["unknownFields"][0], "priority");
+            }
+            other => panic!("Expected InvalidParams, got {:?}", other),
+        }
+
+        // Lenient default parsing still accepts the payload
+        assert!(serde_json::from_str::<Message>(MESSAGE).is_ok());
+    }
+
+    #[test]
+    fn test_strict_rejects_unknown_part_kinds() {
+        let value = serde_json::json!({
+            "kind": "message",
+            "messageId": "msg-1",
+            "parts": [{"kind": "text", "text": "Look"}, {"kind": "video", "url": "https://example.com/v.mp4"}],
+            "role": "user"
+        });
+        assert_eq!(unknown_fields::<Message>(&value), vec!["parts[1].kind"]);
+        assert!(matches!(from_value::<Message>(value.clone()), Err(A2AError::InvalidParams(_))));
+
+        // Lenient default parsing keeps the part
+        let message: Message = serde_json::from_value(value).unwrap();
+        assert!(!message.parts[1].is_known());
+    }
+
+    #[test]
+    fn test_strict_reports_nested_field_paths() {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "message/send",
+            "params": {
+                "message": {
+                    "kind": "message",
+                    "messageId": "msg-1",
+                    "parts": [
+                        {"kind": "text", "text": "Hello"},
+                        {"kind": "file", "file": {"uri": "https://example.com/f", "size": 10}}
+                    ],
+                    "role": "user"
+                },
+                "configuration": {
+                    "acceptedOutputModes": ["text/plain"],
+                    "pushNotificationConfig": {"url": "https://example.com/hook", "extra": 1}
+                }
+            }
+        });
+
+        assert_eq!(
+            unknown_fields::<SendMessageRequest>(&request),
+            vec![
+                "params.message.parts[1].file.size",
+                "params.configuration.pushNotificationConfig.extra",
+            ]
+        );
+        assert!(from_value::<SendMessageRequest>(request).is_err());
+    }
+
+    #[test]
+    fn test_strict_task_and_agent_card() {
+        let task = serde_json::json!({
+            "id": "task-1",
+            "kind": "task",
+            "contextId": "ctx-1",
+            "status": {"state": "working", "progress": 50}
+        });
+        assert_eq!(unknown_fields::<Task>(&task), vec!["status.progress"]);
+
+        let card = serde_json::json!({
+            "name": "Agent",
+            "description": "An agent",
+            "version": "1.0.0",
+            "protocolVersion": "0.2.5",
+            "url": "https://example.com/agent",
+            "capabilities": {"streaming": true},
+            "defaultInputModes": ["text/plain"],
+            "defaultOutputModes": ["text/plain"],
+            "skills": [],
+            "securitySchemes": {
+                "apiKey": {"type": "apiKey", "in": "header", "name": "X-API-Key", "scope": "all"}
+            }
+        });
+        assert_eq!(
+            unknown_fields::<AgentCard>(&card),
+            vec!["securitySchemes.apiKey.scope"]
+        );
+    }
+}
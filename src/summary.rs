@@ -0,0 +1,231 @@
+//! One-line task descriptions for dashboards, CLIs and logs.
+//!
+//! [`Task::summary`] and [`TaskStatusUpdateEvent::summary`] collect the
+//! fields worth showing at a glance, and [`TaskSummary`] renders them as
+//! `task-123 [working] 42s "Analyzing document…" (2 artifacts)`.
+
+use crate::clock::parse_timestamp;
+use crate::{Message, Part, Task, TaskState, TaskStatusUpdateEvent};
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+/// The longest status message text shown by [`TaskSummary`]'s `Display`
+/// implementation, in characters, before it is cut off with `…`.
+pub const MAX_SUMMARY_MESSAGE_CHARS: usize = 60;
+
+/// The at-a-glance state of a task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskSummary {
+    /// The task ID.
+    pub id: String,
+    /// The context the task belongs to.
+    pub context_id: String,
+    /// The current state.
+    pub state: TaskState,
+    /// Time since the task was created, if its `createdAt` is known.
+    pub age: Option<Duration>,
+    /// The text of the current status message, if it has any.
+    pub last_message: Option<String>,
+    /// The number of artifacts produced so far.
+    pub artifact_count: usize,
+}
+
+impl Task {
+    /// Summarize the task as of now.
+    pub fn summary(&self) -> TaskSummary {
+        self.summary_at(SystemTime::now())
+    }
+
+    /// Summarize the task as of a given time.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The time to compute the task's age against.
+    ///
+    /// # Returns
+    ///
+    /// The summary; `age` is `None` if `createdAt` is missing, unparseable
+    /// or after `now`.
+    pub fn summary_at(&self, now: SystemTime) -> TaskSummary {
+        let created_at = self.created_at.as_deref().and_then(parse_timestamp);
+        TaskSummary {
+            id: self.id.clone(),
+            context_id: self.context_id.clone(),
+            state: self.status.state.clone(),
+            age: created_at.and_then(|created_at| now.duration_since(created_at).ok()),
+            last_message: self.status.message.as_ref().and_then(message_text),
+            artifact_count: self.artifacts.as_ref().map_or(0, Vec::len),
+        }
+    }
+}
+
+impl TaskStatusUpdateEvent {
+    /// Summarize the update for a log line.
+    ///
+    /// Events carry neither the task's creation time nor its artifacts, so
+    /// `age` is `None` and `artifact_count` is zero.
+    pub fn summary(&self) -> TaskSummary {
+        TaskSummary {
+            id: self.task_id.clone(),
+            context_id: self.context_id.clone(),
+            state: self.status.state.clone(),
+            age: None,
+            last_message: self.status.message.as_ref().and_then(message_text),
+            artifact_count: 0,
+        }
+    }
+}
+
+/// Join the text parts of a message, or `None` if there is no text.
+fn message_text(message: &Message) -> Option<String> {
+    let text = message
+        .parts
+        .iter()
+        .filter_map(|part| match part {
+            Part::Text(text) => Some(text.text.trim()),
+            _ => None,
+        })
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+impl fmt::Display for TaskSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]", self.id, self.state)?;
+        if let Some(age) = self.age {
+            write!(f, " {}", format_age(age))?;
+        }
+        if let Some(message) = &self.last_message {
+            // Keep the line on one line
+            let message = message.split_whitespace().collect::<Vec<_>>().join(" ");
+            write!(f, " {:?}", truncate(&message, MAX_SUMMARY_MESSAGE_CHARS))?;
+        }
+        match self.artifact_count {
+            0 => Ok(()),
+            1 => write!(f, " (1 artifact)"),
+            count => write!(f, " ({} artifacts)", count),
+        }
+    }
+}
+
+/// Format a duration in its largest whole unit, e.g. `42s`, `5m` or `3d`.
+fn format_age(age: Duration) -> String {
+    let seconds = age.as_secs();
+    match seconds {
+        0..60 => format!("{}s", seconds),
+        60..3600 => format!("{}m", seconds / 60),
+        3600..86_400 => format!("{}h", seconds / 3600),
+        _ => format!("{}d", seconds / 86_400),
+    }
+}
+
+/// Cut `text` to at most `max` characters plus `…`, without separating a
+/// character from the combining marks, variation selectors or joiners that
+/// follow it.
+fn truncate(text: &str, max: usize) -> String {
+    let Some((mut cut, _)) = text.char_indices().nth(max) else {
+        return text.to_string();
+    };
+    while let Some(c) = text[cut..].chars().next() {
+        let joined = text[..cut].ends_with('\u{200D}');
+        if !joined && !extends_previous(c) {
+            break;
+        }
+        cut = text[..cut].char_indices().next_back().map_or(0, |(i, _)| i);
+        if cut == 0 {
+            break;
+        }
+    }
+    format!("{}…", text[..cut].trim_end())
+}
+
+/// Whether `c` belongs to the grapheme cluster of the character before it.
+fn extends_previous(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}'
+        | '\u{1AB0}'..='\u{1AFF}'
+        | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{200D}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE00}'..='\u{FE0F}'
+        | '\u{FE20}'..='\u{FE2F}'
+        | '\u{1F3FB}'..='\u{1F3FF}'
+        | '\u{E0020}'..='\u{E007F}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TaskStatus;
+
+    fn task() -> Task {
+        let mut task = Task::minimal("task-123".to_string(), "ctx-1".to_string());
+        task.status = TaskStatus {
+            state: TaskState::Working,
+            message: Some(Message::agent("msg-1".to_string(), ["Analyzing document"])),
+            ..TaskStatus::default()
+        };
+        task.created_at = Some("2025-01-31T12:00:00Z".to_string());
+        task.artifacts = Some(vec![
+            crate::Artifact::minimal("a".to_string(), vec![]),
+            crate::Artifact::minimal("b".to_string(), vec![]),
+        ]);
+        task
+    }
+
+    #[test]
+    fn test_task_summary() {
+        let now = parse_timestamp("2025-01-31T12:00:42Z").unwrap();
+        let summary = task().summary_at(now);
+        assert_eq!(summary.age, Some(Duration::from_secs(42)));
+        assert_eq!(summary.last_message.as_deref(), Some("Analyzing document"));
+        assert_eq!(summary.to_string(), "task-123 [working] 42s \"Analyzing document\" (2 artifacts)");
+
+        let mut task = task();
+        task.created_at = None;
+        task.status.message = Some(Message::agent("msg-2".to_string(), ["  "]));
+        task.artifacts = None;
+        task.status.state = TaskState::InputRequired;
+        let summary = task.summary_at(now);
+        assert_eq!(summary.age, None);
+        assert_eq!(summary.last_message, None);
+        assert_eq!(summary.to_string(), "task-123 [input-required]");
+
+        // Every state is shown by its wire name
+        for state in TaskState::ALL {
+            task.status.state = state.clone();
+            let wire = serde_json::to_value(state).unwrap();
+            assert_eq!(task.summary_at(now).to_string(), format!("task-123 [{}]", wire.as_str().unwrap()));
+        }
+
+        task.created_at = Some("yesterday".to_string());
+        assert_eq!(task.summary_at(now).age, None);
+    }
+
+    #[test]
+    fn test_status_update_summary() {
+        let task = task();
+        let event = TaskStatusUpdateEvent::new(task.id.clone(), task.context_id.clone(), task.status.clone(), false);
+        assert_eq!(event.summary().to_string(), "task-123 [working] \"Analyzing document\"");
+    }
+
+    #[test]
+    fn test_long_messages_are_truncated_between_graphemes() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("abcdefghij", 4), "abcd…");
+        // "e" followed by a combining acute accent stays together
+        assert_eq!(truncate("cafe\u{301} au lait", 4), "caf…");
+        // A family emoji is one grapheme of five characters
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        assert_eq!(truncate(&format!("ok {}!", family), 5), "ok…");
+
+        let mut task = task();
+        task.status.message = Some(Message::agent("msg-3".to_string(), ["word\nwrap ".repeat(20)]));
+        let line = task.summary_at(SystemTime::UNIX_EPOCH).to_string();
+        assert!(line.contains("\"word wrap word"));
+        assert!(line.contains("…\" (2 artifacts)"));
+        assert!(!line.contains('\n'));
+    }
+}
@@ -0,0 +1,862 @@
+//! Tasks, their status and the artifacts they produce.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::ids::IdSource;
+use crate::page::{self, Page};
+use crate::{A2AError, A2AErrorResponse, ExtraFields, FilePart, Message, Part};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::time::{Duration, SystemTime};
+
+/// Represents the possible states of a Task.
+///
+/// Defaults to [`TaskState::Unknown`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "kebab-case")]
+pub enum TaskState {
+    Submitted,
+    Working,
+    InputRequired,
+    Completed,
+    Canceled,
+    Failed,
+    Rejected,
+    AuthRequired,
+    #[default]
+    Unknown,
+}
+
+all_variants!(TaskState {
+    Submitted,
+    Working,
+    InputRequired,
+    Completed,
+    Canceled,
+    Failed,
+    Rejected,
+    AuthRequired,
+    Unknown,
+});
+
+impl TaskState {
+    /// Get the name of the state on the wire, e.g. `input-required`.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            TaskState::Submitted => "submitted",
+            TaskState::Working => "working",
+            TaskState::InputRequired => "input-required",
+            TaskState::Completed => "completed",
+            TaskState::Canceled => "canceled",
+            TaskState::Failed => "failed",
+            TaskState::Rejected => "rejected",
+            TaskState::AuthRequired => "auth-required",
+            TaskState::Unknown => "unknown",
+        }
+    }
+
+    /// Check if the state is final: completed, canceled, failed or rejected.
+    ///
+    /// Final states are exactly those without [`successors`](Self::successors).
+    pub const fn is_terminal(&self) -> bool {
+        self.successors().is_empty()
+    }
+
+    /// Get the states a task may move to from this one.
+    ///
+    /// This is the transition table that
+    /// [`validate_task_state_transition`](crate::validation::validate_task_state_transition)
+    /// enforces.
+    pub const fn successors(&self) -> &'static [TaskState] {
+        use TaskState::*;
+
+        match self {
+            Submitted => &[Working, Rejected, Canceled, AuthRequired],
+            Working => &[Completed, Failed, Canceled, InputRequired],
+            InputRequired => &[Working, Canceled, Failed],
+            AuthRequired => &[Working, Rejected, Canceled],
+            Completed | Failed | Canceled | Rejected => &[],
+            Unknown => &[Submitted, Working, Completed, Failed, Canceled, Rejected, AuthRequired, InputRequired],
+        }
+    }
+}
+
+impl fmt::Display for TaskState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// Only the four final states have no successors.
+const _: () = {
+    let states = TaskState::ALL;
+    let mut terminal = 0;
+    let mut i = 0;
+    while i < states.len() {
+        if states[i].is_terminal() {
+            terminal += 1;
+        }
+        i += 1;
+    }
+    assert!(terminal == 4, "only completed, canceled, failed and rejected may be final");
+};
+
+/// TaskState and accompanying message.
+///
+/// The default status is [`TaskState::Unknown`] with no message and no
+/// timestamp.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "strict-types", non_exhaustive)]
+pub struct TaskStatus {
+    /// Current state of the task
+    pub state: TaskState,
+    /// Additional status updates for client
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<Message>,
+    /// ISO 8601 datetime string when the status was recorded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// Task.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Task {
+    /// The ID of the task
+    pub id: String,
+    /// Event type
+    pub kind: String, // Always "task"
+    /// The status of the task
+    pub status: TaskStatus,
+    /// Server-generated id for contextual alignment across interactions
+    #[serde(rename = "contextId")]
+    pub context_id: String,
+    /// Collection of artifacts created by the agent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<Vec<Artifact>>,
+    /// Message history for the task
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<Vec<Message>>,
+    /// Extension metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::object))]
+    pub metadata: Option<serde_json::Value>,
+    /// The result of the task, if completed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::optional_value))]
+    pub result: Option<serde_json::Value>,
+    /// The error that occurred, if the task failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<A2AErrorResponse>,
+    /// The time the task was created
+    #[serde(rename = "createdAt", skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    /// The time the task was last updated
+    #[serde(rename = "updatedAt", skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    /// The history of status changes for this task
+    #[serde(rename = "statusHistory", skip_serializing_if = "Option::is_none")]
+    pub status_history: Option<Vec<TaskStatus>>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+/// The current time as a status timestamp, or none without the `std`
+/// feature.
+pub(crate) fn now_timestamp() -> Option<String> {
+    #[cfg(feature = "std")]
+    return Some(crate::clock::format_time(SystemTime::now()));
+    #[cfg(not(feature = "std"))]
+    None
+}
+
+impl TaskStatus {
+    /// Create a status with no message and no timestamp.
+    pub fn new(state: TaskState) -> Self {
+        Self {
+            state,
+            ..Self::default()
+        }
+    }
+
+    /// Create a status explaining itself in a short agent message.
+    ///
+    /// The message has the agent role, a random UUID as its ID and the task
+    /// and context IDs set. With the `std` feature the status is stamped
+    /// with the current time. Without the `uuid` feature, use
+    /// [`TaskStatus::with_agent_text_from`].
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state of the task.
+    /// * `task_id` - The task the status belongs to.
+    /// * `context_id` - The context of the task.
+    /// * `text` - What the agent says, e.g. the question of an
+    ///   `input-required` status.
+    #[cfg(feature = "uuid")]
+    pub fn with_agent_text(state: TaskState, task_id: &str, context_id: &str, text: impl Into<String>) -> Self {
+        Self::with_agent_text_at(state, task_id, context_id, text, SystemTime::now())
+    }
+
+    /// Create a status explaining itself in a short agent message, stamped
+    /// with a given time, e.g. from a [`Clock`](crate::clock::Clock).
+    ///
+    /// See [`TaskStatus::with_agent_text`] for the message.
+    #[cfg(feature = "uuid")]
+    pub fn with_agent_text_at(
+        state: TaskState,
+        task_id: &str,
+        context_id: &str,
+        text: impl Into<String>,
+        time: SystemTime,
+    ) -> Self {
+        let message_id = uuid::Uuid::new_v4().to_string();
+        Self::agent_text(message_id, state, task_id, context_id, text.into(), Some(crate::clock::format_time(time)))
+    }
+
+    /// Create a status explaining itself in a short agent message, with the
+    /// message ID minted by an [`IdSource`].
+    ///
+    /// The message has the agent role and the task and context IDs set.
+    /// With the `std` feature the status is stamped with the current time.
+    pub fn with_agent_text_from<I: IdSource + ?Sized>(
+        ids: &mut I,
+        state: TaskState,
+        task_id: &str,
+        context_id: &str,
+        text: impl Into<String>,
+    ) -> Self {
+        Self::agent_text(ids.new_message_id().into_inner(), state, task_id, context_id, text.into(), now_timestamp())
+    }
+
+    /// Create a status explaining itself in a short agent message, with the
+    /// message ID minted by an [`IdSource`] and the status stamped with a
+    /// given time, e.g. from a [`Clock`](crate::clock::Clock).
+    ///
+    /// See [`TaskStatus::with_agent_text_from`] for the message.
+    #[cfg(feature = "std")]
+    pub fn with_agent_text_from_at<I: IdSource + ?Sized>(
+        ids: &mut I,
+        state: TaskState,
+        task_id: &str,
+        context_id: &str,
+        text: impl Into<String>,
+        time: SystemTime,
+    ) -> Self {
+        let message_id = ids.new_message_id().into_inner();
+        Self::agent_text(message_id, state, task_id, context_id, text.into(), Some(crate::clock::format_time(time)))
+    }
+
+    fn agent_text(
+        message_id: String,
+        state: TaskState,
+        task_id: &str,
+        context_id: &str,
+        text: String,
+        timestamp: Option<String>,
+    ) -> Self {
+        let mut message = Message::agent(message_id, [text]);
+        message.task_id = Some(task_id.to_string());
+        message.context_id = Some(context_id.to_string());
+
+        Self {
+            state,
+            message: Some(message),
+            timestamp,
+            extra: ExtraFields::default(),
+        }
+    }
+}
+
+impl Task {
+    /// Create a task with only the required fields set.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The task ID.
+    /// * `context_id` - The context ID.
+    ///
+    /// # Returns
+    ///
+    /// A new `Task` with the default status and no artifacts, history or metadata.
+    pub fn minimal(id: String, context_id: String) -> Self {
+        Self {
+            id,
+            kind: "task".to_string(),
+            status: TaskStatus::default(),
+            context_id,
+            artifacts: None,
+            history: None,
+            metadata: None,
+            result: None,
+            error: None,
+            created_at: None,
+            updated_at: None,
+            status_history: None,
+            extra: ExtraFields::default(),
+        }
+    }
+
+    /// Create a task for an inbound message.
+    ///
+    /// The task gets a new ID and the message's context, or a new context
+    /// if the message has none. The message is bound to both and becomes
+    /// the history, and the task is `submitted`, stamped with the current
+    /// time with the `std` feature. A task ID the message names is
+    /// replaced; messages continuing a task belong in that task instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message starting the task.
+    /// * `ids` - Mints the task ID and, if needed, the context ID.
+    pub fn from_message<I: IdSource + ?Sized>(message: Message, ids: &mut I) -> Self {
+        Self::from_message_stamped(message, ids, now_timestamp())
+    }
+
+    /// Create a task for an inbound message, stamped with a given time,
+    /// e.g. from a [`Clock`](crate::clock::Clock).
+    ///
+    /// See [`Task::from_message`] for the task.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message starting the task.
+    /// * `ids` - Mints the task ID and, if needed, the context ID.
+    /// * `time` - The time of the `submitted` status.
+    #[cfg(feature = "std")]
+    pub fn from_message_at<I: IdSource + ?Sized>(message: Message, ids: &mut I, time: SystemTime) -> Self {
+        Self::from_message_stamped(message, ids, Some(crate::clock::format_time(time)))
+    }
+
+    fn from_message_stamped<I: IdSource + ?Sized>(
+        mut message: Message,
+        ids: &mut I,
+        timestamp: Option<String>,
+    ) -> Self {
+        let id = ids.new_task_id().into_inner();
+        let context_id = match message.context_id.take() {
+            Some(context_id) => context_id,
+            None => ids.new_context_id().into_inner(),
+        };
+        message.task_id = Some(id.clone());
+        message.context_id = Some(context_id.clone());
+
+        let mut task = Self::minimal(id, context_id);
+        task.status.state = TaskState::Submitted;
+        task.status.timestamp = timestamp;
+        task.history = Some(vec![message]);
+        task
+    }
+
+    /// Create a short agent message acknowledging the task, for answering
+    /// a non-blocking `message/send` before the work is done.
+    ///
+    /// # Returns
+    ///
+    /// An agent message bound to the task and its context, with a random
+    /// UUID as its ID. Without the `uuid` feature, use
+    /// [`Task::to_ack_message_with`].
+    #[cfg(feature = "uuid")]
+    pub fn to_ack_message(&self) -> Message {
+        self.ack_message(uuid::Uuid::new_v4().to_string())
+    }
+
+    /// Create a short agent message acknowledging the task, for answering
+    /// a non-blocking `message/send` before the work is done, with the
+    /// message ID minted by an [`IdSource`].
+    pub fn to_ack_message_with<I: IdSource + ?Sized>(&self, ids: &mut I) -> Message {
+        self.ack_message(ids.new_message_id().into_inner())
+    }
+
+    fn ack_message(&self, message_id: String) -> Message {
+        let mut message = Message::agent(message_id, [format!("Received, working on it as task {}", self.id)]);
+        message.task_id = Some(self.id.clone());
+        message.context_id = Some(self.context_id.clone());
+        message
+    }
+
+    /// Replace the status, unless the new state cannot follow the current
+    /// one.
+    ///
+    /// A status in the same state, e.g. with a new progress message, is
+    /// always accepted. When the state changes, the replaced status is
+    /// appended to `status_history`, so the history stays in the order the
+    /// changes were made.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())`, or an error from
+    /// [`validate_task_state_transition`](crate::validation::validate_task_state_transition)
+    /// leaving the task unchanged.
+    pub fn set_status_checked(&mut self, status: TaskStatus) -> Result<(), String> {
+        if status.state != self.status.state {
+            crate::validation::validate_task_state_transition(&self.status.state, &status.state)?;
+            let previous = core::mem::replace(&mut self.status, status);
+            self.status_history.get_or_insert_with(Vec::new).push(previous);
+        } else {
+            self.status = status;
+        }
+        Ok(())
+    }
+
+    /// Get the status history ordered by timestamp, oldest first.
+    ///
+    /// The sort is stable, and entries whose timestamp is missing or not
+    /// valid RFC 3339 come last, in the order they were recorded.
+    #[cfg(feature = "std")]
+    pub fn status_history_sorted(&self) -> Vec<&TaskStatus> {
+        timeline(self.status_history.iter().flatten()).into_iter().map(|(status, _)| status).collect()
+    }
+
+    /// Get how long the task spent in a state.
+    ///
+    /// Each visit to the state lasts from the timestamp of its status to the
+    /// timestamp of the next one, taking the history and the current status
+    /// in timestamp order. A visit that has not ended yet is not counted.
+    ///
+    /// # Returns
+    ///
+    /// The total time of all ended visits, or `None` if the timestamps show
+    /// no ended visit to the state.
+    #[cfg(feature = "std")]
+    pub fn time_in_state(&self, state: &TaskState) -> Option<Duration> {
+        let timeline = timeline(self.status_history.iter().flatten().chain([&self.status]));
+        let mut total = None;
+        for pair in timeline.windows(2) {
+            if let [(status, Some(start)), (_, Some(end))] = pair
+                && status.state == *state
+            {
+                *total.get_or_insert(Duration::ZERO) += end.duration_since(*start).unwrap_or_default();
+            }
+        }
+        total
+    }
+
+    /// Get the state the task was in at a given time.
+    ///
+    /// # Returns
+    ///
+    /// The state of the latest status, from the history or the current one,
+    /// with a timestamp no later than `time`, or `None` if there is none.
+    #[cfg(feature = "std")]
+    pub fn state_at(&self, time: SystemTime) -> Option<&TaskState> {
+        timeline(self.status_history.iter().flatten().chain([&self.status]))
+            .into_iter()
+            .take_while(|(_, at)| at.is_some_and(|at| at <= time))
+            .last()
+            .map(|(status, _)| &status.state)
+    }
+
+    /// Get one page of the task history, oldest first.
+    ///
+    /// Cursors point at the first message of the next page by index and
+    /// message ID, so pages stay stable when messages are appended and still
+    /// resolve if older messages are trimmed from the front.
+    ///
+    /// # Arguments
+    ///
+    /// * `cursor` - The `next_cursor` of the previous page, or `None` to start.
+    /// * `limit` - The maximum number of messages on the page, at least 1.
+    ///
+    /// # Returns
+    ///
+    /// The page, or an `InvalidParams` error if the limit is zero or the
+    /// cursor is not valid for this history.
+    pub fn history_page(&self, cursor: Option<&str>, limit: usize) -> Result<Page<&Message>, A2AError> {
+        page::check_limit(limit)?;
+        let history = self.history.as_deref().unwrap_or_default();
+
+        let start = match cursor {
+            None => 0,
+            Some(cursor) => {
+                let (index, message_id) = cursor
+                    .split_once(':')
+                    .and_then(|(index, id)| Some((index.parse::<usize>().ok()?, id)))
+                    .ok_or_else(|| page::invalid_cursor(cursor))?;
+
+                if history.get(index).is_some_and(|m| m.message_id == message_id) {
+                    index
+                } else {
+                    history
+                        .iter()
+                        .position(|m| m.message_id == message_id)
+                        .ok_or_else(|| page::invalid_cursor(cursor))?
+                }
+            }
+        };
+
+        let end = start.saturating_add(limit).min(history.len());
+        let next_cursor = history.get(end).map(|next| format!("{}:{}", end, next.message_id));
+        Ok(Page::new(history[start..end].iter().collect(), next_cursor))
+    }
+
+    /// Append a message to the history.
+    ///
+    /// Agents that echo the user's message back would otherwise record it
+    /// twice, so by default a message identical to one already in the
+    /// history is skipped. A different message reusing an ID is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to append.
+    /// * `duplicates` - Whether to skip identical copies.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the message was appended.
+    pub fn add_to_history(&mut self, message: Message, duplicates: DuplicateMessages) -> bool {
+        let history = self.history.get_or_insert_with(Vec::new);
+        if duplicates == DuplicateMessages::SkipIdentical && history.iter().any(|earlier| is_copy(earlier, &message)) {
+            return false;
+        }
+        history.push(message);
+        true
+    }
+
+    /// Remove messages identical to an earlier message of the history.
+    ///
+    /// The first copy keeps its position. Messages reusing an ID with
+    /// different content are kept; [`Task::from_value_lenient`] reports
+    /// them as [`WarningKind::DuplicateMessageId`](crate::lenient::WarningKind::DuplicateMessageId).
+    ///
+    /// # Returns
+    ///
+    /// The number of messages removed.
+    pub fn dedup_history(&mut self) -> usize {
+        let Some(history) = self.history.as_mut() else {
+            return 0;
+        };
+        // Only messages sharing an ID are compared
+        let mut kept: crate::Map<&str, Vec<&Message>> = crate::Map::new();
+        let repeated: Vec<bool> = history
+            .iter()
+            .map(|message| {
+                let same_id = kept.entry(message.message_id.as_str()).or_default();
+                let copy = same_id.contains(&message);
+                if !copy {
+                    same_id.push(message);
+                }
+                copy
+            })
+            .collect();
+        let mut repeated = repeated.into_iter();
+        let before = history.len();
+        history.retain(|_| !repeated.next().unwrap_or(false));
+        before - history.len()
+    }
+
+    /// Get the text output of the task.
+    ///
+    /// # Returns
+    ///
+    /// The [`Artifact::text`] of every artifact that has text, in order and
+    /// separated by newlines, or `None` if no artifact has a text part.
+    pub fn output_text(&self) -> Option<String> {
+        let texts: Vec<String> = self.artifacts.iter().flatten().filter_map(Artifact::text).collect();
+        (!texts.is_empty()).then(|| texts.join("\n"))
+    }
+
+    /// Get the first artifact with a given name.
+    pub fn artifact_named(&self, name: &str) -> Option<&Artifact> {
+        self.artifacts.iter().flatten().find(|artifact| artifact.name.as_deref() == Some(name))
+    }
+
+    /// Iterate over the file parts of every artifact, in order.
+    pub fn output_files(&self) -> impl Iterator<Item = &FilePart> {
+        self.artifacts.iter().flatten().flat_map(|artifact| artifact.parts.iter()).filter_map(|part| match part {
+            Part::File(file) => Some(file.as_ref()),
+            _ => None,
+        })
+    }
+}
+
+impl Task {
+    /// Serialize the task with only a window of its history, without
+    /// cloning it.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The history messages to include, clamped to the history.
+    /// * `writer` - The destination for the JSON bytes.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())`, or an `Internal` error if serialization or a write fails.
+    #[cfg(feature = "std")]
+    pub fn serialize_history_window<W: std::io::Write>(
+        &self,
+        range: core::ops::Range<usize>,
+        writer: W,
+    ) -> Result<(), A2AError> {
+        let mut view = TaskView::new(self);
+        view.history_range = range;
+        crate::helpers::serialize_response_to_writer(&view, writer)
+    }
+}
+
+/// A task serialized with part of its history and optionally without its
+/// artifacts, borrowing from the task instead of cloning it.
+///
+/// Servers answering `tasks/get` with a `historyLength` serialize a view of
+/// the stored task rather than a truncated copy, which for long histories
+/// saves cloning every message kept.
+///
+/// ```
+/// use a2a_rs::{Message, Task, TaskView};
+///
+/// let mut task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+/// task.history = Some((1..=3).map(|n| Message::user(format!("msg-{}", n), ["hi"])).collect());
+///
+/// let json = serde_json::to_value(TaskView::with_history_length(&task, Some(1))).unwrap();
+/// assert_eq!(json["history"][0]["messageId"], "msg-3");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskView<'a> {
+    /// The task
+    pub task: &'a Task,
+    /// The history messages to include, clamped to the history
+    pub history_range: core::ops::Range<usize>,
+    /// Whether to include the artifacts
+    pub include_artifacts: bool,
+}
+
+impl<'a> TaskView<'a> {
+    /// View the whole task.
+    pub fn new(task: &'a Task) -> Self {
+        Self {
+            task,
+            history_range: 0..usize::MAX,
+            include_artifacts: true,
+        }
+    }
+
+    /// View the task with its latest history messages, as `historyLength`
+    /// asks.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task.
+    /// * `history_length` - The number of latest messages to include, or
+    ///   `None` for all.
+    pub fn with_history_length(task: &'a Task, history_length: Option<u32>) -> Self {
+        let len = task.history.as_ref().map_or(0, Vec::len);
+        let start = history_length.map_or(0, |length| len.saturating_sub(length as usize));
+        Self {
+            history_range: start..len,
+            ..Self::new(task)
+        }
+    }
+
+    /// Leave the artifacts out.
+    pub fn without_artifacts(mut self) -> Self {
+        self.include_artifacts = false;
+        self
+    }
+
+    /// The history messages in the view, if the task has a history.
+    pub fn history(&self) -> Option<&'a [Message]> {
+        let history = self.task.history.as_deref()?;
+        let end = self.history_range.end.min(history.len());
+        let start = self.history_range.start.min(end);
+        Some(&history[start..end])
+    }
+
+    /// Serialize the view as the JSON-RPC response to a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the request.
+    /// * `writer` - The destination for the JSON bytes, e.g. a buffered
+    ///   socket.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())`, or an `Internal` error if serialization or a write fails.
+    #[cfg(feature = "std")]
+    pub fn write_response<W: std::io::Write>(&self, id: &crate::JsonRpcId, writer: W) -> Result<(), A2AError> {
+        #[derive(Serialize)]
+        struct Response<'v, 'a> {
+            jsonrpc: &'static str,
+            id: &'v crate::JsonRpcId,
+            result: &'v TaskView<'a>,
+        }
+
+        let response = Response {
+            jsonrpc: "2.0",
+            id,
+            result: self,
+        };
+        crate::helpers::serialize_response_to_writer(&response, writer)
+    }
+}
+
+/// Writes the fields in the order, and with the omissions, of [`Task`].
+impl Serialize for TaskView<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let task = self.task;
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("id", &task.id)?;
+        map.serialize_entry("kind", &task.kind)?;
+        map.serialize_entry("status", &task.status)?;
+        map.serialize_entry("contextId", &task.context_id)?;
+        if let Some(artifacts) = task.artifacts.as_ref().filter(|_| self.include_artifacts) {
+            map.serialize_entry("artifacts", artifacts)?;
+        }
+        if let Some(history) = self.history() {
+            map.serialize_entry("history", history)?;
+        }
+        if let Some(metadata) = &task.metadata {
+            map.serialize_entry("metadata", metadata)?;
+        }
+        if let Some(result) = &task.result {
+            map.serialize_entry("result", result)?;
+        }
+        if let Some(error) = &task.error {
+            map.serialize_entry("error", error)?;
+        }
+        if let Some(created_at) = &task.created_at {
+            map.serialize_entry("createdAt", created_at)?;
+        }
+        if let Some(updated_at) = &task.updated_at {
+            map.serialize_entry("updatedAt", updated_at)?;
+        }
+        if let Some(status_history) = &task.status_history {
+            map.serialize_entry("statusHistory", status_history)?;
+        }
+        for (key, value) in task.extra.iter() {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Pair statuses with their parsed timestamps and sort them by time.
+///
+/// Statuses without a valid timestamp keep their order at the end.
+#[cfg(feature = "std")]
+fn timeline<'a>(statuses: impl Iterator<Item = &'a TaskStatus>) -> Vec<(&'a TaskStatus, Option<SystemTime>)> {
+    let mut timeline: Vec<_> = statuses
+        .map(|status| (status, status.timestamp.as_deref().and_then(crate::clock::parse_timestamp)))
+        .collect();
+    timeline.sort_by(|(_, a), (_, b)| match (a, b) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => core::cmp::Ordering::Less,
+        (None, Some(_)) => core::cmp::Ordering::Greater,
+        (None, None) => core::cmp::Ordering::Equal,
+    });
+    timeline
+}
+
+fn is_copy(earlier: &Message, message: &Message) -> bool {
+    earlier.message_id == message.message_id && earlier == message
+}
+
+/// How [`Task::add_to_history`] treats a message already in the history.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateMessages {
+    /// Skip a message identical to one in the history.
+    #[default]
+    SkipIdentical,
+    /// Append the message anyway.
+    Keep,
+}
+
+/// Represents an artifact generated for a task.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Artifact {
+    /// Unique identifier for the artifact
+    #[serde(rename = "artifactId")]
+    pub artifact_id: String,
+    /// Artifact parts
+    pub parts: Vec<Part>,
+    /// Optional description for the artifact
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The URIs of extensions that are present or contributed to this Artifact
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<Vec<String>>,
+    /// Extension metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_impls::object))]
+    pub metadata: Option<serde_json::Value>,
+    /// Optional name for the artifact
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Unrecognized fields preserved for round-tripping
+    #[serde(flatten, skip_serializing_if = "ExtraFields::is_empty")]
+    pub extra: ExtraFields,
+}
+
+impl Artifact {
+    /// Create an artifact with only the required fields set.
+    ///
+    /// # Arguments
+    ///
+    /// * `artifact_id` - The artifact ID.
+    /// * `parts` - The artifact content.
+    ///
+    /// # Returns
+    ///
+    /// A new `Artifact` with no name, description, extensions or metadata.
+    pub fn minimal(artifact_id: String, parts: Vec<Part>) -> Self {
+        Self {
+            artifact_id,
+            parts,
+            description: None,
+            extensions: None,
+            metadata: None,
+            name: None,
+            extra: ExtraFields::default(),
+        }
+    }
+
+    /// Validate the artifact: it must have parts, and the extension URIs it
+    /// lists must be valid.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if valid, `Err(String)` with error message if invalid.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.parts.is_empty() {
+            return Err("Artifact must contain at least one part".to_string());
+        }
+        self.extensions.iter().flatten().try_for_each(|uri| crate::validation::validate_extension_uri(uri))
+    }
+
+    /// Get the text of the artifact.
+    ///
+    /// # Returns
+    ///
+    /// The text parts concatenated as they are, since streamed artifacts
+    /// arrive as chunks of one text, or `None` if there are no text parts.
+    pub fn text(&self) -> Option<String> {
+        let mut texts = self.parts.iter().filter_map(|part| match part {
+            Part::Text(text) => Some(text.text.as_str()),
+            _ => None,
+        });
+        let first = texts.next()?;
+        Some(texts.fold(first.to_string(), |text, next| text + next))
+    }
+
+    /// Get the data of the first data part of the artifact.
+    pub fn json(&self) -> Option<&serde_json::Value> {
+        self.parts.iter().find_map(|part| match part {
+            Part::Data(data) => Some(&data.data),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,606 @@
+//! Matching responses to requests on multiplexed transports.
+//!
+//! Over websockets or message buses, responses to concurrent requests arrive
+//! in any order and can only be told apart by their JSON-RPC id.
+//! [`RequestTracker`] hands out those ids, remembers which method each one
+//! was for, and parses every response into the result type of that method.
+//! Like [`crate::client`], it does no I/O and reads no clock; the caller
+//! passes in the current time.
+
+use crate::ids::IdSource;
+use crate::response::{IdMatching, ResponseValidator};
+use crate::{
+    A2AError, InvalidAgentResponseError, JSONRPCErrorResponse, JsonRpcId, PushNotificationConfig,
+    PushNotificationConfigInfo, PushNotificationConfigResult, RequestMethod, SendMessageResult,
+    StreamingMessageResult, Task,
+};
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Instant;
+
+/// How many completed ids are remembered to recognize duplicate responses.
+const COMPLETED_CAPACITY: usize = 1024;
+
+/// How many ids [`RequestTracker::start_from`] draws before giving up on a
+/// source that keeps minting ids already in use.
+const MAX_ID_ATTEMPTS: usize = 16;
+
+/// The result of a successful response, typed by the request's method.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
+pub enum ResponseResult {
+    /// The result of `message/send`.
+    SendMessage(SendMessageResult),
+    /// One event of a `message/stream` or `tasks/resubscribe` stream.
+    Stream(StreamingMessageResult),
+    /// The result of `tasks/get` or `tasks/cancel`.
+    Task(Task),
+    /// The result of `tasks/pushNotificationConfig/set`.
+    PushNotificationConfigSet(PushNotificationConfigResult),
+    /// The result of `tasks/pushNotificationConfig/get`.
+    PushNotificationConfig(PushNotificationConfig),
+    /// The result of `tasks/pushNotificationConfig/list`.
+    PushNotificationConfigs(Vec<PushNotificationConfigInfo>),
+    /// The result of `tasks/pushNotificationConfig/delete`.
+    Deleted(bool),
+    /// The result of `tasks/list`.
+    #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+    Tasks(crate::ListTasksResult),
+}
+
+/// A response matched to the request it answers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrelatedResponse {
+    /// The JSON-RPC id shared by the request and the response.
+    pub id: JsonRpcId,
+    /// The method of the request.
+    pub method: RequestMethod,
+    /// The typed result, or the error the agent answered with.
+    pub result: Result<ResponseResult, A2AError>,
+    /// Whether the request is complete. Only streaming requests receive
+    /// more than one response; their last event sets this.
+    pub complete: bool,
+}
+
+/// How [`RequestTracker`] generates request ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdStyle {
+    /// Increasing integers starting at 1.
+    #[default]
+    Sequential,
+    /// Random version 4 UUID strings, unique across trackers.
+    #[cfg(feature = "uuid")]
+    Uuid,
+}
+
+#[derive(Debug, Clone)]
+struct InFlight {
+    validator: ResponseValidator,
+    deadline: Option<Instant>,
+}
+
+/// Tracks in-flight requests and correlates their responses by id.
+#[derive(Debug, Clone, Default)]
+pub struct RequestTracker {
+    id_style: IdStyle,
+    id_matching: IdMatching,
+    next_id: i64,
+    in_flight: HashMap<JsonRpcId, InFlight>,
+    completed: HashSet<JsonRpcId>,
+    completed_order: VecDeque<JsonRpcId>,
+}
+
+impl RequestTracker {
+    /// Create a tracker that numbers requests sequentially.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a tracker that generates ids in the given style.
+    pub fn with_id_style(id_style: IdStyle) -> Self {
+        Self {
+            id_style,
+            ..Self::default()
+        }
+    }
+
+    /// Also match responses whose id is a number where the request sent
+    /// the same digits as a string, or the other way round.
+    ///
+    /// Ids are compared as by [`IdMatching::Lenient`]. Ignoring ids is not
+    /// an option here, since they are what responses are matched by.
+    pub fn lenient_ids(mut self) -> Self {
+        self.id_matching = IdMatching::Lenient;
+        self
+    }
+
+    /// Start tracking a request under a fresh id.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The method of the request, which decides how responses
+    ///   are parsed.
+    /// * `deadline` - When [`RequestTracker::expire`] gives up on the
+    ///   request, or `None` to wait indefinitely. For streaming methods the
+    ///   deadline only applies until the first event arrives.
+    ///
+    /// # Returns
+    ///
+    /// The id to send the request with.
+    pub fn start(&mut self, method: RequestMethod, deadline: Option<Instant>) -> JsonRpcId {
+        let id = loop {
+            let id = self.generate_id();
+            if !self.in_flight.contains_key(&id) && !self.completed.contains(&id) {
+                break id;
+            }
+        };
+        self.track(id.clone(), method, deadline);
+        id
+    }
+
+    /// Start tracking a request under an id minted by an [`IdSource`],
+    /// instead of in the tracker's [`IdStyle`].
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - Mints the id with [`IdSource::new_request_id`]; ids that
+    ///   are already tracked are skipped.
+    /// * `method` - The method of the request.
+    /// * `deadline` - When [`RequestTracker::expire`] gives up on the
+    ///   request, or `None` to wait indefinitely.
+    ///
+    /// # Returns
+    ///
+    /// The id to send the request with, or `Err(A2AError::Internal)` if the
+    /// source minted only ids already in use in 16 attempts, e.g. because
+    /// it always returns the same id.
+    pub fn start_from<I: IdSource + ?Sized>(
+        &mut self,
+        ids: &mut I,
+        method: RequestMethod,
+        deadline: Option<Instant>,
+    ) -> Result<JsonRpcId, A2AError> {
+        for _ in 0..MAX_ID_ATTEMPTS {
+            let id = ids.new_request_id();
+            if !self.in_flight.contains_key(&id) && !self.completed.contains(&id) {
+                self.track(id.clone(), method, deadline);
+                return Ok(id);
+            }
+        }
+        Err(A2AError::Internal(Box::new(crate::InternalError {
+            code: -32603,
+            message: format!("The id source minted no unused request id in {} attempts", MAX_ID_ATTEMPTS),
+            data: None,
+        })))
+    }
+
+    /// Start tracking a request whose id the caller chose.
+    ///
+    /// # Returns
+    ///
+    /// `Err(A2AError::InvalidRequest)` if a request with that id is already
+    /// in flight.
+    pub fn start_with_id(
+        &mut self,
+        id: JsonRpcId,
+        method: RequestMethod,
+        deadline: Option<Instant>,
+    ) -> Result<(), A2AError> {
+        if self.in_flight.contains_key(&id) {
+            return Err(A2AError::InvalidRequest(Box::new(crate::InvalidRequestError {
+                code: -32600,
+                message: format!("A request with id {} is already in flight", display_id(&id)),
+                data: None,
+            })));
+        }
+        self.forget_completed(&id);
+        self.track(id, method, deadline);
+        Ok(())
+    }
+
+    /// Match a response to its request and parse its result.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - One complete JSON-RPC response.
+    ///
+    /// # Returns
+    ///
+    /// The correlated response, or `Err(A2AError::InvalidAgentResponse)` if
+    /// the response is malformed, answers no in-flight request, repeats a
+    /// response already received, or fails a [`ResponseValidator`] check,
+    /// e.g. carries a result of the wrong kind for the request's method. A
+    /// malformed result fails its request, which is no longer tracked
+    /// afterwards.
+    pub fn handle_response(&mut self, json: &[u8]) -> Result<CorrelatedResponse, A2AError> {
+        let mut envelope: serde_json::Value = serde_json::from_slice(json)
+            .map_err(|e| invalid_response(format!("Response is not valid JSON: {}", e)))?;
+        let (id, raw_id): (JsonRpcId, _) = envelope
+            .get("id")
+            .and_then(|id| Some((serde_json::from_value(id.clone()).ok()?, id)))
+            .ok_or_else(|| invalid_response("Response has no valid id".to_string()))?;
+        let id = self.correlate(id, raw_id);
+
+        let Some(entry) = self.in_flight.get_mut(&id) else {
+            let message = if self.completed.contains(&id) {
+                format!("Duplicate response for request {}", display_id(&id))
+            } else {
+                format!("Response for unknown request {}", display_id(&id))
+            };
+            return Err(invalid_response(message));
+        };
+        let method = entry.validator.method();
+        let streaming = matches!(method, RequestMethod::MessageStream | RequestMethod::TasksResubscribe);
+
+        let parsed = if envelope.get("error").is_some() {
+            serde_json::from_value::<JSONRPCErrorResponse>(envelope)
+                .map(|response| Err(response.error))
+                .map_err(|e| invalid_response(format!("Malformed error response: {}", e)))
+        } else {
+            match envelope.get_mut("result").map(serde_json::Value::take) {
+                Some(result) => entry
+                    .validator
+                    .validate(&result)
+                    .and_then(|_| parse_result(method, result))
+                    .map(Ok),
+                None => Err(invalid_response("Response has neither result nor error".to_string())),
+            }
+        };
+
+        let complete = match &parsed {
+            Ok(Ok(ResponseResult::Stream(event))) => ends_stream(event),
+            _ => true,
+        };
+        if complete {
+            self.complete(id.clone());
+        } else if streaming && let Some(entry) = self.in_flight.get_mut(&id) {
+            entry.deadline = None;
+        }
+
+        Ok(CorrelatedResponse {
+            id,
+            method,
+            result: parsed?,
+            complete,
+        })
+    }
+
+    /// Stop tracking requests whose deadline has passed.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time.
+    ///
+    /// # Returns
+    ///
+    /// The expired requests. Late responses to them are reported as
+    /// duplicates by [`RequestTracker::handle_response`].
+    pub fn expire(&mut self, now: Instant) -> Vec<(JsonRpcId, RequestMethod)> {
+        let expired: Vec<_> = self
+            .in_flight
+            .iter()
+            .filter(|(_, entry)| entry.deadline.is_some_and(|deadline| deadline <= now))
+            .map(|(id, entry)| (id.clone(), entry.validator.method()))
+            .collect();
+        for (id, _) in &expired {
+            self.complete(id.clone());
+        }
+        expired
+    }
+
+    /// Stop tracking a request, e.g. when its stream closed early.
+    ///
+    /// # Returns
+    ///
+    /// The method of the request, or `None` if it was not in flight.
+    pub fn cancel(&mut self, id: &JsonRpcId) -> Option<RequestMethod> {
+        let method = self.in_flight.get(id)?.validator.method();
+        self.complete(id.clone());
+        Some(method)
+    }
+
+    /// The method of an in-flight request.
+    pub fn method_of(&self, id: &JsonRpcId) -> Option<RequestMethod> {
+        self.in_flight.get(id).map(|entry| entry.validator.method())
+    }
+
+    /// The number of requests awaiting a response.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// The earliest deadline of any in-flight request, for scheduling the
+    /// next call to [`RequestTracker::expire`].
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.in_flight.values().filter_map(|entry| entry.deadline).min()
+    }
+
+    /// The in-flight or completed id a response id answers.
+    fn correlate(&self, id: JsonRpcId, raw_id: &serde_json::Value) -> JsonRpcId {
+        if self.id_matching == IdMatching::Strict || self.in_flight.contains_key(&id) || self.completed.contains(&id) {
+            return id;
+        }
+        self.in_flight
+            .keys()
+            .chain(&self.completed)
+            .find(|candidate| self.id_matching.matches(candidate, raw_id))
+            .cloned()
+            .unwrap_or(id)
+    }
+
+    fn generate_id(&mut self) -> JsonRpcId {
+        match self.id_style {
+            IdStyle::Sequential => {
+                self.next_id += 1;
+                JsonRpcId::Integer(self.next_id)
+            }
+            #[cfg(feature = "uuid")]
+            IdStyle::Uuid => JsonRpcId::String(uuid::Uuid::new_v4().to_string()),
+        }
+    }
+
+    fn track(&mut self, id: JsonRpcId, method: RequestMethod, deadline: Option<Instant>) {
+        self.in_flight.insert(
+            id,
+            InFlight {
+                validator: ResponseValidator::new(method),
+                deadline,
+            },
+        );
+    }
+
+    fn complete(&mut self, id: JsonRpcId) {
+        self.in_flight.remove(&id);
+        if self.completed.insert(id.clone()) {
+            self.completed_order.push_back(id);
+            if self.completed_order.len() > COMPLETED_CAPACITY
+                && let Some(oldest) = self.completed_order.pop_front()
+            {
+                self.completed.remove(&oldest);
+            }
+        }
+    }
+
+    fn forget_completed(&mut self, id: &JsonRpcId) {
+        if self.completed.remove(id) {
+            self.completed_order.retain(|completed| completed != id);
+        }
+    }
+}
+
+/// Parse a result as the type the method answers with.
+fn parse_result(method: RequestMethod, result: serde_json::Value) -> Result<ResponseResult, A2AError> {
+    fn parse<T: DeserializeOwned>(method: RequestMethod, result: serde_json::Value) -> Result<T, A2AError> {
+        serde_path_to_error::deserialize(&result).map_err(|e| {
+            let path = e.path().to_string();
+            let location = if path == "." { String::new() } else { format!(" at {}", path) };
+            invalid_response(format!("Result is not a valid {} response{}: {}", method, location, e.inner()))
+        })
+    }
+
+    Ok(match method {
+        RequestMethod::MessageSend => ResponseResult::SendMessage(parse(method, result)?),
+        RequestMethod::MessageStream | RequestMethod::TasksResubscribe => ResponseResult::Stream(parse(method, result)?),
+        RequestMethod::TasksGet | RequestMethod::TasksCancel => ResponseResult::Task(parse(method, result)?),
+        RequestMethod::TasksPushNotificationConfigSet => ResponseResult::PushNotificationConfigSet(parse(method, result)?),
+        RequestMethod::TasksPushNotificationConfigGet => ResponseResult::PushNotificationConfig(parse(method, result)?),
+        RequestMethod::TasksPushNotificationConfigList => {
+            ResponseResult::PushNotificationConfigs(parse(method, result)?)
+        }
+        RequestMethod::TasksPushNotificationConfigDelete => ResponseResult::Deleted(parse(method, result)?),
+        #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+        RequestMethod::TasksList => ResponseResult::Tasks(parse(method, result)?),
+    })
+}
+
+/// Whether a stream event is the last one of its stream.
+fn ends_stream(event: &StreamingMessageResult) -> bool {
+    match event {
+        StreamingMessageResult::Message(_) => true,
+        StreamingMessageResult::TaskStatusUpdate(event) => event.final_event,
+        StreamingMessageResult::Task(_) | StreamingMessageResult::TaskArtifactUpdate(_) => false,
+    }
+}
+
+fn display_id(id: &JsonRpcId) -> String {
+    serde_json::to_string(id).unwrap_or_default()
+}
+
+fn invalid_response(message: String) -> A2AError {
+    A2AError::InvalidAgentResponse(Box::new(InvalidAgentResponseError {
+        code: -32006,
+        message,
+        data: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn response(id: &JsonRpcId, result: serde_json::Value) -> Vec<u8> {
+        serde_json::to_vec(&json!({"jsonrpc": "2.0", "id": id, "result": result})).unwrap()
+    }
+
+    fn task(id: &str, state: &str) -> serde_json::Value {
+        json!({"kind": "task", "id": id, "contextId": "ctx-1", "status": {"state": state}})
+    }
+
+    fn status_update(state: &str, final_event: bool) -> serde_json::Value {
+        json!({
+            "kind": "status-update",
+            "taskId": "task-1",
+            "contextId": "ctx-1",
+            "status": {"state": state},
+            "final": final_event
+        })
+    }
+
+    fn message(error: A2AError) -> String {
+        match error {
+            A2AError::InvalidAgentResponse(error) => error.message,
+            other => panic!("expected an invalid agent response, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_out_of_order_completion() {
+        let mut tracker = RequestTracker::new();
+        let get = tracker.start(RequestMethod::TasksGet, None);
+        let stream = tracker.start(RequestMethod::MessageStream, None);
+        let delete = tracker.start(RequestMethod::TasksPushNotificationConfigDelete, None);
+        assert_eq!(get, JsonRpcId::Integer(1));
+        assert_eq!(tracker.in_flight(), 3);
+
+        let first = tracker.handle_response(&response(&stream, task("task-1", "submitted"))).unwrap();
+        assert!(matches!(first.result, Ok(ResponseResult::Stream(StreamingMessageResult::Task(_)))));
+        assert!(!first.complete);
+
+        let deleted = tracker.handle_response(&response(&delete, json!(true))).unwrap();
+        assert_eq!(deleted.method, RequestMethod::TasksPushNotificationConfigDelete);
+        assert_eq!(deleted.result, Ok(ResponseResult::Deleted(true)));
+
+        let error = json!({"jsonrpc": "2.0", "id": get, "error": {"code": -32001, "message": "Task not found"}});
+        let got = tracker.handle_response(error.to_string().as_bytes()).unwrap();
+        assert!(matches!(got.result, Err(A2AError::TaskNotFound(_))));
+        assert!(got.complete);
+
+        let working = tracker.handle_response(&response(&stream, status_update("working", false))).unwrap();
+        assert!(!working.complete);
+        assert_eq!(tracker.method_of(&stream), Some(RequestMethod::MessageStream));
+        let done = tracker.handle_response(&response(&stream, status_update("completed", true))).unwrap();
+        assert!(done.complete);
+        assert_eq!(tracker.in_flight(), 0);
+    }
+
+    #[test]
+    fn test_duplicate_and_unknown_responses() {
+        let mut tracker = RequestTracker::new();
+        let id = tracker.start(RequestMethod::TasksGet, None);
+        let body = response(&id, task("task-1", "working"));
+        assert!(tracker.handle_response(&body).is_ok());
+
+        let error = message(tracker.handle_response(&body).unwrap_err());
+        assert_eq!(error, "Duplicate response for request 1");
+        let error = message(tracker.handle_response(&response(&JsonRpcId::Integer(9), json!(true))).unwrap_err());
+        assert_eq!(error, "Response for unknown request 9");
+        // The string "1" is a different id from the integer 1
+        let other = JsonRpcId::String("1".to_string());
+        assert!(tracker.handle_response(&response(&other, json!(true))).is_err());
+
+        assert!(tracker.start_with_id(other.clone(), RequestMethod::TasksCancel, None).is_ok());
+        assert!(tracker.start_with_id(other, RequestMethod::TasksCancel, None).is_err());
+    }
+
+    #[test]
+    fn test_lenient_ids() {
+        let mut tracker = RequestTracker::new().lenient_ids();
+        let id = tracker.start(RequestMethod::TasksGet, None);
+        let echoed = JsonRpcId::String("1".to_string());
+        let got = tracker.handle_response(&response(&echoed, task("task-1", "working"))).unwrap();
+        assert_eq!(got.id, id);
+        let error = message(tracker.handle_response(&response(&echoed, task("task-1", "working"))).unwrap_err());
+        assert_eq!(error, "Duplicate response for request 1");
+        let other = JsonRpcId::String("01x".to_string());
+        assert!(tracker.handle_response(&response(&other, json!(true))).is_err());
+    }
+
+    #[test]
+    fn test_wrong_result_kind() {
+        let mut tracker = RequestTracker::new();
+        let id = tracker.start(RequestMethod::TasksGet, None);
+        let message_result = json!({"kind": "message", "messageId": "m-1", "role": "agent", "parts": []});
+        let error = message(tracker.handle_response(&response(&id, message_result)).unwrap_err());
+        assert_eq!(error, "tasks/get result has kind \"message\", expected \"task\"");
+        // The request failed and is not retried
+        assert_eq!(tracker.in_flight(), 0);
+
+        let id = tracker.start(RequestMethod::TasksCancel, None);
+        let error = message(tracker.handle_response(&response(&id, json!({"kind": "task"}))).unwrap_err());
+        assert!(error.starts_with("Result is not a valid tasks/cancel response"), "{}", error);
+
+        let id = tracker.start(RequestMethod::TasksPushNotificationConfigSet, None);
+        let error = message(tracker.handle_response(&response(&id, json!({"taskId": 7}))).unwrap_err());
+        assert!(error.contains(" at taskId: "), "{}", error);
+    }
+
+    #[test]
+    fn test_expire_sweeps_overdue_requests() {
+        let now = Instant::now();
+        let mut tracker = RequestTracker::new();
+        let slow = tracker.start(RequestMethod::TasksGet, Some(now + Duration::from_secs(5)));
+        let fast = tracker.start(RequestMethod::TasksCancel, Some(now + Duration::from_secs(1)));
+        let stream = tracker.start(RequestMethod::MessageStream, Some(now + Duration::from_secs(1)));
+        let forever = tracker.start(RequestMethod::TasksGet, None);
+        assert_eq!(tracker.next_deadline(), Some(now + Duration::from_secs(1)));
+
+        // Once events flow, a stream no longer expires
+        tracker.handle_response(&response(&stream, task("task-1", "working"))).unwrap();
+
+        assert!(tracker.expire(now).is_empty());
+        assert_eq!(tracker.expire(now + Duration::from_secs(2)), [(fast.clone(), RequestMethod::TasksCancel)]);
+        assert_eq!(tracker.expire(now + Duration::from_secs(60)), [(slow, RequestMethod::TasksGet)]);
+        assert_eq!(tracker.method_of(&forever), Some(RequestMethod::TasksGet));
+        assert_eq!(tracker.cancel(&stream), Some(RequestMethod::MessageStream));
+
+        let late = tracker.handle_response(&response(&fast, task("task-1", "canceled"))).unwrap_err();
+        assert!(message(late).starts_with("Duplicate response"));
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_uuid_ids() {
+        let mut tracker = RequestTracker::with_id_style(IdStyle::Uuid);
+        let first = tracker.start(RequestMethod::TasksGet, None);
+        let second = tracker.start(RequestMethod::TasksGet, None);
+        assert!(matches!(&first, JsonRpcId::String(id) if id.len() == 36));
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_ids_from_source() {
+        let mut ids = crate::ids::SequentialSource::new();
+        let mut tracker = RequestTracker::new();
+        assert_eq!(tracker.start_from(&mut ids, RequestMethod::TasksGet, None).unwrap(), JsonRpcId::Integer(1));
+        tracker.start_with_id(JsonRpcId::Integer(2), RequestMethod::TasksGet, None).unwrap();
+
+        // Ids already in flight are skipped
+        assert_eq!(tracker.start_from(&mut ids, RequestMethod::TasksGet, None).unwrap(), JsonRpcId::Integer(3));
+        assert_eq!(tracker.in_flight(), 3);
+    }
+
+    /// Mints the same ids over and over.
+    struct ConstantSource;
+
+    impl IdSource for ConstantSource {
+        fn new_task_id(&mut self) -> crate::ids::TaskId {
+            "task".into()
+        }
+
+        fn new_context_id(&mut self) -> crate::ids::ContextId {
+            "ctx".into()
+        }
+
+        fn new_message_id(&mut self) -> crate::ids::MessageId {
+            "msg".into()
+        }
+
+        fn new_config_id(&mut self) -> crate::ids::ConfigId {
+            "config".into()
+        }
+    }
+
+    #[test]
+    fn test_source_minting_ids_in_use_gives_up() {
+        let mut tracker = RequestTracker::new();
+        let id = tracker.start_from(&mut ConstantSource, RequestMethod::TasksGet, None).unwrap();
+        assert_eq!(id, JsonRpcId::String("msg".to_string()));
+
+        let error = tracker.start_from(&mut ConstantSource, RequestMethod::TasksGet, None).unwrap_err();
+        assert!(matches!(error, A2AError::Internal(_)));
+        assert_eq!(tracker.in_flight(), 1);
+    }
+}
@@ -0,0 +1,399 @@
+//! Validation utilities for A2A protocol fields.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+
+/// Validate URL format.
+///
+/// # Arguments
+///
+/// * `url` - The URL string to validate.
+///
+/// # Returns
+///
+/// `Ok(())` if the URL is valid, `Err(String)` with error message if invalid.
+pub fn validate_url(url: &str) -> Result<(), String> {
+    if url.is_empty() {
+        return Err("URL cannot be empty".to_string());
+    }
+
+    // Basic URL validation
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err("URL must start with http:// or https://".to_string());
+    }
+
+    // Check minimum length
+    if url.len() < 10 {
+        return Err("URL appears to be too short".to_string());
+    }
+
+    // Check for valid domain structure
+    let without_protocol = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+
+    if without_protocol.is_empty() {
+        return Err("URL must contain a domain".to_string());
+    }
+
+    // Check for invalid characters
+    if url.contains(' ') {
+        return Err("URL cannot contain spaces".to_string());
+    }
+
+    Ok(())
+}
+
+/// Validate an extension URI.
+///
+/// Extension URIs identify extensions rather than locate them, so no
+/// minimum length applies: the URI must be an HTTP(S) URI with a host and
+/// no whitespace, such as `https://a.io/x`.
+///
+/// # Arguments
+///
+/// * `uri` - The extension URI to validate.
+///
+/// # Returns
+///
+/// `Ok(())` if the URI is valid, `Err(String)` with error message if invalid.
+pub fn validate_extension_uri(uri: &str) -> Result<(), String> {
+    if uri.is_empty() {
+        return Err("Extension URI cannot be empty".to_string());
+    }
+
+    let Some(rest) = uri.strip_prefix("https://").or_else(|| uri.strip_prefix("http://")) else {
+        return Err(format!("Extension URI '{}' must be a valid HTTP or HTTPS URL", uri));
+    };
+
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    if host.is_empty() || host.starts_with(':') {
+        return Err(format!("Extension URI '{}' must contain a host", uri));
+    }
+
+    if uri.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(format!("Extension URI '{}' cannot contain whitespace", uri));
+    }
+
+    Ok(())
+}
+
+/// Image file extensions accepted by [`validate_icon_url`].
+const ICON_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "svg", "webp", "ico", "avif", "bmp"];
+
+/// Validate an icon URL.
+///
+/// The URL must be a valid HTTP(S) URL whose path ends in an image file
+/// extension, or a `data:image/...` URI.
+///
+/// # Arguments
+///
+/// * `url` - The icon URL to validate.
+///
+/// # Returns
+///
+/// `Ok(())` if the URL is valid, `Err(String)` with error message if invalid.
+pub fn validate_icon_url(url: &str) -> Result<(), String> {
+    if let Some(data) = url.strip_prefix("data:") {
+        if !data.starts_with("image/") || !data.contains(',') {
+            return Err("Icon data URI must have an image media type and data".to_string());
+        }
+        return Ok(());
+    }
+
+    validate_url(url)?;
+
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let extension = path.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("");
+    if !ICON_EXTENSIONS.iter().any(|e| e.eq_ignore_ascii_case(extension)) {
+        return Err(format!("Icon URL must point to an image file ({})", ICON_EXTENSIONS.join(", ")));
+    }
+
+    Ok(())
+}
+
+/// Validate media type format.
+///
+/// # Arguments
+///
+/// * `media_type` - The media type string to validate.
+///
+/// # Returns
+///
+/// `Ok(())` if the media type is valid, `Err(String)` with error message if invalid.
+pub fn validate_media_type(media_type: &str) -> Result<(), String> {
+    if media_type.is_empty() {
+        return Err("Media type cannot be empty".to_string());
+    }
+
+    // Basic media type validation (type/subtype)
+    let parts: Vec<&str> = media_type.split('/').collect();
+    if parts.len() != 2 {
+        return Err("Media type must be in format 'type/subtype'".to_string());
+    }
+
+    let (main_type, sub_type) = (parts[0], parts[1]);
+
+    if main_type.is_empty() || sub_type.is_empty() {
+        return Err("Media type parts cannot be empty".to_string());
+    }
+
+    // Validate common media types
+    let valid_main_types = ["text", "image", "audio", "video", "application", "multipart", "message"];
+
+    if !valid_main_types.contains(&main_type) && !main_type.starts_with("x-") {
+        return Err(format!("Unknown media type: {}", main_type));
+    }
+
+    Ok(())
+}
+
+/// Check a file's media type is of the given top-level type, e.g. `image`.
+///
+/// The letter case of the type is ignored.
+pub(crate) fn check_media_kind(media_type: Option<&str>, kind: &str) -> Result<(), String> {
+    match media_type {
+        Some(media_type)
+            if media_type
+                .split_once('/')
+                .is_some_and(|(main_type, sub_type)| main_type.eq_ignore_ascii_case(kind) && !sub_type.is_empty()) =>
+        {
+            Ok(())
+        }
+        Some(media_type) => Err(format!("Media type '{}' is not an {} type", media_type, kind)),
+        None => Err(format!("File has no media type, expected an {} type", kind)),
+    }
+}
+
+/// Validate task state transitions.
+///
+/// # Arguments
+///
+/// * `from_state` - The current task state.
+/// * `to_state` - The target task state.
+///
+/// # Returns
+///
+/// `Ok(())` if the transition is valid, `Err(String)` with error message if invalid.
+pub fn validate_task_state_transition(from_state: &crate::TaskState, to_state: &crate::TaskState) -> Result<(), String> {
+    if !from_state.successors().contains(to_state) {
+        return Err(format!("Invalid task state transition from {:?} to {:?}", from_state, to_state));
+    }
+
+    Ok(())
+}
+
+/// Validate message ID format.
+///
+/// # Arguments
+///
+/// * `message_id` - The message ID to validate.
+///
+/// # Returns
+///
+/// `Ok(())` if the message ID is valid, `Err(String)` with error message if invalid.
+pub fn validate_message_id(message_id: &str) -> Result<(), String> {
+    if message_id.is_empty() {
+        return Err("Message ID cannot be empty".to_string());
+    }
+
+    if message_id.len() > 255 {
+        return Err("Message ID is too long (max 255 characters)".to_string());
+    }
+
+    // Check for valid characters (alphanumeric, hyphens, underscores)
+    if !message_id.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        return Err("Message ID can only contain alphanumeric characters, hyphens, and underscores".to_string());
+    }
+
+    Ok(())
+}
+
+/// Validate task ID format.
+///
+/// # Arguments
+///
+/// * `task_id` - The task ID to validate.
+///
+/// # Returns
+///
+/// `Ok(())` if the task ID is valid, `Err(String)` with error message if invalid.
+pub fn validate_task_id(task_id: &str) -> Result<(), String> {
+    if task_id.is_empty() {
+        return Err("Task ID cannot be empty".to_string());
+    }
+
+    if task_id.len() > 255 {
+        return Err("Task ID is too long (max 255 characters)".to_string());
+    }
+
+    // Check for valid characters (alphanumeric, hyphens, underscores)
+    if !task_id.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        return Err("Task ID can only contain alphanumeric characters, hyphens, and underscores".to_string());
+    }
+
+    Ok(())
+}
+
+/// Validate agent name format.
+///
+/// # Arguments
+///
+/// * `name` - The agent name to validate.
+///
+/// # Returns
+///
+/// `Ok(())` if the name is valid, `Err(String)` with error message if invalid.
+pub fn validate_agent_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Agent name cannot be empty".to_string());
+    }
+
+    if name.len() > 100 {
+        return Err("Agent name is too long (max 100 characters)".to_string());
+    }
+
+    // Check for reasonable characters
+    if name.trim() != name {
+        return Err("Agent name cannot start or end with whitespace".to_string());
+    }
+
+    Ok(())
+}
+
+/// Validate the syntax of a BCP-47 language tag, such as `de` or `de-AT`.
+///
+/// Only the form is checked: a primary language subtag of 2 to 8 letters,
+/// or `x` for a private use tag, followed by subtags of 1 to 8 letters or
+/// digits separated by hyphens. Whether the subtags are registered is not.
+///
+/// # Arguments
+///
+/// * `tag` - The language tag to validate.
+///
+/// # Returns
+///
+/// `Ok(())` if the tag is well-formed, `Err(String)` with error message if invalid.
+pub fn validate_language_tag(tag: &str) -> Result<(), String> {
+    let mut subtags = tag.split('-');
+    let primary = subtags.next().unwrap_or_default();
+    let private_use = primary.eq_ignore_ascii_case("x");
+    if !private_use && !(2..=8).contains(&primary.len()) || !primary.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(format!("Language tag '{}' must start with a language subtag of 2-8 letters", tag));
+    }
+
+    let mut count = 0;
+    for subtag in subtags {
+        if subtag.is_empty() || subtag.len() > 8 || !subtag.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(format!("Language tag '{}' has an invalid subtag '{}'", tag, subtag));
+        }
+        count += 1;
+    }
+    if private_use && count == 0 {
+        return Err(format!("Private use language tag '{}' has no subtags", tag));
+    }
+
+    Ok(())
+}
+
+/// Validate version string format.
+///
+/// # Arguments
+///
+/// * `version` - The version string to validate.
+///
+/// # Returns
+///
+/// `Ok(())` if the version is valid, `Err(String)` with error message if invalid.
+pub fn validate_version(version: &str) -> Result<(), String> {
+    if version.is_empty() {
+        return Err("Version cannot be empty".to_string());
+    }
+
+    if version.len() > 50 {
+        return Err("Version is too long (max 50 characters)".to_string());
+    }
+
+    // Basic semantic version validation (flexible)
+    let parts: Vec<&str> = version.split('.').collect();
+    if parts.is_empty() || parts.len() > 4 {
+        return Err("Version should have 1-4 dot-separated parts".to_string());
+    }
+
+    for part in parts {
+        if part.is_empty() {
+            return Err("Version parts cannot be empty".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate skill ID format.
+///
+/// # Arguments
+///
+/// * `skill_id` - The skill ID to validate.
+///
+/// # Returns
+///
+/// `Ok(())` if the skill ID is valid, `Err(String)` with error message if invalid.
+pub fn validate_skill_id(skill_id: &str) -> Result<(), String> {
+    if skill_id.is_empty() {
+        return Err("Skill ID cannot be empty".to_string());
+    }
+
+    if skill_id.len() > 100 {
+        return Err("Skill ID is too long (max 100 characters)".to_string());
+    }
+
+    // Check for valid characters (alphanumeric, hyphens, underscores, dots)
+    if !skill_id.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.') {
+        return Err("Skill ID can only contain alphanumeric characters, hyphens, underscores, and dots".to_string());
+    }
+
+    Ok(())
+}
+
+/// A validation failure with the field it concerns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The offending field, if the failure concerns a single field.
+    pub field: Option<String>,
+    /// A human-readable description of the failure.
+    pub message: String,
+}
+
+impl ValidationError {
+    /// Create a validation error that is not tied to a field.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            field: None,
+            message: message.into(),
+        }
+    }
+
+    /// Create a validation error for a specific field.
+    pub fn for_field(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: Some(field.into()),
+            message: message.into(),
+        }
+    }
+}
+
+impl core::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl core::error::Error for ValidationError {}
+
+impl From<ValidationError> for String {
+    fn from(error: ValidationError) -> Self {
+        error.message
+    }
+}
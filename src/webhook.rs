@@ -0,0 +1,327 @@
+//! Shared-secret signatures for push notification payloads.
+//!
+//! The agent signs each push notification body with HMAC-SHA256 under a
+//! secret it shares with the receiver, and sends the signature in the
+//! [`SIGNATURE_HEADER`] header as `sha256=<hex>`. The receiver recomputes it
+//! with [`verify_signature`] or a [`WebhookVerifier`].
+//!
+//! To rotate a secret without dropping notifications, the sender can sign
+//! with both the old and the new secret and send both signatures, separated
+//! by commas, while the receiver accepts either secret.
+//!
+//! ```
+//! use a2a_rs::webhook::{sign_payload, verify_signature};
+//!
+//! let body = br#"{"kind":"task","id":"task-1"}"#;
+//! let signature = sign_payload(b"shared-secret", body);
+//! assert!(signature.starts_with("sha256="));
+//! assert!(verify_signature(b"shared-secret", body, &signature).is_ok());
+//! assert!(verify_signature(b"other-secret", body, &signature).is_err());
+//! ```
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::push::PushNotificationPayload;
+use core::fmt;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// The header carrying the payload signature.
+pub const SIGNATURE_HEADER: &str = "X-A2A-Signature";
+
+/// The algorithm prefix of a signature.
+pub const SIGNATURE_PREFIX: &str = "sha256=";
+
+/// Why a signature was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookError {
+    /// The header contains no signature.
+    MissingSignature,
+    /// The header only contains signatures made with other algorithms.
+    UnsupportedAlgorithm(String),
+    /// No signature is valid hex of the right length; holds the first
+    /// malformed one.
+    MalformedSignature(String),
+    /// No signature matches the payload under any of the secrets.
+    Mismatch,
+    /// The signature matches but the body is not a push notification.
+    InvalidPayload(String),
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebhookError::MissingSignature => f.write_str("Webhook signature is missing"),
+            WebhookError::UnsupportedAlgorithm(algorithm) => {
+                write!(f, "Webhook signature algorithm '{}' is not supported", algorithm)
+            }
+            WebhookError::MalformedSignature(signature) => write!(f, "Webhook signature '{}' is malformed", signature),
+            WebhookError::Mismatch => f.write_str("Webhook signature does not match the payload"),
+            WebhookError::InvalidPayload(message) => write!(f, "Webhook payload is invalid: {}", message),
+        }
+    }
+}
+
+impl core::error::Error for WebhookError {}
+
+fn mac(secret: &[u8], body: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    mac
+}
+
+/// Sign a payload.
+///
+/// # Arguments
+///
+/// * `secret` - The secret shared with the receiver.
+/// * `body` - The exact bytes of the request body.
+///
+/// # Returns
+///
+/// The header value, `sha256=` followed by the lowercase hex HMAC-SHA256.
+pub fn sign_payload(secret: &[u8], body: &[u8]) -> String {
+    let digest = mac(secret, body).finalize().into_bytes();
+    let mut signature = String::with_capacity(SIGNATURE_PREFIX.len() + digest.len() * 2);
+    signature.push_str(SIGNATURE_PREFIX);
+    for byte in digest {
+        signature.push(char::from(HEX_DIGITS[usize::from(byte >> 4)]));
+        signature.push(char::from(HEX_DIGITS[usize::from(byte & 0xf)]));
+    }
+    signature
+}
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Verify a payload signature.
+///
+/// The header may hold several signatures separated by commas or
+/// whitespace; the payload is accepted if any of them matches. The
+/// `sha256=` prefix is optional and case-insensitive. Signatures with
+/// other algorithm prefixes and signatures that are not valid hex are
+/// skipped, so one garbled entry does not reject a header that also holds
+/// a good one. Comparison is constant-time.
+///
+/// # Arguments
+///
+/// * `secret` - The secret shared with the sender.
+/// * `body` - The exact bytes of the request body.
+/// * `header_value` - The value of the [`SIGNATURE_HEADER`] header.
+///
+/// # Returns
+///
+/// `Ok(())` if a signature matches, otherwise why none did.
+pub fn verify_signature(secret: &[u8], body: &[u8], header_value: &str) -> Result<(), WebhookError> {
+    verify_any(&[secret], body, header_value)
+}
+
+fn verify_any(secrets: &[&[u8]], body: &[u8], header_value: &str) -> Result<(), WebhookError> {
+    let signatures = parse_signatures(header_value)?;
+    let matched = secrets.iter().any(|secret| {
+        let mac = mac(secret, body);
+        signatures.iter().any(|signature| mac.clone().verify_slice(signature).is_ok())
+    });
+    if matched { Ok(()) } else { Err(WebhookError::Mismatch) }
+}
+
+/// Decode the SHA-256 signatures of a header value, skipping the ones that
+/// do not decode.
+fn parse_signatures(header_value: &str) -> Result<Vec<Vec<u8>>, WebhookError> {
+    let mut signatures = Vec::new();
+    let mut other_algorithm = None;
+    let mut malformed = None;
+    for item in header_value.split(|c: char| c == ',' || c.is_whitespace()).filter(|item| !item.is_empty()) {
+        let hex = match item.split_once('=') {
+            Some((algorithm, hex)) if algorithm.eq_ignore_ascii_case("sha256") => hex,
+            Some((algorithm, _)) => {
+                other_algorithm.get_or_insert_with(|| algorithm.to_string());
+                continue;
+            }
+            None => item,
+        };
+        match decode_hex(hex) {
+            Some(signature) => signatures.push(signature),
+            None => {
+                malformed.get_or_insert_with(|| item.to_string());
+            }
+        }
+    }
+    match (signatures.is_empty(), malformed, other_algorithm) {
+        (false, _, _) => Ok(signatures),
+        (true, Some(item), _) => Err(WebhookError::MalformedSignature(item)),
+        (true, None, Some(algorithm)) => Err(WebhookError::UnsupportedAlgorithm(algorithm)),
+        (true, None, None) => Err(WebhookError::MissingSignature),
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    // A SHA-256 digest is 32 bytes
+    if hex.len() != 64 {
+        return None;
+    }
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let high = char::from(pair[0]).to_digit(16)?;
+            let low = char::from(pair[1]).to_digit(16)?;
+            Some((high * 16 + low) as u8)
+        })
+        .collect()
+}
+
+/// Verifies payloads against one or more secrets.
+///
+/// Holding the previous secret next to the current one keeps notifications
+/// signed by a sender that has not rotated yet verifying.
+#[derive(Clone)]
+pub struct WebhookVerifier {
+    secrets: Vec<Vec<u8>>,
+}
+
+impl WebhookVerifier {
+    /// Create a verifier for a secret.
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            secrets: vec![secret.into()],
+        }
+    }
+
+    /// Also accept signatures made with another secret.
+    pub fn with_secret(mut self, secret: impl Into<Vec<u8>>) -> Self {
+        self.secrets.push(secret.into());
+        self
+    }
+
+    /// Verify a payload signature under any of the secrets.
+    ///
+    /// See [`verify_signature`] for the accepted header formats.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The exact bytes of the request body.
+    /// * `header_value` - The value of the [`SIGNATURE_HEADER`] header.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if a signature matches, otherwise why none did.
+    pub fn verify(&self, body: &[u8], header_value: &str) -> Result<(), WebhookError> {
+        let secrets: Vec<&[u8]> = self.secrets.iter().map(Vec::as_slice).collect();
+        verify_any(&secrets, body, header_value)
+    }
+
+    /// Verify a payload signature, then parse the notification.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The exact bytes of the request body.
+    /// * `header_value` - The value of the [`SIGNATURE_HEADER`] header.
+    ///
+    /// # Returns
+    ///
+    /// The task or status update the notification carries, or why the
+    /// signature or the body was rejected.
+    pub fn verify_payload(&self, body: &[u8], header_value: &str) -> Result<PushNotificationPayload, WebhookError> {
+        self.verify(body, header_value)?;
+        PushNotificationPayload::from_json(body).map_err(|e| WebhookError::InvalidPayload(e.to_string()))
+    }
+}
+
+impl fmt::Debug for WebhookVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookVerifier").field("secrets", &self.secrets.len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_vectors() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            sign_payload(b"Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            sign_payload(b"It's a Secret to Everybody", b"Hello, World!"),
+            "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17"
+        );
+    }
+
+    #[test]
+    fn test_header_parsing() {
+        let body = b"Hello, World!";
+        let secret = b"It's a Secret to Everybody";
+        let hex = "757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+        for header in [
+            format!("sha256={}", hex),
+            format!("SHA256={}", hex.to_uppercase()),
+            hex.to_string(),
+            format!(" sha1=0123, sha256={} ", hex),
+        ] {
+            assert_eq!(verify_signature(secret, body, &header), Ok(()), "{}", header);
+        }
+
+        assert_eq!(verify_signature(secret, body, " , "), Err(WebhookError::MissingSignature));
+        assert_eq!(
+            verify_signature(secret, body, "sha1=0123"),
+            Err(WebhookError::UnsupportedAlgorithm("sha1".to_string()))
+        );
+        assert_eq!(
+            verify_signature(secret, body, "sha256=abc"),
+            Err(WebhookError::MalformedSignature("sha256=abc".to_string()))
+        );
+        let not_hex = format!("sha256={}", "zz".repeat(32));
+        assert!(matches!(verify_signature(secret, body, &not_hex), Err(WebhookError::MalformedSignature(_))));
+
+        // A malformed entry does not reject the good one next to it
+        assert_eq!(verify_signature(secret, body, &format!("sha256=abc, sha256={}", hex)), Ok(()));
+        assert_eq!(verify_signature(secret, body, &format!("{} sha256={}", not_hex, hex)), Ok(()));
+        let wrong = "0".repeat(64);
+        assert_eq!(verify_signature(secret, body, &format!("sha256=abc, {}", wrong)), Err(WebhookError::Mismatch));
+    }
+
+    #[test]
+    fn test_tampered_bodies() {
+        let body = br#"{"kind":"status-update","taskId":"task-1","final":true}"#;
+        let signature = sign_payload(b"secret", body);
+        assert_eq!(verify_signature(b"secret", body, &signature), Ok(()));
+
+        let tampered = br#"{"kind":"status-update","taskId":"task-2","final":true}"#;
+        assert_eq!(verify_signature(b"secret", tampered, &signature), Err(WebhookError::Mismatch));
+        assert_eq!(verify_signature(b"secret", &body[..body.len() - 1], &signature), Err(WebhookError::Mismatch));
+        assert_eq!(verify_signature(b"secreT", body, &signature), Err(WebhookError::Mismatch));
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        let body = b"{\"kind\":\"task\"}";
+        let verifier = WebhookVerifier::new("new-secret").with_secret("old-secret");
+
+        // A sender that has not rotated yet, one that has, and one sending both
+        assert_eq!(verifier.verify(body, &sign_payload(b"old-secret", body)), Ok(()));
+        assert_eq!(verifier.verify(body, &sign_payload(b"new-secret", body)), Ok(()));
+        let both = format!("{}, {}", sign_payload(b"new-secret", body), sign_payload(b"old-secret", body));
+        assert_eq!(verifier.verify(body, &both), Ok(()));
+        assert_eq!(verify_signature(b"old-secret", body, &both), Ok(()));
+
+        assert_eq!(verifier.verify(body, &sign_payload(b"retired-secret", body)), Err(WebhookError::Mismatch));
+        assert_eq!(format!("{:?}", verifier), "WebhookVerifier { secrets: 2 }");
+    }
+
+    #[test]
+    fn test_verify_payload() {
+        let verifier = WebhookVerifier::new("secret");
+        let body = br#"{"kind":"status-update","taskId":"task-1","contextId":"ctx-1","status":{"state":"working"},"final":false}"#;
+        let payload = verifier.verify_payload(body, &sign_payload(b"secret", body)).unwrap();
+        assert!(matches!(payload, PushNotificationPayload::StatusUpdate(ref event) if !event.final_event));
+
+        assert_eq!(verifier.verify_payload(body, &sign_payload(b"other", body)), Err(WebhookError::Mismatch));
+        let body = br#"{"kind":"message"}"#;
+        assert!(matches!(
+            verifier.verify_payload(body, &sign_payload(b"secret", body)),
+            Err(WebhookError::InvalidPayload(_))
+        ));
+    }
+}
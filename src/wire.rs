@@ -0,0 +1,205 @@
+//! Wire format stability.
+//!
+//! The JSON produced for every public protocol type is pinned by golden
+//! files in `tests/snapshots/`, which `tests/wire_snapshots.rs` compares
+//! against one canonical instance of each type. Any change to serialization
+//! fails that suite until the golden files are regenerated with
+//!
+//! ```text
+//! A2A_UPDATE_SNAPSHOTS=1 cargo test --test wire_snapshots
+//! ```
+//!
+//! so every wire change shows up in review as a golden file diff. Changes
+//! that alter the JSON of an existing payload must also bump
+//! [`WIRE_FORMAT_VERSION`]; purely additive changes, such as a new optional
+//! field or a new type, need not.
+//!
+//! Peers on other versions of the A2A specification expect other JSON; see
+//! [`to_json_for_version`].
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::{A2ARequest, AgentCard, Message, SendMessageParams, SendMessageResult, StreamingMessageResult, Task};
+use serde::Serialize;
+use serde_json::Value;
+
+/// The version of the JSON wire format, bumped on every incompatible change
+/// to the golden files.
+pub const WIRE_FORMAT_VERSION: u32 = 1;
+
+/// Get the version of the JSON wire format produced by this crate.
+///
+/// # Returns
+///
+/// [`WIRE_FORMAT_VERSION`].
+pub const fn wire_format_version() -> u32 {
+    WIRE_FORMAT_VERSION
+}
+
+/// A version of the A2A specification whose JSON this crate can produce.
+///
+/// The types follow the default [`PROTOCOL_VERSION`](crate::PROTOCOL_VERSION);
+/// with the `spec-0-3` feature they also carry the fields and methods added
+/// by 0.3. [`to_json_for_version`] leaves out what a peer on an older
+/// version does not know.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProtocolVersion {
+    /// A2A 0.2.5.
+    V0_2_5,
+    /// A2A 0.3.0.
+    V0_3_0,
+}
+
+impl ProtocolVersion {
+    /// Get the version as it appears in `protocolVersion`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProtocolVersion::V0_2_5 => "0.2.5",
+            ProtocolVersion::V0_3_0 => "0.3.0",
+        }
+    }
+
+    /// Read a `protocolVersion` value.
+    ///
+    /// # Returns
+    ///
+    /// The version with the same major and minor version, e.g. 0.3.0 for
+    /// `0.3.1`, or `None` if the crate knows no such version.
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut numbers = version.trim().split('.');
+        match (numbers.next(), numbers.next()) {
+            (Some("0"), Some("2")) => Some(ProtocolVersion::V0_2_5),
+            (Some("0"), Some("3")) => Some(ProtocolVersion::V0_3_0),
+            _ => None,
+        }
+    }
+}
+
+impl core::fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A type whose JSON depends on the protocol version.
+pub trait VersionedJson: Serialize {
+    /// Adapt the JSON of a value, as serialized by this crate, to a
+    /// protocol version.
+    ///
+    /// # Returns
+    ///
+    /// An error message if the value has no form in that version.
+    fn adapt_json(json: &mut Value, version: ProtocolVersion) -> Result<(), String>;
+}
+
+/// Serialize a value as a peer on a given protocol version expects it.
+///
+/// # Arguments
+///
+/// * `value` - The value.
+/// * `version` - The peer's protocol version, e.g. from its agent card.
+///
+/// # Returns
+///
+/// The JSON, without the fields the version does not define, or an error if
+/// the value cannot be expressed in that version, such as a `tasks/list`
+/// request for a 0.2.5 peer.
+pub fn to_json_for_version<T: VersionedJson + ?Sized>(
+    value: &T,
+    version: ProtocolVersion,
+) -> Result<Value, serde_json::Error> {
+    let mut json = serde_json::to_value(value)?;
+    T::adapt_json(&mut json, version).map_err(<serde_json::Error as serde::ser::Error>::custom)?;
+    Ok(json)
+}
+
+/// Types whose JSON is the same in every supported version.
+macro_rules! unversioned_json {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl VersionedJson for $ty {
+                fn adapt_json(_json: &mut Value, _version: ProtocolVersion) -> Result<(), String> {
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+unversioned_json!(Message, Task, SendMessageParams, SendMessageResult, StreamingMessageResult);
+
+impl VersionedJson for AgentCard {
+    fn adapt_json(json: &mut Value, version: ProtocolVersion) -> Result<(), String> {
+        if version < ProtocolVersion::V0_3_0
+            && let Some(card) = json.as_object_mut()
+        {
+            card.remove("signatures");
+        }
+        Ok(())
+    }
+}
+
+impl VersionedJson for A2ARequest {
+    fn adapt_json(json: &mut Value, version: ProtocolVersion) -> Result<(), String> {
+        match json.get("method").and_then(Value::as_str) {
+            Some(method @ "tasks/list") if version < ProtocolVersion::V0_3_0 => {
+                Err(format!("{} is not part of A2A {}", method, version))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn card_json() -> Value {
+        json!({
+            "name": "Agent",
+            "description": "An agent",
+            "version": "1.0.0",
+            "protocolVersion": "0.3.0",
+            "url": "https://agent.example.com/a2a",
+            "capabilities": {},
+            "defaultInputModes": ["text/plain"],
+            "defaultOutputModes": ["text/plain"],
+            "skills": [],
+            "signatures": [{"protected": "abc", "signature": "def"}]
+        })
+    }
+
+    #[test]
+    fn test_protocol_versions() {
+        assert_eq!(ProtocolVersion::parse(crate::PROTOCOL_VERSION), Some(ProtocolVersion::V0_2_5));
+        assert_eq!(ProtocolVersion::parse("0.3.1"), Some(ProtocolVersion::V0_3_0));
+        assert_eq!(ProtocolVersion::parse("1.0"), None);
+        assert_eq!(ProtocolVersion::V0_3_0.to_string(), "0.3.0");
+    }
+
+    #[test]
+    fn test_card_for_each_version() {
+        let card: AgentCard = serde_json::from_value(card_json()).unwrap();
+        assert_eq!(to_json_for_version(&card, ProtocolVersion::V0_3_0).unwrap(), card_json());
+
+        let old = to_json_for_version(&card, ProtocolVersion::V0_2_5).unwrap();
+        assert!(old.get("signatures").is_none());
+        assert_eq!(old["name"], "Agent");
+
+        let task = Task::minimal("t".to_string(), "c".to_string());
+        assert_eq!(to_json_for_version(&task, ProtocolVersion::V0_2_5).unwrap(), serde_json::to_value(&task).unwrap());
+    }
+
+    #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+    #[test]
+    fn test_list_tasks_needs_0_3() {
+        let request: A2ARequest =
+            serde_json::from_value(json!({"jsonrpc": "2.0", "id": "1", "method": "tasks/list"})).unwrap();
+        assert!(matches!(&request, A2ARequest::ListTasks(list) if list.params == crate::ListTasksParams::default()));
+        assert_eq!(to_json_for_version(&request, ProtocolVersion::V0_3_0).unwrap()["params"], json!({}));
+
+        let error = to_json_for_version(&request, ProtocolVersion::V0_2_5).unwrap_err();
+        assert_eq!(error.to_string(), "tasks/list is not part of A2A 0.2.5");
+    }
+}
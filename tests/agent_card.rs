@@ -0,0 +1,323 @@
+//! Agent card construction, validation and queries.
+
+use a2a_rs::*;
+
+#[test]
+fn test_create_agent_card() {
+    let card = AgentCard::new(
+        "Test Agent".to_string(),
+        "A test agent".to_string(),
+        "1.0.0".to_string(),
+        "https://example.com/agent".to_string(),
+        AgentCapabilities {
+            extensions: None,
+            push_notifications: Some(false),
+            state_transition_history: Some(true),
+            streaming: Some(false),
+        },
+        vec!["text/plain".to_string()],
+        vec!["text/plain".to_string()],
+        vec![AgentSkill::minimal("test".to_string(), "A test skill".to_string())],
+    );
+
+    assert_eq!(card.name, "Test Agent");
+    assert_eq!(card.description, "A test agent");
+    assert_eq!(card.version, "1.0.0");
+    assert_eq!(card.protocol_version, PROTOCOL_VERSION);
+    assert_eq!(card.url, "https://example.com/agent");
+    assert_eq!(card.capabilities.push_notifications, Some(false));
+    assert_eq!(card.capabilities.state_transition_history, Some(true));
+    assert_eq!(card.capabilities.streaming, Some(false));
+    assert_eq!(card.default_input_modes, vec!["text/plain"]);
+    assert_eq!(card.default_output_modes, vec!["text/plain"]);
+    assert_eq!(card.skills.len(), 1);
+    assert_eq!(card.skills[0].name, "test");
+    assert_eq!(card.skills[0].description, "A test skill");
+}
+
+#[test]
+fn test_agent_card_spec_compliance() {
+    let agent_card = AgentCard::new(
+        "Test Agent".to_string(),
+        "A test agent for A2A protocol".to_string(),
+        "1.0.0".to_string(),
+        "https://example.com/agent".to_string(),
+        AgentCapabilities {
+            extensions: None,
+            push_notifications: Some(true),
+            state_transition_history: Some(true),
+            streaming: Some(false),
+        },
+        vec!["text/plain".to_string(), "application/json".to_string()],
+        vec!["text/plain".to_string(), "application/json".to_string()],
+        vec![AgentSkill::minimal("text_processing".to_string(), "Process and analyze text content".to_string())
+            .with_input_modes(vec!["text/plain".to_string()])
+            .with_output_modes(vec!["text/plain".to_string()])
+            .with_examples(vec!["Analyze this text".to_string(), "Summarize this document".to_string()])],
+    );
+
+    let json = serde_json::to_value(&agent_card).unwrap();
+
+    // Validate camelCase field names as per spec
+    assert_eq!(json["protocolVersion"], "0.2.5");
+    assert_eq!(json["defaultInputModes"][0], "text/plain");
+    assert_eq!(json["defaultOutputModes"][0], "text/plain");
+    assert_eq!(json["capabilities"]["pushNotifications"], true);
+    assert_eq!(json["capabilities"]["stateTransitionHistory"], true);
+    assert_eq!(json["capabilities"]["streaming"], false);
+
+    // Validate skill structure
+    assert_eq!(json["skills"][0]["inputModes"][0], "text/plain");
+    assert_eq!(json["skills"][0]["outputModes"][0], "text/plain");
+    assert_eq!(json["skills"][0]["examples"][0], "Analyze this text");
+
+    // Test round-trip serialization
+    let serialized = serde_json::to_string(&agent_card).unwrap();
+    let deserialized: AgentCard = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(agent_card, deserialized);
+}
+
+#[test]
+fn test_agent_provider_spec_compliance() {
+    let provider = AgentProvider {
+        organization: "Test Organization".to_string(),
+        url: "https://test-org.com".to_string(),
+    };
+
+    let json = serde_json::to_value(&provider).unwrap();
+
+    // Validate field names match spec exactly
+    assert_eq!(json["organization"], "Test Organization");
+    assert_eq!(json["url"], "https://test-org.com");
+
+    // Test round-trip serialization
+    let serialized = serde_json::to_string(&provider).unwrap();
+    let deserialized: AgentProvider = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(provider, deserialized);
+}
+
+#[test]
+fn test_agent_card_link_validation() {
+    let mut card = AgentCard::new(
+        "Test Agent".to_string(),
+        "A test agent".to_string(),
+        "1.0.0".to_string(),
+        "https://example.com/agent".to_string(),
+        AgentCapabilities::default(),
+        vec!["text/plain".to_string()],
+        vec!["text/plain".to_string()],
+        vec![],
+    );
+    card.provider = Some(AgentProvider {
+        organization: "Test Organization".to_string(),
+        url: "https://test-org.com".to_string(),
+    });
+    card.documentation_url = Some("https://example.com/docs".to_string());
+    card.icon_url = Some("https://example.com/icon.svg?v=2".to_string());
+    assert!(card.validate().is_ok());
+
+    let field_of = |card: &AgentCard| {
+        assert!(card.validate().is_err());
+        card.validate_links(&CardValidationOptions::default()).unwrap_err().field.unwrap()
+    };
+
+    let mut invalid = card.clone();
+    invalid.provider.as_mut().unwrap().url = "not a url".to_string();
+    assert_eq!(field_of(&invalid), "provider.url");
+
+    let mut invalid = card.clone();
+    invalid.provider.as_mut().unwrap().organization = "x".repeat(AgentProvider::MAX_ORGANIZATION_LENGTH + 1);
+    assert_eq!(field_of(&invalid), "provider.organization");
+
+    let mut invalid = card.clone();
+    invalid.documentation_url = Some("ftp://example.com/docs".to_string());
+    assert_eq!(field_of(&invalid), "documentationUrl");
+
+    // Icons without a file extension are valid unless images are required
+    let strict = CardValidationOptions {
+        require_image_icon: true,
+    };
+    let mut icon = card.clone();
+    icon.icon_url = Some("https://example.com/icon".to_string());
+    assert!(icon.validate().is_ok());
+    assert!(icon.validate_with_options(&strict).is_err());
+    assert_eq!(icon.validate_links(&strict).unwrap_err().field.as_deref(), Some("iconUrl"));
+
+    icon.icon_url = Some("data:image/png;base64,iVBORw0KGgo=".to_string());
+    assert!(icon.validate_with_options(&strict).is_ok());
+    icon.icon_url = Some("data:text/plain,hello".to_string());
+    assert_eq!(icon.validate_links(&strict).unwrap_err().field.as_deref(), Some("iconUrl"));
+
+    let mut invalid = card.clone();
+    invalid.icon_url = Some("ftp://example.com/icon.png".to_string());
+    assert_eq!(field_of(&invalid), "iconUrl");
+}
+
+#[test]
+fn test_agent_card_skill_queries() {
+    let mut card = AgentCard::new(
+        "Translator".to_string(),
+        "Translates text".to_string(),
+        "1.2.0".to_string(),
+        "https://example.com/a2a".to_string(),
+        AgentCapabilities {
+            streaming: Some(true),
+            ..Default::default()
+        },
+        vec!["text/plain".to_string()],
+        vec!["text/plain".to_string()],
+        vec![
+            AgentSkill::minimal("translate".to_string(), "Translate text".to_string()),
+            AgentSkill::minimal("detect-language".to_string(), "Detect the language".to_string())
+                .with_output_modes(vec!["application/json".to_string()]),
+            AgentSkill::minimal("ocr".to_string(), "Read text in images".to_string())
+                .with_input_modes(vec!["image/*".to_string()]),
+        ],
+    );
+
+    assert_eq!(card.find_skill("ocr").unwrap().description, "Read text in images");
+    assert_eq!(card.find_skill("OCR").unwrap().name, "ocr");
+    assert!(card.find_skill("summarize").is_none());
+
+    let names = |skills: Vec<&AgentSkill>| skills.into_iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+    // Skills without modes fall back to the card defaults
+    assert_eq!(names(card.skills_accepting("text/plain; charset=utf-8")), ["translate", "detect-language"]);
+    assert_eq!(names(card.skills_accepting("image/png")), ["ocr"]);
+    assert_eq!(names(card.skills_accepting("*/*")).len(), 3);
+    assert_eq!(names(card.skills_producing("application/json")), ["detect-language"]);
+    assert_eq!(names(card.skills_producing("text/*")), ["translate", "ocr"]);
+
+    assert_eq!(
+        card.summary(),
+        "Translator v1.2.0 - Translates text (skills: translate, detect-language, ocr; capabilities: streaming)"
+    );
+    card.skills.clear();
+    card.capabilities = AgentCapabilities::default();
+    assert_eq!(card.summary(), "Translator v1.2.0 - Translates text");
+}
+
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+#[test]
+fn test_list_tasks_only_for_agents_serving_it() {
+    let mut card = AgentCard::new(
+        "Test Agent".to_string(),
+        "A test agent".to_string(),
+        "1.0.0".to_string(),
+        "https://example.com/agent".to_string(),
+        AgentCapabilities::default(),
+        vec!["text/plain".to_string()],
+        vec!["text/plain".to_string()],
+        vec![],
+    );
+    card.protocol_version = "0.2.5".to_string();
+    let list = |card: &AgentCard| ListTasksRequest::for_agent(card, "1".to_string(), ListTasksParams::default());
+    assert!(matches!(list(&card), Err(A2AError::UnsupportedOperation(ref e)) if e.code == -32004));
+
+    card.capabilities.extensions = Some(vec![AgentExtension::new(extensions::TASKS_LIST_EXTENSION_URI.to_string())]);
+    let request = list(&card).unwrap();
+    assert_eq!(request.method, RequestMethod::TasksList);
+    assert_eq!(serde_json::to_value(&request).unwrap()["method"], "tasks/list");
+
+    card.capabilities.extensions = None;
+    card.protocol_version = "0.3.0".to_string();
+    assert!(list(&card).is_ok());
+}
+
+const ORDERED_CARD: &str = r#"{
+    "name": "Secure Agent",
+    "description": "Guards its skills",
+    "version": "1.0.0",
+    "protocolVersion": "0.2.5",
+    "url": "https://example.com/agent",
+    "capabilities": {},
+    "defaultInputModes": ["text/plain"],
+    "defaultOutputModes": ["text/plain"],
+    "skills": [],
+    "securitySchemes": {
+        "zeta": {"type": "http", "scheme": "bearer"},
+        "oauth": {
+            "type": "oauth2",
+            "flows": {
+                "clientCredentials": {
+                    "tokenUrl": "https://example.com/token",
+                    "scopes": {"tasks:write": "Write tasks", "admin": "Everything", "tasks:read": "Read tasks"}
+                }
+            }
+        },
+        "apiKey": {"type": "apiKey", "in": "header", "name": "X-API-Key"}
+    },
+    "security": [{"zeta": [], "apiKey": []}, {"oauth": ["tasks:write", "tasks:read"]}]
+}"#;
+
+#[test]
+fn test_card_serialization_is_deterministic() {
+    let bytes = || serde_json::to_vec(&serde_json::from_str::<AgentCard>(ORDERED_CARD).unwrap()).unwrap();
+    let first = bytes();
+    for _ in 0..9 {
+        assert_eq!(bytes(), first);
+    }
+}
+
+// Without `std` the maps are sorted
+#[cfg(feature = "std")]
+#[test]
+fn test_card_maps_keep_the_source_order() {
+    let card: AgentCard = serde_json::from_str(ORDERED_CARD).unwrap();
+    let schemes = card.security_schemes.as_ref().unwrap();
+    assert_eq!(schemes.keys().collect::<Vec<_>>(), ["zeta", "oauth", "apiKey"]);
+    let SecurityScheme::OAuth2(oauth) = &schemes["oauth"] else { panic!("not an OAuth2 scheme") };
+    let scopes = oauth.scopes_for_flow(OAuthFlowKind::ClientCredentials).unwrap();
+    assert_eq!(scopes.keys().collect::<Vec<_>>(), ["tasks:write", "admin", "tasks:read"]);
+    assert_eq!(card.security.as_ref().unwrap()[0].keys().collect::<Vec<_>>(), ["zeta", "apiKey"]);
+
+    // Serializing writes the keys back in the same order
+    let json = serde_json::to_string(&card).unwrap();
+    let at = |needle: &str| json.find(needle).unwrap();
+    assert!(at(r#""zeta":{"#) < at(r#""oauth":{"#) && at(r#""oauth":{"#) < at(r#""apiKey":{"#));
+    assert!(at(r#""tasks:write":"#) < at(r#""admin":"#) && at(r#""admin":"#) < at(r#""tasks:read":"#));
+    assert!(json.contains(r#""security":[{"zeta":[],"apiKey":[]},"#));
+
+    // Inserted entries go last
+    let mut card = card;
+    let bearer = SecurityScheme::Http(HttpSecurityScheme::bearer(None));
+    card.security_schemes.as_mut().unwrap().insert("alpha".to_string(), bearer);
+    let keys: Vec<_> = card.security_schemes.as_ref().unwrap().keys().cloned().collect();
+    assert_eq!(keys, ["zeta", "oauth", "apiKey", "alpha"]);
+}
+
+#[test]
+fn test_card_localizations_on_the_wire() {
+    let mut card = AgentCard::new(
+        "Weather".to_string(),
+        "Forecasts for any city".to_string(),
+        "1.0.0".to_string(),
+        "https://example.com/agent".to_string(),
+        AgentCapabilities::default(),
+        vec!["text/plain".to_string()],
+        vec!["text/plain".to_string()],
+        vec![AgentSkill::minimal("forecast".to_string(), "Forecasts the weather".to_string())],
+    );
+    let plain = serde_json::to_value(&card).unwrap();
+
+    // Without translations, or with empty ones, the card is what the spec describes
+    card.name_localizations = Some(Map::new());
+    card.skills[0].description_localizations = Some(Map::new());
+    let json = serde_json::to_value(&card).unwrap();
+    assert_eq!(json, plain);
+    assert!(strict::from_value::<AgentCard>(json).is_ok());
+
+    card.name_localizations = Some(Map::from([("de".to_string(), "Wetter".to_string())]));
+    card.skills[0].description_localizations =
+        Some(Map::from([("de-AT".to_string(), "Sagt das Wetter voraus".to_string())]));
+    let json = serde_json::to_value(&card).unwrap();
+    assert_eq!(json["x-nameLocalizations"], serde_json::json!({"de": "Wetter"}));
+    assert_eq!(json["skills"][0]["x-descriptionLocalizations"], serde_json::json!({"de-AT": "Sagt das Wetter voraus"}));
+    assert!(json.get("x-descriptionLocalizations").is_none());
+    assert!(card.extra.is_empty());
+
+    let parsed = strict::from_value::<AgentCard>(json).unwrap();
+    assert_eq!(parsed, card);
+    assert_eq!(parsed.localized("de-AT").skills[0].description, "Sagt das Wetter voraus");
+    assert_eq!(parsed.localized("de-AT").name, "Wetter");
+    assert_eq!(parsed.localized("en").name, "Weather");
+}
@@ -0,0 +1,132 @@
+//! Allocation budgets for the small-request path.
+//!
+//! A counting global allocator records the allocations made on the current
+//! thread while a closure runs, so tests running in parallel do not disturb
+//! each other. Each budget is a little above today's count; a change that
+//! allocates more on these paths fails here and should either be reworked
+//! or raise the budget knowingly.
+
+use a2a_rs::context::RequestContext;
+use a2a_rs::helpers::parse_a2a_request;
+use a2a_rs::sse::SseDecoder;
+use a2a_rs::{A2ARequest, GetTaskResponse, Message, Task, TaskView};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count();
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count();
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count();
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count() {
+    // The thread-local may already be gone while a thread shuts down
+    let _ = ALLOCATIONS.try_with(|count| {
+        if let Some(n) = count.get() {
+            count.set(Some(n + 1));
+        }
+    });
+}
+
+/// Run a closure and count the allocations, including reallocations, it
+/// makes on this thread.
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    ALLOCATIONS.with(|count| count.set(Some(0)));
+    let result = f();
+    let n = ALLOCATIONS.with(|count| count.replace(None)).unwrap();
+    (result, n)
+}
+
+const GET_TASK: &str = r#"{"jsonrpc": "2.0", "id": "req-1", "method": "tasks/get", "params": {"taskId": "task-1"}}"#;
+
+#[test]
+fn test_small_request_parse_budget() {
+    let (request, n) = allocations(|| {
+        let request = parse_a2a_request(GET_TASK).unwrap();
+        let context = RequestContext::for_request(&request);
+        (request, context)
+    });
+    assert!(matches!(request.0, A2ARequest::GetTask(_)));
+    assert!(n <= 28, "parsing a tasks/get request took {} allocations, budget 28", n);
+}
+
+#[test]
+fn test_small_response_serialize_budget() {
+    let mut task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+    task.status.timestamp = Some("2025-01-31T12:00:00Z".to_string());
+    let response = GetTaskResponse {
+        id: "req-1".to_string(),
+        jsonrpc: "2.0".to_string(),
+        result: task,
+    };
+
+    let (bytes, n) = allocations(|| serde_json::to_vec(&response).unwrap());
+    assert!(!bytes.is_empty());
+    assert!(n <= 4, "serializing a tasks/get response took {} allocations, budget 4", n);
+}
+
+#[test]
+fn test_history_window_does_not_copy_the_history() {
+    let mut task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+    task.history = Some((0..2000).map(|n| Message::user(format!("msg-{}", n), [format!("hi {}", n)])).collect());
+
+    let (_, cloned) = allocations(|| {
+        let mut truncated = task.clone();
+        truncated.history.as_mut().unwrap().drain(..1000);
+        serde_json::to_writer(std::io::sink(), &truncated).unwrap();
+    });
+    let (_, viewed) = allocations(|| {
+        serde_json::to_writer(std::io::sink(), &TaskView::with_history_length(&task, Some(1000))).unwrap();
+    });
+    assert!(viewed <= 4, "serializing a history window took {} allocations, budget 4", viewed);
+    assert!(cloned > 1000 * viewed, "cloning took {} allocations, the view {}", cloned, viewed);
+}
+
+#[test]
+fn test_steady_state_sse_decoding_allocates_only_the_events() {
+    let event = br#"data: {"jsonrpc":"2.0","id":"1","result":{"kind":"status-update","final":false}}"#;
+    let mut decoder = SseDecoder::new();
+    let mut events = Vec::with_capacity(1);
+
+    // Grow the buffers once; a keep-alive and a line split across chunks
+    // must not make later events allocate more
+    let mut decode = |events: &mut Vec<_>| {
+        decoder.feed_into(b": keep-alive\n", events);
+        decoder.feed_into(&event[..20], events);
+        decoder.feed_into(&event[20..], events);
+        decoder.feed_into(b"\n\n", events);
+        assert!(events.pop().unwrap().is_ok());
+    };
+    decode(&mut events);
+
+    let (_, n) = allocations(|| {
+        for _ in 0..100 {
+            decode(&mut events);
+        }
+    });
+    assert!(n <= 100, "decoding 100 events took {} allocations, budget 1 per event", n);
+}
@@ -0,0 +1,9 @@
+//! Compile-fail tests for type-level guarantees.
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    #[cfg(feature = "strict-types")]
+    t.compile_fail("tests/ui/strict_types/*.rs");
+}
@@ -0,0 +1,112 @@
+//! Golden-file tests pinning the output of `a2a_rs::debug`.
+//!
+//! Each test explains a fixture and compares the text with
+//! `tests/snapshots/debug/<name>.txt`. After an intentional format change,
+//! regenerate the golden files with
+//! `A2A_UPDATE_SNAPSHOTS=1 cargo test --features diagnostics --test debug`
+//! and review the diff.
+
+#![cfg(feature = "diagnostics")]
+
+use a2a_rs::debug::{explain, explain_stream};
+use std::path::PathBuf;
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/debug").join(format!("{}.txt", name));
+
+    if std::env::var_os("A2A_UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+    }
+
+    let golden = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!("missing golden file {}; run with A2A_UPDATE_SNAPSHOTS=1 to create it", path.display())
+    });
+    assert_eq!(actual, golden, "{} no longer matches {}", name, path.display());
+}
+
+#[test]
+fn test_explain_send_message_request() {
+    let request = r#"{
+        "jsonrpc": "2.0",
+        "id": "req-1",
+        "method": "message/send",
+        "params": {
+            "message": {
+                "kind": "message",
+                "messageId": "msg-1",
+                "role": "user",
+                "contextId": "ctx-1",
+                "parts": [
+                    {"kind": "text", "text": "Summarize the attached report and chart the quarterly revenue figures, please."},
+                    {"kind": "file", "file": {"name": "report.pdf", "mimeType": "application/pdf", "bytes": "JVBERi0xLjQK"}},
+                    {"kind": "file", "file": {"uri": "https://example.com/chart.png"}},
+                    {"kind": "data", "data": {"quarter": "Q3", "currency": "EUR"}},
+                    {"kind": "video", "url": "https://example.com/talk.mp4"}
+                ],
+                "x-trace": "abc"
+            },
+            "configuration": {"blocking": true, "acceptedOutputModes": ["text/plain"], "historyLength": 5}
+        }
+    }"#;
+    assert_snapshot("send_message_request", &explain(request));
+}
+
+#[test]
+fn test_explain_responses() {
+    let task = r#"{
+        "jsonrpc": "2.0",
+        "id": "req-2",
+        "result": {
+            "kind": "task",
+            "id": "task-1",
+            "contextId": "ctx-1",
+            "status": {
+                "state": "input-required",
+                "timestamp": "2025-01-01T10:00:00Z",
+                "message": {"kind": "message", "messageId": "msg-3", "role": "agent", "parts": [{"kind": "text", "text": "Which quarter?"}]}
+            },
+            "history": [
+                {"kind": "message", "messageId": "msg-1", "role": "user", "parts": [{"kind": "text", "text": "Chart revenue"}]}
+            ],
+            "artifacts": [
+                {"artifactId": "a-1", "name": "draft", "parts": [{"kind": "text", "text": "Revenue grew."}]}
+            ],
+            "metadata": {"priority": "high"}
+        }
+    }"#;
+    let error = r#"{"jsonrpc": "2.0", "id": "req-3", "error": {"code": -32001, "message": "Task task-9 not found"}}"#;
+    let vendor_error = r#"{"jsonrpc": "2.0", "id": null, "error": {"code": -32050, "message": "Quota exceeded", "data": {"retryAfter": 30}}}"#;
+    let malformed = r#"{"jsonrpc": "2.0", "id": "req-4", "result": {"kind": "task", "id": "task-1"}}"#;
+    let output = [task, error, vendor_error, malformed, "not json"].map(explain).concat();
+    assert_snapshot("responses", &output);
+}
+
+#[test]
+fn test_explain_agent_card() {
+    let card = std::fs::read_to_string(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/agent_card.json"))
+        .unwrap();
+    assert_snapshot("agent_card", &explain(&card));
+}
+
+#[test]
+fn test_explain_stream() {
+    let capture = concat!(
+        "id: 1\n",
+        r#"data: {"jsonrpc": "2.0", "id": "req-5", "result": {"kind": "task", "id": "task-1", "contextId": "ctx-1", "status": {"state": "submitted"}}}"#,
+        "\n\n",
+        "id: 2\n",
+        r#"data: {"jsonrpc": "2.0", "id": "req-5", "result": {"kind": "status-update", "taskId": "task-1", "contextId": "ctx-1", "status": {"state": "working"}, "final": false}}"#,
+        "\n\n",
+        "id: 3\n",
+        r#"data: {"jsonrpc": "2.0", "id": "req-5", "result": {"kind": "artifact-update", "taskId": "task-1", "contextId": "ctx-1", "artifact": {"artifactId": "a-1", "parts": [{"kind": "text", "text": "Done"}]}, "lastChunk": true}}"#,
+        "\n\n",
+        "id: 4\n",
+        r#"data: {"jsonrpc": "2.0", "id": "req-5", "result": {"kind": "status-update", "taskId": "task-1", "contextId": "ctx-1", "status": {"state": "completed"}, "final": true}}"#,
+        "\n\n",
+        r#"data: {"jsonrpc": "2.0", "id": "req-5", "result": {"kind": "status-update", "taskId": "task-1", "contextId": "ctx-1", "status": {"state": "working"}, "final": false}}"#,
+        "\n\n",
+        "data: {\"jsonrpc\": \"2.0\"",
+    );
+    assert_snapshot("stream", &explain_stream(capture));
+}
@@ -0,0 +1,186 @@
+//! Protocol errors and their wire form.
+
+use a2a_rs::*;
+
+#[test]
+fn test_a2a_error_types_spec_compliance() {
+    // Test TaskNotFoundError
+    let task_not_found = TaskNotFoundError {
+        code: -32001,
+        message: "Task not found".to_string(),
+        data: None,
+    };
+    let serialized = serde_json::to_string(&task_not_found).unwrap();
+    let deserialized: TaskNotFoundError = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.code, -32001);
+    assert_eq!(deserialized.message, "Task not found");
+
+    // Test InternalError
+    let internal_error = InternalError {
+        code: -32603,
+        message: "Internal error".to_string(),
+        data: None,
+    };
+    let serialized = serde_json::to_string(&internal_error).unwrap();
+    let deserialized: InternalError = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.code, -32603);
+    assert_eq!(deserialized.message, "Internal error");
+
+    // Test JSONParseError
+    let json_parse_error = JSONParseError {
+        code: -32700,
+        message: "Invalid JSON payload".to_string(),
+        data: None,
+    };
+    let serialized = serde_json::to_string(&json_parse_error).unwrap();
+    let deserialized: JSONParseError = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.code, -32700);
+    assert_eq!(deserialized.message, "Invalid JSON payload");
+
+    // Test A2AError union type
+    let error = A2AError::TaskNotFound(Box::new(TaskNotFoundError {
+        code: -32001,
+        message: "Task not found".to_string(),
+        data: None,
+    }));
+    let serialized = serde_json::to_string(&error).unwrap();
+    let deserialized: A2AError = serde_json::from_str(&serialized).unwrap();
+    match deserialized {
+        A2AError::TaskNotFound(e) => {
+            assert_eq!(e.code, -32001);
+            assert_eq!(e.message, "Task not found");
+        }
+        _ => panic!("Expected TaskNotFound error"),
+    }
+}
+
+#[test]
+fn test_error_response_round_trips_every_code() {
+    let data = Some(serde_json::json!({"detail": [1, 2]}));
+    assert_eq!(A2AError::CODES, [-32700, -32600, -32601, -32602, -32603, -32001, -32002, -32003, -32004, -32005, -32006]);
+    for &code in A2AError::CODES {
+        let response = A2AErrorResponse { code, message: format!("Error {}", code), data: data.clone() };
+        let error = response.to_a2a_error();
+        // Every code maps back to the variant of the same code
+        assert_eq!(error.standard_code(), code);
+        // Same wire form as the error deserialized from the response
+        assert_eq!(error, serde_json::from_value::<A2AError>(serde_json::to_value(&response).unwrap()).unwrap());
+        // The display prefix is the code's name
+        assert!(error.to_string().starts_with(&format!("{}: ", A2AError::code_name(code).unwrap())));
+        assert_eq!(A2AErrorResponse::from(error), response);
+    }
+    assert_eq!(A2AError::code_name(-32001), Some("Task not found"));
+    assert_eq!(A2AError::code_name(-31999), None);
+
+    // Unknown codes fall back to an internal error keeping the code
+    let response = A2AErrorResponse { code: -31999, message: "Quota exceeded".to_string(), data: data.clone() };
+    let error = A2AError::from(response.clone());
+    match &error {
+        A2AError::Internal(e) => assert_eq!((e.code, e.message.as_str(), &e.data), (-31999, "Quota exceeded", &data)),
+        other => panic!("Expected Internal error, got {:?}", other),
+    }
+    assert_eq!(error.standard_code(), -32603);
+    assert_eq!(A2AErrorResponse::from(error), response);
+}
+
+#[test]
+fn test_comprehensive_error_types_spec_compliance() {
+    // Test all error types with their specific codes
+    let test_cases = vec![
+        (A2AError::JSONParse(Box::new(JSONParseError {
+            code: -32700,
+            message: "Parse error".to_string(),
+            data: None,
+        })), -32700),
+        (A2AError::InvalidRequest(Box::new(InvalidRequestError {
+            code: -32600,
+            message: "Invalid Request".to_string(),
+            data: None,
+        })), -32600),
+        (A2AError::MethodNotFound(Box::new(MethodNotFoundError {
+            code: -32601,
+            message: "Method not found".to_string(),
+            data: None,
+        })), -32601),
+        (A2AError::InvalidParams(Box::new(InvalidParamsError {
+            code: -32602,
+            message: "Invalid params".to_string(),
+            data: None,
+        })), -32602),
+        (A2AError::Internal(Box::new(InternalError {
+            code: -32603,
+            message: "Internal error".to_string(),
+            data: None,
+        })), -32603),
+        (A2AError::TaskNotFound(Box::new(TaskNotFoundError {
+            code: -32001,
+            message: "Task not found".to_string(),
+            data: None,
+        })), -32001),
+        (A2AError::TaskNotCancelable(Box::new(TaskNotCancelableError {
+            code: -32002,
+            message: "Task not cancelable".to_string(),
+            data: None,
+        })), -32002),
+        (A2AError::PushNotificationNotSupported(Box::new(PushNotificationNotSupportedError {
+            code: -32003,
+            message: "Push notifications not supported".to_string(),
+            data: None,
+        })), -32003),
+        (A2AError::UnsupportedOperation(Box::new(UnsupportedOperationError {
+            code: -32004,
+            message: "Unsupported operation".to_string(),
+            data: None,
+        })), -32004),
+        (A2AError::ContentTypeNotSupported(Box::new(ContentTypeNotSupportedError {
+            code: -32005,
+            message: "Content type not supported".to_string(),
+            data: None,
+        })), -32005),
+        (A2AError::InvalidAgentResponse(Box::new(InvalidAgentResponseError {
+            code: -32006,
+            message: "Invalid agent response".to_string(),
+            data: None,
+        })), -32006),
+    ];
+
+    for (error, expected_code) in test_cases {
+        // Test serialization
+        let serialized = serde_json::to_string(&error).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(json["code"].as_i64().unwrap(), expected_code);
+
+        // Test deserialization
+        let deserialized: A2AError = serde_json::from_str(&serialized).unwrap();
+
+        // Verify the error type matches
+        match (&error, &deserialized) {
+            (A2AError::JSONParse(_), A2AError::JSONParse(_)) => {},
+            (A2AError::InvalidRequest(_), A2AError::InvalidRequest(_)) => {},
+            (A2AError::MethodNotFound(_), A2AError::MethodNotFound(_)) => {},
+            (A2AError::InvalidParams(_), A2AError::InvalidParams(_)) => {},
+            (A2AError::Internal(_), A2AError::Internal(_)) => {},
+            (A2AError::TaskNotFound(_), A2AError::TaskNotFound(_)) => {},
+            (A2AError::TaskNotCancelable(_), A2AError::TaskNotCancelable(_)) => {},
+            (A2AError::PushNotificationNotSupported(_), A2AError::PushNotificationNotSupported(_)) => {},
+            (A2AError::UnsupportedOperation(_), A2AError::UnsupportedOperation(_)) => {},
+            (A2AError::ContentTypeNotSupported(_), A2AError::ContentTypeNotSupported(_)) => {},
+            (A2AError::InvalidAgentResponse(_), A2AError::InvalidAgentResponse(_)) => {},
+            _ => panic!("Error type mismatch during deserialization"),
+        }
+    }
+}
+
+#[test]
+fn test_foreign_error_codes() {
+    let error = |code: serde_json::Value| {
+        serde_json::from_value::<A2AError>(serde_json::json!({"code": code, "message": "Task not found"}))
+    };
+    // Some SDKs write the code as a float
+    assert!(matches!(error(serde_json::json!(-32001.0)), Ok(A2AError::TaskNotFound(e)) if e.code == -32001));
+    // Codes outside the i32 range are unknown, not a panic
+    assert!(error(serde_json::json!(2147483648_u64)).unwrap_err().to_string().contains("Unknown error code"));
+    assert!(error(serde_json::json!(u64::MAX)).unwrap_err().to_string().contains("Unknown error code"));
+    assert!(error(serde_json::json!(-32001.5)).unwrap_err().to_string().contains("Invalid error code: -32001.5"));
+    assert!(error(serde_json::json!("-32001")).is_err());
+}
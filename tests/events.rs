@@ -0,0 +1,163 @@
+//! Streaming status and artifact update events.
+
+use a2a_rs::*;
+
+// Struct literals of the event types need them exhaustive
+#[cfg(not(feature = "strict-types"))]
+#[test]
+fn test_task_artifact_update_event_spec_compliance() {
+    let artifact = Artifact {
+        artifact_id: "artifact-456".to_string(),
+        parts: vec![Part::Text(TextPart {
+            text: "Updated content".to_string(),
+            metadata: None,
+        })],
+        description: None,
+        extensions: None,
+        metadata: None,
+        name: None,
+        extra: ExtraFields::default(),
+    };
+
+    let event = TaskArtifactUpdateEvent {
+        kind: "artifact-update".to_string(),
+        task_id: "task-789".to_string(),
+        context_id: "ctx-123".to_string(),
+        artifact,
+        append: Some(true),
+        last_chunk: Some(false),
+        metadata: Some(serde_json::json!({"timestamp": "2023-10-27T10:00:00Z"})),
+        extra: ExtraFields::default(),
+    };
+
+    let json = serde_json::to_value(&event).unwrap();
+
+    // Validate required fields
+    assert_eq!(json["kind"], "artifact-update");
+    assert_eq!(json["taskId"], "task-789");
+    assert_eq!(json["contextId"], "ctx-123");
+    assert!(json["artifact"].is_object());
+    assert_eq!(json["artifact"]["artifactId"], "artifact-456");
+
+    // Validate optional fields
+    assert_eq!(json["append"], true);
+    assert_eq!(json["lastChunk"], false);
+    assert_eq!(json["metadata"]["timestamp"], "2023-10-27T10:00:00Z");
+
+    // Test round-trip serialization
+    let serialized = serde_json::to_string(&event).unwrap();
+    let deserialized: TaskArtifactUpdateEvent = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(event, deserialized);
+}
+
+// Struct literals of the event types need them exhaustive
+#[cfg(not(feature = "strict-types"))]
+#[test]
+fn test_task_status_update_event_spec_compliance() {
+    let status = TaskStatus {
+        state: TaskState::Working,
+        message: None,
+        timestamp: Some("2023-10-27T10:00:00Z".to_string()),
+        extra: ExtraFields::default(),
+    };
+
+    let event = TaskStatusUpdateEvent {
+        kind: "status-update".to_string(),
+        task_id: "task-abc".to_string(),
+        context_id: "ctx-def".to_string(),
+        status,
+        final_event: false,
+        metadata: Some(serde_json::json!({"source": "agent"})),
+        extra: ExtraFields::default(),
+    };
+
+    let json = serde_json::to_value(&event).unwrap();
+
+    // Validate required fields
+    assert_eq!(json["kind"], "status-update");
+    assert_eq!(json["taskId"], "task-abc");
+    assert_eq!(json["contextId"], "ctx-def");
+    assert_eq!(json["final"], false);
+    assert!(json["status"].is_object());
+    assert_eq!(json["status"]["state"], "working");
+
+    // Validate optional fields
+    assert_eq!(json["metadata"]["source"], "agent");
+
+    // Test round-trip serialization
+    let serialized = serde_json::to_string(&event).unwrap();
+    let deserialized: TaskStatusUpdateEvent = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(event, deserialized);
+}
+
+#[test]
+fn test_task_status_update_event_transitions() {
+    let (task, context) = ("task-abc".to_string(), "ctx-def".to_string());
+    let message = Message::agent("msg-1".to_string(), ["Which city?"]);
+    let events = [
+        (TaskStatusUpdateEvent::working(task.clone(), context.clone()), TaskState::Working, false),
+        (TaskStatusUpdateEvent::completed(task.clone(), context.clone()), TaskState::Completed, true),
+        (TaskStatusUpdateEvent::failed(task.clone(), context.clone(), message.clone()), TaskState::Failed, true),
+        (TaskStatusUpdateEvent::input_required(task.clone(), context.clone(), message), TaskState::InputRequired, false),
+    ];
+    for (event, state, final_event) in events {
+        assert_eq!(event.status.state, state);
+        assert_eq!(event.final_event, final_event, "{:?}", state);
+        assert!(event.validate().is_ok(), "{:?}", state);
+        assert!(clock::parse_timestamp(event.status.timestamp.as_deref().unwrap()).is_some());
+        assert_eq!(event.status.message.is_some(), matches!(state, TaskState::Failed | TaskState::InputRequired));
+    }
+
+    let event = TaskStatusUpdateEvent::completed(task.clone(), context.clone()).at(std::time::UNIX_EPOCH);
+    assert_eq!(event.status.timestamp.as_deref(), Some("1970-01-01T00:00:00.000000Z"));
+
+    // The `_at` variants stamp a given time instead of the current one
+    let time = std::time::UNIX_EPOCH + std::time::Duration::from_secs(90);
+    let message = Message::agent("msg-2".to_string(), ["Which day?"]);
+    let events = [
+        TaskStatusUpdateEvent::working_at(task.clone(), context.clone(), time),
+        TaskStatusUpdateEvent::completed_at(task.clone(), context.clone(), time),
+        TaskStatusUpdateEvent::failed_at(task.clone(), context.clone(), message.clone(), time),
+        TaskStatusUpdateEvent::input_required_at(task.clone(), context.clone(), message, time),
+        TaskStatusUpdateEvent::with_text_from_at(
+            &mut ids::SequentialSource::new(),
+            task.clone(),
+            context.clone(),
+            TaskState::Working,
+            "On it",
+            false,
+            time,
+        ),
+    ];
+    for event in events {
+        assert_eq!(event.status.timestamp.as_deref(), Some("1970-01-01T00:01:30.000000Z"));
+    }
+
+    let mut ids = ids::SequentialSource::new();
+    let event =
+        TaskStatusUpdateEvent::with_text_from(&mut ids, task, context, TaskState::InputRequired, "Which city?", false);
+    assert!(event.validate().is_ok());
+    assert!(clock::parse_timestamp(event.status.timestamp.as_deref().unwrap()).is_some());
+    let message = event.status.message.unwrap();
+    assert_eq!(message.validate(), Ok(()));
+    assert_eq!(message.role, MessageRole::Agent);
+    assert_eq!(message.task_id.as_deref(), Some("task-abc"));
+    assert_eq!(message.context_id.as_deref(), Some("ctx-def"));
+    assert_eq!(message.message_id, "msg-1");
+    let other = TaskStatus::with_agent_text_from(&mut ids, TaskState::Working, "task-abc", "ctx-def", "On it");
+    assert_eq!(other.message.unwrap().message_id, "msg-2");
+
+    // With the `uuid` feature the message IDs can be left to the crate
+    #[cfg(feature = "uuid")]
+    {
+        let event = TaskStatusUpdateEvent::with_text(
+            "task-abc".to_string(),
+            "ctx-def".to_string(),
+            TaskState::Working,
+            "On it",
+            false,
+        );
+        let other = TaskStatus::with_agent_text(TaskState::Working, "task-abc", "ctx-def", "On it");
+        assert_ne!(other.message.unwrap().message_id, event.status.message.unwrap().message_id);
+    }
+}
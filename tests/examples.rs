@@ -0,0 +1,121 @@
+//! The example agent and client, driven against each other.
+
+#[allow(dead_code)]
+#[path = "../examples/client.rs"]
+mod client;
+#[allow(dead_code)]
+#[path = "../examples/echo_agent.rs"]
+mod echo_agent;
+
+use a2a_rs::client::Effect;
+use a2a_rs::rejection::RejectionReason;
+use a2a_rs::{GetTaskParams, TaskState};
+use echo_agent::EchoAgent;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_client_round_trip_through_echo_agent() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let agent = Arc::new(EchoAgent::new(&url));
+    tokio::spawn(echo_agent::serve(listener, agent.clone()));
+
+    let (card, task, effects) = tokio::task::spawn_blocking(move || {
+        let card = client::fetch_card(&url)?;
+        let mut effects = Vec::new();
+        let result = client::send(&card, "Hello, agent", |effect| effects.push(effect.clone()))?;
+        Ok::<_, a2a_rs::client::ClientError>((card, result.into_task().unwrap(), effects))
+    })
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(card.name, "Echo Agent");
+    assert_eq!(task.status.state, TaskState::Completed);
+    let artifacts = task.artifacts.as_deref().unwrap();
+    assert_eq!(artifacts.len(), 1);
+    assert_eq!(artifacts[0].text().as_deref(), Some("Hello, agent"));
+
+    // Every step arrived, in order, and names the same task
+    assert!(matches!(&effects[0], Effect::TaskCreated(created) if created.id == task.id));
+    assert!(matches!(&effects[1], Effect::StatusUpdate(event) if event.status.state == TaskState::Working));
+    assert!(matches!(&effects[2], Effect::ArtifactUpdate(event) if event.task_id == task.id));
+    assert!(matches!(&effects[3], Effect::StatusUpdate(event) if event.final_event));
+    assert!(matches!(&effects[4], Effect::Completed(_)));
+    assert_eq!(effects.len(), 5);
+
+    // The agent stored what it streamed
+    let params = GetTaskParams {
+        task_id: task.id.clone(),
+        history_length: None,
+        extra: Default::default(),
+    };
+    let stored = agent.tasks().get_task(&params).await.unwrap();
+    assert_eq!(stored.status.state, TaskState::Completed);
+    assert_eq!(stored.artifacts, task.artifacts);
+    assert_eq!(stored.history.unwrap()[0].task_id.as_ref(), Some(&task.id));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_client_reads_replies_without_a_task() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let agent = Arc::new(EchoAgent::new(&url));
+    tokio::spawn(echo_agent::serve(listener, agent.clone()));
+
+    let (result, effects) = tokio::task::spawn_blocking(move || {
+        let card = client::fetch_card(&url)?;
+        let mut effects = Vec::new();
+        let result = client::send(&card, "ping", |effect| effects.push(effect.clone()))?;
+        Ok::<_, a2a_rs::client::ClientError>((result, effects))
+    })
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(result.text().as_deref(), Some("pong"));
+    assert!(result.context_id().is_some());
+    assert_eq!(result.task(), None);
+    assert!(matches!(&effects[..], [Effect::Message(message)] if Some(message) == result.message()));
+    assert!(agent.tasks().tasks().is_empty());
+}
+
+#[tokio::test]
+async fn test_echo_agent_rejects_unaccepted_output_modes() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let agent = Arc::new(EchoAgent::new(&format!("http://{}/", address)));
+    tokio::spawn(echo_agent::serve(listener, agent));
+
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": "1",
+        "method": "message/send",
+        "params": {
+            "message": {
+                "kind": "message",
+                "messageId": "msg-1",
+                "role": "user",
+                "parts": [{"kind": "text", "text": "Hi"}]
+            },
+            "configuration": {"acceptedOutputModes": ["video/mp4"]}
+        }
+    })
+    .to_string();
+    let mut socket = tokio::net::TcpStream::connect(address).await.unwrap();
+    let head = format!("POST / HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\n\r\n", address, body.len());
+    socket.write_all(head.as_bytes()).await.unwrap();
+    socket.write_all(body.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    socket.read_to_string(&mut response).await.unwrap();
+
+    // The task exists, but is rejected instead of failing the call
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    let response: serde_json::Value = serde_json::from_str(body).unwrap();
+    let task: a2a_rs::Task = serde_json::from_value(response["result"].clone()).unwrap();
+    assert_eq!(task.status.state, TaskState::Rejected);
+    assert_eq!(task.rejection_reason().unwrap().code, RejectionReason::CONTENT_TYPE_NOT_SUPPORTED);
+}
@@ -0,0 +1,154 @@
+//! Parsing and serialization helpers.
+
+use a2a_rs::*;
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[cfg(feature = "simd")]
+#[test]
+fn test_parse_request_simd_matches_serde_json() {
+    let json = r#"{"jsonrpc":"2.0","id":7,"method":"message/send","params":{"message":{
+        "kind":"message","messageId":"msg-1","role":"user","futureField":[1,2],
+        "parts":[{"kind":"text","text":"Hello \u00e9"},{"kind":"file","file":{"uri":"https://example.com/a"}}]}}}"#;
+
+    let mut bytes = json.as_bytes().to_vec();
+    let simd: SendMessageRequest = helpers::parse_request_simd(&mut bytes).unwrap();
+    let serde: SendMessageRequest = serde_json::from_str(json).unwrap();
+    assert_eq!(simd, serde);
+    assert_eq!(simd.params.message.extra.get("futureField"), Some(&serde_json::json!([1, 2])));
+
+    let error = helpers::parse_request_simd::<SendMessageRequest>(&mut b"{".to_vec()).unwrap_err();
+    assert!(matches!(error, A2AError::JSONParse(ref e) if e.code == -32700));
+}
+
+#[test]
+fn test_parse_a2a_request() {
+    let request = helpers::parse_a2a_request(
+        r#"{"jsonrpc":"2.0","id":"1","method":"tasks/cancel","params":{"taskId":"task-1"}}"#,
+    )
+    .unwrap();
+    assert_eq!(request.method(), RequestMethod::TasksCancel);
+    assert!(matches!(helpers::parse_a2a_request("{"), Err(A2AError::JSONParse(_))));
+}
+
+#[test]
+fn test_serialize_response_variants() {
+    let response = GetTaskResponse {
+        id: "1".to_string(),
+        jsonrpc: "2.0".to_string(),
+        result: Task::minimal("task-1".to_string(), "ctx-1".to_string()),
+    };
+
+    let compact = helpers::serialize_response(&response).unwrap();
+    let pretty = helpers::serialize_response_pretty(&response).unwrap();
+    assert!(!compact.contains('\n'));
+    assert!(pretty.contains("\n  \"result\": {"));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&pretty).unwrap(),
+        serde_json::from_str::<serde_json::Value>(&compact).unwrap()
+    );
+    assert_eq!(helpers::serialize_response_to_vec(&response).unwrap(), compact.as_bytes());
+
+    let mut out = Vec::new();
+    helpers::serialize_response_to_writer(&response, &mut out).unwrap();
+    assert_eq!(out, compact.as_bytes());
+}
+
+#[test]
+fn test_serialize_response_errors_are_internal() {
+    /// Accepts a few bytes, then fails every write.
+    struct FailingWriter(usize);
+
+    impl std::io::Write for FailingWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.0 == 0 {
+                return Err(std::io::Error::new(std::io::ErrorKind::BrokenPipe, "connection closed"));
+            }
+            let n = buf.len().min(self.0);
+            self.0 -= n;
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+    let error = helpers::serialize_response_to_writer(&task, FailingWriter(10)).unwrap_err();
+    assert!(matches!(error, A2AError::Internal(ref e) if e.code == -32603 && e.message.contains("connection closed")));
+
+    #[derive(Serialize)]
+    struct Response {
+        result: HashMap<Vec<u8>, u8>,
+    }
+    let response = Response {
+        result: HashMap::from([(vec![1], 1)]),
+    };
+    match helpers::serialize_response(&response).unwrap_err() {
+        A2AError::Internal(e) => {
+            assert!(e.message.contains("result"));
+            assert_eq!(e.data, Some(serde_json::json!({"path": "result"})));
+        }
+        other => panic!("Expected Internal, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_response_for_every_method() {
+    use helpers::parse_response_for;
+    use serde_json::json;
+
+    let task = json!({"kind": "task", "id": "task-1", "contextId": "ctx-1", "status": {"state": "working"}});
+    let message = json!({"kind": "message", "messageId": "m-1", "role": "agent", "parts": [{"kind": "text", "text": "Hi"}]});
+    let status = json!({"kind": "status-update", "taskId": "task-1", "contextId": "ctx-1", "status": {"state": "completed"}, "final": true});
+    let config = json!({"url": "https://example.com/hook", "id": "cfg-1"});
+    type Check = fn(&A2AResponsePayload) -> bool;
+    let cases: Vec<(RequestMethod, serde_json::Value, Check)> = vec![
+        (RequestMethod::MessageSend, task.clone(), |p| matches!(p, A2AResponsePayload::SendMessage(SendMessageResult::Task(_)))),
+        (RequestMethod::MessageSend, message.clone(), |p| matches!(p, A2AResponsePayload::SendMessage(SendMessageResult::Message(_)))),
+        (RequestMethod::MessageStream, status.clone(), |p| {
+            matches!(p, A2AResponsePayload::Streaming(StreamingMessageResult::TaskStatusUpdate(_)))
+        }),
+        (RequestMethod::TasksResubscribe, task.clone(), |p| matches!(p, A2AResponsePayload::Streaming(StreamingMessageResult::Task(_)))),
+        (RequestMethod::TasksGet, task.clone(), |p| matches!(p, A2AResponsePayload::Task(t) if t.id == "task-1")),
+        (RequestMethod::TasksCancel, task.clone(), |p| matches!(p, A2AResponsePayload::Task(_))),
+        (RequestMethod::TasksPushNotificationConfigSet, json!({"taskId": "task-1", "configId": "cfg-1"}), |p| {
+            matches!(p, A2AResponsePayload::PushConfigSet(r) if r.config_id == "cfg-1")
+        }),
+        (RequestMethod::TasksPushNotificationConfigGet, config, |p| {
+            matches!(p, A2AResponsePayload::PushConfig(c) if c.url == "https://example.com/hook")
+        }),
+        (RequestMethod::TasksPushNotificationConfigList, json!([{"configId": "cfg-1", "url": "https://example.com/hook"}]), |p| {
+            matches!(p, A2AResponsePayload::PushConfigList(list) if list.len() == 1)
+        }),
+        (RequestMethod::TasksPushNotificationConfigDelete, json!(null), |p| matches!(p, A2AResponsePayload::Deleted)),
+        (RequestMethod::TasksPushNotificationConfigDelete, json!(true), |p| matches!(p, A2AResponsePayload::Deleted)),
+    ];
+    let id = JsonRpcId::Integer(7);
+    for (method, result, expected) in cases {
+        let body = json!({"jsonrpc": "2.0", "id": 7, "result": result}).to_string();
+        let payload = parse_response_for(method, &body, Some(&id)).unwrap_or_else(|e| panic!("{}: {}", method, e));
+        assert!(expected(&payload), "{}: {:?}", method, payload);
+
+        let error = json!({"jsonrpc": "2.0", "id": 7, "error": {"code": -32001, "message": "Task not found"}});
+        assert!(matches!(parse_response_for(method, &error.to_string(), Some(&id)), Err(A2AError::TaskNotFound(_))));
+        let unreadable = json!({"jsonrpc": "2.0", "id": null, "error": {"code": -32700, "message": "Parse error"}});
+        assert!(matches!(parse_response_for(method, &unreadable.to_string(), Some(&id)), Err(A2AError::JSONParse(_))));
+
+        let other_id = body.replace("\"id\":7", "\"id\":\"7\"");
+        assert!(matches!(parse_response_for(method, &other_id, Some(&id)), Err(A2AError::InvalidAgentResponse(e)) if e.message.contains("id")));
+        assert!(parse_response_for(method, &other_id, None).is_ok());
+    }
+
+    let invalid = |method, body: serde_json::Value| match parse_response_for(method, &body.to_string(), None) {
+        Err(A2AError::InvalidAgentResponse(e)) => e.message,
+        other => panic!("expected an invalid agent response, got {:?}", other),
+    };
+    assert!(invalid(RequestMethod::TasksGet, json!({"jsonrpc": "2.0", "id": 1, "result": message})).contains("kind"));
+    assert!(invalid(RequestMethod::TasksGet, json!({"jsonrpc": "2.0", "id": 1})).contains("neither result nor error"));
+    assert!(invalid(RequestMethod::TasksGet, json!({"jsonrpc": "2.0", "id": 1, "result": {"kind": "task"}})).starts_with("Malformed tasks/get result"));
+    assert!(invalid(RequestMethod::TasksPushNotificationConfigDelete, json!({"jsonrpc": "2.0", "id": 1, "result": false})).contains("expected null"));
+    assert!(invalid(RequestMethod::TasksGet, json!({"jsonrpc": "2.0", "id": 1, "error": {"message": "no code"}})).starts_with("Malformed error response"));
+    assert!(matches!(parse_response_for(RequestMethod::TasksGet, "{", None), Err(A2AError::JSONParse(_))));
+}
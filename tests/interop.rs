@@ -0,0 +1,265 @@
+//! Interop tests against JSON in the shape the reference SDKs produce.
+//!
+//! Every file under `tests/interop/<dir>/` wraps one payload as
+//! `{"type": ..., "source": ..., "payload": ...}`. Each payload must parse
+//! into the named crate type and serialize back to the same JSON. The
+//! committed fixtures in `python-shaped/` and `js-shaped/` were written by
+//! hand after the Python and JS SDKs' types; `python-sdk/` and `js-sdk/`,
+//! when present, hold output of the SDKs themselves. See
+//! `tests/interop/README.md` for how those are generated.
+
+use a2a_rs::*;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// Differences between a fixture and its re-serialized form that are known
+/// and accepted for now, as `(fixture, JSON pointer)`. The suite fails once
+/// a gap is closed, so the entry gets removed along with the fix.
+const KNOWN_GAPS: &[(&str, &str)] = &[
+    // AgentSkill has no `id` or `tags` fields and drops them
+    ("python-shaped/agent_card_security.json", "/skills/0/id"),
+    ("python-shaped/agent_card_security.json", "/skills/0/tags"),
+    ("js-shaped/agent_card.json", "/skills/0/id"),
+    ("js-shaped/agent_card.json", "/skills/0/tags"),
+];
+
+struct Fixture {
+    name: String,
+    kind: String,
+    payload: Value,
+}
+
+fn fixture_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/interop")
+}
+
+fn load(name: &str) -> Fixture {
+    let path = fixture_dir().join(name);
+    let text = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    let mut wrapper: Value = serde_json::from_str(&text).unwrap_or_else(|e| panic!("{}: invalid JSON: {}", name, e));
+    let kind = wrapper["type"]
+        .as_str()
+        .unwrap_or_else(|| panic!("{}: missing \"type\"", name))
+        .to_string();
+    // Every fixture says where it came from
+    assert!(wrapper["source"].is_string(), "{}: missing \"source\"", name);
+    let payload = wrapper
+        .get_mut("payload")
+        .map(Value::take)
+        .unwrap_or_else(|| panic!("{}: missing \"payload\"", name));
+    Fixture {
+        name: name.to_string(),
+        kind,
+        payload,
+    }
+}
+
+fn all_fixtures() -> Vec<Fixture> {
+    let mut names = Vec::new();
+    for dir in std::fs::read_dir(fixture_dir()).unwrap() {
+        let dir = dir.unwrap().path();
+        if !dir.is_dir() {
+            continue;
+        }
+        let dir_name = dir.file_name().unwrap().to_string_lossy().into_owned();
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let file = path.file_name().unwrap().to_string_lossy();
+                names.push(format!("{}/{}", dir_name, file));
+            }
+        }
+    }
+    names.sort();
+    names.iter().map(|name| load(name)).collect()
+}
+
+/// Parse `value`, naming the fixture and the JSON path of any failure.
+fn try_parse<T: DeserializeOwned>(fixture: &str, value: &Value) -> Result<T, String> {
+    serde_path_to_error::deserialize(value).map_err(|e| {
+        format!("{}: cannot parse as {} at {}: {}", fixture, std::any::type_name::<T>(), e.path(), e.inner())
+    })
+}
+
+fn parse<T: DeserializeOwned>(fixture: &str, value: &Value) -> T {
+    try_parse(fixture, value).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Collect the JSON pointers at which `expected` and `actual` differ.
+fn diff(pointer: &str, expected: &Value, actual: &Value, out: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, value) in expected {
+                let child = format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+                match actual.get(key) {
+                    Some(other) => diff(&child, value, other, out),
+                    None => out.push(child),
+                }
+            }
+            for key in actual.keys().filter(|key| !expected.contains_key(*key)) {
+                out.push(format!("{}/{}", pointer, key));
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+            for (i, (value, other)) in expected.iter().zip(actual).enumerate() {
+                diff(&format!("{}/{}", pointer, i), value, other, out);
+            }
+        }
+        _ if expected == actual => {}
+        _ => out.push(pointer.to_string()),
+    }
+}
+
+/// Parse `value` as `T`, check it serializes back to the same JSON apart
+/// from known gaps, and return it with the gaps that were hit.
+fn round_trip<T: Serialize + DeserializeOwned>(fixture: &str, pointer: &str, value: &Value) -> (T, Vec<String>) {
+    let parsed: T = parse(fixture, value);
+    let mut differences = Vec::new();
+    diff(pointer, value, &serde_json::to_value(&parsed).unwrap(), &mut differences);
+
+    let (gaps, unexpected): (Vec<_>, Vec<_>) = differences
+        .into_iter()
+        .partition(|p| KNOWN_GAPS.contains(&(fixture, p.as_str())));
+    assert!(
+        unexpected.is_empty(),
+        "{}: re-serialized {} differs at {}",
+        fixture,
+        std::any::type_name::<T>(),
+        unexpected.join(", ")
+    );
+    (parsed, gaps)
+}
+
+fn check(fixture: &Fixture) -> Vec<String> {
+    let name = fixture.name.as_str();
+    match fixture.kind.as_str() {
+        "SendMessageRequest" => round_trip::<SendMessageRequest>(name, "", &fixture.payload).1,
+        "JSONRPCErrorResponse" => round_trip::<JSONRPCErrorResponse>(name, "", &fixture.payload).1,
+        "AgentCard" => round_trip::<AgentCard>(name, "", &fixture.payload).1,
+        "Task" => round_trip::<Task>(name, "", &fixture.payload).1,
+        "SendStreamingMessageResponse[]" => stream_results(fixture).into_iter().flat_map(|(_, gaps)| gaps).collect(),
+        other => panic!("{}: unknown fixture type {}", name, other),
+    }
+}
+
+/// Parse each response of a recorded stream, checking the envelope and
+/// returning the results.
+fn stream_results(fixture: &Fixture) -> Vec<(StreamingMessageResult, Vec<String>)> {
+    let responses = fixture
+        .payload
+        .as_array()
+        .unwrap_or_else(|| panic!("{}: payload is not an array", fixture.name));
+    responses
+        .iter()
+        .enumerate()
+        .map(|(i, response)| {
+            let pointer = format!("/{}", i);
+            let _: JsonRpcId = round_trip(&fixture.name, &format!("{}/id", pointer), &response["id"]).0;
+            assert_eq!(response["jsonrpc"], "2.0", "{}: {}/jsonrpc", fixture.name, pointer);
+            round_trip(&fixture.name, &format!("{}/result", pointer), &response["result"])
+        })
+        .collect()
+}
+
+#[test]
+fn test_all_fixtures_round_trip() {
+    let fixtures = all_fixtures();
+    assert!(fixtures.iter().any(|f| f.name.starts_with("python-shaped/")));
+    assert!(fixtures.iter().any(|f| f.name.starts_with("js-shaped/")));
+
+    let hit: Vec<(String, String)> = fixtures
+        .iter()
+        .flat_map(|fixture| check(fixture).into_iter().map(|gap| (fixture.name.clone(), gap)))
+        .collect();
+    for (fixture, pointer) in KNOWN_GAPS {
+        assert!(
+            hit.iter().any(|(f, p)| f == fixture && p == pointer),
+            "{}: known gap at {} no longer occurs; remove it from KNOWN_GAPS",
+            fixture,
+            pointer
+        );
+    }
+}
+
+#[test]
+fn test_send_message_requests() {
+    let fixture = load("python-shaped/send_message_request.json");
+    let request: SendMessageRequest = round_trip(&fixture.name, "", &fixture.payload).0;
+    assert_eq!(request.method, RequestMethod::MessageSend);
+    assert_eq!(request.id, JsonRpcId::String("3f8c1a52-6d0e-4a53-9d5e-7b2f9f0c1e44".to_string()));
+    let message = &request.params.message;
+    assert_eq!(message.role, MessageRole::User);
+    assert_eq!(message.context_id.as_deref(), Some("0f4e9a7c-3b1d-4c8e-a6f2-5d9b8e7c6a10"));
+    assert!(matches!(&message.parts[0], Part::Text(text) if text.text == "how much is 10 USD in INR?"));
+    assert!(matches!(&message.parts[1], Part::Data(data) if data.data["amount"] == 10));
+    assert!(matches!(&message.parts[2], Part::File(_)));
+    let configuration = request.params.configuration.as_ref().unwrap();
+    assert_eq!(configuration.history_length, Some(10));
+
+    // Numbered, as the JS SDK numbers its requests
+    let fixture = load("js-shaped/send_message_request.json");
+    let request: SendMessageRequest = round_trip(&fixture.name, "", &fixture.payload).0;
+    assert_eq!(request.id, JsonRpcId::Integer(1));
+    assert_eq!(request.params.message.message_id, "2c1f6a0e-4b3d-4e8a-9f7c-6d5e4c3b2a1f");
+}
+
+#[test]
+fn test_stream_event_sequences() {
+    let events: Vec<_> = stream_results(&load("python-shaped/stream_events.json"))
+        .into_iter()
+        .map(|(result, _)| result)
+        .collect();
+    assert!(matches!(&events[0], StreamingMessageResult::Task(task) if task.status.state == TaskState::Submitted));
+    assert!(matches!(&events[1], StreamingMessageResult::TaskStatusUpdate(event)
+        if event.status.state == TaskState::Working && !event.final_event));
+    assert!(matches!(&events[2], StreamingMessageResult::TaskArtifactUpdate(event)
+        if event.artifact.name.as_deref() == Some("conversion_result") && event.last_chunk == Some(true)));
+    assert!(matches!(&events[3], StreamingMessageResult::TaskStatusUpdate(event)
+        if event.status.state == TaskState::Completed && event.final_event));
+
+    let events = stream_results(&load("js-shaped/stream_events.json"));
+    assert!(matches!(&events[1].0, StreamingMessageResult::TaskStatusUpdate(event)
+        if event.status.state == TaskState::InputRequired && event.final_event));
+}
+
+#[test]
+fn test_error_responses() {
+    let fixture = load("python-shaped/error_task_not_found.json");
+    let response: JSONRPCErrorResponse = round_trip(&fixture.name, "", &fixture.payload).0;
+    assert!(matches!(&response.error, A2AError::TaskNotFound(error) if error.code == -32001));
+
+    let fixture = load("js-shaped/error_invalid_params.json");
+    let response: JSONRPCErrorResponse = round_trip(&fixture.name, "", &fixture.payload).0;
+    assert_eq!(response.id, JsonRpcId::Integer(3));
+    assert!(matches!(response.error, A2AError::InvalidParams(_)));
+}
+
+#[test]
+fn test_agent_cards_with_security_schemes() {
+    let fixture = load("python-shaped/agent_card_security.json");
+    let card: AgentCard = round_trip(&fixture.name, "", &fixture.payload).0;
+    assert_eq!(card.name, "Currency Agent");
+    let schemes = card.security_schemes.as_ref().unwrap();
+    assert_eq!(schemes.len(), 4);
+    assert!(matches!(schemes["apiKey"], SecurityScheme::ApiKey { .. }));
+    assert!(matches!(schemes["bearer"], SecurityScheme::Http { .. }));
+    assert!(matches!(schemes["oauth"], SecurityScheme::OAuth2 { .. }));
+    assert!(matches!(schemes["oidc"], SecurityScheme::OpenIdConnect { .. }));
+    assert_eq!(card.security.as_ref().unwrap().len(), 2);
+
+    let fixture = load("js-shaped/agent_card.json");
+    let card: AgentCard = round_trip(&fixture.name, "", &fixture.payload).0;
+    assert_eq!(card.provider.as_ref().unwrap().organization, "A2A Samples");
+    assert_eq!(card.skills[0].name, "General Movie Chat");
+}
+
+#[test]
+fn test_failures_name_the_fixture_and_path() {
+    let mut payload = load("python-shaped/send_message_request.json").payload;
+    payload["params"]["configuration"]["historyLength"] = Value::from("ten");
+    let error = try_parse::<SendMessageRequest>("python-shaped/send_message_request.json", &payload).unwrap_err();
+    assert!(error.starts_with("python-shaped/send_message_request.json: "), "{}", error);
+    assert!(error.contains(" at params.configuration.historyLength: "), "{}", error);
+}
@@ -0,0 +1,137 @@
+// Regenerate the JS SDK fixtures in tests/interop/js-sdk.
+//
+// Requires the reference SDK, from the 0.2 series that speaks protocol 0.2.6:
+// npm install @a2a-js/sdk@0.2
+// Run from the repository root: node tests/interop/scripts/regenerate.mjs
+//
+// The SDK's types are TypeScript-only, so the payloads below are checked
+// against them with `// @ts-check` and serialized as the SDK's server would,
+// with JSON.stringify.
+
+// @ts-check
+import { mkdirSync, readFileSync, writeFileSync } from "node:fs";
+import { dirname, join } from "node:path";
+import { fileURLToPath } from "node:url";
+
+const OUT = join(dirname(fileURLToPath(import.meta.url)), "..", "js-sdk");
+// The package's exports hide its package.json from import, so read it from node_modules
+const SDK_VERSION = JSON.parse(readFileSync("node_modules/@a2a-js/sdk/package.json", "utf8")).version;
+const SDK_SERIES = "0.2.";
+if (!SDK_VERSION.startsWith(SDK_SERIES)) {
+  throw new Error(`@a2a-js/sdk ${SDK_VERSION} is installed, the fixtures are pinned to ${SDK_SERIES}x`);
+}
+const SOURCE = `generated by @a2a-js/sdk ${SDK_VERSION}`;
+const TASK_ID = "4a5b6c7d-8e9f-4a0b-9c1d-2e3f4a5b6c7d";
+const CONTEXT_ID = "9e8d7c6b-5a4f-4e3d-8c2b-1a0f9e8d7c6b";
+
+/**
+ * @param {string} name
+ * @param {string} type
+ * @param {unknown} payload
+ */
+function write(name, type, payload) {
+  mkdirSync(OUT, { recursive: true });
+  writeFileSync(join(OUT, `${name}.json`), JSON.stringify({ type, source: SOURCE, payload }, null, 2) + "\n");
+}
+
+/** @type {import("@a2a-js/sdk").SendMessageRequest} */
+const sendMessageRequest = {
+  jsonrpc: "2.0",
+  method: "message/send",
+  id: 1,
+  params: {
+    message: {
+      messageId: "2c1f6a0e-4b3d-4e8a-9f7c-6d5e4c3b2a1f",
+      role: "user",
+      parts: [{ kind: "text", text: "Tell me about the movie Inception" }],
+      kind: "message",
+    },
+    configuration: { blocking: true, acceptedOutputModes: ["text/plain"] },
+  },
+};
+write("send_message_request", "SendMessageRequest", sendMessageRequest);
+
+/** @type {import("@a2a-js/sdk").SendStreamingMessageSuccessResponse[]} */
+const streamEvents = [
+  {
+    jsonrpc: "2.0",
+    id: 2,
+    result: {
+      kind: "task",
+      id: TASK_ID,
+      contextId: CONTEXT_ID,
+      status: { state: "submitted", timestamp: "2025-07-01T12:00:00.000Z" },
+      history: [
+        {
+          messageId: "3d4e5f6a-7b8c-4d9e-8f0a-1b2c3d4e5f6a",
+          role: "user",
+          parts: [{ kind: "text", text: "Recommend a movie" }],
+          kind: "message",
+          taskId: TASK_ID,
+          contextId: CONTEXT_ID,
+        },
+      ],
+      metadata: {},
+      artifacts: [],
+    },
+  },
+  {
+    jsonrpc: "2.0",
+    id: 2,
+    result: {
+      kind: "status-update",
+      taskId: TASK_ID,
+      contextId: CONTEXT_ID,
+      status: {
+        state: "input-required",
+        message: {
+          kind: "message",
+          role: "agent",
+          messageId: "6f7a8b9c-0d1e-4f2a-8b3c-4d5e6f7a8b9c",
+          parts: [{ kind: "text", text: "Which genre do you prefer?" }],
+          taskId: TASK_ID,
+          contextId: CONTEXT_ID,
+        },
+        timestamp: "2025-07-01T12:00:00.420Z",
+      },
+      final: true,
+    },
+  },
+];
+write("stream_events", "SendStreamingMessageResponse[]", streamEvents);
+
+/** @type {import("@a2a-js/sdk").JSONRPCErrorResponse} */
+const errorInvalidParams = {
+  jsonrpc: "2.0",
+  id: 3,
+  error: { code: -32602, message: "Invalid parameters", data: { details: "message.parts must not be empty" } },
+};
+write("error_invalid_params", "JSONRPCErrorResponse", errorInvalidParams);
+
+/** @type {import("@a2a-js/sdk").AgentCard} */
+const agentCard = {
+  name: "Movie Agent",
+  description: "An agent that can answer questions about movies and actors using TMDB.",
+  url: "http://localhost:41241/",
+  provider: { organization: "A2A Samples", url: "https://example.com/a2a-samples" },
+  version: "0.0.2",
+  protocolVersion: "0.2.6",
+  capabilities: { streaming: true, pushNotifications: false, stateTransitionHistory: true },
+  securitySchemes: { bearer: { type: "http", scheme: "bearer" } },
+  security: [{ bearer: [] }],
+  defaultInputModes: ["text"],
+  defaultOutputModes: ["text", "task-status"],
+  skills: [
+    {
+      id: "general_movie_chat",
+      name: "General Movie Chat",
+      description: "Answer general questions or chat about movies, actors, directors.",
+      tags: ["movies", "actors", "directors"],
+      examples: ["Tell me about the plot of Inception.", "Recommend a good sci-fi movie."],
+      inputModes: ["text"],
+      outputModes: ["text", "task-status"],
+    },
+  ],
+  supportsAuthenticatedExtendedCard: true,
+};
+write("agent_card", "AgentCard", agentCard);
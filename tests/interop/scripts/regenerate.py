@@ -0,0 +1,212 @@
+"""Regenerate the Python SDK fixtures in tests/interop/python-sdk.
+
+Requires the reference SDK, from the 0.2 series that speaks protocol 0.2.6:
+pip install "a2a-sdk>=0.2.6,<0.3"
+Run from the repository root: python tests/interop/scripts/regenerate.py
+"""
+
+import json
+from importlib.metadata import version
+from pathlib import Path
+
+from a2a.types import (
+    AgentCapabilities,
+    AgentCard,
+    AgentSkill,
+    APIKeySecurityScheme,
+    Artifact,
+    ClientCredentialsOAuthFlow,
+    DataPart,
+    FilePart,
+    FileWithUri,
+    HTTPAuthSecurityScheme,
+    JSONRPCErrorResponse,
+    Message,
+    MessageSendConfiguration,
+    MessageSendParams,
+    OAuth2SecurityScheme,
+    OAuthFlows,
+    OpenIdConnectSecurityScheme,
+    Role,
+    SendMessageRequest,
+    SendStreamingMessageResponse,
+    SendStreamingMessageSuccessResponse,
+    Task,
+    TaskArtifactUpdateEvent,
+    TaskNotFoundError,
+    TaskState,
+    TaskStatus,
+    TaskStatusUpdateEvent,
+    TextPart,
+)
+
+OUT = Path(__file__).resolve().parent.parent / "python-sdk"
+SDK_SERIES = "0.2."
+SDK_VERSION = version("a2a-sdk")
+if not SDK_VERSION.startswith(SDK_SERIES):
+    raise SystemExit(f"a2a-sdk {SDK_VERSION} is installed, the fixtures are pinned to {SDK_SERIES}x")
+SOURCE = f"generated by a2a-sdk {SDK_VERSION}"
+CONTEXT_ID = "0f4e9a7c-3b1d-4c8e-a6f2-5d9b8e7c6a10"
+TASK_ID = "c8a0d2f1-7e6b-4d3c-9a8b-2f1e0d9c8b7a"
+STREAM_ID = "5a1b2c3d-4e5f-4a6b-8c7d-9e0f1a2b3c4d"
+
+
+def dump(model):
+    return model.model_dump(mode="json", by_alias=True, exclude_none=True)
+
+
+def write(name, kind, payload):
+    text = json.dumps({"type": kind, "source": SOURCE, "payload": payload}, indent=2, sort_keys=True)
+    OUT.mkdir(exist_ok=True)
+    (OUT / f"{name}.json").write_text(text + "\n")
+
+
+def text(value):
+    return TextPart(text=value)
+
+
+def user_message(**fields):
+    return Message(
+        context_id=CONTEXT_ID,
+        message_id="b7d4c1e2-9a3f-4e5d-8c6b-1a2f3e4d5c6b",
+        role=Role.user,
+        **fields,
+    )
+
+
+def streamed(result):
+    response = SendStreamingMessageSuccessResponse(id=STREAM_ID, result=result)
+    return dump(SendStreamingMessageResponse(root=response))
+
+
+write(
+    "send_message_request",
+    "SendMessageRequest",
+    dump(
+        SendMessageRequest(
+            id="3f8c1a52-6d0e-4a53-9d5e-7b2f9f0c1e44",
+            params=MessageSendParams(
+                configuration=MessageSendConfiguration(
+                    accepted_output_modes=["text", "text/plain"],
+                    blocking=True,
+                    history_length=10,
+                ),
+                message=user_message(
+                    parts=[
+                        text("how much is 10 USD in INR?"),
+                        DataPart(data={"amount": 10, "from": "USD", "to": "INR"}),
+                        FilePart(
+                            file=FileWithUri(
+                                mime_type="text/csv",
+                                name="rates.csv",
+                                uri="https://example.com/rates.csv",
+                            )
+                        ),
+                    ]
+                ),
+            ),
+        )
+    ),
+)
+
+write(
+    "stream_events",
+    "SendStreamingMessageResponse[]",
+    [
+        streamed(
+            Task(
+                context_id=CONTEXT_ID,
+                history=[user_message(parts=[text("how much is 10 USD in INR?")], task_id=TASK_ID)],
+                id=TASK_ID,
+                status=TaskStatus(state=TaskState.submitted, timestamp="2025-07-01T12:00:00.000001+00:00"),
+            )
+        ),
+        streamed(
+            TaskStatusUpdateEvent(
+                context_id=CONTEXT_ID,
+                final=False,
+                status=TaskStatus(
+                    message=Message(
+                        context_id=CONTEXT_ID,
+                        message_id="e1f2a3b4-c5d6-4e7f-8a9b-0c1d2e3f4a5b",
+                        parts=[text("Looking up the exchange rates...")],
+                        role=Role.agent,
+                        task_id=TASK_ID,
+                    ),
+                    state=TaskState.working,
+                    timestamp="2025-07-01T12:00:00.250000+00:00",
+                ),
+                task_id=TASK_ID,
+            )
+        ),
+        streamed(
+            TaskArtifactUpdateEvent(
+                append=False,
+                artifact=Artifact(
+                    artifact_id="a9b8c7d6-e5f4-4a3b-2c1d-0e9f8a7b6c5d",
+                    name="conversion_result",
+                    parts=[text("10 USD is 856.23 INR.")],
+                ),
+                context_id=CONTEXT_ID,
+                last_chunk=True,
+                task_id=TASK_ID,
+            )
+        ),
+        streamed(
+            TaskStatusUpdateEvent(
+                context_id=CONTEXT_ID,
+                final=True,
+                status=TaskStatus(state=TaskState.completed, timestamp="2025-07-01T12:00:01.000000+00:00"),
+                task_id=TASK_ID,
+            )
+        ),
+    ],
+)
+
+write(
+    "error_task_not_found",
+    "JSONRPCErrorResponse",
+    dump(JSONRPCErrorResponse(error=TaskNotFoundError(), id="7d6c5b4a-3f2e-4d1c-9b8a-7f6e5d4c3b2a")),
+)
+
+write(
+    "agent_card_security",
+    "AgentCard",
+    dump(
+        AgentCard(
+            capabilities=AgentCapabilities(push_notifications=True, streaming=True),
+            default_input_modes=["text", "text/plain"],
+            default_output_modes=["text", "text/plain"],
+            description="Helps with exchange values between various currencies",
+            name="Currency Agent",
+            security=[{"oauth": ["rates:read"]}, {"apiKey": []}],
+            security_schemes={
+                "apiKey": APIKeySecurityScheme(in_="header", name="X-API-Key"),
+                "bearer": HTTPAuthSecurityScheme(bearer_format="JWT", scheme="bearer"),
+                "oauth": OAuth2SecurityScheme(
+                    flows=OAuthFlows(
+                        client_credentials=ClientCredentialsOAuthFlow(
+                            scopes={"rates:read": "Read exchange rates"},
+                            token_url="https://auth.example.com/oauth/token",
+                        )
+                    )
+                ),
+                "oidc": OpenIdConnectSecurityScheme(
+                    open_id_connect_url="https://auth.example.com/.well-known/openid-configuration"
+                ),
+            },
+            skills=[
+                AgentSkill(
+                    description="Helps with exchange values between various currencies",
+                    examples=["What is exchange rate between USD and GBP?"],
+                    id="convert_currency",
+                    name="Currency Exchange Rates Tool",
+                    tags=["currency conversion", "currency exchange"],
+                )
+            ],
+            supports_authenticated_extended_card=False,
+            url="http://localhost:10000/",
+            version="1.0.0",
+        )
+    ),
+)
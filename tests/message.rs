@@ -0,0 +1,230 @@
+//! Messages and their parts.
+
+use a2a_rs::*;
+
+#[test]
+fn test_message_spec_compliance() {
+    let message = Message {
+        kind: "message".to_string(),
+        message_id: "msg-123".to_string(),
+        parts: vec![Part::Text(TextPart {
+            text: "Hello, world!".to_string(),
+            metadata: None,
+        })],
+        role: MessageRole::User,
+        context_id: Some("ctx-456".to_string()),
+        extensions: None,
+        metadata: None,
+        reference_task_ids: None,
+        task_id: Some("task-789".to_string()),
+        extra: ExtraFields::default(),
+    };
+
+    let json = serde_json::to_value(&message).unwrap();
+
+    // Validate required fields
+    assert_eq!(json["kind"], "message");
+    assert_eq!(json["messageId"], "msg-123");
+    assert_eq!(json["role"], "user");
+    assert_eq!(json["contextId"], "ctx-456");
+    assert_eq!(json["taskId"], "task-789");
+
+    // Validate parts structure
+    assert!(json["parts"].is_array());
+    assert_eq!(json["parts"][0]["kind"], "text");
+    assert_eq!(json["parts"][0]["text"], "Hello, world!");
+
+    // Test round-trip serialization
+    let serialized = serde_json::to_string(&message).unwrap();
+    let deserialized: Message = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(message, deserialized);
+}
+
+#[test]
+fn test_part_variants_spec_compliance() {
+    // Test TextPart
+    let text_part = Part::Text(TextPart {
+        text: "Hello world".to_string(),
+        metadata: Some(serde_json::json!({"source": "user"})),
+    });
+
+    let json = serde_json::to_value(&text_part).unwrap();
+    assert_eq!(json["kind"], "text");
+    assert_eq!(json["text"], "Hello world");
+    assert_eq!(json["metadata"]["source"], "user");
+
+    // Test FilePart with bytes
+    let file_part = Part::File(Box::new(FilePart {
+        file: FileContent::WithBytes(FileWithBytes {
+            bytes: "SGVsbG8gd29ybGQ=".to_string(), // "Hello world" in base64
+            name: Some("test.txt".to_string()),
+            mime_type: Some("text/plain".to_string()),
+        }),
+        metadata: None,
+    }));
+
+    let json = serde_json::to_value(&file_part).unwrap();
+    assert_eq!(json["kind"], "file");
+    assert_eq!(json["file"]["bytes"], "SGVsbG8gd29ybGQ=");
+    assert_eq!(json["file"]["name"], "test.txt");
+    assert_eq!(json["file"]["mimeType"], "text/plain");
+
+    // Test FilePart with URI
+    let file_part_uri = Part::File(Box::new(FilePart {
+        file: FileContent::WithUri(FileWithUri {
+            uri: "https://example.com/file.txt".to_string(),
+            name: Some("remote.txt".to_string()),
+            mime_type: Some("text/plain".to_string()),
+        }),
+        metadata: None,
+    }));
+
+    let json = serde_json::to_value(&file_part_uri).unwrap();
+    assert_eq!(json["kind"], "file");
+    assert_eq!(json["file"]["uri"], "https://example.com/file.txt");
+
+    // Test DataPart
+    let data_part = Part::Data(DataPart {
+        data: serde_json::json!({"key": "value", "number": 42}),
+        metadata: None,
+    });
+
+    let json = serde_json::to_value(&data_part).unwrap();
+    assert_eq!(json["kind"], "data");
+    assert_eq!(json["data"]["key"], "value");
+    assert_eq!(json["data"]["number"], 42);
+}
+
+#[test]
+fn test_part_conversions() {
+    let texts: Vec<String> = vec!["first".to_string(), "second".to_string()];
+    let message = Message::user("msg-1".to_string(), texts.into_iter().map(Part::from));
+    assert_eq!(message.role, MessageRole::User);
+    assert_eq!(message.parts, vec![Part::from("first"), Part::from("second")]);
+
+    let parts: Vec<Part> = vec![
+        Part::from(DataPart::from(serde_json::json!({"n": 1}))),
+        Part::from(FilePart::from(FileContent::from(FileWithUri {
+            uri: "https://example.com/a.png".to_string(),
+            name: None,
+            mime_type: None,
+        }))),
+    ];
+    let reply = Message::agent("msg-2".to_string(), parts);
+    assert_eq!(serde_json::to_value(&reply.parts[0]).unwrap(), serde_json::json!({"kind": "data", "data": {"n": 1}}));
+
+    let text: Vec<String> = message
+        .parts
+        .into_iter()
+        .filter_map(|part| TextPart::try_from(part).ok())
+        .map(|part| part.text)
+        .collect();
+    assert_eq!(text, ["first", "second"]);
+
+    // A failed conversion hands the part back
+    let part = DataPart::try_from(Part::from("not data")).unwrap_err();
+    assert_eq!(part, Part::Text(TextPart::from("not data")));
+    assert!(FilePart::try_from(reply.parts[1].clone()).is_ok());
+}
+
+#[test]
+fn test_unknown_part_passes_through() {
+    let video = serde_json::json!({
+        "kind": "video",
+        "url": "https://example.com/clip.mp4",
+        "durationMs": 1500,
+        "codecs": {"video": "av1", "audio": null},
+        "metadata": {"vendor": "acme"}
+    });
+    let message: Message = serde_json::from_value(serde_json::json!({
+        "kind": "message",
+        "messageId": "msg-1",
+        "role": "agent",
+        "parts": [{"kind": "text", "text": "Here is the clip"}, video.clone()]
+    }))
+    .unwrap();
+
+    assert!(message.parts[0].is_known());
+    let part = &message.parts[1];
+    assert!(!part.is_known());
+    assert_eq!(part.kind(), "video");
+    assert_eq!(metadata::MetadataExt::get_metadata(part, "vendor"), Some(&serde_json::json!("acme")));
+    match part {
+        Part::Unknown(part) => assert_eq!(part.body["durationMs"], 1500),
+        other => panic!("Expected an unknown part, got {:?}", other),
+    }
+    assert_eq!(serde_json::to_value(part).unwrap(), video);
+
+    // Malformed parts of a known kind still fail
+    let error = serde_json::from_value::<Part>(serde_json::json!({"kind": "text", "body": "x"})).unwrap_err();
+    assert!(error.to_string().contains("missing field `text`"));
+    assert!(serde_json::from_value::<Part>(serde_json::json!({"text": "no kind"})).is_err());
+
+    // The kind may come after the other fields
+    let part: Part = serde_json::from_str(r#"{"text": "late", "metadata": {"n": 1}, "kind": "text"}"#).unwrap();
+    assert_eq!(part, Part::Text(TextPart { text: "late".to_string(), metadata: Some(serde_json::json!({"n": 1})) }));
+    let part: Part = serde_json::from_str(r#"{"durationMs": 1500, "kind": "video", "url": "u"}"#).unwrap();
+    assert_eq!(serde_json::to_value(&part).unwrap(), serde_json::json!({"kind": "video", "durationMs": 1500, "url": "u"}));
+}
+
+#[test]
+fn test_unknown_message_roles() {
+    let json = serde_json::json!({
+        "kind": "task",
+        "id": "task-1",
+        "contextId": "ctx-1",
+        "status": {"state": "auth-required"},
+        "history": [
+            {"kind": "message", "messageId": "msg-1", "role": "user", "parts": [{"kind": "text", "text": "Hi"}]},
+            {"kind": "message", "messageId": "msg-2", "role": "agent", "parts": [{"kind": "text", "text": "Sign in"}]},
+            {"kind": "message", "messageId": "msg-3", "role": "system", "parts": [{"kind": "text", "text": "Be brief"}]}
+        ]
+    });
+    let task: Task = serde_json::from_value(json.clone()).unwrap();
+    let history = task.history.as_ref().unwrap();
+    assert_eq!(history[2].role, MessageRole::Other("system".to_string()));
+    assert_eq!(history[2].role.as_str(), "system");
+    assert_eq!(history[0].role.to_string(), "user");
+    assert_eq!(serde_json::to_value(&task).unwrap(), json);
+
+    // Unknown roles are neither the user nor the agent
+    let info = challenge::AuthRequiredInfo::from_task(&task).unwrap();
+    assert_eq!(info.message_id.as_deref(), Some("msg-2"));
+    let continuation = SendMessageParams::continuation(&task, "msg-4".to_string(), ["Done"]);
+    assert_eq!(metadata::get_meta(&continuation.message.metadata, challenge::IN_REPLY_TO_KEY), Some(&serde_json::json!("msg-2")));
+    let mut with_output = task.clone();
+    with_output.artifacts = Some(vec![Artifact::minimal("a-1".to_string(), vec!["Result".into()])]);
+    let conversation = conversation::Conversation::from_task(&with_output);
+    assert_eq!(conversation.turns[1].artifacts.len(), 1);
+    assert!(conversation.turns[2].artifacts.is_empty());
+    assert!(conversation.to_plain_text().contains("system: Be brief"));
+
+    // Only strings are roles
+    let numeric = r#"{"kind":"message","messageId":"m","role":7,"parts":[]}"#;
+    assert!(serde_json::from_str::<Message>(numeric).is_err());
+}
+
+#[test]
+fn test_file_content_key_presence() {
+    let parse = |file: serde_json::Value| serde_json::from_value::<Part>(serde_json::json!({"kind": "file", "file": file}));
+
+    let bytes = serde_json::json!({"bytes": "aGVsbG8=", "name": "hello.txt", "mimeType": "text/plain"});
+    let Part::File(part) = parse(bytes.clone()).unwrap() else { panic!("expected a file part") };
+    assert_eq!(part.file, FileContent::WithBytes(FileWithBytes {
+        bytes: "aGVsbG8=".to_string(),
+        name: Some("hello.txt".to_string()),
+        mime_type: Some("text/plain".to_string()),
+    }));
+    assert_eq!(serde_json::to_value(&part.file).unwrap(), bytes);
+
+    let uri = serde_json::json!({"uri": "https://example.com/a.png", "futureField": 1});
+    let Part::File(part) = parse(uri).unwrap() else { panic!("expected a file part") };
+    assert!(matches!(&part.file, FileContent::WithUri(file) if file.uri == "https://example.com/a.png" && file.name.is_none()));
+    assert_eq!(serde_json::to_value(&part.file).unwrap(), serde_json::json!({"uri": "https://example.com/a.png"}));
+
+    let both = parse(serde_json::json!({"bytes": "aGVsbG8=", "uri": "https://example.com/a"})).unwrap_err();
+    assert!(both.to_string().contains("both `bytes` and `uri`"), "{}", both);
+    let neither = parse(serde_json::json!({"name": "a.txt"})).unwrap_err();
+    assert!(neither.to_string().contains("neither `bytes` nor `uri`"), "{}", neither);
+    assert!(parse(serde_json::json!({"uri": 7})).is_err());
+}
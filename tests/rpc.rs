@@ -0,0 +1,626 @@
+//! JSON-RPC requests, responses and methods.
+
+use a2a_rs::*;
+
+#[test]
+fn test_create_send_message_request() {
+    let request = SendMessageRequest::new(
+        "1".to_string(),
+        "msg-123".to_string(),
+        "Hello, world!".to_string(),
+        MessageRole::User,
+        None,
+        None,
+    );
+
+    assert_eq!(request.method, RequestMethod::MessageSend);
+    assert_eq!(request.id, JsonRpcId::String("1".to_string()));
+    assert_eq!(request.jsonrpc, "2.0");
+    assert_eq!(request.params.message.kind, "message");
+    assert_eq!(request.params.message.message_id, "msg-123");
+    assert_eq!(request.params.message.role, MessageRole::User);
+    assert_eq!(request.params.message.parts.len(), 1);
+    if let Part::Text(text_part) = &request.params.message.parts[0] {
+        assert_eq!(text_part.text, "Hello, world!");
+    } else {
+        panic!("Expected TextPart");
+    }
+    assert_eq!(request.params.configuration, None);
+    assert_eq!(request.params.metadata, None);
+}
+
+#[test]
+fn test_create_get_task_request() {
+    let request = GetTaskRequest::new("1".to_string(), "task1".to_string());
+
+    assert_eq!(request.method, RequestMethod::TasksGet);
+    assert_eq!(request.id, "1");
+    assert_eq!(request.jsonrpc, "2.0");
+    assert_eq!(request.params.task_id, "task1");
+}
+
+#[test]
+fn test_create_cancel_task_request() {
+    let request = CancelTaskRequest::new("1".to_string(), "task1".to_string());
+
+    assert_eq!(request.method, RequestMethod::TasksCancel);
+    assert_eq!(request.id, "1");
+    assert_eq!(request.jsonrpc, "2.0");
+    assert_eq!(request.params.task_id, "task1");
+}
+
+#[test]
+fn test_request_method_serialization() {
+    // Test MessageSend serialization and deserialization
+    let method = RequestMethod::MessageSend;
+    let serialized = serde_json::to_string(&method).unwrap();
+    assert_eq!(serialized, "\"message/send\"");
+
+    let deserialized: RequestMethod = serde_json::from_str("\"message/send\"").unwrap();
+    assert_eq!(deserialized, RequestMethod::MessageSend);
+
+    // Test backward compatibility for MessageSend
+    let deserialized: RequestMethod = serde_json::from_str("\"sendMessage\"").unwrap();
+    assert_eq!(deserialized, RequestMethod::MessageSend);
+
+    // Test TasksGet serialization and deserialization
+    let method = RequestMethod::TasksGet;
+    let serialized = serde_json::to_string(&method).unwrap();
+    assert_eq!(serialized, "\"tasks/get\"");
+
+    let deserialized: RequestMethod = serde_json::from_str("\"tasks/get\"").unwrap();
+    assert_eq!(deserialized, RequestMethod::TasksGet);
+
+    // Test backward compatibility for TasksGet
+    let deserialized: RequestMethod = serde_json::from_str("\"getTask\"").unwrap();
+    assert_eq!(deserialized, RequestMethod::TasksGet);
+
+    // Test TasksCancel serialization and deserialization
+    let method = RequestMethod::TasksCancel;
+    let serialized = serde_json::to_string(&method).unwrap();
+    assert_eq!(serialized, "\"tasks/cancel\"");
+
+    let deserialized: RequestMethod = serde_json::from_str("\"tasks/cancel\"").unwrap();
+    assert_eq!(deserialized, RequestMethod::TasksCancel);
+
+    // Test backward compatibility for TasksCancel
+    let deserialized: RequestMethod = serde_json::from_str("\"cancelTask\"").unwrap();
+    assert_eq!(deserialized, RequestMethod::TasksCancel);
+
+    // Every legacy alias parses to its method, which serializes with the current name
+    let expected = [
+        ("tasks/send", "message/send"),
+        ("tasks/sendSubscribe", "message/stream"),
+        ("tasks/pushNotification/set", "tasks/pushNotificationConfig/set"),
+        ("tasks/pushNotification/get", "tasks/pushNotificationConfig/get"),
+        ("sendMessage", "message/send"),
+        ("getTask", "tasks/get"),
+        ("cancelTask", "tasks/cancel"),
+    ];
+    assert_eq!(RequestMethod::legacy_aliases().len(), expected.len());
+    for (alias, canonical) in expected {
+        let method: RequestMethod = serde_json::from_value(serde_json::json!(alias)).unwrap();
+        assert_eq!(RequestMethod::from_str(alias), Some(method));
+        assert_eq!(serde_json::to_value(method).unwrap(), canonical);
+        assert!(RequestMethod::is_legacy_alias(alias));
+        assert!(!RequestMethod::is_legacy_alias(canonical));
+    }
+
+    // Test MessageStream serialization and deserialization
+    let method = RequestMethod::MessageStream;
+    let serialized = serde_json::to_string(&method).unwrap();
+    assert_eq!(serialized, "\"message/stream\"");
+
+    let deserialized: RequestMethod = serde_json::from_str("\"message/stream\"").unwrap();
+    assert_eq!(deserialized, RequestMethod::MessageStream);
+
+    // Test TasksPushNotificationConfigSet serialization and deserialization
+    let method = RequestMethod::TasksPushNotificationConfigSet;
+    let serialized = serde_json::to_string(&method).unwrap();
+    assert_eq!(serialized, "\"tasks/pushNotificationConfig/set\"");
+
+    let deserialized: RequestMethod = serde_json::from_str("\"tasks/pushNotificationConfig/set\"").unwrap();
+    assert_eq!(deserialized, RequestMethod::TasksPushNotificationConfigSet);
+}
+
+#[test]
+fn test_message_send_params_spec_compliance() {
+    let message = Message {
+        kind: "message".to_string(),
+        message_id: "msg-456".to_string(),
+        parts: vec![Part::Text(TextPart {
+            text: "Test message".to_string(),
+            metadata: None,
+        })],
+        role: MessageRole::User,
+        context_id: None,
+        extensions: None,
+        metadata: None,
+        reference_task_ids: None,
+        task_id: None,
+        extra: ExtraFields::default(),
+    };
+
+    let send_params = SendMessageParams {
+        message: message.clone(),
+        configuration: Some(MessageSendConfiguration {
+            accepted_output_modes: Some(vec!["text/plain".to_string()]),
+            blocking: Some(true),
+            history_length: Some(10),
+            push_notification_config: Some(PushNotificationConfig {
+                url: "https://example.com/webhook".to_string(),
+                authentication: Some(PushNotificationAuthenticationInfo {
+                    schemes: vec!["Bearer".to_string()],
+                    credentials: Some("token123".to_string()),
+                }),
+                id: Some("webhook-1".to_string()),
+                token: Some("session-token".to_string()),
+            }),
+            extra: ExtraFields::default(),
+        }),
+        metadata: None,
+        extra: ExtraFields::default(),
+    };
+
+    let json = serde_json::to_value(&send_params).unwrap();
+
+    // Validate camelCase field names
+    assert_eq!(json["configuration"]["acceptedOutputModes"][0], "text/plain");
+    assert_eq!(json["configuration"]["historyLength"], 10);
+    assert_eq!(json["configuration"]["pushNotificationConfig"]["url"], "https://example.com/webhook");
+
+    // Test round-trip serialization
+    let serialized = serde_json::to_string(&send_params).unwrap();
+    let deserialized: SendMessageParams = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(send_params, deserialized);
+}
+
+#[test]
+fn test_push_notification_config_spec_compliance() {
+    let config = PushNotificationConfig {
+        url: "https://example.com/webhook".to_string(),
+        authentication: Some(PushNotificationAuthenticationInfo {
+            schemes: vec!["Bearer".to_string(), "Basic".to_string()],
+            credentials: Some("secret-token".to_string()),
+        }),
+        id: Some("notification-1".to_string()),
+        token: Some("session-abc123".to_string()),
+    };
+
+    let json = serde_json::to_value(&config).unwrap();
+
+    // Validate structure matches spec
+    assert_eq!(json["url"], "https://example.com/webhook");
+    assert_eq!(json["authentication"]["schemes"][0], "Bearer");
+    assert_eq!(json["authentication"]["schemes"][1], "Basic");
+    assert_eq!(json["authentication"]["credentials"], "secret-token");
+    assert_eq!(json["id"], "notification-1");
+    assert_eq!(json["token"], "session-abc123");
+
+    // Test round-trip serialization
+    let serialized = serde_json::to_string(&config).unwrap();
+    let deserialized: PushNotificationConfig = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(config, deserialized);
+}
+
+#[test]
+fn test_send_message_request_with_integer_id() {
+    let json_with_int_id = r#"{
+        "id": 123,
+        "jsonrpc": "2.0",
+        "method": "message/send",
+        "params": {
+            "message": {
+                "kind": "message",
+                "messageId": "msg-1",
+                "parts": [{"kind": "text", "text": "Hello"}],
+                "role": "user"
+            }
+        }
+    }"#;
+
+    let result = serde_json::from_str::<SendMessageRequest>(json_with_int_id);
+    assert!(result.is_ok(), "Should parse SendMessageRequest with integer ID");
+
+    let request = result.unwrap();
+    assert_eq!(request.id, JsonRpcId::Integer(123));
+    assert_eq!(request.method, RequestMethod::MessageSend);
+    assert_eq!(request.params.message.message_id, "msg-1");
+}
+
+#[test]
+fn test_send_message_response_error_case() {
+    let error_response_json = r#"{
+        "id": "123",
+        "jsonrpc": "2.0",
+        "error": {
+            "code": -32601,
+            "message": "Method not found"
+        }
+    }"#;
+
+    let result = serde_json::from_str::<SendMessageResponse>(error_response_json);
+    assert!(result.is_ok(), "Should parse SendMessageResponse error case");
+
+    let response = result.unwrap();
+    match response {
+        SendMessageResponse::Error(error_resp) => {
+            assert_eq!(error_resp.id, JsonRpcId::String("123".to_string()));
+            assert_eq!(error_resp.jsonrpc, "2.0");
+        }
+        _ => panic!("Expected error response"),
+    }
+}
+
+#[test]
+fn test_send_message_response_with_task_result() {
+    let task_result_json = r#"{
+        "id": "123",
+        "jsonrpc": "2.0",
+        "result": {
+            "id": "task-1",
+            "kind": "task",
+            "status": {
+                "state": "submitted"
+            },
+            "contextId": "ctx-1"
+        }
+    }"#;
+
+    let result = serde_json::from_str::<SendMessageResponse>(task_result_json);
+    assert!(result.is_ok(), "Should parse SendMessageResponse with Task result");
+
+    let response = result.unwrap();
+    match response {
+        SendMessageResponse::Success(success_resp) => {
+            assert_eq!(success_resp.id, JsonRpcId::String("123".to_string()));
+            assert_eq!(success_resp.jsonrpc, "2.0");
+            match success_resp.result {
+                SendMessageResult::Task(task) => {
+                    assert_eq!(task.id, "task-1");
+                    assert_eq!(task.kind, "task");
+                    assert_eq!(task.context_id, "ctx-1");
+                    assert_eq!(task.status.state, TaskState::Submitted);
+                }
+                _ => panic!("Expected Task result"),
+            }
+        }
+        _ => panic!("Expected success response"),
+    }
+}
+
+#[test]
+fn test_send_message_response_with_message_result() {
+    let message_result_json = r#"{
+        "id": "123",
+        "jsonrpc": "2.0",
+        "result": {
+            "kind": "message",
+            "messageId": "msg-2",
+            "parts": [{"kind": "text", "text": "Response"}],
+            "role": "agent"
+        }
+    }"#;
+
+    let result = serde_json::from_str::<SendMessageResponse>(message_result_json);
+    assert!(result.is_ok(), "Should parse SendMessageResponse with Message result");
+
+    let response = result.unwrap();
+    match response {
+        SendMessageResponse::Success(success_resp) => {
+            assert_eq!(success_resp.id, JsonRpcId::String("123".to_string()));
+            assert_eq!(success_resp.jsonrpc, "2.0");
+            match success_resp.result {
+                SendMessageResult::Message(message) => {
+                    assert_eq!(message.kind, "message");
+                    assert_eq!(message.message_id, "msg-2");
+                    assert_eq!(message.role, MessageRole::Agent);
+                    assert_eq!(message.parts.len(), 1);
+                    if let Part::Text(text_part) = &message.parts[0] {
+                        assert_eq!(text_part.text, "Response");
+                    } else {
+                        panic!("Expected TextPart");
+                    }
+                }
+                _ => panic!("Expected Message result"),
+            }
+        }
+        _ => panic!("Expected success response"),
+    }
+}
+
+#[test]
+fn test_lazy_request_forwards_params_verbatim() {
+    let json = r#"{"jsonrpc":"2.0","id":"req-1","method":"message/send","params":{ "message" : {
+        "kind":"message", "messageId":"msg-1","role":"user",
+        "parts":[ {"kind":"text","text":"Hi"} ] } }}"#;
+
+    let request = helpers::parse_envelope(json).unwrap();
+    assert_eq!(request.method().unwrap(), RequestMethod::MessageSend);
+    assert_eq!(request.id(), &JsonRpcId::String("req-1".to_string()));
+
+    let raw = &json[json.find(r#"{ "message""#).unwrap()..json.len() - 1];
+    assert_eq!(request.params.as_ref().unwrap().get(), raw);
+    assert!(request.forward().unwrap().contains(raw));
+
+    let params: SendMessageParams = request.params_as().unwrap();
+    assert_eq!(params.message.message_id, "msg-1");
+    let params: borrowed::SendMessageParamsRef = request.params_as().unwrap();
+    assert_eq!(params.message.message_id, "msg-1");
+}
+
+#[test]
+fn test_lazy_request_defers_params_errors() {
+    let json = r#"{"jsonrpc":"2.0","id":3,"method":"vendor/custom","params":{"message":42}}"#;
+
+    let request = helpers::parse_envelope(json).unwrap();
+    assert_eq!(request.id(), &JsonRpcId::Integer(3));
+    assert!(matches!(request.method(), Err(A2AError::MethodNotFound(_))));
+    assert_eq!(request.forward().unwrap(), json);
+
+    let error = request.params_as::<SendMessageParams>().unwrap_err();
+    assert!(matches!(error, A2AError::InvalidParams(ref e) if e.code == -32602));
+
+    let request = helpers::parse_envelope(r#"{"jsonrpc":"2.0","id":null,"method":"tasks/get"}"#).unwrap();
+    assert!(matches!(request.params_as::<GetTaskParams>(), Err(A2AError::InvalidParams(_))));
+    assert!(matches!(helpers::parse_envelope("{"), Err(A2AError::JSONParse(_))));
+}
+
+#[test]
+fn test_notifications_get_no_response() {
+    let json = r#"{"jsonrpc":"2.0","method":"message/send","params":{"message":{"kind":"message","messageId":"msg-1","role":"user","parts":[{"kind":"text","text":"ping"}]}}}"#;
+    let incoming = helpers::parse_incoming_request(json).unwrap();
+    assert!(incoming.is_notification());
+    assert_eq!(incoming.request().method(), RequestMethod::MessageSend);
+
+    let mut handled = 0;
+    let mut failures = Vec::new();
+    let mut sink = |request: &A2ARequest, error: A2AError| failures.push((request.method(), error));
+    let output = incoming.dispatch(|_| { handled += 1; Ok(serde_json::Value::Null) }, &mut sink);
+    assert_eq!(output, None);
+    let output = incoming.dispatch(
+        |_| Err::<(), _>(A2AError::TaskNotFound(Box::new(TaskNotFoundError {
+            code: -32001,
+            message: "Task not found".to_string(),
+            data: None,
+        }))),
+        &mut sink,
+    );
+    assert_eq!(output, None);
+    assert_eq!(handled, 1);
+    assert_eq!(failures.len(), 1);
+    assert!(matches!(failures[0], (RequestMethod::MessageSend, A2AError::TaskNotFound(_))));
+
+    let json = r#"{"jsonrpc":"2.0","id":"req-7","method":"tasks/get","params":{"taskId":"task-1"}}"#;
+    let incoming = helpers::parse_incoming_request(json).unwrap();
+    assert!(!incoming.is_notification());
+    let output = incoming.dispatch(|_| Ok(true), &mut |_: &A2ARequest, _| unreachable!()).unwrap();
+    assert_eq!(output, br#"{"id":"req-7","jsonrpc":"2.0","result":true}"#);
+
+    // A result that fails to serialize is answered with an internal error
+    let mut result = std::collections::BTreeMap::new();
+    result.insert(vec![1], "non-string key");
+    let output = incoming.dispatch(|_| Ok(result), &mut |_: &A2ARequest, _| unreachable!()).unwrap();
+    let response: JSONRPCErrorResponse = serde_json::from_slice(&output).unwrap();
+    assert_eq!(response.id, JsonRpcId::String("req-7".to_string()));
+    assert!(matches!(response.error, A2AError::Internal(ref e) if e.code == -32603));
+}
+
+#[test]
+fn test_refused_notifications_get_no_response() {
+    // Bad params: a notification gets no answer, a request gets its ID back
+    let json = r#"{"jsonrpc":"2.0","method":"tasks/get","params":{"taskId":42}}"#;
+    let error = helpers::parse_incoming_request(json).unwrap_err();
+    assert!(error.is_notification());
+    assert!(matches!(error.error, A2AError::InvalidParams(_)));
+    assert_eq!(error.into_response(), None);
+
+    let json = r#"{"jsonrpc":"2.0","id":"req-7","method":"tasks/get","params":{"taskId":42}}"#;
+    let error = helpers::parse_incoming_request(json).unwrap_err();
+    assert_eq!(error.id, Some(JsonRpcId::String("req-7".to_string())));
+    let response: serde_json::Value = serde_json::from_slice(&error.into_response().unwrap()).unwrap();
+    assert_eq!((response["id"].clone(), response["error"]["code"].clone()), ("req-7".into(), (-32602).into()));
+
+    // An unknown method is not answered for a notification either
+    let error = helpers::parse_incoming_request(r#"{"jsonrpc":"2.0","method":"tasks/frobnicate"}"#).unwrap_err();
+    assert!(matches!(error.error, A2AError::MethodNotFound(_)));
+    assert!(error.is_notification());
+
+    // Requests too malformed to tell are answered to the null ID
+    for json in ["{", r#"{"jsonrpc":"2.0","id":{},"method":"tasks/get"}"#, r#"{"jsonrpc":"2.0","method":7}"#] {
+        let error = helpers::parse_incoming_request(json).unwrap_err();
+        assert_eq!(error.id, Some(JsonRpcId::Null), "{}", json);
+        let response: serde_json::Value = serde_json::from_slice(&error.into_response().unwrap()).unwrap();
+        assert_eq!(response["id"], serde_json::Value::Null);
+    }
+}
+
+#[test]
+fn test_request_method_table_is_exhaustive() {
+    // `RequestMethod::ALL` is checked when the crate compiles; this pins
+    // the declaration order and the names on the wire.
+    fn position(method: RequestMethod) -> usize {
+        match method {
+            RequestMethod::MessageSend => 0,
+            RequestMethod::MessageStream => 1,
+            RequestMethod::TasksGet => 2,
+            RequestMethod::TasksCancel => 3,
+            RequestMethod::TasksPushNotificationConfigSet => 4,
+            RequestMethod::TasksPushNotificationConfigGet => 5,
+            RequestMethod::TasksPushNotificationConfigList => 6,
+            RequestMethod::TasksPushNotificationConfigDelete => 7,
+            RequestMethod::TasksResubscribe => 8,
+            #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+            RequestMethod::TasksList => 9,
+            // The enum is non-exhaustive; a new method must be added above
+            _ => panic!("{:?} is missing from this table", method),
+        }
+    }
+
+    let all = RequestMethod::all();
+    assert_eq!(all.len(), if cfg!(any(feature = "spec-0-3", feature = "ext-methods")) { 10 } else { 9 });
+    for (i, method) in all.iter().enumerate() {
+        assert_eq!(position(*method), i);
+        assert_eq!(RequestMethod::from_str(method.as_str()), Some(*method));
+        assert_eq!(serde_json::to_value(method).unwrap(), method.as_str());
+        assert_eq!(serde_json::from_value::<RequestMethod>(method.as_str().into()).unwrap(), *method);
+        assert_eq!(method.expects_params(), method.as_str() != "tasks/list");
+    }
+    for (alias, method) in RequestMethod::LEGACY_ALIASES {
+        assert_eq!(RequestMethod::from_str(alias), Some(*method));
+        assert!(all.iter().all(|m| m.as_str() != *alias));
+    }
+
+    let streaming: Vec<_> = all.iter().filter(|m| m.is_streaming()).collect();
+    assert_eq!(streaming, [&RequestMethod::MessageStream, &RequestMethod::TasksResubscribe]);
+}
+
+#[test]
+fn test_required_capabilities() {
+    let capabilities = AgentCapabilities {
+        streaming: Some(true),
+        ..Default::default()
+    };
+
+    for method in RequestMethod::all() {
+        let supported = method
+            .required_capability()
+            .is_none_or(|capability| capability.is_supported_by(&capabilities));
+        assert_eq!(supported, !method.as_str().starts_with("tasks/pushNotificationConfig/"));
+        assert_eq!(method.is_streaming(), method.required_capability() == Some(Capability::Streaming));
+    }
+
+    let error = Capability::PushNotifications.unsupported_error(RequestMethod::TasksPushNotificationConfigSet);
+    assert!(matches!(error, A2AError::PushNotificationNotSupported(ref e) if e.code == -32003));
+    let error = Capability::Streaming.unsupported_error(RequestMethod::MessageStream);
+    assert!(matches!(error, A2AError::UnsupportedOperation(ref e) if e.code == -32004));
+}
+
+#[test]
+fn test_try_from_value_reports_error_path() {
+    let value = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "message/send",
+        "params": {"message": {"kind": "message", "messageId": 42, "role": "user", "parts": []}}
+    });
+
+    let error = SendMessageRequest::try_from(&value).unwrap_err();
+    match error {
+        A2AError::InvalidParams(e) => {
+            assert_eq!(e.code, -32602);
+            assert_eq!(e.data, Some(serde_json::json!({"path": "params.message.messageId"})));
+        }
+        other => panic!("Expected InvalidParams, got {:?}", other),
+    }
+    assert!(matches!(A2ARequest::try_from(value), Err(A2AError::InvalidParams(_))));
+
+    let value = serde_json::json!({"jsonrpc": "2.0", "id": [], "method": "tasks/get", "params": {"id": "t"}});
+    match A2ARequest::try_from(value).unwrap_err() {
+        A2AError::InvalidRequest(e) => assert_eq!(e.data, Some(serde_json::json!({"path": "id"}))),
+        other => panic!("Expected InvalidRequest, got {:?}", other),
+    }
+
+    let value = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "tasks/archive"});
+    assert!(matches!(A2ARequest::try_from(value), Err(A2AError::MethodNotFound(_))));
+
+    let task = serde_json::json!({"id": "t", "kind": "task", "contextId": "c", "status": {"state": 7}});
+    match Task::try_from(task).unwrap_err() {
+        A2AError::InvalidParams(e) => assert_eq!(e.data, Some(serde_json::json!({"path": "status.state"}))),
+        other => panic!("Expected InvalidParams, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_history_length_tolerates_foreign_numbers() {
+    let request = |history_length: serde_json::Value| {
+        let value = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "message/send",
+            "params": {
+                "message": {"kind": "message", "messageId": "msg-1", "role": "user", "parts": []},
+                "configuration": {"historyLength": history_length}
+            }
+        });
+        SendMessageRequest::try_from(&value).map(|r| r.params.configuration.unwrap())
+    };
+
+    // Other SDKs send integral floats
+    let configuration = request(serde_json::json!(10.0)).unwrap();
+    assert_eq!(configuration.history_length, Some(10));
+    assert_eq!(configuration.history_limit().unwrap(), Some(10));
+    assert!(matches!(request(serde_json::json!(10.5)), Err(A2AError::InvalidParams(_))));
+
+    // Negative lengths parse but are rejected when used
+    let configuration = request(serde_json::json!(-5)).unwrap();
+    match configuration.history_limit().unwrap_err() {
+        A2AError::InvalidParams(e) => assert_eq!(e.message, "historyLength must not be negative, got -5"),
+        other => panic!("Expected InvalidParams, got {:?}", other),
+    }
+
+    // Values beyond the wire type saturate
+    let configuration = request(serde_json::json!(2147483648_u64)).unwrap();
+    assert_eq!(configuration.history_limit().unwrap(), Some(i32::MAX as u32));
+    let configuration = MessageSendConfiguration::default().with_history_length(u32::MAX);
+    assert_eq!(configuration.history_length, Some(i32::MAX));
+
+    // tasks/get takes a count, so negative lengths fail to parse
+    let get = |history_length: serde_json::Value| {
+        let value = serde_json::json!({"jsonrpc": "2.0", "id": "1", "method": "tasks/get",
+            "params": {"taskId": "task-1", "historyLength": history_length}});
+        GetTaskRequest::try_from(&value).map(|r| r.params.history_length)
+    };
+    assert_eq!(get(serde_json::json!(10.0)).unwrap(), Some(10));
+    assert_eq!(get(serde_json::json!(4294967296_u64)).unwrap(), Some(u32::MAX));
+    match get(serde_json::json!(-5)).unwrap_err() {
+        A2AError::InvalidParams(e) => {
+            assert!(e.message.contains("integer `-5`, expected a non-negative integer"), "{}", e.message);
+            assert_eq!(e.data, Some(serde_json::json!({"path": "params.historyLength"})));
+        }
+        other => panic!("Expected InvalidParams, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_send_message_result_message_answers() {
+    let mut message = Message::agent("msg-2".to_string(), [
+        Part::from("Paris"),
+        Part::from(DataPart::from(serde_json::json!({"population": 2102650}))),
+        Part::from("France"),
+    ]);
+    message.context_id = Some("ctx-1".to_string());
+    let result = SendMessageResult::from(message.clone());
+
+    assert_eq!(result.text().as_deref(), Some("Paris\nFrance"));
+    assert_eq!(result.task(), None);
+    assert_eq!(result.task_id(), None);
+    assert_eq!(result.context_id(), Some("ctx-1"));
+    assert!(result.is_final());
+    assert_eq!(result.clone().into_task(), None);
+    assert_eq!(result.into_message(), Some(message));
+}
+
+#[test]
+fn test_send_message_result_task_answers() {
+    let mut ids = ids::SequentialSource::new();
+    let mut task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+    task.status = TaskStatus::with_agent_text_from(&mut ids, TaskState::InputRequired, "task-1", "ctx-1", "Which city?");
+    let result = SendMessageResult::from(task.clone());
+    assert_eq!(result.text().as_deref(), Some("Which city?"));
+    assert_eq!(result.task_id(), Some("task-1"));
+    assert!(!result.is_final());
+    assert_eq!(result.message(), None);
+
+    // The output takes precedence over the status message
+    task.status = TaskStatus::with_agent_text_from(&mut ids, TaskState::Completed, "task-1", "ctx-1", "Done");
+    task.artifacts = Some(vec![Artifact::minimal("a-1".to_string(), vec![Part::from("Paris")])]);
+    let result = SendMessageResult::Task(task.clone());
+    assert_eq!(result.text().as_deref(), Some("Paris"));
+    assert!(result.is_final());
+    assert_eq!(result.clone().into_message(), None);
+    assert_eq!(result.into_task(), Some(task));
+
+    let silent = SendMessageResult::Task(Task::minimal("task-2".to_string(), "ctx-1".to_string()));
+    assert_eq!(silent.text(), None);
+}
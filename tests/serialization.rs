@@ -0,0 +1,320 @@
+//! Wire format: schema examples, unknown fields and defaults.
+
+use a2a_rs::*;
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn test_json_schema_examples_validation() {
+    // Test with realistic JSON examples that match the schema
+
+    // Test Message with all fields
+    let message_json = r#"{
+        "kind": "message",
+        "messageId": "msg-123",
+        "parts": [
+            {
+                "kind": "text",
+                "text": "Hello, world!"
+            }
+        ],
+        "role": "user",
+        "contextId": "ctx-456",
+        "taskId": "task-789"
+    }"#;
+
+    let message: Message = serde_json::from_str(message_json).unwrap();
+    assert_eq!(message.message_id, "msg-123");
+    assert_eq!(message.role, MessageRole::User);
+    assert_eq!(message.context_id, Some("ctx-456".to_string()));
+    assert_eq!(message.task_id, Some("task-789".to_string()));
+
+    // Test round-trip
+    let serialized = serde_json::to_string(&message).unwrap();
+    let deserialized: Message = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(message.message_id, deserialized.message_id);
+
+    // Test AgentCard with comprehensive fields
+    let agent_card_json = r#"{
+        "name": "Test Agent",
+        "description": "A comprehensive test agent",
+        "version": "1.0.0",
+        "protocolVersion": "0.2.5",
+        "url": "https://example.com/agent",
+        "capabilities": {
+            "pushNotifications": true,
+            "stateTransitionHistory": true,
+            "streaming": false
+        },
+        "defaultInputModes": ["text/plain", "application/json"],
+        "defaultOutputModes": ["text/plain", "application/json"],
+        "skills": [
+            {
+                "name": "text_processing",
+                "description": "Process text content",
+                "inputModes": ["text/plain"],
+                "outputModes": ["text/plain"],
+                "examples": ["Analyze this text"]
+            }
+        ]
+    }"#;
+
+    let agent_card: AgentCard = serde_json::from_str(agent_card_json).unwrap();
+    assert_eq!(agent_card.name, "Test Agent");
+    assert_eq!(agent_card.protocol_version, "0.2.5");
+    assert_eq!(agent_card.capabilities.push_notifications, Some(true));
+    assert_eq!(agent_card.skills.len(), 1);
+    assert_eq!(agent_card.skills[0].name, "text_processing");
+}
+
+#[test]
+fn test_performance_serialization_deserialization() {
+    use std::time::Instant;
+
+    // Create a complex AgentCard for performance testing
+    let agent_card = AgentCard::new(
+        "Performance Test Agent".to_string(),
+        "A complex agent for performance testing".to_string(),
+        "1.0.0".to_string(),
+        "https://example.com/agent".to_string(),
+        AgentCapabilities {
+            extensions: Some(vec![
+                AgentExtension {
+                    uri: "https://example.com/ext1".to_string(),
+                    required: Some(true),
+                    description: Some("Extension 1".to_string()),
+                    params: Some(serde_json::json!({"param1": "value1"})),
+                },
+                AgentExtension {
+                    uri: "https://example.com/ext2".to_string(),
+                    required: Some(false),
+                    description: Some("Extension 2".to_string()),
+                    params: Some(serde_json::json!({"param2": "value2"})),
+                },
+            ]),
+            push_notifications: Some(true),
+            state_transition_history: Some(true),
+            streaming: Some(true),
+        },
+        vec!["text/plain".to_string(), "application/json".to_string(), "image/png".to_string()],
+        vec!["text/plain".to_string(), "application/json".to_string(), "image/png".to_string()],
+        vec![
+            AgentSkill::minimal("text_processing".to_string(), "Advanced text processing capabilities".to_string())
+                .with_input_modes(vec!["text/plain".to_string(), "text/markdown".to_string()])
+                .with_output_modes(vec!["text/plain".to_string(), "text/html".to_string()])
+                .with_examples(vec![
+                    "Analyze sentiment".to_string(),
+                    "Extract entities".to_string(),
+                    "Summarize content".to_string(),
+                ]),
+            AgentSkill::minimal("image_processing".to_string(), "Image analysis and processing".to_string())
+                .with_input_modes(vec!["image/png".to_string(), "image/jpeg".to_string()])
+                .with_output_modes(vec!["application/json".to_string()])
+                .with_examples(vec![
+                    "Detect objects".to_string(),
+                    "Extract text".to_string(),
+                ]),
+        ],
+    );
+
+    // Performance test: Serialize 1000 times
+    let start = Instant::now();
+    for _ in 0..1000 {
+        let _serialized = serde_json::to_string(&agent_card).unwrap();
+    }
+    let serialize_duration = start.elapsed();
+
+    // Performance test: Deserialize 1000 times
+    let serialized = serde_json::to_string(&agent_card).unwrap();
+    let start = Instant::now();
+    for _ in 0..1000 {
+        let _deserialized: AgentCard = serde_json::from_str(&serialized).unwrap();
+    }
+    let deserialize_duration = start.elapsed();
+
+    // Basic performance assertions (should complete within reasonable time)
+    assert!(serialize_duration.as_millis() < 1000, "Serialization took too long: {:?}", serialize_duration);
+    assert!(deserialize_duration.as_millis() < 1000, "Deserialization took too long: {:?}", deserialize_duration);
+
+    println!("Performance test results:");
+    println!("  Serialization (1000x): {:?}", serialize_duration);
+    println!("  Deserialization (1000x): {:?}", deserialize_duration);
+}
+
+/// Parse `json` as `T`, check that unknown fields were captured, and
+/// check that serializing again reproduces the input exactly.
+fn assert_lossless<T>(json: serde_json::Value)
+where
+    T: Serialize + for<'de> Deserialize<'de>,
+{
+    let parsed: T = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&parsed).unwrap(), json);
+}
+
+#[test]
+fn test_unknown_fields_round_trip() {
+    let message = serde_json::json!({
+        "kind": "message",
+        "messageId": "msg-1",
+        "parts": [{"kind": "text", "text": "Hello"}],
+        "role": "user",
+        "futureField": {"nested": [1, 2, 3]}
+    });
+    let status = serde_json::json!({
+        "state": "working",
+        "message": message,
+        "x-vendor-progress": 0.5
+    });
+    let artifact = serde_json::json!({
+        "artifactId": "artifact-1",
+        "parts": [{"kind": "text", "text": "Bonjour"}],
+        "revision": 3
+    });
+
+    assert_lossless::<Message>(message.clone());
+    assert_lossless::<TaskStatus>(status.clone());
+    assert_lossless::<Artifact>(artifact.clone());
+    assert_lossless::<Task>(serde_json::json!({
+        "id": "task-1",
+        "kind": "task",
+        "contextId": "ctx-1",
+        "status": status,
+        "artifacts": [artifact],
+        "priority": "high"
+    }));
+    assert_lossless::<TaskStatusUpdateEvent>(serde_json::json!({
+        "kind": "status-update",
+        "taskId": "task-1",
+        "contextId": "ctx-1",
+        "status": {"state": "completed", "reason": "done"},
+        "final": true,
+        "sequence": 7
+    }));
+    assert_lossless::<TaskArtifactUpdateEvent>(serde_json::json!({
+        "kind": "artifact-update",
+        "taskId": "task-1",
+        "contextId": "ctx-1",
+        "artifact": artifact,
+        "chunkIndex": 0
+    }));
+    assert_lossless::<AgentCard>(serde_json::json!({
+        "name": "Test Agent",
+        "description": "A test agent",
+        "version": "1.0.0",
+        "protocolVersion": PROTOCOL_VERSION,
+        "url": "https://example.com/agent",
+        "capabilities": {},
+        "defaultInputModes": ["text/plain"],
+        "defaultOutputModes": ["text/plain"],
+        "skills": [],
+        "signatures": [{"protected": "abc", "signature": "def"}]
+    }));
+    assert_lossless::<SendMessageRequest>(serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "message/send",
+        "params": {
+            "message": message,
+            "configuration": {
+                "acceptedOutputModes": ["text/plain"],
+                "blocking": true,
+                "timeoutMs": 500
+            },
+            "tenant": "acme"
+        },
+        "trace": "t-1"
+    }));
+    assert_lossless::<GetTaskRequest>(serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": "req-1",
+        "method": "tasks/get",
+        "params": {"taskId": "task-1", "includeArtifacts": false},
+        "trace": "t-1"
+    }));
+    assert_lossless::<TaskIdParams>(serde_json::json!({"id": "task-1", "reason": "user abort"}));
+}
+
+#[test]
+fn test_empty_extra_fields_are_not_serialized() {
+    let request = SendMessageRequest::new(
+        "req-1".to_string(),
+        "msg-1".to_string(),
+        "Hello".to_string(),
+        MessageRole::User,
+        None,
+        None,
+    );
+
+    let json = serde_json::to_string(&request).unwrap();
+    assert!(!json.contains("extra"));
+}
+
+#[test]
+fn test_extra_fields_ignored_by_equality_and_validation() {
+    let plain: TaskStatus = serde_json::from_value(serde_json::json!({"state": "working"})).unwrap();
+    let extended: TaskStatus =
+        serde_json::from_value(serde_json::json!({"state": "working", "progress": 0.5})).unwrap();
+
+    assert_eq!(extended.extra.get("progress"), Some(&serde_json::json!(0.5)));
+    assert_eq!(plain.extra, extended.extra);
+
+    let event: TaskStatusUpdateEvent = serde_json::from_value(serde_json::json!({
+        "kind": "status-update",
+        "taskId": "task-1",
+        "contextId": "ctx-1",
+        "status": {"state": "working"},
+        "final": false,
+        "sequence": 7
+    }))
+    .unwrap();
+    assert!(event.validate().is_ok());
+}
+
+#[test]
+fn test_defaults_serialize_minimally() {
+    assert_eq!(serde_json::to_value(TaskStatus::default()).unwrap(), serde_json::json!({"state": "unknown"}));
+    assert_eq!(serde_json::to_value(AgentCapabilities::default()).unwrap(), serde_json::json!({}));
+    assert_eq!(serde_json::to_value(MessageSendConfiguration::default()).unwrap(), serde_json::json!({}));
+    assert_eq!(serde_json::to_value(OAuth2Flows::default()).unwrap(), serde_json::json!({}));
+    assert_eq!(
+        serde_json::to_value(PushNotificationAuthenticationInfo::default()).unwrap(),
+        serde_json::json!({"schemes": []})
+    );
+
+    let capabilities = AgentCapabilities {
+        streaming: Some(true),
+        ..Default::default()
+    };
+    assert_eq!(serde_json::to_value(capabilities).unwrap(), serde_json::json!({"streaming": true}));
+}
+
+#[test]
+fn test_minimal_constructors() {
+    let part = Part::Text(TextPart {
+        text: "hi".to_string(),
+        metadata: None,
+    });
+
+    let message = Message::minimal("msg-1".to_string(), MessageRole::User, vec![part.clone()]);
+    assert_eq!(
+        serde_json::to_value(&message).unwrap(),
+        serde_json::json!({"kind": "message", "messageId": "msg-1", "role": "user", "parts": [{"kind": "text", "text": "hi"}]})
+    );
+
+    let task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+    assert_eq!(
+        serde_json::to_value(&task).unwrap(),
+        serde_json::json!({"id": "task-1", "kind": "task", "contextId": "ctx-1", "status": {"state": "unknown"}})
+    );
+
+    let artifact = Artifact::minimal("art-1".to_string(), vec![part]);
+    assert_eq!(
+        serde_json::to_value(&artifact).unwrap(),
+        serde_json::json!({"artifactId": "art-1", "parts": [{"kind": "text", "text": "hi"}]})
+    );
+
+    let skill = AgentSkill::minimal("echo".to_string(), "Echoes input".to_string());
+    assert_eq!(
+        serde_json::to_value(&skill).unwrap(),
+        serde_json::json!({"name": "echo", "description": "Echoes input"})
+    );
+}
@@ -0,0 +1,127 @@
+//! SQLite-backed tests for `SqlTaskStore`.
+
+#![cfg(feature = "sqlx")]
+
+use a2a_rs::ids::{ContextId, TaskId};
+use a2a_rs::store::sql::SqlTaskStore;
+use a2a_rs::store::{TaskStore, is_conflict};
+use a2a_rs::{A2AError, Task, TaskState};
+use std::path::PathBuf;
+
+struct TempDatabase(PathBuf);
+
+impl TempDatabase {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("a2a-rs-{}-{}.db", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        Self(path)
+    }
+
+    fn url(&self) -> String {
+        format!("sqlite://{}?mode=rwc", self.0.display())
+    }
+}
+
+impl Drop for TempDatabase {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+fn task(id: &str, context_id: &str) -> Task {
+    Task::minimal(id.to_string(), context_id.to_string())
+}
+
+#[tokio::test]
+async fn test_crud_and_unknown_fields() {
+    let db = TempDatabase::new("crud");
+    let store = SqlTaskStore::connect(&db.url()).await.unwrap();
+    // Migrations are idempotent
+    store.migrate().await.unwrap();
+
+    let mut with_extra: Task = serde_json::from_value(serde_json::json!({
+        "kind": "task",
+        "id": "task-1",
+        "contextId": "ctx-1",
+        "status": {"state": "submitted"},
+        "futureField": [1, 2, 3]
+    }))
+    .unwrap();
+    with_extra.metadata = Some(serde_json::json!({"owner": "test"}));
+
+    let created = store.create_task(with_extra).await.unwrap();
+    let loaded = store.get_task(&TaskId::from("task-1")).await.unwrap();
+    assert_eq!(loaded, created);
+    assert_eq!(loaded.updated_at, created.updated_at);
+    assert_eq!(serde_json::to_value(&loaded).unwrap()["futureField"], serde_json::json!([1, 2, 3]));
+
+    assert!(is_conflict(&store.create_task(task("task-1", "ctx-1")).await.unwrap_err()));
+
+    store.delete_task(&TaskId::from("task-1")).await.unwrap();
+    assert!(matches!(store.get_task(&TaskId::from("task-1")).await, Err(A2AError::TaskNotFound(_))));
+    assert!(matches!(store.delete_task(&TaskId::from("task-1")).await, Err(A2AError::TaskNotFound(_))));
+    assert!(matches!(store.update_task(loaded).await, Err(A2AError::TaskNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_list_by_context_paginates() {
+    let db = TempDatabase::new("list");
+    let store = SqlTaskStore::connect(&db.url()).await.unwrap();
+
+    for n in 0..5 {
+        store.create_task(task(&format!("task-{}", n), "ctx-a")).await.unwrap();
+    }
+    store.create_task(task("task-other", "ctx-b")).await.unwrap();
+
+    let context = ContextId::from("ctx-a");
+    let first = store.list_tasks_by_context(&context, None, 2).await.unwrap();
+    assert!(!first.is_last());
+    let rest = store.list_tasks_by_context(&context, first.next_cursor.as_deref(), 10).await.unwrap();
+    assert!(rest.is_last());
+    let ids: Vec<_> = first.items.iter().chain(&rest.items).map(|t| t.id.as_str()).collect();
+    assert_eq!(ids, ["task-0", "task-1", "task-2", "task-3", "task-4"]);
+
+    let exact = store.list_tasks_by_context(&context, None, 5).await.unwrap();
+    assert_eq!(exact.items.len(), 5);
+    assert!(exact.is_last());
+}
+
+#[tokio::test]
+async fn test_concurrent_updates_conflict() {
+    let db = TempDatabase::new("conflict");
+    let store = SqlTaskStore::connect(&db.url()).await.unwrap();
+    let created = store.create_task(task("task-1", "ctx-1")).await.unwrap();
+
+    // Every writer read the same version, so exactly one may win
+    let writers: Vec<_> = (0..8)
+        .map(|n| {
+            let store = store.clone();
+            let mut task = created.clone();
+            tokio::spawn(async move {
+                task.metadata = Some(serde_json::json!({"writer": n}));
+                task.status.state = TaskState::Working;
+                store.update_task(task).await
+            })
+        })
+        .collect();
+
+    let mut winners = Vec::new();
+    for writer in writers {
+        match writer.await.unwrap() {
+            Ok(task) => winners.push(task),
+            Err(e) => assert!(is_conflict(&e), "unexpected error: {:?}", e),
+        }
+    }
+    assert_eq!(winners.len(), 1);
+
+    let stored = store.get_task(&TaskId::from("task-1")).await.unwrap();
+    assert_eq!(stored, winners[0]);
+    assert_eq!(stored.status.state, TaskState::Working);
+    assert_eq!(stored.created_at, created.created_at);
+
+    // A retry based on the winning version succeeds
+    let mut retry = stored.clone();
+    retry.status.state = TaskState::Completed;
+    assert!(store.update_task(retry).await.is_ok());
+    assert!(is_conflict(&store.update_task(stored).await.unwrap_err()));
+}
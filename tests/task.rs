@@ -0,0 +1,495 @@
+//! Tasks, their status, history and artifacts.
+
+use a2a_rs::*;
+
+// Struct literals of the event types need them exhaustive
+#[cfg(not(feature = "strict-types"))]
+#[test]
+fn test_task_status_spec_compliance() {
+    let message = Message {
+        kind: "message".to_string(),
+        message_id: "status-msg".to_string(),
+        parts: vec![Part::Text(TextPart {
+            text: "Task is working".to_string(),
+            metadata: None,
+        })],
+        role: MessageRole::Agent,
+        context_id: None,
+        extensions: None,
+        metadata: None,
+        reference_task_ids: None,
+        task_id: None,
+        extra: ExtraFields::default(),
+    };
+
+    let task_status = TaskStatus {
+        state: TaskState::Working,
+        message: Some(message),
+        timestamp: Some("2023-10-27T10:00:00Z".to_string()),
+        extra: ExtraFields::default(),
+    };
+
+    let json = serde_json::to_value(&task_status).unwrap();
+
+    // Validate TaskState serialization uses kebab-case
+    assert_eq!(json["state"], "working");
+    assert_eq!(json["timestamp"], "2023-10-27T10:00:00Z");
+    assert!(json["message"].is_object());
+
+    // Test all TaskState variants
+    let states = vec![
+        (TaskState::Submitted, "submitted"),
+        (TaskState::Working, "working"),
+        (TaskState::InputRequired, "input-required"),
+        (TaskState::Completed, "completed"),
+        (TaskState::Canceled, "canceled"),
+        (TaskState::Failed, "failed"),
+        (TaskState::Rejected, "rejected"),
+        (TaskState::AuthRequired, "auth-required"),
+        (TaskState::Unknown, "unknown"),
+    ];
+
+    for (state, expected_json) in states {
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(json, format!("\"{}\"", expected_json));
+
+        let deserialized: TaskState = serde_json::from_str(&json).unwrap();
+        assert_eq!(std::mem::discriminant(&state), std::mem::discriminant(&deserialized));
+    }
+}
+
+#[test]
+fn test_artifact_spec_compliance() {
+    let artifact = Artifact {
+        artifact_id: "artifact-123".to_string(),
+        parts: vec![Part::Text(TextPart {
+            text: "Generated content".to_string(),
+            metadata: None,
+        })],
+        description: Some("Test artifact".to_string()),
+        extensions: Some(vec!["https://example.com/extension".to_string()]),
+        metadata: Some(serde_json::json!({"source": "agent"})),
+        name: Some("test-artifact.txt".to_string()),
+        extra: ExtraFields::default(),
+    };
+
+    let json = serde_json::to_value(&artifact).unwrap();
+
+    // Validate required fields
+    assert_eq!(json["artifactId"], "artifact-123");
+    assert!(json["parts"].is_array());
+    assert_eq!(json["parts"][0]["kind"], "text");
+    assert_eq!(json["parts"][0]["text"], "Generated content");
+
+    // Validate optional fields
+    assert_eq!(json["description"], "Test artifact");
+    assert_eq!(json["extensions"][0], "https://example.com/extension");
+    assert_eq!(json["metadata"]["source"], "agent");
+    assert_eq!(json["name"], "test-artifact.txt");
+
+    // Test round-trip serialization
+    let serialized = serde_json::to_string(&artifact).unwrap();
+    let deserialized: Artifact = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(artifact, deserialized);
+}
+
+#[test]
+fn test_checked_api_rejects_invalid_combinations() {
+    let working = TaskStatus::new(TaskState::Working);
+    let completed = TaskStatus::new(TaskState::Completed);
+
+    // A final event in a state that does not end the stream cannot be built or reached
+    let error = TaskStatusUpdateEvent::try_new("t".to_string(), "c".to_string(), working.clone(), true).unwrap_err();
+    assert!(error.starts_with("Final status update events"));
+    let mut event = TaskStatusUpdateEvent::try_new("t".to_string(), "c".to_string(), working.clone(), false).unwrap();
+    assert!(event.set_final_checked(true).is_err());
+    assert!(!event.final_event);
+    event.set_status_checked(completed.clone()).unwrap();
+    event.set_final_checked(true).unwrap();
+    assert!(event.set_status_checked(working.clone()).is_err());
+    assert_eq!(event.status.state, TaskState::Completed);
+    assert!(event.validate().is_ok());
+
+    assert!(TaskArtifactUpdateEvent::try_new("t".to_string(), "c".to_string(), Artifact::minimal("a".to_string(), vec![])).is_err());
+
+    // Tasks only move along valid transitions
+    let mut task = Task::minimal("t".to_string(), "c".to_string());
+    task.set_status_checked(working.clone()).unwrap();
+    task.set_status_checked(working.clone()).unwrap();
+    task.set_status_checked(completed).unwrap();
+    assert!(task.set_status_checked(working).is_err());
+    assert_eq!(task.status.state, TaskState::Completed);
+    let states: Vec<_> = task.status_history.iter().flatten().map(|status| status.state.clone()).collect();
+    assert_eq!(states, [TaskState::Unknown, TaskState::Working]);
+}
+
+#[test]
+fn test_state_transition_table() {
+    assert_eq!(TaskState::ALL.len(), 9);
+    let terminal: Vec<_> = TaskState::ALL.iter().filter(|state| state.is_terminal()).collect();
+    assert_eq!(terminal, [&TaskState::Completed, &TaskState::Canceled, &TaskState::Failed, &TaskState::Rejected]);
+
+    for from in TaskState::ALL {
+        let json = serde_json::to_value(from).unwrap();
+        assert_eq!(&serde_json::from_value::<TaskState>(json).unwrap(), from);
+        for to in TaskState::ALL {
+            let valid = validation::validate_task_state_transition(from, to).is_ok();
+            assert_eq!(valid, from.successors().contains(to), "{:?} -> {:?}", from, to);
+        }
+        // Every state but unknown itself can be reached from unknown
+        assert_eq!(TaskState::Unknown.successors().contains(from), *from != TaskState::Unknown);
+    }
+}
+
+#[test]
+fn test_status_history_queries() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let at = |secs: u64| clock::format_time(UNIX_EPOCH + Duration::from_secs(secs));
+    let status = |state: TaskState, timestamp: Option<String>| {
+        let mut status = TaskStatus::new(state);
+        status.timestamp = timestamp;
+        status
+    };
+    let mut task = Task::minimal("t".to_string(), "c".to_string());
+    task.status = status(TaskState::Completed, Some(at(100)));
+    task.status_history = Some(vec![
+        status(TaskState::Working, Some(at(10))),
+        status(TaskState::Unknown, None),
+        status(TaskState::Submitted, Some(at(0))),
+        status(TaskState::InputRequired, Some("2025-13-01T00:00:00Z".to_string())),
+        status(TaskState::InputRequired, Some(at(40))),
+        status(TaskState::Working, Some(at(60))),
+    ]);
+
+    // Timed entries in time order, then the rest in arrival order
+    let sorted: Vec<_> = task.status_history_sorted().into_iter().map(|status| status.state.clone()).collect();
+    assert_eq!(sorted, [
+        TaskState::Submitted,
+        TaskState::Working,
+        TaskState::InputRequired,
+        TaskState::Working,
+        TaskState::Unknown,
+        TaskState::InputRequired,
+    ]);
+
+    assert_eq!(task.time_in_state(&TaskState::Working), Some(Duration::from_secs(70)));
+    assert_eq!(task.time_in_state(&TaskState::InputRequired), Some(Duration::from_secs(20)));
+    assert_eq!(task.time_in_state(&TaskState::Completed), None);
+    assert_eq!(task.time_in_state(&TaskState::Failed), None);
+
+    assert_eq!(task.state_at(UNIX_EPOCH + Duration::from_secs(45)), Some(&TaskState::InputRequired));
+    assert_eq!(task.state_at(UNIX_EPOCH + Duration::from_secs(10)), Some(&TaskState::Working));
+    assert_eq!(task.state_at(UNIX_EPOCH + Duration::from_secs(500)), Some(&TaskState::Completed));
+    assert_eq!(task.state_at(UNIX_EPOCH - Duration::from_secs(1)), None);
+
+    // Tasks changed through the checked setter are already in order
+    let mut task = Task::minimal("t".to_string(), "c".to_string());
+    task.status = status(TaskState::Submitted, Some(at(1)));
+    for (state, secs) in [(TaskState::Working, 2), (TaskState::Completed, 5)] {
+        task.set_status_checked(status(state, Some(at(secs)))).unwrap();
+    }
+    let raw: Vec<_> = task.status_history.iter().flatten().collect();
+    assert_eq!(task.status_history_sorted(), raw);
+    assert_eq!(task.time_in_state(&TaskState::Working), Some(Duration::from_secs(3)));
+}
+
+#[test]
+fn test_task_id_params_spec_compliance() {
+    let params = TaskIdParams {
+        id: "task-xyz".to_string(),
+        metadata: Some(serde_json::json!({"priority": "high"})),
+        extra: ExtraFields::default(),
+    };
+
+    let json = serde_json::to_value(&params).unwrap();
+
+    // Validate required fields
+    assert_eq!(json["id"], "task-xyz");
+
+    // Validate optional fields
+    assert_eq!(json["metadata"]["priority"], "high");
+
+    // Test round-trip serialization
+    let serialized = serde_json::to_string(&params).unwrap();
+    let deserialized: TaskIdParams = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(params, deserialized);
+}
+
+// Struct literals of the event types need them exhaustive
+#[cfg(not(feature = "strict-types"))]
+#[test]
+fn test_updated_task_spec_compliance() {
+    let message = Message {
+        kind: "message".to_string(),
+        message_id: "msg-456".to_string(),
+        parts: vec![Part::Text(TextPart {
+            text: "Task progress update".to_string(),
+            metadata: None,
+        })],
+        role: MessageRole::Agent,
+        context_id: None,
+        extensions: None,
+        metadata: None,
+        reference_task_ids: None,
+        task_id: None,
+        extra: ExtraFields::default(),
+    };
+
+    let artifact = Artifact {
+        artifact_id: "artifact-789".to_string(),
+        parts: vec![Part::Text(TextPart {
+            text: "Task output".to_string(),
+            metadata: None,
+        })],
+        description: None,
+        extensions: None,
+        metadata: None,
+        name: None,
+        extra: ExtraFields::default(),
+    };
+
+    let task = Task {
+        id: "task-123".to_string(),
+        kind: "task".to_string(),
+        status: TaskStatus {
+            state: TaskState::Completed,
+            message: Some(message.clone()),
+            timestamp: Some("2023-10-27T10:00:00Z".to_string()),
+            extra: ExtraFields::default(),
+        },
+        context_id: "ctx-456".to_string(),
+        artifacts: Some(vec![artifact]),
+        history: Some(vec![message]),
+        metadata: Some(serde_json::json!({"priority": "normal"})),
+        result: Some(serde_json::json!({"success": true})),
+        error: None,
+        created_at: Some("2023-10-27T09:00:00Z".to_string()),
+        updated_at: Some("2023-10-27T10:00:00Z".to_string()),
+        status_history: None,
+        extra: ExtraFields::default(),
+    };
+
+    let json = serde_json::to_value(&task).unwrap();
+
+    // Validate required fields
+    assert_eq!(json["id"], "task-123");
+    assert_eq!(json["kind"], "task");
+    assert_eq!(json["contextId"], "ctx-456");
+    assert!(json["status"].is_object());
+    assert_eq!(json["status"]["state"], "completed");
+
+    // Validate optional fields
+    assert!(json["artifacts"].is_array());
+    assert_eq!(json["artifacts"][0]["artifactId"], "artifact-789");
+    assert!(json["history"].is_array());
+    assert_eq!(json["history"][0]["messageId"], "msg-456");
+    assert_eq!(json["metadata"]["priority"], "normal");
+    assert_eq!(json["result"]["success"], true);
+    assert_eq!(json["createdAt"], "2023-10-27T09:00:00Z");
+    assert_eq!(json["updatedAt"], "2023-10-27T10:00:00Z");
+
+    // Test round-trip serialization
+    let serialized = serde_json::to_string(&task).unwrap();
+    let deserialized: Task = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(task, deserialized);
+}
+
+#[test]
+fn test_task_history_page_is_stable_across_appends() {
+    let mut task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+    task.history = Some((1..=5).map(|n| Message::user(format!("msg-{}", n), [format!("hi {}", n)])).collect());
+
+    let ids = |page: &crate::page::Page<&Message>| page.items.iter().map(|m| m.message_id.clone()).collect::<Vec<_>>();
+
+    let first = task.history_page(None, 2).unwrap();
+    assert_eq!(ids(&first), ["msg-1", "msg-2"]);
+
+    // Appending between pages neither repeats nor skips messages
+    let cursor = first.next_cursor.clone().unwrap();
+    let history = task.history.as_mut().unwrap();
+    history.push(Message::agent("msg-6".to_string(), ["reply"]));
+    history.push(Message::user("msg-7".to_string(), ["more"]));
+
+    let second = task.history_page(Some(&cursor), 2).unwrap();
+    assert_eq!(ids(&second), ["msg-3", "msg-4"]);
+    let third = task.history_page(second.next_cursor.as_deref(), 10).unwrap();
+    assert_eq!(ids(&third), ["msg-5", "msg-6", "msg-7"]);
+    assert!(third.is_last());
+
+    // Cursors still resolve after older messages are trimmed
+    task.history.as_mut().unwrap().drain(..2);
+    assert_eq!(ids(&task.history_page(Some(&cursor), 1).unwrap()), ["msg-3"]);
+
+    assert!(matches!(task.history_page(Some("bogus"), 1), Err(A2AError::InvalidParams(_))));
+    assert!(Task::minimal("t".to_string(), "c".to_string()).history_page(None, 5).unwrap().items.is_empty());
+}
+
+#[test]
+fn test_task_from_message_links_ids() {
+    let mut ids = ids::SequentialSource::new();
+
+    let task = Task::from_message(Message::user("msg-a".to_string(), ["Book a flight"]), &mut ids);
+    assert_eq!((task.id.as_str(), task.context_id.as_str()), ("task-1", "ctx-1"));
+    assert_eq!(task.status.state, TaskState::Submitted);
+    assert!(task.status.timestamp.is_some());
+    let history = task.history.as_ref().unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].message_id, "msg-a");
+    assert_eq!(history[0].task_id.as_deref(), Some("task-1"));
+    assert_eq!(history[0].context_id.as_deref(), Some("ctx-1"));
+
+    // The message's own context is adopted, a named task replaced
+    let mut message = Message::user("msg-b".to_string(), ["And a hotel"]);
+    message.context_id = Some("ctx-trip".to_string());
+    message.task_id = Some("task-old".to_string());
+    let task = Task::from_message(message, &mut ids as &mut dyn ids::IdSource);
+    assert_eq!((task.id.as_str(), task.context_id.as_str()), ("task-2", "ctx-trip"));
+    let message = &task.history.as_ref().unwrap()[0];
+    assert_eq!(message.task_id.as_deref(), Some("task-2"));
+    assert_eq!(message.context_id.as_deref(), Some("ctx-trip"));
+
+    // Acknowledgements and status messages mint from the source too
+    let ack = task.to_ack_message_with(&mut ids);
+    assert_eq!(ack.message_id, "msg-1");
+    assert_eq!(ack.role, MessageRole::Agent);
+    assert_eq!(ack.task_id.as_deref(), Some("task-2"));
+    assert_eq!(ack.context_id.as_deref(), Some("ctx-trip"));
+    assert!(ack.validate().is_ok());
+    let status = TaskStatus::with_agent_text_from(&mut ids, TaskState::Working, &task.id, &task.context_id, "On it");
+    assert_eq!(status.message.unwrap().message_id, "msg-2");
+
+    // The `_at` variants stamp a given time instead of the current one
+    let time = std::time::UNIX_EPOCH + std::time::Duration::from_secs(90);
+    let later = Task::from_message_at(Message::user("msg-c".to_string(), ["Or a train"]), &mut ids, time);
+    assert_eq!(later.status.timestamp.as_deref(), Some("1970-01-01T00:01:30.000000Z"));
+    let status =
+        TaskStatus::with_agent_text_from_at(&mut ids, TaskState::Working, &later.id, &later.context_id, "On it", time);
+    assert_eq!(status.timestamp.as_deref(), Some("1970-01-01T00:01:30.000000Z"));
+    assert_eq!(status.message.unwrap().message_id, "msg-3");
+
+    // With the `uuid` feature the ID can be left to the crate
+    #[cfg(feature = "uuid")]
+    {
+        let ack = task.to_ack_message();
+        assert_eq!(ack.task_id.as_deref(), Some("task-2"));
+        assert_ne!(ack.message_id, task.to_ack_message().message_id);
+    }
+}
+
+#[test]
+fn test_duplicated_message_ids_in_history() {
+    let question = Message::user("msg-1".to_string(), ["Book a flight"]);
+    let mut edited = question.clone();
+    edited.parts = vec!["Book a train".into()];
+    let mut task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+
+    // An echoed message is skipped unless duplicates are kept
+    assert!(task.add_to_history(question.clone(), DuplicateMessages::SkipIdentical));
+    assert!(!task.add_to_history(question.clone(), DuplicateMessages::SkipIdentical));
+    assert!(task.add_to_history(edited.clone(), DuplicateMessages::SkipIdentical));
+    assert!(task.add_to_history(question.clone(), DuplicateMessages::Keep));
+    assert_eq!(task.history.as_ref().unwrap().len(), 3);
+
+    let answer = Message::agent("msg-2".to_string(), ["Where to?"]);
+    task.add_to_history(answer.clone(), DuplicateMessages::Keep);
+    task.add_to_history(answer.clone(), DuplicateMessages::Keep);
+    assert_eq!(task.dedup_history(), 2);
+    assert_eq!(task.history, Some(vec![question, edited, answer]));
+    assert_eq!(task.dedup_history(), 0);
+    assert_eq!(Task::minimal("t".to_string(), "c".to_string()).dedup_history(), 0);
+}
+
+#[test]
+fn test_task_view_matches_truncated_clone() {
+    let mut task: Task = serde_json::from_value(serde_json::json!({
+        "id": "task-1",
+        "kind": "task",
+        "contextId": "ctx-1",
+        "status": {"state": "working"},
+        "artifacts": [{"artifactId": "a-1", "parts": [{"kind": "text", "text": "draft"}]}],
+        "metadata": {"priority": "high"},
+        "createdAt": "2025-01-31T12:00:00Z",
+        "statusHistory": [{"state": "submitted"}],
+        "x-vendor": {"shard": 3}
+    }))
+    .unwrap();
+    task.history = Some((1..=5).map(|n| Message::user(format!("msg-{}", n), [format!("hi {}", n)])).collect());
+
+    for length in [None, Some(0), Some(2), Some(5), Some(50)] {
+        let mut truncated = task.clone();
+        let history = truncated.history.as_mut().unwrap();
+        history.drain(..history.len().saturating_sub(length.map_or(usize::MAX, |n| n as usize)));
+        let view = TaskView::with_history_length(&task, length);
+        assert_eq!(serde_json::to_string(&view).unwrap(), serde_json::to_string(&truncated).unwrap());
+
+        truncated.artifacts = None;
+        let view = view.without_artifacts();
+        assert_eq!(serde_json::to_string(&view).unwrap(), serde_json::to_string(&truncated).unwrap());
+    }
+
+    let mut window = Vec::new();
+    task.serialize_history_window(1..3, &mut window).unwrap();
+    let window: Task = serde_json::from_slice(&window).unwrap();
+    let ids: Vec<&str> = window.history.iter().flatten().map(|m| m.message_id.as_str()).collect();
+    assert_eq!(ids, ["msg-2", "msg-3"]);
+    assert_eq!(window.extra.get("x-vendor"), Some(&serde_json::json!({"shard": 3})));
+
+    // Out of range windows are clamped, a missing history stays missing
+    let mut view = TaskView::new(&task);
+    view.history_range = 4..9;
+    assert_eq!(view.history().unwrap().len(), 1);
+    view.history_range = 7..9;
+    assert_eq!(view.history().unwrap().len(), 0);
+    task.history = None;
+    assert!(serde_json::to_value(TaskView::with_history_length(&task, Some(1))).unwrap().get("history").is_none());
+
+    let mut response = Vec::new();
+    TaskView::new(&task).write_response(&JsonRpcId::Integer(7), &mut response).unwrap();
+    let response: serde_json::Value = serde_json::from_slice(&response).unwrap();
+    assert_eq!(response["id"], 7);
+    assert_eq!(response["jsonrpc"], "2.0");
+    assert_eq!(response["result"], serde_json::to_value(&task).unwrap());
+}
+
+#[test]
+fn test_task_output_helpers() {
+    let mut report = Artifact::minimal("a-1".to_string(), vec!["The total ".into(), "is 42.".into()]);
+    report.name = Some("report".to_string());
+    let file = Part::from(FilePart::from(FileContent::WithUri(FileWithUri {
+        uri: "https://example.com/chart.png".to_string(),
+        name: Some("chart.png".to_string()),
+        mime_type: Some("image/png".to_string()),
+    })));
+    let chart = Artifact::minimal("a-2".to_string(), vec![file]);
+    let data = Artifact::minimal(
+        "a-3".to_string(),
+        vec![DataPart::from(serde_json::json!({"total": 42})).into(), "Summary".into()],
+    );
+
+    let mut task = Task::minimal("task-1".to_string(), "ctx-1".to_string());
+    assert_eq!(task.output_text(), None);
+    task.artifacts = Some(vec![report, chart, data]);
+
+    assert_eq!(task.output_text().as_deref(), Some("The total is 42.\nSummary"));
+    assert_eq!(task.artifact_named("report").and_then(Artifact::text).as_deref(), Some("The total is 42."));
+    assert!(task.artifact_named("missing").is_none());
+
+    let artifacts = task.artifacts.as_ref().unwrap();
+    assert_eq!(artifacts[1].text(), None);
+    assert_eq!(artifacts[1].json(), None);
+    assert_eq!(artifacts[2].json(), Some(&serde_json::json!({"total": 42})));
+
+    let files: Vec<&FilePart> = task.output_files().collect();
+    assert_eq!(files.len(), 1);
+    assert!(matches!(&files[0].file, FileContent::WithUri(uri) if uri.uri == "https://example.com/chart.png"));
+}
+
+#[test]
+fn test_task_state_wire_names() {
+    for state in TaskState::ALL {
+        assert_eq!(serde_json::to_value(state).unwrap(), state.as_str());
+        assert_eq!(state.to_string(), state.as_str());
+    }
+    assert_eq!(TaskState::InputRequired.to_string(), "input-required");
+}
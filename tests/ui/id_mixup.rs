@@ -0,0 +1,8 @@
+use a2a_rs::ids::{ContextId, TaskId};
+
+fn cancel(_task_id: &TaskId) {}
+
+fn main() {
+    let context_id = ContextId::from("ctx-1");
+    cancel(&context_id);
+}
@@ -0,0 +1,14 @@
+use a2a_rs::{ExtraFields, TaskState, TaskStatus, TaskStatusUpdateEvent};
+
+fn main() {
+    // With `strict-types`, events can only be built through constructors
+    let _event = TaskStatusUpdateEvent {
+        kind: "status-update".to_string(),
+        task_id: "task-1".to_string(),
+        context_id: "ctx-1".to_string(),
+        status: TaskStatus::new(TaskState::Working),
+        final_event: true,
+        metadata: None,
+        extra: ExtraFields::default(),
+    };
+}
@@ -0,0 +1,226 @@
+//! Audit of the wire field names of every registered type.
+//!
+//! The field names in the [`KnownFields`] registry are compared with the
+//! spec's JSON schema in `src/a2a.json`, adjusted by the deliberate
+//! differences listed in `tests/wire_fields.json`. A mismatch fails with the
+//! fields each side has that the other lacks: if the spec is right, fix the
+//! struct and its registry entry; if the crate differs on purpose, record
+//! the difference in the manifest. With the `schema` feature the registry is
+//! also compared with the names serde actually uses.
+
+use a2a_rs::strict::{KnownFields, wire_fields};
+use a2a_rs::*;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+type Fields = BTreeSet<String>;
+
+/// A registered type: its name and wire fields.
+struct Entry {
+    name: &'static str,
+    fields: &'static [&'static str],
+}
+
+fn entry<T: KnownFields>(name: &'static str) -> Entry {
+    Entry {
+        name,
+        fields: wire_fields::<T>(),
+    }
+}
+
+macro_rules! registry {
+    ($($ty:ident),* $(,)?) => {
+        vec![$(entry::<$ty>(stringify!($ty)),)*]
+    };
+}
+
+fn registry() -> Vec<Entry> {
+    #[allow(unused_mut)]
+    let mut entries = registry![
+        Message,
+        Part,
+        TextPart,
+        FilePart,
+        DataPart,
+        FileContent,
+        TaskStatus,
+        Artifact,
+        A2AErrorResponse,
+        Task,
+        TaskStatusUpdateEvent,
+        TaskArtifactUpdateEvent,
+        AgentExtension,
+        AgentCapabilities,
+        AgentInterface,
+        AgentProvider,
+        AgentSkill,
+        SecurityScheme,
+        ApiKeySecurityScheme,
+        HttpSecurityScheme,
+        OpenIdConnectSecurityScheme,
+        OAuth2SecurityScheme,
+        OAuth2Flows,
+        AuthorizationCodeOAuthFlow,
+        ClientCredentialsOAuthFlow,
+        ImplicitOAuthFlow,
+        PasswordOAuthFlow,
+        AgentCard,
+        PushNotificationAuthenticationInfo,
+        PushNotificationConfig,
+        PushNotificationConfigResult,
+        PushNotificationConfigInfo,
+        MessageSendConfiguration,
+        SendMessageParams,
+        TaskIdParams,
+        GetTaskParams,
+        CancelTaskParams,
+        SetTaskPushNotificationConfigParams,
+        GetTaskPushNotificationConfigParams,
+        TaskResubscriptionParams,
+        ListTaskPushNotificationConfigParams,
+        DeleteTaskPushNotificationConfigParams,
+        SendMessageRequest,
+        SendStreamingMessageRequest,
+        GetTaskRequest,
+        CancelTaskRequest,
+        SetTaskPushNotificationConfigRequest,
+        GetTaskPushNotificationConfigRequest,
+        TaskResubscriptionRequest,
+        ListTaskPushNotificationConfigRequest,
+        DeleteTaskPushNotificationConfigRequest,
+    ];
+    #[cfg(feature = "spec-0-3")]
+    entries.extend(registry![AgentCardSignature]);
+    #[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+    entries.extend(registry![ListTasksParams, ListTasksRequest, ListTasksResult]);
+    entries
+}
+
+fn read_json(path: &str) -> Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path);
+    serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap()
+}
+
+fn names(value: &Value) -> impl Iterator<Item = String> + '_ {
+    value.as_array().into_iter().flatten().map(|name| name.as_str().unwrap().to_string())
+}
+
+/// The properties of a schema definition, following references and
+/// combining the alternatives of unions.
+fn spec_fields(definitions: &Value, name: &str, fields: &mut Fields) {
+    let definition = definitions.get(name).unwrap_or_else(|| panic!("src/a2a.json has no definition {}", name));
+    collect_properties(definitions, definition, fields);
+}
+
+fn collect_properties(definitions: &Value, schema: &Value, fields: &mut Fields) {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return spec_fields(definitions, reference.trim_start_matches("#/definitions/"), fields);
+    }
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        fields.extend(properties.keys().cloned());
+    }
+    for key in ["allOf", "anyOf", "oneOf"] {
+        for alternative in schema.get(key).and_then(Value::as_array).into_iter().flatten() {
+            collect_properties(definitions, alternative, fields);
+        }
+    }
+}
+
+fn feature_enabled(feature: &str) -> bool {
+    match feature {
+        "spec-0-3" => cfg!(feature = "spec-0-3"),
+        "ext-methods" => cfg!(feature = "ext-methods"),
+        other => panic!("tests/wire_fields.json names unknown feature {}", other),
+    }
+}
+
+/// The fields a type should have according to the spec and the manifest.
+fn expected_fields(definitions: &Value, manifest: &Value) -> Fields {
+    let mut fields = Fields::new();
+    match &manifest["spec"] {
+        Value::String(name) => spec_fields(definitions, name, &mut fields),
+        Value::Array(names) => {
+            for name in names {
+                spec_fields(definitions, name.as_str().unwrap(), &mut fields);
+            }
+        }
+        _ => fields.extend(names(&manifest["fields"])),
+    }
+    for (spec_name, crate_name) in manifest["renamed"].as_object().into_iter().flatten() {
+        assert!(fields.remove(spec_name), "renamed field {} is not in the spec", spec_name);
+        fields.insert(crate_name.as_str().unwrap().to_string());
+    }
+    for missing in names(&manifest["missing"]) {
+        assert!(fields.remove(&missing), "missing field {} is not in the spec", missing);
+    }
+    fields.extend(names(&manifest["added"]));
+    fields.extend(names(&manifest["aliases"]));
+    for (feature, added) in manifest["addedWith"].as_object().into_iter().flatten() {
+        if feature_enabled(feature) {
+            fields.extend(names(added));
+        }
+    }
+    fields
+}
+
+/// Describe how two sets of fields differ, or `None` if they are equal.
+fn diff(name: &str, registered: &Fields, expected: &Fields, expected_from: &str) -> Option<String> {
+    let extra: Vec<_> = registered.difference(expected).collect();
+    let lacking: Vec<_> = expected.difference(registered).collect();
+    (!extra.is_empty() || !lacking.is_empty()).then(|| {
+        format!("{}: registry has {:?} not in {}, lacks {:?}", name, extra, expected_from, lacking)
+    })
+}
+
+#[test]
+fn test_registry_matches_spec_and_manifest() {
+    let definitions = read_json("src/a2a.json")["definitions"].clone();
+    let manifest = read_json("tests/wire_fields.json");
+
+    let mut mismatches = Vec::new();
+    for entry in registry() {
+        let Some(expected) = manifest.get(entry.name) else {
+            mismatches.push(format!("{}: not in tests/wire_fields.json", entry.name));
+            continue;
+        };
+        let registered: Fields = entry.fields.iter().map(|field| field.to_string()).collect();
+        assert_eq!(registered.len(), entry.fields.len(), "{} registers a field twice", entry.name);
+        mismatches.extend(diff(entry.name, &registered, &expected_fields(&definitions, expected), "the spec"));
+    }
+    assert!(mismatches.is_empty(), "wire field names drifted:\n{}", mismatches.join("\n"));
+}
+
+/// The registry lists what serde reads, as seen in the generated schema.
+#[cfg(feature = "schema")]
+#[test]
+fn test_registry_matches_serde() {
+    let bundle = a2a_rs::schema::export_all();
+    let definitions = &bundle["$defs"];
+    let manifest = read_json("tests/wire_fields.json");
+
+    let mut mismatches = Vec::new();
+    for entry in registry() {
+        // Unions have no fields of their own; their variants are checked
+        if manifest[entry.name]["union"].as_bool() == Some(true) {
+            continue;
+        }
+        let serde: Fields = definitions[entry.name]["properties"]
+            .as_object()
+            .unwrap_or_else(|| panic!("no schema properties for {}", entry.name))
+            .keys()
+            .cloned()
+            .collect();
+        let mut registered: Fields = entry.fields.iter().map(|field| field.to_string()).collect();
+        // The tag of the enclosing union is read by the union
+        if let Some(tag) = manifest[entry.name]["tag"].as_str() {
+            registered.remove(tag);
+        }
+        // Aliases are read but not part of the schema
+        for alias in names(&manifest[entry.name]["aliases"]) {
+            registered.remove(&alias);
+        }
+        mismatches.extend(diff(entry.name, &registered, &serde, "serde"));
+    }
+    assert!(mismatches.is_empty(), "registry and serde disagree:\n{}", mismatches.join("\n"));
+}
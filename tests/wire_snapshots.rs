@@ -0,0 +1,667 @@
+//! Golden-file tests pinning the JSON wire format of every protocol type.
+//!
+//! Each `snapshot!` line serializes a canonical instance, compares it with
+//! `tests/snapshots/<name>.json` and parses the golden file back into an
+//! equal value. Each golden file is also parsed with every field the type
+//! can do without set to an explicit `null`, as some SDKs send them, which
+//! must mean the same as leaving the field out. After an intentional
+//! change, regenerate the golden files with
+//! `A2A_UPDATE_SNAPSHOTS=1 cargo test --test wire_snapshots` and review the
+//! diff; see `a2a_rs::wire` for when to bump the wire format version.
+
+use a2a_rs::*;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::fmt::Debug;
+use std::path::PathBuf;
+
+fn assert_snapshot<T: Serialize + DeserializeOwned + PartialEq + Debug>(name: &str, value: &T) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots").join(format!("{}.json", name));
+    let actual = serde_json::to_value(value).unwrap();
+
+    if std::env::var_os("A2A_UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, serde_json::to_string_pretty(&actual).unwrap() + "\n").unwrap();
+    }
+
+    let golden = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!("missing golden file {}; run with A2A_UPDATE_SNAPSHOTS=1 to create it", path.display())
+    });
+    let expected: Value = serde_json::from_str(&golden).unwrap();
+    assert_eq!(
+        actual,
+        expected,
+        "{} no longer matches {}; if the wire change is intended, run with A2A_UPDATE_SNAPSHOTS=1",
+        name,
+        path.display()
+    );
+
+    assert_accepts_explicit_nulls::<T>(name, &expected);
+    let parsed: T = serde_json::from_value(expected).unwrap();
+    assert_eq!(&parsed, value, "{} does not parse back from its golden file", name);
+}
+
+/// Keys whose values are typed maps, or lists of them, rather than objects
+/// with fields; a `null` entry is an invalid value, not an absent field.
+const MAP_KEYS: &[&str] = &["scopes", "security", "securitySchemes"];
+
+/// Every object key of a value, as the path of object keys and array
+/// indices leading to it.
+fn field_paths(value: &Value, path: &mut Vec<Value>, paths: &mut Vec<Vec<Value>>) {
+    match value {
+        Value::Object(object) => {
+            for (key, child) in object {
+                path.push(json!(key));
+                paths.push(path.clone());
+                if !MAP_KEYS.contains(&key.as_str()) {
+                    field_paths(child, path, paths);
+                }
+                path.pop();
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                path.push(json!(index));
+                field_paths(item, path, paths);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replace (`Some`) or remove (`None`) the field at a path.
+fn with_field(value: &Value, path: &[Value], replacement: Option<Value>) -> Value {
+    let mut value = value.clone();
+    let (last, parents) = path.split_last().unwrap();
+    let mut parent = &mut value;
+    for step in parents {
+        parent = match step {
+            Value::String(key) => &mut parent[key.as_str()],
+            step => &mut parent[step.as_u64().unwrap() as usize],
+        };
+    }
+    let object = parent.as_object_mut().unwrap();
+    match replacement {
+        Some(replacement) => object.insert(last.as_str().unwrap().to_string(), replacement),
+        None => object.remove(last.as_str().unwrap()),
+    };
+    value
+}
+
+fn pointer<'a>(value: &'a Value, path: &[Value]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, step| match step {
+        Value::String(key) => value.get(key.as_str()),
+        step => value.get(step.as_u64()? as usize),
+    })
+}
+
+/// Check that every field the type can do without also parses as an
+/// explicit `null`, as some SDKs send them, and means the same as leaving
+/// it out.
+fn assert_accepts_explicit_nulls<T: Serialize + DeserializeOwned>(name: &str, golden: &Value) {
+    let mut paths = Vec::new();
+    field_paths(golden, &mut Vec::new(), &mut paths);
+    for path in paths {
+        let Ok(without) = serde_json::from_value::<T>(with_field(golden, &path, None)) else {
+            continue;
+        };
+        let with_null = serde_json::from_value::<T>(with_field(golden, &path, Some(Value::Null)))
+            .unwrap_or_else(|e| panic!("{}: explicit null at {:?} is rejected: {}", name, path, e));
+        let with_null = serde_json::to_value(with_null).unwrap();
+        if pointer(&with_null, &path) == Some(&Value::Null) {
+            // Kept as data, e.g. an entry of `metadata`
+            continue;
+        }
+        assert_eq!(
+            with_null,
+            serde_json::to_value(without).unwrap(),
+            "{}: explicit null at {:?} differs from leaving the field out",
+            name,
+            path
+        );
+    }
+}
+
+/// Add a type to the suite: `snapshot!(test_name: Type = expression);`
+macro_rules! snapshot {
+    ($name:ident: $ty:ty = $value:expr) => {
+        #[test]
+        fn $name() {
+            let value: $ty = $value;
+            assert_snapshot(stringify!($name), &value);
+        }
+    };
+}
+
+// ============================================================================
+// CANONICAL INSTANCES
+// ============================================================================
+
+fn sample_metadata() -> Option<Value> {
+    Some(json!({"trace": "abc123"}))
+}
+
+fn sample_text_part() -> Part {
+    Part::Text(TextPart {
+        text: "Summarize the attached report".to_string(),
+        metadata: sample_metadata(),
+    })
+}
+
+fn sample_file_bytes_part() -> Part {
+    Part::File(Box::new(FilePart {
+        file: FileContent::WithBytes(FileWithBytes {
+            bytes: "aGVsbG8=".to_string(),
+            name: Some("hello.txt".to_string()),
+            mime_type: Some("text/plain".to_string()),
+        }),
+        metadata: sample_metadata(),
+    }))
+}
+
+fn sample_file_uri_part() -> Part {
+    Part::File(Box::new(FilePart {
+        file: FileContent::WithUri(FileWithUri {
+            uri: "https://example.com/report.pdf".to_string(),
+            name: Some("report.pdf".to_string()),
+            mime_type: Some("application/pdf".to_string()),
+        }),
+        metadata: None,
+    }))
+}
+
+fn sample_data_part() -> Part {
+    Part::Data(DataPart {
+        data: json!({"rows": 3, "columns": ["a", "b"]}),
+        metadata: sample_metadata(),
+    })
+}
+
+fn sample_message() -> Message {
+    Message {
+        kind: "message".to_string(),
+        message_id: "msg-1".to_string(),
+        parts: vec![sample_text_part(), sample_file_bytes_part(), sample_file_uri_part(), sample_data_part()],
+        role: MessageRole::User,
+        context_id: Some("ctx-1".to_string()),
+        extensions: Some(vec!["https://example.com/ext/v1".to_string()]),
+        metadata: sample_metadata(),
+        reference_task_ids: Some(vec!["task-0".to_string()]),
+        task_id: Some("task-1".to_string()),
+        extra: ExtraFields::default(),
+    }
+}
+
+// Types that are `#[non_exhaustive]` with the `strict-types` feature are
+// built with their constructors
+
+fn sample_status() -> TaskStatus {
+    let mut status = TaskStatus::new(TaskState::Working);
+    status.message = Some(Message::agent("msg-2".to_string(), ["Reading the report"]));
+    status.timestamp = Some("2025-01-01T00:00:00Z".to_string());
+    status
+}
+
+fn sample_artifact() -> Artifact {
+    Artifact {
+        artifact_id: "artifact-1".to_string(),
+        parts: vec![sample_text_part(), sample_data_part()],
+        description: Some("The summary".to_string()),
+        extensions: Some(vec!["https://example.com/ext/v1".to_string()]),
+        metadata: sample_metadata(),
+        name: Some("summary".to_string()),
+        extra: ExtraFields::default(),
+    }
+}
+
+fn sample_error_response() -> A2AErrorResponse {
+    A2AErrorResponse {
+        code: -32603,
+        message: "Internal error".to_string(),
+        data: Some(json!({"detail": "disk full"})),
+    }
+}
+
+fn sample_task() -> Task {
+    Task {
+        id: "task-1".to_string(),
+        kind: "task".to_string(),
+        status: sample_status(),
+        context_id: "ctx-1".to_string(),
+        artifacts: Some(vec![sample_artifact()]),
+        history: Some(vec![sample_message()]),
+        metadata: sample_metadata(),
+        result: Some(json!({"pages": 12})),
+        error: Some(sample_error_response()),
+        created_at: Some("2025-01-01T00:00:00Z".to_string()),
+        updated_at: Some("2025-01-01T00:01:00Z".to_string()),
+        status_history: Some(vec![{
+            let mut status = TaskStatus::new(TaskState::Submitted);
+            status.timestamp = Some("2025-01-01T00:00:00Z".to_string());
+            status
+        }]),
+        extra: ExtraFields::default(),
+    }
+}
+
+fn sample_scopes() -> Map<String, String> {
+    Map::from([("read".to_string(), "Read tasks".to_string())])
+}
+
+fn sample_security_schemes() -> Vec<SecurityScheme> {
+    vec![
+        SecurityScheme::ApiKey(ApiKeySecurityScheme {
+            type_: "apiKey".to_string(),
+            in_: ApiKeyLocation::Header,
+            name: "X-API-Key".to_string(),
+            description: Some("API key".to_string()),
+        }),
+        SecurityScheme::Http(HttpSecurityScheme {
+            type_: "http".to_string(),
+            scheme: "bearer".to_string(),
+            bearer_format: Some("JWT".to_string()),
+            description: None,
+        }),
+        SecurityScheme::OAuth2(OAuth2SecurityScheme {
+            type_: "oauth2".to_string(),
+            flows: OAuth2Flows {
+                implicit: Some(ImplicitOAuthFlow {
+                    authorization_url: "https://auth.example.com/authorize".to_string(),
+                    refresh_url: None,
+                    scopes: sample_scopes(),
+                }),
+                password: Some(PasswordOAuthFlow {
+                    token_url: "https://auth.example.com/token".to_string(),
+                    refresh_url: None,
+                    scopes: sample_scopes(),
+                }),
+                client_credentials: Some(ClientCredentialsOAuthFlow {
+                    token_url: "https://auth.example.com/token".to_string(),
+                    refresh_url: Some("https://auth.example.com/refresh".to_string()),
+                    scopes: sample_scopes(),
+                }),
+                authorization_code: Some(AuthorizationCodeOAuthFlow {
+                    authorization_url: "https://auth.example.com/authorize".to_string(),
+                    token_url: "https://auth.example.com/token".to_string(),
+                    refresh_url: None,
+                    scopes: sample_scopes(),
+                }),
+            },
+            description: Some("OAuth".to_string()),
+        }),
+        SecurityScheme::OpenIdConnect(OpenIdConnectSecurityScheme {
+            type_: "openIdConnect".to_string(),
+            open_id_connect_url: "https://auth.example.com/.well-known/openid-configuration".to_string(),
+            description: None,
+        }),
+    ]
+}
+
+fn sample_extension() -> AgentExtension {
+    AgentExtension {
+        uri: "https://example.com/ext/v1".to_string(),
+        required: Some(false),
+        description: Some("An example extension".to_string()),
+        params: Some(json!({"level": 2})),
+    }
+}
+
+fn sample_skill() -> AgentSkill {
+    let mut skill = AgentSkill::minimal("summarize".to_string(), "Summarizes documents".to_string());
+    skill.input_modes = Some(vec!["application/pdf".to_string()]);
+    skill.output_modes = Some(vec!["text/plain".to_string()]);
+    skill.examples = Some(vec!["Summarize this report".to_string()]);
+    skill
+}
+
+fn sample_agent_card() -> AgentCard {
+    let schemes = sample_security_schemes();
+    let capabilities = AgentCapabilities {
+        extensions: Some(vec![sample_extension()]),
+        push_notifications: Some(true),
+        state_transition_history: Some(false),
+        streaming: Some(true),
+    };
+    let mut card = AgentCard::new(
+        "Summarizer".to_string(),
+        "Summarizes documents".to_string(),
+        "1.0.0".to_string(),
+        "https://agent.example.com/a2a".to_string(),
+        capabilities,
+        vec!["text/plain".to_string()],
+        vec!["text/plain".to_string()],
+        vec![sample_skill()],
+    );
+    card.protocol_version = "0.2.6".to_string();
+    card.preferred_transport = Some("JSONRPC".to_string());
+    card.provider = Some(AgentProvider {
+        organization: "Example".to_string(),
+        url: "https://example.com".to_string(),
+    });
+    card.documentation_url = Some("https://example.com/docs".to_string());
+    card.icon_url = Some("https://example.com/icon.png".to_string());
+    card.supports_authenticated_extended_card = Some(true);
+    card.additional_interfaces = Some(vec![AgentInterface {
+        url: "https://agent.example.com/grpc".to_string(),
+        transport: "GRPC".to_string(),
+    }]);
+    card.security = Some(vec![Map::from([("oauth".to_string(), vec!["read".to_string()])])]);
+    card.security_schemes = Some(Map::from([
+        ("apiKey".to_string(), schemes[0].clone()),
+        ("oauth".to_string(), schemes[2].clone()),
+    ]));
+    card
+}
+
+fn sample_push_config() -> PushNotificationConfig {
+    PushNotificationConfig {
+        url: "https://client.example.com/hook".to_string(),
+        authentication: Some(PushNotificationAuthenticationInfo {
+            schemes: vec!["Bearer".to_string()],
+            credentials: Some("secret".to_string()),
+        }),
+        id: Some("config-1".to_string()),
+        token: Some("token-1".to_string()),
+    }
+}
+
+fn sample_send_params() -> SendMessageParams {
+    SendMessageParams {
+        message: sample_message(),
+        configuration: Some(MessageSendConfiguration {
+            accepted_output_modes: Some(vec!["text/plain".to_string()]),
+            blocking: Some(true),
+            history_length: Some(5),
+            push_notification_config: Some(sample_push_config()),
+            extra: ExtraFields::default(),
+        }),
+        metadata: sample_metadata(),
+        extra: ExtraFields::default(),
+    }
+}
+
+fn sample_status_event() -> TaskStatusUpdateEvent {
+    let mut event = TaskStatusUpdateEvent::new("task-1".to_string(), "ctx-1".to_string(), sample_status(), false);
+    event.metadata = sample_metadata();
+    event
+}
+
+fn sample_artifact_event() -> TaskArtifactUpdateEvent {
+    let mut event = TaskArtifactUpdateEvent::new("task-1".to_string(), "ctx-1".to_string(), sample_artifact());
+    event.append = Some(true);
+    event.last_chunk = Some(false);
+    event.metadata = sample_metadata();
+    event
+}
+
+fn sample_errors() -> Vec<A2AError> {
+    fn data() -> Option<Value> {
+        Some(json!({"field": "id"}))
+    }
+    vec![
+        A2AError::JSONParse(Box::new(JSONParseError {
+            code: -32700,
+            message: "Invalid JSON payload".to_string(),
+            data: data(),
+        })),
+        A2AError::InvalidRequest(Box::new(InvalidRequestError {
+            code: -32600,
+            message: "Request payload validation error".to_string(),
+            data: data(),
+        })),
+        A2AError::MethodNotFound(Box::new(MethodNotFoundError {
+            code: -32601,
+            message: "Method not found".to_string(),
+            data: None,
+        })),
+        A2AError::InvalidParams(Box::new(InvalidParamsError {
+            code: -32602,
+            message: "Invalid parameters".to_string(),
+            data: data(),
+        })),
+        A2AError::Internal(Box::new(InternalError {
+            code: -32603,
+            message: "Internal error".to_string(),
+            data: None,
+        })),
+        A2AError::TaskNotFound(Box::new(TaskNotFoundError {
+            code: -32001,
+            message: "Task not found".to_string(),
+            data: None,
+        })),
+        A2AError::TaskNotCancelable(Box::new(TaskNotCancelableError {
+            code: -32002,
+            message: "Task cannot be canceled".to_string(),
+            data: None,
+        })),
+        A2AError::PushNotificationNotSupported(Box::new(PushNotificationNotSupportedError {
+            code: -32003,
+            message: "Push Notification is not supported".to_string(),
+            data: None,
+        })),
+        A2AError::UnsupportedOperation(Box::new(UnsupportedOperationError {
+            code: -32004,
+            message: "This operation is not supported".to_string(),
+            data: None,
+        })),
+        A2AError::ContentTypeNotSupported(Box::new(ContentTypeNotSupportedError {
+            code: -32005,
+            message: "Incompatible content types".to_string(),
+            data: None,
+        })),
+        A2AError::InvalidAgentResponse(Box::new(InvalidAgentResponseError {
+            code: -32006,
+            message: "Invalid agent response".to_string(),
+            data: None,
+        })),
+    ]
+}
+
+fn request<P>(method: RequestMethod, params: P) -> (RequestMethod, P, String, String, ExtraFields) {
+    (method, params, "req-1".to_string(), "2.0".to_string(), ExtraFields::default())
+}
+
+// ============================================================================
+// SNAPSHOTS
+// ============================================================================
+
+snapshot!(wire_format_version: u32 = a2a_rs::wire::wire_format_version());
+
+// Messages and tasks
+snapshot!(message: Message = sample_message());
+snapshot!(parts: Vec<Part> = vec![sample_text_part(), sample_file_bytes_part(), sample_file_uri_part(), sample_data_part()]);
+snapshot!(message_roles: Vec<MessageRole> = vec![MessageRole::Agent, MessageRole::User]);
+snapshot!(task: Task = sample_task());
+snapshot!(task_minimal: Task = Task::minimal("task-1".to_string(), "ctx-1".to_string()));
+snapshot!(task_states: Vec<TaskState> = vec![
+    TaskState::Submitted,
+    TaskState::Working,
+    TaskState::InputRequired,
+    TaskState::Completed,
+    TaskState::Canceled,
+    TaskState::Failed,
+    TaskState::Rejected,
+    TaskState::AuthRequired,
+    TaskState::Unknown,
+]);
+snapshot!(artifact: Artifact = sample_artifact());
+snapshot!(status_update_event: TaskStatusUpdateEvent = sample_status_event());
+snapshot!(artifact_update_event: TaskArtifactUpdateEvent = sample_artifact_event());
+
+// Agent cards and security
+snapshot!(agent_card: AgentCard = sample_agent_card());
+snapshot!(agent_skill: AgentSkill = sample_skill());
+snapshot!(agent_extension: AgentExtension = sample_extension());
+snapshot!(security_schemes: Vec<SecurityScheme> = sample_security_schemes());
+snapshot!(api_key_locations: Vec<ApiKeyLocation> = vec![ApiKeyLocation::Cookie, ApiKeyLocation::Header, ApiKeyLocation::Query]);
+
+// JSON-RPC envelopes
+// The 0.2.5 methods; `tasks/list` is pinned by the spec-0-3 snapshots
+snapshot!(request_methods: Vec<RequestMethod> = RequestMethod::all().iter().copied().filter(|m| m.as_str() != "tasks/list").collect());
+snapshot!(jsonrpc_ids: Vec<JsonRpcId> = vec![JsonRpcId::String("1".to_string()), JsonRpcId::Integer(1), JsonRpcId::Null]);
+snapshot!(errors: Vec<A2AError> = sample_errors());
+snapshot!(error_response: JSONRPCErrorResponse = JSONRPCErrorResponse {
+    id: JsonRpcId::Integer(1),
+    jsonrpc: "2.0".to_string(),
+    error: sample_errors().remove(5),
+});
+snapshot!(send_message_request: SendMessageRequest = SendMessageRequest {
+    method: RequestMethod::MessageSend,
+    params: sample_send_params(),
+    id: JsonRpcId::Integer(1),
+    jsonrpc: "2.0".to_string(),
+    extra: ExtraFields::default(),
+});
+snapshot!(send_message_responses: Vec<SendMessageResponse> = vec![
+    SendMessageResponse::Success(SendMessageSuccessResponse {
+        id: JsonRpcId::Integer(1),
+        jsonrpc: "2.0".to_string(),
+        result: SendMessageResult::Task(sample_task()),
+    }),
+    SendMessageResponse::Success(SendMessageSuccessResponse {
+        id: JsonRpcId::Integer(2),
+        jsonrpc: "2.0".to_string(),
+        result: SendMessageResult::Message(Message::agent("msg-3".to_string(), ["Done"])),
+    }),
+]);
+snapshot!(send_streaming_message_request: SendStreamingMessageRequest = {
+    let (method, params, id, jsonrpc, extra) = request(RequestMethod::MessageStream, sample_send_params());
+    SendStreamingMessageRequest { method, params, id, jsonrpc, extra }
+});
+snapshot!(streaming_results: Vec<StreamingMessageResult> = vec![
+    StreamingMessageResult::Task(sample_task()),
+    StreamingMessageResult::Message(sample_message()),
+    StreamingMessageResult::TaskStatusUpdate(sample_status_event()),
+    StreamingMessageResult::TaskArtifactUpdate(sample_artifact_event()),
+]);
+snapshot!(get_task_request: GetTaskRequest = {
+    let params = GetTaskParams { task_id: "task-1".to_string(), history_length: Some(10), extra: ExtraFields::default() };
+    let (method, params, id, jsonrpc, extra) = request(RequestMethod::TasksGet, params);
+    GetTaskRequest { method, params, id, jsonrpc, extra }
+});
+snapshot!(get_task_response: GetTaskResponse = GetTaskResponse {
+    id: "req-1".to_string(),
+    jsonrpc: "2.0".to_string(),
+    result: sample_task(),
+});
+snapshot!(cancel_task_request: CancelTaskRequest = {
+    let params = CancelTaskParams { task_id: "task-1".to_string(), extra: ExtraFields::default() };
+    let (method, params, id, jsonrpc, extra) = request(RequestMethod::TasksCancel, params);
+    CancelTaskRequest { method, params, id, jsonrpc, extra }
+});
+snapshot!(cancel_task_response: CancelTaskResponse = CancelTaskResponse {
+    id: "req-1".to_string(),
+    jsonrpc: "2.0".to_string(),
+    result: sample_task(),
+});
+snapshot!(task_id_params: TaskIdParams = TaskIdParams {
+    id: "task-1".to_string(),
+    metadata: sample_metadata(),
+    extra: ExtraFields::default(),
+});
+snapshot!(set_push_config_request: SetTaskPushNotificationConfigRequest = {
+    let params = SetTaskPushNotificationConfigParams {
+        task_id: "task-1".to_string(),
+        config: sample_push_config(),
+        extra: ExtraFields::default(),
+    };
+    let (method, params, id, jsonrpc, extra) = request(RequestMethod::TasksPushNotificationConfigSet, params);
+    SetTaskPushNotificationConfigRequest { method, params, id, jsonrpc, extra }
+});
+snapshot!(set_push_config_response: SetTaskPushNotificationConfigResponse = SetTaskPushNotificationConfigResponse {
+    id: "req-1".to_string(),
+    jsonrpc: "2.0".to_string(),
+    result: PushNotificationConfigResult { task_id: "task-1".to_string(), config_id: "config-1".to_string() },
+});
+snapshot!(get_push_config_request: GetTaskPushNotificationConfigRequest = {
+    let params = GetTaskPushNotificationConfigParams {
+        task_id: "task-1".to_string(),
+        config_id: "config-1".to_string(),
+        extra: ExtraFields::default(),
+    };
+    let (method, params, id, jsonrpc, extra) = request(RequestMethod::TasksPushNotificationConfigGet, params);
+    GetTaskPushNotificationConfigRequest { method, params, id, jsonrpc, extra }
+});
+snapshot!(get_push_config_response: GetTaskPushNotificationConfigResponse = GetTaskPushNotificationConfigResponse {
+    id: "req-1".to_string(),
+    jsonrpc: "2.0".to_string(),
+    result: sample_push_config(),
+});
+snapshot!(list_push_config_request: ListTaskPushNotificationConfigRequest = {
+    let params = ListTaskPushNotificationConfigParams { task_id: "task-1".to_string(), extra: ExtraFields::default() };
+    let (method, params, id, jsonrpc, extra) = request(RequestMethod::TasksPushNotificationConfigList, params);
+    ListTaskPushNotificationConfigRequest { method, params, id, jsonrpc, extra }
+});
+snapshot!(list_push_config_response: ListTaskPushNotificationConfigResponse = ListTaskPushNotificationConfigResponse {
+    id: "req-1".to_string(),
+    jsonrpc: "2.0".to_string(),
+    result: vec![PushNotificationConfigInfo {
+        config_id: "config-1".to_string(),
+        url: "https://client.example.com/hook".to_string(),
+    }],
+});
+snapshot!(delete_push_config_request: DeleteTaskPushNotificationConfigRequest = {
+    let params = DeleteTaskPushNotificationConfigParams {
+        task_id: "task-1".to_string(),
+        config_id: "config-1".to_string(),
+        extra: ExtraFields::default(),
+    };
+    let (method, params, id, jsonrpc, extra) = request(RequestMethod::TasksPushNotificationConfigDelete, params);
+    DeleteTaskPushNotificationConfigRequest { method, params, id, jsonrpc, extra }
+});
+snapshot!(delete_push_config_response: DeleteTaskPushNotificationConfigResponse = DeleteTaskPushNotificationConfigResponse {
+    id: "req-1".to_string(),
+    jsonrpc: "2.0".to_string(),
+    result: true,
+});
+snapshot!(resubscribe_request: TaskResubscriptionRequest = {
+    let params = TaskResubscriptionParams { task_id: "task-1".to_string(), extra: ExtraFields::default() };
+    let (method, params, id, jsonrpc, extra) = request(RequestMethod::TasksResubscribe, params);
+    TaskResubscriptionRequest { method, params, id, jsonrpc, extra }
+});
+snapshot!(resubscribe_response: TaskResubscriptionResponse = TaskResubscriptionResponse {
+    id: "req-1".to_string(),
+    jsonrpc: "2.0".to_string(),
+    result: sample_task(),
+});
+
+// Shapes added by the 0.3 specification, behind the `spec-0-3` feature;
+// the snapshots above pin the default 0.2.5 output with the feature on too
+
+#[cfg(feature = "spec-0-3")]
+snapshot!(spec_0_3_agent_card: AgentCard = {
+    let mut card = sample_agent_card();
+    card.protocol_version = "0.3.0".to_string();
+    card.signatures = Some(vec![AgentCardSignature {
+        protected: "eyJhbGciOiJFUzI1NiJ9".to_string(),
+        signature: "c2lnbmF0dXJl".to_string(),
+        header: Some(json!({"kid": "key-1"})),
+        extra: ExtraFields::default(),
+    }]);
+    card
+});
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+snapshot!(list_tasks_request: ListTasksRequest = {
+    let params = ListTasksParams {
+        context_id: Some("ctx-1".to_string()),
+        status: Some(TaskState::Working),
+        page_size: Some(50),
+        page_token: Some("page-2".to_string()),
+        history_length: Some(0),
+        extra: ExtraFields::default(),
+    };
+    let (method, params, id, jsonrpc, extra) = request(RequestMethod::TasksList, params);
+    ListTasksRequest { method, params, id, jsonrpc, extra }
+});
+#[cfg(any(feature = "spec-0-3", feature = "ext-methods"))]
+snapshot!(list_tasks_response: ListTasksResponse = ListTasksResponse {
+    id: "req-1".to_string(),
+    jsonrpc: "2.0".to_string(),
+    result: ListTasksResult {
+        tasks: vec![sample_task()],
+        next_page_token: Some("page-3".to_string()),
+        extra: ExtraFields::default(),
+    },
+});